@@ -0,0 +1,171 @@
+//! Other fan control software
+//!
+//! Two programs driving the same PWM outputs fight each other: each writes
+//! its own value (or flips `pwmN_enable` back to automatic) and the fans
+//! audibly hunt between speeds. [`detect_competing_software`] looks for the
+//! daemons known to do this among the running processes, so the daemon and
+//! the GUI can name the culprit and the service to disable.
+
+use std::fs;
+use std::path::Path;
+
+/// A fan control program that conflicts with Hyperfan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownFanSoftware {
+    /// Display name
+    pub name: &'static str,
+    /// Executable names it runs as (compared against argv[0] and, for
+    /// interpreted programs, argv[1])
+    pub executables: &'static [&'static str],
+    /// Service unit that starts it
+    pub unit: &'static str,
+}
+
+/// Fan control programs known to write PWM outputs or `pwmN_enable`
+pub const KNOWN_FAN_SOFTWARE: &[KnownFanSoftware] = &[
+    KnownFanSoftware { name: "fancontrol (lm-sensors)", executables: &["fancontrol"], unit: "fancontrol" },
+    KnownFanSoftware { name: "NBFC", executables: &["nbfc_service", "NbfcService.exe"], unit: "nbfc_service" },
+    KnownFanSoftware { name: "CoolerControl", executables: &["coolercontrold"], unit: "coolercontrold" },
+    KnownFanSoftware { name: "thinkfan", executables: &["thinkfan"], unit: "thinkfan" },
+    KnownFanSoftware { name: "fan2go", executables: &["fan2go"], unit: "fan2go" },
+    KnownFanSoftware { name: "i8kmon (i8kutils)", executables: &["i8kmon"], unit: "i8kmon" },
+    KnownFanSoftware { name: "mbpfan", executables: &["mbpfan"], unit: "mbpfan" },
+    KnownFanSoftware { name: "asusd (asusctl)", executables: &["asusd"], unit: "asusd" },
+    KnownFanSoftware { name: "system76-power", executables: &["system76-power"], unit: "com.system76.PowerDaemon" },
+];
+
+/// A running conflicting program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedFanSoftware {
+    pub software: KnownFanSoftware,
+    pub pids: Vec<i32>,
+}
+
+impl DetectedFanSoftware {
+    /// Command that stops the program and keeps it from starting again
+    pub fn disable_hint(&self) -> String {
+        match self.disable_script() {
+            Some(script) => script.split(" && ").map(|c| format!("sudo {}", c)).collect::<Vec<_>>().join(" && "),
+            None => format!("stop {} and remove it from autostart", self.software.name),
+        }
+    }
+
+    /// Root shell script behind [`Self::disable_hint`]; `None` when the init
+    /// system is unknown
+    pub fn disable_script(&self) -> Option<String> {
+        let unit = self.software.unit;
+        match crate::service::detect_init_system() {
+            crate::service::InitSystem::Systemd => Some(format!("systemctl disable --now {}.service", unit)),
+            crate::service::InitSystem::OpenRC => Some(format!("rc-service {0} stop && rc-update del {0}", unit)),
+            crate::service::InitSystem::Runit => Some(format!("sv down {}", unit)),
+            crate::service::InitSystem::BsdRc => Some(format!("service {0} stop && sysrc {0}_enable=NO", unit)),
+            crate::service::InitSystem::Unknown => None,
+        }
+    }
+
+    /// Stop the program and keep it from starting again (asks for root)
+    pub fn disable(&self) -> Result<(), String> {
+        let script = self
+            .disable_script()
+            .ok_or_else(|| format!("Unknown init system - {}", self.disable_hint()))?;
+        crate::service::run_privileged(&script)
+    }
+}
+
+impl From<DetectedFanSoftware> for hf_protocol::CompetingSoftware {
+    fn from(detected: DetectedFanSoftware) -> Self {
+        Self {
+            disable_hint: detected.disable_hint(),
+            name: detected.software.name.to_string(),
+            unit: detected.software.unit.to_string(),
+            pids: detected.pids,
+        }
+    }
+}
+
+/// Running fan control programs other than Hyperfan, in table order
+pub fn detect_competing_software() -> Vec<DetectedFanSoftware> {
+    detect_in(Path::new("/proc"))
+}
+
+fn detect_in(proc_root: &Path) -> Vec<DetectedFanSoftware> {
+    let mut detected: Vec<DetectedFanSoftware> = Vec::new();
+    let Ok(entries) = fs::read_dir(proc_root) else {
+        return detected;
+    };
+
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<i32>().ok()) else {
+            continue;
+        };
+        // Kernel threads have an empty cmdline
+        let Ok(cmdline) = fs::read(entry.path().join("cmdline")) else {
+            continue;
+        };
+        let Some(software) = match_cmdline(&cmdline) else {
+            continue;
+        };
+        match detected.iter_mut().find(|d| d.software == *software) {
+            Some(d) => d.pids.push(pid),
+            None => detected.push(DetectedFanSoftware { software: *software, pids: vec![pid] }),
+        }
+    }
+
+    detected.sort_by_key(|d| KNOWN_FAN_SOFTWARE.iter().position(|k| *k == d.software));
+    for d in &mut detected {
+        d.pids.sort_unstable();
+    }
+    detected
+}
+
+/// Known program a NUL-separated `/proc/<pid>/cmdline` belongs to
+fn match_cmdline(cmdline: &[u8]) -> Option<&'static KnownFanSoftware> {
+    let mut args = cmdline.split(|b| *b == 0).filter(|a| !a.is_empty()).take(2).map(|arg| {
+        let arg = String::from_utf8_lossy(arg);
+        arg.rsplit('/').next().unwrap_or_default().to_string()
+    });
+    let argv0 = args.next()?;
+    let argv1 = args.next();
+
+    KNOWN_FAN_SOFTWARE.iter().find(|known| {
+        known.executables.iter().any(|exe| {
+            argv0 == *exe || (is_interpreter(&argv0) && argv1.as_deref() == Some(*exe))
+        })
+    })
+}
+
+/// Shells and runtimes that run fan control scripts (fancontrol is a shell script)
+fn is_interpreter(name: &str) -> bool {
+    matches!(name, "sh" | "bash" | "dash" | "mono" | "python" | "python3" | "perl")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_cmdline() {
+        assert_eq!(match_cmdline(b"/usr/sbin/fancontrol\0").map(|k| k.unit), Some("fancontrol"));
+        assert_eq!(match_cmdline(b"/bin/bash\0/usr/sbin/fancontrol\0").map(|k| k.unit), Some("fancontrol"));
+        assert_eq!(match_cmdline(b"mono\0/opt/nbfc/NbfcService.exe\0").map(|k| k.unit), Some("nbfc_service"));
+        assert_eq!(match_cmdline(b"/usr/bin/coolercontrold\0--debug\0").map(|k| k.unit), Some("coolercontrold"));
+        // Editing the config isn't running it
+        assert_eq!(match_cmdline(b"vim\0/etc/fancontrol\0"), None);
+        assert_eq!(match_cmdline(b""), None);
+    }
+
+    #[test]
+    fn test_detect_in_proc() {
+        let dir = tempfile::tempdir().unwrap();
+        for (pid, cmdline) in [("412", &b"/usr/sbin/thinkfan\0-n\0"[..]), ("97", b"/usr/sbin/thinkfan\0"), ("3", b"/sbin/init\0")] {
+            fs::create_dir(dir.path().join(pid)).unwrap();
+            fs::write(dir.path().join(pid).join("cmdline"), cmdline).unwrap();
+        }
+        fs::create_dir(dir.path().join("self")).unwrap();
+
+        let detected = detect_in(dir.path());
+        assert_eq!(detected.len(), 1);
+        assert_eq!(detected[0].software.name, "thinkfan");
+        assert_eq!(detected[0].pids, vec![97, 412]);
+    }
+}
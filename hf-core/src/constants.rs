@@ -21,12 +21,34 @@ pub mod paths {
     #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
     pub const HWMON_BASE: &str = "/sys/class/hwmon"; // Default to Linux-style
 
+    /// Thermal subsystem (cooling devices of devicetree fans)
+    pub const THERMAL_BASE: &str = "/sys/class/thermal";
+
+    /// Block devices (drives without a hwmon temperature)
+    pub const BLOCK_BASE: &str = "/sys/block";
+
+    /// Path prefix for smartctl drive temperatures ("smart:sda")
+    pub const SMART_PREFIX: &str = "smart:";
+
+    /// Path prefix for sysctl-backed sensors and controls
+    /// e.g. "sysctl:dev.cpu.0.temperature" (FreeBSD)
+    pub const SYSCTL_PREFIX: &str = "sysctl:";
+
+    /// Path prefix for BMC sensors and fan zones ("ipmi:temp:cpu_temp", "ipmi:zone:0")
+    pub const IPMI_PREFIX: &str = "ipmi:";
+
+    /// DMI system vendor (selects the IPMI raw fan commands)
+    pub const DMI_SYS_VENDOR: &str = "/sys/class/dmi/id/sys_vendor";
+
     /// Configuration directory
     pub const CONFIG_DIR: &str = "/etc/hyperfan";
 
     /// Profile configuration file
     pub const PROFILE_FILE: &str = "profile.json";
 
+    /// Daemon configuration file (client permission tiers), inside CONFIG_DIR
+    pub const DAEMON_CONFIG_FILE: &str = "daemon.json";
+
     /// User configuration directory - works on both Linux and BSD
     /// Handles the case where daemon runs as root but needs to access user's config
     /// Uses SUDO_USER/PKEXEC_UID to find the original user when running elevated
@@ -101,7 +123,7 @@ pub mod paths {
     /// Returns the config base path (e.g., /home/user/.config) if hyperfan config exists
     /// 
     /// Strategy:
-    /// 1. First, look for users with existing hyperfan config (settings.json OR curves.json)
+    /// 1. First, look for users with existing hyperfan config (settings OR curves, JSON or TOML)
     /// 2. If none found, look for the currently logged-in user via /run/user/<uid>
     /// 3. Fall back to first regular user with a home directory
     fn find_first_user_config() -> Option<std::path::PathBuf> {
@@ -150,18 +172,20 @@ pub mod paths {
             }
         }
         
-        // Strategy 2: Find user with existing hyperfan config (settings.json OR curves.json)
+        // Strategy 2: Find user with existing hyperfan config (settings OR curves, JSON or TOML)
         for (username, uid, home) in &users {
             let hyperfan_dir = home.join(".config").join("hyperfan");
-            let settings_path = hyperfan_dir.join("settings.json");
-            let curves_path = hyperfan_dir.join("curves.json");
+            let has_file = |stem: &str| {
+                ["json", "toml"].iter().any(|ext| hyperfan_dir.join(format!("{}.{}", stem, ext)).exists())
+            };
+            let (has_settings, has_curves) = (has_file("settings"), has_file("curves"));
             
             debug!("Checking user {} (UID {}) for config at {:?}", username, uid, hyperfan_dir);
             
-            // Check for either settings.json or curves.json (user may have created curves first)
-            if settings_path.exists() || curves_path.exists() {
+            // Check for either settings or curves (user may have created curves first)
+            if has_settings || has_curves {
                 info!("Found hyperfan config for user {} at {:?} (settings={}, curves={})", 
-                      username, hyperfan_dir, settings_path.exists(), curves_path.exists());
+                      username, hyperfan_dir, has_settings, has_curves);
                 return Some(home.join(".config"));
             }
         }
@@ -190,6 +214,8 @@ pub mod paths {
         pub const BOARD_NAME: &str = "/sys/devices/virtual/dmi/id/board_name";
         #[cfg(target_os = "linux")]
         pub const PRODUCT_NAME: &str = "/sys/devices/virtual/dmi/id/product_name";
+        #[cfg(target_os = "linux")]
+        pub const SYS_VENDOR: &str = "/sys/devices/virtual/dmi/id/sys_vendor";
         
         // BSD uses kenv or sysctl for DMI info
         #[cfg(target_os = "freebsd")]
@@ -198,6 +224,8 @@ pub mod paths {
         pub const BOARD_NAME: &str = "smbios.planar.product";
         #[cfg(target_os = "freebsd")]
         pub const PRODUCT_NAME: &str = "smbios.system.product";
+        #[cfg(target_os = "freebsd")]
+        pub const SYS_VENDOR: &str = "smbios.system.maker";
         
         #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
         pub const BOARD_VENDOR: &str = "";
@@ -205,6 +233,8 @@ pub mod paths {
         pub const BOARD_NAME: &str = "";
         #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
         pub const PRODUCT_NAME: &str = "";
+        #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+        pub const SYS_VENDOR: &str = "";
     }
 
     /// System info paths
@@ -273,6 +303,25 @@ pub mod pwm {
         pub const AUTOMATIC: u8 = 2;
     }
 
+    /// `pwmN_mode` values
+    pub mod mode {
+        /// Voltage (DC) output, for 3-pin fans
+        pub const DC: u8 = 0;
+        /// PWM signal on the fourth pin
+        pub const PWM: u8 = 1;
+    }
+
+    /// Lowest duty a typical 3-pin fan keeps turning at on a DC output
+    /// Curve output above 0% is scaled into the range above it until the fan is calibrated
+    pub const DC_FLOOR_PERCENT: f32 = 40.0;
+
+    /// Attempts at a PWM write that does not read back before it is reported as failed
+    pub const WRITE_ATTEMPTS: u32 = 4;
+
+    /// Pause before the first retry, doubled for each further one
+    /// (lets board firmware finish its own update)
+    pub const WRITE_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(10);
+
     /// Convert percentage (0-100) to PWM value (0-255)
     #[inline]
     pub fn from_percent(percent: f32) -> u8 {
@@ -286,6 +335,101 @@ pub mod pwm {
     }
 }
 
+/// Thermal cooling devices used as fan controllers
+pub mod cooling {
+    /// Cooling device types that drive a fan, with the hwmon chip name their
+    /// driver registers for the same fan
+    pub const FAN_DEVICE_TYPES: &[(&str, &str)] = &[("pwm-fan", "pwmfan"), ("gpio-fan", "gpio_fan")];
+
+    /// Control file holding the current state (0..=max_state)
+    pub const STATE_FILE: &str = "cur_state";
+
+    /// File holding the highest state
+    pub const MAX_STATE_FILE: &str = "max_state";
+
+    /// Convert a cooling state to a PWM value (0-255)
+    #[inline]
+    pub fn state_to_pwm(state: u32, max_state: u32) -> u8 {
+        if max_state == 0 {
+            return 0;
+        }
+        ((state.min(max_state) as f32 / max_state as f32) * 255.0).round() as u8
+    }
+
+    /// Convert a PWM value (0-255) to the nearest cooling state
+    #[inline]
+    pub fn pwm_to_state(value: u8, max_state: u32) -> u32 {
+        ((value as f32 / 255.0) * max_state as f32).round() as u32
+    }
+}
+
+/// Thermal zones used as temperature sources
+pub mod thermal_zone {
+    /// Pseudo-chip grouping `/sys/class/thermal/thermal_zoneN`
+    pub const CHIP_NAME: &str = "thermal";
+
+    /// Zone temperature file (millidegrees Celsius)
+    pub const TEMP_FILE: &str = "temp";
+}
+
+/// Storage drive temperature sources
+pub mod storage {
+    use std::time::Duration;
+
+    /// hwmon drivers reporting drive temperatures
+    pub const HWMON_CHIP_NAMES: &[&str] = &["nvme", "drivetemp"];
+
+    /// Pseudo-chip grouping smartctl readings
+    pub const SMART_CHIP_NAME: &str = "smart";
+
+    /// smartmontools binary
+    pub const SMARTCTL: &str = "smartctl";
+
+    /// How long a smartctl reading is reused (drive temps change slowly)
+    pub const SMART_CACHE_TTL: Duration = Duration::from_secs(60);
+
+    /// Block device name prefixes queried with smartctl (SATA/SAS)
+    pub const SMART_DEVICE_PREFIXES: &[&str] = &["sd"];
+}
+
+/// BMC access through ipmitool
+pub mod ipmi {
+    use std::time::Duration;
+
+    /// ipmitool binary
+    pub const IPMITOOL: &str = "ipmitool";
+
+    /// Device nodes of the kernel IPMI driver (Linux, FreeBSD, Solaris-style)
+    pub const DEVICE_NODES: &[&str] = &["/dev/ipmi0", "/dev/ipmi/0", "/dev/ipmidev/0"];
+
+    /// Pseudo-chip grouping BMC sensors and fan zones
+    pub const CHIP_NAME: &str = "ipmi";
+
+    /// How long an SDR sensor listing is reused (one listing takes ~1s on many BMCs)
+    pub const SDR_CACHE_TTL: Duration = Duration::from_secs(5);
+
+    /// Shortest time between duty commands to the same zone
+    pub const MIN_WRITE_INTERVAL: Duration = Duration::from_secs(5);
+
+    /// ipmitool calls taking longer are killed (unresponsive BMC)
+    pub const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+}
+
+/// Unit scaling for voltage/current/power/humidity hwmon channels
+pub mod sensor {
+    /// `inN_input` is in millivolts
+    pub const MILLIVOLTS_PER_VOLT: f32 = 1000.0;
+
+    /// `currN_input` is in milliamps
+    pub const MILLIAMPS_PER_AMP: f32 = 1000.0;
+
+    /// `powerN_input` / `powerN_average` are in microwatts
+    pub const MICROWATTS_PER_WATT: f32 = 1_000_000.0;
+
+    /// `humidityN_input` is in milli-percent
+    pub const MILLIPERCENT_PER_PERCENT: f32 = 1000.0;
+}
+
 /// Temperature constants
 pub mod temperature {
     /// Temperature readings are in millidegrees, divide by this to get Celsius
@@ -297,6 +441,14 @@ pub mod temperature {
     /// High temperature threshold (Celsius)
     pub const HIGH_THRESHOLD: f32 = 80.0;
 
+    /// Failsafe engages this far below a sensor's critical limit (Celsius)
+    pub const FAILSAFE_MARGIN: f32 = 5.0;
+
+    /// Driver limits outside this range are treated as unset (Celsius);
+    /// some chips report 0 or 255 for channels without a limit
+    pub const LIMIT_MIN_PLAUSIBLE: f32 = 20.0;
+    pub const LIMIT_MAX_PLAUSIBLE: f32 = 150.0;
+
     /// Target temperature for balanced operation (Celsius)
     pub const TARGET_THRESHOLD: f32 = 70.0;
 
@@ -320,6 +472,15 @@ pub mod timing {
     /// Brief delay between detection tests
     pub const DETECTION_DELAY: Duration = Duration::from_millis(1000);
 
+    /// How often waits during detection check for cancellation
+    pub const DETECTION_CANCEL_POLL: Duration = Duration::from_millis(100);
+
+    /// How long passive detection watches PWM and RPM readings
+    pub const PASSIVE_DETECTION_WINDOW: Duration = Duration::from_secs(180);
+
+    /// Interval between passive detection samples
+    pub const PASSIVE_SAMPLE_INTERVAL: Duration = Duration::from_millis(1000);
+
     /// Polling interval for fan curve updates
     pub const CURVE_UPDATE_INTERVAL: Duration = Duration::from_millis(1000);
 
@@ -340,6 +501,18 @@ pub mod detection {
 
     /// High confidence threshold
     pub const HIGH_CONFIDENCE: f32 = 0.8;
+
+    /// Minimum PWM/RPM correlation for a passive detection match
+    pub const PASSIVE_MIN_CORRELATION: f32 = 0.8;
+
+    /// Minimum PWM range (raw units) seen before passive detection tries an output
+    pub const PASSIVE_MIN_PWM_SWING: u8 = 12;
+
+    /// Minimum RPM range seen before passive detection considers a fan
+    pub const PASSIVE_MIN_RPM_SWING: u32 = 100;
+
+    /// Fan response delay (in samples) allowed when correlating
+    pub const PASSIVE_MAX_LAG_SAMPLES: usize = 4;
     
     /// Daemon startup delay for hardware module initialization (milliseconds)
     pub const DAEMON_STARTUP_DELAY_MS: u64 = 500;
@@ -391,11 +564,74 @@ pub mod detection {
     }
 }
 
+/// Session recording (record-and-replay)
+pub mod recording {
+    /// Directory the daemon writes recordings to
+    pub const RECORDINGS_DIR: &str = "/var/lib/hyperfan/recordings";
+
+    /// Default sample interval (ms)
+    pub const DEFAULT_INTERVAL_MS: u32 = 1000;
+
+    /// Samples after which a recording stops automatically (24h at 1s)
+    pub const MAX_SAMPLES: u64 = 86_400;
+
+    /// Maximum recording file size accepted by the loader (64MB)
+    pub const MAX_FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+    /// Recording file format version
+    pub const FORMAT_VERSION: u32 = 1;
+}
+
+/// Audit log of control writes made through the daemon
+pub mod audit {
+    /// Directory the daemon writes the audit log to
+    pub const AUDIT_LOG_DIR: &str = "/var/log/hyperfan";
+
+    /// Current log file; rotated files get a `.1`, `.2`, ... suffix
+    pub const AUDIT_LOG_FILE: &str = "audit.jsonl";
+
+    /// Size at which the log is rotated (10MB)
+    pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+    /// Rotated files kept besides the current one
+    pub const MAX_ROTATED_FILES: u32 = 5;
+}
+
+/// Curve profiles (quick-switchable curve sets)
+pub mod profiles {
+    /// Low-noise curves for desktop use
+    pub const QUIET: &str = "quiet";
+
+    /// Everyday curves (each pair's own `curve_id`)
+    pub const NORMAL: &str = "normal";
+
+    /// Aggressive cooling for gaming and sustained load
+    pub const PERFORMANCE: &str = "performance";
+
+    /// Profile active when none has been selected
+    pub const DEFAULT: &str = NORMAL;
+
+    /// Profiles always offered to the user, in display order
+    pub const BUILTIN: &[&str] = &[QUIET, NORMAL, PERFORMANCE];
+
+    /// How often the daemon evaluates the profile schedule (seconds)
+    pub const SCHEDULE_CHECK_INTERVAL_SECS: u64 = 30;
+}
+
+/// Power source tracking (battery curve offsets)
+pub mod power {
+    /// How often the daemon re-reads the power supply state (seconds)
+    pub const CHECK_INTERVAL_SECS: u64 = 5;
+}
+
 /// File size limits for security
 pub mod limits {
     /// Maximum profile config file size (1MB)
     pub const MAX_PROFILE_SIZE: u64 = 1024 * 1024;
 
+    /// Maximum daemon config file size (64KB)
+    pub const MAX_DAEMON_CONFIG_SIZE: u64 = 64 * 1024;
+
     /// Maximum number of fan mappings
     pub const MAX_MAPPINGS: usize = 32;
 
@@ -462,6 +698,124 @@ pub mod curve {
     pub const MIN_RAMP_SPEED: f32 = 0.0;
 }
 
+/// Fan calibration sweep
+pub mod calibration {
+    use std::time::Duration;
+
+    /// PWM step between readings
+    pub const PWM_STEP: u8 = 16;
+
+    /// Wait after each PWM change before reading RPM
+    pub const SETTLE: Duration = Duration::from_secs(2);
+
+    /// Wait at full duty before the downward sweep
+    pub const SPIN_UP: Duration = Duration::from_secs(4);
+
+    /// RPM changes within this fraction of max RPM count as no change
+    pub const DEAD_ZONE_FRACTION: f32 = 0.02;
+
+    /// Smallest RPM change counted as a response
+    pub const MIN_RESPONSE_RPM: u32 = 30;
+}
+
+/// Curve auto-tuning under a load test
+pub mod auto_tune {
+    use std::time::Duration;
+
+    /// Duties tested, highest first
+    pub const DUTY_STEPS: [f32; 6] = [100.0, 80.0, 65.0, 50.0, 40.0, 30.0];
+
+    /// Wait between temperature readings
+    pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+    /// A step has settled once its readings stay within the tolerance this long
+    pub const SETTLE_WINDOW: Duration = Duration::from_secs(30);
+
+    /// Spread of readings (°C) still counted as steady
+    pub const SETTLE_TOLERANCE_C: f32 = 0.5;
+
+    /// Longest wait for one step to settle
+    pub const MAX_STEP: Duration = Duration::from_secs(300);
+
+    /// A step ends, and no lower duty is tried, this far past the target (°C)
+    pub const ABORT_MARGIN_C: f32 = 5.0;
+
+    /// The tuned curve reaches its working duty this far below the target (°C)
+    pub const TARGET_MARGIN_C: f32 = 2.0;
+
+    /// The tuned curve starts rising this far below the target (°C)
+    pub const RAMP_SPAN_C: f32 = 20.0;
+
+    /// The tuned curve reaches full speed this far past the target (°C)
+    pub const FULL_SPEED_MARGIN_C: f32 = 5.0;
+}
+
+/// Closed-loop RPM targeting
+pub mod rpm_control {
+    /// Fraction of the RPM error (relative to max RPM) applied to PWM per iteration
+    pub const GAIN: f32 = 0.5;
+
+    /// Largest PWM change per control iteration
+    pub const MAX_STEP: f32 = 16.0;
+
+    /// Errors within this fraction of max RPM are left alone (tach jitter)
+    pub const DEADBAND_FRACTION: f32 = 0.02;
+
+    /// Smallest deadband in RPM
+    pub const MIN_DEADBAND_RPM: f32 = 30.0;
+}
+
+/// Fan noise estimation
+pub mod noise {
+    /// Full-speed loudness assumed for a fan without a noise profile (dBA)
+    pub const DEFAULT_MAX_DBA: f32 = 35.0;
+
+    /// Loudness change per decade of fan speed (fan laws: 50·log10 of the speed ratio)
+    pub const DBA_PER_SPEED_DECADE: f32 = 50.0;
+
+    /// Loudest level accepted in a noise profile (dBA)
+    pub const MAX_DBA: f32 = 90.0;
+
+    /// Most points in a measured noise profile
+    pub const MAX_POINTS: usize = 16;
+}
+
+/// User-defined virtual temperature sensors
+pub mod virtual_sensor {
+    /// Path prefix of virtual sensors ("virtual:<id>")
+    pub const PATH_PREFIX: &str = hf_protocol::VIRTUAL_PATH_PREFIX;
+
+    /// Most inputs a max/min/average sensor combines
+    pub const MAX_INPUTS: usize = 8;
+
+    /// Largest correction scale factor
+    pub const MAX_SCALE: f32 = 10.0;
+
+    /// Largest correction offset in degrees Celsius
+    pub const MAX_OFFSET_CELSIUS: f32 = 50.0;
+
+    /// Longest sensor name
+    pub const MAX_NAME_LENGTH: usize = 64;
+}
+
+/// Per-source temperature filters
+pub mod sensor_filter {
+    /// Smallest EMA factor (1.0 = no smoothing)
+    pub const MIN_EMA_ALPHA: f32 = 0.05;
+
+    /// Longest median window in samples
+    pub const MAX_MEDIAN_WINDOW: u32 = 15;
+
+    /// Smallest spike rejection threshold in degrees Celsius
+    pub const MIN_SPIKE_THRESHOLD: f32 = 1.0;
+
+    /// Largest spike rejection threshold in degrees Celsius
+    pub const MAX_SPIKE_THRESHOLD: f32 = 50.0;
+
+    /// Consecutive samples rejected as spikes before a jump is accepted as real
+    pub const MAX_REJECTED_SPIKES: u32 = 3;
+}
+
 // GPU-related constants have been moved to hf-gpu crate
 
 /// Fingerprint validation thresholds
@@ -505,6 +859,15 @@ pub mod fingerprint {
     
     /// Value range tolerance for expected_value_range guard
     pub const VALUE_RANGE_TOLERANCE: f32 = 0.20; // 20% outside learned range triggers warning
+
+    /// Weight for an unchanged channel number when ranking rebind candidates
+    pub const WEIGHT_CHANNEL_INDEX: f32 = 10.0;
+    /// Share of a rebind candidate's confidence taken from its fan input
+    pub const CANDIDATE_FAN_SHARE: f32 = 0.3;
+    /// Rebind candidates below this confidence are not offered
+    pub const MIN_CANDIDATE_CONFIDENCE: f32 = 0.40;
+    /// Rebind candidates offered per conflicting binding
+    pub const MAX_BINDING_CANDIDATES: usize = 5;
 }
 
 /// Default fan curve points
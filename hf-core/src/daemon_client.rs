@@ -3,6 +3,7 @@
 //! Communicates with the privileged hyperfand daemon via Unix socket.
 //! Provides a safe interface for hardware operations without requiring root.
 
+use std::collections::VecDeque;
 use std::io::{BufRead, BufReader, Write};
 use std::os::unix::net::UnixStream;
 use std::time::{Duration, Instant};
@@ -15,6 +16,7 @@ use crate::service::get_socket_path;
 const TIMEOUT_MS: u64 = 5000;
 
 const MAX_MESSAGE_SIZE: usize = hf_protocol::MAX_MESSAGE_SIZE;
+const MAX_RESPONSE_SIZE: usize = hf_protocol::MAX_RESPONSE_SIZE;
 
 /// Initial buffer size for responses (ListAll responses are ~4KB typically)
 const INITIAL_BUFFER_SIZE: usize = 4096;
@@ -30,6 +32,22 @@ pub const MIN_RATE_LIMIT: u32 = hf_protocol::MIN_RATE_LIMIT;
 /// Maximum rate limit (cannot exceed this)  
 pub const MAX_RATE_LIMIT: u32 = hf_protocol::MAX_RATE_LIMIT;
 
+/// Shortest sampling interval accepted by `daemon_watch_ec_registers`
+pub const MIN_EC_WATCH_INTERVAL_MS: u32 = hf_protocol::MIN_EC_WATCH_INTERVAL_MS;
+
+/// Longest span a single EC watch may cover
+pub const MAX_EC_WATCH_DURATION_MS: u32 = hf_protocol::MAX_EC_WATCH_DURATION_MS;
+
+/// Largest offset (either direction) a PWM group member may have
+pub const MAX_PWM_GROUP_OFFSET_PERCENT: f32 = hf_protocol::MAX_PWM_GROUP_OFFSET_PERCENT;
+
+/// Longest TTL `daemon_set_pwm_override` accepts
+pub const MAX_PWM_OVERRIDE_TTL_MS: u32 = hf_protocol::MAX_PWM_OVERRIDE_TTL_MS;
+
+/// Auto-tune temperature targets accepted by `daemon_start_auto_tune` (°C)
+pub const MIN_AUTO_TUNE_TARGET: f32 = hf_protocol::MIN_AUTO_TUNE_TARGET;
+pub const MAX_AUTO_TUNE_TARGET: f32 = hf_protocol::MAX_AUTO_TUNE_TARGET;
+
 /// Current client-side rate limit (configurable at runtime)
 static CLIENT_RATE_LIMIT: AtomicU32 = AtomicU32::new(DEFAULT_CLIENT_RATE_LIMIT);
 
@@ -104,16 +122,69 @@ pub type DaemonTempSensor = hf_protocol::TempSensor;
 pub type DaemonFanSensor = hf_protocol::FanSensor;
 pub type DaemonPwmControl = hf_protocol::PwmControl;
 pub type DaemonGpuInfo = hf_protocol::GpuInfo;
+pub type DaemonGpuTempInfo = hf_protocol::GpuTempInfo;
+pub type DaemonHwSensor = hf_protocol::HwSensor;
+pub type DaemonSensorKind = hf_protocol::SensorKind;
+pub type DaemonHwAlarm = hf_protocol::HwAlarm;
+pub type DaemonAlarmKind = hf_protocol::AlarmKind;
 pub type DaemonFanMapping = hf_protocol::FanMapping;
 pub type DaemonManualPwmFanPairing = hf_protocol::ManualPwmFanPairing;
 pub type DaemonEcChipInfo = hf_protocol::EcChipInfo;
 pub type DaemonEcRegisterValue = hf_protocol::EcRegisterValue;
+pub type DaemonEcRegisterChange = hf_protocol::EcRegisterChange;
+pub type DaemonEcFanProfile = hf_protocol::EcFanProfile;
+pub type DaemonEcFanMap = hf_protocol::EcFanMap;
+pub type DaemonPwmGroup = hf_protocol::PwmGroup;
+pub type DaemonPwmGroupMember = hf_protocol::PwmGroupMember;
 pub type DaemonAllHardwareData = hf_protocol::AllHardwareData;
+pub type DaemonFanAlert = hf_protocol::FanAlert;
+pub type DaemonFanAlertKind = hf_protocol::FanAlertKind;
+pub type DaemonHardwareChange = hf_protocol::HardwareChange;
+pub type DaemonHardwareChangeKind = hf_protocol::HardwareChangeKind;
+pub type DaemonLogEntry = hf_protocol::LogEntry;
+pub type DaemonEvent = hf_protocol::Event;
+pub type DaemonEventEnvelope = hf_protocol::EventEnvelope;
+pub type DaemonConfigSection = hf_protocol::ConfigSection;
+pub type DaemonLogLevel = hf_protocol::LogLevel;
+pub type DaemonRecordingInfo = hf_protocol::RecordingInfo;
+pub type DaemonRecordingFormat = hf_protocol::RecordingFormat;
+pub type DaemonHistoryData = hf_protocol::HistoryData;
+pub type DaemonHistorySeries = hf_protocol::HistorySeries;
+pub type DaemonHistorySeriesKind = hf_protocol::HistorySeriesKind;
+pub type DaemonHistoryPoint = hf_protocol::HistoryPoint;
+pub type DaemonCurveTuning = hf_protocol::CurveTuning;
+pub type DaemonPairingControlMode = hf_protocol::PairingControlMode;
+pub type DaemonPwmEnforcement = hf_protocol::PwmEnforcement;
+pub type DaemonFanCalibration = hf_protocol::FanCalibration;
+pub type DaemonCalibrationStatus = hf_protocol::CalibrationStatus;
+pub type DaemonDetectionProgress = hf_protocol::DetectionProgress;
+pub type DaemonDetectionJobState = hf_protocol::DetectionJobState;
+pub type DaemonDetectionStrategy = hf_protocol::DetectionStrategy;
+pub type DaemonAutoTuneProgress = hf_protocol::AutoTuneProgress;
+pub type DaemonAutoTuneStep = hf_protocol::AutoTuneStep;
+pub type DaemonBindingConflict = hf_protocol::BindingConflict;
+pub type DaemonBindingConflictState = hf_protocol::BindingConflictState;
+pub type DaemonBindingCandidate = hf_protocol::BindingCandidate;
+pub type DaemonProfileInfo = hf_protocol::ProfileInfo;
+pub type DaemonGpuFanCurveInfo = hf_protocol::GpuFanCurveInfo;
+pub type DaemonRateLimitConfig = hf_protocol::RateLimitConfig;
+pub type DaemonRequestCosts = hf_protocol::RequestCosts;
+pub type DaemonRequestClass = hf_protocol::RequestClass;
+pub type DaemonGpuFanCurvePoint = hf_protocol::GpuFanCurvePoint;
+pub type DaemonGpuPowerLimitInfo = hf_protocol::GpuPowerLimitInfo;
+pub type DaemonActiveOverride = hf_protocol::ActiveOverride;
+pub type DaemonCompetingSoftware = hf_protocol::CompetingSoftware;
+pub type DaemonCompetingSoftwareReport = hf_protocol::CompetingSoftwareReport;
+pub type DaemonPwmEnableFlip = hf_protocol::PwmEnableFlip;
+pub type DaemonControlStatus = hf_protocol::ControlStatus;
+pub type DaemonImplausibleReadings = hf_protocol::ImplausibleReadings;
 
 /// Daemon client for making requests
 pub struct DaemonClient {
     writer: UnixStream,
     reader: BufReader<UnixStream>,
+    /// Daemon accepts length-prefixed frames (negotiated via Version on connect)
+    framed: bool,
 }
 
 impl DaemonClient {
@@ -206,10 +277,72 @@ impl DaemonClient {
             .set_write_timeout(Some(Duration::from_millis(TIMEOUT_MS)))
             .map_err(|e| format!("Failed to set write timeout: {}", e))?;
 
-        Ok(Self {
+        let mut client = Self {
             writer: stream,
             reader: BufReader::new(reader_stream),
-        })
+            framed: false,
+        };
+        client.negotiate_framing()?;
+        Ok(client)
+    }
+
+    /// Switch to length-prefixed frames if the daemon offers them
+    /// Daemons without capabilities in their Version response only speak JSON lines
+    fn negotiate_framing(&mut self) -> Result<(), String> {
+        let request_id = hf_protocol::generate_request_id();
+        let envelope = hf_protocol::RequestEnvelope::with_id(DaemonRequest::Version, request_id);
+        let json = serde_json::to_vec(&envelope)
+            .map_err(|e| format!("Failed to serialize request: {}", e))?;
+        self.write_message(&json)
+            .map_err(|e| format!("Failed to send request: {}", e))?;
+        let response = self.read_message(&mut Vec::new())
+            .map_err(|e| format!("Failed to read response: {}", e))?;
+
+        let framed = match serde_json::from_slice::<hf_protocol::ResponseEnvelope>(&response) {
+            Ok(hf_protocol::ResponseEnvelope { id, response: DaemonResponse::Ok(data) }) if id == request_id => {
+                data.capabilities.is_some_and(|c| c.supports_frames())
+            }
+            _ => false,
+        };
+        self.framed = framed;
+        Ok(())
+    }
+
+    /// Send one message in the connection's framing
+    fn write_message(&mut self, json: &[u8]) -> std::io::Result<()> {
+        if self.framed {
+            self.writer.write_all(&hf_protocol::frame::encode_frame(json))
+        } else {
+            self.writer.write_all(json)?;
+            self.writer.write_all(b"\n")
+        }
+    }
+
+    /// Read one message in the connection's framing
+    /// `pending` holds bytes of a message cut short by an earlier read timeout
+    fn read_message(&mut self, pending: &mut Vec<u8>) -> std::io::Result<Vec<u8>> {
+        loop {
+            if let Some(message) = take_message(pending, self.framed)? {
+                return Ok(message);
+            }
+            let available = self.reader.fill_buf()?;
+            if available.is_empty() {
+                return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Daemon closed connection"));
+            }
+            // Take no more than this message so the next one stays buffered
+            let len = if self.framed {
+                let header_len = hf_protocol::frame::FRAME_HEADER_LEN;
+                let wanted = match frame_header(pending)? {
+                    Some(header) => header_len + header.len - pending.len(),
+                    None => header_len - pending.len(),
+                };
+                wanted.min(available.len())
+            } else {
+                available.iter().position(|b| *b == b'\n').map_or(available.len(), |pos| pos + 1)
+            };
+            pending.extend_from_slice(&available[..len]);
+            self.reader.consume(len);
+        }
     }
 
     /// Send a request and get response (with automatic retry on connection failure)
@@ -233,19 +366,21 @@ impl DaemonClient {
         
         // Serialize request envelope
         // PERF: Use to_vec to avoid intermediate String allocation
-        let mut json = serde_json::to_vec(&envelope)
+        let json = serde_json::to_vec(&envelope)
             .map_err(|e| format!("Failed to serialize request: {}", e))?;
-        json.push(b'\n');
 
-        if json.len() > MAX_MESSAGE_SIZE {
-            return Err(crate::error::HyperfanError::MessageTooLarge {
-                size: json.len(),
-                max_size: MAX_MESSAGE_SIZE
-            }.to_string());
+        // JSON lines (newline included) are capped far below frames
+        let (size, max_size) = if self.framed {
+            (json.len(), hf_protocol::frame::MAX_FRAMED_REQUEST_SIZE)
+        } else {
+            (json.len() + 1, MAX_MESSAGE_SIZE)
+        };
+        if size > max_size {
+            return Err(crate::error::HyperfanError::MessageTooLarge { size, max_size }.to_string());
         }
 
         // Send (with automatic reconnect on failure)
-        if let Err(e) = self.writer.write_all(&json) {
+        if let Err(e) = self.write_message(&json) {
             if allow_retry {
                 // Connection failed - try to reconnect and retry once
                 *self = Self::connect()
@@ -256,34 +391,25 @@ impl DaemonClient {
         }
 
         // Read response with efficient buffer allocation (with automatic reconnect on failure)
-        let mut response_buf: Vec<u8> = Vec::with_capacity(INITIAL_BUFFER_SIZE);
-        if let Err(e) = self.reader.read_until(b'\n', &mut response_buf) {
-            if allow_retry {
-                // Connection failed - try to reconnect and retry once
-                *self = Self::connect()
-                    .map_err(|e2| format!("Failed to reconnect after read error: {}", e2))?;
-                return self.request_with_retry(req, false);
+        let mut pending: Vec<u8> = Vec::with_capacity(INITIAL_BUFFER_SIZE);
+        let response_buf = match self.read_message(&mut pending) {
+            Ok(message) => message,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                return Err(crate::error::HyperfanError::DaemonConnection("Daemon closed connection".to_string()).to_string());
             }
-            return Err(format!("Failed to read response: {}", e));
-        }
-
-        if response_buf.is_empty() {
-            return Err(crate::error::HyperfanError::DaemonConnection("Daemon closed connection".to_string()).to_string());
-        }
-
-        if response_buf.len() > MAX_MESSAGE_SIZE {
-            return Err(crate::error::HyperfanError::MessageTooLarge {
-                size: response_buf.len(),
-                max_size: MAX_MESSAGE_SIZE
-            }.to_string());
-        }
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => return Err(e.to_string()),
+            Err(e) => {
+                if allow_retry {
+                    // Connection failed - try to reconnect and retry once
+                    *self = Self::connect()
+                        .map_err(|e2| format!("Failed to reconnect after read error: {}", e2))?;
+                    return self.request_with_retry(req, false);
+                }
+                return Err(format!("Failed to read response: {}", e));
+            }
+        };
 
         // PERF: Parse directly from bytes, skip UTF-8 string conversion
-        // Trim trailing newline in-place
-        if response_buf.last() == Some(&b'\n') {
-            response_buf.pop();
-        }
-        
         // Parse response envelope directly from bytes
         let response_envelope: hf_protocol::ResponseEnvelope = serde_json::from_slice(&response_buf)
             .map_err(|e| format!("Failed to parse response: {}", e))?;
@@ -317,10 +443,38 @@ impl DaemonClient {
                     DaemonRequest::ReadPwm { .. } => data.pwm.is_some(),
                     DaemonRequest::ListGpus => data.gpus.is_some(),
                     DaemonRequest::DetectFanMappings => data.fan_mappings.is_some(),
+                    DaemonRequest::StartDetection { .. } | DaemonRequest::GetDetectionProgress { .. }
+                    | DaemonRequest::CancelDetection { .. } => data.detection.is_some(),
+                    DaemonRequest::StartAutoTune { .. } | DaemonRequest::GetAutoTuneProgress { .. }
+                    | DaemonRequest::CancelAutoTune { .. } => data.auto_tune.is_some(),
+                    DaemonRequest::GetBindingConflicts => data.binding_conflicts.is_some(),
                     DaemonRequest::GetManualPairings => data.manual_pairings.is_some(),
                     DaemonRequest::ListEcChips => data.ec_chips.is_some(),
                     DaemonRequest::ReadEcRegister { .. } => data.ec_register.is_some(),
                     DaemonRequest::ReadEcRegisterRange { .. } => data.ec_registers.is_some(),
+                    DaemonRequest::WatchEcRegisters { .. } => data.ec_changes.is_some(),
+                    DaemonRequest::GetFanAlerts { .. } => data.fan_alerts.is_some(),
+                    DaemonRequest::GetHardwareChanges { .. } => data.hardware_changes.is_some(),
+                    DaemonRequest::GetLogs { .. } => data.logs.is_some(),
+                    DaemonRequest::Subscribe { .. } => data.events.is_some(),
+                    DaemonRequest::GetPwmGroups | DaemonRequest::SetPwmGroup { .. }
+                    | DaemonRequest::DeletePwmGroup { .. } => data.pwm_groups.is_some(),
+                    DaemonRequest::CalibrateFan { .. } | DaemonRequest::GetFanCalibration { .. } => {
+                        data.calibration_status.is_some()
+                    }
+                    DaemonRequest::StartRecording { .. } | DaemonRequest::StopRecording
+                    | DaemonRequest::GetRecording => data.recording.is_some(),
+                    DaemonRequest::QueryHistory { .. } => data.history.is_some(),
+                    DaemonRequest::GetActiveProfile | DaemonRequest::SetActiveProfile { .. } => data.profile.is_some(),
+                    DaemonRequest::GetDryRun | DaemonRequest::SetDryRun { .. } => data.dry_run.is_some(),
+                    DaemonRequest::GetSafeMode => data.safe_mode.is_some(),
+                    DaemonRequest::GetControlStatus => data.control_status.is_some(),
+                    DaemonRequest::ListActiveOverrides => data.active_overrides.is_some(),
+                    DaemonRequest::GetCompetingSoftware => data.competing_software.is_some(),
+                    DaemonRequest::GetGpuFanCurve { .. } | DaemonRequest::SetGpuFanCurve { .. }
+                    | DaemonRequest::ResetGpuFanCurve { .. } => data.gpu_fan_curve.is_some(),
+                    DaemonRequest::GetGpuPowerLimit { .. } | DaemonRequest::SetGpuPowerLimit { .. }
+                    | DaemonRequest::ResetGpuPowerLimit { .. } => data.gpu_power_limit.is_some(),
                     // Commands that return empty response
                     _ => true,
                 };
@@ -478,6 +632,65 @@ pub fn daemon_clear_pwm_override(path: &str) -> Result<(), String> {
     result
 }
 
+/// List the PWM overrides in effect and who set them
+pub fn daemon_list_active_overrides() -> Result<Vec<DaemonActiveOverride>, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::ListActiveOverrides)? {
+        DaemonResponse::Ok(data) if data.active_overrides.is_some() => Ok(data.active_overrides.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Other fan control software the daemon found running or flipping `pwmN_enable`
+pub fn daemon_get_competing_software() -> Result<DaemonCompetingSoftwareReport, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::GetCompetingSoftware)? {
+        DaemonResponse::Ok(data) if data.competing_software.is_some() => Ok(data.competing_software.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Drive the outputs following `curve_id` from unsaved `points` for `duration_ms`
+/// The daemon returns to the saved curve afterwards (or on the next config reload).
+pub fn daemon_preview_curve(
+    curve_id: &str,
+    points: &[(f32, f32)],
+    interpolation: crate::CurveInterpolation,
+    duration_ms: u32,
+) -> Result<(), String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::PreviewCurve {
+        curve_id: curve_id.to_string(),
+        points: points.to_vec(),
+        interpolation,
+        duration_ms,
+    })? {
+        DaemonResponse::Ok(_) => Ok(()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// End a curve preview before it expires
+pub fn daemon_clear_curve_preview(curve_id: &str) -> Result<(), String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::ClearCurvePreview {
+        curve_id: curve_id.to_string(),
+    })? {
+        DaemonResponse::Ok(_) => Ok(()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
 /// List all hardware via daemon
 pub fn daemon_list_hardware() -> Result<DaemonHardwareInfo, String> {
     let mut client = DaemonClient::get_pooled()?;
@@ -568,6 +781,86 @@ pub fn daemon_detect_fan_mappings() -> Result<Vec<DaemonFanMapping>, String> {
     result
 }
 
+/// Start fan detection in the daemon; poll it with [`daemon_get_detection_progress`]
+pub fn daemon_start_detection(strategy: DaemonDetectionStrategy) -> Result<DaemonDetectionProgress, String> {
+    detection_request(DaemonRequest::StartDetection { strategy })
+}
+
+/// Progress of a detection job (its mappings once completed)
+pub fn daemon_get_detection_progress(job_id: u64) -> Result<DaemonDetectionProgress, String> {
+    detection_request(DaemonRequest::GetDetectionProgress { job_id })
+}
+
+/// Abort a detection job; the daemon restores the PWM state it started from
+pub fn daemon_cancel_detection(job_id: u64) -> Result<DaemonDetectionProgress, String> {
+    detection_request(DaemonRequest::CancelDetection { job_id })
+}
+
+fn detection_request(request: DaemonRequest) -> Result<DaemonDetectionProgress, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(request)? {
+        DaemonResponse::Ok(data) if data.detection.is_some() => Ok(data.detection.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Start auto-tuning curve `curve_id` for `target_temp`; poll it with [`daemon_get_auto_tune_progress`]
+pub fn daemon_start_auto_tune(curve_id: &str, target_temp: f32) -> Result<DaemonAutoTuneProgress, String> {
+    auto_tune_request(DaemonRequest::StartAutoTune { curve_id: curve_id.to_string(), target_temp })
+}
+
+/// Progress of an auto-tune job (its suggested curve once completed)
+pub fn daemon_get_auto_tune_progress(job_id: u64) -> Result<DaemonAutoTuneProgress, String> {
+    auto_tune_request(DaemonRequest::GetAutoTuneProgress { job_id })
+}
+
+/// Abort an auto-tune job; the outputs go back to their curves
+pub fn daemon_cancel_auto_tune(job_id: u64) -> Result<DaemonAutoTuneProgress, String> {
+    auto_tune_request(DaemonRequest::CancelAutoTune { job_id })
+}
+
+fn auto_tune_request(request: DaemonRequest) -> Result<DaemonAutoTuneProgress, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(request)? {
+        DaemonResponse::Ok(data) if data.auto_tune.is_some() => Ok(data.auto_tune.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Fingerprinted bindings that no longer match the hardware, with candidate matches
+pub fn daemon_get_binding_conflicts() -> Result<Vec<DaemonBindingConflict>, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::GetBindingConflicts)? {
+        DaemonResponse::Ok(data) if data.binding_conflicts.is_some() => Ok(data.binding_conflicts.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Confirm that `pwm_path` (and `fan_path`) is the hardware of binding `pwm_id`
+/// The daemon moves the configuration of the old PWM path to the new one
+pub fn daemon_accept_binding_match(pwm_id: &str, pwm_path: &str, fan_path: Option<&str>) -> Result<(), String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::AcceptBindingMatch {
+        pwm_id: pwm_id.to_string(),
+        pwm_path: pwm_path.to_string(),
+        fan_path: fan_path.map(String::from),
+    })? {
+        DaemonResponse::Ok(_) => Ok(()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
 /// Tell daemon to reload its configuration
 pub fn daemon_reload_config() -> Result<(), String> {
     let mut client = DaemonClient::get_pooled()?;
@@ -579,6 +872,18 @@ pub fn daemon_reload_config() -> Result<(), String> {
     result
 }
 
+/// Tell daemon to reload one part of its configuration after it was edited on disk
+/// Other clients are told to re-read only that section
+pub fn daemon_reload_config_section(section: DaemonConfigSection) -> Result<(), String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::ReloadConfigSection { section })? {
+        DaemonResponse::Ok(_) => Ok(()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
 pub fn daemon_get_manual_pairings() -> Result<Vec<DaemonManualPwmFanPairing>, String> {
     let mut client = DaemonClient::get_pooled()?;
     let result = match client.request(DaemonRequest::GetManualPairings)? {
@@ -595,6 +900,18 @@ pub fn daemon_set_manual_pairing(
     pwm_path: &str, 
     fan_uuid: Option<&str>,
     fan_path: Option<&str>,
+) -> Result<(), String> {
+    daemon_set_manual_pairing_with_tuning(pwm_uuid, pwm_path, fan_uuid, fan_path, None)
+}
+
+/// Set a PWM-fan pairing with response tuning (hysteresis, ramp rates, smoothing)
+/// Passing `None` keeps the pairing's existing tuning
+pub fn daemon_set_manual_pairing_with_tuning(
+    pwm_uuid: &str,
+    pwm_path: &str,
+    fan_uuid: Option<&str>,
+    fan_path: Option<&str>,
+    tuning: Option<DaemonCurveTuning>,
 ) -> Result<(), String> {
     let mut client = DaemonClient::get_pooled()?;
     let result = match client.request(DaemonRequest::SetManualPairing {
@@ -602,6 +919,7 @@ pub fn daemon_set_manual_pairing(
         pwm_path: pwm_path.to_string(),
         fan_uuid: fan_uuid.map(|s| s.to_string()),
         fan_path: fan_path.map(|s| s.to_string()),
+        tuning,
     })? {
         DaemonResponse::Ok(_) => Ok(()),
         DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
@@ -622,6 +940,72 @@ pub fn daemon_delete_manual_pairing(pwm_path: &str) -> Result<(), String> {
     result
 }
 
+/// Switch a pairing between duty-cycle and closed-loop RPM control
+pub fn daemon_set_pairing_control_mode(pwm_path: &str, mode: DaemonPairingControlMode) -> Result<(), String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::SetPairingControlMode {
+        pwm_path: pwm_path.to_string(),
+        mode,
+    })? {
+        DaemonResponse::Ok(_) => Ok(()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Allow or prevent a pairing's curve stopping the fan below its stall duty
+pub fn daemon_set_pairing_zero_rpm(pwm_path: &str, enabled: bool) -> Result<(), String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::SetPairingZeroRpm {
+        pwm_path: pwm_path.to_string(),
+        enabled,
+    })? {
+        DaemonResponse::Ok(_) => Ok(()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Choose whether the daemon re-asserts, yields or only alerts when another program writes a pairing's PWM
+pub fn daemon_set_pairing_enforcement(pwm_path: &str, policy: DaemonPwmEnforcement) -> Result<(), String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::SetPairingEnforcement {
+        pwm_path: pwm_path.to_string(),
+        policy,
+    })? {
+        DaemonResponse::Ok(_) => Ok(()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Start calibrating a PWM output against its fan (takes about a minute)
+pub fn daemon_calibrate_fan(pwm_path: &str, fan_path: &str) -> Result<DaemonCalibrationStatus, String> {
+    calibration_request(DaemonRequest::CalibrateFan {
+        pwm_path: pwm_path.to_string(),
+        fan_path: fan_path.to_string(),
+    })
+}
+
+/// Get the stored calibration of a PWM output and whether a sweep is running
+pub fn daemon_get_fan_calibration(pwm_path: &str) -> Result<DaemonCalibrationStatus, String> {
+    calibration_request(DaemonRequest::GetFanCalibration { pwm_path: pwm_path.to_string() })
+}
+
+fn calibration_request(request: DaemonRequest) -> Result<DaemonCalibrationStatus, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(request)? {
+        DaemonResponse::Ok(data) if data.calibration_status.is_some() => Ok(data.calibration_status.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
 pub fn daemon_list_ec_chips() -> Result<Vec<DaemonEcChipInfo>, String> {
     let mut client = DaemonClient::get_pooled()?;
     let result = match client.request(DaemonRequest::ListEcChips)? {
@@ -680,6 +1064,421 @@ pub fn daemon_read_ec_register_range(
     result
 }
 
+/// Sample `count` EC registers from `start_register` `samples` times, `interval_ms`
+/// apart, and return those whose value changed (blocks for the whole watch)
+pub fn daemon_watch_ec_registers(
+    chip_path: &str,
+    start_register: u8,
+    count: u8,
+    interval_ms: u32,
+    samples: u32,
+) -> Result<Vec<DaemonEcRegisterChange>, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::WatchEcRegisters {
+        chip_path: chip_path.to_string(),
+        start_register,
+        count,
+        interval_ms,
+        samples,
+    })? {
+        DaemonResponse::Ok(data) if data.ec_changes.is_some() => Ok(data.ec_changes.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Drive fan `fan` of the chip's known-model EC profile at `percent`
+/// (None = back to automatic EC control)
+pub fn daemon_set_ec_fan_duty(chip_path: &str, fan: u8, percent: Option<u32>) -> Result<(), String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::SetEcFanDuty {
+        chip_path: chip_path.to_string(),
+        fan,
+        percent,
+    })? {
+        DaemonResponse::Ok(_) => Ok(()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Get the configured PWM groups
+pub fn daemon_get_pwm_groups() -> Result<Vec<DaemonPwmGroup>, String> {
+    pwm_group_request(DaemonRequest::GetPwmGroups)
+}
+
+/// Create or replace a PWM group (matched by ID); returns all groups
+pub fn daemon_set_pwm_group(group: DaemonPwmGroup) -> Result<Vec<DaemonPwmGroup>, String> {
+    pwm_group_request(DaemonRequest::SetPwmGroup { group })
+}
+
+/// Delete a PWM group; returns the remaining groups
+pub fn daemon_delete_pwm_group(id: &str) -> Result<Vec<DaemonPwmGroup>, String> {
+    pwm_group_request(DaemonRequest::DeletePwmGroup { id: id.to_string() })
+}
+
+fn pwm_group_request(request: DaemonRequest) -> Result<Vec<DaemonPwmGroup>, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(request)? {
+        DaemonResponse::Ok(data) if data.pwm_groups.is_some() => Ok(data.pwm_groups.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Get fan failure alerts raised by the daemon after `since_id` (0 = all retained)
+pub fn daemon_get_fan_alerts(since_id: u64) -> Result<Vec<DaemonFanAlert>, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::GetFanAlerts { since_id })? {
+        DaemonResponse::Ok(data) if data.fan_alerts.is_some() => Ok(data.fan_alerts.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Get hwmon/DRM hotplug events seen by the daemon after `since_id` (0 = all retained)
+pub fn daemon_get_hardware_changes(since_id: u64) -> Result<Vec<DaemonHardwareChange>, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::GetHardwareChanges { since_id })? {
+        DaemonResponse::Ok(data) if data.hardware_changes.is_some() => Ok(data.hardware_changes.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Get daemon log entries after `since_id` (0 = all retained), at `min_level` or more severe
+pub fn daemon_get_logs(since_id: u64, min_level: Option<DaemonLogLevel>) -> Result<Vec<DaemonLogEntry>, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::GetLogs { since_id, min_level })? {
+        DaemonResponse::Ok(data) if data.logs.is_some() => Ok(data.logs.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Header of the frame at the start of `pending`, once all of it has arrived
+fn frame_header(pending: &[u8]) -> std::io::Result<Option<hf_protocol::frame::FrameHeader>> {
+    let Some(header) = pending.first_chunk::<{ hf_protocol::frame::FRAME_HEADER_LEN }>() else {
+        return Ok(None);
+    };
+    hf_protocol::frame::parse_header(header, hf_protocol::frame::MAX_FRAME_SIZE)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Complete message at the start of `pending` (payload only), if it has fully arrived
+fn take_message(pending: &mut Vec<u8>, framed: bool) -> std::io::Result<Option<Vec<u8>>> {
+    if framed {
+        let header_len = hf_protocol::frame::FRAME_HEADER_LEN;
+        return Ok(match frame_header(pending)? {
+            Some(header) if pending.len() >= header_len + header.len => {
+                let message = pending[header_len..header_len + header.len].to_vec();
+                pending.clear();
+                Some(message)
+            }
+            _ => None,
+        });
+    }
+
+    if pending.len() > MAX_RESPONSE_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            crate::error::HyperfanError::MessageTooLarge { size: pending.len(), max_size: MAX_RESPONSE_SIZE }.to_string(),
+        ));
+    }
+    if pending.last() == Some(&b'\n') {
+        let mut message = std::mem::take(pending);
+        message.pop();
+        return Ok(Some(message));
+    }
+    Ok(None)
+}
+
+/// Events pushed by the daemon, received on a dedicated connection
+///
+/// When the daemon goes away the next call reconnects and resubscribes with
+/// the last sequence number seen, so events raised in between are replayed
+/// (as far as the daemon retained them). The connection is not used for other
+/// requests.
+pub struct DaemonEventSubscription {
+    client: Option<DaemonClient>,
+    /// Sequence number of the last event returned
+    last_seq: Option<u64>,
+    /// When the first subscription was made, for replays before any event was seen
+    subscribed_at_ms: Option<u64>,
+    pending: VecDeque<DaemonEventEnvelope>,
+    /// Bytes of an event cut short by a read timeout
+    partial: Vec<u8>,
+}
+
+impl DaemonEventSubscription {
+    /// Subscribe to events, replaying retained ones after `since_seq` first
+    /// (None = only new events). Connects on the first `next_event`.
+    pub fn new(since_seq: Option<u64>) -> Self {
+        Self {
+            client: None,
+            last_seq: since_seq,
+            subscribed_at_ms: None,
+            pending: VecDeque::new(),
+            partial: Vec::new(),
+        }
+    }
+
+    /// Sequence number of the last event returned
+    pub fn last_seq(&self) -> Option<u64> {
+        self.last_seq
+    }
+
+    /// Wait up to `timeout` for the next event (None if none arrived)
+    /// Errors if the daemon cannot be reached; the next call reconnects
+    pub fn next_event(&mut self, timeout: Duration) -> Result<Option<DaemonEventEnvelope>, String> {
+        if self.client.is_none() {
+            self.client = Some(self.resubscribe()?);
+        }
+        if let Some(event) = self.pending.pop_front() {
+            self.last_seq = Some(event.seq);
+            return Ok(Some(event));
+        }
+        let Some(client) = self.client.as_mut() else {
+            return Ok(None);
+        };
+
+        client.reader.get_ref()
+            .set_read_timeout(Some(timeout.max(Duration::from_millis(1))))
+            .map_err(|e| format!("Failed to set read timeout: {}", e))?;
+        match client.read_message(&mut self.partial) {
+            Ok(message) => match serde_json::from_slice::<DaemonEventEnvelope>(&message) {
+                Ok(event) => {
+                    self.last_seq = Some(event.seq);
+                    Ok(Some(event))
+                }
+                Err(e) => Err(format!("Failed to parse event: {}", e)),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                self.client = None;
+                Err(crate::error::HyperfanError::DaemonConnection("Daemon closed connection".to_string()).to_string())
+            }
+            Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => Ok(None),
+            Err(e) => {
+                self.client = None;
+                Err(format!("Failed to read event: {}", e))
+            }
+        }
+    }
+
+    /// Open a connection and subscribe, queueing the replayed events
+    fn resubscribe(&mut self) -> Result<DaemonClient, String> {
+        let mut client = DaemonClient::connect()?;
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        // A reconnect before any event was seen replays everything since the
+        // first subscription
+        let first = self.subscribed_at_ms.is_none();
+        let since_seq = if first { self.last_seq } else { Some(self.last_seq.unwrap_or(0)) };
+        let replay = match client.request(DaemonRequest::Subscribe { since_seq })? {
+            DaemonResponse::Ok(data) if data.events.is_some() => data.events.unwrap(),
+            DaemonResponse::Ok(_) => return Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+            DaemonResponse::Error { message } => return Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+        };
+
+        let subscribed_at_ms = *self.subscribed_at_ms.get_or_insert(now_ms);
+        let skip_before_ms = if !first && self.last_seq.is_none() { subscribed_at_ms } else { 0 };
+        self.pending.extend(replay.into_iter().filter(|e| e.timestamp_ms >= skip_before_ms));
+        self.partial.clear();
+        Ok(client)
+    }
+}
+
+/// Start a daemon recording session (sensor snapshot every `interval_ms`)
+/// Returns the recording file location; load it with `load_recording`
+pub fn daemon_start_recording(interval_ms: u32) -> Result<DaemonRecordingInfo, String> {
+    daemon_start_recording_with_limits(interval_ms, DaemonRecordingFormat::Jsonl, None, None)
+}
+
+/// Start a daemon recording in `format` that stops by itself after
+/// `max_duration_ms` or once the file reaches `max_bytes`
+pub fn daemon_start_recording_with_limits(
+    interval_ms: u32,
+    format: DaemonRecordingFormat,
+    max_duration_ms: Option<u64>,
+    max_bytes: Option<u64>,
+) -> Result<DaemonRecordingInfo, String> {
+    recording_request(DaemonRequest::StartRecording { interval_ms, format, max_duration_ms, max_bytes })
+}
+
+/// Stop the active daemon recording session
+pub fn daemon_stop_recording() -> Result<DaemonRecordingInfo, String> {
+    recording_request(DaemonRequest::StopRecording)
+}
+
+/// Progress of the active recording, or the result of the last one stopped
+pub fn daemon_get_recording() -> Result<DaemonRecordingInfo, String> {
+    recording_request(DaemonRequest::GetRecording)
+}
+
+fn recording_request(request: DaemonRequest) -> Result<DaemonRecordingInfo, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(request)? {
+        DaemonResponse::Ok(data) if data.recording.is_some() => Ok(data.recording.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Get the daemon's stored sensor history between `start_ms` and `end_ms` (Unix ms)
+/// `resolution_secs` 0 = finest stored; `paths` empty = every series
+pub fn daemon_query_history(
+    start_ms: u64,
+    end_ms: u64,
+    resolution_secs: u32,
+    paths: Vec<String>,
+) -> Result<DaemonHistoryData, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::QueryHistory { start_ms, end_ms, resolution_secs, paths })? {
+        DaemonResponse::Ok(data) if data.history.is_some() => Ok(data.history.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Whether the daemon only logs its PWM writes
+pub fn daemon_get_dry_run() -> Result<bool, String> {
+    dry_run_request(DaemonRequest::GetDryRun)
+}
+
+/// Turn the daemon's dry-run mode on or off
+pub fn daemon_set_dry_run(enabled: bool) -> Result<bool, String> {
+    dry_run_request(DaemonRequest::SetDryRun { enabled })
+}
+
+fn dry_run_request(request: DaemonRequest) -> Result<bool, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(request)? {
+        DaemonResponse::Ok(data) if data.dry_run.is_some() => Ok(data.dry_run.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Whether the daemon runs in safe mode (fans under firmware control, nothing written)
+pub fn daemon_get_safe_mode() -> Result<bool, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::GetSafeMode)? {
+        DaemonResponse::Ok(data) if data.safe_mode.is_some() => Ok(data.safe_mode.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Control loop status, including sensor readings rejected as implausible
+pub fn daemon_get_control_status() -> Result<DaemonControlStatus, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::GetControlStatus)? {
+        DaemonResponse::Ok(data) if data.control_status.is_some() => Ok(data.control_status.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Get the active curve profile and the profiles available
+pub fn daemon_get_active_profile() -> Result<DaemonProfileInfo, String> {
+    profile_request(DaemonRequest::GetActiveProfile)
+}
+
+/// Switch all pairs to their curves for profile `name`
+pub fn daemon_set_active_profile(name: &str) -> Result<DaemonProfileInfo, String> {
+    profile_request(DaemonRequest::SetActiveProfile { name: name.to_string() })
+}
+
+fn profile_request(request: DaemonRequest) -> Result<DaemonProfileInfo, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(request)? {
+        DaemonResponse::Ok(data) if data.profile.is_some() => Ok(data.profile.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Get the firmware fan curve of AMD GPU card `index`
+pub fn daemon_get_gpu_fan_curve(index: u32) -> Result<DaemonGpuFanCurveInfo, String> {
+    fan_curve_request(DaemonRequest::GetGpuFanCurve { index })
+}
+
+/// Replace the firmware fan curve of AMD GPU card `index`
+/// Returns the curve as stored by the firmware
+pub fn daemon_set_gpu_fan_curve(index: u32, points: Vec<DaemonGpuFanCurvePoint>) -> Result<DaemonGpuFanCurveInfo, String> {
+    fan_curve_request(DaemonRequest::SetGpuFanCurve { index, points })
+}
+
+/// Restore the factory firmware fan curve of AMD GPU card `index`
+pub fn daemon_reset_gpu_fan_curve(index: u32) -> Result<DaemonGpuFanCurveInfo, String> {
+    fan_curve_request(DaemonRequest::ResetGpuFanCurve { index })
+}
+
+fn fan_curve_request(request: DaemonRequest) -> Result<DaemonGpuFanCurveInfo, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(request)? {
+        DaemonResponse::Ok(data) if data.gpu_fan_curve.is_some() => Ok(data.gpu_fan_curve.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Get the power limit of GPU `index`
+pub fn daemon_get_gpu_power_limit(index: u32) -> Result<DaemonGpuPowerLimitInfo, String> {
+    power_limit_request(DaemonRequest::GetGpuPowerLimit { index })
+}
+
+/// Set the power limit of GPU `index` (needs `advanced.gpu_power_limits_enabled`)
+/// Returns the limit as reported by the driver
+pub fn daemon_set_gpu_power_limit(index: u32, watts: u32) -> Result<DaemonGpuPowerLimitInfo, String> {
+    power_limit_request(DaemonRequest::SetGpuPowerLimit { index, watts })
+}
+
+/// Restore the factory power limit of GPU `index`
+pub fn daemon_reset_gpu_power_limit(index: u32) -> Result<DaemonGpuPowerLimitInfo, String> {
+    power_limit_request(DaemonRequest::ResetGpuPowerLimit { index })
+}
+
+fn power_limit_request(request: DaemonRequest) -> Result<DaemonGpuPowerLimitInfo, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(request)? {
+        DaemonResponse::Ok(data) if data.gpu_power_limit.is_some() => Ok(data.gpu_power_limit.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
 // ============================================================================
 // Rate Limit Configuration
 // ============================================================================
@@ -713,7 +1512,7 @@ pub fn daemon_get_rate_limit() -> Result<u32, String> {
 /// Returns the actual limit that was set
 pub fn daemon_set_rate_limit(limit: u32) -> Result<u32, String> {
     let mut client = DaemonClient::get_pooled()?;
-    let result = match client.request(DaemonRequest::SetRateLimit { limit })? {
+    let result = match client.request(DaemonRequest::SetRateLimit { limit, burst: None, costs: None })? {
         DaemonResponse::Ok(data) if data.rate_limit.is_some() => Ok(data.rate_limit.unwrap()),
         DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
         DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
@@ -722,6 +1521,35 @@ pub fn daemon_set_rate_limit(limit: u32) -> Result<u32, String> {
     result
 }
 
+/// Get the daemon's token-bucket configuration (refill limit, burst, per-class costs)
+pub fn daemon_get_rate_limit_config() -> Result<DaemonRateLimitConfig, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::GetRateLimit)? {
+        DaemonResponse::Ok(data) if data.rate_limit_config.is_some() => Ok(data.rate_limit_config.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
+/// Set the daemon's token-bucket configuration
+/// `burst` and `costs` keep their current values when `None`; returns the applied configuration
+pub fn daemon_set_rate_limit_config(
+    limit: u32,
+    burst: Option<u32>,
+    costs: Option<DaemonRequestCosts>,
+) -> Result<DaemonRateLimitConfig, String> {
+    let mut client = DaemonClient::get_pooled()?;
+    let result = match client.request(DaemonRequest::SetRateLimit { limit, burst, costs })? {
+        DaemonResponse::Ok(data) if data.rate_limit_config.is_some() => Ok(data.rate_limit_config.unwrap()),
+        DaemonResponse::Ok(_) => Err(crate::error::HyperfanError::IpcProtocol("Unexpected response type".to_string()).to_string()),
+        DaemonResponse::Error { message } => Err(crate::error::HyperfanError::DaemonResponse(message).to_string()),
+    };
+    client.return_to_pool();
+    result
+}
+
 /// Set both client and daemon rate limits simultaneously
 /// Returns (client_limit, daemon_limit) - the actual limits that were set
 pub fn set_rate_limits(limit: u32) -> Result<(u32, u32), String> {
@@ -0,0 +1,323 @@
+//! Config file formats
+//!
+//! Settings and curves are stored as JSON by default. TOML is offered for
+//! users who keep their fan config in a dotfiles repository: the output has
+//! a fixed key order, and rewriting an existing TOML file merges the new
+//! values into it, so comments and layout the user added survive and a
+//! change diffs as just the lines that changed.
+//!
+//! Only one file per config exists at a time (`settings.json` or
+//! `settings.toml`); writing one format removes the other.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table, Value};
+use tracing::debug;
+
+use crate::error::{HyperfanError, Result};
+
+/// On-disk format of the settings and curves files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    #[default]
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    pub const ALL: [ConfigFormat; 2] = [ConfigFormat::Json, ConfigFormat::Toml];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ConfigFormat::Json => "json",
+            ConfigFormat::Toml => "toml",
+        }
+    }
+
+    /// Format of a config file, by extension (JSON unless `.toml`)
+    pub fn of_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigFormat::Json => write!(f, "JSON"),
+            ConfigFormat::Toml => write!(f, "TOML"),
+        }
+    }
+}
+
+/// Path of config `stem` in `dir`: the existing file (the newer one if both
+/// formats exist), or the `preferred` format when there is none yet
+pub fn config_file_path(dir: &Path, stem: &str, preferred: ConfigFormat) -> PathBuf {
+    let mut existing: Vec<(PathBuf, Option<std::time::SystemTime>)> = ConfigFormat::ALL
+        .iter()
+        .map(|format| dir.join(format!("{}.{}", stem, format.extension())))
+        .filter(|path| path.exists())
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect();
+    existing.sort_by_key(|(_, modified)| *modified);
+    existing
+        .pop()
+        .map(|(path, _)| path)
+        .unwrap_or_else(|| dir.join(format!("{}.{}", stem, preferred.extension())))
+}
+
+/// Parse a config file in the format its extension names
+pub fn read_config<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| HyperfanError::FileRead { path: path.to_path_buf(), source: e })?;
+    match ConfigFormat::of_path(path) {
+        ConfigFormat::Json => Ok(serde_json::from_str(&contents)?),
+        ConfigFormat::Toml => toml::from_str(&contents)
+            .map_err(|e| HyperfanError::config(format!("Failed to parse {}: {}", path.display(), e))),
+    }
+}
+
+/// Atomically write `value` as `dir/stem.<ext>` and remove the other format's file
+///
+/// An existing TOML file is updated in place, keeping its comments.
+pub fn write_config<T: Serialize>(dir: &Path, stem: &str, format: ConfigFormat, value: &T) -> Result<PathBuf> {
+    stage_config(dir, stem, format, value)?.commit()
+}
+
+/// A config file written and synced next to its destination, not yet in place
+///
+/// Staging every file first lets several configs be replaced together: if any
+/// of them fails to serialize or write, none is touched. Dropping an
+/// uncommitted file removes it.
+#[derive(Debug)]
+pub struct StagedConfig {
+    dir: PathBuf,
+    stem: String,
+    format: ConfigFormat,
+    path: PathBuf,
+    temp_path: PathBuf,
+}
+
+/// Write `value` to a temp file beside `dir/stem.<ext>`
+pub fn stage_config<T: Serialize>(dir: &Path, stem: &str, format: ConfigFormat, value: &T) -> Result<StagedConfig> {
+    let path = dir.join(format!("{}.{}", stem, format.extension()));
+    let contents = match format {
+        ConfigFormat::Json => serde_json::to_string_pretty(value)?,
+        ConfigFormat::Toml => {
+            let existing = fs::read_to_string(&path).ok();
+            to_toml(value, existing.as_deref())?
+        }
+    };
+
+    let temp_path = path.with_extension(format!("{}.tmp", format.extension()));
+    let write_err = |e| HyperfanError::FileWrite { path: temp_path.clone(), source: e };
+    let mut file = fs::File::create(&temp_path).map_err(write_err)?;
+    file.write_all(contents.as_bytes()).map_err(write_err)?;
+    file.sync_all().map_err(write_err)?;
+    drop(file);
+
+    Ok(StagedConfig { dir: dir.to_path_buf(), stem: stem.to_string(), format, path, temp_path })
+}
+
+impl StagedConfig {
+    /// Rename the file into place and remove the other format's file
+    pub fn commit(self) -> Result<PathBuf> {
+        fs::rename(&self.temp_path, &self.path)
+            .map_err(|e| HyperfanError::FileWrite { path: self.path.clone(), source: e })?;
+
+        for other in ConfigFormat::ALL.into_iter().filter(|&f| f != self.format) {
+            let stale = self.dir.join(format!("{}.{}", self.stem, other.extension()));
+            if stale.exists() {
+                debug!("Removing {:?} after switching to {}", stale, self.format);
+                fs::remove_file(&stale).map_err(|e| HyperfanError::FileWrite { path: stale, source: e })?;
+            }
+        }
+        Ok(self.path.clone())
+    }
+}
+
+impl Drop for StagedConfig {
+    fn drop(&mut self) {
+        // Gone already once committed
+        let _ = fs::remove_file(&self.temp_path);
+    }
+}
+
+/// Serialize `value` as TOML, merged into `existing` when given
+fn to_toml<T: Serialize>(value: &T, existing: Option<&str>) -> Result<String> {
+    let fresh = toml::to_string_pretty(value)
+        .map_err(|e| HyperfanError::config(format!("Failed to serialize TOML: {}", e)))?;
+    let fresh: DocumentMut = fresh
+        .parse()
+        .map_err(|e| HyperfanError::config(format!("Failed to serialize TOML: {}", e)))?;
+
+    let Some(mut doc) = existing.and_then(|text| text.parse::<DocumentMut>().ok()) else {
+        return Ok(fresh.to_string());
+    };
+    merge_table(doc.as_table_mut(), fresh.as_table().clone());
+    Ok(doc.to_string())
+}
+
+/// Make `old` hold `new`'s keys and values, keeping `old`'s comments and order
+fn merge_table(old: &mut Table, new: Table) {
+    let removed: Vec<String> = old.iter().map(|(k, _)| k.to_string()).filter(|k| !new.contains_key(k)).collect();
+    for key in removed {
+        old.remove(&key);
+    }
+    for (key, mut item) in new {
+        match old.get_mut(&key) {
+            Some(existing) => merge_item(existing, item),
+            None => {
+                clear_positions(&mut item);
+                old.insert(&key, item);
+            }
+        }
+    }
+}
+
+fn merge_item(old: &mut Item, new: Item) {
+    match (old, new) {
+        (Item::Table(old), Item::Table(new)) => merge_table(old, new),
+        (Item::ArrayOfTables(old), Item::ArrayOfTables(new)) => {
+            let count = new.len();
+            for (i, mut table) in new.into_iter().enumerate() {
+                match old.get_mut(i) {
+                    Some(existing) => merge_table(existing, table),
+                    None => {
+                        table.set_position(None);
+                        old.push(table);
+                    }
+                }
+            }
+            while old.len() > count {
+                old.remove(old.len() - 1);
+            }
+        }
+        (Item::Value(old), Item::Value(mut new)) => {
+            if normalized(old) != normalized(&new) {
+                *new.decor_mut() = old.decor().clone();
+                *old = new;
+            }
+        }
+        (old, mut new) => {
+            clear_positions(&mut new);
+            *old = new;
+        }
+    }
+}
+
+/// Value without formatting, for comparing what it holds
+fn normalized(value: &Value) -> String {
+    fn strip(value: &mut Value) {
+        value.decor_mut().clear();
+        match value {
+            Value::Array(array) => {
+                array.iter_mut().for_each(strip);
+                array.fmt();
+            }
+            Value::InlineTable(table) => {
+                table.iter_mut().for_each(|(_, v)| strip(v));
+                table.fmt();
+            }
+            _ => {}
+        }
+    }
+    let mut value = value.clone();
+    strip(&mut value);
+    value.to_string()
+}
+
+/// Let tables from a fresh document take their place after their parent
+fn clear_positions(item: &mut Item) {
+    match item {
+        Item::Table(table) => {
+            table.set_position(None);
+            table.iter_mut().for_each(|(_, child)| clear_positions(child));
+        }
+        Item::ArrayOfTables(tables) => {
+            for table in tables.iter_mut() {
+                table.set_position(None);
+                table.iter_mut().for_each(|(_, child)| clear_positions(child));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        poll_ms: u32,
+        name: String,
+        curves: BTreeMap<String, Curve>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Curve {
+        points: Vec<(f32, f32)>,
+    }
+
+    fn config() -> Config {
+        Config {
+            poll_ms: 100,
+            name: "desk".to_string(),
+            curves: BTreeMap::from([("cpu".to_string(), Curve { points: vec![(30.0, 20.0), (80.0, 100.0)] })]),
+        }
+    }
+
+    #[test]
+    fn test_toml_keeps_comments() {
+        let original = to_toml(&config(), None).unwrap();
+        let commented = format!("# My fan setup\n{}", original.replace("poll_ms = 100", "poll_ms = 100 # fast"));
+
+        let mut changed = config();
+        changed.poll_ms = 250;
+        changed.curves.insert("gpu".to_string(), Curve { points: vec![(40.0, 30.0)] });
+        let merged = to_toml(&changed, Some(&commented)).unwrap();
+
+        assert!(merged.starts_with("# My fan setup\n"));
+        assert!(merged.contains("poll_ms = 250 # fast"));
+        assert_eq!(toml::from_str::<Config>(&merged).unwrap(), changed);
+
+        // Unchanged values keep their text
+        assert_eq!(to_toml(&changed, Some(&merged)).unwrap(), merged);
+    }
+
+    #[test]
+    fn test_write_switches_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = write_config(dir.path(), "settings", ConfigFormat::Json, &config()).unwrap();
+        assert_eq!(config_file_path(dir.path(), "settings", ConfigFormat::Toml), json);
+
+        let toml_path = write_config(dir.path(), "settings", ConfigFormat::Toml, &config()).unwrap();
+        assert!(!json.exists());
+        assert_eq!(config_file_path(dir.path(), "settings", ConfigFormat::Json), toml_path);
+        assert_eq!(read_config::<Config>(&toml_path).unwrap(), config());
+    }
+
+    #[test]
+    fn test_dropped_stage_leaves_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let staged = stage_config(dir.path(), "curves", ConfigFormat::Json, &config()).unwrap();
+        assert!(!dir.path().join("curves.json").exists());
+        drop(staged);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        let path = stage_config(dir.path(), "curves", ConfigFormat::Json, &config()).unwrap().commit().unwrap();
+        assert_eq!(read_config::<Config>(&path).unwrap(), config());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
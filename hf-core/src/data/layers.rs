@@ -0,0 +1,126 @@
+//! Layered configuration
+//!
+//! Settings and curves are read from two layers, both optional: the system
+//! layer in `/etc/hyperfan` (written by an administrator or configuration
+//! management, shared by every user and the daemon) and the user layer in
+//! `~/.config/hyperfan`, which overrides it.
+//!
+//! Settings merge key by key over the built-in defaults: tables merge
+//! recursively, anything else (numbers, strings, lists such as `active_pairs`)
+//! is replaced whole by the upper layer. While a system layer exists, saving
+//! writes only the keys whose value differs from it, so later changes to the
+//! system file still reach users who never touched those keys. An option the
+//! system layer sets can be changed by a user but not cleared.
+//!
+//! Curves merge by ID: a user curve replaces the system curve with the same
+//! ID. System curves are only written to the user file once edited, and a
+//! user cannot delete one.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use super::format::{read_config, ConfigFormat};
+use crate::constants::paths;
+use crate::error::Result;
+
+/// Existing `stem.json` or `stem.toml` in the system config directory
+pub fn system_config_path(stem: &str) -> Option<PathBuf> {
+    config_path_in(Path::new(paths::CONFIG_DIR), stem)
+}
+
+/// Existing `stem.json` or `stem.toml` in `dir`
+pub(crate) fn config_path_in(dir: &Path, stem: &str) -> Option<PathBuf> {
+    ConfigFormat::ALL
+        .iter()
+        .map(|format| dir.join(format!("{}.{}", stem, format.extension())))
+        .find(|path| path.is_file())
+}
+
+/// Parsed system layer of config `stem` (None if there is no system file)
+pub fn load_system_layer(stem: &str) -> Result<Option<Value>> {
+    system_config_path(stem).map(|path| read_config(&path)).transpose()
+}
+
+/// Merge `layer` over `base`: tables recursively, other values replaced
+pub fn merge_layer(base: &mut Value, layer: Value) {
+    match (base, layer) {
+        (Value::Object(base), Value::Object(layer)) => {
+            for (key, value) in layer {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_layer(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, layer) => *base = layer,
+    }
+}
+
+/// Keys of `merged` whose value differs from `base`, for writing an upper layer
+///
+/// `merge_layer(base, layer_overrides(base, merged))` gives back `merged`.
+pub fn layer_overrides(base: &Value, merged: &Value) -> Value {
+    let (Value::Object(base), Value::Object(merged)) = (base, merged) else {
+        return merged.clone();
+    };
+    let mut overrides = Map::new();
+    for (key, value) in merged {
+        match base.get(key) {
+            Some(existing) if existing == value => {}
+            Some(existing @ Value::Object(_)) if value.is_object() => {
+                overrides.insert(key.clone(), layer_overrides(existing, value));
+            }
+            _ => {
+                overrides.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Value::Object(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_and_overrides() {
+        let mut base = json!({
+            "general": { "poll_interval_ms": 1000, "start_minimized": false },
+            "active_pairs": [{ "id": "a" }, { "id": "b" }],
+        });
+        let system = base.clone();
+        merge_layer(&mut base, json!({
+            "general": { "poll_interval_ms": 500 },
+            "active_pairs": [{ "id": "c" }],
+            "active_profile": "Quiet",
+        }));
+        assert_eq!(base, json!({
+            "general": { "poll_interval_ms": 500, "start_minimized": false },
+            "active_pairs": [{ "id": "c" }],
+            "active_profile": "Quiet",
+        }));
+
+        // Only what differs from the lower layer is written back
+        let overrides = layer_overrides(&system, &base);
+        assert_eq!(overrides, json!({
+            "general": { "poll_interval_ms": 500 },
+            "active_pairs": [{ "id": "c" }],
+            "active_profile": "Quiet",
+        }));
+        let mut rebuilt = system.clone();
+        merge_layer(&mut rebuilt, overrides);
+        assert_eq!(rebuilt, base);
+    }
+
+    #[test]
+    fn test_system_config_path() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(config_path_in(dir.path(), "settings"), None);
+        std::fs::write(dir.path().join("settings.toml"), "").unwrap();
+        assert_eq!(config_path_in(dir.path(), "settings"), Some(dir.path().join("settings.toml")));
+    }
+}
@@ -3,7 +3,10 @@
 //! Contains all core data structures and configuration management.
 
 mod config;
+mod format;
+mod layers;
 mod persistence;
+mod template;
 mod types;
 mod validation;
 
@@ -11,16 +14,25 @@ pub use config::{
     create_default_curve,
 };
 pub use types::{
-    CurvePoint, FanMapping, FanSensor, HwmonChip, ProbeResult, PwmController,
+    CoolingDevice, CurvePoint, FanMapping, FanSensor, HwmonAlarm, HwmonChip, HwmonSensor, ProbeResult, PwmController, SensorKind,
     RawChipData, RawControllerSnapshot, RawFanReading, RawPwmReading, RawTempReading,
-    SystemSummary, TempSource, TemperatureSensor,
+    SystemSummary, TempSource, TemperatureLimits, TemperatureSensor,
 };
 
 // Re-export GPU types from hf-gpu crate
-pub use hf_gpu::{GpuDevice, GpuFan, GpuSnapshot, GpuTemperature, GpuVendor};
+pub use hf_gpu::{GpuDevice, GpuFan, GpuFanCurve, GpuFanCurvePoint, GpuPowerLimit, GpuSnapshot, GpuTemperature, GpuVendor};
+pub use format::{config_file_path, read_config, stage_config, write_config, ConfigFormat, StagedConfig};
+pub use layers::{layer_overrides, load_system_layer, merge_layer, system_config_path};
+pub(crate) use layers::config_path_in;
+pub(crate) use persistence::user_curves;
 pub use persistence::{
-    delete_curve, get_curves_path, load_curves, save_curve, save_curves,
-    update_curve_points, CurveStore, PersistedCurve,
+    copy_curve_to_pairs, delete_curve, get_curves_path, load_curves, save_curve, save_curves,
+    update_curve_points, set_curve_battery_offset, set_curve_fallback_sources, CurveCopy, CurveInterpolation, CurveOffset, CurveStore, PersistedCurve,
+};
+pub use template::{
+    export_curves_template, export_profile_template, import_template, suggest_sensor_mapping, CurveTemplate,
+    SensorMapping, SensorSuggestion, TemplateImport, TemplateMetadata, TemplateProfile, TemplateSensor,
+    TEMPLATE_FORMAT, TEMPLATE_VERSION,
 };
 pub use validation::{
     validate_curve_points, validate_fan_path, validate_file_size, validate_percentage,
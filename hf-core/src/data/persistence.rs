@@ -1,8 +1,9 @@
-//! JSON persistence for fan curves
+//! Persistence for fan curves
 //!
-//! Automatically saves and loads fan curve configurations.
+//! Automatically saves and loads fan curve configurations as JSON or TOML,
+//! over the curves of the system layer (see `layers`).
 
-use crate::error::Result;
+use crate::error::{HyperfanError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -10,6 +11,7 @@ use std::path::PathBuf;
 use tracing::{debug, info, warn};
 
 use crate::constants::paths;
+use super::format::{config_file_path, read_config, write_config, ConfigFormat};
 
 /// Check if a string is a valid UUID format (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx)
 fn is_valid_uuid(s: &str) -> bool {
@@ -50,6 +52,39 @@ pub struct PersistedCurve {
     /// Ramp down speed in percent per second (how fast fan slows down)
     #[serde(default = "default_ramp_down_speed")]
     pub ramp_down_speed: f32,
+    /// How fan speed is interpolated between points
+    #[serde(default)]
+    pub interpolation: CurveInterpolation,
+    /// Adjustment applied by the daemon while the system runs on battery
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub battery_offset: Option<CurveOffset>,
+    /// Sensors the daemon switches to, in order, when the pair's or group's
+    /// source fails or reads bogus values
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_sources: Vec<String>,
+}
+
+pub use hf_protocol::CurveInterpolation;
+
+/// Shift applied to every point of a curve (e.g. quieter on battery)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CurveOffset {
+    /// Added to each point's fan speed (negative = quieter)
+    #[serde(default)]
+    pub duty_percent: f32,
+    /// Added to each point's temperature (positive = fans ramp up later)
+    #[serde(default)]
+    pub temp_celsius: f32,
+}
+
+impl CurveOffset {
+    /// Offset points, keeping fan speeds within 0-100%
+    pub fn apply(&self, points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        points
+            .iter()
+            .map(|(temp, percent)| (temp + self.temp_celsius, (percent + self.duty_percent).clamp(0.0, 100.0)))
+            .collect()
+    }
 }
 
 fn default_hysteresis() -> f32 {
@@ -69,12 +104,21 @@ fn default_ramp_down_speed() -> f32 {
 }
 
 /// Collection of all persisted curves
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CurveStore {
     pub version: u32,
+    #[serde(serialize_with = "serialize_sorted")]
     pub curves: HashMap<String, PersistedCurve>,
 }
 
+/// Write curves ordered by ID so saves don't reorder the file
+fn serialize_sorted<S: serde::Serializer>(
+    curves: &HashMap<String, PersistedCurve>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_map(curves.iter().collect::<std::collections::BTreeMap<_, _>>())
+}
+
 impl CurveStore {
     /// Create a new empty store
     pub fn new() -> Self {
@@ -91,6 +135,14 @@ impl CurveStore {
         
         if let Some(existing) = self.curves.get(&curve.id) {
             curve.created_at = existing.created_at;
+            // Editors don't carry the offset; clear it with set_curve_battery_offset
+            if curve.battery_offset.is_none() {
+                curve.battery_offset = existing.battery_offset;
+            }
+            // Same for the fallback sources (set_curve_fallback_sources)
+            if curve.fallback_sources.is_empty() {
+                curve.fallback_sources = existing.fallback_sources.clone();
+            }
         } else {
             curve.created_at = now;
         }
@@ -125,26 +177,29 @@ impl CurveStore {
     }
 }
 
-/// Get the path to the curves JSON file
+/// Get the path to the curves file (curves.json or curves.toml)
 pub fn get_curves_path() -> PathBuf {
-    paths::user_config_dir()
-        .unwrap_or_else(|| PathBuf::from(".").join("hyperfan"))
-        .join("curves.json")
+    let dir = paths::user_config_dir().unwrap_or_else(|| PathBuf::from(".").join("hyperfan"));
+    config_file_path(&dir, "curves", configured_format())
+}
+
+/// Format chosen in settings for new config files
+fn configured_format() -> ConfigFormat {
+    crate::settings::get_cached_settings().general.config_format
 }
 
 /// Load curves from disk
 pub fn load_curves() -> Result<CurveStore> {
     let path = get_curves_path();
 
+    let system = load_system_curves()?;
+
     if !path.exists() {
-        debug!("No curves file found at {:?}, returning empty store", path);
-        return Ok(CurveStore::new());
+        debug!("No curves file found at {:?}, returning system curves only", path);
+        return Ok(system.unwrap_or_else(CurveStore::new));
     }
 
-    let contents = fs::read_to_string(&path)
-        .map_err(|e| crate::error::HyperfanError::FileRead { path: path.clone(), source: e })?;
-
-    let mut store: CurveStore = serde_json::from_str(&contents)?;
+    let mut store: CurveStore = read_config(&path)?;
 
     // MIGRATION: Ensure all curves have valid UUIDs
     // This handles legacy configs with empty IDs or old-style "curve_timestamp" IDs
@@ -165,46 +220,52 @@ pub fn load_curves() -> Result<CurveStore> {
         for curve in curves {
             store.curves.insert(curve.id.clone(), curve);
         }
-        // Save migrated store
-        if let Ok(json) = serde_json::to_string_pretty(&store) {
-            let _ = fs::write(&path, json);
+        // Save migrated store in the format it was read from
+        if let Some(dir) = path.parent() {
+            let _ = write_config(dir, "curves", ConfigFormat::of_path(&path), &store);
         }
         info!("Migrated curve IDs to UUIDs");
     }
 
     info!("Loaded {} curves from {:?}", store.len(), path);
+
+    // User curves replace system curves with the same ID
+    if let Some(mut system) = system {
+        system.curves.extend(store.curves);
+        store.curves = system.curves;
+    }
     Ok(store)
 }
 
-/// Save curves to disk
-pub fn save_curves(store: &CurveStore) -> Result<()> {
-    let path = get_curves_path();
+/// Curves of the system layer (`/etc/hyperfan/curves.*`), if there is one
+fn load_system_curves() -> Result<Option<CurveStore>> {
+    super::layers::system_config_path("curves").map(|path| read_config(&path)).transpose()
+}
 
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+/// `store` without the system curves it holds unedited, for the user file
+pub(crate) fn user_curves(store: &CurveStore) -> Result<CurveStore> {
+    let mut user = store.clone();
+    if let Some(system) = load_system_curves()? {
+        user.curves.retain(|id, curve| {
+            let unedited = system.curves.get(id).is_some_and(|system_curve| {
+                matches!(
+                    (serde_json::to_value(system_curve), serde_json::to_value(curve)),
+                    (Ok(a), Ok(b)) if a == b
+                )
+            });
+            !unedited
+        });
     }
+    Ok(user)
+}
 
-    let json = serde_json::to_string_pretty(store)?;
+/// Save curves to disk in the configured format
+/// Uses atomic write (temp file + rename)
+pub fn save_curves(store: &CurveStore) -> Result<()> {
+    let dir = paths::user_config_dir().unwrap_or_else(|| PathBuf::from(".").join("hyperfan"));
+    fs::create_dir_all(&dir)?;
 
-    // CRITICAL: Atomic write - write to temp file then rename
-    use std::io::Write;
-    let temp_path = path.with_extension("json.tmp");
-    
-    let mut file = fs::File::create(&temp_path)
-        .map_err(|e| crate::error::HyperfanError::FileWrite { path: temp_path.clone(), source: e })?;
-    
-    file.write_all(json.as_bytes())
-        .map_err(|e| crate::error::HyperfanError::FileWrite { path: temp_path.clone(), source: e })?;
-    
-    file.sync_all()
-        .map_err(|e| crate::error::HyperfanError::FileWrite { path: temp_path.clone(), source: e })?;
-    
-    drop(file);
-    
-    // Atomic rename
-    fs::rename(&temp_path, &path)
-        .map_err(|e| crate::error::HyperfanError::FileWrite { path: path.clone(), source: e })?;
+    let path = write_config(&dir, "curves", configured_format(), &user_curves(store)?)?;
 
     debug!("Saved {} curves to {:?}", store.len(), path);
     Ok(())
@@ -249,6 +310,116 @@ pub fn update_curve_points(id: &str, points: Vec<(f32, f32)>) -> Result<bool> {
     }
 }
 
+/// Set or clear a curve's on-battery offset
+pub fn set_curve_battery_offset(id: &str, offset: Option<CurveOffset>) -> Result<bool> {
+    let mut store = load_curves()?;
+
+    if let Some(curve) = store.curves.get_mut(id) {
+        curve.battery_offset = offset;
+        curve.updated_at = current_timestamp();
+        save_curves(&store)?;
+        debug!("Updated battery offset for curve {}: {:?}", id, offset);
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Set (or with an empty list, clear) a curve's fallback temperature sources
+pub fn set_curve_fallback_sources(id: &str, sources: Vec<String>) -> Result<bool> {
+    let mut store = load_curves()?;
+
+    if let Some(curve) = store.curves.get_mut(id) {
+        debug!("Updated fallback sources for curve {}: {:?}", id, sources);
+        curve.fallback_sources = sources;
+        curve.updated_at = current_timestamp();
+        save_curves(&store)?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Result of [`copy_curve_to_pairs`]
+#[derive(Debug, Clone, Default)]
+pub struct CurveCopy {
+    /// Curve the pairs now use: the original, or its offset copy
+    pub curve_id: String,
+    pub curve_name: String,
+    /// Whether an offset copy was saved
+    pub created: bool,
+    /// Names of the pairs that were assigned the curve
+    pub assigned_pairs: Vec<String>,
+}
+
+/// Use one curve for several fan-curve pairs
+///
+/// With a non-zero `duty_offset` a copy of the curve with every point shifted
+/// by that many percent is saved first and assigned instead, so fans that need
+/// a little more or less airflow can still share one shape. With a `profile`
+/// the pairs only use the curve while that profile is active.
+pub fn copy_curve_to_pairs(
+    curve_id: &str,
+    pair_ids: &[String],
+    duty_offset: f32,
+    profile: Option<&str>,
+) -> Result<CurveCopy> {
+    if pair_ids.is_empty() {
+        return Err(HyperfanError::config("no pairs selected"));
+    }
+    if !(-100.0..=100.0).contains(&duty_offset) {
+        return Err(HyperfanError::config(format!("offset must be between -100 and 100%, got {}", duty_offset)));
+    }
+    if let Some(profile) = profile {
+        hf_protocol::validate_profile_name(profile).map_err(HyperfanError::config)?;
+    }
+
+    let pairs = crate::settings::get_active_pairs()?;
+    if let Some(missing) = pair_ids.iter().find(|id| !pairs.iter().any(|p| &p.id == *id)) {
+        return Err(HyperfanError::config(format!("Pair not found: {}", missing)));
+    }
+
+    let mut store = load_curves()?;
+    let original = store.get(curve_id).cloned().ok_or_else(|| HyperfanError::config(format!("curve not found: {}", curve_id)))?;
+    let mut result = CurveCopy { curve_id: original.id.clone(), curve_name: original.name.clone(), ..CurveCopy::default() };
+    if duty_offset.abs() >= crate::constants::curve::FLOAT_EPSILON {
+        let copy = offset_copy(&original, duty_offset);
+        result.curve_id = copy.id.clone();
+        result.curve_name = copy.name.clone();
+        result.created = true;
+        store.upsert(copy);
+        save_curves(&store)?;
+    }
+
+    crate::settings::update_setting(|settings| {
+        for pair in settings.active_pairs.iter_mut().filter(|p| pair_ids.contains(&p.id)) {
+            match profile {
+                Some(profile) => {
+                    pair.profile_curves.insert(profile.to_string(), result.curve_id.clone());
+                }
+                None => pair.curve_id = result.curve_id.clone(),
+            }
+            result.assigned_pairs.push(pair.name.clone());
+        }
+    })?;
+
+    info!("Assigned curve {} to {} pairs", result.curve_id, result.assigned_pairs.len());
+    Ok(result)
+}
+
+/// New curve with every point of `curve` shifted by `duty_offset` percent
+fn offset_copy(curve: &PersistedCurve, duty_offset: f32) -> PersistedCurve {
+    let offset = CurveOffset { duty_percent: duty_offset, temp_celsius: 0.0 };
+    PersistedCurve {
+        id: crate::settings::generate_guid(),
+        name: format!("{} ({:+.0}%)", curve.name, duty_offset),
+        points: offset.apply(&curve.points),
+        created_at: 0,
+        updated_at: 0,
+        ..curve.clone()
+    }
+}
+
 fn current_timestamp() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -260,6 +431,27 @@ fn current_timestamp() -> u64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_battery_offset() {
+        let offset = CurveOffset { duty_percent: -10.0, temp_celsius: 5.0 };
+        let points = offset.apply(&[(30.0, 5.0), (80.0, 100.0)]);
+        assert_eq!(points, vec![(35.0, 0.0), (85.0, 90.0)]);
+    }
+
+    #[test]
+    fn test_offset_copy() {
+        let curve: PersistedCurve = serde_json::from_value(serde_json::json!({
+            "id": "case", "name": "Case", "temp_source_path": "/test", "temp_source_label": "CPU",
+            "points": [[30.0, 95.0], [80.0, 100.0]], "interpolation": "monotone",
+        }))
+        .unwrap();
+        let copy = offset_copy(&curve, 10.0);
+        assert_ne!(copy.id, curve.id);
+        assert_eq!(copy.name, "Case (+10%)");
+        assert_eq!(copy.points, vec![(30.0, 100.0), (80.0, 100.0)]);
+        assert_eq!(copy.interpolation, CurveInterpolation::Monotone);
+    }
+
     #[test]
     fn test_curve_store_upsert() {
         let mut store = CurveStore::new();
@@ -272,6 +464,13 @@ mod tests {
             points: vec![(30.0, 20.0), (80.0, 100.0)],
             created_at: 0,
             updated_at: 0,
+            hysteresis: default_hysteresis(),
+            delay_ms: default_delay_ms(),
+            ramp_up_speed: default_ramp_up_speed(),
+            ramp_down_speed: default_ramp_down_speed(),
+            interpolation: CurveInterpolation::default(),
+            battery_offset: None,
+            fallback_sources: Vec::new(),
         };
         
         store.upsert(curve);
@@ -291,6 +490,13 @@ mod tests {
             points: vec![],
             created_at: 0,
             updated_at: 0,
+            hysteresis: default_hysteresis(),
+            delay_ms: default_delay_ms(),
+            ramp_up_speed: default_ramp_up_speed(),
+            ramp_down_speed: default_ramp_down_speed(),
+            interpolation: CurveInterpolation::default(),
+            battery_offset: None,
+            fallback_sources: Vec::new(),
         };
         
         store.upsert(curve);
@@ -0,0 +1,451 @@
+//! Shareable curve templates
+//!
+//! A template is a JSON file holding one or more curves, optionally with the
+//! curve each pair uses in a profile, plus metadata (author, hardware, notes)
+//! so others can judge whether it suits their system.
+//!
+//! Sensor paths differ between machines, so a template also describes each
+//! temperature source its curves use (chip, channel, label); curves without
+//! a source of their own (the pair supplies it) need no mapping.
+//! [`suggest_sensor_mapping`] proposes a local sensor for every source and the
+//! caller confirms or changes the choice before [`import_template`] writes
+//! anything.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use tracing::{debug, info};
+
+use crate::data::{validate_curve_points, validate_file_size, CurvePoint, HwmonChip, PersistedCurve};
+use crate::error::{HyperfanError, Result};
+
+/// `format` field of every template file
+pub const TEMPLATE_FORMAT: &str = "hyperfan-curve-template";
+
+/// Newest template version this build reads and the one it writes
+pub const TEMPLATE_VERSION: u32 = 1;
+
+/// Who made a template and for what
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TemplateMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// System the template was made on (board name by default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hardware: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Unix time in seconds
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+/// Temperature source a template curve reads, as found on the exporting system
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateSensor {
+    pub path: String,
+    pub label: String,
+    /// hwmon chip name (e.g. `k10temp`), if the sensor was found
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chip: Option<String>,
+    /// Channel name (e.g. `temp1`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+}
+
+/// Curve assignments of one profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateProfile {
+    pub name: String,
+    /// Pair name -> template curve ID
+    pub pairs: BTreeMap<String, String>,
+}
+
+/// Contents of a template file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurveTemplate {
+    pub format: String,
+    pub version: u32,
+    #[serde(default)]
+    pub metadata: TemplateMetadata,
+    pub curves: Vec<PersistedCurve>,
+    #[serde(default)]
+    pub sensors: Vec<TemplateSensor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<TemplateProfile>,
+}
+
+impl CurveTemplate {
+    /// Template of `curves`, describing their sensors from `chips`
+    pub fn new(curves: Vec<PersistedCurve>, metadata: TemplateMetadata, chips: &[HwmonChip]) -> Self {
+        let mut sensors: Vec<TemplateSensor> = Vec::new();
+        for curve in &curves {
+            if curve.temp_source_path.is_empty() || sensors.iter().any(|s| s.path == curve.temp_source_path) {
+                continue;
+            }
+            let found = chips.iter().find_map(|chip| {
+                chip.temperatures
+                    .iter()
+                    .find(|t| t.input_path.to_string_lossy() == curve.temp_source_path)
+                    .map(|t| (chip.name.clone(), t.name.clone()))
+            });
+            sensors.push(TemplateSensor {
+                path: curve.temp_source_path.clone(),
+                label: curve.temp_source_label.clone(),
+                chip: found.as_ref().map(|(chip, _)| chip.clone()),
+                channel: found.map(|(_, channel)| channel),
+            });
+        }
+
+        Self {
+            format: TEMPLATE_FORMAT.to_string(),
+            version: TEMPLATE_VERSION,
+            metadata,
+            curves,
+            sensors,
+            profile: None,
+        }
+    }
+
+    /// Read and validate a template file
+    pub fn load(path: &Path) -> Result<Self> {
+        validate_file_size(path)?;
+        let contents = fs::read_to_string(path)
+            .map_err(|e| HyperfanError::FileRead { path: path.to_path_buf(), source: e })?;
+        let template: Self = serde_json::from_str(&contents)?;
+        template.validate()?;
+        debug!("Loaded template with {} curves from {:?}", template.curves.len(), path);
+        Ok(template)
+    }
+
+    /// Write the template as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).map_err(|e| HyperfanError::FileWrite { path: path.to_path_buf(), source: e })?;
+        info!("Exported {} curves to {:?}", self.curves.len(), path);
+        Ok(())
+    }
+
+    /// Check the format, every curve and the profile's curve references
+    pub fn validate(&self) -> Result<()> {
+        if self.format != TEMPLATE_FORMAT {
+            return Err(HyperfanError::config("not a Hyperfan curve template"));
+        }
+        if self.version == 0 || self.version > TEMPLATE_VERSION {
+            return Err(HyperfanError::config(format!(
+                "template version {} is not supported (newest: {})",
+                self.version, TEMPLATE_VERSION
+            )));
+        }
+        if self.curves.is_empty() {
+            return Err(HyperfanError::config("template contains no curves"));
+        }
+
+        for curve in &self.curves {
+            if curve.name.trim().is_empty() {
+                return Err(HyperfanError::config("template curve has no name"));
+            }
+            let points: Vec<CurvePoint> = curve
+                .points
+                .iter()
+                .map(|&(temperature, fan_percent)| CurvePoint { temperature, fan_percent })
+                .collect();
+            validate_curve_points(&points)
+                .map_err(|e| HyperfanError::config(format!("curve '{}': {}", curve.name, e)))?;
+        }
+
+        if let Some(profile) = &self.profile {
+            hf_protocol::validate_profile_name(&profile.name).map_err(HyperfanError::config)?;
+            if let Some(id) = profile.pairs.values().find(|id| !self.curves.iter().any(|c| &c.id == *id)) {
+                return Err(HyperfanError::config(format!("profile refers to unknown curve {}", id)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sensors the curves read; older files without descriptions fall back to the curves
+    pub fn sensor_sources(&self) -> Vec<TemplateSensor> {
+        let mut sources = self.sensors.clone();
+        for curve in self.curves.iter().filter(|c| !c.temp_source_path.is_empty()) {
+            if !sources.iter().any(|s| s.path == curve.temp_source_path) {
+                sources.push(TemplateSensor {
+                    path: curve.temp_source_path.clone(),
+                    label: curve.temp_source_label.clone(),
+                    chip: None,
+                    channel: None,
+                });
+            }
+        }
+        sources
+    }
+}
+
+/// Template sensor path -> local sensor path
+pub type SensorMapping = HashMap<String, String>;
+
+/// Proposed local sensor for one template sensor
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorSuggestion {
+    pub source: TemplateSensor,
+    pub suggested_path: Option<String>,
+    /// Same chip and channel (or, without those, the same path) exist here
+    pub exact: bool,
+}
+
+/// Propose a sensor in `chips` for every source in `template`
+///
+/// Prefers the same chip and channel, then the same chip and label, then
+/// the same label on any chip, then the same path.
+pub fn suggest_sensor_mapping(template: &CurveTemplate, chips: &[HwmonChip]) -> Vec<SensorSuggestion> {
+    let local: Vec<(&HwmonChip, &crate::data::TemperatureSensor)> =
+        chips.iter().flat_map(|chip| chip.temperatures.iter().map(move |t| (chip, t))).collect();
+    let label_matches = |source: &TemplateSensor, sensor: &crate::data::TemperatureSensor| {
+        sensor.label.as_deref().is_some_and(|l| !l.is_empty() && source.label.to_lowercase().contains(&l.to_lowercase()))
+    };
+
+    template
+        .sensor_sources()
+        .into_iter()
+        .map(|source| {
+            let same_chip = |chip: &HwmonChip| source.chip.as_deref() == Some(chip.name.as_str());
+            let exact = local
+                .iter()
+                .find(|(chip, t)| same_chip(chip) && source.channel.as_deref() == Some(t.name.as_str()))
+                .or_else(|| {
+                    local
+                        .iter()
+                        .filter(|_| source.chip.is_none())
+                        .find(|(_, t)| t.input_path.to_string_lossy() == source.path)
+                });
+            let close = exact.is_none().then(|| {
+                local
+                    .iter()
+                    .find(|(chip, t)| same_chip(chip) && label_matches(&source, t))
+                    .or_else(|| local.iter().find(|(_, t)| label_matches(&source, t)))
+                    .or_else(|| local.iter().find(|(_, t)| t.input_path.to_string_lossy() == source.path))
+            });
+
+            SensorSuggestion {
+                suggested_path: exact
+                    .or(close.flatten())
+                    .map(|(_, t)| t.input_path.to_string_lossy().to_string()),
+                exact: exact.is_some(),
+                source,
+            }
+        })
+        .collect()
+}
+
+/// What [`import_template`] added
+#[derive(Debug, Clone, Default)]
+pub struct TemplateImport {
+    /// IDs of the new curves, in template order
+    pub curve_ids: Vec<String>,
+    /// Pairs whose profile curve was set
+    pub assigned_pairs: Vec<String>,
+    /// Pair names in the template's profile with no pair of that name here
+    pub unmatched_pairs: Vec<String>,
+}
+
+/// Add the template's curves (and profile assignments) with sensors remapped
+///
+/// Every sensor in the template needs an entry in `mapping`. Curves get new
+/// IDs; names already in use get an " (imported)" suffix. Profile curves are
+/// assigned to local pairs with the same name when `apply_profile` is set.
+pub fn import_template(template: &CurveTemplate, mapping: &SensorMapping, apply_profile: bool) -> Result<TemplateImport> {
+    template.validate()?;
+    if let Some(source) = template.sensor_sources().into_iter().find(|s| !mapping.contains_key(&s.path)) {
+        return Err(HyperfanError::config(format!("no local sensor chosen for '{}' ({})", source.label, source.path)));
+    }
+
+    let mut store = crate::data::load_curves()?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut result = TemplateImport::default();
+    let mut new_ids: HashMap<&str, String> = HashMap::new();
+    for curve in &template.curves {
+        let mut imported = curve.clone();
+        imported.id = crate::settings::generate_guid();
+        if let Some(local) = mapping.get(&curve.temp_source_path) {
+            imported.temp_source_path = local.clone();
+        }
+        for source in &mut imported.fallback_sources {
+            if let Some(local) = mapping.get(source) {
+                *source = local.clone();
+            }
+        }
+        if store.all().iter().any(|c| c.name == imported.name) {
+            imported.name = format!("{} (imported)", imported.name);
+        }
+        imported.created_at = now;
+        imported.updated_at = now;
+        new_ids.insert(curve.id.as_str(), imported.id.clone());
+        result.curve_ids.push(imported.id.clone());
+        store.upsert(imported);
+    }
+    crate::data::save_curves(&store)?;
+
+    if let Some(profile) = template.profile.as_ref().filter(|_| apply_profile) {
+        let pairs = crate::settings::get_active_pairs()?;
+        for (pair_name, curve_id) in &profile.pairs {
+            match pairs.iter().find(|p| p.name.eq_ignore_ascii_case(pair_name)) {
+                Some(pair) => {
+                    crate::settings::set_pair_profile_curve(&pair.id, &profile.name, Some(&new_ids[curve_id.as_str()]))?;
+                    result.assigned_pairs.push(pair.name.clone());
+                }
+                None => result.unmatched_pairs.push(pair_name.clone()),
+            }
+        }
+    }
+
+    info!(
+        "Imported {} curves, assigned {} pairs from template",
+        result.curve_ids.len(),
+        result.assigned_pairs.len()
+    );
+    Ok(result)
+}
+
+/// Template of the curves with `curve_ids` (all curves when empty)
+pub fn export_curves_template(curve_ids: &[String], metadata: TemplateMetadata) -> Result<CurveTemplate> {
+    let store = crate::data::load_curves()?;
+    let curves: Vec<PersistedCurve> = if curve_ids.is_empty() {
+        store.all().into_iter().cloned().collect()
+    } else {
+        curve_ids
+            .iter()
+            .map(|id| store.get(id).cloned().ok_or_else(|| HyperfanError::config(format!("curve not found: {}", id))))
+            .collect::<Result<_>>()?
+    };
+    if curves.is_empty() {
+        return Err(HyperfanError::config("no curves to export"));
+    }
+    let chips = crate::hw::enumerate_hwmon_chips().unwrap_or_default();
+    Ok(CurveTemplate::new(curves, with_defaults(metadata), &chips))
+}
+
+/// Template of every curve the pairs use in `profile`, with the assignments
+pub fn export_profile_template(profile: &str, metadata: TemplateMetadata) -> Result<CurveTemplate> {
+    hf_protocol::validate_profile_name(profile).map_err(HyperfanError::config)?;
+    let pairs = crate::settings::get_active_pairs()?;
+    let mut assignments = BTreeMap::new();
+    let mut curve_ids: Vec<String> = Vec::new();
+    for pair in &pairs {
+        let curve_id = pair.curve_for_profile(profile).to_string();
+        if !curve_ids.contains(&curve_id) {
+            curve_ids.push(curve_id.clone());
+        }
+        assignments.insert(pair.name.clone(), curve_id);
+    }
+    if assignments.is_empty() {
+        return Err(HyperfanError::config("no fan-curve pairs to export"));
+    }
+
+    let mut template = export_curves_template(&curve_ids, metadata)?;
+    template.profile = Some(TemplateProfile { name: profile.to_string(), pairs: assignments });
+    Ok(template)
+}
+
+/// Fill in the creation time and, if unset, the board name
+fn with_defaults(mut metadata: TemplateMetadata) -> TemplateMetadata {
+    if metadata.created_at == 0 {
+        metadata.created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+    }
+    if metadata.hardware.is_none() {
+        metadata.hardware = crate::system::get_system_summary()
+            .ok()
+            .map(|s| s.motherboard_name)
+            .filter(|name| !name.is_empty());
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{TemperatureLimits, TemperatureSensor};
+    use std::path::PathBuf;
+
+    fn curve(id: &str, path: &str, label: &str, points: Vec<(f32, f32)>) -> PersistedCurve {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "name": id, "temp_source_path": path, "temp_source_label": label, "points": points,
+        }))
+        .unwrap()
+    }
+
+    fn chip(name: &str, hwmon: &str, sensors: &[(&str, &str)]) -> HwmonChip {
+        HwmonChip {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/sys/class/hwmon/{}", hwmon)),
+            temperatures: sensors
+                .iter()
+                .map(|(channel, label)| TemperatureSensor {
+                    name: channel.to_string(),
+                    input_path: PathBuf::from(format!("/sys/class/hwmon/{}/{}_input", hwmon, channel)),
+                    label: Some(label.to_string()),
+                    current_temp: None,
+                    limits: TemperatureLimits::default(),
+                })
+                .collect(),
+            fans: Vec::new(),
+            pwms: Vec::new(),
+            sensors: Vec::new(),
+            alarms: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_template_validation() {
+        let mut template = CurveTemplate::new(
+            vec![curve("cpu", "/sys/class/hwmon/hwmon1/temp1_input", "CPU Tctl", vec![(30.0, 20.0), (80.0, 100.0)])],
+            TemplateMetadata::default(),
+            &[],
+        );
+        assert!(template.validate().is_ok());
+
+        template.profile = Some(TemplateProfile {
+            name: "quiet".to_string(),
+            pairs: BTreeMap::from([("CPU".to_string(), "missing".to_string())]),
+        });
+        assert!(template.validate().is_err());
+        template.profile = None;
+
+        template.curves[0].points = vec![(80.0, 100.0), (30.0, 20.0)];
+        assert!(template.validate().is_err());
+
+        template.curves[0].points = vec![(30.0, 20.0)];
+        template.version = TEMPLATE_VERSION + 1;
+        assert!(template.validate().is_err());
+    }
+
+    #[test]
+    fn test_sensor_suggestions() {
+        let exporter = [chip("k10temp", "hwmon1", &[("temp1", "Tctl")]), chip("nvme", "hwmon2", &[("temp1", "Composite")])];
+        let template = CurveTemplate::new(
+            vec![
+                curve("cpu", "/sys/class/hwmon/hwmon1/temp1_input", "k10temp Tctl", vec![(30.0, 20.0)]),
+                curve("ssd", "/sys/class/hwmon/hwmon2/temp1_input", "nvme Composite", vec![(30.0, 20.0)]),
+                curve("gpu", "/sys/class/hwmon/hwmon3/temp1_input", "amdgpu edge", vec![(30.0, 20.0)]),
+            ],
+            TemplateMetadata::default(),
+            &exporter,
+        );
+        assert_eq!(template.sensors[0].chip.as_deref(), Some("k10temp"));
+
+        // Numbering differs on the importing system
+        let local = [chip("nvme", "hwmon0", &[("temp1", "Composite")]), chip("k10temp", "hwmon4", &[("temp1", "Tctl")])];
+        let suggestions = suggest_sensor_mapping(&template, &local);
+        assert_eq!(suggestions[0].suggested_path.as_deref(), Some("/sys/class/hwmon/hwmon4/temp1_input"));
+        assert!(suggestions[0].exact);
+        assert_eq!(suggestions[1].suggested_path.as_deref(), Some("/sys/class/hwmon/hwmon0/temp1_input"));
+        assert_eq!(suggestions[2].suggested_path, None);
+        assert!(!suggestions[2].exact);
+    }
+}
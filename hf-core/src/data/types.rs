@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::constants::temperature;
+
 /// System information summary
 #[derive(Debug, Serialize)]
 pub struct SystemSummary {
@@ -25,6 +27,106 @@ pub struct HwmonChip {
     pub temperatures: Vec<TemperatureSensor>,
     pub fans: Vec<FanSensor>,
     pub pwms: Vec<PwmController>,
+    /// Voltage, current, power and humidity channels
+    #[serde(default)]
+    pub sensors: Vec<HwmonSensor>,
+    /// `fanN_alarm`, `tempN_alarm` and `intrusionN_alarm` flags
+    #[serde(default)]
+    pub alarms: Vec<HwmonAlarm>,
+}
+
+/// Type of a non-temperature, non-fan hwmon channel
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SensorKind {
+    /// `inN_input` (millivolts)
+    Voltage,
+    /// `currN_input` (milliamps)
+    Current,
+    /// `powerN_input` / `powerN_average` (microwatts)
+    Power,
+    /// `humidityN_input` (milli-percent relative humidity)
+    Humidity,
+}
+
+impl SensorKind {
+    pub const ALL: [SensorKind; 4] = [SensorKind::Voltage, SensorKind::Current, SensorKind::Power, SensorKind::Humidity];
+
+    /// hwmon file prefix (`in`, `curr`, `power`, `humidity`)
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            SensorKind::Voltage => "in",
+            SensorKind::Current => "curr",
+            SensorKind::Power => "power",
+            SensorKind::Humidity => "humidity",
+        }
+    }
+
+    /// Display unit of the scaled value
+    pub fn unit(&self) -> &'static str {
+        match self {
+            SensorKind::Voltage => "V",
+            SensorKind::Current => "A",
+            SensorKind::Power => "W",
+            SensorKind::Humidity => "%RH",
+        }
+    }
+
+    /// Divide the raw sysfs value by this to get `unit()`
+    pub fn divisor(&self) -> f32 {
+        use crate::constants::sensor;
+        match self {
+            SensorKind::Voltage => sensor::MILLIVOLTS_PER_VOLT,
+            SensorKind::Current => sensor::MILLIAMPS_PER_AMP,
+            SensorKind::Power => sensor::MICROWATTS_PER_WATT,
+            SensorKind::Humidity => sensor::MILLIPERCENT_PER_PERCENT,
+        }
+    }
+}
+
+impl std::fmt::Display for SensorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SensorKind::Voltage => write!(f, "Voltage"),
+            SensorKind::Current => write!(f, "Current"),
+            SensorKind::Power => write!(f, "Power"),
+            SensorKind::Humidity => write!(f, "Humidity"),
+        }
+    }
+}
+
+/// Voltage/current/power/humidity sensor data (value already scaled to `kind.unit()`)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HwmonSensor {
+    pub name: String,
+    pub kind: SensorKind,
+    pub input_path: PathBuf,
+    pub label: Option<String>,
+    pub current_value: Option<f32>,
+}
+
+/// Alarm flag of an hwmon channel
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HwmonAlarm {
+    /// Channel the alarm belongs to ("fan2", "temp1", "intrusion0")
+    pub name: String,
+    pub kind: hf_protocol::AlarmKind,
+    pub alarm_path: PathBuf,
+    pub active: bool,
+    /// Chip beeps on this alarm (`<name>_beep`); None without a beep file
+    pub beep: Option<bool>,
+}
+
+impl From<&HwmonAlarm> for hf_protocol::HwAlarm {
+    fn from(alarm: &HwmonAlarm) -> Self {
+        Self {
+            name: alarm.name.clone(),
+            kind: alarm.kind,
+            path: alarm.alarm_path.to_string_lossy().to_string(),
+            active: alarm.active,
+            beep: alarm.beep,
+        }
+    }
 }
 
 /// Temperature sensor data
@@ -34,6 +136,53 @@ pub struct TemperatureSensor {
     pub input_path: PathBuf,
     pub label: Option<String>,
     pub current_temp: Option<f32>,
+    /// Driver-reported thresholds, empty when the driver has none
+    #[serde(default)]
+    pub limits: TemperatureLimits,
+}
+
+/// Thresholds a driver reports for a temperature channel
+/// (hwmon `tempN_max` / `tempN_crit` / `tempN_crit_alarm`, thermal zone trip points)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct TemperatureLimits {
+    /// High threshold in °C
+    pub max: Option<f32>,
+    /// Critical threshold in °C
+    pub crit: Option<f32>,
+    /// The hardware has latched its critical alarm
+    #[serde(default)]
+    pub crit_alarm: bool,
+}
+
+impl TemperatureLimits {
+    pub fn is_empty(&self) -> bool {
+        self.max.is_none() && self.crit.is_none() && !self.crit_alarm
+    }
+
+    /// Reading is at or above the high threshold
+    pub fn exceeds_max(&self, temp: f32) -> bool {
+        self.max.is_some_and(|max| temp >= max)
+    }
+
+    /// Reading is at or above the critical threshold, or the alarm is raised
+    pub fn exceeds_crit(&self, temp: f32) -> bool {
+        self.crit_alarm || self.crit.is_some_and(|crit| temp >= crit)
+    }
+
+    /// Temperature at which the daemon forces fans to full speed
+    ///
+    /// A margin below `crit` (the kernel or firmware may shut down at crit
+    /// itself), else `max`, else [`temperature::CRITICAL_THRESHOLD`]. Never
+    /// below [`temperature::HIGH_THRESHOLD`] so a low user-set alarm limit
+    /// does not pin fans at full speed.
+    pub fn failsafe_threshold(&self) -> f32 {
+        let threshold = match (self.crit, self.max) {
+            (Some(crit), _) => crit - temperature::FAILSAFE_MARGIN,
+            (None, Some(max)) => max,
+            (None, None) => temperature::CRITICAL_THRESHOLD,
+        };
+        threshold.max(temperature::HIGH_THRESHOLD)
+    }
 }
 
 /// Fan sensor data
@@ -54,6 +203,27 @@ pub struct PwmController {
     pub label: Option<String>,
     pub current_value: Option<u8>,
     pub current_percent: Option<f32>,
+    /// Set when the controller is a thermal cooling device rather than a
+    /// `pwmN` file; `pwm_path` is then its `cur_state` and has no enable file
+    #[serde(default)]
+    pub cooling_device: Option<CoolingDevice>,
+    /// Output drives its fan by voltage (`pwmN_mode` = 0), as 3-pin fans need
+    #[serde(default)]
+    pub dc_mode: bool,
+}
+
+/// Thermal cooling device driving a fan (devicetree `pwm-fan` / `gpio-fan`)
+///
+/// Controlled through `/sys/class/thermal/cooling_deviceN/cur_state`
+/// (0..=`max_state`); reads and writes are scaled to the usual 0-255 PWM range.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CoolingDevice {
+    /// Directory name ("cooling_device0")
+    pub name: String,
+    /// Cooling device type ("pwm-fan", "gpio-fan")
+    pub device_type: String,
+    /// Highest `cur_state` value
+    pub max_state: u32,
 }
 
 /// Mapping between a fan and its PWM controller
@@ -12,7 +12,8 @@
 
 use std::path::{Path, PathBuf};
 
-use crate::constants::{limits, pwm};
+use crate::constants::{limits, pwm, thermal_zone};
+use crate::hw::is_cooling_device_path;
 use crate::error::{HyperfanError, Result};
 
 /// Validates that a PWM value is within the valid range (0-255)
@@ -40,7 +41,7 @@ pub fn validate_pwm_path(path: &Path) -> Result<PathBuf> {
         .and_then(|n| n.to_str())
         .ok_or_else(|| HyperfanError::invalid_path(&canonical, "invalid filename"))?;
 
-    if !filename.starts_with("pwm") {
+    if !filename.starts_with("pwm") && !is_cooling_device_path(&canonical) {
         return Err(HyperfanError::invalid_path(
             &canonical,
             "not a PWM control file",
@@ -59,7 +60,14 @@ pub fn validate_temp_path(path: &Path) -> Result<PathBuf> {
         .and_then(|n| n.to_str())
         .ok_or_else(|| HyperfanError::invalid_path(&canonical, "invalid filename"))?;
 
-    if !filename.starts_with("temp") || !filename.contains("_input") {
+    let is_thermal_zone = filename == thermal_zone::TEMP_FILE
+        && canonical
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("thermal_zone"));
+
+    if !is_thermal_zone && (!filename.starts_with("temp") || !filename.contains("_input")) {
         return Err(HyperfanError::invalid_path(
             &canonical,
             "not a temperature sensor file",
@@ -0,0 +1,440 @@
+//! Setup diagnostics
+//!
+//! [`run_diagnostics`] checks the things fan control depends on, in the order
+//! they matter: the daemon answering on its socket, its service starting at
+//! boot, a driver exposing fan outputs, those outputs being writable, NVIDIA
+//! fan control being possible, no other program driving the same fans, and
+//! settings and curves that load and refer to each other consistently.
+//! Each check carries a hint, and a [`DiagnosticFix`] where the fix is a
+//! service command that is safe to run with one click. Driver and X server
+//! configuration are left to the user: the hint names the command instead.
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::competing_software::detect_competing_software;
+use crate::data::{load_curves, validate_curve_points, CurvePoint, CurveStore};
+use crate::settings::{load_settings, AppSettings};
+use crate::service::{self, InitSystem};
+
+/// Outcome of a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticStatus {
+    Ok,
+    Warning,
+    Error,
+    /// Does not apply to this machine
+    Skipped,
+}
+
+/// One-click fix for a failed check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticFix {
+    InstallService,
+    StartService,
+    EnableService,
+    /// Disable the services of these conflicting programs (by unit name)
+    DisableConflictingSoftware(Vec<String>),
+}
+
+impl DiagnosticFix {
+    /// Button label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::InstallService => "Install Service",
+            Self::StartService => "Start Service",
+            Self::EnableService => "Enable at Boot",
+            Self::DisableConflictingSoftware(_) => "Disable",
+        }
+    }
+}
+
+/// Result of one check
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticCheck {
+    /// Stable identifier (`daemon`, `service`, `drivers`, `pwm`, `nvidia`,
+    /// `conflicts`, `config`)
+    pub id: &'static str,
+    pub title: &'static str,
+    pub status: DiagnosticStatus,
+    /// What was found
+    pub summary: String,
+    /// What to do about it, when the status isn't `Ok`
+    pub hint: Option<String>,
+    pub fix: Option<DiagnosticFix>,
+}
+
+impl DiagnosticCheck {
+    fn new(id: &'static str, title: &'static str, status: DiagnosticStatus, summary: impl Into<String>) -> Self {
+        Self { id, title, status, summary: summary.into(), hint: None, fix: None }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    fn fix(mut self, fix: DiagnosticFix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// Kernel modules that expose motherboard or laptop fan outputs as hwmon PWM files
+const FAN_DRIVER_MODULES: &[&str] = &[
+    "nct6775", "nct6683", "it87", "f71882fg", "w83627ehf", "w83795", "asus_ec_sensors",
+    "asus_wmi_sensors", "dell_smm_hwmon", "thinkpad_acpi", "applesmc", "gigabyte_wmi",
+    "amdgpu", "radeon",
+];
+
+/// X server configuration searched for NVIDIA's Coolbits option
+const XORG_CONFIG_PATHS: &[&str] = &["/etc/X11/xorg.conf", "/etc/X11/xorg.conf.d", "/usr/share/X11/xorg.conf.d"];
+
+/// Coolbits bit that enables manual fan control
+const COOLBITS_FAN_CONTROL: u32 = 4;
+
+/// Run every check; blocks on process spawns and the daemon socket, so call
+/// it off the UI thread
+pub fn run_diagnostics() -> Vec<DiagnosticCheck> {
+    let chips = crate::hw::enumerate_hwmon_chips().unwrap_or_default();
+    vec![
+        check_daemon(),
+        check_service(),
+        check_drivers(&chips),
+        check_pwm_writable(&chips),
+        check_nvidia(),
+        check_conflicts(),
+        check_config(),
+    ]
+}
+
+/// Apply a check's fix; asks for root through the usual privilege prompt
+pub fn apply_fix(fix: &DiagnosticFix) -> Result<(), String> {
+    match fix {
+        DiagnosticFix::InstallService => service::install_service(),
+        DiagnosticFix::StartService => service::start_service(),
+        DiagnosticFix::EnableService => service::enable_service(),
+        DiagnosticFix::DisableConflictingSoftware(units) => detect_competing_software()
+            .iter()
+            .filter(|d| units.iter().any(|u| u == d.software.unit))
+            .try_for_each(|d| d.disable()),
+    }
+}
+
+fn check_daemon() -> DiagnosticCheck {
+    const TITLE: &str = "Daemon connection";
+    let socket = service::get_socket_path();
+    match crate::daemon_client::ping_daemon() {
+        Ok(()) => DiagnosticCheck::new("daemon", TITLE, DiagnosticStatus::Ok, format!("The daemon answers on {}", socket)),
+        Err(e) if !service::is_service_installed() => {
+            DiagnosticCheck::new("daemon", TITLE, DiagnosticStatus::Error, format!("No daemon service is installed ({})", e))
+                .hint("Fans can only be controlled through the daemon; install it to leave read-only mode.")
+                .fix(DiagnosticFix::InstallService)
+        }
+        Err(e) if !service::is_service_running() => {
+            DiagnosticCheck::new("daemon", TITLE, DiagnosticStatus::Error, format!("The daemon service is stopped ({})", e))
+                .fix(DiagnosticFix::StartService)
+        }
+        Err(e) => DiagnosticCheck::new("daemon", TITLE, DiagnosticStatus::Error, format!("The service runs but {} does not answer: {}", socket, e))
+            .hint("Check the daemon log (`journalctl -u hyperfan.service` on systemd) for startup errors."),
+    }
+}
+
+fn check_service() -> DiagnosticCheck {
+    const TITLE: &str = "Service enabled at boot";
+    let init = service::detect_init_system();
+    if init == InitSystem::Unknown {
+        return DiagnosticCheck::new("service", TITLE, DiagnosticStatus::Skipped, "Init system not recognized")
+            .hint("Start hyperfand from your init system's autostart.");
+    }
+    if !service::is_service_installed() {
+        return DiagnosticCheck::new("service", TITLE, DiagnosticStatus::Error, format!("Not installed ({})", init))
+            .fix(DiagnosticFix::InstallService);
+    }
+    if service::is_service_enabled() {
+        DiagnosticCheck::new("service", TITLE, DiagnosticStatus::Ok, format!("Enabled ({})", init))
+    } else {
+        DiagnosticCheck::new("service", TITLE, DiagnosticStatus::Warning, format!("Installed but not enabled ({})", init))
+            .hint("Fans stay under firmware control after a reboot until the service is started.")
+            .fix(DiagnosticFix::EnableService)
+    }
+}
+
+fn check_drivers(chips: &[crate::HwmonChip]) -> DiagnosticCheck {
+    let loaded: Vec<&str> = FAN_DRIVER_MODULES
+        .iter()
+        .copied()
+        .filter(|m| Path::new("/sys/module").join(m).exists())
+        .collect();
+    let pwm_count: usize = chips.iter().map(|c| c.pwms.len()).sum();
+    drivers_check(chips.len(), pwm_count, &loaded)
+}
+
+fn drivers_check(chip_count: usize, pwm_count: usize, loaded: &[&str]) -> DiagnosticCheck {
+    const TITLE: &str = "Fan drivers";
+    const SENSORS_DETECT: &str =
+        "Run `sudo sensors-detect` and load the Super I/O driver it suggests, e.g. `sudo modprobe nct6775`.";
+    let drivers = if loaded.is_empty() { String::new() } else { format!(" (loaded: {})", loaded.join(", ")) };
+
+    if chip_count == 0 {
+        DiagnosticCheck::new("drivers", TITLE, DiagnosticStatus::Error, "No hardware monitoring chips found").hint(SENSORS_DETECT)
+    } else if pwm_count == 0 {
+        DiagnosticCheck::new(
+            "drivers",
+            TITLE,
+            DiagnosticStatus::Warning,
+            format!("{} sensor chips but no fan outputs{}", chip_count, drivers),
+        )
+        .hint(SENSORS_DETECT)
+    } else {
+        DiagnosticCheck::new(
+            "drivers",
+            TITLE,
+            DiagnosticStatus::Ok,
+            format!("{} fan outputs on {} chips{}", pwm_count, chip_count, drivers),
+        )
+    }
+}
+
+fn check_pwm_writable(chips: &[crate::HwmonChip]) -> DiagnosticCheck {
+    const TITLE: &str = "Fan outputs writable";
+    let mut total = 0;
+    let mut read_only = Vec::new();
+    let mut thinkpad = false;
+    for chip in chips {
+        for pwm in chip.pwms.iter().filter(|p| p.cooling_device.is_none()) {
+            total += 1;
+            // The daemon writes as root, so only the owner write bit matters
+            let writable = fs::metadata(&pwm.pwm_path).map(|m| m.permissions().mode() & 0o200 != 0).unwrap_or(false);
+            if !writable {
+                read_only.push(format!("{}/{}", chip.name, pwm.name));
+                thinkpad |= chip.name == "thinkpad";
+            }
+        }
+    }
+
+    if total == 0 {
+        return DiagnosticCheck::new("pwm", TITLE, DiagnosticStatus::Skipped, "No fan outputs to check");
+    }
+    if read_only.is_empty() {
+        return DiagnosticCheck::new("pwm", TITLE, DiagnosticStatus::Ok, format!("All {} outputs accept writes", total));
+    }
+    let hint = if thinkpad {
+        "thinkpad_acpi only allows fan control with `options thinkpad_acpi fan_control=1` in /etc/modprobe.d."
+    } else {
+        "The driver exposes these outputs read-only; check its module options or the BIOS fan settings."
+    };
+    DiagnosticCheck::new("pwm", TITLE, DiagnosticStatus::Warning, format!("Read-only: {}", read_only.join(", "))).hint(hint)
+}
+
+fn check_nvidia() -> DiagnosticCheck {
+    const TITLE: &str = "NVIDIA fan control";
+    if !Path::new("/sys/module/nvidia").exists() {
+        return DiagnosticCheck::new("nvidia", TITLE, DiagnosticStatus::Skipped, "No NVIDIA driver loaded");
+    }
+    if hf_gpu::nvidia::nvml::is_available() {
+        return DiagnosticCheck::new("nvidia", TITLE, DiagnosticStatus::Ok, "Controlled through NVML; Coolbits is not needed");
+    }
+
+    let coolbits = XORG_CONFIG_PATHS.iter().filter_map(|p| xorg_coolbits(Path::new(p))).max();
+    match coolbits {
+        Some(bits) if bits & COOLBITS_FAN_CONTROL != 0 => {
+            DiagnosticCheck::new("nvidia", TITLE, DiagnosticStatus::Ok, format!("Coolbits is {} (fan control enabled)", bits))
+        }
+        bits => DiagnosticCheck::new(
+            "nvidia",
+            TITLE,
+            DiagnosticStatus::Warning,
+            match bits {
+                Some(bits) => format!("Coolbits is {}, which leaves fan control off", bits),
+                None => "Coolbits is not set and the driver has no NVML fan control (needs 520+)".to_string(),
+            },
+        )
+        .hint("Run `sudo nvidia-xconfig --cool-bits=4` and restart the X server, or update to driver 520 or newer."),
+    }
+}
+
+/// Highest Coolbits value set in an xorg config file, or in any `.conf` file of a directory
+fn xorg_coolbits(path: &Path) -> Option<u32> {
+    if path.is_dir() {
+        return fs::read_dir(path)
+            .ok()?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "conf"))
+            .filter_map(|p| fs::read_to_string(p).ok())
+            .filter_map(|text| parse_coolbits(&text))
+            .max();
+    }
+    parse_coolbits(&fs::read_to_string(path).ok()?)
+}
+
+/// Value of an uncommented `Option "Coolbits" "N"` line
+fn parse_coolbits(config: &str) -> Option<u32> {
+    config
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .filter_map(|line| {
+            let mut words = line.split_whitespace().map(|w| w.trim_matches('"'));
+            if !words.next()?.eq_ignore_ascii_case("option") || !words.next()?.eq_ignore_ascii_case("coolbits") {
+                return None;
+            }
+            words.next()?.parse().ok()
+        })
+        .max()
+}
+
+fn check_conflicts() -> DiagnosticCheck {
+    const TITLE: &str = "Other fan control software";
+    let detected = detect_competing_software();
+    // Only the daemon sees outputs switched back to automatic behind its back
+    let flips = crate::daemon_client::daemon_get_competing_software().map(|r| r.enable_flips).unwrap_or_default();
+    let flipped = if flips.is_empty() {
+        String::new()
+    } else {
+        let paths: Vec<&str> = flips.iter().map(|f| f.pwm_path.as_str()).collect();
+        format!("Switched back to automatic by another program: {}", paths.join(", "))
+    };
+
+    if detected.is_empty() {
+        return if flips.is_empty() {
+            DiagnosticCheck::new("conflicts", TITLE, DiagnosticStatus::Ok, "None running")
+        } else {
+            DiagnosticCheck::new("conflicts", TITLE, DiagnosticStatus::Warning, flipped)
+                .hint("Look for BIOS fan utilities, vendor tools or scripts that write pwmN_enable.")
+        };
+    }
+    let names: Vec<&str> = detected.iter().map(|d| d.software.name).collect();
+    let hints: Vec<String> = detected.iter().map(|d| d.disable_hint()).collect();
+    let mut summary = format!("{} also drives fans; the two will fight over the same outputs", names.join(", "));
+    if !flipped.is_empty() {
+        summary.push_str(". ");
+        summary.push_str(&flipped);
+    }
+    DiagnosticCheck::new("conflicts", TITLE, DiagnosticStatus::Error, summary)
+        .hint(hints.join("\n"))
+        .fix(DiagnosticFix::DisableConflictingSoftware(
+            detected.iter().map(|d| d.software.unit.to_string()).collect(),
+        ))
+}
+
+fn check_config() -> DiagnosticCheck {
+    const TITLE: &str = "Configuration";
+    let loaded = load_settings().and_then(|settings| Ok((settings, load_curves()?)));
+    let (settings, curves) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            return DiagnosticCheck::new("config", TITLE, DiagnosticStatus::Error, e.to_string())
+                .hint("Fix or move the file aside; Hyperfan starts from defaults when it is missing.")
+        }
+    };
+
+    let problems = config_problems(&settings, &curves);
+    if problems.is_empty() {
+        DiagnosticCheck::new(
+            "config",
+            TITLE,
+            DiagnosticStatus::Ok,
+            format!("{} curves and {} pairs load without problems", curves.all().len(), settings.active_pairs.len()),
+        )
+    } else {
+        DiagnosticCheck::new("config", TITLE, DiagnosticStatus::Warning, problems.join("\n"))
+            .hint("The daemon skips the affected pairs, sensors or schedule entries; edit or remove them.")
+    }
+}
+
+/// Everything in loaded settings and curves the daemon would reject or skip
+pub fn config_problems(settings: &AppSettings, curves: &CurveStore) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for curve in curves.all() {
+        let points: Vec<CurvePoint> = curve
+            .points
+            .iter()
+            .map(|&(temperature, fan_percent)| CurvePoint { temperature, fan_percent })
+            .collect();
+        if let Err(e) = validate_curve_points(&points) {
+            problems.push(format!("Curve '{}': {}", curve.name, e));
+        }
+    }
+
+    for pair in &settings.active_pairs {
+        let curve_ids = std::iter::once(&pair.curve_id).chain(pair.profile_curves.values());
+        for id in curve_ids.filter(|id| curves.get(id).is_none()) {
+            problems.push(format!("Pair '{}' refers to unknown curve {}", pair.name, id));
+        }
+    }
+    for group in settings.pwm_groups.iter().filter(|g| curves.get(&g.curve_id).is_none()) {
+        problems.push(format!("Group '{}' refers to unknown curve {}", group.name, group.curve_id));
+    }
+
+    for sensor in &settings.virtual_sensors {
+        if let Err(e) = sensor.validate() {
+            problems.push(format!("Virtual sensor '{}': {}", sensor.name, e));
+        }
+    }
+    for filter in &settings.sensor_filters {
+        if let Err(e) = filter.validate() {
+            problems.push(format!("Filter on {}: {}", filter.path, e));
+        }
+    }
+    if let Err(e) = settings.profile_schedule.validate() {
+        problems.push(format!("Profile schedule: {}", e));
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_coolbits() {
+        let config = r#"
+Section "Device"
+    Identifier "Device0"
+    Driver     "nvidia"
+#   Option     "Coolbits" "28"
+    Option     "Coolbits" "12"
+EndSection
+"#;
+        assert_eq!(parse_coolbits(config), Some(12));
+        assert_eq!(parse_coolbits("Option \"Coolbits\" \"4\" # fan control"), Some(4));
+        assert_eq!(parse_coolbits("# Option \"Coolbits\" \"4\""), None);
+    }
+
+    #[test]
+    fn test_config_problems() {
+        let mut settings = AppSettings::default();
+        let curves = CurveStore::new();
+        assert!(config_problems(&settings, &curves).is_empty());
+
+        settings.active_pairs.push(crate::settings::FanCurvePair {
+            id: "pair".to_string(),
+            name: "CPU".to_string(),
+            curve_id: "missing".to_string(),
+            temp_source_path: String::new(),
+            fan_path: "/sys/class/hwmon/hwmon2/pwm1".to_string(),
+            fan_paths: Vec::new(),
+            hysteresis_ms: 0,
+            active: true,
+            profile_curves: Default::default(),
+        });
+        let problems = config_problems(&settings, &curves);
+        assert_eq!(problems, vec!["Pair 'CPU' refers to unknown curve missing".to_string()]);
+    }
+
+    #[test]
+    fn test_drivers_check() {
+        assert_eq!(drivers_check(0, 0, &[]).status, DiagnosticStatus::Error);
+        assert_eq!(drivers_check(3, 0, &["amdgpu"]).status, DiagnosticStatus::Warning);
+        let ok = drivers_check(2, 5, &["nct6775"]);
+        assert_eq!(ok.status, DiagnosticStatus::Ok);
+        assert!(ok.summary.contains("nct6775"));
+    }
+}
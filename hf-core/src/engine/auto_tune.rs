@@ -0,0 +1,93 @@
+//! Curve auto-tuning
+//!
+//! The daemon holds a curve's outputs at a few duties, highest first, while
+//! the user runs a steady load, and records the temperature each one settles
+//! at. The quietest duty that still holds the target becomes the curve's
+//! working point: fans sit at the lowest tested duty well below the target,
+//! reach the working duty just under it, and run at full speed a few degrees
+//! past it.
+
+use crate::constants::auto_tune;
+use crate::error::{HyperfanError, Result};
+use hf_protocol::AutoTuneStep;
+
+/// Whether `samples` (oldest first) stay within `tolerance` of each other
+pub fn is_settled(samples: &[f32], tolerance: f32) -> bool {
+    let min = samples.iter().copied().fold(f32::MAX, f32::min);
+    let max = samples.iter().copied().fold(f32::MIN, f32::max);
+    samples.len() >= 2 && max - min <= tolerance
+}
+
+/// Lowest duty (percent) holding `target` under the measured load
+///
+/// Interpolates between the quietest step at or below the target and the next
+/// lower one when that step settled; a step cut short ran hotter than it
+/// read, so the tested duty is used instead. `None` when every step ran hot.
+pub fn required_duty(steps: &[AutoTuneStep], target: f32) -> Option<f32> {
+    let mut sorted = steps.to_vec();
+    sorted.sort_by(|a, b| a.duty_percent.total_cmp(&b.duty_percent));
+
+    let index = sorted.iter().position(|s| s.temp <= target)?;
+    let holding = sorted[index];
+    let Some(hot) = index.checked_sub(1).map(|i| sorted[i]).filter(|s| s.settled) else {
+        return Some(holding.duty_percent);
+    };
+
+    let t = (hot.temp - target) / (hot.temp - holding.temp).max(f32::EPSILON);
+    Some((hot.duty_percent + t * (holding.duty_percent - hot.duty_percent)).ceil())
+}
+
+/// Quietest curve keeping the measured load at or below `target`
+pub fn auto_tune_curve(steps: &[AutoTuneStep], target: f32) -> Result<Vec<(f32, f32)>> {
+    let duty = required_duty(steps, target).ok_or_else(|| {
+        HyperfanError::generic(format!("{:.0}°C can't be held under this load, even at the highest tested duty", target))
+    })?;
+    let floor = steps.iter().map(|s| s.duty_percent).fold(duty, f32::min);
+
+    let mut points = vec![
+        ((target - auto_tune::RAMP_SPAN_C).max(0.0), floor),
+        (target - auto_tune::TARGET_MARGIN_C, duty),
+    ];
+    if duty < 100.0 {
+        points.push((target + auto_tune::FULL_SPEED_MARGIN_C, 100.0));
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(duty_percent: f32, temp: f32, settled: bool) -> AutoTuneStep {
+        AutoTuneStep { duty_percent, temp, settled }
+    }
+
+    #[test]
+    fn test_required_duty_interpolates_settled_steps() {
+        let steps = [step(100.0, 62.0, true), step(80.0, 66.0, true), step(60.0, 74.0, true)];
+        assert_eq!(required_duty(&steps, 70.0), Some(70.0));
+        assert_eq!(required_duty(&steps, 80.0), Some(60.0));
+        assert_eq!(required_duty(&steps, 60.0), None);
+    }
+
+    #[test]
+    fn test_required_duty_ignores_aborted_step() {
+        let steps = [step(100.0, 62.0, true), step(80.0, 66.0, true), step(60.0, 76.0, false)];
+        assert_eq!(required_duty(&steps, 70.0), Some(80.0));
+    }
+
+    #[test]
+    fn test_auto_tune_curve_shape() {
+        let steps = [step(100.0, 62.0, true), step(80.0, 66.0, true), step(60.0, 74.0, true)];
+        let points = auto_tune_curve(&steps, 70.0).unwrap();
+        assert_eq!(points, vec![(50.0, 60.0), (68.0, 70.0), (75.0, 100.0)]);
+        assert!(auto_tune_curve(&steps, 55.0).is_err());
+    }
+
+    #[test]
+    fn test_is_settled() {
+        assert!(is_settled(&[70.0, 70.3, 70.2], 0.5));
+        assert!(!is_settled(&[68.0, 69.0, 70.0], 0.5));
+        assert!(!is_settled(&[70.0], 0.5));
+    }
+}
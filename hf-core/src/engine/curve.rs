@@ -5,7 +5,7 @@
 //! # How It Works
 //!
 //! 1. **Interpolation**: Given a temperature, finds the fan speed by interpolating
-//!    between defined curve points (linear interpolation).
+//!    between defined curve points (linear, or monotone cubic for smooth curves).
 //!
 //! 2. **Hysteresis**: Prevents rapid fan speed oscillation by requiring temperature
 //!    to change by a minimum amount before adjusting speed (default: 2°C).
@@ -16,7 +16,7 @@
 use std::time::Instant;
 
 use crate::constants::{curve as curve_const, timing};
-use crate::data::CurvePoint;
+use crate::data::{CurveInterpolation, CurvePoint};
 
 /// Fan curve controller with hysteresis, delay, and asymmetric ramp speeds
 #[derive(Debug, Clone)]
@@ -38,6 +38,9 @@ pub struct FanCurve {
     pending_target: Option<(f32, Instant)>,
     /// Stepped mode: jump instantly to next curve point instead of interpolating
     stepped: bool,
+    interpolation: CurveInterpolation,
+    /// Per-point slopes for monotone interpolation (empty when linear)
+    tangents: Vec<f32>,
 }
 
 impl FanCurve {
@@ -58,6 +61,8 @@ impl FanCurve {
             ramp_down_speed: curve_const::DEFAULT_RAMP_DOWN_SPEED,
             pending_target: None,
             stepped: false,
+            interpolation: CurveInterpolation::Linear,
+            tangents: Vec::new(),
         }
     }
 
@@ -67,6 +72,13 @@ impl FanCurve {
         self
     }
 
+    /// Set how fan speed is interpolated between points (stepped mode takes precedence)
+    pub fn with_interpolation(mut self, interpolation: CurveInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self.update_tangents();
+        self
+    }
+
     /// Create a curve with custom hysteresis (minimum temperature change to trigger adjustment)
     ///
     /// # Arguments
@@ -85,6 +97,15 @@ impl FanCurve {
         self
     }
 
+    /// Set smoothing from a moving-average window size
+    ///
+    /// Uses the exponential average equivalent to an N-sample moving average
+    /// (`alpha = 2 / (N + 1)`), so a window of 1 means instant response.
+    pub fn with_smoothing_window(self, window: u32) -> Self {
+        let window = window.max(1) as f32;
+        self.with_smoothing((window - 1.0) / (window + 1.0))
+    }
+
     /// Set minimum fan speed (for fans that stall at low PWM)
     pub fn with_min_speed(mut self, min_speed: f32) -> Self {
         self.min_speed = min_speed.clamp(0.0, 100.0);
@@ -281,11 +302,11 @@ impl FanCurve {
         }
     }
 
-    /// Linearly interpolate fan speed between curve points
+    /// Interpolate fan speed between curve points
     ///
     /// - Below minimum temp: returns lowest defined fan speed
     /// - Above maximum temp: returns highest defined fan speed
-    /// - Between points: linear interpolation
+    /// - Between points: linear or monotone cubic interpolation
     fn interpolate(&self, current_temp: f32) -> f32 {
         if self.points.is_empty() {
             return curve_const::FALLBACK_FAN_PERCENT;
@@ -308,7 +329,7 @@ impl FanCurve {
         }
 
         // Find the two points that bracket the current temperature
-        for window in self.points.windows(2) {
+        for (index, window) in self.points.windows(2).enumerate() {
             // Safe: windows(2) guarantees exactly 2 elements
             let lower_point = &window[0];
             let upper_point = &window[1];
@@ -328,6 +349,12 @@ impl FanCurve {
 
                 // Linear interpolation: how far between the two points (0.0 to 1.0)
                 let interpolation_ratio = (current_temp - lower_point.temperature) / temp_range;
+
+                // Monotone mode: cubic through both points using their slopes
+                if let (Some(&m0), Some(&m1)) = (self.tangents.get(index), self.tangents.get(index + 1)) {
+                    return hermite(lower_point, upper_point, m0, m1, interpolation_ratio);
+                }
+
                 let fan_range = upper_point.fan_percent - lower_point.fan_percent;
 
                 return lower_point.fan_percent + (interpolation_ratio * fan_range);
@@ -338,6 +365,14 @@ impl FanCurve {
         curve_const::FALLBACK_FAN_PERCENT
     }
 
+    /// Recompute monotone slopes after the points or interpolation changed
+    fn update_tangents(&mut self) {
+        self.tangents = match self.interpolation {
+            CurveInterpolation::Linear => Vec::new(),
+            CurveInterpolation::Monotone => monotone_tangents(&self.points),
+        };
+    }
+
     fn apply_smoothing(&self, target: f32) -> f32 {
         let last_update = match self.last_update {
             Some(instant) => instant,
@@ -361,6 +396,7 @@ impl FanCurve {
     /// Update the curve points
     pub fn set_points(&mut self, points: Vec<CurvePoint>) {
         self.points = points;
+        self.update_tangents();
     }
 
     /// Reset the curve state (smoothing history, delay, ramping)
@@ -372,6 +408,106 @@ impl FanCurve {
     }
 }
 
+/// Slopes for Fritsch-Carlson monotone cubic (PCHIP) interpolation
+///
+/// Flat wherever the curve changes direction, so the result never rises above
+/// or dips below the neighbouring points.
+fn monotone_tangents(points: &[CurvePoint]) -> Vec<f32> {
+    let n = points.len();
+    if n < 2 {
+        return vec![0.0; n];
+    }
+
+    let widths: Vec<f32> = points.windows(2).map(|w| w[1].temperature - w[0].temperature).collect();
+    let slopes: Vec<f32> = points
+        .windows(2)
+        .zip(&widths)
+        .map(|(w, &h)| {
+            if h.abs() < curve_const::FLOAT_EPSILON {
+                0.0
+            } else {
+                (w[1].fan_percent - w[0].fan_percent) / h
+            }
+        })
+        .collect();
+
+    if n == 2 {
+        return vec![slopes[0]; 2];
+    }
+
+    let mut tangents = vec![0.0; n];
+    for k in 1..n - 1 {
+        let (h0, h1) = (widths[k - 1], widths[k]);
+        let (d0, d1) = (slopes[k - 1], slopes[k]);
+        if d0 * d1 <= 0.0 {
+            continue;
+        }
+        // Weighted harmonic mean keeps the slope between d0 and d1
+        let w0 = 2.0 * h1 + h0;
+        let w1 = h1 + 2.0 * h0;
+        tangents[k] = (w0 + w1) / (w0 / d0 + w1 / d1);
+    }
+    tangents[0] = end_tangent(widths[0], widths[1], slopes[0], slopes[1]);
+    tangents[n - 1] = end_tangent(widths[n - 2], widths[n - 3], slopes[n - 2], slopes[n - 3]);
+    tangents
+}
+
+/// One-sided three-point slope at an end of the curve, limited to stay monotone
+fn end_tangent(h0: f32, h1: f32, d0: f32, d1: f32) -> f32 {
+    if (h0 + h1).abs() < curve_const::FLOAT_EPSILON {
+        return 0.0;
+    }
+    let slope = ((2.0 * h0 + h1) * d0 - h0 * d1) / (h0 + h1);
+    if slope * d0 <= 0.0 {
+        0.0
+    } else if d0 * d1 <= 0.0 && slope.abs() > 3.0 * d0.abs() {
+        3.0 * d0
+    } else {
+        slope
+    }
+}
+
+/// Cubic Hermite value at `t` (0.0 to 1.0) between two points
+fn hermite(lower: &CurvePoint, upper: &CurvePoint, m0: f32, m1: f32, t: f32) -> f32 {
+    let h = upper.temperature - lower.temperature;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let value = (2.0 * t3 - 3.0 * t2 + 1.0) * lower.fan_percent
+        + (t3 - 2.0 * t2 + t) * h * m0
+        + (-2.0 * t3 + 3.0 * t2) * upper.fan_percent
+        + (t3 - t2) * h * m1;
+    value.clamp(0.0, 100.0)
+}
+
+/// Engine for (temperature, percent) pairs as stored in curve files
+fn pair_curve(points: &[(f32, f32)], interpolation: CurveInterpolation) -> FanCurve {
+    FanCurve::new(points.iter().map(|&(temperature, fan_percent)| CurvePoint { temperature, fan_percent }).collect())
+        .with_interpolation(interpolation)
+}
+
+/// Fan speed a curve gives at `temp`, without hysteresis or smoothing
+pub fn curve_value(points: &[(f32, f32)], interpolation: CurveInterpolation, temp: f32) -> f32 {
+    pair_curve(points, interpolation).preview(temp)
+}
+
+/// Sample (temperature, percent) points for drawing a curve
+///
+/// Returns `steps + 1` evenly spaced samples from the first to the last
+/// point, so monotone curves can be drawn as a polyline.
+pub fn sample_curve(points: &[(f32, f32)], interpolation: CurveInterpolation, steps: usize) -> Vec<(f32, f32)> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    let curve = pair_curve(points, interpolation);
+    let steps = steps.max(1);
+    (0..=steps)
+        .map(|i| {
+            let temp = first.0 + (last.0 - first.0) * i as f32 / steps as f32;
+            (temp, curve.preview(temp))
+        })
+        .collect()
+}
+
 impl Default for FanCurve {
     fn default() -> Self {
         Self::new(crate::constants::default_curve::balanced())
@@ -405,6 +541,17 @@ impl CurvePreset {
     pub fn to_curve(&self) -> FanCurve {
         FanCurve::new(self.points())
     }
+
+    /// Display name, also used for curves created from the preset
+    pub fn label(&self) -> &'static str {
+        match self {
+            CurvePreset::Quiet => "Quiet",
+            CurvePreset::Balanced => "Balanced",
+            CurvePreset::Performance => "Performance",
+            CurvePreset::FullSpeed => "Full Speed",
+            CurvePreset::Custom => "Custom",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -445,4 +592,29 @@ mod tests {
         let mut curve = test_curve();
         assert!((curve.calculate(90.0) - 100.0).abs() < 1.0);
     }
+
+    #[test]
+    fn test_monotone_interpolation() {
+        let curve = FanCurve::new(vec![
+            CurvePoint { temperature: 30.0, fan_percent: 20.0 },
+            CurvePoint { temperature: 50.0, fan_percent: 20.0 },
+            CurvePoint { temperature: 60.0, fan_percent: 70.0 },
+            CurvePoint { temperature: 80.0, fan_percent: 100.0 },
+        ])
+        .with_interpolation(CurveInterpolation::Monotone);
+
+        for point in curve.points() {
+            assert!((curve.preview(point.temperature) - point.fan_percent).abs() < 0.01);
+        }
+        // The flat stretch stays flat and the rise never overshoots
+        assert!((curve.preview(40.0) - 20.0).abs() < 0.01);
+        let mut previous = 0.0;
+        for temp in 30..=80 {
+            let value = curve.preview(temp as f32);
+            assert!(value >= previous && value <= 100.0);
+            previous = value;
+        }
+        // Smooth: differs from the straight line between points
+        assert!((curve.preview(55.0) - 45.0).abs() > 1.0);
+    }
 }
@@ -1,7 +1,19 @@
 //! Fan control engine modules
 //!
-//! Contains the fan curve engine.
+//! Contains the fan curve engine, the closed-loop RPM controller, virtual
+//! sensor evaluation, temperature filters, fan noise estimation, and curve
+//! auto-tuning.
 
+mod auto_tune;
 mod curve;
+mod noise;
+mod rpm;
+mod sensor_filter;
+mod virtual_sensor;
 
-pub use curve::{CurvePreset, FanCurve};
+pub use auto_tune::{auto_tune_curve, is_settled, required_duty};
+pub use curve::{curve_value, sample_curve, CurvePreset, FanCurve};
+pub use noise::{combined_dba, curve_dba, limit_curve_noise, max_duty_for_noise, NoiseProfile};
+pub use rpm::RpmController;
+pub use sensor_filter::{FilterState, SensorFilter};
+pub use virtual_sensor::{virtual_sensor_id, VirtualFormula, VirtualSensor};
@@ -0,0 +1,150 @@
+//! Fan noise estimation
+//!
+//! A noise profile maps a fan's duty cycle to its loudness, either from a few
+//! levels the user measured or from the fan laws, where sound level falls by
+//! 50·log10 of the speed ratio below the full-speed level. Fans driven by the
+//! same curve add up as sound powers, and a curve can be capped at the duty
+//! that keeps them under a noise target.
+
+use crate::constants::noise;
+use crate::error::{HyperfanError, Result};
+use serde::{Deserialize, Serialize};
+
+/// How loud a fan is at each duty cycle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NoiseProfile {
+    /// Fan-law estimate from the loudness at 100% duty
+    Generic { max_dba: f32 },
+    /// Measured (duty %, dBA) levels, interpolated linearly
+    Measured { points: Vec<(f32, f32)> },
+}
+
+impl Default for NoiseProfile {
+    fn default() -> Self {
+        Self::Generic { max_dba: noise::DEFAULT_MAX_DBA }
+    }
+}
+
+impl NoiseProfile {
+    /// Measured profile from (duty %, dBA) levels, sorted by duty
+    pub fn measured(mut points: Vec<(f32, f32)>) -> Result<Self> {
+        if points.is_empty() || points.len() > noise::MAX_POINTS {
+            return Err(HyperfanError::config(format!("noise profile needs 1-{} levels", noise::MAX_POINTS)));
+        }
+        for &(duty, dba) in &points {
+            if !(0.0..=100.0).contains(&duty) || !(0.0..=noise::MAX_DBA).contains(&dba) {
+                return Err(HyperfanError::config(format!(
+                    "noise level {}%:{}dBA out of range (0-100%, 0-{} dBA)", duty, dba, noise::MAX_DBA
+                )));
+            }
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(Self::Measured { points })
+    }
+
+    /// Estimated loudness at `duty` percent (0 when the fan is stopped)
+    pub fn dba_at(&self, duty: f32) -> f32 {
+        let duty = duty.clamp(0.0, 100.0);
+        match self {
+            Self::Generic { max_dba } => {
+                if duty <= 0.0 {
+                    return 0.0;
+                }
+                (max_dba + noise::DBA_PER_SPEED_DECADE * (duty / 100.0).log10()).max(0.0)
+            }
+            Self::Measured { points } => {
+                let Some(first) = points.first() else { return 0.0 };
+                if duty <= first.0 {
+                    // Quieter than the lowest measurement, down to silence at 0%
+                    return if first.0 > 0.0 { first.1 * duty / first.0 } else { first.1 };
+                }
+                for pair in points.windows(2) {
+                    let ((d0, l0), (d1, l1)) = (pair[0], pair[1]);
+                    if duty <= d1 {
+                        let t = if d1 > d0 { (duty - d0) / (d1 - d0) } else { 1.0 };
+                        return l0 + t * (l1 - l0);
+                    }
+                }
+                points.last().map(|p| p.1).unwrap_or(0.0)
+            }
+        }
+    }
+}
+
+/// Combined loudness of several sources (decibels add as sound powers)
+pub fn combined_dba(levels: impl IntoIterator<Item = f32>) -> f32 {
+    let power: f32 = levels.into_iter().filter(|l| *l > 0.0).map(|l| 10f32.powf(l / 10.0)).sum();
+    if power > 0.0 { 10.0 * power.log10() } else { 0.0 }
+}
+
+/// Loudness of all `profiles` running at `duty` percent
+pub fn curve_dba(profiles: &[NoiseProfile], duty: f32) -> f32 {
+    combined_dba(profiles.iter().map(|p| p.dba_at(duty)))
+}
+
+/// Highest whole duty percentage keeping `profiles` at or below `max_dba`
+pub fn max_duty_for_noise(profiles: &[NoiseProfile], max_dba: f32) -> f32 {
+    (0..=100).rev()
+        .map(|duty| duty as f32)
+        .find(|&duty| curve_dba(profiles, duty) <= max_dba)
+        .unwrap_or(0.0)
+}
+
+/// Flatten a curve to at most `max_duty` below `ceiling_c`
+///
+/// Points at or above the ceiling keep their duty, and a point is added at
+/// the ceiling so the curve still reaches its original speed there: the noise
+/// target gives way once the temperature limit is reached.
+pub fn limit_curve_noise(points: &[(f32, f32)], max_duty: f32, ceiling_c: f32) -> Vec<(f32, f32)> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let at_ceiling = super::curve_value(&sorted, crate::CurveInterpolation::Linear, ceiling_c);
+    let mut limited: Vec<(f32, f32)> = sorted.iter()
+        .map(|&(temp, duty)| if temp < ceiling_c { (temp, duty.min(max_duty)) } else { (temp, duty) })
+        .collect();
+
+    let spans_ceiling = sorted.first().is_some_and(|p| p.0 < ceiling_c) && sorted.last().is_some_and(|p| p.0 > ceiling_c);
+    if spans_ceiling && !sorted.iter().any(|p| p.0 == ceiling_c) && at_ceiling > max_duty {
+        let index = limited.iter().position(|p| p.0 > ceiling_c).unwrap_or(limited.len());
+        limited.insert(index, (ceiling_c, at_ceiling));
+    }
+    limited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generic_profile_follows_fan_laws() {
+        let profile = NoiseProfile::Generic { max_dba: 40.0 };
+        assert_eq!(profile.dba_at(100.0), 40.0);
+        assert!((profile.dba_at(50.0) - 24.95).abs() < 0.1);
+        assert_eq!(profile.dba_at(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_measured_profile_interpolates() {
+        let profile = NoiseProfile::measured(vec![(100.0, 38.0), (40.0, 20.0)]).unwrap();
+        assert_eq!(profile.dba_at(40.0), 20.0);
+        assert_eq!(profile.dba_at(70.0), 29.0);
+        assert_eq!(profile.dba_at(20.0), 10.0);
+        assert!(NoiseProfile::measured(vec![(120.0, 20.0)]).is_err());
+    }
+
+    #[test]
+    fn test_identical_fans_add_three_db() {
+        let fans = vec![NoiseProfile::Generic { max_dba: 30.0 }; 2];
+        assert!((curve_dba(&fans, 100.0) - 33.0).abs() < 0.1);
+        assert_eq!(max_duty_for_noise(&fans, 30.0), 87.0);
+    }
+
+    #[test]
+    fn test_limit_curve_keeps_ceiling() {
+        let points = vec![(30.0, 30.0), (60.0, 60.0), (90.0, 100.0)];
+        let limited = limit_curve_noise(&points, 40.0, 75.0);
+        assert_eq!(limited, vec![(30.0, 30.0), (60.0, 40.0), (75.0, 80.0), (90.0, 100.0)]);
+    }
+}
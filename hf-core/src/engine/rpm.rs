@@ -0,0 +1,100 @@
+//! Closed-loop RPM targeting
+//!
+//! Fans of the same model rarely spin at the same speed for the same duty
+//! cycle. In RPM mode the curve output is read as a fraction of the fan's
+//! maximum RPM, and an integral controller nudges PWM every control iteration
+//! until the tachometer reading matches. Identical fans driven by one curve
+//! then stay in sync despite their PWM response differences.
+
+use crate::constants::{pwm, rpm_control};
+
+/// Integral PWM controller tracking a target RPM
+#[derive(Debug, Clone)]
+pub struct RpmController {
+    max_rpm: u32,
+    /// Current PWM output, kept fractional so small corrections accumulate
+    output: Option<f32>,
+}
+
+impl RpmController {
+    /// Controller for a fan reaching `max_rpm` at full duty
+    pub fn new(max_rpm: u32) -> Self {
+        Self {
+            max_rpm: max_rpm.max(1),
+            output: None,
+        }
+    }
+
+    pub fn max_rpm(&self) -> u32 {
+        self.max_rpm
+    }
+
+    /// RPM the curve asks for at `fan_percent`
+    pub fn target_rpm(&self, fan_percent: f32) -> u32 {
+        (fan_percent.clamp(0.0, 100.0) / 100.0 * self.max_rpm as f32).round() as u32
+    }
+
+    /// PWM value for the curve output `fan_percent` given the last measured RPM
+    ///
+    /// The first call starts from the matching duty cycle, later calls correct
+    /// it by the RPM error. A 0% target stops the fan outright.
+    pub fn update(&mut self, fan_percent: f32, measured_rpm: u32) -> u8 {
+        let target = self.target_rpm(fan_percent) as f32;
+        let max_pwm = pwm::MAX_VALUE as f32;
+        if target == 0.0 {
+            self.output = Some(0.0);
+            return pwm::MIN_VALUE;
+        }
+
+        let output = match self.output {
+            None => fan_percent.clamp(0.0, 100.0) / 100.0 * max_pwm,
+            Some(output) => {
+                let error = target - measured_rpm as f32;
+                let deadband = (self.max_rpm as f32 * rpm_control::DEADBAND_FRACTION).max(rpm_control::MIN_DEADBAND_RPM);
+                if error.abs() <= deadband {
+                    output
+                } else {
+                    let step = (error / self.max_rpm as f32 * max_pwm * rpm_control::GAIN)
+                        .clamp(-rpm_control::MAX_STEP, rpm_control::MAX_STEP);
+                    (output + step).clamp(0.0, max_pwm)
+                }
+            }
+        };
+        self.output = Some(output);
+        output.round() as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fans_converge_to_target() {
+        // Same model, different duty response
+        let fans: [fn(u8) -> u32; 2] = [|p| 300 + p as u32 * 6, |p| (p as f32 * 7.5) as u32];
+        let mut rpms = Vec::new();
+        for fan in fans {
+            let mut controller = RpmController::new(1800);
+            let mut rpm = 0;
+            for _ in 0..50 {
+                rpm = fan(controller.update(50.0, rpm));
+            }
+            assert!((rpm as i32 - 900).abs() <= 40, "settled at {} RPM", rpm);
+            rpms.push(rpm);
+        }
+        assert!((rpms[0] as i32 - rpms[1] as i32).abs() <= 80);
+    }
+
+    #[test]
+    fn test_zero_and_unreachable_targets() {
+        let mut controller = RpmController::new(2000);
+        assert_eq!(controller.update(0.0, 1500), 0);
+
+        // A fan that cannot reach the target saturates at full duty
+        for _ in 0..40 {
+            controller.update(100.0, 1200);
+        }
+        assert_eq!(controller.update(100.0, 1200), pwm::MAX_VALUE);
+    }
+}
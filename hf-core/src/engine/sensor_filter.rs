@@ -0,0 +1,196 @@
+//! Temperature source filters
+//!
+//! Some SuperIO chips report single-sample spikes of 10-15°C that make fans
+//! surge. Each temperature source can be given a filter chain, applied by the
+//! daemon before curve evaluation and by the GUI graphs:
+//!
+//! 1. **Spike rejection**: a sample jumping more than the threshold from the
+//!    last accepted one is replaced by that value. A jump that persists for
+//!    more than `MAX_REJECTED_SPIKES` samples is real and is accepted.
+//! 2. **Median**: the median of the last N samples.
+//! 3. **EMA**: `output += alpha * (value - output)`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::constants::sensor_filter::{
+    MAX_MEDIAN_WINDOW, MAX_REJECTED_SPIKES, MAX_SPIKE_THRESHOLD, MIN_EMA_ALPHA, MIN_SPIKE_THRESHOLD,
+};
+
+/// Filter settings of one temperature source (stages left `None` are skipped)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorFilter {
+    /// Temperature source path
+    pub path: String,
+
+    /// Exponential moving average factor (1.0 = no smoothing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ema_alpha: Option<f32>,
+
+    /// Median of the last N samples
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub median_window: Option<u32>,
+
+    /// Largest believable jump between samples in degrees Celsius
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spike_threshold: Option<f32>,
+}
+
+impl SensorFilter {
+    /// A filter that passes readings through unchanged
+    pub fn new(path: &str) -> Self {
+        Self { path: path.to_string(), ema_alpha: None, median_window: None, spike_threshold: None }
+    }
+
+    /// True if no stage changes the readings
+    pub fn is_passthrough(&self) -> bool {
+        self.ema_alpha.is_none_or(|a| a >= 1.0)
+            && self.median_window.is_none_or(|n| n <= 1)
+            && self.spike_threshold.is_none()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.path.is_empty() {
+            return Err("Filter has no temperature source".to_string());
+        }
+        if let Some(alpha) = self.ema_alpha {
+            if !(MIN_EMA_ALPHA..=1.0).contains(&alpha) {
+                return Err(format!("Smoothing factor must be between {} and 1", MIN_EMA_ALPHA));
+            }
+        }
+        if let Some(window) = self.median_window {
+            if !(1..=MAX_MEDIAN_WINDOW).contains(&window) {
+                return Err(format!("Median window must be 1-{} samples", MAX_MEDIAN_WINDOW));
+            }
+        }
+        if let Some(threshold) = self.spike_threshold {
+            if !(MIN_SPIKE_THRESHOLD..=MAX_SPIKE_THRESHOLD).contains(&threshold) {
+                return Err(format!(
+                    "Spike threshold must be {}-{}°C",
+                    MIN_SPIKE_THRESHOLD, MAX_SPIKE_THRESHOLD
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Running state of a filter chain
+#[derive(Debug, Clone)]
+pub struct FilterState {
+    filter: SensorFilter,
+    last_accepted: Option<f32>,
+    rejected: u32,
+    window: VecDeque<f32>,
+    output: Option<f32>,
+}
+
+impl FilterState {
+    pub fn new(filter: &SensorFilter) -> Self {
+        Self {
+            filter: filter.clone(),
+            last_accepted: None,
+            rejected: 0,
+            window: VecDeque::new(),
+            output: None,
+        }
+    }
+
+    /// Settings this state was created from
+    pub fn filter(&self) -> &SensorFilter {
+        &self.filter
+    }
+
+    /// Filter one reading (non-finite readings pass through without touching the state)
+    pub fn apply(&mut self, raw: f32) -> f32 {
+        if !raw.is_finite() {
+            return raw;
+        }
+        let value = self.reject_spike(raw);
+        let value = self.median(value);
+        let value = match (self.filter.ema_alpha, self.output) {
+            (Some(alpha), Some(previous)) => previous + alpha * (value - previous),
+            _ => value,
+        };
+        self.output = Some(value);
+        value
+    }
+
+    fn reject_spike(&mut self, raw: f32) -> f32 {
+        if let (Some(threshold), Some(last)) = (self.filter.spike_threshold, self.last_accepted) {
+            if (raw - last).abs() > threshold && self.rejected < MAX_REJECTED_SPIKES {
+                self.rejected += 1;
+                return last;
+            }
+        }
+        self.rejected = 0;
+        self.last_accepted = Some(raw);
+        raw
+    }
+
+    fn median(&mut self, value: f32) -> f32 {
+        let size = match self.filter.median_window {
+            Some(size) if size > 1 => size as usize,
+            _ => return value,
+        };
+        self.window.push_back(value);
+        while self.window.len() > size {
+            self.window.pop_front();
+        }
+
+        let mut sorted: Vec<f32> = self.window.iter().copied().collect();
+        sorted.sort_by(f32::total_cmp);
+        let mid = sorted.len() / 2;
+        if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spike_rejection() {
+        let mut filter = SensorFilter::new("temp1");
+        filter.spike_threshold = Some(8.0);
+        let mut state = FilterState::new(&filter);
+
+        assert_eq!(state.apply(45.0), 45.0);
+        // Single-sample spike is dropped
+        assert_eq!(state.apply(59.0), 45.0);
+        assert_eq!(state.apply(46.0), 46.0);
+
+        // A jump that persists is accepted after MAX_REJECTED_SPIKES samples
+        for _ in 0..MAX_REJECTED_SPIKES {
+            assert_eq!(state.apply(70.0), 46.0);
+        }
+        assert_eq!(state.apply(70.0), 70.0);
+    }
+
+    #[test]
+    fn test_median_and_ema() {
+        let mut filter = SensorFilter::new("temp1");
+        filter.median_window = Some(3);
+        let mut median = FilterState::new(&filter);
+        assert_eq!(median.apply(40.0), 40.0);
+        assert_eq!(median.apply(60.0), 50.0);
+        assert_eq!(median.apply(41.0), 41.0);
+        assert_eq!(median.apply(42.0), 42.0);
+
+        let mut filter = SensorFilter::new("temp1");
+        filter.ema_alpha = Some(0.5);
+        let mut ema = FilterState::new(&filter);
+        assert_eq!(ema.apply(40.0), 40.0);
+        assert_eq!(ema.apply(50.0), 45.0);
+        assert!(ema.apply(f32::NAN).is_nan());
+        assert_eq!(ema.apply(45.0), 45.0);
+
+        filter.ema_alpha = Some(0.0);
+        assert!(filter.validate().is_err());
+        assert!(SensorFilter::new("temp1").is_passthrough());
+    }
+}
@@ -0,0 +1,159 @@
+//! Virtual temperature sensors
+//!
+//! User-defined channels derived from real sensors: the hottest of several
+//! temperatures, the difference between two (water out minus water in), or a
+//! corrected reading for a chip known to report high. Definitions are stored
+//! in the settings and evaluated by the daemon, which lists them under a
+//! "virtual" chip with `virtual:<id>` paths so they can be used as curve
+//! sources and graph series like any other sensor.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::virtual_sensor::{MAX_INPUTS, MAX_NAME_LENGTH, MAX_OFFSET_CELSIUS, MAX_SCALE, PATH_PREFIX};
+
+/// A derived temperature channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VirtualSensor {
+    pub id: String,
+    pub name: String,
+    pub formula: VirtualFormula,
+}
+
+/// How a virtual sensor combines its inputs (temperature source paths)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VirtualFormula {
+    /// Hottest input
+    Max { inputs: Vec<String> },
+    /// Coolest input
+    Min { inputs: Vec<String> },
+    /// Mean of the inputs
+    Average { inputs: Vec<String> },
+    /// `minuend - subtrahend` (e.g. loop outlet minus inlet)
+    Delta { minuend: String, subtrahend: String },
+    /// `input * scale + offset` (correction for a miscalibrated sensor)
+    Linear { input: String, scale: f32, offset: f32 },
+}
+
+/// ID of the virtual sensor a temperature path refers to
+pub fn virtual_sensor_id(path: &str) -> Option<&str> {
+    path.strip_prefix(PATH_PREFIX)
+}
+
+impl VirtualSensor {
+    /// Temperature source path of this sensor
+    pub fn path(&self) -> String {
+        format!("{}{}", PATH_PREFIX, self.id)
+    }
+
+    /// Paths this sensor reads
+    pub fn inputs(&self) -> Vec<&str> {
+        match &self.formula {
+            VirtualFormula::Max { inputs } | VirtualFormula::Min { inputs } | VirtualFormula::Average { inputs } => {
+                inputs.iter().map(String::as_str).collect()
+            }
+            VirtualFormula::Delta { minuend, subtrahend } => vec![minuend, subtrahend],
+            VirtualFormula::Linear { input, .. } => vec![input],
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        hf_protocol::validate_virtual_sensor_id(&self.id)?;
+        let name = self.name.trim();
+        if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+            return Err(format!("Virtual sensor name must be 1-{} characters", MAX_NAME_LENGTH));
+        }
+
+        let inputs = self.inputs();
+        if inputs.is_empty() || inputs.len() > MAX_INPUTS {
+            return Err(format!("Virtual sensors combine 1-{} inputs", MAX_INPUTS));
+        }
+        // Inputs must be real sensors, so definitions can never form a cycle
+        if let Some(input) = inputs.iter().find(|i| i.is_empty() || virtual_sensor_id(i).is_some()) {
+            return Err(format!("Invalid input '{}': inputs must be hardware sensors", input));
+        }
+
+        if let VirtualFormula::Linear { scale, offset, .. } = self.formula {
+            if !scale.is_finite() || scale <= 0.0 || scale > MAX_SCALE {
+                return Err(format!("Scale must be above 0 and at most {}", MAX_SCALE));
+            }
+            if !offset.is_finite() || offset.abs() > MAX_OFFSET_CELSIUS {
+                return Err(format!("Offset must be within ±{}°C", MAX_OFFSET_CELSIUS));
+            }
+        }
+        Ok(())
+    }
+
+    /// Compute the sensor from its inputs, read with `read`
+    /// Any failed input fails the sensor, so a curve never runs on partial data
+    pub fn evaluate<F>(&self, mut read: F) -> Result<f32, String>
+    where
+        F: FnMut(&str) -> Result<f32, String>,
+    {
+        let mut values = Vec::new();
+        for input in self.inputs() {
+            let value = read(input).map_err(|e| format!("{}: input {}: {}", self.name, input, e))?;
+            if !value.is_finite() {
+                return Err(format!("{}: input {} is not a valid temperature", self.name, input));
+            }
+            values.push(value);
+        }
+
+        let result = match &self.formula {
+            VirtualFormula::Max { .. } => values.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            VirtualFormula::Min { .. } => values.iter().copied().fold(f32::INFINITY, f32::min),
+            VirtualFormula::Average { .. } => values.iter().sum::<f32>() / values.len() as f32,
+            VirtualFormula::Delta { .. } => values[0] - values[1],
+            VirtualFormula::Linear { scale, offset, .. } => values[0] * scale + offset,
+        };
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sensor(formula: VirtualFormula) -> VirtualSensor {
+        VirtualSensor { id: "loop".to_string(), name: "Loop".to_string(), formula }
+    }
+
+    fn read(path: &str) -> Result<f32, String> {
+        match path {
+            "in" => Ok(30.0),
+            "out" => Ok(36.5),
+            "gpu" => Ok(62.0),
+            _ => Err("not found".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let inputs = vec!["in".to_string(), "gpu".to_string(), "out".to_string()];
+        assert_eq!(sensor(VirtualFormula::Max { inputs: inputs.clone() }).evaluate(read), Ok(62.0));
+        assert_eq!(sensor(VirtualFormula::Min { inputs: inputs.clone() }).evaluate(read), Ok(30.0));
+        let pair = vec!["in".to_string(), "out".to_string()];
+        assert_eq!(sensor(VirtualFormula::Average { inputs: pair }).evaluate(read), Ok(33.25));
+
+        let delta = sensor(VirtualFormula::Delta { minuend: "out".to_string(), subtrahend: "in".to_string() });
+        assert_eq!(delta.evaluate(read), Ok(6.5));
+        let corrected = sensor(VirtualFormula::Linear { input: "gpu".to_string(), scale: 1.0, offset: -7.0 });
+        assert_eq!(corrected.evaluate(read), Ok(55.0));
+
+        let missing = sensor(VirtualFormula::Max { inputs: vec!["in".to_string(), "gone".to_string()] });
+        assert!(missing.evaluate(read).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(sensor(VirtualFormula::Max { inputs: vec!["in".to_string()] }).validate().is_ok());
+        assert!(sensor(VirtualFormula::Max { inputs: Vec::new() }).validate().is_err());
+        // No virtual sensors as inputs
+        assert!(sensor(VirtualFormula::Max { inputs: vec!["virtual:other".to_string()] }).validate().is_err());
+
+        let scaled = |scale| sensor(VirtualFormula::Linear { input: "in".to_string(), scale, offset: 0.0 });
+        assert!(scaled(0.9).validate().is_ok());
+        assert!(scaled(0.0).validate().is_err());
+        assert!(scaled(f32::NAN).validate().is_err());
+    }
+}
@@ -249,7 +249,7 @@ mod tests {
             .as_millis() as u64;
         
         assert!(validate_timestamp(now, "test").is_ok());
-        assert!(validate_timestamp(super::super::MAX_TIMESTAMP_MS + 1, "test").is_err());
+        assert!(validate_timestamp(MAX_TIMESTAMP_MS + 1, "test").is_err());
         assert!(validate_timestamp(100_000, "test").is_err()); // Before year 2000
     }
 
@@ -0,0 +1,90 @@
+//! Hardware access backends
+//!
+//! Linux exposes sensors and PWM outputs as sysfs files, FreeBSD as sysctl
+//! OIDs. Enumerated paths say which backend they belong to (`/sys/...` or
+//! `sysctl:<oid>`, `smart:<drive>`, `ipmi:<sensor>`), and the control functions pick the backend from the path,
+//! so callers never need to know which platform they run on.
+
+use std::path::Path;
+
+use crate::constants::{cooling, paths};
+use crate::data::{HwmonChip, SensorKind};
+use crate::error::Result;
+
+/// Platform interface for sensor reads and fan control
+pub trait HwmonBackend: Sync {
+    /// Short name for logs ("sysfs", "sysctl")
+    fn name(&self) -> &'static str;
+
+    /// Enumerate every chip this backend can see
+    fn enumerate(&self) -> Result<Vec<HwmonChip>>;
+
+    /// Whether a sensor or control path is present
+    fn exists(&self, path: &Path) -> bool;
+
+    /// Temperature in degrees Celsius
+    fn read_temperature(&self, path: &Path) -> Result<f32>;
+
+    /// Fan speed in RPM
+    fn read_fan_rpm(&self, path: &Path) -> Result<u32>;
+
+    /// Voltage, current, power or humidity in base units
+    fn read_sensor(&self, path: &Path, kind: SensorKind) -> Result<f32>;
+
+    /// PWM duty cycle (0-255)
+    fn read_pwm(&self, path: &Path) -> Result<u8>;
+
+    /// Set PWM duty cycle (0-255)
+    fn write_pwm(&self, path: &Path, value: u8) -> Result<()>;
+
+    /// Hand a channel to software control via its enable path
+    fn enable_manual(&self, enable_path: &Path) -> Result<()>;
+
+    /// Hand a channel back to firmware/driver control via its enable path
+    fn enable_auto(&self, enable_path: &Path) -> Result<()>;
+
+    /// Read a control value verbatim (used to save and restore original state)
+    fn read_raw(&self, path: &Path) -> Result<String>;
+
+    /// Write a control value verbatim
+    fn write_raw(&self, path: &Path, value: &str) -> Result<()>;
+}
+
+/// Whether a path refers to a sysctl OID rather than a file
+pub fn is_sysctl_path(path: &Path) -> bool {
+    path.to_str().is_some_and(|p| p.starts_with(paths::SYSCTL_PREFIX))
+}
+
+/// Whether a path is a thermal cooling device's `cur_state` (devicetree fans)
+pub fn is_cooling_device_path(path: &Path) -> bool {
+    path.file_name().is_some_and(|n| n == cooling::STATE_FILE)
+        && path
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("cooling_device"))
+}
+
+/// Backend responsible for a sensor or control path
+///
+/// sysctl paths are rejected on platforms without the sysctl backend instead
+/// of being treated as (relative) file names.
+pub fn backend_for(path: &Path) -> Result<&'static dyn HwmonBackend> {
+    if is_sysctl_path(path) {
+        #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+        return Ok(&super::freebsd::SysctlBackend);
+
+        #[cfg(not(any(target_os = "freebsd", target_os = "dragonfly")))]
+        return Err(crate::error::HyperfanError::NotSupported(format!(
+            "sysctl sensors are not available on this platform: {}",
+            path.display()
+        )));
+    }
+    if super::storage::is_smart_path(path) {
+        return Ok(&super::storage::SmartctlBackend);
+    }
+    if super::ipmi::is_ipmi_path(path) {
+        return Ok(&super::ipmi::IpmiBackend);
+    }
+    Ok(&super::sysfs::SysfsBackend)
+}
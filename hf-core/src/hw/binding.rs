@@ -12,6 +12,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, info, warn};
 
 use crate::constants::fingerprint as fp_const;
+use hf_protocol::{BindingCandidate, BindingConflict, BindingConflictState, FanCalibration};
 use crate::hw::fingerprint::{
     extract_chip_fingerprint, extract_channel_fingerprint, extract_pwm_fingerprint,
     find_matching_hwmon, generate_channel_id, generate_chip_id,
@@ -37,6 +38,9 @@ pub struct BindingStore {
     pub temp_channels: HashMap<String, ChannelFingerprint>,
     /// Active PWM-fan bindings, keyed by PWM channel ID
     pub bindings: HashMap<String, ValidatedPwmFanBinding>,
+    /// Measured PWM-to-RPM tables, keyed by PWM path
+    #[serde(default)]
+    pub calibrations: HashMap<String, FanCalibration>,
     /// Store version for migration
     pub version: u32,
     /// Last full validation timestamp
@@ -54,6 +58,7 @@ impl BindingStore {
             fan_channels: HashMap::new(),
             temp_channels: HashMap::new(),
             bindings: HashMap::new(),
+            calibrations: HashMap::new(),
             version: Self::CURRENT_VERSION,
             last_validated_at: None,
         }
@@ -107,6 +112,16 @@ impl BindingStore {
         Ok(())
     }
 
+    /// Calibration table of a PWM output
+    pub fn calibration(&self, pwm_path: &str) -> Option<&FanCalibration> {
+        self.calibrations.get(pwm_path)
+    }
+
+    /// Store a calibration, replacing any earlier one for the same PWM
+    pub fn set_calibration(&mut self, calibration: FanCalibration) {
+        self.calibrations.insert(calibration.pwm_path.clone(), calibration);
+    }
+
     /// Register a chip fingerprint
     pub fn register_chip(&mut self, chip: ChipFingerprint) -> String {
         let id = generate_chip_id(&chip);
@@ -192,6 +207,109 @@ impl BindingStore {
         Ok(())
     }
 
+    /// Re-point binding `pwm_id` at hardware the user confirmed is the same fan
+    ///
+    /// The PWM channel and fan input are fingerprinted at their current paths
+    /// and the binding is marked valid. Probe data, the fallback policy and
+    /// the PWM's calibration are carried over.
+    pub fn accept_match(
+        &mut self,
+        pwm_id: &str,
+        pwm_path: &Path,
+        fan_path: Option<&Path>,
+    ) -> Result<(), String> {
+        self.rebind(pwm_id, pwm_path, fan_path, "Confirmed by user after a hardware change")?;
+        info!(pwm_id = %pwm_id, path = ?pwm_path, "User accepted binding match");
+        Ok(())
+    }
+
+    /// Fingerprint binding `pwm_id` at `pwm_path` / `fan_path` and mark it valid for `reason`
+    pub(crate) fn rebind(
+        &mut self,
+        pwm_id: &str,
+        pwm_path: &Path,
+        fan_path: Option<&Path>,
+        reason: &str,
+    ) -> Result<(), String> {
+        if !self.bindings.contains_key(pwm_id) {
+            return Err(format!("Binding {} not found", pwm_id));
+        }
+
+        let pwm_name = channel_name(pwm_path)
+            .filter(|name| name.starts_with("pwm") && !name.contains('_'))
+            .ok_or_else(|| format!("{:?} is not a PWM channel", pwm_path))?;
+        let hwmon_path = pwm_path
+            .parent()
+            .ok_or_else(|| format!("Invalid PWM path: {:?}", pwm_path))?;
+        if !pwm_path.exists() {
+            return Err(format!("PWM channel {:?} not found", pwm_path));
+        }
+        let chip_fp = extract_chip_fingerprint(hwmon_path)
+            .ok_or_else(|| format!("No hwmon chip at {:?}", hwmon_path))?;
+
+        let enable_path = hwmon_path.join(format!("{}_enable", pwm_name));
+        let pwm_fp = extract_pwm_fingerprint(&chip_fp, &pwm_name, pwm_path, &enable_path);
+
+        // Validation looks for the fan on the PWM's chip
+        let fan_fp = match fan_path {
+            Some(path) => {
+                let fan_name = channel_name(path)
+                    .filter(|name| name.starts_with("fan") && name.ends_with("_input"))
+                    .ok_or_else(|| format!("{:?} is not a fan input", path))?;
+                if path.parent() != Some(hwmon_path) {
+                    return Err("Fan input must be on the same chip as the PWM channel".to_string());
+                }
+                if !path.exists() {
+                    return Err(format!("Fan input {:?} not found", path));
+                }
+                Some(extract_channel_fingerprint(&chip_fp, ChannelType::Fan, &fan_name, path))
+            }
+            None => None,
+        };
+
+        self.register_chip(chip_fp);
+        self.register_pwm_channel(pwm_fp.clone());
+        let fan_id = fan_fp.clone().map(|fp| self.register_fan_channel(fp));
+
+        let binding = self
+            .bindings
+            .get_mut(pwm_id)
+            .ok_or_else(|| format!("Binding {} not found", pwm_id))?;
+        let old_pwm_path = binding.pwm_fingerprint.channel.original_path.to_string_lossy().to_string();
+        let new_pwm_path = pwm_path.to_string_lossy().to_string();
+
+        binding.pwm_fingerprint = PwmChannelFingerprint {
+            probe_data: binding.pwm_fingerprint.probe_data.take(),
+            safe_fallback_policy: binding.pwm_fingerprint.safe_fallback_policy,
+            paired_fan_fingerprint_id: fan_id,
+            ..pwm_fp
+        };
+        binding.fan_fingerprint = fan_fp;
+        binding.validation_state = ValidationState::Ok;
+        binding.confidence_score = 1.0;
+        binding.user_override_ack = false;
+        binding.confidence_reasons = vec![reason.to_string()];
+        binding.last_validated_at = Some(current_timestamp_ms());
+
+        if old_pwm_path != new_pwm_path {
+            if let Some(mut calibration) = self.calibrations.remove(&old_pwm_path) {
+                calibration.pwm_path = new_pwm_path.clone();
+                if let Some(path) = fan_path {
+                    calibration.fan_path = path.to_string_lossy().to_string();
+                }
+                self.calibrations.insert(new_pwm_path.clone(), calibration);
+            }
+        }
+
+        debug!(
+            pwm_id = %pwm_id,
+            from = %old_pwm_path,
+            to = %new_pwm_path,
+            "Rebound binding"
+        );
+        Ok(())
+    }
+
     /// Get all bindings that need user attention
     pub fn get_bindings_needing_attention(&self) -> Vec<(&str, &ValidatedPwmFanBinding)> {
         self.bindings
@@ -592,10 +710,218 @@ pub fn execute_fallback(action: &FallbackAction, pwm_path: &Path) -> Result<(),
     Ok(())
 }
 
+// ============================================================================
+// Conflict Resolution
+// ============================================================================
+
+/// Channels of one hwmon chip as it is now, for ranking rebind candidates
+#[derive(Debug, Clone)]
+struct CurrentChip {
+    fingerprint: ChipFingerprint,
+    pwms: Vec<PwmChannelFingerprint>,
+    fans: Vec<ChannelFingerprint>,
+}
+
+/// Validate every binding and list the ones that would fall back, each with
+/// the current channels that could be the same fan
+///
+/// Covers the same bindings as [`apply_safe_fallbacks`]. Validation updates
+/// the binding states in `store`; the caller decides whether to save them.
+pub fn find_binding_conflicts(store: &mut BindingStore) -> Vec<BindingConflict> {
+    let report = validate_all_bindings(store);
+    let chips = scan_current_chips();
+
+    let mut conflicts: Vec<BindingConflict> = report
+        .results
+        .iter()
+        .filter_map(|result| {
+            let binding = store.bindings.get(&result.pwm_id)?;
+            let state = match result.new_state {
+                ValidationState::Ok => return None,
+                ValidationState::Degraded | ValidationState::NeedsRebind
+                    if binding.user_override_ack =>
+                {
+                    return None
+                }
+                ValidationState::Degraded => BindingConflictState::Degraded,
+                ValidationState::NeedsRebind => BindingConflictState::NeedsRebind,
+                ValidationState::Unsafe => BindingConflictState::Unsafe,
+            };
+            let stored_chip = store.chips.get(&binding.pwm_fingerprint.channel.chip_fingerprint_id);
+
+            Some(BindingConflict {
+                pwm_id: result.pwm_id.clone(),
+                chip_name: stored_chip.map(|c| c.driver_name.clone()).unwrap_or_default(),
+                label: binding.pwm_fingerprint.channel.label_text_raw.clone(),
+                previous_pwm_path: binding.pwm_fingerprint.channel.original_path.to_string_lossy().to_string(),
+                previous_fan_path: binding
+                    .fan_fingerprint
+                    .as_ref()
+                    .map(|fan| fan.original_path.to_string_lossy().to_string()),
+                state,
+                confidence: result.confidence,
+                reasons: result.reasons.clone(),
+                candidates: rank_candidates(binding, stored_chip, &chips),
+            })
+        })
+        .collect();
+
+    conflicts.sort_by(|a, b| a.pwm_id.cmp(&b.pwm_id));
+    conflicts
+}
+
+/// Fingerprint the PWM channels and fan inputs of every hwmon chip
+fn scan_current_chips() -> Vec<CurrentChip> {
+    let Ok(entries) = fs::read_dir("/sys/class/hwmon") else {
+        return Vec::new();
+    };
+
+    let mut chips = Vec::new();
+    for entry in entries.flatten() {
+        let hwmon_path = entry.path();
+        let Some(fingerprint) = extract_chip_fingerprint(&hwmon_path) else {
+            continue;
+        };
+        let mut chip = CurrentChip { fingerprint, pwms: Vec::new(), fans: Vec::new() };
+
+        for file in fs::read_dir(&hwmon_path).into_iter().flatten().flatten() {
+            let name = file.file_name().to_string_lossy().to_string();
+            if name.starts_with("pwm") && !name.contains('_') {
+                let enable_path = hwmon_path.join(format!("{}_enable", name));
+                chip.pwms.push(extract_pwm_fingerprint(&chip.fingerprint, &name, &file.path(), &enable_path));
+            } else if name.starts_with("fan") && name.ends_with("_input") {
+                chip.fans.push(extract_channel_fingerprint(&chip.fingerprint, ChannelType::Fan, &name, &file.path()));
+            }
+        }
+        chips.push(chip);
+    }
+    chips
+}
+
+/// Current PWM channels most likely to be `binding`'s hardware, best first
+fn rank_candidates(
+    binding: &ValidatedPwmFanBinding,
+    stored_chip: Option<&ChipFingerprint>,
+    chips: &[CurrentChip],
+) -> Vec<BindingCandidate> {
+    let mut candidates = Vec::new();
+
+    for chip in chips {
+        for pwm in &chip.pwms {
+            let (pwm_conf, mut reasons) =
+                channel_similarity(&binding.pwm_fingerprint.channel, stored_chip, &pwm.channel, &chip.fingerprint);
+
+            // The fan input is judged on the candidate's own chip
+            let (confidence, fan_path) = match &binding.fan_fingerprint {
+                Some(stored_fan) => {
+                    let best_fan = chip
+                        .fans
+                        .iter()
+                        .map(|fan| (fan, channel_similarity(stored_fan, stored_chip, fan, &chip.fingerprint)))
+                        .max_by(|a, b| a.1 .0.total_cmp(&b.1 .0));
+                    match best_fan {
+                        Some((fan, (fan_conf, _))) => {
+                            reasons.push(format!("Fan input {} ({:.0}% match)", fan.original_name, fan_conf * 100.0));
+                            (
+                                pwm_conf * (1.0 - fp_const::CANDIDATE_FAN_SHARE)
+                                    + fan_conf * fp_const::CANDIDATE_FAN_SHARE,
+                                Some(fan.original_path.to_string_lossy().to_string()),
+                            )
+                        }
+                        None => {
+                            reasons.push("No fan input on this chip".to_string());
+                            (pwm_conf * (1.0 - fp_const::CANDIDATE_FAN_SHARE), None)
+                        }
+                    }
+                }
+                None => (pwm_conf, None),
+            };
+
+            if confidence >= fp_const::MIN_CANDIDATE_CONFIDENCE {
+                candidates.push(BindingCandidate {
+                    pwm_path: pwm.channel.original_path.to_string_lossy().to_string(),
+                    fan_path,
+                    chip_name: chip.fingerprint.driver_name.clone(),
+                    confidence,
+                    reasons,
+                });
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    candidates.truncate(fp_const::MAX_BINDING_CANDIDATES);
+    candidates
+}
+
+/// How closely a current channel matches a stored fingerprint, regardless
+/// of where either lives (0.0 - 1.0)
+fn channel_similarity(
+    stored: &ChannelFingerprint,
+    stored_chip: Option<&ChipFingerprint>,
+    current: &ChannelFingerprint,
+    current_chip: &ChipFingerprint,
+) -> (f32, Vec<String>) {
+    let mut score = 0.0f32;
+    let mut max_score = 0.0f32;
+    let mut reasons = Vec::new();
+
+    max_score += fp_const::WEIGHT_DRIVER_NAME;
+    if let Some(chip) = stored_chip {
+        if chip.driver_name == current_chip.driver_name {
+            score += fp_const::WEIGHT_DRIVER_NAME;
+            reasons.push(format!("Same chip driver ({})", chip.driver_name));
+        } else {
+            reasons.push(format!(
+                "Chip driver changed: {} -> {}",
+                chip.driver_name, current_chip.driver_name
+            ));
+        }
+
+        if chip.device_symlink_target.is_some() {
+            max_score += fp_const::WEIGHT_DEVICE_SYMLINK;
+            if chip.device_symlink_target == current_chip.device_symlink_target {
+                score += fp_const::WEIGHT_DEVICE_SYMLINK;
+                reasons.push("Same device".to_string());
+            }
+        }
+    }
+
+    if let Some(label) = &stored.label_text_normalized {
+        max_score += fp_const::WEIGHT_LABEL_MATCH;
+        if current.label_text_normalized.as_ref() == Some(label) {
+            score += fp_const::WEIGHT_LABEL_MATCH;
+            reasons.push("Label matches".to_string());
+        } else {
+            reasons.push("Label differs".to_string());
+        }
+    }
+
+    max_score += fp_const::WEIGHT_ATTR_FINGERPRINT;
+    let union = stored.attribute_fingerprint.union(&current.attribute_fingerprint).count();
+    if union > 0 {
+        let shared = stored.attribute_fingerprint.intersection(&current.attribute_fingerprint).count();
+        score += fp_const::WEIGHT_ATTR_FINGERPRINT * shared as f32 / union as f32;
+    }
+
+    max_score += fp_const::WEIGHT_CHANNEL_INDEX;
+    if stored.channel_index == current.channel_index {
+        score += fp_const::WEIGHT_CHANNEL_INDEX;
+        reasons.push(format!("Same channel number ({})", current.original_name));
+    }
+
+    (score / max_score, reasons)
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// File name of a sysfs channel path
+fn channel_name(path: &Path) -> Option<String> {
+    path.file_name().map(|name| name.to_string_lossy().to_string())
+}
+
 /// Get current timestamp in milliseconds
 fn current_timestamp_ms() -> u64 {
     SystemTime::now()
@@ -709,6 +1035,83 @@ mod tests {
         assert_eq!(confidence_to_state(0.0), ValidationState::Unsafe);
     }
 
+    fn chip(driver: &str) -> ChipFingerprint {
+        ChipFingerprint { driver_name: driver.to_string(), ..Default::default() }
+    }
+
+    fn channel(channel_type: ChannelType, path: &str, label: Option<&str>) -> ChannelFingerprint {
+        let name = path.rsplit('/').next().unwrap().trim_end_matches("_input");
+        ChannelFingerprint {
+            channel_type,
+            label_text_raw: label.map(String::from),
+            label_text_normalized: label.map(|l| l.to_lowercase()),
+            channel_index: name.trim_start_matches(|c: char| c.is_ascii_alphabetic()).parse().unwrap_or(0),
+            attribute_fingerprint: [format!("{}_enable", name)].into_iter().collect(),
+            original_name: name.to_string(),
+            original_path: PathBuf::from(path),
+            ..Default::default()
+        }
+    }
+
+    fn pwm(path: &str, label: Option<&str>) -> PwmChannelFingerprint {
+        PwmChannelFingerprint { channel: channel(ChannelType::Pwm, path, label), ..Default::default() }
+    }
+
+    fn binding(pwm_fp: PwmChannelFingerprint, fan_fp: Option<ChannelFingerprint>) -> ValidatedPwmFanBinding {
+        ValidatedPwmFanBinding {
+            pwm_fingerprint: pwm_fp,
+            fan_fingerprint: fan_fp,
+            temp_fingerprint: None,
+            validation_state: ValidationState::NeedsRebind,
+            confidence_score: 0.5,
+            user_override_ack: false,
+            confidence_reasons: Vec::new(),
+            created_at: 0,
+            last_validated_at: None,
+            validation_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_channel_similarity_identical() {
+        let stored = channel(ChannelType::Pwm, "/sys/class/hwmon/hwmon2/pwm2", Some("CPU_FAN"));
+        let (conf, _) = channel_similarity(&stored, Some(&chip("nct6798")), &stored, &chip("nct6798"));
+        assert!((conf - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_rank_candidates_prefers_same_channel_after_reindex() {
+        let stored = binding(
+            pwm("/sys/class/hwmon/hwmon2/pwm2", Some("CPU_FAN")),
+            Some(channel(ChannelType::Fan, "/sys/class/hwmon/hwmon2/fan2_input", None)),
+        );
+        let chips = vec![
+            CurrentChip {
+                fingerprint: chip("nct6798"),
+                pwms: vec![
+                    pwm("/sys/class/hwmon/hwmon3/pwm1", Some("SYS_FAN1")),
+                    pwm("/sys/class/hwmon/hwmon3/pwm2", Some("CPU_FAN")),
+                ],
+                fans: vec![
+                    channel(ChannelType::Fan, "/sys/class/hwmon/hwmon3/fan1_input", None),
+                    channel(ChannelType::Fan, "/sys/class/hwmon/hwmon3/fan2_input", None),
+                ],
+            },
+            CurrentChip {
+                fingerprint: chip("amdgpu"),
+                pwms: vec![pwm("/sys/class/hwmon/hwmon1/pwm1", None)],
+                fans: Vec::new(),
+            },
+        ];
+
+        let candidates = rank_candidates(&stored, Some(&chip("nct6798")), &chips);
+        assert_eq!(candidates[0].pwm_path, "/sys/class/hwmon/hwmon3/pwm2");
+        assert_eq!(candidates[0].fan_path.as_deref(), Some("/sys/class/hwmon/hwmon3/fan2_input"));
+        assert!(candidates[0].confidence > 0.9);
+        // Different driver, no label and no fan: not worth offering
+        assert!(candidates.iter().all(|c| c.chip_name != "amdgpu"));
+    }
+
     #[test]
     fn test_binding_store_new() {
         let store = BindingStore::new();
@@ -0,0 +1,339 @@
+//! Binding store export and import between machines
+//!
+//! Fleets built on the same motherboard can share one set of fan bindings:
+//! the store is exported to a portable file and imported on another machine,
+//! where each binding is re-anchored on the local hardware by its fingerprints
+//! rather than by its hwmon paths. [`plan_binding_import`] reports what would
+//! and wouldn't match without writing anything; [`import_bindings`]
+//! re-fingerprints the matching bindings locally and merges them into the
+//! local store.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::data::validate_file_size;
+use crate::error::{HyperfanError, Result};
+use crate::hw::binding::{validate_all_bindings, BindingStore};
+use crate::hw::fingerprint::ValidationState;
+
+/// `format` field of every binding export
+pub const BINDING_EXPORT_FORMAT: &str = "hyperfan-binding-store";
+
+/// Newest export version this build reads and the one it writes
+pub const BINDING_EXPORT_VERSION: u32 = 1;
+
+/// Where a binding export was made
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BindingExportMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// Board name of the exporting machine
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hardware: Option<String>,
+    /// Unix time in seconds
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+/// Contents of a binding export file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindingExport {
+    pub format: String,
+    pub version: u32,
+    #[serde(default)]
+    pub metadata: BindingExportMetadata,
+    pub store: BindingStore,
+}
+
+impl BindingExport {
+    pub fn new(store: BindingStore, metadata: BindingExportMetadata) -> Self {
+        Self {
+            format: BINDING_EXPORT_FORMAT.to_string(),
+            version: BINDING_EXPORT_VERSION,
+            metadata,
+            store,
+        }
+    }
+
+    /// Read and validate an export file
+    pub fn load(path: &Path) -> Result<Self> {
+        validate_file_size(path)?;
+        let contents = fs::read_to_string(path)
+            .map_err(|e| HyperfanError::FileRead { path: path.to_path_buf(), source: e })?;
+        let export: Self = serde_json::from_str(&contents)?;
+        export.validate()?;
+        debug!("Loaded binding export with {} bindings from {:?}", export.store.bindings.len(), path);
+        Ok(export)
+    }
+
+    /// Write the export as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).map_err(|e| HyperfanError::FileWrite { path: path.to_path_buf(), source: e })?;
+        info!("Exported {} bindings to {:?}", self.store.bindings.len(), path);
+        Ok(())
+    }
+
+    /// Check the format and the export and store versions
+    pub fn validate(&self) -> Result<()> {
+        if self.format != BINDING_EXPORT_FORMAT {
+            return Err(HyperfanError::config("not a Hyperfan binding export"));
+        }
+        if self.version == 0 || self.version > BINDING_EXPORT_VERSION {
+            return Err(HyperfanError::config(format!(
+                "binding export version {} is not supported (newest: {})",
+                self.version, BINDING_EXPORT_VERSION
+            )));
+        }
+        if self.store.version > BindingStore::CURRENT_VERSION {
+            return Err(HyperfanError::config(format!(
+                "binding store version {} is newer than this build supports",
+                self.store.version
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// What importing one binding would do on this machine
+#[derive(Debug, Clone)]
+pub struct BindingImportEntry {
+    pub pwm_id: String,
+    /// Channel label, or its name when the chip has no labels
+    pub name: String,
+    pub chip_name: String,
+    /// PWM path on the exporting machine
+    pub exported_pwm_path: String,
+    /// Where the binding's PWM channel and fan input were found here
+    pub pwm_path: Option<PathBuf>,
+    pub fan_path: Option<PathBuf>,
+    pub state: ValidationState,
+    pub confidence: f32,
+    pub reasons: Vec<String>,
+}
+
+impl BindingImportEntry {
+    /// Whether the binding matches well enough to be imported
+    pub fn importable(&self) -> bool {
+        self.pwm_path.is_some() && matches!(self.state, ValidationState::Ok | ValidationState::Degraded)
+    }
+}
+
+/// Dry-run report of an import
+#[derive(Debug, Clone, Default)]
+pub struct BindingImportPlan {
+    pub entries: Vec<BindingImportEntry>,
+}
+
+impl BindingImportPlan {
+    pub fn importable_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.importable()).count()
+    }
+}
+
+/// Bindings written by [`import_bindings`]
+#[derive(Debug, Clone, Default)]
+pub struct BindingImport {
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// Export of the local binding store
+pub fn export_bindings() -> Result<BindingExport> {
+    let store = BindingStore::load().map_err(HyperfanError::config)?;
+    if store.bindings.is_empty() {
+        return Err(HyperfanError::config("no bindings to export"));
+    }
+    let summary = crate::system::get_system_summary().ok();
+    let metadata = BindingExportMetadata {
+        hostname: summary.as_ref().map(|s| s.hostname.clone()).filter(|h| !h.is_empty()),
+        hardware: summary.map(|s| s.motherboard_name).filter(|name| !name.is_empty()),
+        created_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+    Ok(BindingExport::new(store, metadata))
+}
+
+/// Match every exported binding against this machine's hardware
+///
+/// Nothing is written; the exported store is validated as if it were local.
+pub fn plan_binding_import(export: &BindingExport) -> BindingImportPlan {
+    let mut store = export.store.clone();
+    let report = validate_all_bindings(&mut store);
+
+    let mut entries: Vec<BindingImportEntry> = report
+        .results
+        .into_iter()
+        .filter_map(|result| {
+            let binding = store.bindings.get(&result.pwm_id)?;
+            let channel = &binding.pwm_fingerprint.channel;
+            Some(BindingImportEntry {
+                name: channel.label_text_raw.clone().unwrap_or_else(|| channel.original_name.clone()),
+                chip_name: store
+                    .chips
+                    .get(&channel.chip_fingerprint_id)
+                    .map(|chip| chip.driver_name.clone())
+                    .unwrap_or_default(),
+                exported_pwm_path: channel.original_path.to_string_lossy().to_string(),
+                pwm_path: result.resolved_pwm_path,
+                fan_path: result.resolved_fan_path,
+                state: result.new_state,
+                confidence: result.confidence,
+                reasons: result.reasons,
+                pwm_id: result.pwm_id,
+            })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| a.exported_pwm_path.cmp(&b.exported_pwm_path));
+    BindingImportPlan { entries }
+}
+
+/// Re-fingerprint the importable bindings of `plan` here and merge them into
+/// the local store, replacing local bindings with the same ID
+///
+/// Calibrations travel with their bindings. Bindings that don't match are
+/// left out and listed in [`BindingImport::skipped`].
+pub fn import_bindings(export: &BindingExport, plan: &BindingImportPlan) -> Result<BindingImport> {
+    let mut local = BindingStore::load().map_err(HyperfanError::config)?;
+    let result = merge_importable(&mut local, export, plan);
+
+    if !result.imported.is_empty() {
+        local.save().map_err(HyperfanError::config)?;
+    }
+    info!(
+        "Imported {} bindings, skipped {}",
+        result.imported.len(),
+        result.skipped.len()
+    );
+    Ok(result)
+}
+
+/// Re-fingerprint the importable bindings of `plan` into `local` without saving
+pub(crate) fn merge_importable(
+    local: &mut BindingStore,
+    export: &BindingExport,
+    plan: &BindingImportPlan,
+) -> BindingImport {
+    let reason = format!(
+        "Imported from {}",
+        export.metadata.hostname.as_deref().unwrap_or("another machine")
+    );
+    let mut result = BindingImport::default();
+
+    for entry in &plan.entries {
+        let (Some(binding), Some(pwm_path)) = (export.store.bindings.get(&entry.pwm_id), &entry.pwm_path) else {
+            result.skipped.push(entry.pwm_id.clone());
+            continue;
+        };
+        if !entry.importable() {
+            result.skipped.push(entry.pwm_id.clone());
+            continue;
+        }
+
+        // Rebind in a scratch store so a failure leaves the local one untouched
+        let mut staged = BindingStore::new();
+        staged.bindings.insert(entry.pwm_id.clone(), binding.clone());
+        if let Some(calibration) = export.store.calibrations.get(&entry.exported_pwm_path) {
+            staged.calibrations.insert(entry.exported_pwm_path.clone(), calibration.clone());
+        }
+        if let Err(e) = staged.rebind(&entry.pwm_id, pwm_path, entry.fan_path.as_deref(), &reason) {
+            warn!("Skipping binding {} on import: {}", entry.pwm_id, e);
+            result.skipped.push(entry.pwm_id.clone());
+            continue;
+        }
+
+        local.chips.extend(staged.chips);
+        local.pwm_channels.extend(staged.pwm_channels);
+        local.fan_channels.extend(staged.fan_channels);
+        local.calibrations.extend(staged.calibrations);
+        local.bindings.extend(staged.bindings);
+        result.imported.push(entry.pwm_id.clone());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hw::fingerprint::{ChannelFingerprint, ChipFingerprint, PwmChannelFingerprint, ValidatedPwmFanBinding};
+
+    fn exported_store() -> BindingStore {
+        let mut store = BindingStore::new();
+        let chip_id = store.register_chip(ChipFingerprint {
+            driver_name: "nct6798-not-present".to_string(),
+            ..Default::default()
+        });
+        let pwm = PwmChannelFingerprint {
+            channel: ChannelFingerprint {
+                chip_fingerprint_id: chip_id,
+                original_name: "pwm2".to_string(),
+                original_path: PathBuf::from("/sys/class/hwmon/hwmon2/pwm2"),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        store.bindings.insert(
+            "cpu".to_string(),
+            ValidatedPwmFanBinding {
+                pwm_fingerprint: pwm,
+                fan_fingerprint: None,
+                temp_fingerprint: None,
+                validation_state: ValidationState::Ok,
+                confidence_score: 1.0,
+                user_override_ack: false,
+                confidence_reasons: Vec::new(),
+                created_at: 0,
+                last_validated_at: None,
+                validation_count: 1,
+            },
+        );
+        store
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bindings-export.json");
+        let export = BindingExport::new(exported_store(), BindingExportMetadata {
+            hostname: Some("node-01".to_string()),
+            ..Default::default()
+        });
+        export.save(&path).unwrap();
+
+        let loaded = BindingExport::load(&path).unwrap();
+        assert_eq!(loaded.metadata, export.metadata);
+        assert!(loaded.store.bindings.contains_key("cpu"));
+    }
+
+    #[test]
+    fn test_export_validation() {
+        let mut export = BindingExport::new(exported_store(), BindingExportMetadata::default());
+        assert!(export.validate().is_ok());
+
+        export.version = BINDING_EXPORT_VERSION + 1;
+        assert!(export.validate().is_err());
+
+        export.version = BINDING_EXPORT_VERSION;
+        export.format = "hyperfan-curve-template".to_string();
+        assert!(export.validate().is_err());
+    }
+
+    #[test]
+    fn test_plan_reports_missing_hardware() {
+        let export = BindingExport::new(exported_store(), BindingExportMetadata::default());
+        let plan = plan_binding_import(&export);
+
+        assert_eq!(plan.entries.len(), 1);
+        let entry = &plan.entries[0];
+        assert_eq!(entry.chip_name, "nct6798-not-present");
+        assert_eq!(entry.exported_pwm_path, "/sys/class/hwmon/hwmon2/pwm2");
+        assert!(entry.pwm_path.is_none());
+        assert!(!entry.importable());
+        assert_eq!(plan.importable_count(), 0);
+    }
+}
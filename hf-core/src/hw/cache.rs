@@ -0,0 +1,165 @@
+//! Hwmon layout cache
+//!
+//! Walking `/sys/class/hwmon` means opening every label, name and enable file
+//! of every chip, which adds up on boards with many chips. The cache keeps
+//! each chip's layout (sensor names, labels and paths) and on each call only
+//! re-reads the value files.
+//!
+//! A chip's layout is re-scanned when its TTL expires, when its `hwmonN`
+//! entry appears, disappears or points at a different device, or after
+//! [`invalidate_hwmon_cache`] (called on udev hwmon add/remove events).
+//! Thermal zones and fan cooling devices under `/sys/class/thermal` are few
+//! and always re-read; smartctl drive readings have their own cache.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, trace};
+
+use crate::constants::{paths, pwm};
+use crate::data::HwmonChip;
+use crate::error::Result;
+use crate::hw::{read_fan_rpm, read_pwm_value, read_sensor_value, read_temperature};
+
+/// Cached layout of one `hwmonN` entry
+struct CachedChip {
+    /// Device the entry links to, so a reused `hwmonN` number is noticed
+    device: Option<PathBuf>,
+    /// `None` when the entry had no usable sensors
+    chip: Option<HwmonChip>,
+    scanned_at: Instant,
+}
+
+/// Per-chip layout cache for one hwmon base directory
+#[derive(Default)]
+struct HwmonCache {
+    chips: HashMap<PathBuf, CachedChip>,
+}
+
+static CACHE: Mutex<Option<HwmonCache>> = Mutex::new(None);
+
+impl HwmonCache {
+    /// Chips under `base` with fresh values, re-scanning layouts older than `ttl`
+    fn chips(&mut self, base: &Path, ttl: Duration) -> Result<Vec<HwmonChip>> {
+        let mut entries: Vec<PathBuf> = fs::read_dir(base)?.flatten().map(|e| e.path()).collect();
+        entries.sort();
+
+        self.chips.retain(|path, _| entries.contains(path));
+
+        let mut chips = Vec::with_capacity(entries.len());
+        for path in entries {
+            let device = fs::read_link(&path).ok();
+            let reuse = self
+                .chips
+                .get(&path)
+                .is_some_and(|c| c.device == device && c.scanned_at.elapsed() < ttl);
+
+            if reuse {
+                if let Some(chip) = self.chips.get_mut(&path).and_then(|c| c.chip.as_mut()) {
+                    refresh_values(chip);
+                    chips.push(chip.clone());
+                }
+                continue;
+            }
+
+            trace!(path = ?path, "Scanning hwmon chip layout");
+            let chip = super::hardware::read_hwmon_chip(&path)?.and_then(super::exclusion::filter_chip);
+            if let Some(ref chip) = chip {
+                chips.push(chip.clone());
+            }
+            self.chips.insert(path, CachedChip { device, chip, scanned_at: Instant::now() });
+        }
+
+        super::hardware::add_extra_sources(&mut chips);
+        Ok(chips)
+    }
+}
+
+/// Enumerate hwmon chips, re-using cached layouts younger than `ttl`
+///
+/// Values (temperatures, RPM, PWM, voltages...) are always re-read. Falls
+/// back to a full [`enumerate_hwmon_chips`](super::enumerate_hwmon_chips)
+/// on platforms without sysfs hwmon.
+pub fn cached_hwmon_chips(ttl: Duration) -> Result<Vec<HwmonChip>> {
+    let base = Path::new(paths::HWMON_BASE);
+    if !base.is_dir() {
+        return super::enumerate_hwmon_chips();
+    }
+
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.get_or_insert_with(HwmonCache::default).chips(base, ttl)
+}
+
+/// Drop cached layouts so the next call re-scans them
+/// `chip` limits this to one `hwmonN` path; `None` clears everything.
+pub fn invalidate_hwmon_cache(chip: Option<&Path>) {
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(cache) = cache.as_mut() {
+        match chip {
+            Some(path) => {
+                cache.chips.remove(path);
+            }
+            None => cache.chips.clear(),
+        }
+    }
+    debug!(chip = ?chip, "Invalidated hwmon layout cache");
+}
+
+/// Re-read every value file of a cached chip
+fn refresh_values(chip: &mut HwmonChip) {
+    for temp in &mut chip.temperatures {
+        temp.current_temp = read_temperature(&temp.input_path).ok();
+        if temp.limits.crit.is_some() {
+            temp.limits.crit_alarm = super::hardware::read_crit_alarm(&temp.input_path);
+        }
+    }
+    for fan in &mut chip.fans {
+        fan.current_rpm = read_fan_rpm(&fan.input_path).ok();
+    }
+    for controller in &mut chip.pwms {
+        controller.current_value = read_pwm_value(&controller.pwm_path).ok();
+        controller.current_percent = controller.current_value.map(pwm::to_percent);
+    }
+    for sensor in &mut chip.sensors {
+        sensor.current_value = read_sensor_value(&sensor.input_path, sensor.kind).ok();
+    }
+    for alarm in &mut chip.alarms {
+        alarm.active = super::hardware::read_alarm_flag(&alarm.alarm_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_reused_until_invalidated() {
+        let base = tempfile::tempdir().unwrap();
+        let chip_dir = base.path().join("hwmon0");
+        fs::create_dir(&chip_dir).unwrap();
+        fs::write(chip_dir.join("name"), "nct6798\n").unwrap();
+        fs::write(chip_dir.join("temp1_input"), "40000\n").unwrap();
+
+        let mut cache = HwmonCache::default();
+        let ttl = Duration::from_secs(60);
+        let chips = cache.chips(base.path(), ttl).unwrap();
+        assert_eq!(chips[0].temperatures[0].current_temp, Some(40.0));
+
+        // Values are re-read, new channels are not picked up until a re-scan
+        fs::write(chip_dir.join("temp1_input"), "45500\n").unwrap();
+        fs::write(chip_dir.join("temp2_input"), "30000\n").unwrap();
+        let chips = cache.chips(base.path(), ttl).unwrap();
+        assert_eq!(chips[0].temperatures.len(), 1);
+        assert_eq!(chips[0].temperatures[0].current_temp, Some(45.5));
+
+        let chips = cache.chips(base.path(), Duration::ZERO).unwrap();
+        assert_eq!(chips[0].temperatures.len(), 2);
+
+        // Removed entries are dropped
+        fs::remove_dir_all(&chip_dir).unwrap();
+        assert!(cache.chips(base.path(), ttl).unwrap().is_empty());
+        assert!(cache.chips.is_empty());
+    }
+}
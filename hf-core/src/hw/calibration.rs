@@ -0,0 +1,303 @@
+//! Fan response calibration
+//!
+//! Sweeps a PWM output from full duty down to 0 in steps, recording the paired
+//! fan's RPM at each one, then steps back up from standstill to find the duty
+//! that restarts it. The resulting table gives the fan's stop and start duty,
+//! its maximum RPM and any dead zones (PWM ranges the RPM does not follow).
+//!
+//! [`calibrated_pwm`] uses the table to linearize the curve output: a percent
+//! becomes that fraction of the fan's maximum RPM rather than of duty cycle.
+//! [`curve_point_issue`] flags curve outputs the fan cannot follow.
+//!
+//! 3-pin fans only follow voltage. On a DC output that has not been
+//! calibrated yet, [`dc_output_pwm`] scales curve output above the voltage
+//! they stall at; on an output left in PWM mode they keep running at full
+//! speed, which [`ignores_pwm`] recognizes in the sweep.
+
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::constants::{calibration, pwm};
+use crate::error::{HyperfanError, Result};
+use crate::hw::{
+    enable_manual_pwm, enumerate_hwmon_chips, read_control_value, read_fan_rpm, read_pwm_value, set_pwm_value,
+    write_control_value,
+};
+use hf_protocol::FanCalibration;
+
+/// Calibrate the fan on `fan_path` driven by `pwm_path`
+///
+/// Blocks for about a minute. `on_step` is called with each PWM value before
+/// it is written, so a caller running a control loop can hold the output.
+/// The original PWM value and enable mode are restored afterwards, also when
+/// the sweep fails.
+pub fn calibrate_fan(pwm_path: &Path, fan_path: &Path, mut on_step: impl FnMut(u8)) -> Result<FanCalibration> {
+    let controller = enumerate_hwmon_chips()?
+        .into_iter()
+        .flat_map(|chip| chip.pwms)
+        .find(|c| c.pwm_path == pwm_path)
+        .ok_or_else(|| HyperfanError::invalid_path(pwm_path, "not a PWM controller"))?;
+
+    let original_pwm = read_pwm_value(pwm_path).ok();
+    let original_enable = read_control_value(&controller.enable_path).ok();
+
+    info!(pwm = ?pwm_path, fan = ?fan_path, "Starting fan calibration");
+    enable_manual_pwm(&controller.enable_path)?;
+    let sweep = sweep(pwm_path, fan_path, &mut on_step);
+
+    if let Some(value) = original_pwm {
+        if let Err(e) = set_pwm_value(pwm_path, value) {
+            warn!(pwm = ?pwm_path, "Failed to restore PWM after calibration: {}", e);
+        }
+    }
+    if let Some(mode) = original_enable {
+        if let Err(e) = write_control_value(&controller.enable_path, &mode) {
+            warn!(pwm = ?pwm_path, "Failed to restore PWM mode after calibration: {}", e);
+        }
+    }
+
+    let (readings, start_pwm) = sweep?;
+    let result = analyze_sweep(pwm_path, fan_path, readings, start_pwm);
+    info!(
+        pwm = ?pwm_path,
+        max_rpm = result.max_rpm,
+        min_pwm = result.min_pwm,
+        start_pwm = ?result.start_pwm,
+        dead_zones = result.dead_zones.len(),
+        "Fan calibration finished"
+    );
+    Ok(result)
+}
+
+/// (PWM, RPM) readings of a sweep
+type Readings = Vec<(u8, u32)>;
+
+/// Downward sweep readings and the restart duty (if the fan stopped)
+fn sweep(pwm_path: &Path, fan_path: &Path, on_step: &mut impl FnMut(u8)) -> Result<(Readings, Option<u8>)> {
+    let mut measure = |value: u8, settle: Duration| -> Result<u32> {
+        on_step(value);
+        set_pwm_value(pwm_path, value)?;
+        thread::sleep(settle);
+        let rpm = read_fan_rpm(fan_path)?;
+        debug!(pwm = value, rpm, "Calibration reading");
+        Ok(rpm)
+    };
+
+    let steps: Vec<u8> = (0..pwm::MAX_VALUE).step_by(calibration::PWM_STEP as usize).collect();
+
+    // Full duty first so the sweep starts from a known state
+    let mut readings = vec![(pwm::MAX_VALUE, measure(pwm::MAX_VALUE, calibration::SPIN_UP)?)];
+    for &value in steps.iter().rev() {
+        readings.push((value, measure(value, calibration::SETTLE)?));
+    }
+
+    let stopped = readings.last().is_some_and(|&(_, rpm)| rpm < calibration::MIN_RESPONSE_RPM);
+    if !stopped {
+        return Ok((readings, None));
+    }
+
+    for &value in steps.iter().skip(1) {
+        if measure(value, calibration::SETTLE)? >= calibration::MIN_RESPONSE_RPM {
+            return Ok((readings, Some(value)));
+        }
+    }
+    Ok((readings, Some(pwm::MAX_VALUE)))
+}
+
+/// Build the calibration table from sweep readings
+fn analyze_sweep(pwm_path: &Path, fan_path: &Path, mut readings: Readings, start_pwm: Option<u8>) -> FanCalibration {
+    readings.sort_by_key(|&(value, _)| value);
+    readings.dedup_by_key(|&mut (value, _)| value);
+
+    let max_rpm = readings.iter().map(|&(_, rpm)| rpm).max().unwrap_or(0);
+    let spinning = |rpm: u32| rpm >= calibration::MIN_RESPONSE_RPM;
+
+    // Lowest step above the highest reading where the fan stood still
+    let min_pwm = match readings.iter().rposition(|&(_, rpm)| !spinning(rpm)) {
+        Some(i) => readings.get(i + 1).map_or(pwm::MAX_VALUE, |&(value, _)| value),
+        None => 0,
+    };
+
+    let tolerance = ((max_rpm as f32 * calibration::DEAD_ZONE_FRACTION) as u32).max(calibration::MIN_RESPONSE_RPM);
+    let mut dead_zones: Vec<(u8, u8)> = Vec::new();
+    for pair in readings.windows(2).filter(|w| w[0].0 >= min_pwm && spinning(w[0].1)) {
+        let ((low, low_rpm), (high, high_rpm)) = (pair[0], pair[1]);
+        if low_rpm.abs_diff(high_rpm) >= tolerance {
+            continue;
+        }
+        match dead_zones.last_mut() {
+            Some(zone) if zone.1 == low => zone.1 = high,
+            _ => dead_zones.push((low, high)),
+        }
+    }
+
+    let calibrated_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    FanCalibration {
+        pwm_path: pwm_path.to_string_lossy().to_string(),
+        fan_path: fan_path.to_string_lossy().to_string(),
+        points: readings,
+        max_rpm,
+        min_pwm,
+        start_pwm,
+        dead_zones,
+        calibrated_at,
+    }
+}
+
+/// PWM value giving `percent` of the fan's maximum RPM
+///
+/// Interpolates the calibration table, so equal curve steps give equal RPM
+/// steps. 0% and 100% still map to 0 and full duty.
+pub fn calibrated_pwm(calibration: &FanCalibration, percent: f32) -> u8 {
+    let percent = percent.clamp(0.0, 100.0);
+    if percent <= 0.0 {
+        return pwm::MIN_VALUE;
+    }
+    if percent >= 100.0 || calibration.max_rpm == 0 {
+        return pwm::from_percent(percent);
+    }
+
+    let target = percent / 100.0 * calibration.max_rpm as f32;
+    let mut previous = (0u8, 0u32);
+    // Running maximum keeps the lookup monotonic despite tach noise
+    let mut envelope = 0u32;
+    for &(value, rpm) in &calibration.points {
+        envelope = envelope.max(rpm);
+        if envelope as f32 >= target {
+            let (low, low_rpm) = previous;
+            if envelope == low_rpm {
+                return value;
+            }
+            let t = (target - low_rpm as f32) / (envelope - low_rpm) as f32;
+            return (low as f32 + t * (value - low) as f32).round() as u8;
+        }
+        previous = (value, envelope);
+    }
+    pwm::MAX_VALUE
+}
+
+/// PWM value for a curve output of `percent` on an uncalibrated DC output
+///
+/// 0% stays off; anything above is scaled into `floor`..full voltage, so the
+/// bottom of the curve doesn't fall where the fan stalls.
+pub fn dc_output_pwm(percent: f32, floor: u8) -> u8 {
+    if percent <= 0.0 {
+        return pwm::MIN_VALUE;
+    }
+    let span = (pwm::MAX_VALUE - floor) as f32;
+    (floor as f32 + percent.min(100.0) / 100.0 * span).round() as u8
+}
+
+/// PWM below which the running fan no longer slows down
+///
+/// The top of a dead zone starting at the lowest duty the fan spins at.
+/// `None` when the fan follows the duty all the way down.
+pub fn response_floor(calibration: &FanCalibration) -> Option<u8> {
+    let lowest = calibration
+        .points
+        .iter()
+        .find(|&&(value, rpm)| value >= calibration.min_pwm && rpm >= calibration::MIN_RESPONSE_RPM)?
+        .0;
+    calibration.dead_zones.iter().find(|&&(low, _)| low == lowest).map(|&(_, high)| high)
+}
+
+/// Whether the fan runs at the same speed at every duty
+///
+/// Typical of a 3-pin fan on an output in PWM mode: it only sees the supply
+/// voltage, so the output has to be switched to DC mode to control it.
+pub fn ignores_pwm(calibration: &FanCalibration) -> bool {
+    response_floor(calibration) == Some(pwm::MAX_VALUE)
+}
+
+/// Why a curve output does not reach the fan as drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurvePointIssue {
+    /// Below the stall duty: raised to `min_pwm` unless zero-RPM is allowed
+    BelowStall { pwm: u8, min_pwm: u8 },
+    /// Inside a PWM range the RPM does not follow
+    DeadZone { pwm: u8, zone: (u8, u8) },
+}
+
+/// Check where a curve output of `percent` lands on a calibrated fan
+pub fn curve_point_issue(calibration: &FanCalibration, percent: f32) -> Option<CurvePointIssue> {
+    let pwm = calibrated_pwm(calibration, percent);
+    if pwm > pwm::MIN_VALUE && pwm < calibration.min_pwm {
+        return Some(CurvePointIssue::BelowStall { pwm, min_pwm: calibration.min_pwm });
+    }
+    calibration
+        .dead_zones
+        .iter()
+        .find(|&&(low, high)| pwm > low && pwm < high)
+        .map(|&zone| CurvePointIssue::DeadZone { pwm, zone })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn readings() -> Vec<(u8, u32)> {
+        // Stops below 48, flat above 208
+        let mut readings = vec![(0, 0), (16, 0), (32, 0)];
+        readings.extend((48..=208).step_by(16).map(|p| (p as u8, 400 + (p as u32 - 48) * 10)));
+        readings.extend([(224, 2000), (240, 2005), (255, 2010)]);
+        readings
+    }
+
+    #[test]
+    fn test_analyze_sweep() {
+        let cal = analyze_sweep(Path::new("/pwm1"), Path::new("/fan1_input"), readings(), Some(64));
+        assert_eq!(cal.max_rpm, 2010);
+        assert_eq!(cal.min_pwm, 48);
+        assert_eq!(cal.start_pwm, Some(64));
+        assert_eq!(cal.dead_zones, vec![(208, 255)]);
+        assert!(cal.points.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_calibrated_pwm() {
+        let cal = analyze_sweep(Path::new("/pwm1"), Path::new("/fan1_input"), readings(), None);
+        assert_eq!(calibrated_pwm(&cal, 0.0), 0);
+        assert_eq!(calibrated_pwm(&cal, 100.0), 255);
+        // 50% of 2010 RPM = 1005 RPM, reached between 96 (880) and 112 (1040)
+        assert_eq!(calibrated_pwm(&cal, 50.0), 109);
+        // Targets below the stall speed land inside the stop zone
+        assert!(calibrated_pwm(&cal, 10.0) < cal.min_pwm);
+    }
+
+    #[test]
+    fn test_curve_point_issue() {
+        let mut cal = analyze_sweep(Path::new("/pwm1"), Path::new("/fan1_input"), readings(), None);
+        assert_eq!(curve_point_issue(&cal, 0.0), None);
+        assert_eq!(curve_point_issue(&cal, 50.0), None);
+        assert!(matches!(curve_point_issue(&cal, 10.0), Some(CurvePointIssue::BelowStall { min_pwm: 48, .. })));
+
+        cal.dead_zones = vec![(96, 128)];
+        assert!(matches!(curve_point_issue(&cal, 50.0), Some(CurvePointIssue::DeadZone { zone: (96, 128), .. })));
+    }
+
+    #[test]
+    fn test_response_floor() {
+        let cal = analyze_sweep(Path::new("/pwm1"), Path::new("/fan1_input"), readings(), None);
+        assert_eq!(response_floor(&cal), None);
+        assert!(!ignores_pwm(&cal));
+
+        // 3-pin fan on a PWM output: full speed whatever the duty
+        let flat: Vec<(u8, u32)> = (0..=240).step_by(16).map(|p| (p as u8, 1500)).chain([(255, 1505)]).collect();
+        let cal = analyze_sweep(Path::new("/pwm1"), Path::new("/fan1_input"), flat, None);
+        assert_eq!(response_floor(&cal), Some(255));
+        assert!(ignores_pwm(&cal));
+    }
+
+    #[test]
+    fn test_dc_output_pwm() {
+        assert_eq!(dc_output_pwm(0.0, 102), 0);
+        assert_eq!(dc_output_pwm(100.0, 102), 255);
+        assert_eq!(dc_output_pwm(50.0, 102), 179);
+    }
+}
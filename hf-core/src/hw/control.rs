@@ -1,6 +1,8 @@
 //! PWM and sensor control functions
 //!
-//! Low-level read/write operations for fan speed control.
+//! Low-level read/write operations for fan speed control. Each call is
+//! dispatched to the [`HwmonBackend`](super::backend::HwmonBackend) that owns
+//! the path (sysfs files on Linux, `sysctl:` OIDs on FreeBSD).
 //!
 //! # PWM Values
 //!
@@ -12,21 +14,217 @@
 //!
 //! Linux hwmon reports temperatures in millidegrees Celsius.
 //! We convert to standard Celsius for user-facing values.
+//!
+//! # Write verification
+//!
+//! A write the driver accepted has not necessarily reached the fan: some
+//! chips ignore it or board firmware reverts it. Duties written to sysfs
+//! `pwmN` files are read back, and a write that did not stick or failed with
+//! a transient error (EAGAIN, EBUSY) is retried with exponential backoff. A
+//! write that never sticks is reported as
+//! [`HyperfanError::PwmWriteVerifyFailed`].
+//!
+//! Chips listed in `WRITE_QUIRKS` additionally get their writes sequenced as
+//! they need.
 
-use crate::error::Result;
+use crate::error::{HyperfanError, Result};
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fs;
-use std::path::Path;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{debug, warn};
+
+use super::backend::{backend_for, is_cooling_device_path, HwmonBackend};
+use crate::constants::pwm;
+use crate::data::SensorKind;
+
+/// How a hwmon driver needs its PWM writes handled
+#[derive(Debug, PartialEq, Eq)]
+struct WriteQuirks {
+    /// Prefixes of the hwmon `name` of the chips this applies to
+    drivers: &'static [&'static str],
+    /// Board firmware flips `pwmN_mode` (DC/PWM) behind the driver's back:
+    /// the mode found at the first write is written back before each duty
+    hold_pwm_mode: bool,
+    /// The chip only applies a duty written while entering manual mode:
+    /// `pwmN_enable` is written again after `pwmN`
+    enable_after_pwm: bool,
+    /// Largest accepted difference between the written and read-back duty
+    pwm_tolerance: u8,
+}
+
+static WRITE_QUIRKS: &[WriteQuirks] = &[
+    // NCT6775-NCT6799, NCT6106/NCT6116: ASUS and MSI firmware switches channels
+    // back to SmartFan and rewrites pwm_mode on its own
+    WriteQuirks { drivers: &["nct6"], hold_pwm_mode: true, enable_after_pwm: false, pwm_tolerance: 0 },
+    // it87: older chips keep a duty written in automatic mode until the next
+    // switch to manual, and have 7-bit duty registers that drop the low bit
+    WriteQuirks { drivers: &["it87", "it86"], hold_pwm_mode: false, enable_after_pwm: true, pwm_tolerance: 1 },
+    // amdgpu stores the duty as a percentage
+    WriteQuirks { drivers: &["amdgpu"], hold_pwm_mode: false, enable_after_pwm: false, pwm_tolerance: 3 },
+];
+
+/// Chips without quirks: plain write, exact read-back
+static NO_QUIRKS: WriteQuirks =
+    WriteQuirks { drivers: &[], hold_pwm_mode: false, enable_after_pwm: false, pwm_tolerance: 0 };
+
+/// `pwmN_mode` of each quirked channel when it was first written
+static HELD_MODES: Mutex<Option<HashMap<PathBuf, String>>> = Mutex::new(None);
+
+fn quirks_for_driver(name: &str) -> Option<&'static WriteQuirks> {
+    WRITE_QUIRKS.iter().find(|q| q.drivers.iter().any(|d| name.starts_with(d)))
+}
+
+/// Quirks of the chip owning a sysfs `pwmN` or `pwmN_enable` file
+/// (None for paths that are not sysfs PWM files, which are written unverified)
+///
+/// The chip name is read on every call: hwmon numbers are reassigned when
+/// drivers are reloaded.
+fn write_quirks(path: &Path) -> Option<&'static WriteQuirks> {
+    if !path.is_absolute() || is_cooling_device_path(path) {
+        return None;
+    }
+    let name = path.parent().and_then(|dir| fs::read_to_string(dir.join("name")).ok());
+    Some(name.and_then(|name| quirks_for_driver(name.trim())).unwrap_or(&NO_QUIRKS))
+}
 
-use crate::constants::{pwm, temperature};
+/// Write errors worth retrying: the driver was busy talking to the chip
+fn is_transient(error: &HyperfanError) -> bool {
+    match error {
+        HyperfanError::FileWrite { source, .. } | HyperfanError::Io(source) => {
+            matches!(source.kind(), ErrorKind::WouldBlock | ErrorKind::ResourceBusy | ErrorKind::Interrupted)
+        }
+        _ => false,
+    }
+}
+
+/// `pwmN` + `suffix` (e.g. `_enable`)
+fn pwm_sibling(pwm_path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(pwm_path.as_os_str());
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Whether `pwm_path` drives its fan by voltage (`pwmN_mode` = 0), as 3-pin fans need
+///
+/// False when the chip has no mode file: most only have PWM outputs.
+pub fn is_dc_mode(pwm_path: &Path) -> bool {
+    if !pwm_path.is_absolute() || is_cooling_device_path(pwm_path) {
+        return false;
+    }
+    fs::read_to_string(pwm_sibling(pwm_path, "_mode")).is_ok_and(|mode| mode.trim().parse() == Ok(pwm::mode::DC))
+}
+
+/// Write back the held `pwmN_mode` if firmware changed it (holding the current one the first time)
+fn restore_held_mode(backend: &dyn HwmonBackend, pwm_path: &Path) {
+    let mode_path = pwm_sibling(pwm_path, "_mode");
+    let Ok(current) = backend.read_raw(&mode_path) else {
+        return;
+    };
+    let mut held = HELD_MODES.lock().unwrap_or_else(|e| e.into_inner());
+    let held = held.get_or_insert_with(HashMap::new).entry(pwm_path.to_path_buf()).or_insert_with(|| current.clone());
+    if *held != current {
+        warn!(path = %mode_path.display(), from = %current, to = %held, "PWM mode changed behind our back, restoring it");
+        if let Err(e) = backend.write_raw(&mode_path, held) {
+            warn!(path = %mode_path.display(), error = %e, "Failed to restore PWM mode");
+        }
+    }
+}
+
+/// Run `attempt` until it succeeds, fails permanently, or WRITE_ATTEMPTS are used up
+/// (the delay doubles after each retry)
+fn with_retries(path: &Path, written: u8, mut attempt: impl FnMut() -> Result<()>) -> Result<()> {
+    let mut delay = pwm::WRITE_RETRY_DELAY;
+    let mut result = attempt();
+    for retry in 1..pwm::WRITE_ATTEMPTS {
+        match &result {
+            Err(e) if is_transient(e) || matches!(e, HyperfanError::PwmWriteVerifyFailed { .. }) => {
+                debug!(path = %path.display(), retry, error = %e, "Write did not stick, retrying");
+            }
+            _ => return result,
+        }
+        std::thread::sleep(delay);
+        delay *= 2;
+        result = attempt();
+    }
+
+    match result {
+        Err(HyperfanError::PwmWriteVerifyFailed { read, .. }) => Err(HyperfanError::PwmWriteVerifyFailed {
+            path: path.to_path_buf(),
+            written,
+            read,
+            attempts: pwm::WRITE_ATTEMPTS,
+        }),
+        Err(e) if is_transient(&e) => Err(HyperfanError::PwmWriteVerifyFailed {
+            path: path.to_path_buf(),
+            written,
+            read: None,
+            attempts: pwm::WRITE_ATTEMPTS,
+        }),
+        result => result,
+    }
+}
+
+/// Mismatch found on one attempt (`with_retries` fills in the final count)
+fn mismatch(path: &Path, written: u8, read: Option<u8>) -> HyperfanError {
+    HyperfanError::PwmWriteVerifyFailed { path: path.to_path_buf(), written, read, attempts: 1 }
+}
+
+fn write_pwm_verified(backend: &dyn HwmonBackend, quirks: &WriteQuirks, pwm_path: &Path, value: u8) -> Result<()> {
+    let enable_path = pwm_sibling(pwm_path, "_enable");
+    with_retries(pwm_path, value, || {
+        if quirks.hold_pwm_mode {
+            restore_held_mode(backend, pwm_path);
+        }
+        backend.write_pwm(pwm_path, value)?;
+
+        // Outside manual mode the chip reports its own duty, so there is nothing to verify
+        let manual = backend
+            .read_raw(&enable_path)
+            .map_or(!enable_path.exists(), |mode| mode == pwm::enable::MANUAL.to_string());
+        if !manual {
+            return Ok(());
+        }
+        if quirks.enable_after_pwm {
+            backend.write_raw(&enable_path, &pwm::enable::MANUAL.to_string())?;
+        }
+
+        let read = backend.read_pwm(pwm_path)?;
+        if read.abs_diff(value) > quirks.pwm_tolerance {
+            return Err(mismatch(pwm_path, value, Some(read)));
+        }
+        Ok(())
+    })
+}
+
+fn write_enable_verified(backend: &dyn HwmonBackend, enable_path: &Path, mode: u8) -> Result<()> {
+    with_retries(enable_path, mode, || {
+        backend.write_raw(enable_path, &mode.to_string())?;
+        let read = backend.read_raw(enable_path)?;
+        if read != mode.to_string() {
+            return Err(mismatch(enable_path, mode, read.parse().ok()));
+        }
+        Ok(())
+    })
+}
 
 /// Set PWM value directly (0-255)
 ///
+/// Writes to sysfs `pwmN` files are verified by reading the value back (see
+/// the module documentation); a write that never takes effect fails with
+/// [`HyperfanError::PwmWriteVerifyFailed`].
+///
 /// # Arguments
 /// * `pwm_path` - Path to the PWM control file (e.g., /sys/class/hwmon/hwmon0/pwm1)
 /// * `value` - PWM value from 0 (off/min) to 255 (full speed)
 pub fn set_pwm_value(pwm_path: &Path, value: u8) -> Result<()> {
-    fs::write(pwm_path, value.to_string())
-        .map_err(|e| crate::error::HyperfanError::PwmWrite { path: pwm_path.to_path_buf(), reason: format!("Failed to write PWM value {}: {}", value, e) })
+    let backend = backend_for(pwm_path)?;
+    match write_quirks(pwm_path) {
+        Some(quirks) => write_pwm_verified(backend, quirks, pwm_path, value),
+        None => backend.write_pwm(pwm_path, value),
+    }
 }
 
 /// Set PWM as percentage (0.0-100.0)
@@ -47,35 +245,40 @@ pub fn set_pwm_percent(pwm_path: &Path, percent: f32) -> Result<()> {
 /// - 1 = manual (software control)
 /// - 2 = automatic (hardware thermal control)
 pub fn enable_manual_pwm(enable_path: &Path) -> Result<()> {
-    if enable_path.exists() {
-        let manual_mode = pwm::enable::MANUAL.to_string();
-        fs::write(enable_path, &manual_mode)
-            .map_err(|e| crate::error::HyperfanError::PwmWrite { path: enable_path.to_path_buf(), reason: format!("Failed to enable manual PWM control: {}", e) })
-    } else {
-        Ok(()) // No enable file means manual control is always active
+    let backend = backend_for(enable_path)?;
+    match write_quirks(enable_path) {
+        Some(quirks) if *quirks != NO_QUIRKS && enable_path.exists() => {
+            write_enable_verified(backend, enable_path, pwm::enable::MANUAL)
+        }
+        _ => backend.enable_manual(enable_path),
+    }
+}
+
+/// Return a PWM channel to automatic (firmware/driver) control
+pub fn enable_auto_pwm(enable_path: &Path) -> Result<()> {
+    let backend = backend_for(enable_path)?;
+    match write_quirks(enable_path) {
+        Some(quirks) if *quirks != NO_QUIRKS && enable_path.exists() => {
+            // Firmware owns the mode again
+            if let Some(pwm_path) = enable_path.to_str().and_then(|p| p.strip_suffix("_enable")) {
+                HELD_MODES.lock().unwrap_or_else(|e| e.into_inner())
+                    .get_or_insert_with(HashMap::new)
+                    .remove(Path::new(pwm_path));
+            }
+            write_enable_verified(backend, enable_path, pwm::enable::AUTOMATIC)
+        }
+        _ => backend.enable_auto(enable_path),
     }
 }
 
 /// Read current PWM value (0-255)
 pub fn read_pwm_value(pwm_path: &Path) -> Result<u8> {
-    let content = fs::read_to_string(pwm_path)
-        .map_err(|e| crate::error::HyperfanError::PwmRead { path: pwm_path.to_path_buf(), reason: format!("Failed to read: {}", e) })?;
-
-    content
-        .trim()
-        .parse::<u8>()
-        .map_err(|e| crate::error::HyperfanError::PwmRead { path: pwm_path.to_path_buf(), reason: format!("Failed to parse '{}': {}", content.trim(), e) })
+    backend_for(pwm_path)?.read_pwm(pwm_path)
 }
 
 /// Read current fan speed in RPM
 pub fn read_fan_rpm(fan_path: &Path) -> Result<u32> {
-    let content = fs::read_to_string(fan_path)
-        .map_err(|e| crate::error::HyperfanError::FanRead { path: fan_path.to_path_buf(), reason: format!("Failed to read: {}", e) })?;
-
-    content
-        .trim()
-        .parse::<u32>()
-        .map_err(|e| crate::error::HyperfanError::FanRead { path: fan_path.to_path_buf(), reason: format!("Failed to parse '{}': {}", content.trim(), e) })
+    backend_for(fan_path)?.read_fan_rpm(fan_path)
 }
 
 /// Read temperature sensor value in degrees Celsius
@@ -83,14 +286,54 @@ pub fn read_fan_rpm(fan_path: &Path) -> Result<u32> {
 /// Linux hwmon reports temperatures in millidegrees (e.g., 45000 = 45.0°C).
 /// This function handles the conversion automatically.
 pub fn read_temperature(temp_path: &Path) -> Result<f32> {
-    let content = fs::read_to_string(temp_path)
-        .map_err(|e| crate::error::HyperfanError::TemperatureRead { path: temp_path.to_path_buf(), reason: format!("Failed to read: {}", e) })?;
+    backend_for(temp_path)?.read_temperature(temp_path)
+}
+
+/// Read a voltage, current, power or humidity channel in base units
+/// (V, A, W or %RH) according to `kind`
+pub fn read_sensor_value(path: &Path, kind: SensorKind) -> Result<f32> {
+    backend_for(path)?.read_sensor(path, kind)
+}
+
+/// Whether a sensor or control path exists on its backend
+pub fn control_path_exists(path: &Path) -> bool {
+    backend_for(path).is_ok_and(|backend| backend.exists(path))
+}
+
+/// Read a control value verbatim, trimmed (e.g. a `pwmN_enable` mode)
+pub fn read_control_value(path: &Path) -> Result<String> {
+    backend_for(path)?.read_raw(path)
+}
+
+/// Write a control value verbatim
+pub fn write_control_value(path: &Path, value: &str) -> Result<()> {
+    backend_for(path)?.write_raw(path, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let millidegrees = content
-        .trim()
-        .parse::<i32>()
-        .map_err(|e| crate::error::HyperfanError::TemperatureRead { path: temp_path.to_path_buf(), reason: format!("Failed to parse '{}': {}", content.trim(), e) })?;
+    #[test]
+    fn test_quirked_pwm_write() {
+        assert_eq!(quirks_for_driver("nct6798").map(|q| q.hold_pwm_mode), Some(true));
+        assert_eq!(quirks_for_driver("it8688").map(|q| q.enable_after_pwm), Some(true));
+        assert!(quirks_for_driver("k10temp").is_none());
 
-    // Convert millidegrees to degrees Celsius
-    Ok(millidegrees as f32 / temperature::MILLIDEGREE_DIVISOR)
+        let chip = tempfile::tempdir().unwrap();
+        fs::write(chip.path().join("name"), "nct6798\n").unwrap();
+        fs::write(chip.path().join("pwm1"), "255").unwrap();
+        fs::write(chip.path().join("pwm1_enable"), "2").unwrap();
+        fs::write(chip.path().join("pwm1_mode"), "1").unwrap();
+        let pwm = chip.path().join("pwm1");
+
+        enable_manual_pwm(&chip.path().join("pwm1_enable")).unwrap();
+        set_pwm_value(&pwm, 128).unwrap();
+        assert_eq!(fs::read_to_string(&pwm).unwrap(), "128");
+
+        // Firmware switched the channel to DC mode: the held mode is restored
+        fs::write(chip.path().join("pwm1_mode"), "0").unwrap();
+        set_pwm_value(&pwm, 100).unwrap();
+        assert_eq!(fs::read_to_string(chip.path().join("pwm1_mode")).unwrap(), "1");
+    }
 }
@@ -7,8 +7,15 @@
 //!
 //! 1. **Active Probing** (primary): Sets each PWM to 0%, measures which
 //!    fans slow down, calculates confidence based on RPM drop percentage.
+//!    Chips are probed concurrently, each against its own fans; PWMs left
+//!    unmatched are then retried one at a time against the remaining fans.
 //!
-//! 2. **Heuristic Matching** (fallback): Matches fans to PWMs by index
+//! 2. **Passive Observation**: Never writes a PWM. Samples every PWM value
+//!    and fan RPM over a longer window and pairs outputs with the fans whose
+//!    speed follows them, for systems where fans must not stop. Only outputs
+//!    that actually change during the window (curves, firmware) can be matched.
+//!
+//! 3. **Heuristic Matching** (fallback): Matches fans to PWMs by index
 //!    (fan1 -> pwm1) or label similarity when probing isn't possible.
 //!
 //! # Fingerprinting
@@ -16,16 +23,20 @@
 //! Detection now also creates comprehensive fingerprints for all discovered
 //! sensors to prevent mispairing across reboots.
 
-use crate::error::Result;
+use crate::error::{HyperfanError, Result};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
 use std::thread;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, trace, warn};
 
 use crate::constants::{
     detection::{confidence_scores, heuristic, rpm_drop_thresholds},
     limits,
+    pwm,
     timing,
 };
 use crate::data::{FanMapping, HwmonChip, PwmController, TempSource};
@@ -34,6 +45,7 @@ use crate::hw::fingerprint::{
     extract_chip_fingerprint, extract_channel_fingerprint, extract_pwm_fingerprint,
     ChannelType, PwmProbeData,
 };
+use crate::hw::control::{read_fan_rpm, read_pwm_value, set_pwm_value};
 use crate::hw::hardware::{check_pwm_permissions, enumerate_hwmon_chips};
 
 /// Ultra-advanced auto-detection with active probing for accurate PWM/FAN pairing.
@@ -180,6 +192,19 @@ fn count_common_prefix_chars(string_a: &str, string_b: &str) -> usize {
 
 /// Advanced probing-based auto-detection with systematic PWM testing
 pub fn autodetect_fan_pwm_mappings_advanced() -> Result<Vec<FanMapping>> {
+    autodetect_fan_pwm_mappings_observed(&AtomicBool::new(false), |_, _, _| {})
+}
+
+/// Active probing with progress reporting and cancellation
+///
+/// `on_step(step, total, pwm_name)` is called before each PWM output is
+/// tested, counting from 1. Setting `cancel` stops the probe at its next
+/// wait with [`HyperfanError::Cancelled`]. The PWM values and enable modes
+/// found at the start are restored however the probe ends.
+pub fn autodetect_fan_pwm_mappings_observed(
+    cancel: &AtomicBool,
+    mut on_step: impl FnMut(usize, usize, &str) + Send,
+) -> Result<Vec<FanMapping>> {
     let chips = enumerate_hwmon_chips()?;
 
     let total_pwm_count: usize = chips.iter().map(|c| c.pwms.len()).sum();
@@ -239,6 +264,7 @@ pub fn autodetect_fan_pwm_mappings_advanced() -> Result<Vec<FanMapping>> {
     }
 
     let mut original_states: Vec<(PathBuf, u8)> = Vec::new();
+    let mut original_modes: Vec<(PathBuf, String)> = Vec::new();
     let mut all_pwms: Vec<&PwmController> = Vec::new();
     let mut all_fans: Vec<(PathBuf, String)> = Vec::new();
 
@@ -249,6 +275,9 @@ pub fn autodetect_fan_pwm_mappings_advanced() -> Result<Vec<FanMapping>> {
                     original_states.push((pwm.pwm_path.clone(), value));
                 }
             }
+            if let Ok(mode) = fs::read_to_string(&pwm.enable_path) {
+                original_modes.push((pwm.enable_path.clone(), mode.trim().to_string()));
+            }
             all_pwms.push(pwm);
         }
 
@@ -259,9 +288,53 @@ pub fn autodetect_fan_pwm_mappings_advanced() -> Result<Vec<FanMapping>> {
 
     debug!(fan_count = all_fans.len(), "Fans to monitor");
 
+    let result = probe_pwms(&chips, &all_pwms, &all_fans, cancel, &mut on_step);
+
+    info!("Restoring original PWM states");
+    for (path, value) in &original_states {
+        let _ = fs::write(path, value.to_string());
+    }
+    for (path, mode) in &original_modes {
+        let _ = fs::write(path, mode);
+    }
+
+    let mappings = result?;
+    info!(mappings_found = mappings.len(), "Detection complete");
+
+    if mappings.is_empty() {
+        debug!("No mappings found via probing, falling back to heuristic method");
+        return autodetect_fan_pwm_mappings_heuristic();
+    }
+
+    Ok(mappings)
+}
+
+/// Sleep for `duration` unless `cancel` is set first
+fn wait_unless_cancelled(duration: Duration, cancel: &AtomicBool) -> Result<()> {
+    let deadline = Instant::now() + duration;
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(HyperfanError::Cancelled("fan detection".to_string()));
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(());
+        }
+        thread::sleep(remaining.min(timing::DETECTION_CANCEL_POLL));
+    }
+}
+
+/// Ramp every PWM to full speed, then drop each one and match the fan that slows
+fn probe_pwms(
+    chips: &[HwmonChip],
+    all_pwms: &[&PwmController],
+    all_fans: &[(PathBuf, String)],
+    cancel: &AtomicBool,
+    on_step: &mut (impl FnMut(usize, usize, &str) + Send),
+) -> Result<Vec<FanMapping>> {
     info!("Step 1: Ramping all fans to 100%");
-    for pwm in &all_pwms {
-        let pwm_name = path_to_pwm_name(&pwm.pwm_path, &chips);
+    for pwm in all_pwms {
+        let pwm_name = path_to_pwm_name(&pwm.pwm_path, chips);
         trace!(pwm = %pwm_name, "Setting up PWM controller");
 
         if pwm.enable_path.exists() {
@@ -272,7 +345,7 @@ pub fn autodetect_fan_pwm_mappings_advanced() -> Result<Vec<FanMapping>> {
             }
         }
 
-        if let Err(e) = fs::write(&pwm.pwm_path, "255") {
+        if let Err(e) = set_pwm_value(&pwm.pwm_path, pwm::MAX_VALUE) {
             return Err(crate::error::HyperfanError::PwmWrite {
                 path: pwm.pwm_path.clone(),
                 reason: format!("Failed to set PWM to 100%: {}. Check permissions.", e)
@@ -281,148 +354,342 @@ pub fn autodetect_fan_pwm_mappings_advanced() -> Result<Vec<FanMapping>> {
     }
 
     debug!("Waiting for fans to reach full speed");
-    thread::sleep(timing::FAN_SPINUP);
+    wait_unless_cancelled(timing::FAN_SPINUP, cancel)?;
+
+    // Chips are probed in parallel, one PWM at a time within a chip. Each
+    // worker only watches its own chip's fans so concurrent drops on other
+    // chips cannot be mistaken for a response.
+    let started = AtomicUsize::new(0);
+    let on_step = Mutex::new(on_step);
+    let report = |pwm_name: &str| {
+        let step = started.fetch_add(1, Ordering::Relaxed) + 1;
+        (on_step.lock().unwrap_or_else(|e| e.into_inner()))(step, all_pwms.len(), pwm_name);
+    };
+
+    let results: Vec<Result<ChipProbe>> = thread::scope(|scope| {
+        let workers: Vec<_> = chips
+            .iter()
+            .filter(|chip| !chip.pwms.is_empty())
+            .map(|chip| scope.spawn(|| probe_chip(chip, chips, cancel, &report)))
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|_| Err(HyperfanError::generic("Detection worker panicked")))
+            })
+            .collect()
+    });
 
     let mut mappings = Vec::new();
+    let mut unmatched = Vec::new();
+    for result in results {
+        let probe = result?;
+        mappings.extend(probe.mappings);
+        unmatched.extend(probe.unmatched);
+    }
 
-    for (pwm_index, pwm) in all_pwms.iter().enumerate() {
-        let pwm_name = path_to_pwm_name(&pwm.pwm_path, &chips);
-        info!(step = pwm_index + 1, pwm = %pwm_name, "Testing PWM controller");
-
-        let mut baseline_rpms: HashMap<PathBuf, u32> = HashMap::new();
-        for (fan_path, _) in &all_fans {
-            if let Ok(content) = fs::read_to_string(fan_path) {
-                if let Ok(rpm) = content.trim().parse::<u32>() {
-                    baseline_rpms.insert(fan_path.clone(), rpm);
-                }
-            }
-        }
-
-        if let Err(e) = fs::write(&pwm.pwm_path, "0") {
-            warn!(pwm = %pwm_name, error = %e, "Failed to set PWM to 0");
+    // PWMs driving a fan on another chip (fan cooling devices, split
+    // controllers): retried alone against the fans nobody has claimed yet
+    for (chip, pwm) in unmatched {
+        let candidates: Vec<(PathBuf, String)> = all_fans
+            .iter()
+            .filter(|(path, _)| !chip.fans.iter().any(|fan| fan.input_path == *path))
+            .filter(|(_, name)| !mappings.iter().any(|m| &m.fan_name == name))
+            .cloned()
+            .collect();
+        if candidates.is_empty() {
             continue;
         }
+        debug!(chip = %chip.name, pwm = %pwm.name, "Retrying PWM against fans on other chips");
+        if let Some(mapping) = probe_one(pwm, chips, &candidates, cancel)? {
+            mappings.push(mapping);
+        }
+    }
 
-        trace!("Waiting for fan response");
-        thread::sleep(timing::FAN_STABILIZATION);
+    Ok(mappings)
+}
 
-        let mut test_rpms: HashMap<PathBuf, u32> = HashMap::new();
-        for (fan_path, _) in &all_fans {
-            if let Ok(content) = fs::read_to_string(fan_path) {
-                if let Ok(rpm) = content.trim().parse::<u32>() {
-                    test_rpms.insert(fan_path.clone(), rpm);
-                }
+/// Mappings found on one chip and the PWMs none of its fans responded to
+struct ChipProbe<'a> {
+    mappings: Vec<FanMapping>,
+    unmatched: Vec<(&'a HwmonChip, &'a PwmController)>,
+}
+
+/// Probe a chip's PWMs one after another against its own fans
+fn probe_chip<'a>(
+    chip: &'a HwmonChip,
+    chips: &[HwmonChip],
+    cancel: &AtomicBool,
+    report: &(impl Fn(&str) + Sync),
+) -> Result<ChipProbe<'a>> {
+    let fans: Vec<(PathBuf, String)> = chip
+        .fans
+        .iter()
+        .map(|fan| (fan.input_path.clone(), format!("{}/{}", chip.name, fan.name)))
+        .collect();
+
+    let mut probe = ChipProbe { mappings: Vec::new(), unmatched: Vec::new() };
+    for pwm in &chip.pwms {
+        report(&path_to_pwm_name(&pwm.pwm_path, chips));
+        let mapping = if fans.is_empty() { None } else { probe_one(pwm, chips, &fans, cancel)? };
+        match mapping {
+            Some(mapping) => probe.mappings.push(mapping),
+            None => probe.unmatched.push((chip, pwm)),
+        }
+    }
+    Ok(probe)
+}
+
+/// Drop one PWM to 0 and match the watched fan that slows down the most
+fn probe_one(
+    pwm: &PwmController,
+    chips: &[HwmonChip],
+    fans: &[(PathBuf, String)],
+    cancel: &AtomicBool,
+) -> Result<Option<FanMapping>> {
+    let pwm_name = path_to_pwm_name(&pwm.pwm_path, chips);
+    info!(pwm = %pwm_name, "Testing PWM controller");
+
+    let mut baseline_rpms: HashMap<PathBuf, u32> = HashMap::new();
+    for (fan_path, _) in fans {
+        if let Ok(content) = fs::read_to_string(fan_path) {
+            if let Ok(rpm) = content.trim().parse::<u32>() {
+                baseline_rpms.insert(fan_path.clone(), rpm);
             }
         }
+    }
 
-        let mut best_match: Option<(PathBuf, i32, f32)> = None;
-        let mut any_fans_detected = false;
+    if let Err(e) = set_pwm_value(&pwm.pwm_path, pwm::MIN_VALUE) {
+        warn!(pwm = %pwm_name, error = %e, "Failed to set PWM to 0");
+        return Ok(None);
+    }
 
-        for (fan_path, fan_name) in &all_fans {
-            if let (Some(baseline), Some(test)) =
-                (baseline_rpms.get(fan_path), test_rpms.get(fan_path))
-            {
-                any_fans_detected = true;
-                let rpm_drop = (*baseline as i32) - (*test as i32);
+    trace!("Waiting for fan response");
+    wait_unless_cancelled(timing::FAN_STABILIZATION, cancel)?;
 
-                // Calculate confidence based on how much the fan slowed down
-                let confidence = if *baseline > 0 {
-                    let percent_drop = (rpm_drop as f32) / (*baseline as f32) * 100.0;
+    let mut test_rpms: HashMap<PathBuf, u32> = HashMap::new();
+    for (fan_path, _) in fans {
+        if let Ok(content) = fs::read_to_string(fan_path) {
+            if let Ok(rpm) = content.trim().parse::<u32>() {
+                test_rpms.insert(fan_path.clone(), rpm);
+            }
+        }
+    }
 
-                    // Higher percentage drops indicate stronger PWM-fan correlation
-                    if percent_drop > rpm_drop_thresholds::VERY_HIGH {
-                        confidence_scores::VERY_HIGH_DROP
-                    } else if percent_drop > rpm_drop_thresholds::HIGH {
-                        confidence_scores::HIGH_DROP
-                    } else if percent_drop > rpm_drop_thresholds::MEDIUM {
-                        confidence_scores::MEDIUM_DROP
-                    } else if percent_drop > rpm_drop_thresholds::LOW {
-                        confidence_scores::LOW_DROP
-                    } else if percent_drop > rpm_drop_thresholds::MINIMAL {
-                        confidence_scores::MINIMAL_DROP
-                    } else if rpm_drop > crate::constants::detection::MIN_RPM_DROP {
-                        // Small percentage but absolute drop exceeds threshold
-                        confidence_scores::ABSOLUTE_DROP
-                    } else {
-                        0.0 // No significant response detected
-                    }
+    let mut best_match: Option<(PathBuf, i32, f32)> = None;
+    let mut any_fans_detected = false;
+
+    for (fan_path, fan_name) in fans {
+        if let (Some(baseline), Some(test)) =
+            (baseline_rpms.get(fan_path), test_rpms.get(fan_path))
+        {
+            any_fans_detected = true;
+            let rpm_drop = (*baseline as i32) - (*test as i32);
+
+            // Calculate confidence based on how much the fan slowed down
+            let confidence = if *baseline > 0 {
+                let percent_drop = (rpm_drop as f32) / (*baseline as f32) * 100.0;
+
+                // Higher percentage drops indicate stronger PWM-fan correlation
+                if percent_drop > rpm_drop_thresholds::VERY_HIGH {
+                    confidence_scores::VERY_HIGH_DROP
+                } else if percent_drop > rpm_drop_thresholds::HIGH {
+                    confidence_scores::HIGH_DROP
+                } else if percent_drop > rpm_drop_thresholds::MEDIUM {
+                    confidence_scores::MEDIUM_DROP
+                } else if percent_drop > rpm_drop_thresholds::LOW {
+                    confidence_scores::LOW_DROP
+                } else if percent_drop > rpm_drop_thresholds::MINIMAL {
+                    confidence_scores::MINIMAL_DROP
+                } else if rpm_drop > crate::constants::detection::MIN_RPM_DROP {
+                    // Small percentage but absolute drop exceeds threshold
+                    confidence_scores::ABSOLUTE_DROP
                 } else {
-                    0.0 // Can't calculate confidence without baseline RPM
-                };
+                    0.0 // No significant response detected
+                }
+            } else {
+                0.0 // Can't calculate confidence without baseline RPM
+            };
 
-                trace!(
-                    fan = %fan_name,
-                    baseline_rpm = baseline,
-                    test_rpm = test,
-                    rpm_drop = rpm_drop,
-                    confidence = format!("{:.2}", confidence),
-                    "Fan response measured"
-                );
+            trace!(
+                fan = %fan_name,
+                baseline_rpm = baseline,
+                test_rpm = test,
+                rpm_drop = rpm_drop,
+                confidence = format!("{:.2}", confidence),
+                "Fan response measured"
+            );
 
-                if rpm_drop > crate::constants::detection::MIN_RPM_DROP && confidence > crate::constants::detection::MIN_CONFIDENCE {
-                    if let Some((_, current_drop, current_conf)) = &best_match {
-                        if rpm_drop > *current_drop
-                            || (rpm_drop == *current_drop && confidence > *current_conf)
-                        {
-                            best_match = Some((fan_path.clone(), rpm_drop, confidence));
-                        }
-                    } else {
+            if rpm_drop > crate::constants::detection::MIN_RPM_DROP && confidence > crate::constants::detection::MIN_CONFIDENCE {
+                if let Some((_, current_drop, current_conf)) = &best_match {
+                    if rpm_drop > *current_drop
+                        || (rpm_drop == *current_drop && confidence > *current_conf)
+                    {
                         best_match = Some((fan_path.clone(), rpm_drop, confidence));
                     }
+                } else {
+                    best_match = Some((fan_path.clone(), rpm_drop, confidence));
                 }
-            } else {
-                trace!(fan = %fan_name, "Could not read RPM values");
             }
+        } else {
+            trace!(fan = %fan_name, "Could not read RPM values");
         }
+    }
 
-        if !any_fans_detected {
-            warn!(pwm = %pwm_name, "No fan RPM readings available");
-        }
+    if !any_fans_detected {
+        warn!(pwm = %pwm_name, "No fan RPM readings available");
+    }
 
-        if let Some((fan_path, rpm_drop, confidence)) = best_match {
-            let temp_sources = collect_temp_sources(&chips);
-            let fan_name = path_to_fan_name(&fan_path, &chips);
+    let mapping = best_match.map(|(fan_path, rpm_drop, confidence)| {
+        let temp_sources = collect_temp_sources(chips);
+        let fan_name = path_to_fan_name(&fan_path, chips);
 
-            info!(
-                pwm = %pwm_name,
-                fan = %fan_name,
-                rpm_drop = rpm_drop,
-                confidence = format!("{:.2}", confidence),
-                "Matched PWM to fan"
-            );
+        info!(
+            pwm = %pwm_name,
+            fan = %fan_name,
+            rpm_drop = rpm_drop,
+            confidence = format!("{:.2}", confidence),
+            "Matched PWM to fan"
+        );
 
-            mappings.push(FanMapping {
-                fan_name: fan_name.clone(),
-                pwm_name: path_to_pwm_name(&pwm.pwm_path, &chips),
-                confidence,
-                temp_sources,
-                response_time_ms: Some(timing::FAN_STABILIZATION_MS),
-                min_pwm: None,
-                max_rpm: baseline_rpms.get(&fan_path).copied(),
-            });
-        } else {
-            debug!(pwm = %pwm_name, "No clear fan match found");
+        FanMapping {
+            fan_name: fan_name.clone(),
+            pwm_name: path_to_pwm_name(&pwm.pwm_path, chips),
+            confidence,
+            temp_sources,
+            response_time_ms: Some(timing::FAN_STABILIZATION_MS),
+            min_pwm: None,
+            max_rpm: baseline_rpms.get(&fan_path).copied(),
         }
+    });
+    if mapping.is_none() {
+        debug!(pwm = %pwm_name, "No clear fan match found");
+    }
 
-        let _ = fs::write(&pwm.pwm_path, "255");
-        thread::sleep(timing::DETECTION_DELAY);
+    let _ = set_pwm_value(&pwm.pwm_path, pwm::MAX_VALUE);
+    wait_unless_cancelled(timing::DETECTION_DELAY, cancel)?;
+
+    Ok(mapping)
+}
+
+/// Passive detection: match PWM outputs to fans by watching, not probing
+///
+/// Samples for `window` without touching any output. `on_step(sample, total,
+/// "")` is called after each sample; `cancel` stops the observation with
+/// [`HyperfanError::Cancelled`]. Falls back to heuristic matching when no
+/// output moved enough to be correlated.
+pub fn autodetect_fan_pwm_mappings_passive(
+    window: Duration,
+    cancel: &AtomicBool,
+    mut on_step: impl FnMut(usize, usize, &str),
+) -> Result<Vec<FanMapping>> {
+    let chips = enumerate_hwmon_chips()?;
+    let pwms: Vec<&PwmController> = chips.iter().flat_map(|c| &c.pwms).collect();
+    let fans: Vec<&Path> = chips.iter().flat_map(|c| &c.fans).map(|f| f.input_path.as_path()).collect();
+    if pwms.is_empty() {
+        return Err(HyperfanError::HardwareNotFound("No PWM controllers found on this system".to_string()));
     }
 
-    info!("Restoring original PWM states");
-    for (path, value) in &original_states {
-        let _ = fs::write(path, value.to_string());
+    let total = (window.as_millis() / timing::PASSIVE_SAMPLE_INTERVAL.as_millis().max(1)).max(2) as usize;
+    info!(pwm_count = pwms.len(), fan_count = fans.len(), samples = total, "Starting passive PWM-fan detection");
+
+    let mut pwm_series: Vec<Vec<f32>> = vec![Vec::with_capacity(total); pwms.len()];
+    let mut fan_series: Vec<Vec<f32>> = vec![Vec::with_capacity(total); fans.len()];
+    for sample in 1..=total {
+        // Unreadable values repeat the last one so the series stay aligned
+        for (series, pwm) in pwm_series.iter_mut().zip(&pwms) {
+            let value = read_pwm_value(&pwm.pwm_path).ok().map(f32::from);
+            series.push(value.or(series.last().copied()).unwrap_or(0.0));
+        }
+        for (series, fan) in fan_series.iter_mut().zip(&fans) {
+            let rpm = read_fan_rpm(fan).ok().map(|rpm| rpm as f32);
+            series.push(rpm.or(series.last().copied()).unwrap_or(0.0));
+        }
+        on_step(sample, total, "");
+        if sample < total {
+            wait_unless_cancelled(timing::PASSIVE_SAMPLE_INTERVAL, cancel)?;
+        }
     }
 
-    info!(mappings_found = mappings.len(), "Detection complete");
+    let swing = |series: &[f32]| {
+        let (min, max) = series.iter().fold((f32::MAX, f32::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+        max - min
+    };
+    let mut candidates: Vec<(f32, usize, usize)> = Vec::new();
+    for (p, pwm_values) in pwm_series.iter().enumerate() {
+        if swing(pwm_values) < f32::from(crate::constants::detection::PASSIVE_MIN_PWM_SWING) {
+            debug!(pwm = %path_to_pwm_name(&pwms[p].pwm_path, &chips), "PWM did not change during observation");
+            continue;
+        }
+        for (f, rpms) in fan_series.iter().enumerate() {
+            if swing(rpms) < crate::constants::detection::PASSIVE_MIN_RPM_SWING as f32 {
+                continue;
+            }
+            let score = lagged_correlation(pwm_values, rpms, crate::constants::detection::PASSIVE_MAX_LAG_SAMPLES);
+            trace!(pwm = %pwms[p].name, fan = ?fans[f], score, "Passive correlation");
+            if score >= crate::constants::detection::PASSIVE_MIN_CORRELATION {
+                candidates.push((score, p, f));
+            }
+        }
+    }
+
+    // Strongest pairs first; each output and each fan is used once
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let (mut used_pwms, mut used_fans) = (vec![false; pwms.len()], vec![false; fans.len()]);
+    let mut mappings = Vec::new();
+    for (score, p, f) in candidates {
+        if used_pwms[p] || used_fans[f] {
+            continue;
+        }
+        used_pwms[p] = true;
+        used_fans[f] = true;
+        let pwm_name = path_to_pwm_name(&pwms[p].pwm_path, &chips);
+        let fan_name = path_to_fan_name(fans[f], &chips);
+        info!(pwm = %pwm_name, fan = %fan_name, correlation = score, "Passive match");
+        mappings.push(FanMapping {
+            fan_name,
+            pwm_name,
+            confidence: score.min(1.0),
+            temp_sources: collect_temp_sources(&chips),
+            response_time_ms: None,
+            min_pwm: None,
+            max_rpm: fan_series[f].iter().copied().reduce(f32::max).map(|rpm| rpm as u32),
+        });
+    }
+    info!(mappings_found = mappings.len(), "Passive detection complete");
 
     if mappings.is_empty() {
-        debug!("No mappings found via probing, falling back to heuristic method");
+        debug!("No PWM output moved enough to correlate, falling back to heuristic method");
         return autodetect_fan_pwm_mappings_heuristic();
     }
-
     Ok(mappings)
 }
 
+/// Best Pearson correlation of `rpms` against `pwm_values` delayed by up to `max_lag` samples
+fn lagged_correlation(pwm_values: &[f32], rpms: &[f32], max_lag: usize) -> f32 {
+    (0..=max_lag)
+        .filter(|&lag| pwm_values.len() > lag + 2)
+        .map(|lag| pearson(&pwm_values[..pwm_values.len() - lag], &rpms[lag..]))
+        .fold(0.0, f32::max)
+}
+
+fn pearson(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len()) as f32;
+    let (mean_a, mean_b) = (a.iter().sum::<f32>() / n, b.iter().sum::<f32>() / n);
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        cov += (x - mean_a) * (y - mean_b);
+        var_a += (x - mean_a).powi(2);
+        var_b += (y - mean_b).powi(2);
+    }
+    if var_a == 0.0 || var_b == 0.0 {
+        return 0.0;
+    }
+    cov / (var_a.sqrt() * var_b.sqrt())
+}
+
 fn collect_temp_sources(chips: &[HwmonChip]) -> Vec<TempSource> {
     let mut sources = Vec::new();
 
@@ -600,7 +867,7 @@ pub fn autodetect_with_fingerprints() -> Result<FingerprintedDetectionResult> {
         if pwm.enable_path.exists() {
             let _ = fs::write(&pwm.enable_path, "1");
         }
-        if let Err(e) = fs::write(&pwm.pwm_path, "255") {
+        if let Err(e) = set_pwm_value(&pwm.pwm_path, pwm::MAX_VALUE) {
             warn!(path = ?pwm.pwm_path, error = %e, "Failed to set PWM to 100%");
         }
     }
@@ -625,7 +892,7 @@ pub fn autodetect_with_fingerprints() -> Result<FingerprintedDetectionResult> {
         }
         
         // Set PWM to 0
-        if let Err(e) = fs::write(&pwm.pwm_path, "0") {
+        if let Err(e) = set_pwm_value(&pwm.pwm_path, pwm::MIN_VALUE) {
             warn!(pwm = %pwm_name, error = %e, "Failed to set PWM to 0");
             continue;
         }
@@ -734,7 +1001,7 @@ pub fn autodetect_with_fingerprints() -> Result<FingerprintedDetectionResult> {
         }
         
         // Restore PWM to 100%
-        let _ = fs::write(&pwm.pwm_path, "255");
+        let _ = set_pwm_value(&pwm.pwm_path, pwm::MAX_VALUE);
         thread::sleep(timing::DETECTION_DELAY);
     }
     
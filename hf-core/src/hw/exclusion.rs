@@ -0,0 +1,219 @@
+//! Controller Exclusion
+//!
+//! Some outputs must never be driven: PSU fans wired to a board header, pumps
+//! owned by their own controller. The `controller_filter` setting lists hwmon
+//! chips, or single PWM channels of a chip, by chip fingerprint (see
+//! [`generate_chip_id`]), so entries survive `hwmonN` renumbering.
+//!
+//! - `Blacklist`: a chip entry hides the whole chip from enumeration; a
+//!   channel entry hides that PWM output only.
+//! - `Allowlist`: only the listed chips and channels keep their PWM outputs.
+//!   Unlisted chips still report temperatures and fan speeds.
+//!
+//! Enumeration, detection and the daemon's control loop all work from the
+//! filtered chip list; the daemon also refuses direct writes to hidden outputs.
+//! Only sysfs hwmon chips are filtered.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{debug, info};
+
+use super::fingerprint::{extract_chip_fingerprint, generate_chip_id};
+use crate::data::HwmonChip;
+
+/// How the entries of a [`ControllerFilter`] are applied
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControllerFilterMode {
+    /// Listed chips and channels are left alone
+    #[default]
+    Blacklist,
+    /// Only listed chips and channels may be controlled
+    Allowlist,
+}
+
+/// One chip or PWM channel in the controller filter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControllerFilterEntry {
+    /// Chip fingerprint id ([`generate_chip_id`])
+    pub chip_id: String,
+    /// PWM channel (`pwm2`); `None` matches the whole chip
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Chip name and path when the entry was added, for display
+    #[serde(default)]
+    pub label: String,
+}
+
+/// Chips and PWM channels excluded from enumeration, detection and control
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControllerFilter {
+    #[serde(default)]
+    pub mode: ControllerFilterMode,
+    #[serde(default)]
+    pub entries: Vec<ControllerFilterEntry>,
+}
+
+impl ControllerFilter {
+    /// Whether the filter changes nothing (an empty allowlist hides every output)
+    pub fn is_passthrough(&self) -> bool {
+        self.mode == ControllerFilterMode::Blacklist && self.entries.is_empty()
+    }
+
+    fn listed(&self, chip_id: &str, channel: Option<&str>) -> bool {
+        self.entries.iter().any(|e| {
+            e.chip_id == chip_id && (e.channel.is_none() || e.channel.as_deref() == channel)
+        })
+    }
+
+    /// Whether the chip `chip_id` is left out of enumeration entirely
+    pub fn hides_chip(&self, chip_id: &str) -> bool {
+        self.mode == ControllerFilterMode::Blacklist
+            && self.entries.iter().any(|e| e.chip_id == chip_id && e.channel.is_none())
+    }
+
+    /// Whether PWM `channel` of chip `chip_id` may be enumerated and controlled
+    pub fn allows_pwm(&self, chip_id: &str, channel: &str) -> bool {
+        match self.mode {
+            ControllerFilterMode::Blacklist => !self.listed(chip_id, Some(channel)),
+            ControllerFilterMode::Allowlist => self.listed(chip_id, Some(channel)),
+        }
+    }
+}
+
+/// Active filter; `None` until loaded from the settings or set by the daemon
+static FILTER: Mutex<Option<ControllerFilter>> = Mutex::new(None);
+
+/// Chip fingerprint ids by `hwmonN` path (and the device it linked to)
+type ChipIds = HashMap<PathBuf, (Option<PathBuf>, String)>;
+
+static CHIP_IDS: Mutex<Option<ChipIds>> = Mutex::new(None);
+
+/// The active controller filter (read from the settings on first use)
+pub fn controller_filter() -> ControllerFilter {
+    let mut filter = FILTER.lock().unwrap_or_else(|e| e.into_inner());
+    filter
+        .get_or_insert_with(|| crate::settings::load_settings().map(|s| s.controller_filter).unwrap_or_default())
+        .clone()
+}
+
+/// Replace the active controller filter, e.g. after the settings were reloaded
+/// Cached hwmon layouts are re-scanned when it changed.
+pub fn set_controller_filter(filter: ControllerFilter) {
+    let changed = {
+        let mut current = FILTER.lock().unwrap_or_else(|e| e.into_inner());
+        let changed = current.as_ref() != Some(&filter);
+        *current = Some(filter);
+        changed
+    };
+    if changed {
+        info!("Controller filter updated");
+        super::invalidate_hwmon_cache(None);
+    }
+}
+
+/// Fingerprint id of the hwmon chip at `hwmon_dir`
+pub fn chip_fingerprint_id(hwmon_dir: &Path) -> Option<String> {
+    let device = std::fs::read_link(hwmon_dir).ok();
+    let mut ids = CHIP_IDS.lock().unwrap_or_else(|e| e.into_inner());
+    let ids = ids.get_or_insert_with(HashMap::new);
+    if let Some((cached_device, id)) = ids.get(hwmon_dir) {
+        if *cached_device == device {
+            return Some(id.clone());
+        }
+    }
+    let id = generate_chip_id(&extract_chip_fingerprint(hwmon_dir)?);
+    ids.insert(hwmon_dir.to_path_buf(), (device, id.clone()));
+    Some(id)
+}
+
+/// Filter entry for the hwmon chip at `path`, or the PWM output at `path`
+pub fn controller_filter_entry(path: &Path, chip_name: &str) -> Option<ControllerFilterEntry> {
+    let (hwmon_dir, channel) = match path.file_name()?.to_str()? {
+        name if name.starts_with("pwm") => (path.parent()?, Some(name.to_string())),
+        _ => (path, None),
+    };
+    Some(ControllerFilterEntry {
+        chip_id: chip_fingerprint_id(hwmon_dir)?,
+        channel,
+        label: format!("{} ({})", chip_name, hwmon_dir.display()),
+    })
+}
+
+/// Whether the PWM output at `pwm_path` is hidden by the active filter
+pub fn is_pwm_excluded(pwm_path: &Path) -> bool {
+    if !pwm_path.starts_with("/sys/") || super::is_cooling_device_path(pwm_path) {
+        return false;
+    }
+    let filter = controller_filter();
+    if filter.is_passthrough() {
+        return false;
+    }
+    let (Some(hwmon_dir), Some(channel)) = (pwm_path.parent(), pwm_path.file_name().and_then(|n| n.to_str())) else {
+        return false;
+    };
+    chip_fingerprint_id(hwmon_dir).is_some_and(|id| filter.hides_chip(&id) || !filter.allows_pwm(&id, channel))
+}
+
+/// Apply the active filter to a freshly read chip; `None` hides the chip
+pub(super) fn filter_chip(mut chip: HwmonChip) -> Option<HwmonChip> {
+    let filter = controller_filter();
+    if filter.is_passthrough() {
+        return Some(chip);
+    }
+    let Some(id) = chip_fingerprint_id(&chip.path) else {
+        return Some(chip);
+    };
+    if filter.hides_chip(&id) {
+        debug!(chip = %chip.name, path = ?chip.path, "Chip excluded by controller filter");
+        return None;
+    }
+    chip.pwms.retain(|pwm| {
+        let allowed = filter.allows_pwm(&id, &pwm.name);
+        if !allowed {
+            debug!(chip = %chip.name, pwm = %pwm.name, "PWM excluded by controller filter");
+        }
+        allowed
+    });
+    Some(chip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(chip_id: &str, channel: Option<&str>) -> ControllerFilterEntry {
+        ControllerFilterEntry { chip_id: chip_id.into(), channel: channel.map(Into::into), label: String::new() }
+    }
+
+    #[test]
+    fn test_blacklist() {
+        let filter = ControllerFilter {
+            mode: ControllerFilterMode::Blacklist,
+            entries: vec![entry("psu", None), entry("board", Some("pwm3"))],
+        };
+        assert!(filter.hides_chip("psu"));
+        assert!(!filter.hides_chip("board"));
+        assert!(!filter.allows_pwm("board", "pwm3"));
+        assert!(filter.allows_pwm("board", "pwm1"));
+        assert!(filter.allows_pwm("other", "pwm3"));
+        assert!(ControllerFilter::default().is_passthrough());
+    }
+
+    #[test]
+    fn test_allowlist() {
+        let filter = ControllerFilter {
+            mode: ControllerFilterMode::Allowlist,
+            entries: vec![entry("board", Some("pwm1")), entry("aio", None)],
+        };
+        // Allowlists never hide chips, only their outputs
+        assert!(!filter.hides_chip("psu"));
+        assert!(!filter.allows_pwm("psu", "pwm1"));
+        assert!(filter.allows_pwm("board", "pwm1"));
+        assert!(!filter.allows_pwm("board", "pwm2"));
+        assert!(filter.allows_pwm("aio", "pwm2"));
+        assert!(!filter.is_passthrough());
+    }
+}
@@ -0,0 +1,299 @@
+//! FreeBSD sysctl backend
+//!
+//! FreeBSD has no hwmon; sensors are sysctl OIDs:
+//! - `dev.cpu.N.temperature` - per-core temperature (coretemp/amdtemp)
+//! - `hw.acpi.thermal.tzN.temperature` - ACPI thermal zones
+//! - `dev.acpi_ibm.0.fan_speed` / `fan_level` / `fan` - ThinkPad fan
+//!   (acpi_ibm(4)): RPM, level 0-7, and 1 = firmware control / 0 = manual
+//!
+//! Paths are handed out as `sysctl:<oid>` and read or written with sysctl(8).
+//! The acpi_ibm fan level is exposed as a regular 0-255 PWM channel so curves
+//! work unchanged; it only takes effect while `fan` is 0, so writing a level
+//! switches the fan to manual first.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::{debug, info};
+
+use super::backend::HwmonBackend;
+use crate::constants::{paths, pwm};
+use crate::data::{FanSensor, HwmonChip, PwmController, SensorKind, TemperatureLimits, TemperatureSensor};
+use crate::error::{HyperfanError, Result};
+
+/// acpi_ibm(4) device node
+const ACPI_IBM: &str = "dev.acpi_ibm.0";
+
+/// Highest acpi_ibm fan level
+const FAN_LEVEL_MAX: u8 = 7;
+
+/// acpi_ibm `fan` values
+const FAN_MODE_MANUAL: &str = "0";
+const FAN_MODE_AUTO: &str = "1";
+
+/// Subtrees listed during enumeration
+const SYSCTL_TREES: &[&str] = &["dev.cpu", "hw.acpi.thermal", ACPI_IBM];
+
+/// sysctl access (FreeBSD, DragonFly)
+pub struct SysctlBackend;
+
+fn sysctl_path(oid: &str) -> PathBuf {
+    PathBuf::from(format!("{}{}", paths::SYSCTL_PREFIX, oid))
+}
+
+/// OID named by a `sysctl:` path
+fn oid_of(path: &Path) -> Result<&str> {
+    path.to_str()
+        .and_then(|p| p.strip_prefix(paths::SYSCTL_PREFIX))
+        .filter(|oid| !oid.is_empty() && oid.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+        .ok_or_else(|| HyperfanError::invalid_path(path, "not a sysctl OID"))
+}
+
+fn sysctl_get(oid: &str) -> Result<String> {
+    let output = Command::new("sysctl").args(["-n", oid]).output()?;
+    if !output.status.success() {
+        return Err(HyperfanError::Generic(format!(
+            "sysctl {} failed: {}",
+            oid,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn sysctl_set(oid: &str, value: &str) -> Result<()> {
+    let output = Command::new("sysctl").arg(format!("{}={}", oid, value)).output()?;
+    if !output.status.success() {
+        return Err(HyperfanError::Generic(format!(
+            "sysctl {}={} failed: {}",
+            oid,
+            value,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Parse a sysctl temperature ("45.0C")
+fn parse_celsius(value: &str) -> Option<f32> {
+    value.trim().trim_end_matches('C').parse().ok()
+}
+
+fn level_to_pwm(level: u8) -> u8 {
+    ((level.min(FAN_LEVEL_MAX) as u32 * pwm::MAX_VALUE as u32 + FAN_LEVEL_MAX as u32 / 2) / FAN_LEVEL_MAX as u32) as u8
+}
+
+fn pwm_to_level(value: u8) -> u8 {
+    ((value as u32 * FAN_LEVEL_MAX as u32 + pwm::MAX_VALUE as u32 / 2) / pwm::MAX_VALUE as u32) as u8
+}
+
+/// Whether an OID is the acpi_ibm fan level
+fn is_fan_level(oid: &str) -> bool {
+    oid.strip_prefix(ACPI_IBM) == Some(".fan_level")
+}
+
+/// Build chips from `sysctl <trees>` output ("name: value" lines)
+fn parse_sysctl_tree(output: &str) -> Vec<HwmonChip> {
+    let mut cpu_temps = Vec::new();
+    let mut acpi_temps = Vec::new();
+    let mut fans = Vec::new();
+    let mut pwms = Vec::new();
+
+    for line in output.lines() {
+        let Some((oid, value)) = line.split_once(": ") else {
+            continue;
+        };
+
+        // dev.cpu.0.temperature: 45.0C
+        if let Some(cpu) = oid.strip_prefix("dev.cpu.").and_then(|r| r.strip_suffix(".temperature")) {
+            cpu_temps.push(TemperatureSensor {
+                name: format!("temp{}", cpu),
+                input_path: sysctl_path(oid),
+                label: Some(format!("CPU {}", cpu)),
+                current_temp: parse_celsius(value),
+                limits: TemperatureLimits::default(),
+            });
+        // hw.acpi.thermal.tz0.temperature: 50.0C
+        } else if let Some(zone) = oid.strip_prefix("hw.acpi.thermal.").and_then(|r| r.strip_suffix(".temperature")) {
+            acpi_temps.push(TemperatureSensor {
+                name: zone.to_string(),
+                input_path: sysctl_path(oid),
+                label: Some(format!("ACPI Thermal Zone {}", zone)),
+                current_temp: parse_celsius(value),
+                limits: TemperatureLimits::default(),
+            });
+        } else if let Some(field) = oid.strip_prefix(ACPI_IBM).and_then(|r| r.strip_prefix('.')) {
+            match field {
+                "fan_speed" => fans.push(FanSensor {
+                    name: "fan1".to_string(),
+                    input_path: sysctl_path(oid),
+                    label: Some("ThinkPad Fan".to_string()),
+                    current_rpm: value.trim().parse().ok(),
+                }),
+                "fan_level" => {
+                    let current_value = value.trim().parse().ok().map(level_to_pwm);
+                    pwms.push(PwmController {
+                        name: "pwm1".to_string(),
+                        pwm_path: sysctl_path(oid),
+                        enable_path: sysctl_path(&format!("{}.fan", ACPI_IBM)),
+                        label: Some("ThinkPad Fan".to_string()),
+                        current_value,
+                        current_percent: current_value.map(pwm::to_percent),
+                        cooling_device: None,
+                        dc_mode: false,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut chips = Vec::new();
+    for (name, path, temperatures) in [("cpu", "dev.cpu", cpu_temps), ("acpi_thermal", "hw.acpi.thermal", acpi_temps)] {
+        if !temperatures.is_empty() {
+            chips.push(HwmonChip {
+                name: name.to_string(),
+                path: sysctl_path(path),
+                temperatures,
+                fans: Vec::new(),
+                pwms: Vec::new(),
+                sensors: Vec::new(),
+                alarms: Vec::new(),
+            });
+        }
+    }
+    if !fans.is_empty() || !pwms.is_empty() {
+        chips.push(HwmonChip {
+            name: "acpi_ibm".to_string(),
+            path: sysctl_path(ACPI_IBM),
+            temperatures: Vec::new(),
+            fans,
+            pwms,
+            sensors: Vec::new(),
+            alarms: Vec::new(),
+        });
+    }
+    chips
+}
+
+impl HwmonBackend for SysctlBackend {
+    fn name(&self) -> &'static str {
+        "sysctl"
+    }
+
+    fn enumerate(&self) -> Result<Vec<HwmonChip>> {
+        debug!("Scanning FreeBSD sensors via sysctl");
+
+        // Missing subtrees (no acpi_ibm on non-ThinkPads) make sysctl exit
+        // non-zero but the others are still printed
+        let output = Command::new("sysctl").args(SYSCTL_TREES).output()?;
+        let chips = parse_sysctl_tree(&String::from_utf8_lossy(&output.stdout));
+
+        info!("FreeBSD sensors found: {} chips", chips.len());
+        Ok(chips)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        oid_of(path).is_ok_and(|oid| sysctl_get(oid).is_ok())
+    }
+
+    fn read_temperature(&self, path: &Path) -> Result<f32> {
+        let value = sysctl_get(oid_of(path)?)?;
+        parse_celsius(&value).ok_or_else(|| HyperfanError::TemperatureRead {
+            path: path.to_path_buf(),
+            reason: format!("Failed to parse '{}'", value),
+        })
+    }
+
+    fn read_fan_rpm(&self, path: &Path) -> Result<u32> {
+        let value = sysctl_get(oid_of(path)?)?;
+        value.parse().map_err(|e| HyperfanError::FanRead {
+            path: path.to_path_buf(),
+            reason: format!("Failed to parse '{}': {}", value, e),
+        })
+    }
+
+    fn read_sensor(&self, path: &Path, kind: SensorKind) -> Result<f32> {
+        Err(HyperfanError::NotSupported(format!("{} sensors via sysctl: {}", kind, path.display())))
+    }
+
+    fn read_pwm(&self, path: &Path) -> Result<u8> {
+        let oid = oid_of(path)?;
+        let value = sysctl_get(oid)?;
+        let parsed: u8 = value.parse().map_err(|e| HyperfanError::PwmRead {
+            path: path.to_path_buf(),
+            reason: format!("Failed to parse '{}': {}", value, e),
+        })?;
+        Ok(if is_fan_level(oid) { level_to_pwm(parsed) } else { parsed })
+    }
+
+    fn write_pwm(&self, path: &Path, value: u8) -> Result<()> {
+        let oid = oid_of(path)?;
+        if !is_fan_level(oid) {
+            return Err(HyperfanError::NotSupported(format!("PWM control via {}", oid)));
+        }
+
+        let mode_oid = format!("{}.fan", ACPI_IBM);
+        if sysctl_get(&mode_oid)? != FAN_MODE_MANUAL {
+            sysctl_set(&mode_oid, FAN_MODE_MANUAL)?;
+        }
+        sysctl_set(oid, &pwm_to_level(value).to_string()).map_err(|e| HyperfanError::PwmWrite {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// Any acpi_ibm path selects the fan's mode OID
+    fn enable_manual(&self, enable_path: &Path) -> Result<()> {
+        oid_of(enable_path)?;
+        sysctl_set(&format!("{}.fan", ACPI_IBM), FAN_MODE_MANUAL)
+    }
+
+    fn enable_auto(&self, enable_path: &Path) -> Result<()> {
+        oid_of(enable_path)?;
+        sysctl_set(&format!("{}.fan", ACPI_IBM), FAN_MODE_AUTO)
+    }
+
+    fn read_raw(&self, path: &Path) -> Result<String> {
+        sysctl_get(oid_of(path)?)
+    }
+
+    fn write_raw(&self, path: &Path, value: &str) -> Result<()> {
+        sysctl_set(oid_of(path)?, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sysctl_tree() {
+        let output = "dev.cpu.0.temperature: 45.0C\n\
+                      dev.cpu.0.freq: 2400\n\
+                      dev.cpu.1.temperature: 47.5C\n\
+                      hw.acpi.thermal.tz0.temperature: 50.0C\n\
+                      dev.acpi_ibm.0.fan_speed: 2650\n\
+                      dev.acpi_ibm.0.fan_level: 7\n\
+                      dev.acpi_ibm.0.fan: 1\n";
+        let chips = parse_sysctl_tree(output);
+        assert_eq!(chips.len(), 3);
+        assert_eq!(chips[0].temperatures[1].current_temp, Some(47.5));
+        assert_eq!(chips[0].temperatures[1].input_path, PathBuf::from("sysctl:dev.cpu.1.temperature"));
+        assert_eq!(chips[1].temperatures[0].name, "tz0");
+
+        let fan = &chips[2];
+        assert_eq!(fan.fans[0].current_rpm, Some(2650));
+        assert_eq!(fan.pwms[0].current_value, Some(255));
+        assert_eq!(fan.pwms[0].enable_path, PathBuf::from("sysctl:dev.acpi_ibm.0.fan"));
+    }
+
+    #[test]
+    fn test_fan_level_mapping() {
+        for level in 0..=FAN_LEVEL_MAX {
+            assert_eq!(pwm_to_level(level_to_pwm(level)), level);
+        }
+        assert_eq!(pwm_to_level(0), 0);
+        assert_eq!(pwm_to_level(128), 4);
+        assert_eq!(pwm_to_level(255), FAN_LEVEL_MAX);
+    }
+}
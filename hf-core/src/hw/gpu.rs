@@ -19,19 +19,46 @@ pub fn set_nvidia_fan_speed(gpu_index: u32, fan_index: u32, percent: u32) -> Res
 }
 
 /// Set fan speed for an AMD GPU via sysfs (wrapper for compatibility)
-pub fn set_amd_fan_speed(hwmon_path: &Path, percent: u32) -> Result<()> {
-    hf_gpu::amd::set_fan_speed(&hwmon_path.to_string_lossy(), percent)
+/// `pwm_path` is the channel's `pwmN` file
+pub fn set_amd_fan_speed(pwm_path: &Path, percent: u32) -> Result<()> {
+    hf_gpu::amd::set_fan_speed(&pwm_path.to_string_lossy(), percent)
         .map_err(|e| e.into())
 }
 
 /// Reset AMD GPU fan to automatic control (wrapper for compatibility)
-pub fn reset_amd_fan_auto(hwmon_path: &Path) -> Result<()> {
-    hf_gpu::amd::reset_fan_auto(&hwmon_path.to_string_lossy())
+/// `pwm_path` is the channel's `pwmN` file
+pub fn reset_amd_fan_auto(pwm_path: &Path) -> Result<()> {
+    hf_gpu::amd::reset_fan_auto(&pwm_path.to_string_lossy())
         .map_err(|e| e.into())
 }
 
+/// Read the firmware fan curve of AMD GPU card `card_index` (PMFW, RDNA3+)
+pub fn get_amd_fan_curve(card_index: u32) -> Result<hf_gpu::GpuFanCurve> {
+    hf_gpu::amd::read_fan_curve(card_index)
+}
+
+/// Replace the firmware fan curve of AMD GPU card `card_index`
+pub fn set_amd_fan_curve(card_index: u32, points: &[hf_gpu::GpuFanCurvePoint]) -> Result<()> {
+    hf_gpu::amd::set_fan_curve(card_index, points)
+}
+
+/// Restore the factory firmware fan curve of AMD GPU card `card_index`
+pub fn reset_amd_fan_curve(card_index: u32) -> Result<()> {
+    hf_gpu::amd::reset_fan_curve(card_index)
+}
+
 /// Reset NVIDIA GPU fan to automatic control (wrapper for compatibility)
 pub fn reset_nvidia_fan_auto(gpu_index: u32) -> Result<()> {
     hf_gpu::nvidia::reset_fan_auto(gpu_index)
         .map_err(|e| e.into())
 }
+
+/// Read the power limit of GPU `index` (NVIDIA index or AMD DRM card number)
+pub fn get_gpu_power_limit(vendor: hf_gpu::GpuVendor, index: u32) -> Result<hf_gpu::GpuPowerLimit> {
+    hf_gpu::read_gpu_power_limit(vendor, index)
+}
+
+/// Set the power limit of GPU `index` to `watts`
+pub fn set_gpu_power_limit(vendor: hf_gpu::GpuVendor, index: u32, watts: u32) -> Result<()> {
+    hf_gpu::set_gpu_power_limit(vendor, index, watts)
+}
@@ -0,0 +1,156 @@
+//! GPU Fan Reconciliation
+//!
+//! amdgpu, i915 and xe expose GPU fan outputs as ordinary hwmon `pwmN` files,
+//! and hf-gpu lists the same outputs again as [`GpuPwmController`]s. Listing
+//! both shows every such fan twice, and two controls on one output fight.
+//!
+//! [`reconcile_gpu_pwms`] matches the two by PCI address (the hwmon chip's
+//! `device` link against the controller's bus ID) and channel. The hwmon
+//! output stays as the single entry, since that path is what detection,
+//! fingerprint bindings and the control loop work with; the matched
+//! controller is dropped from the GPU list and kept as the output's vendor
+//! tag. NVIDIA fans have no hwmon output and pass through unchanged.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use hf_gpu::{GpuFanBackend, GpuPwmController, GpuVendor};
+
+/// GPU controllers after merging the ones that duplicate a hwmon output
+#[derive(Debug, Clone, Default)]
+pub struct GpuPwmReconciliation {
+    /// Controllers with no hwmon output of their own, to list alongside the chips
+    pub controllers: Vec<GpuPwmController>,
+    /// hwmon `pwmN` path -> GPU controller for the same output
+    merged: HashMap<String, GpuPwmController>,
+}
+
+impl GpuPwmReconciliation {
+    /// GPU controller merged into the hwmon output at `pwm_path`
+    pub fn gpu_for(&self, pwm_path: &str) -> Option<&GpuPwmController> {
+        self.merged.get(pwm_path)
+    }
+
+    /// Vendor of the GPU whose fan the hwmon output at `pwm_path` drives
+    pub fn vendor_for(&self, pwm_path: &str) -> Option<GpuVendor> {
+        self.gpu_for(pwm_path).map(|gpu| gpu.vendor)
+    }
+
+    /// Number of controllers merged into hwmon outputs
+    pub fn merged_count(&self) -> usize {
+        self.merged.len()
+    }
+}
+
+/// Merge GPU controllers into the hwmon chips (`hwmonN` dirs) exposing the same outputs
+pub fn reconcile_gpu_pwms<P: AsRef<Path>>(
+    chip_dirs: impl IntoIterator<Item = P>,
+    controllers: Vec<GpuPwmController>,
+) -> GpuPwmReconciliation {
+    let chips: Vec<(String, PathBuf)> = chip_dirs
+        .into_iter()
+        .filter_map(|dir| {
+            let dir = dir.as_ref();
+            chip_pci_address(dir).map(|address| (address, dir.to_path_buf()))
+        })
+        .collect();
+    reconcile(&chips, controllers)
+}
+
+/// Normalized PCI address of the device behind a hwmon chip, if it is a PCI device
+pub fn chip_pci_address(chip_dir: &Path) -> Option<String> {
+    let device = fs::canonicalize(chip_dir.join("device")).ok()?;
+    let name = device.file_name()?.to_str()?;
+    // Platform and I2C devices ("nct6775.656", "0-002e") have no bus:device.function
+    if !name.contains(':') || !name.contains('.') {
+        return None;
+    }
+    Some(hf_gpu::pci::normalize_bus_id(name))
+}
+
+/// Match controllers to (PCI address, chip dir) pairs by address and `pwmN` channel
+fn reconcile(chips: &[(String, PathBuf)], controllers: Vec<GpuPwmController>) -> GpuPwmReconciliation {
+    let mut result = GpuPwmReconciliation::default();
+    for controller in controllers {
+        let hwmon_pwm = sysfs_channel(&controller).and_then(|(address, channel)| {
+            chips
+                .iter()
+                .find(|(chip_address, dir)| *chip_address == address && dir.join(&channel).exists())
+                .map(|(_, dir)| dir.join(channel).to_string_lossy().into_owned())
+        });
+        match hwmon_pwm {
+            Some(pwm_path) => {
+                tracing::debug!("GPU controller {} is hwmon output {}", controller.id, pwm_path);
+                result.merged.insert(pwm_path, controller);
+            }
+            None => result.controllers.push(controller),
+        }
+    }
+    result
+}
+
+/// PCI address and `pwmN` file name of a controller written through hwmon
+fn sysfs_channel(controller: &GpuPwmController) -> Option<(String, String)> {
+    if controller.backend != GpuFanBackend::Sysfs {
+        return None;
+    }
+    let address = hf_gpu::pci::normalize_bus_id(controller.pci_bus_id.as_deref()?);
+    let channel = Path::new(&controller.pwm_path).file_name()?.to_str()?.to_string();
+    Some((address, channel))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller(id: &str, vendor: GpuVendor, pwm_path: &str, bus_id: &str, backend: GpuFanBackend) -> GpuPwmController {
+        GpuPwmController {
+            id: id.into(),
+            name: id.into(),
+            vendor,
+            gpu_index: 0,
+            fan_index: 0,
+            pwm_path: pwm_path.into(),
+            fan_input_path: None,
+            current_percent: None,
+            current_rpm: None,
+            manual_control: false,
+            pci_bus_id: Some(bus_id.into()),
+            backend,
+        }
+    }
+
+    #[test]
+    fn test_reconcile() {
+        let dir = std::env::temp_dir().join(format!("hf-gpu-reconcile-{}", std::process::id()));
+        let gpu_chip = dir.join("hwmon3");
+        let board_chip = dir.join("hwmon1");
+        fs::create_dir_all(&gpu_chip).unwrap();
+        fs::create_dir_all(&board_chip).unwrap();
+        fs::write(gpu_chip.join("pwm1"), "128").unwrap();
+        fs::write(board_chip.join("pwm1"), "128").unwrap();
+
+        let chips = vec![
+            ("0000:03:00.0".to_string(), gpu_chip.clone()),
+            ("0000:00:1f.3".to_string(), board_chip.clone()),
+        ];
+        let controllers = vec![
+            // Same output reached through /sys/class/drm
+            controller("amd:0:0", GpuVendor::Amd, "/sys/class/drm/card0/device/hwmon/hwmon3/pwm1", "0000:03:00.0", GpuFanBackend::Sysfs),
+            // No pwm2 on the chip
+            controller("amd:0:1", GpuVendor::Amd, "/sys/class/drm/card0/device/hwmon/hwmon3/pwm2", "0000:03:00.0", GpuFanBackend::Sysfs),
+            controller("nvidia:0:0", GpuVendor::Nvidia, "nvidia:0:0", "00000000:03:00.0", GpuFanBackend::Nvml),
+        ];
+        let result = reconcile(&chips, controllers);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let merged = gpu_chip.join("pwm1").to_string_lossy().into_owned();
+        assert_eq!(result.gpu_for(&merged).map(|c| c.id.as_str()), Some("amd:0:0"));
+        assert_eq!(result.vendor_for(&merged), Some(GpuVendor::Amd));
+        assert_eq!(result.vendor_for(&board_chip.join("pwm1").to_string_lossy()), None);
+        assert_eq!(result.merged_count(), 1);
+        let ids: Vec<&str> = result.controllers.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["amd:0:1", "nvidia:0:0"]);
+    }
+}
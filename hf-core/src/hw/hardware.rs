@@ -2,7 +2,7 @@
 //!
 //! Cross-platform support for hardware monitoring:
 //! - **Linux**: Uses `/sys/class/hwmon` (hwmon subsystem)
-//! - **FreeBSD**: Uses `sysctl dev.cpu` and `dev.acpi_thermal`
+//! - **FreeBSD**: Uses `sysctl dev.cpu`, `hw.acpi.thermal` and `dev.acpi_ibm` (see `freebsd`)
 //! - **OpenBSD/NetBSD**: Uses `sysctl hw.sensors`
 //!
 //! # Sensor Types
@@ -10,14 +10,25 @@
 //! - **Temperature**: `tempN_input` files (millidegrees Celsius)
 //! - **Fan**: `fanN_input` files (RPM)
 //! - **PWM**: `pwmN` files (0-255 duty cycle)
+//! - **Voltage/Current/Power/Humidity**: `inN_input` (mV), `currN_input` (mA),
+//!   `powerN_input` or `powerN_average` (µW), `humidityN_input` (m%RH)
+//! - **Alarms**: `fanN_alarm`, `tempN_alarm`, `intrusionN_alarm` (0/1), with
+//!   the matching `_beep` file where the chip has one
 
 use crate::error::Result;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+#[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
+use std::process::Command;
 use tracing::{debug, info, trace, warn};
 
-use crate::constants::{paths, temperature};
-use crate::data::{FanSensor, HwmonChip, PwmController, TemperatureSensor};
+use super::backend::HwmonBackend;
+use crate::constants::{cooling, paths, temperature, thermal_zone};
+use crate::data::{
+    CoolingDevice, FanSensor, HwmonAlarm, HwmonChip, HwmonSensor, PwmController, SensorKind, TemperatureLimits,
+    TemperatureSensor,
+};
+use hf_protocol::AlarmKind;
 
 /// Enumerate all hwmon chips and their sensors
 /// Works on Linux (hwmon), FreeBSD (sysctl), and OpenBSD/NetBSD (hw.sensors)
@@ -32,7 +43,8 @@ pub fn enumerate_hwmon_chips() -> Result<Vec<HwmonChip>> {
     // Try BSD sysctl-based detection
     #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
     {
-        return enumerate_freebsd_sensors();
+        use super::backend::HwmonBackend;
+        return super::freebsd::SysctlBackend.enumerate();
     }
     
     #[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
@@ -46,7 +58,7 @@ pub fn enumerate_hwmon_chips() -> Result<Vec<HwmonChip>> {
 }
 
 /// Linux hwmon enumeration
-fn enumerate_linux_hwmon(hwmon_path: &Path) -> Result<Vec<HwmonChip>> {
+pub(super) fn enumerate_linux_hwmon(hwmon_path: &Path) -> Result<Vec<HwmonChip>> {
     let mut chips = Vec::new();
     
     debug!("Scanning Linux hwmon chips in {:?}", hwmon_path);
@@ -56,12 +68,13 @@ fn enumerate_linux_hwmon(hwmon_path: &Path) -> Result<Vec<HwmonChip>> {
         let path = entry.path();
         trace!("Checking hwmon device: {:?}", path);
 
-        if let Some(chip) = read_hwmon_chip(&path)? {
+        if let Some(chip) = read_hwmon_chip(&path)?.and_then(super::exclusion::filter_chip) {
             info!(
                 chip = %chip.name,
                 temps = chip.temperatures.len(),
                 fans = chip.fans.len(),
                 pwms = chip.pwms.len(),
+                sensors = chip.sensors.len(),
                 "Found hwmon chip"
             );
             chips.push(chip);
@@ -70,79 +83,9 @@ fn enumerate_linux_hwmon(hwmon_path: &Path) -> Result<Vec<HwmonChip>> {
         }
     }
 
-    info!("Total hwmon chips found: {}", chips.len());
-    Ok(chips)
-}
+    add_extra_sources(&mut chips);
 
-/// FreeBSD sensor enumeration using sysctl
-#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
-fn enumerate_freebsd_sensors() -> Result<Vec<HwmonChip>> {
-    let mut chips = Vec::new();
-    
-    debug!("Scanning FreeBSD sensors via sysctl");
-    
-    // Get CPU temperatures (dev.cpu.N.temperature)
-    if let Ok(output) = Command::new("sysctl").args(["-a"]).output() {
-        if output.status.success() {
-            let sysctl_output = String::from_utf8_lossy(&output.stdout);
-            
-            let mut cpu_temps = Vec::new();
-            let mut acpi_temps = Vec::new();
-            
-            for line in sysctl_output.lines() {
-                // CPU temperature: dev.cpu.0.temperature: 45.0C
-                if line.starts_with("dev.cpu.") && line.contains(".temperature:") {
-                    if let Some(temp) = parse_bsd_temperature(line) {
-                        let parts: Vec<&str> = line.split('.').collect();
-                        if parts.len() >= 3 {
-                            let cpu_num = parts[2];
-                            cpu_temps.push(TemperatureSensor {
-                                name: format!("temp{}", cpu_num),
-                                input_path: PathBuf::from(format!("sysctl:dev.cpu.{}.temperature", cpu_num)),
-                                label: Some(format!("CPU {}", cpu_num)),
-                                current_temp: Some(temp),
-                            });
-                        }
-                    }
-                }
-                
-                // ACPI thermal zones: hw.acpi.thermal.tz0.temperature: 50.0C
-                if line.contains("acpi.thermal") && line.contains(".temperature:") {
-                    if let Some(temp) = parse_bsd_temperature(line) {
-                        let name = line.split(':').next().unwrap_or("unknown");
-                        acpi_temps.push(TemperatureSensor {
-                            name: name.replace("hw.acpi.thermal.", "").replace(".temperature", ""),
-                            input_path: PathBuf::from(format!("sysctl:{}", name)),
-                            label: Some("ACPI Thermal Zone".to_string()),
-                            current_temp: Some(temp),
-                        });
-                    }
-                }
-            }
-            
-            if !cpu_temps.is_empty() {
-                chips.push(HwmonChip {
-                    name: "cpu".to_string(),
-                    path: PathBuf::from("sysctl:dev.cpu"),
-                    temperatures: cpu_temps,
-                    fans: Vec::new(),
-                    pwms: Vec::new(),
-                });
-            }
-            
-            if !acpi_temps.is_empty() {
-                chips.push(HwmonChip {
-                    name: "acpi_thermal".to_string(),
-                    path: PathBuf::from("sysctl:hw.acpi.thermal"),
-                    temperatures: acpi_temps,
-                    fans: Vec::new(),
-                    pwms: Vec::new(),
-                });
-            }
-        }
-    }
-    
-    info!("FreeBSD sensors found: {} chips", chips.len());
+    info!("Total hwmon chips found: {}", chips.len());
     Ok(chips)
 }
 
@@ -181,6 +124,8 @@ fn enumerate_openbsd_sensors() -> Result<Vec<HwmonChip>> {
                                 temperatures: Vec::new(),
                                 fans: Vec::new(),
                                 pwms: Vec::new(),
+                                sensors: Vec::new(),
+                                alarms: Vec::new(),
                             });
                         }
                         
@@ -192,6 +137,7 @@ fn enumerate_openbsd_sensors() -> Result<Vec<HwmonChip>> {
                                         input_path: PathBuf::from(format!("sysctl:hw.sensors.{}.{}", chip_name, parts[1])),
                                         label: Some(format!("{} {}", chip_name, parts[1])),
                                         current_temp: Some(temp),
+                                        limits: TemperatureLimits::default(),
                                     });
                                 }
                             }
@@ -213,7 +159,7 @@ fn enumerate_openbsd_sensors() -> Result<Vec<HwmonChip>> {
 }
 
 /// Parse BSD temperature string (e.g., "45.0C" or "45.00 degC")
-#[cfg(any(target_os = "freebsd", target_os = "openbsd", target_os = "netbsd", target_os = "dragonfly"))]
+#[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
 fn parse_bsd_temperature(line: &str) -> Option<f32> {
     // Extract value after colon or equals
     let value_part = line.split([':', '=']).last()?.trim();
@@ -227,7 +173,7 @@ fn parse_bsd_temperature(line: &str) -> Option<f32> {
     numeric.parse::<f32>().ok()
 }
 
-fn read_hwmon_chip(chip_path: &Path) -> Result<Option<HwmonChip>> {
+pub(super) fn read_hwmon_chip(chip_path: &Path) -> Result<Option<HwmonChip>> {
     let name_path = chip_path.join("name");
     let name = if name_path.exists() {
         fs::read_to_string(&name_path)?.trim().to_string()
@@ -244,6 +190,8 @@ fn read_hwmon_chip(chip_path: &Path) -> Result<Option<HwmonChip>> {
     let mut temperatures = Vec::new();
     let mut fans = Vec::new();
     let mut pwms = Vec::new();
+    let mut sensors = Vec::new();
+    let mut alarms = Vec::new();
 
     let entries = fs::read_dir(chip_path)?;
     let mut all_files = Vec::new();
@@ -271,30 +219,108 @@ fn read_hwmon_chip(chip_path: &Path) -> Result<Option<HwmonChip>> {
                 trace!(controller = %file_name_str, "Found PWM controller");
                 pwms.push(pwm);
             }
+        } else if let Some(kind) = aux_sensor_kind(file_name_str, &all_files) {
+            if let Some(sensor) = read_aux_sensor(chip_path, file_name_str, kind) {
+                trace!(sensor = %file_name_str, kind = %kind, "Found sensor");
+                sensors.push(sensor);
+            }
+        } else if let Some(alarm) = read_hwmon_alarm(chip_path, file_name_str, &all_files) {
+            trace!(alarm = %file_name_str, active = alarm.active, "Found alarm");
+            alarms.push(alarm);
         }
     }
+    sensors.sort_by_key(|s| (s.kind as u8, natural_key(&s.name)));
+    alarms.sort_by_key(|a| (a.kind as u8, natural_key(&a.name)));
 
     debug!(
         chip = %name,
         temps = temperatures.len(),
         fans = fans.len(),
         pwms = pwms.len(),
+        sensors = sensors.len(),
+        alarms = alarms.len(),
         "Chip sensor counts"
     );
 
-    if temperatures.is_empty() && fans.is_empty() && pwms.is_empty() {
+    if temperatures.is_empty() && fans.is_empty() && pwms.is_empty() && sensors.is_empty() && alarms.is_empty() {
         return Ok(None);
     }
 
+    // Several nvme/drivetemp chips share a name; tell drives apart by model
+    if super::storage::is_storage_chip(&name) {
+        if let Some(model) = super::storage::read_drive_model(&chip_path.join("device")) {
+            for temp in &mut temperatures {
+                let sensor = temp.label.take().unwrap_or_else(|| temp.name.clone());
+                temp.label = Some(format!("{} {}", model, sensor));
+            }
+        }
+    }
+
     Ok(Some(HwmonChip {
         name,
         path: chip_path.to_path_buf(),
         temperatures,
         fans,
         pwms,
+        sensors,
+        alarms,
     }))
 }
 
+/// Kind of a voltage/current/power/humidity value file, if `file_name` is one
+/// `powerN_average` only counts when the chip has no `powerN_input`
+fn aux_sensor_kind(file_name: &str, all_files: &[String]) -> Option<SensorKind> {
+    for kind in SensorKind::ALL {
+        let Some(rest) = file_name.strip_prefix(kind.prefix()) else {
+            continue;
+        };
+        let (index, suffix) = rest.split_once('_')?;
+        if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        return match (kind, suffix) {
+            (_, "input") => Some(kind),
+            (SensorKind::Power, "average") => {
+                let input = format!("power{}_input", index);
+                (!all_files.contains(&input)).then_some(kind)
+            }
+            _ => None,
+        };
+    }
+    None
+}
+
+/// Sort key so in2 comes before in10
+fn natural_key(name: &str) -> (String, u32) {
+    let digits: String = name.chars().filter(|c| c.is_ascii_digit()).collect();
+    let prefix: String = name.chars().filter(|c| !c.is_ascii_digit()).collect();
+    (prefix, digits.parse().unwrap_or(0))
+}
+
+fn read_aux_sensor(chip_path: &Path, value_file: &str, kind: SensorKind) -> Option<HwmonSensor> {
+    let input_path = chip_path.join(value_file);
+    let base_name = value_file.split('_').next()?.to_string();
+    let label = fs::read_to_string(chip_path.join(format!("{}_label", base_name)))
+        .ok()
+        .map(|s| s.trim().to_string());
+
+    let current_value = match crate::hw::read_sensor_value(&input_path, kind) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            trace!(path = ?input_path, error = %e, "Could not read sensor value");
+            None
+        }
+    };
+
+    Some(HwmonSensor {
+        name: base_name,
+        kind,
+        input_path,
+        label,
+        current_value,
+    })
+}
+
 fn read_temperature_sensor(
     chip_path: &Path,
     input_file: &str,
@@ -320,14 +346,117 @@ fn read_temperature_sensor(
         None
     };
 
+    let limits = read_temperature_limits(&input_path);
+
     Ok(Some(TemperatureSensor {
         name: base_name,
         input_path,
         label,
         current_temp,
+        limits,
     }))
 }
 
+/// Read the driver-reported thresholds of a temperature input
+///
+/// hwmon inputs use the `tempN_max`, `tempN_crit` and `tempN_crit_alarm`
+/// siblings; thermal zones use their "hot" and "critical" trip points.
+/// Other sources (sysctl, smartctl, GPU) report no limits.
+pub fn read_temperature_limits(input_path: &Path) -> TemperatureLimits {
+    let Some(file_name) = input_path.file_name().and_then(|n| n.to_str()) else {
+        return TemperatureLimits::default();
+    };
+    let Some(dir) = input_path.parent() else {
+        return TemperatureLimits::default();
+    };
+
+    if let Some(base) = file_name.strip_suffix("_input") {
+        return TemperatureLimits {
+            max: read_limit(&dir.join(format!("{}_max", base))),
+            crit: read_limit(&dir.join(format!("{}_crit", base))),
+            crit_alarm: read_alarm(&dir.join(format!("{}_crit_alarm", base))),
+        };
+    }
+
+    if file_name == thermal_zone::TEMP_FILE {
+        return read_trip_points(dir);
+    }
+
+    TemperatureLimits::default()
+}
+
+/// Re-read only the latched alarm (limits themselves rarely change)
+pub(super) fn read_crit_alarm(input_path: &Path) -> bool {
+    input_path
+        .to_str()
+        .and_then(|p| p.strip_suffix("_input"))
+        .is_some_and(|base| read_alarm(Path::new(&format!("{}_crit_alarm", base))))
+}
+
+/// Millidegree limit file, `None` if missing or implausible
+fn read_limit(path: &Path) -> Option<f32> {
+    let millidegrees: i32 = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    let celsius = millidegrees as f32 / temperature::MILLIDEGREE_DIVISOR;
+    (temperature::LIMIT_MIN_PLAUSIBLE..=temperature::LIMIT_MAX_PLAUSIBLE)
+        .contains(&celsius)
+        .then_some(celsius)
+}
+
+fn read_alarm(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|s| s.trim() == "1")
+}
+
+/// Whether an hwmon alarm file (`HwmonAlarm::alarm_path`) is raised
+pub fn read_alarm_flag(alarm_path: &Path) -> bool {
+    read_alarm(alarm_path)
+}
+
+/// Alarm flag of a fan, temperature or intrusion channel, if `file_name` is one
+/// Limit-specific flags (`tempN_crit_alarm`, `fanN_min_alarm`) are not included.
+fn read_hwmon_alarm(chip_path: &Path, file_name: &str, all_files: &[String]) -> Option<HwmonAlarm> {
+    let name = file_name.strip_suffix("_alarm")?;
+    let (kind, index) = [("fan", AlarmKind::Fan), ("temp", AlarmKind::Temp), ("intrusion", AlarmKind::Intrusion)]
+        .into_iter()
+        .find_map(|(prefix, kind)| name.strip_prefix(prefix).map(|index| (kind, index)))?;
+    if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let alarm_path = chip_path.join(file_name);
+    let beep_file = format!("{}_beep", name);
+    let beep = all_files.contains(&beep_file).then(|| read_alarm(&chip_path.join(&beep_file)));
+    Some(HwmonAlarm {
+        name: name.to_string(),
+        kind,
+        active: read_alarm(&alarm_path),
+        alarm_path,
+        beep,
+    })
+}
+
+/// Lowest "hot" and "critical" trip points of a thermal zone
+fn read_trip_points(zone_dir: &Path) -> TemperatureLimits {
+    let mut limits = TemperatureLimits::default();
+    let Ok(entries) = fs::read_dir(zone_dir) else {
+        return limits;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(trip) = name.to_str().and_then(|n| n.strip_suffix("_type")).filter(|n| n.starts_with("trip_point_")) else {
+            continue;
+        };
+        let slot = match fs::read_to_string(entry.path()).map(|s| s.trim().to_string()).as_deref() {
+            Ok("hot") => &mut limits.max,
+            Ok("critical") => &mut limits.crit,
+            _ => continue,
+        };
+        if let Some(value) = read_limit(&zone_dir.join(format!("{}_temp", trip))) {
+            *slot = Some(slot.map_or(value, |current: f32| current.min(value)));
+        }
+    }
+    limits
+}
+
 fn read_fan_sensor(chip_path: &Path, input_file: &str) -> Result<Option<FanSensor>> {
     let input_path = chip_path.join(input_file);
     let base_name = input_file.replace("_input", "");
@@ -407,14 +536,171 @@ fn read_pwm_controller(chip_path: &Path, pwm_file: &str) -> Result<Option<PwmCon
 
     Ok(Some(PwmController {
         name: pwm_file.to_string(),
+        dc_mode: super::is_dc_mode(&pwm_path),
         pwm_path,
         enable_path,
         label,
         current_value,
         current_percent,
+        cooling_device: None,
     }))
 }
 
+/// Add sources that are not hwmon chips: thermal zones and fan cooling
+/// devices from `/sys/class/thermal`, drive temperatures via smartctl, and
+/// BMC sensors and fan zones via ipmitool
+pub(super) fn add_extra_sources(chips: &mut Vec<HwmonChip>) {
+    let thermal_path = Path::new(paths::THERMAL_BASE);
+    add_thermal_zones_from(chips, thermal_path);
+    attach_cooling_devices_from(chips, thermal_path);
+
+    match super::storage::SmartctlBackend.enumerate() {
+        Ok(drives) => chips.extend(drives),
+        Err(e) => trace!(error = %e, "smartctl drive scan failed"),
+    }
+    match super::ipmi::IpmiBackend.enumerate() {
+        Ok(bmc) => chips.extend(bmc),
+        Err(e) => trace!(error = %e, "IPMI sensor scan failed"),
+    }
+}
+
+/// Add thermal zones as temperature sources on a "thermal" pseudo-chip
+///
+/// Some laptops only report a useful CPU temperature here (`x86_pkg_temp`,
+/// `acpitz`) rather than through hwmon. Zones of a type the thermal core
+/// already registered as a hwmon chip are skipped to avoid duplicates, as are
+/// zones that cannot be read (e.g. disabled).
+fn add_thermal_zones_from(chips: &mut Vec<HwmonChip>, thermal_path: &Path) {
+    let Ok(entries) = fs::read_dir(thermal_path) else {
+        return;
+    };
+    let mut dirs: Vec<_> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("thermal_zone")))
+        .collect();
+    dirs.sort_by_key(|p| natural_key(&p.file_name().unwrap_or_default().to_string_lossy()));
+
+    let mut temperatures = Vec::new();
+    for dir in dirs {
+        let Some(name) = dir.file_name().and_then(|n| n.to_str()).map(|n| n.to_string()) else {
+            continue;
+        };
+        let zone_type = fs::read_to_string(dir.join("type")).map(|s| s.trim().to_string()).unwrap_or_default();
+        if !zone_type.is_empty() && chips.iter().any(|c| c.name == zone_type.replace('-', "_")) {
+            trace!(zone = %name, zone_type = %zone_type, "Thermal zone already exposed via hwmon");
+            continue;
+        }
+
+        let input_path = dir.join(thermal_zone::TEMP_FILE);
+        let Some(millidegrees) = fs::read_to_string(&input_path).ok().and_then(|s| s.trim().parse::<i32>().ok()) else {
+            trace!(zone = %name, "Thermal zone not readable, skipping");
+            continue;
+        };
+
+        debug!(zone = %name, zone_type = %zone_type, "Found thermal zone");
+        temperatures.push(TemperatureSensor {
+            label: (!zone_type.is_empty()).then_some(zone_type),
+            name,
+            current_temp: Some(millidegrees as f32 / temperature::MILLIDEGREE_DIVISOR),
+            limits: read_trip_points(&dir),
+            input_path,
+        });
+    }
+
+    if !temperatures.is_empty() {
+        chips.push(HwmonChip {
+            name: thermal_zone::CHIP_NAME.to_string(),
+            path: thermal_path.to_path_buf(),
+            temperatures,
+            fans: Vec::new(),
+            pwms: Vec::new(),
+            sensors: Vec::new(),
+            alarms: Vec::new(),
+        });
+    }
+}
+
+/// Attach fan cooling devices (`pwm-fan`, `gpio-fan`)
+///
+/// The cooling device goes on the matching driver's hwmon chip when that chip
+/// has no `pwmN` file (a `pwmN` gives finer control of the same fan), or on a
+/// chip of its own when the driver registered no hwmon chip at all.
+fn attach_cooling_devices_from(chips: &mut Vec<HwmonChip>, thermal_path: &Path) {
+    let Ok(entries) = fs::read_dir(thermal_path) else {
+        return;
+    };
+    let mut dirs: Vec<_> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("cooling_device")))
+        .collect();
+    dirs.sort_by_key(|p| natural_key(&p.file_name().unwrap_or_default().to_string_lossy()));
+
+    for dir in dirs {
+        let Some(controller) = read_cooling_controller(&dir) else {
+            continue;
+        };
+        let Some(device_type) = controller.cooling_device.as_ref().map(|c| c.device_type.clone()) else {
+            continue;
+        };
+        let hwmon_name = cooling::FAN_DEVICE_TYPES
+            .iter()
+            .find(|(t, _)| *t == device_type)
+            .map(|(_, name)| *name)
+            .unwrap_or_default();
+
+        if chips.iter().any(|c| c.name == hwmon_name && c.pwms.iter().any(|p| p.cooling_device.is_none())) {
+            trace!(device = %dir.display(), "Fan has a pwmN control, skipping its cooling device");
+            continue;
+        }
+
+        debug!(device = %dir.display(), device_type = %device_type, "Found fan cooling device");
+        match chips.iter_mut().find(|c| c.name == hwmon_name) {
+            Some(chip) => chip.pwms.push(controller),
+            None => chips.push(HwmonChip {
+                name: device_type,
+                path: dir,
+                temperatures: Vec::new(),
+                fans: Vec::new(),
+                pwms: vec![controller],
+                sensors: Vec::new(),
+                alarms: Vec::new(),
+            }),
+        }
+    }
+}
+
+/// Read a `cooling_deviceN` directory if its type drives a fan
+fn read_cooling_controller(dir: &Path) -> Option<PwmController> {
+    let device_type = fs::read_to_string(dir.join("type")).ok()?.trim().to_string();
+    if !cooling::FAN_DEVICE_TYPES.iter().any(|(t, _)| *t == device_type) {
+        return None;
+    }
+    let max_state: u32 = fs::read_to_string(dir.join(cooling::MAX_STATE_FILE)).ok()?.trim().parse().ok()?;
+    if max_state == 0 {
+        return None;
+    }
+
+    let name = dir.file_name()?.to_str()?.to_string();
+    let pwm_path = dir.join(cooling::STATE_FILE);
+    let current_value = fs::read_to_string(&pwm_path)
+        .ok()
+        .and_then(|s| s.trim().parse::<u32>().ok())
+        .map(|state| cooling::state_to_pwm(state, max_state));
+
+    Some(PwmController {
+        label: Some(format!("{} ({} levels)", device_type, max_state + 1)),
+        pwm_path,
+        enable_path: PathBuf::new(),
+        current_percent: current_value.map(crate::constants::pwm::to_percent),
+        current_value,
+        cooling_device: Some(CoolingDevice { name: name.clone(), device_type, max_state }),
+        dc_mode: false,
+        name,
+    })
+}
+
 /// Check if we have write permissions to PWM controls (non-destructive)
 pub fn check_pwm_permissions(chips: &[HwmonChip]) -> bool {
     use std::fs::OpenOptions;
@@ -434,3 +720,128 @@ pub fn check_pwm_permissions(chips: &[HwmonChip]) -> bool {
     }
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cooling_device(thermal: &Path, name: &str, device_type: &str, max: u32, cur: u32) {
+        let dir = thermal.join(name);
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("type"), format!("{}\n", device_type)).unwrap();
+        fs::write(dir.join("max_state"), format!("{}\n", max)).unwrap();
+        fs::write(dir.join("cur_state"), format!("{}\n", cur)).unwrap();
+    }
+
+    #[test]
+    fn test_attach_cooling_devices() {
+        let thermal = tempfile::tempdir().unwrap();
+        cooling_device(thermal.path(), "cooling_device0", "Processor", 3, 0);
+        cooling_device(thermal.path(), "cooling_device1", "pwm-fan", 4, 2);
+        cooling_device(thermal.path(), "cooling_device2", "gpio-fan", 1, 1);
+
+        // pwm-fan chip without pwm1, no gpio_fan chip
+        let mut chips = vec![HwmonChip {
+            name: "pwmfan".to_string(),
+            path: PathBuf::from("/sys/class/hwmon/hwmon1"),
+            temperatures: Vec::new(),
+            fans: Vec::new(),
+            pwms: Vec::new(),
+            sensors: Vec::new(),
+            alarms: Vec::new(),
+        }];
+        attach_cooling_devices_from(&mut chips, thermal.path());
+
+        assert_eq!(chips.len(), 2);
+        let pwm = &chips[0].pwms[0];
+        assert_eq!(pwm.pwm_path, thermal.path().join("cooling_device1/cur_state"));
+        assert_eq!(pwm.current_value, Some(128));
+        assert_eq!(pwm.cooling_device.as_ref().unwrap().max_state, 4);
+        assert_eq!(chips[1].name, "gpio-fan");
+        assert_eq!(chips[1].pwms[0].current_value, Some(255));
+    }
+
+    #[test]
+    fn test_thermal_zones() {
+        let thermal = tempfile::tempdir().unwrap();
+        for (zone, zone_type, temp) in [("thermal_zone0", "acpitz", "27800"), ("thermal_zone1", "x86_pkg_temp", "51000")] {
+            let dir = thermal.path().join(zone);
+            fs::create_dir(&dir).unwrap();
+            fs::write(dir.join("type"), format!("{}\n", zone_type)).unwrap();
+            fs::write(dir.join("temp"), format!("{}\n", temp)).unwrap();
+        }
+        // Disabled zones fail to read
+        fs::create_dir(thermal.path().join("thermal_zone2")).unwrap();
+
+        // acpitz is already registered as a hwmon chip
+        let acpitz = HwmonChip {
+            name: "acpitz".to_string(),
+            path: PathBuf::from("/sys/class/hwmon/hwmon0"),
+            temperatures: Vec::new(),
+            fans: Vec::new(),
+            pwms: Vec::new(),
+            sensors: Vec::new(),
+            alarms: Vec::new(),
+        };
+        let mut chips = vec![acpitz];
+        add_thermal_zones_from(&mut chips, thermal.path());
+
+        assert_eq!(chips.len(), 2);
+        let zones = &chips[1].temperatures;
+        assert_eq!(zones.len(), 1);
+        assert_eq!(zones[0].name, "thermal_zone1");
+        assert_eq!(zones[0].label.as_deref(), Some("x86_pkg_temp"));
+        assert_eq!(zones[0].current_temp, Some(51.0));
+    }
+
+    #[test]
+    fn test_temperature_limits() {
+        let chip = tempfile::tempdir().unwrap();
+        fs::write(chip.path().join("temp1_input"), "45000\n").unwrap();
+        fs::write(chip.path().join("temp1_max"), "80000\n").unwrap();
+        fs::write(chip.path().join("temp1_crit"), "100000\n").unwrap();
+        fs::write(chip.path().join("temp1_crit_alarm"), "0\n").unwrap();
+        // Unset limits some chips report
+        fs::write(chip.path().join("temp2_input"), "30000\n").unwrap();
+        fs::write(chip.path().join("temp2_max"), "0\n").unwrap();
+        fs::write(chip.path().join("temp2_crit"), "255000\n").unwrap();
+
+        let limits = read_temperature_limits(&chip.path().join("temp1_input"));
+        assert_eq!(limits, TemperatureLimits { max: Some(80.0), crit: Some(100.0), crit_alarm: false });
+        assert!(limits.exceeds_max(80.0) && !limits.exceeds_crit(99.0));
+        assert_eq!(limits.failsafe_threshold(), 95.0);
+        assert!(read_temperature_limits(&chip.path().join("temp2_input")).is_empty());
+
+        // Lowest trip point of each kind
+        let zone = tempfile::tempdir().unwrap();
+        for (trip, kind, temp) in [(0, "passive", "60000"), (1, "critical", "105000"), (2, "critical", "98000"), (3, "hot", "90000")] {
+            fs::write(zone.path().join(format!("trip_point_{}_type", trip)), format!("{}\n", kind)).unwrap();
+            fs::write(zone.path().join(format!("trip_point_{}_temp", trip)), format!("{}\n", temp)).unwrap();
+        }
+        let limits = read_temperature_limits(&zone.path().join("temp"));
+        assert_eq!((limits.max, limits.crit), (Some(90.0), Some(98.0)));
+    }
+
+    #[test]
+    fn test_alarms() {
+        let chip = tempfile::tempdir().unwrap();
+        fs::write(chip.path().join("name"), "nct6798\n").unwrap();
+        fs::write(chip.path().join("temp1_input"), "45000\n").unwrap();
+        for (file, value) in [
+            ("temp1_alarm", "0"), ("temp1_crit_alarm", "1"), ("fan2_alarm", "1"), ("fan2_beep", "1"),
+            ("fan10_alarm", "0"), ("intrusion0_alarm", "1"), ("intrusion0_beep", "0"), ("in0_alarm", "1"),
+        ] {
+            fs::write(chip.path().join(file), format!("{}\n", value)).unwrap();
+        }
+
+        let chip = read_hwmon_chip(chip.path()).unwrap().unwrap();
+        let alarms: Vec<_> = chip.alarms.iter().map(|a| (a.name.as_str(), a.kind, a.active, a.beep)).collect();
+        assert_eq!(alarms, vec![
+            ("fan2", AlarmKind::Fan, true, Some(true)),
+            ("fan10", AlarmKind::Fan, false, None),
+            ("temp1", AlarmKind::Temp, false, None),
+            ("intrusion0", AlarmKind::Intrusion, true, Some(false)),
+        ]);
+        assert!(read_alarm_flag(&chip.alarms[0].alarm_path));
+    }
+}
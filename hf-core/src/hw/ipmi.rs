@@ -0,0 +1,602 @@
+//! IPMI (BMC) sensors and fan control
+//!
+//! Servers often expose their fans only through the BMC. When `ipmitool` is
+//! installed and the kernel IPMI driver is loaded, BMC temperatures and fan
+//! tachometers from the sensor data repository appear on an "ipmi"
+//! pseudo-chip as `ipmi:temp:<sensor>` and `ipmi:fan:<sensor>` paths.
+//!
+//! Fan duty is set with vendor raw commands, chosen by the DMI system vendor:
+//! - **Supermicro** (X9-X13): zone 0 drives FAN1.., zone 1 FANA..; manual
+//!   control switches the board-wide fan mode to "full" so the BMC does not
+//!   override the duty, and automatic control restores the previous mode
+//! - **Dell PowerEdge** (iDRAC 7-9, firmware before 3.30): one zone for all fans
+//!
+//! Zones are `ipmi:zone:<n>` PWM paths with `ipmi:zone:<n>:enable` enable paths.
+//!
+//! BMCs are slow and some stop responding when flooded, so traffic is kept
+//! low: sensor listings are cached for [`SDR_CACHE_TTL`](ipmi::SDR_CACHE_TTL),
+//! ipmitool calls run one at a time and are killed after
+//! [`COMMAND_TIMEOUT`](ipmi::COMMAND_TIMEOUT), and a zone's duty is only sent
+//! when it changed. Increases are sent at once (a fan must never wait to speed
+//! up); decreases at most once per [`MIN_WRITE_INTERVAL`](ipmi::MIN_WRITE_INTERVAL)
+//! after the last command. A lower value set sooner is held and sent by the
+//! next write after the interval.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use tracing::{debug, info, trace};
+
+use super::backend::HwmonBackend;
+use crate::constants::{ipmi, paths, pwm};
+use crate::data::{FanSensor, HwmonChip, PwmController, SensorKind, TemperatureLimits, TemperatureSensor};
+use crate::error::{HyperfanError, Result};
+
+/// BMC vendors with known raw fan commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Vendor {
+    Supermicro,
+    Dell,
+}
+
+/// Supermicro fan modes (`raw 0x30 0x45`)
+const SUPERMICRO_MODE_STANDARD: u8 = 0x00;
+const SUPERMICRO_MODE_FULL: u8 = 0x01;
+const SUPERMICRO_MODE_OPTIMAL: u8 = 0x02;
+const SUPERMICRO_MODE_HEAVY_IO: u8 = 0x04;
+
+impl Vendor {
+    fn detect() -> Option<Self> {
+        let vendor = std::fs::read_to_string(paths::DMI_SYS_VENDOR).ok()?.to_lowercase();
+        if vendor.contains("supermicro") {
+            Some(Vendor::Supermicro)
+        } else if vendor.contains("dell") {
+            Some(Vendor::Dell)
+        } else {
+            None
+        }
+    }
+
+    fn zones(self) -> u8 {
+        match self {
+            Vendor::Supermicro => 2,
+            Vendor::Dell => 1,
+        }
+    }
+
+    fn zone_label(self, zone: u8) -> &'static str {
+        match (self, zone) {
+            (Vendor::Supermicro, 0) => "BMC zone 0 (FAN1-FAN9)",
+            (Vendor::Supermicro, _) => "BMC zone 1 (FANA-FANZ)",
+            (Vendor::Dell, _) => "BMC (all fans)",
+        }
+    }
+
+    fn duty_command(self, zone: u8, percent: u8) -> Vec<String> {
+        let args = match self {
+            Vendor::Supermicro => vec!["0x70".to_string(), "0x66".to_string(), "0x01".to_string(), format!("0x{:02x}", zone)],
+            Vendor::Dell => vec!["0x30".to_string(), "0x30".to_string(), "0x02".to_string(), "0xff".to_string()],
+        };
+        std::iter::once("raw".to_string())
+            .chain(args)
+            .chain(std::iter::once(format!("0x{:02x}", percent)))
+            .collect()
+    }
+}
+
+/// Last duty per zone and the state of its write limit
+#[derive(Default)]
+struct ZoneState {
+    sent: Option<u8>,
+    sent_at: Option<Instant>,
+    pending: Option<u8>,
+    manual: bool,
+}
+
+impl ZoneState {
+    /// Whether `percent` must wait for the write interval (only decreases do)
+    fn holds(&self, percent: u8) -> bool {
+        self.sent.is_some_and(|sent| percent < sent)
+            && self.sent_at.is_some_and(|at| at.elapsed() < ipmi::MIN_WRITE_INTERVAL)
+    }
+}
+
+/// Cached SDR listing: when it was taken and the readings (empty after a failed call)
+type SdrCache = Option<(Instant, Vec<SdrReading>)>;
+
+static SDR: Mutex<SdrCache> = Mutex::new(None);
+static ZONES: Mutex<Option<HashMap<u8, ZoneState>>> = Mutex::new(None);
+/// Supermicro fan mode from before the first switch to manual
+static SAVED_MODE: Mutex<Option<u8>> = Mutex::new(None);
+/// Serializes ipmitool calls
+static COMMAND_LOCK: Mutex<()> = Mutex::new(());
+static AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// ipmitool-backed BMC sensors and fan zones (`ipmi:`)
+pub struct IpmiBackend;
+
+/// Whether a path refers to a BMC sensor or fan zone
+pub fn is_ipmi_path(path: &Path) -> bool {
+    path.to_str().is_some_and(|p| p.starts_with(paths::IPMI_PREFIX))
+}
+
+fn ipmi_available() -> bool {
+    *AVAILABLE.get_or_init(|| {
+        let device = ipmi::DEVICE_NODES.iter().any(|node| Path::new(node).exists());
+        let available = device
+            && Command::new(ipmi::IPMITOOL).arg("-V").output().is_ok_and(|o| o.status.success());
+        debug!(available, "Checked for ipmitool and an IPMI device");
+        available
+    })
+}
+
+/// Run ipmitool, killing it after COMMAND_TIMEOUT
+fn ipmitool<S: AsRef<std::ffi::OsStr>>(args: &[S]) -> Result<String> {
+    let _guard = COMMAND_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut child = Command::new(ipmi::IPMITOOL)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let deadline = Instant::now() + ipmi::COMMAND_TIMEOUT;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(HyperfanError::Timeout(format!("ipmitool after {:?}", ipmi::COMMAND_TIMEOUT)));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    };
+
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout.read_to_string(&mut output)?;
+    }
+    if !status.success() {
+        return Err(HyperfanError::generic(format!("ipmitool exited with {}", status)));
+    }
+    Ok(output)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SdrKind {
+    Temperature,
+    Fan,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SdrReading {
+    name: String,
+    id: String,
+    kind: SdrKind,
+    value: f32,
+}
+
+/// Path-safe sensor ID ("CPU Temp" -> "cpu_temp")
+fn sensor_id(name: &str) -> String {
+    let id: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    id.split('_').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("_")
+}
+
+/// Parse `ipmitool sdr elist full` output
+///
+/// ```text
+/// CPU Temp         | 01h | ok  |  3.1 | 45 degrees C
+/// FAN1             | 41h | ok  | 29.1 | 1400 RPM
+/// FAN2             | 42h | ns  | 29.2 | No Reading
+/// ```
+fn parse_sdr(output: &str) -> Vec<SdrReading> {
+    let mut readings: Vec<SdrReading> = Vec::new();
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+        let (Some(name), Some(reading)) = (fields.first(), fields.get(4)) else {
+            continue;
+        };
+        let kind = if reading.ends_with("degrees C") {
+            SdrKind::Temperature
+        } else if reading.ends_with("RPM") {
+            SdrKind::Fan
+        } else {
+            continue;
+        };
+        let Some(value) = reading.split_whitespace().next().and_then(|v| v.parse::<f32>().ok()) else {
+            continue;
+        };
+
+        let mut id = sensor_id(name);
+        if id.is_empty() {
+            continue;
+        }
+        // Keep IDs unique when names differ only in punctuation
+        let base = id.clone();
+        let mut n = 2;
+        while readings.iter().any(|r| r.kind == kind && r.id == id) {
+            id = format!("{}_{}", base, n);
+            n += 1;
+        }
+        readings.push(SdrReading { name: name.to_string(), id, kind, value });
+    }
+    readings
+}
+
+/// SDR readings, re-listed when the cached ones expired
+fn sdr_readings() -> Vec<SdrReading> {
+    let mut cache = SDR.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((at, readings)) = cache.as_ref() {
+        if at.elapsed() < ipmi::SDR_CACHE_TTL {
+            return readings.clone();
+        }
+    }
+    let readings = match ipmitool(&["sdr", "elist", "full"]) {
+        Ok(output) => parse_sdr(&output),
+        Err(e) => {
+            // Cached as empty too, so a failing BMC is not queried on every read
+            debug!(error = %e, "IPMI sensor listing failed");
+            Vec::new()
+        }
+    };
+    *cache = Some((Instant::now(), readings.clone()));
+    readings
+}
+
+/// What an `ipmi:` path names
+#[derive(Debug, Clone, PartialEq)]
+enum IpmiPath<'a> {
+    Sensor(SdrKind, &'a str),
+    Zone(u8),
+    ZoneEnable(u8),
+}
+
+fn parse_path(path: &Path) -> Result<IpmiPath<'_>> {
+    let invalid = || HyperfanError::invalid_path(path, "not an IPMI sensor or fan zone");
+    let rest = path.to_str().and_then(|p| p.strip_prefix(paths::IPMI_PREFIX)).ok_or_else(invalid)?;
+    let parts: Vec<&str> = rest.split(':').collect();
+    match parts.as_slice() {
+        ["temp", id] => Ok(IpmiPath::Sensor(SdrKind::Temperature, id)),
+        ["fan", id] => Ok(IpmiPath::Sensor(SdrKind::Fan, id)),
+        ["zone", zone] => zone.parse().map(IpmiPath::Zone).map_err(|_| invalid()),
+        ["zone", zone, "enable"] => zone.parse().map(IpmiPath::ZoneEnable).map_err(|_| invalid()),
+        _ => Err(invalid()),
+    }
+}
+
+/// Zone of a zone or zone enable path (the daemon switches modes by PWM path, as for sysctl fans)
+fn zone_of(path: &Path) -> Result<u8> {
+    match parse_path(path)? {
+        IpmiPath::Zone(zone) | IpmiPath::ZoneEnable(zone) => Ok(zone),
+        IpmiPath::Sensor(..) => Err(HyperfanError::invalid_path(path, "not an IPMI fan zone")),
+    }
+}
+
+fn vendor_for_zone(zone: u8, path: &Path) -> Result<Vendor> {
+    let vendor = Vendor::detect()
+        .ok_or_else(|| HyperfanError::NotSupported(format!("IPMI fan control on this vendor: {}", path.display())))?;
+    if zone >= vendor.zones() {
+        return Err(HyperfanError::invalid_path(path, "no such IPMI fan zone"));
+    }
+    Ok(vendor)
+}
+
+fn sensor_value(kind: SdrKind, id: &str) -> Option<f32> {
+    sdr_readings().into_iter().find(|r| r.kind == kind && r.id == id).map(|r| r.value)
+}
+
+fn supermicro_mode() -> Result<u8> {
+    let output = ipmitool(&["raw", "0x30", "0x45", "0x00"])?;
+    u8::from_str_radix(output.trim(), 16)
+        .map_err(|_| HyperfanError::generic(format!("unexpected fan mode reply '{}'", output.trim())))
+}
+
+fn set_supermicro_mode(mode: u8) -> Result<()> {
+    ipmitool(&["raw", "0x30", "0x45", "0x01", &format!("0x{:02x}", mode)]).map(|_| ())
+}
+
+/// Hand all zones of the board to the BMC (`mode` None: the mode saved before manual control)
+fn set_auto(vendor: Vendor, mode: Option<u8>) -> Result<()> {
+    match vendor {
+        Vendor::Supermicro => {
+            let saved = SAVED_MODE.lock().unwrap_or_else(|e| e.into_inner()).take();
+            set_supermicro_mode(mode.or(saved).unwrap_or(SUPERMICRO_MODE_OPTIMAL))?;
+        }
+        Vendor::Dell => {
+            ipmitool(&["raw", "0x30", "0x30", "0x01", "0x01"])?;
+        }
+    }
+    let mut zones = ZONES.lock().unwrap_or_else(|e| e.into_inner());
+    for state in zones.get_or_insert_with(HashMap::new).values_mut() {
+        state.manual = false;
+        state.sent = None;
+        state.pending = None;
+    }
+    info!(?vendor, "IPMI fans returned to BMC control");
+    Ok(())
+}
+
+fn set_manual(vendor: Vendor, zone: u8) -> Result<()> {
+    match vendor {
+        Vendor::Supermicro => {
+            let mode = supermicro_mode()?;
+            if mode != SUPERMICRO_MODE_FULL {
+                SAVED_MODE.lock().unwrap_or_else(|e| e.into_inner()).get_or_insert(mode);
+                set_supermicro_mode(SUPERMICRO_MODE_FULL)?;
+            }
+        }
+        Vendor::Dell => {
+            ipmitool(&["raw", "0x30", "0x30", "0x01", "0x00"])?;
+        }
+    }
+    let mut zones = ZONES.lock().unwrap_or_else(|e| e.into_inner());
+    zones.get_or_insert_with(HashMap::new).entry(zone).or_default().manual = true;
+    info!(?vendor, zone, "IPMI fan zone under manual control");
+    Ok(())
+}
+
+/// Send a zone's duty if it changed (decreases only after the write interval)
+fn write_duty(vendor: Vendor, zone: u8, value: u8) -> Result<()> {
+    let percent = (value as f32 / pwm::MAX_VALUE as f32 * 100.0).round() as u8;
+    let mut zones = ZONES.lock().unwrap_or_else(|e| e.into_inner());
+    let state = zones.get_or_insert_with(HashMap::new).entry(zone).or_default();
+    if state.sent == Some(percent) {
+        state.pending = None;
+        return Ok(());
+    }
+    if state.holds(percent) {
+        state.pending = Some(percent);
+        return Ok(());
+    }
+
+    ipmitool(&vendor.duty_command(zone, percent))?;
+    trace!(zone, percent, "IPMI fan duty sent");
+    state.sent = Some(percent);
+    state.sent_at = Some(Instant::now());
+    state.pending = None;
+    Ok(())
+}
+
+fn zone_manual(zone: u8) -> bool {
+    let zones = ZONES.lock().unwrap_or_else(|e| e.into_inner());
+    zones.as_ref().and_then(|z| z.get(&zone)).is_some_and(|s| s.manual)
+}
+
+fn zone_duty(zone: u8) -> Option<u8> {
+    let zones = ZONES.lock().unwrap_or_else(|e| e.into_inner());
+    let state = zones.as_ref()?.get(&zone)?;
+    state.pending.or(state.sent)
+}
+
+impl HwmonBackend for IpmiBackend {
+    fn name(&self) -> &'static str {
+        "ipmi"
+    }
+
+    fn enumerate(&self) -> Result<Vec<HwmonChip>> {
+        if !ipmi_available() {
+            return Ok(Vec::new());
+        }
+
+        let readings = sdr_readings();
+        let temperatures = readings.iter()
+            .filter(|r| r.kind == SdrKind::Temperature)
+            .map(|r| TemperatureSensor {
+                name: r.id.clone(),
+                input_path: PathBuf::from(format!("{}temp:{}", paths::IPMI_PREFIX, r.id)),
+                label: Some(r.name.clone()),
+                current_temp: Some(r.value),
+                limits: TemperatureLimits::default(),
+            })
+            .collect::<Vec<_>>();
+        let fans = readings.iter()
+            .filter(|r| r.kind == SdrKind::Fan)
+            .map(|r| FanSensor {
+                name: r.id.clone(),
+                input_path: PathBuf::from(format!("{}fan:{}", paths::IPMI_PREFIX, r.id)),
+                label: Some(r.name.clone()),
+                current_rpm: Some(r.value as u32),
+            })
+            .collect::<Vec<_>>();
+        // Zones are only offered where the raw commands are known
+        let pwms = Vendor::detect().map_or_else(Vec::new, |vendor| {
+            (0..vendor.zones()).map(|zone| {
+                let current_value = zone_duty(zone).map(|p| (p as f32 / 100.0 * pwm::MAX_VALUE as f32).round() as u8);
+                PwmController {
+                    name: format!("zone{}", zone),
+                    pwm_path: PathBuf::from(format!("{}zone:{}", paths::IPMI_PREFIX, zone)),
+                    enable_path: PathBuf::from(format!("{}zone:{}:enable", paths::IPMI_PREFIX, zone)),
+                    label: Some(vendor.zone_label(zone).to_string()),
+                    current_value,
+                    current_percent: zone_duty(zone).map(f32::from),
+                    cooling_device: None,
+                    dc_mode: false,
+                }
+            }).collect()
+        });
+
+        if temperatures.is_empty() && fans.is_empty() && pwms.is_empty() {
+            return Ok(Vec::new());
+        }
+        debug!(temperatures = temperatures.len(), fans = fans.len(), zones = pwms.len(), "Found IPMI sensors");
+        Ok(vec![HwmonChip {
+            name: ipmi::CHIP_NAME.to_string(),
+            path: PathBuf::from(paths::IPMI_PREFIX),
+            temperatures,
+            fans,
+            pwms,
+            sensors: Vec::new(),
+            alarms: Vec::new(),
+        }])
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        if !ipmi_available() {
+            return false;
+        }
+        match parse_path(path) {
+            Ok(IpmiPath::Sensor(kind, id)) => sdr_readings().iter().any(|r| r.kind == kind && r.id == id),
+            Ok(IpmiPath::Zone(zone) | IpmiPath::ZoneEnable(zone)) => vendor_for_zone(zone, path).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    fn read_temperature(&self, path: &Path) -> Result<f32> {
+        let IpmiPath::Sensor(SdrKind::Temperature, id) = parse_path(path)? else {
+            return Err(HyperfanError::invalid_path(path, "not an IPMI temperature"));
+        };
+        sensor_value(SdrKind::Temperature, id).ok_or_else(|| HyperfanError::TemperatureRead {
+            path: path.to_path_buf(),
+            reason: "No IPMI reading available".to_string(),
+        })
+    }
+
+    fn read_fan_rpm(&self, path: &Path) -> Result<u32> {
+        let IpmiPath::Sensor(SdrKind::Fan, id) = parse_path(path)? else {
+            return Err(HyperfanError::invalid_path(path, "not an IPMI fan"));
+        };
+        sensor_value(SdrKind::Fan, id).map(|rpm| rpm as u32).ok_or_else(|| HyperfanError::FanRead {
+            path: path.to_path_buf(),
+            reason: "No IPMI reading available".to_string(),
+        })
+    }
+
+    fn read_sensor(&self, path: &Path, kind: SensorKind) -> Result<f32> {
+        Err(HyperfanError::NotSupported(format!("{} via IPMI: {}", kind, path.display())))
+    }
+
+    fn read_pwm(&self, path: &Path) -> Result<u8> {
+        let IpmiPath::Zone(zone) = parse_path(path)? else {
+            return Err(HyperfanError::invalid_path(path, "not an IPMI fan zone"));
+        };
+        // BMCs cannot report the duty they were given; use the last one sent
+        zone_duty(zone)
+            .map(|p| (p as f32 / 100.0 * pwm::MAX_VALUE as f32).round() as u8)
+            .ok_or_else(|| HyperfanError::PwmRead {
+                path: path.to_path_buf(),
+                reason: "No duty set yet".to_string(),
+            })
+    }
+
+    fn write_pwm(&self, path: &Path, value: u8) -> Result<()> {
+        let IpmiPath::Zone(zone) = parse_path(path)? else {
+            return Err(HyperfanError::invalid_path(path, "not an IPMI fan zone"));
+        };
+        let vendor = vendor_for_zone(zone, path)?;
+        // Like sysctl fans, a write takes the zone over from the BMC
+        if !zone_manual(zone) {
+            set_manual(vendor, zone)?;
+        }
+        write_duty(vendor, zone, value).map_err(|e| HyperfanError::PwmWrite {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })
+    }
+
+    fn enable_manual(&self, enable_path: &Path) -> Result<()> {
+        let zone = zone_of(enable_path)?;
+        set_manual(vendor_for_zone(zone, enable_path)?, zone)
+    }
+
+    fn enable_auto(&self, enable_path: &Path) -> Result<()> {
+        let zone = zone_of(enable_path)?;
+        set_auto(vendor_for_zone(zone, enable_path)?, None)
+    }
+
+    /// Enable paths read as "manual" or "auto" (Supermicro: "auto:<mode>"),
+    /// zones as the last duty sent
+    fn read_raw(&self, path: &Path) -> Result<String> {
+        match parse_path(path)? {
+            IpmiPath::ZoneEnable(zone) => match vendor_for_zone(zone, path)? {
+                Vendor::Supermicro => Ok(match supermicro_mode()? {
+                    SUPERMICRO_MODE_FULL => "manual".to_string(),
+                    mode => format!("auto:{}", mode),
+                }),
+                Vendor::Dell => Ok(if zone_manual(zone) { "manual" } else { "auto" }.to_string()),
+            },
+            IpmiPath::Zone(_) => self.read_pwm(path).map(|v| v.to_string()),
+            IpmiPath::Sensor(SdrKind::Temperature, _) => self.read_temperature(path).map(|t| t.to_string()),
+            IpmiPath::Sensor(SdrKind::Fan, _) => self.read_fan_rpm(path).map(|rpm| rpm.to_string()),
+        }
+    }
+
+    fn write_raw(&self, path: &Path, value: &str) -> Result<()> {
+        match parse_path(path)? {
+            IpmiPath::ZoneEnable(zone) => {
+                let vendor = vendor_for_zone(zone, path)?;
+                match value.trim() {
+                    "manual" => set_manual(vendor, zone),
+                    "auto" => set_auto(vendor, None),
+                    other => {
+                        let mode = other.strip_prefix("auto:").and_then(|m| m.parse::<u8>().ok())
+                            .filter(|m| [SUPERMICRO_MODE_STANDARD, SUPERMICRO_MODE_OPTIMAL, SUPERMICRO_MODE_HEAVY_IO].contains(m))
+                            .ok_or_else(|| HyperfanError::invalid_path(path, format!("invalid IPMI fan mode '{}'", other)))?;
+                        set_auto(vendor, Some(mode))
+                    }
+                }
+            }
+            IpmiPath::Zone(_) => {
+                let value: u8 = value.trim().parse()
+                    .map_err(|_| HyperfanError::invalid_path(path, format!("invalid PWM value '{}'", value.trim())))?;
+                self.write_pwm(path, value)
+            }
+            IpmiPath::Sensor(..) => Err(HyperfanError::NotSupported(format!("writing IPMI sensors: {}", path.display()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sdr() {
+        let output = "\
+CPU Temp         | 01h | ok  |  3.1 | 45 degrees C
+PCH Temp         | 0Ah | ok  |  7.1 | 52 degrees C
+FAN1             | 41h | ok  | 29.1 | 1400 RPM
+FAN2             | 42h | ns  | 29.2 | No Reading
+12V              | 30h | ok  |  7.17 | 12.19 Volts
+";
+        let readings = parse_sdr(output);
+        assert_eq!(readings.len(), 3);
+        assert_eq!(readings[0], SdrReading {
+            name: "CPU Temp".to_string(),
+            id: "cpu_temp".to_string(),
+            kind: SdrKind::Temperature,
+            value: 45.0,
+        });
+        assert_eq!(readings[2].kind, SdrKind::Fan);
+        assert_eq!(readings[2].value, 1400.0);
+
+        assert_eq!(parse_path(Path::new("ipmi:zone:1:enable")).unwrap(), IpmiPath::ZoneEnable(1));
+        assert!(parse_path(Path::new("ipmi:zone:x")).is_err());
+    }
+
+    #[test]
+    fn test_duty_command() {
+        assert_eq!(Vendor::Supermicro.duty_command(1, 50).join(" "), "raw 0x70 0x66 0x01 0x01 0x32");
+        assert_eq!(Vendor::Dell.duty_command(0, 100).join(" "), "raw 0x30 0x30 0x02 0xff 0x64");
+    }
+
+    #[test]
+    fn test_zone_holds_decreases_only() {
+        let mut state = ZoneState::default();
+        assert!(!state.holds(30));
+
+        state.sent = Some(50);
+        state.sent_at = Some(Instant::now());
+        assert!(state.holds(30));
+        assert!(!state.holds(100));
+
+        state.sent_at = Instant::now().checked_sub(ipmi::MIN_WRITE_INTERVAL);
+        assert!(!state.holds(30));
+    }
+}
@@ -2,29 +2,59 @@
 //!
 //! Contains all low-level hardware access for hwmon devices and GPUs.
 
+mod backend;
 pub mod binding;
+pub mod binding_transfer;
+mod cache;
+mod calibration;
 mod capture;
 mod control;
 mod detection;
+mod exclusion;
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+mod freebsd;
 pub mod fingerprint;
 mod gpu;
+mod gpu_reconcile;
 mod hardware;
+mod ipmi;
+mod sensor_uuid;
+mod storage;
+mod sysfs;
 
+pub use backend::{backend_for, is_cooling_device_path, is_sysctl_path, HwmonBackend};
+pub use cache::{cached_hwmon_chips, invalidate_hwmon_cache};
+pub use calibration::{
+    calibrate_fan, calibrated_pwm, curve_point_issue, dc_output_pwm, ignores_pwm, response_floor, CurvePointIssue,
+};
 pub use capture::{
     capture_chip_data, capture_raw_snapshot, snapshot_to_json, snapshot_to_json_compact,
 };
 pub use control::{
-    enable_manual_pwm, read_fan_rpm, read_pwm_value, read_temperature, set_pwm_percent,
-    set_pwm_value,
+    control_path_exists, enable_auto_pwm, enable_manual_pwm, is_dc_mode, read_control_value, read_fan_rpm, read_pwm_value,
+    read_sensor_value, read_temperature, set_pwm_percent, set_pwm_value, write_control_value,
 };
 pub use detection::{
-    autodetect_fan_pwm_mappings, autodetect_fan_pwm_mappings_advanced,
-    autodetect_fan_pwm_mappings_heuristic, autodetect_with_fingerprints,
+    autodetect_fan_pwm_mappings, autodetect_fan_pwm_mappings_advanced, autodetect_fan_pwm_mappings_observed,
+    autodetect_fan_pwm_mappings_passive, autodetect_fan_pwm_mappings_heuristic, autodetect_with_fingerprints,
     FingerprintedDetectionResult,
 };
+pub use exclusion::{
+    chip_fingerprint_id, controller_filter, controller_filter_entry, is_pwm_excluded, set_controller_filter,
+    ControllerFilter, ControllerFilterEntry, ControllerFilterMode,
+};
 pub use gpu::{
     capture_gpu_snapshot, enumerate_gpus, enumerate_gpu_pwm_controllers,
     reset_amd_fan_auto, reset_nvidia_fan_auto, set_amd_fan_speed, set_nvidia_fan_speed,
     set_gpu_fan_speed_by_id, GpuPwmController,
+    get_amd_fan_curve, set_amd_fan_curve, reset_amd_fan_curve,
+    get_gpu_power_limit, set_gpu_power_limit,
 };
-pub use hardware::{check_pwm_permissions, enumerate_hwmon_chips};
+pub use gpu_reconcile::{chip_pci_address, reconcile_gpu_pwms, GpuPwmReconciliation};
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+pub use freebsd::SysctlBackend;
+pub use hardware::{check_pwm_permissions, enumerate_hwmon_chips, read_alarm_flag, read_temperature_limits};
+pub use ipmi::{is_ipmi_path, IpmiBackend};
+pub use sensor_uuid::{gpu_pwm_uuid, sensor_uuid};
+pub use storage::{is_smart_path, is_storage_chip, SmartctlBackend};
+pub use sysfs::SysfsBackend;
@@ -0,0 +1,74 @@
+//! Sensor UUIDs
+//!
+//! Fan inputs and PWM outputs carry a UUID that every component derives the
+//! same way, so a pairing saved from one frontend (or by the daemon's
+//! detection) is found by the others and survives `hwmonN` renumbering.
+//!
+//! The UUID is the SHA-256 of the chip's fingerprint id ([`chip_fingerprint_id`]:
+//! driver, device link, PCI or I2C address), the channel name (`pwm2`) and the
+//! channel type, with the first 16 bytes laid out as `8-4-4-4-12` hex. Chips
+//! without a sysfs fingerprint (sysctl, IPMI, SMART) use their name instead,
+//! and GPU fans use their controller id. Nothing is stored: anything that can
+//! read sysfs recomputes the same value without asking the daemon.
+
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use super::exclusion::chip_fingerprint_id;
+use super::fingerprint::ChannelType;
+
+/// Anchor used for GPU fan controllers, which have no hwmon chip of their own
+const GPU_ANCHOR: &str = "gpu";
+
+/// UUID of channel `channel` (`fan1`, `pwm2`) of the hwmon chip at `chip_dir`
+pub fn sensor_uuid(chip_dir: &Path, chip_name: &str, channel: &str, channel_type: ChannelType) -> String {
+    let anchor = chip_fingerprint_id(chip_dir).unwrap_or_else(|| chip_name.to_string());
+    derive_uuid(&anchor, channel, channel_type)
+}
+
+/// UUID of a GPU fan controller (`amd:0:0`, `nvidia:1:0`)
+pub fn gpu_pwm_uuid(controller_id: &str) -> String {
+    derive_uuid(GPU_ANCHOR, controller_id, ChannelType::Pwm)
+}
+
+fn derive_uuid(anchor: &str, channel: &str, channel_type: ChannelType) -> String {
+    let mut hasher = Sha256::new();
+    // NUL-separated so ("ab", "c") and ("a", "bc") differ
+    for part in [anchor, channel, type_tag(channel_type)] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    let hash = hasher.finalize();
+    let hex: String = hash[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+/// Fixed tag per channel type; part of the derivation, so never change these
+fn type_tag(channel_type: ChannelType) -> &'static str {
+    match channel_type {
+        ChannelType::Temperature => "temp",
+        ChannelType::Fan => "fan",
+        ChannelType::Pwm => "pwm",
+        ChannelType::Voltage => "in",
+        ChannelType::Power => "power",
+        ChannelType::Current => "curr",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_uuid() {
+        let uuid = derive_uuid("3f2a9c0d1e4b5a68", "pwm2", ChannelType::Pwm);
+        // Saved pairings depend on this value; a change here orphans them
+        assert_eq!(uuid, "3692136a-558d-7556-7a70-41d0638d602e");
+        // Distinct per chip, channel and type
+        assert_ne!(uuid, derive_uuid("3f2a9c0d1e4b5a69", "pwm2", ChannelType::Pwm));
+        assert_ne!(uuid, derive_uuid("3f2a9c0d1e4b5a68", "pwm1", ChannelType::Pwm));
+        assert_ne!(derive_uuid("chip", "fan2", ChannelType::Fan), derive_uuid("chip", "fan2", ChannelType::Pwm));
+        assert_ne!(derive_uuid("ab", "c", ChannelType::Fan), derive_uuid("a", "bc", ChannelType::Fan));
+    }
+}
@@ -0,0 +1,216 @@
+//! Storage drive temperatures
+//!
+//! NVMe drives report through the `nvme` hwmon driver and SATA/SAS drives
+//! through `drivetemp` when that module is loaded; both are regular hwmon
+//! chips. Drives without a hwmon chip are read with `smartctl -j -A` when
+//! smartmontools is installed and exposed as `smart:<device>` paths on a
+//! "smart" pseudo-chip.
+//!
+//! smartctl takes tens of milliseconds per drive, so readings are cached for
+//! [`SMART_CACHE_TTL`](crate::constants::storage::SMART_CACHE_TTL). Drives in
+//! standby are not woken up (`-n standby`) and keep their last reading.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use tracing::{debug, trace};
+
+use super::backend::HwmonBackend;
+use crate::constants::{paths, storage};
+use crate::data::{HwmonChip, SensorKind, TemperatureLimits, TemperatureSensor};
+use crate::error::{HyperfanError, Result};
+
+/// When each drive was last queried and what it reported
+type Readings = HashMap<String, (Instant, Option<f32>)>;
+
+/// Last smartctl reading per block device
+static READINGS: Mutex<Option<Readings>> = Mutex::new(None);
+
+/// Whether smartctl could be run (checked once)
+static SMARTCTL_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// smartctl-backed drive temperatures (`smart:<device>`)
+pub struct SmartctlBackend;
+
+/// Whether a chip reports drive temperatures (nvme, drivetemp, smartctl)
+pub fn is_storage_chip(name: &str) -> bool {
+    name == storage::SMART_CHIP_NAME || storage::HWMON_CHIP_NAMES.contains(&name)
+}
+
+/// Whether a path refers to a smartctl drive temperature
+pub fn is_smart_path(path: &Path) -> bool {
+    path.to_str().is_some_and(|p| p.starts_with(paths::SMART_PREFIX))
+}
+
+fn smartctl_available() -> bool {
+    *SMARTCTL_AVAILABLE.get_or_init(|| {
+        let available = Command::new(storage::SMARTCTL).arg("--version").output().is_ok_and(|o| o.status.success());
+        debug!(available, "Checked for smartctl");
+        available
+    })
+}
+
+/// Block device named by a `smart:` path
+fn device_of(path: &Path) -> Result<&str> {
+    path.to_str()
+        .and_then(|p| p.strip_prefix(paths::SMART_PREFIX))
+        .filter(|dev| !dev.is_empty() && dev.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()))
+        .ok_or_else(|| HyperfanError::invalid_path(path, "not a smartctl drive"))
+}
+
+/// `temperature.current` from `smartctl -j` output
+fn parse_smartctl_temperature(output: &[u8]) -> Option<f32> {
+    let json: serde_json::Value = serde_json::from_slice(output).ok()?;
+    json.get("temperature")?.get("current")?.as_f64().map(|t| t as f32)
+}
+
+/// Temperature of `device`, re-running smartctl when the cached reading expired
+fn smart_temperature(device: &str) -> Option<f32> {
+    let mut readings = READINGS.lock().unwrap_or_else(|e| e.into_inner());
+    let readings = readings.get_or_insert_with(HashMap::new);
+    if let Some((at, temp)) = readings.get(device) {
+        if at.elapsed() < storage::SMART_CACHE_TTL {
+            return *temp;
+        }
+    }
+
+    let dev_path = format!("/dev/{}", device);
+    let temp = match Command::new(storage::SMARTCTL).args(["-j", "-A", "-n", "standby", &dev_path]).output() {
+        // Drives in standby print no attributes: keep the last reading
+        Ok(output) => parse_smartctl_temperature(&output.stdout)
+            .or_else(|| readings.get(device).and_then(|(_, last)| *last)),
+        Err(e) => {
+            trace!(device, error = %e, "smartctl failed");
+            None
+        }
+    };
+    readings.insert(device.to_string(), (Instant::now(), temp));
+    temp
+}
+
+/// Drive model from sysfs, for labels
+pub(super) fn read_drive_model(device_dir: &Path) -> Option<String> {
+    let model = fs::read_to_string(device_dir.join("model")).ok()?;
+    let model = model.trim();
+    (!model.is_empty()).then(|| model.to_string())
+}
+
+impl HwmonBackend for SmartctlBackend {
+    fn name(&self) -> &'static str {
+        "smartctl"
+    }
+
+    fn enumerate(&self) -> Result<Vec<HwmonChip>> {
+        if !smartctl_available() {
+            return Ok(Vec::new());
+        }
+
+        let mut devices: Vec<PathBuf> = fs::read_dir(paths::BLOCK_BASE)?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| storage::SMART_DEVICE_PREFIXES.iter().any(|prefix| n.starts_with(prefix)))
+            })
+            .collect();
+        devices.sort();
+
+        let mut temperatures = Vec::new();
+        for dir in devices {
+            // drivetemp already exposes this drive as a hwmon chip
+            if dir.join("device/hwmon").is_dir() {
+                continue;
+            }
+            let Some(device) = dir.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some(temp) = smart_temperature(device) else {
+                trace!(device, "No SMART temperature");
+                continue;
+            };
+            temperatures.push(TemperatureSensor {
+                name: device.to_string(),
+                input_path: PathBuf::from(format!("{}{}", paths::SMART_PREFIX, device)),
+                label: Some(read_drive_model(&dir.join("device")).unwrap_or_else(|| device.to_string())),
+                current_temp: Some(temp),
+                limits: TemperatureLimits::default(),
+            });
+        }
+
+        if temperatures.is_empty() {
+            return Ok(Vec::new());
+        }
+        debug!(drives = temperatures.len(), "Found drive temperatures via smartctl");
+        Ok(vec![HwmonChip {
+            name: storage::SMART_CHIP_NAME.to_string(),
+            path: PathBuf::from(paths::BLOCK_BASE),
+            temperatures,
+            fans: Vec::new(),
+            pwms: Vec::new(),
+            sensors: Vec::new(),
+            alarms: Vec::new(),
+        }])
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        device_of(path).is_ok_and(|dev| Path::new(paths::BLOCK_BASE).join(dev).exists())
+    }
+
+    fn read_temperature(&self, path: &Path) -> Result<f32> {
+        smart_temperature(device_of(path)?).ok_or_else(|| HyperfanError::TemperatureRead {
+            path: path.to_path_buf(),
+            reason: "No SMART temperature available".to_string(),
+        })
+    }
+
+    fn read_fan_rpm(&self, path: &Path) -> Result<u32> {
+        Err(HyperfanError::NotSupported(format!("fan speed via smartctl: {}", path.display())))
+    }
+
+    fn read_sensor(&self, path: &Path, kind: SensorKind) -> Result<f32> {
+        Err(HyperfanError::NotSupported(format!("{} via smartctl: {}", kind, path.display())))
+    }
+
+    fn read_pwm(&self, path: &Path) -> Result<u8> {
+        Err(HyperfanError::NotSupported(format!("PWM via smartctl: {}", path.display())))
+    }
+
+    fn write_pwm(&self, path: &Path, _value: u8) -> Result<()> {
+        Err(HyperfanError::NotSupported(format!("PWM via smartctl: {}", path.display())))
+    }
+
+    fn enable_manual(&self, enable_path: &Path) -> Result<()> {
+        Err(HyperfanError::NotSupported(format!("PWM via smartctl: {}", enable_path.display())))
+    }
+
+    fn enable_auto(&self, enable_path: &Path) -> Result<()> {
+        Err(HyperfanError::NotSupported(format!("PWM via smartctl: {}", enable_path.display())))
+    }
+
+    fn read_raw(&self, path: &Path) -> Result<String> {
+        self.read_temperature(path).map(|t| t.to_string())
+    }
+
+    fn write_raw(&self, path: &Path, _value: &str) -> Result<()> {
+        Err(HyperfanError::NotSupported(format!("writing via smartctl: {}", path.display())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_smartctl_temperature() {
+        let output = br#"{"json_format_version":[1,0],"device":{"name":"/dev/sda"},"temperature":{"current":34}}"#;
+        assert_eq!(parse_smartctl_temperature(output), Some(34.0));
+
+        // Standby: smartctl prints no attributes
+        let standby = br#"{"json_format_version":[1,0],"smartctl":{"exit_status":2}}"#;
+        assert_eq!(parse_smartctl_temperature(standby), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! Linux sysfs hwmon backend
+//!
+//! Every sensor and control is a small text file under `/sys/class/hwmon`:
+//! temperatures in millidegrees, fan speeds in RPM, PWM duty cycles 0-255 and
+//! `pwmN_enable` modes (1 = manual, 2 = automatic). Thermal cooling device
+//! `cur_state` files (devicetree fans) are scaled from their `max_state` to
+//! 0-255 so they behave like any other PWM output.
+
+use std::fs;
+use std::path::Path;
+
+use super::backend::{is_cooling_device_path, HwmonBackend};
+use crate::constants::{cooling, paths, pwm, temperature};
+use crate::data::{HwmonChip, SensorKind};
+use crate::error::{HyperfanError, Result};
+
+/// sysfs hwmon access (Linux)
+pub struct SysfsBackend;
+
+/// `max_state` of the cooling device owning a `cur_state` path
+fn cooling_max_state(state_path: &Path) -> Result<u32> {
+    let max_path = state_path.with_file_name(cooling::MAX_STATE_FILE);
+    let content = fs::read_to_string(&max_path)
+        .map_err(|e| HyperfanError::FileRead { path: max_path.clone(), source: e })?;
+    content
+        .trim()
+        .parse()
+        .map_err(|e| HyperfanError::Generic(format!("Failed to parse max_state '{}' from {}: {}", content.trim(), max_path.display(), e)))
+}
+
+impl HwmonBackend for SysfsBackend {
+    fn name(&self) -> &'static str {
+        "sysfs"
+    }
+
+    fn enumerate(&self) -> Result<Vec<HwmonChip>> {
+        super::hardware::enumerate_linux_hwmon(Path::new(paths::HWMON_BASE))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn read_temperature(&self, path: &Path) -> Result<f32> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| HyperfanError::TemperatureRead { path: path.to_path_buf(), reason: format!("Failed to read: {}", e) })?;
+
+        let millidegrees = content
+            .trim()
+            .parse::<i32>()
+            .map_err(|e| HyperfanError::TemperatureRead { path: path.to_path_buf(), reason: format!("Failed to parse '{}': {}", content.trim(), e) })?;
+
+        // Convert millidegrees to degrees Celsius
+        Ok(millidegrees as f32 / temperature::MILLIDEGREE_DIVISOR)
+    }
+
+    fn read_fan_rpm(&self, path: &Path) -> Result<u32> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| HyperfanError::FanRead { path: path.to_path_buf(), reason: format!("Failed to read: {}", e) })?;
+
+        content
+            .trim()
+            .parse::<u32>()
+            .map_err(|e| HyperfanError::FanRead { path: path.to_path_buf(), reason: format!("Failed to parse '{}': {}", content.trim(), e) })
+    }
+
+    fn read_sensor(&self, path: &Path, kind: SensorKind) -> Result<f32> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| HyperfanError::FileRead { path: path.to_path_buf(), source: e })?;
+
+        let raw = content
+            .trim()
+            .parse::<i64>()
+            .map_err(|e| HyperfanError::Generic(format!("Failed to parse {} value '{}' from {}: {}", kind, content.trim(), path.display(), e)))?;
+
+        Ok(raw as f32 / kind.divisor())
+    }
+
+    fn read_pwm(&self, path: &Path) -> Result<u8> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| HyperfanError::PwmRead { path: path.to_path_buf(), reason: format!("Failed to read: {}", e) })?;
+
+        if is_cooling_device_path(path) {
+            let state = content
+                .trim()
+                .parse::<u32>()
+                .map_err(|e| HyperfanError::PwmRead { path: path.to_path_buf(), reason: format!("Failed to parse '{}': {}", content.trim(), e) })?;
+            return Ok(cooling::state_to_pwm(state, cooling_max_state(path)?));
+        }
+
+        content
+            .trim()
+            .parse::<u8>()
+            .map_err(|e| HyperfanError::PwmRead { path: path.to_path_buf(), reason: format!("Failed to parse '{}': {}", content.trim(), e) })
+    }
+
+    fn write_pwm(&self, path: &Path, value: u8) -> Result<()> {
+        if is_cooling_device_path(path) {
+            let state = cooling::pwm_to_state(value, cooling_max_state(path)?);
+            return fs::write(path, state.to_string())
+                .map_err(|e| HyperfanError::PwmWrite { path: path.to_path_buf(), reason: format!("Failed to write cooling state {}: {}", state, e) });
+        }
+
+        fs::write(path, value.to_string()).map_err(|e| match e.kind() {
+            // The driver was busy talking to the chip: keep the error so the write can be retried
+            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::ResourceBusy | std::io::ErrorKind::Interrupted => {
+                HyperfanError::FileWrite { path: path.to_path_buf(), source: e }
+            }
+            _ => HyperfanError::PwmWrite { path: path.to_path_buf(), reason: format!("Failed to write PWM value {}: {}", value, e) },
+        })
+    }
+
+    fn enable_manual(&self, enable_path: &Path) -> Result<()> {
+        if !enable_path.exists() {
+            return Ok(()); // No enable file means manual control is always active
+        }
+        fs::write(enable_path, pwm::enable::MANUAL.to_string())
+            .map_err(|e| HyperfanError::PwmWrite { path: enable_path.to_path_buf(), reason: format!("Failed to enable manual PWM control: {}", e) })
+    }
+
+    fn enable_auto(&self, enable_path: &Path) -> Result<()> {
+        if !enable_path.exists() {
+            return Ok(());
+        }
+        fs::write(enable_path, pwm::enable::AUTOMATIC.to_string())
+            .map_err(|e| HyperfanError::PwmWrite { path: enable_path.to_path_buf(), reason: format!("Failed to restore automatic PWM control: {}", e) })
+    }
+
+    fn read_raw(&self, path: &Path) -> Result<String> {
+        fs::read_to_string(path)
+            .map(|s| s.trim().to_string())
+            .map_err(|e| HyperfanError::FileRead { path: path.to_path_buf(), source: e })
+    }
+
+    fn write_raw(&self, path: &Path, value: &str) -> Result<()> {
+        fs::write(path, value).map_err(|e| HyperfanError::FileWrite { path: path.to_path_buf(), source: e })
+    }
+}
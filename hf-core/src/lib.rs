@@ -35,21 +35,28 @@ pub mod fingerprinting;
 pub mod hw;
 
 // Standalone modules
+pub mod competing_software;
 pub mod constants;
 pub mod daemon_client;
+pub mod diagnostics;
 pub mod display;
 pub mod error;
+pub mod provision;
+pub mod recording;
+pub mod schedule;
 pub mod service;
 pub mod settings;
+pub mod setup;
+pub mod support_bundle;
 pub mod system;
 
 // Re-export primary types from data/
 pub use data::{
-    CurvePoint, FanMapping, FanSensor, HwmonChip, ProbeResult, PwmController,
+    CoolingDevice, CurvePoint, FanMapping, FanSensor, HwmonAlarm, HwmonChip, HwmonSensor, ProbeResult, PwmController, SensorKind,
     RawChipData, RawControllerSnapshot, RawFanReading, RawPwmReading, RawTempReading,
-    SystemSummary, TempSource, TemperatureSensor,
+    SystemSummary, TempSource, TemperatureLimits, TemperatureSensor,
     // GPU types
-    GpuDevice, GpuFan, GpuSnapshot, GpuTemperature, GpuVendor,
+    GpuDevice, GpuFan, GpuFanCurve, GpuFanCurvePoint, GpuPowerLimit, GpuSnapshot, GpuTemperature, GpuVendor,
 };
 
 // Re-export config functions from data/
@@ -65,31 +72,56 @@ pub use data::{
 
 // Re-export persistence functions from data/
 pub use data::{
-    delete_curve, get_curves_path, load_curves, save_curve, save_curves,
-    update_curve_points, CurveStore, PersistedCurve,
+    copy_curve_to_pairs, delete_curve, get_curves_path, load_curves, save_curve, save_curves,
+    update_curve_points, set_curve_battery_offset, set_curve_fallback_sources, ConfigFormat, CurveCopy, CurveInterpolation, CurveOffset, CurveStore, PersistedCurve,
+};
+
+// Re-export curve template sharing from data/
+pub use data::{
+    export_curves_template, export_profile_template, import_template, suggest_sensor_mapping, CurveTemplate,
+    SensorMapping, SensorSuggestion, TemplateImport, TemplateMetadata, TemplateProfile, TemplateSensor,
 };
 
 // Re-export error types
 pub use error::{HyperfanError, Result};
 
 // Re-export engine types
-pub use engine::{CurvePreset, FanCurve};
+pub use engine::{
+    auto_tune_curve, is_settled, required_duty, curve_value, sample_curve, CurvePreset, FanCurve, FilterState, RpmController, NoiseProfile, combined_dba, curve_dba, limit_curve_noise, max_duty_for_noise, SensorFilter, virtual_sensor_id, VirtualFormula, VirtualSensor,
+};
 
 // Re-export hardware functions from hw/
 pub use hw::{
-    autodetect_fan_pwm_mappings, autodetect_fan_pwm_mappings_advanced,
-    autodetect_fan_pwm_mappings_heuristic, autodetect_with_fingerprints,
+    autodetect_fan_pwm_mappings, autodetect_fan_pwm_mappings_advanced, autodetect_fan_pwm_mappings_observed,
+    autodetect_fan_pwm_mappings_passive, autodetect_fan_pwm_mappings_heuristic, autodetect_with_fingerprints,
     FingerprintedDetectionResult,
     capture_chip_data, capture_raw_snapshot,
-    check_pwm_permissions, enable_manual_pwm, enumerate_hwmon_chips, read_fan_rpm,
-    read_pwm_value, read_temperature, set_pwm_percent, set_pwm_value, snapshot_to_json,
+    cached_hwmon_chips, invalidate_hwmon_cache,
+    calibrate_fan, calibrated_pwm, curve_point_issue, dc_output_pwm, ignores_pwm, response_floor, CurvePointIssue,
+    check_pwm_permissions, enable_manual_pwm, enumerate_hwmon_chips, read_alarm_flag, read_fan_rpm, read_temperature_limits,
+    read_pwm_value, read_sensor_value, read_temperature, set_pwm_percent, set_pwm_value, snapshot_to_json,
     snapshot_to_json_compact,
+    // Platform backends
+    backend_for, is_cooling_device_path, is_ipmi_path, is_smart_path, is_storage_chip, is_sysctl_path,
+    HwmonBackend, IpmiBackend, SmartctlBackend, SysfsBackend,
+    control_path_exists, enable_auto_pwm, is_dc_mode, read_control_value, write_control_value,
+    // Sensor UUIDs
+    gpu_pwm_uuid, sensor_uuid,
+    // Controller exclusion
+    chip_fingerprint_id, controller_filter, controller_filter_entry, is_pwm_excluded, set_controller_filter,
+    ControllerFilter, ControllerFilterEntry, ControllerFilterMode,
     // GPU functions
     capture_gpu_snapshot, enumerate_gpus, enumerate_gpu_pwm_controllers,
     reset_amd_fan_auto, reset_nvidia_fan_auto, set_amd_fan_speed, set_nvidia_fan_speed,
     set_gpu_fan_speed_by_id, GpuPwmController,
+    get_amd_fan_curve, set_amd_fan_curve, reset_amd_fan_curve,
+    get_gpu_power_limit, set_gpu_power_limit,
+    chip_pci_address, reconcile_gpu_pwms, GpuPwmReconciliation,
 };
 
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+pub use hw::SysctlBackend;
+
 // Re-export fingerprint types and functions from hw/fingerprint
 pub use hw::fingerprint::{
     // Core enums
@@ -115,18 +147,30 @@ pub use hw::fingerprint::{
 pub use hw::binding::{
     BindingStore, ValidationReport, BindingValidationResult, FallbackAction,
     validate_all_bindings, discover_and_fingerprint_system,
-    apply_safe_fallbacks, execute_fallback,
+    apply_safe_fallbacks, execute_fallback, find_binding_conflicts,
+};
+pub use hw::binding_transfer::{
+    export_bindings, import_bindings, plan_binding_import, BindingExport, BindingExportMetadata,
+    BindingImport, BindingImportEntry, BindingImportPlan,
 };
+pub use provision::{
+    apply_bundle, check_bundle, check_bundle_with_snapshot, check_config, export_bundle, ConfigBundle, ConfigCheckError,
+    ConfigCheckReport, ProvisionCheck, ProvisionOutcome, ProvisionRepoint,
+};
+pub use setup::{apply_setup, SetupFan, SetupPlan};
+pub use support_bundle::{create_support_bundle, default_bundle_name};
+pub use diagnostics::{run_diagnostics, DiagnosticCheck, DiagnosticFix, DiagnosticStatus};
+pub use competing_software::{detect_competing_software, DetectedFanSoftware, KnownFanSoftware};
 
 // Re-export system functions
-pub use system::{get_os_name, get_system_summary, get_memory_available_mb, get_memory_total_mb, is_bsd, is_linux};
+pub use system::{get_os_name, get_power_source, get_system_summary, get_memory_available_mb, get_memory_total_mb, is_bsd, is_linux, PowerSource};
 
 // Re-export settings functions
 pub use settings::{
-    AppSettings, DisplaySettings, FanCurvePair, GeneralSettings, PwmFanPairing,
-    SensorFriendlyName, PwmHardwareId, FanHardwareId, AdvancedSettings,
+    AppSettings, DisplaySettings, FanCurvePair, GeneralSettings, AdaptivePollSettings, AlarmBoostSettings, PwmFanPairing,
+    SensorFriendlyName, PwmHardwareId, FanHardwareId, AdvancedSettings, NotificationSettings,
     delete_pair, get_active_pairs, get_settings_path, load_settings,
-    save_pair, save_settings, update_setting,
+    save_pair, save_settings, save_virtual_sensor, delete_virtual_sensor, update_setting, set_pair_profile_curve,
     // Cached settings (PERFORMANCE: use these in hot paths like draw functions)
     get_cached_settings, get_graph_style, get_graph_smoothing, get_frame_rate, invalidate_settings_cache,
     // PWM-fan mapping functions
@@ -140,6 +184,12 @@ pub use settings::{
     load_binding_store, save_binding_store, get_binding_store_path, binding_store_exists,
     // Sensor friendly name functions
     get_sensor_friendly_name, set_sensor_friendly_name, get_all_sensor_friendly_names,
+    // Sensor colors and theme presets
+    parse_hex_color, ThemePreset,
+    // Per-source temperature filters
+    get_sensor_filter, set_sensor_filter,
+    // Controller exclusion
+    add_controller_filter_entry, remove_controller_filter_entry, set_controller_filter_mode,
     // Hardware identification extraction (CRITICAL for safe pairings)
     extract_pwm_hardware_id, extract_fan_hardware_id,
     // Fingerprinted pairing creation and validation (ZERO DRIFT)
@@ -155,10 +205,10 @@ pub use settings::{
 
 // Re-export service management functions
 pub use service::{
-    InitSystem, detect_init_system, get_socket_path,
-    is_service_installed, is_service_running, is_socket_available,
+    InitSystem, detect_init_system, get_socket_path, get_monitor_socket_path,
+    is_service_installed, is_service_running, is_service_enabled, is_socket_available,
     install_service, uninstall_service, reinstall_service,
-    start_service, stop_service, restart_service,
+    start_service, enable_service, stop_service, restart_service,
     get_service_status, find_daemon_binary,
 };
 
@@ -166,22 +216,75 @@ pub use service::{
 pub use daemon_client::{
     DaemonClient, DaemonRequest, DaemonResponse, DaemonResponseData,
     DaemonHardwareInfo, DaemonHwmonChip, DaemonTempSensor, DaemonFanSensor,
-    DaemonPwmControl, DaemonGpuInfo, DaemonFanMapping,
-    DaemonManualPwmFanPairing, DaemonEcChipInfo, DaemonEcRegisterValue,
+    DaemonPwmControl, DaemonGpuInfo, DaemonGpuTempInfo, DaemonHwSensor, DaemonSensorKind, DaemonHwAlarm, DaemonAlarmKind, DaemonFanMapping,
+    DaemonManualPwmFanPairing, DaemonEcChipInfo, DaemonEcRegisterValue, DaemonEcRegisterChange,
+    DaemonEcFanProfile, DaemonEcFanMap,
     is_daemon_available, ping_daemon, get_daemon_version,
     daemon_read_temperature, daemon_read_fan_rpm, daemon_read_pwm,
     daemon_set_pwm, daemon_enable_manual_pwm, daemon_disable_manual_pwm,
-    daemon_set_pwm_override, daemon_clear_pwm_override,
+    daemon_set_pwm_override, daemon_clear_pwm_override, daemon_list_active_overrides, DaemonActiveOverride,
+    MAX_PWM_OVERRIDE_TTL_MS,
+    DaemonCompetingSoftware, DaemonCompetingSoftwareReport, DaemonPwmEnableFlip, daemon_get_competing_software, daemon_preview_curve, daemon_clear_curve_preview,
     daemon_list_hardware, daemon_list_all, DaemonAllHardwareData,
     daemon_list_gpus, daemon_set_gpu_fan, daemon_set_gpu_fan_for_fan,
-    daemon_detect_fan_mappings, daemon_reload_config,
+    daemon_detect_fan_mappings, daemon_reload_config, daemon_reload_config_section,
+    DaemonDetectionProgress, DaemonDetectionJobState, DaemonDetectionStrategy,
+    daemon_start_detection, daemon_get_detection_progress, daemon_cancel_detection,
+    DaemonAutoTuneProgress, DaemonAutoTuneStep, MIN_AUTO_TUNE_TARGET, MAX_AUTO_TUNE_TARGET,
+    daemon_start_auto_tune, daemon_get_auto_tune_progress, daemon_cancel_auto_tune,
+    DaemonBindingConflict, DaemonBindingConflictState, DaemonBindingCandidate,
+    daemon_get_binding_conflicts, daemon_accept_binding_match,
     daemon_reset_gpu_fan_auto,
     daemon_get_manual_pairings, daemon_set_manual_pairing, daemon_delete_manual_pairing,
+    DaemonCurveTuning, daemon_set_manual_pairing_with_tuning,
+    DaemonPairingControlMode, daemon_set_pairing_control_mode, daemon_set_pairing_zero_rpm,
+    DaemonPwmEnforcement, daemon_set_pairing_enforcement,
+    DaemonFanCalibration, DaemonCalibrationStatus, daemon_calibrate_fan, daemon_get_fan_calibration,
     daemon_list_ec_chips, daemon_read_ec_register, daemon_write_ec_register, daemon_read_ec_register_range,
+    daemon_watch_ec_registers, MIN_EC_WATCH_INTERVAL_MS, MAX_EC_WATCH_DURATION_MS,
+    daemon_set_ec_fan_duty,
+    // PWM groups
+    DaemonPwmGroup, DaemonPwmGroupMember, daemon_get_pwm_groups, daemon_set_pwm_group, daemon_delete_pwm_group,
+    MAX_PWM_GROUP_OFFSET_PERCENT,
+    // Fan failure alerts
+    DaemonFanAlert, DaemonFanAlertKind, daemon_get_fan_alerts,
+    // Hotplug events
+    DaemonHardwareChange, DaemonHardwareChangeKind, daemon_get_hardware_changes,
+    // Daemon log ring buffer
+    DaemonLogEntry, DaemonLogLevel, daemon_get_logs,
+    // Pushed events
+    DaemonEvent, DaemonEventEnvelope, DaemonEventSubscription, DaemonConfigSection,
+    // Session recording
+    DaemonRecordingInfo, DaemonRecordingFormat, daemon_start_recording, daemon_stop_recording,
+    daemon_start_recording_with_limits, daemon_get_recording,
+    // Long-term sensor history
+    DaemonHistoryData, DaemonHistorySeries, DaemonHistorySeriesKind, DaemonHistoryPoint, daemon_query_history,
+    // Curve profiles
+    DaemonProfileInfo, daemon_get_active_profile, daemon_set_active_profile, daemon_get_dry_run, daemon_set_dry_run, daemon_get_safe_mode,
+    // Control loop status
+    DaemonControlStatus, DaemonImplausibleReadings, daemon_get_control_status,
+    // GPU firmware fan curves
+    DaemonGpuFanCurveInfo, DaemonGpuFanCurvePoint,
+    daemon_get_gpu_fan_curve, daemon_set_gpu_fan_curve, daemon_reset_gpu_fan_curve,
+    // GPU power limits
+    DaemonGpuPowerLimitInfo,
+    daemon_get_gpu_power_limit, daemon_set_gpu_power_limit, daemon_reset_gpu_power_limit,
     // Rate limit configuration
     MIN_RATE_LIMIT, MAX_RATE_LIMIT,
     get_client_rate_limit, set_client_rate_limit,
     daemon_get_rate_limit, daemon_set_rate_limit, set_rate_limits,
+    daemon_get_rate_limit_config, daemon_set_rate_limit_config,
+    DaemonRateLimitConfig, DaemonRequestCosts, DaemonRequestClass,
+};
+
+// Re-export session recording types
+pub use recording::{
+    load_recording, Recording, RecordingHeader, RecordingSample, RecordingWriter,
+};
+
+// Re-export profile schedule types
+pub use schedule::{
+    LocalTime, ProfileSchedule, ScheduleEntry, parse_time_of_day,
 };
 
 // Re-export display formatting functions
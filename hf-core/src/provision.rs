@@ -0,0 +1,592 @@
+//! Headless provisioning from a config bundle
+//!
+//! A [`ConfigBundle`] carries everything one workstation needs: settings
+//! (pairings, pairs, groups, virtual sensors and the active profile), curves
+//! and, optionally, the fingerprinted binding store. Sysadmins imaging many
+//! identical machines export the bundle once and apply it on each machine
+//! with `hyperfand --provision` or `hyperfan bundle apply`.
+//!
+//! [`check_bundle`] validates the bundle against this machine without writing
+//! anything: bindings are re-anchored by their fingerprints (hwmon numbering
+//! may differ between otherwise identical machines), PWM paths are moved to
+//! wherever their bindings were found, and every hardware path the settings
+//! and curves refer to must exist. [`apply_bundle`] stages all stores before
+//! moving any of them into place, so a failure leaves the old config intact.
+//!
+//! [`check_config`] runs the same checks for `hyperfand --check-config`, on a
+//! bundle or a config directory, and collects every problem into a
+//! [`ConfigCheckReport`] for CI and configuration management. It can check
+//! against a `hyperfan hardware snapshot` instead of this machine.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+use crate::constants::paths;
+use crate::data::{
+    config_path_in, merge_layer, read_config, stage_config, validate_file_size, ConfigFormat, CurveStore,
+    RawControllerSnapshot,
+};
+use crate::engine::virtual_sensor_id;
+use crate::error::{HyperfanError, Result};
+use crate::hw::binding::BindingStore;
+use crate::hw::binding_transfer::{
+    merge_importable, plan_binding_import, BindingExport, BindingExportMetadata, BindingImport, BindingImportPlan,
+};
+use crate::settings::AppSettings;
+
+/// `format` field of every config bundle
+pub const BUNDLE_FORMAT: &str = "hyperfan-config-bundle";
+
+/// Newest bundle version this build reads and the one it writes
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Contents of a config bundle file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub format: String,
+    pub version: u32,
+    /// Where the bundle was made (same fields as a binding export)
+    #[serde(default)]
+    pub metadata: BindingExportMetadata,
+    pub settings: AppSettings,
+    pub curves: CurveStore,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bindings: Option<BindingStore>,
+}
+
+impl ConfigBundle {
+    pub fn new(
+        settings: AppSettings,
+        curves: CurveStore,
+        bindings: Option<BindingStore>,
+        metadata: BindingExportMetadata,
+    ) -> Self {
+        Self {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            metadata,
+            settings,
+            curves,
+            bindings,
+        }
+    }
+
+    /// Read and validate a bundle file
+    pub fn load(path: &Path) -> Result<Self> {
+        let bundle = Self::read(path)?;
+        bundle.validate()?;
+        debug!(
+            "Loaded config bundle with {} pairs and {} curves from {:?}",
+            bundle.settings.active_pairs.len(),
+            bundle.curves.len(),
+            path
+        );
+        Ok(bundle)
+    }
+
+    fn read(path: &Path) -> Result<Self> {
+        validate_file_size(path)?;
+        let contents = fs::read_to_string(path)
+            .map_err(|e| HyperfanError::FileRead { path: path.to_path_buf(), source: e })?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Bundle of the settings, curves and bindings files in a config
+    /// directory (such as a staged `/etc/hyperfan`), not validated
+    ///
+    /// Settings may list only some keys; the rest keep their defaults.
+    pub fn load_dir(dir: &Path) -> Result<Self> {
+        let settings_path = config_path_in(dir, "settings");
+        let curves_path = config_path_in(dir, "curves");
+        if settings_path.is_none() && curves_path.is_none() {
+            return Err(HyperfanError::config(format!("no settings or curves file in {}", dir.display())));
+        }
+
+        let mut settings = serde_json::to_value(AppSettings::default())?;
+        if let Some(path) = settings_path {
+            validate_file_size(&path)?;
+            merge_layer(&mut settings, read_config(&path)?);
+        }
+        let curves = match curves_path {
+            Some(path) => {
+                validate_file_size(&path)?;
+                read_config(&path)?
+            }
+            None => CurveStore::new(),
+        };
+        let bindings = config_path_in(dir, "bindings").map(|path| read_config(&path)).transpose()?;
+        Ok(Self::new(serde_json::from_value(settings)?, curves, bindings, BindingExportMetadata::default()))
+    }
+
+    /// Write the bundle as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).map_err(|e| HyperfanError::FileWrite { path: path.to_path_buf(), source: e })?;
+        info!("Exported config bundle to {:?}", path);
+        Ok(())
+    }
+
+    /// Check the format, the curves and that every curve a pair, group or
+    /// profile refers to is in the bundle
+    pub fn validate(&self) -> Result<()> {
+        self.validate_format()?;
+        match self.problems().into_iter().next() {
+            Some(problem) => Err(HyperfanError::config(problem)),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_format(&self) -> Result<()> {
+        if self.format != BUNDLE_FORMAT {
+            return Err(HyperfanError::config("not a Hyperfan config bundle"));
+        }
+        if self.version == 0 || self.version > BUNDLE_VERSION {
+            return Err(HyperfanError::config(format!(
+                "config bundle version {} is not supported (newest: {})",
+                self.version, BUNDLE_VERSION
+            )));
+        }
+        if let Some(store) = &self.bindings {
+            if store.version > BindingStore::CURRENT_VERSION {
+                return Err(HyperfanError::config(format!(
+                    "binding store version {} is newer than this build supports",
+                    store.version
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Everything wrong with the settings and curves: bad curve points,
+    /// references to curves not in the bundle, invalid virtual sensors,
+    /// filters, profile name or schedule
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Err(e) = hf_protocol::validate_profile_name(&self.settings.active_profile) {
+            problems.push(format!("Active profile: {}", e));
+        }
+        problems.extend(crate::diagnostics::config_problems(&self.settings, &self.curves));
+        problems
+    }
+}
+
+/// A PWM path of the bundle and where its binding was found on this machine
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProvisionRepoint {
+    pub from: String,
+    pub to: String,
+    pub fan_path: Option<String>,
+}
+
+/// Dry-run result of provisioning this machine from a bundle
+#[derive(Debug, Clone, Default)]
+pub struct ProvisionCheck {
+    /// Bundle bindings matched against this machine (None without bindings)
+    pub bindings: Option<BindingImportPlan>,
+    /// PWM paths that moved between the bundle's machine and this one
+    pub repointed: Vec<ProvisionRepoint>,
+    /// Paths the settings or curves refer to that don't exist here
+    pub missing: Vec<String>,
+}
+
+impl ProvisionCheck {
+    /// Bindings in the bundle that don't match any hardware here
+    pub fn unmatched_bindings(&self) -> usize {
+        self.bindings
+            .as_ref()
+            .map(|plan| plan.entries.len() - plan.importable_count())
+            .unwrap_or(0)
+    }
+
+    /// Whether the bundle fits this machine completely
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.unmatched_bindings() == 0
+    }
+}
+
+/// Stores written by [`apply_bundle`]
+#[derive(Debug, Clone)]
+pub struct ProvisionOutcome {
+    pub settings_path: PathBuf,
+    pub curves_path: PathBuf,
+    /// None when the bundle had no bindings
+    pub bindings: Option<BindingImport>,
+}
+
+/// Bundle of this machine's current settings, curves and bindings
+pub fn export_bundle() -> Result<ConfigBundle> {
+    let settings = crate::settings::load_settings()?;
+    let curves = crate::data::load_curves()?;
+    let bindings = BindingStore::load().map_err(HyperfanError::config)?;
+    Ok(ConfigBundle::new(
+        settings,
+        curves,
+        Some(bindings).filter(|store| !store.bindings.is_empty()),
+        bundle_metadata(),
+    ))
+}
+
+fn bundle_metadata() -> BindingExportMetadata {
+    let summary = crate::system::get_system_summary().ok();
+    BindingExportMetadata {
+        hostname: summary.as_ref().map(|s| s.hostname.clone()).filter(|h| !h.is_empty()),
+        hardware: summary.map(|s| s.motherboard_name).filter(|name| !name.is_empty()),
+        created_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    }
+}
+
+/// Match the bundle against this machine's hardware
+///
+/// Nothing is written.
+pub fn check_bundle(bundle: &ConfigBundle) -> ProvisionCheck {
+    check_against(bundle, true, |path| Path::new(path).exists())
+}
+
+/// Match the bundle against a hardware snapshot from another machine
+///
+/// Only hwmon paths are checked, since that is what a snapshot records, and
+/// bindings are not matched: that needs the live chips to fingerprint.
+pub fn check_bundle_with_snapshot(bundle: &ConfigBundle, snapshot: &RawControllerSnapshot) -> ProvisionCheck {
+    let mut present = BTreeSet::new();
+    for chip in &snapshot.chips {
+        present.extend(chip.temperatures.iter().map(|t| t.sensor_path.clone()));
+        present.extend(chip.fans.iter().map(|f| f.sensor_path.clone()));
+        present.extend(chip.pwms.iter().map(|p| p.pwm_path.clone()));
+    }
+    check_against(bundle, false, |path| !path.starts_with(paths::HWMON_BASE) || present.contains(Path::new(path)))
+}
+
+fn check_against(bundle: &ConfigBundle, match_bindings: bool, exists: impl Fn(&str) -> bool) -> ProvisionCheck {
+    let mut check = ProvisionCheck::default();
+
+    if let Some(store) = bundle.bindings.as_ref().filter(|_| match_bindings) {
+        let plan = plan_binding_import(&binding_export(bundle, store));
+        check.repointed = plan
+            .entries
+            .iter()
+            .filter(|entry| entry.importable())
+            .filter_map(|entry| {
+                let to = entry.pwm_path.as_ref()?.to_string_lossy().to_string();
+                (to != entry.exported_pwm_path).then(|| ProvisionRepoint {
+                    from: entry.exported_pwm_path.clone(),
+                    to,
+                    fan_path: entry.fan_path.as_ref().map(|p| p.to_string_lossy().to_string()),
+                })
+            })
+            .collect();
+        check.bindings = Some(plan);
+    }
+
+    let settings = provisioned_settings(bundle, &check);
+    check.missing = referenced_paths(&settings, &bundle.curves)
+        .into_iter()
+        .filter(|path| match virtual_sensor_id(path) {
+            Some(id) => !settings.virtual_sensors.iter().any(|s| s.id == id),
+            // GPU and other non-file sources are resolved by the daemon
+            None => path.starts_with('/') && !exists(path),
+        })
+        .collect();
+    check
+}
+
+/// Result of validating a bundle for `hyperfand --check-config`
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigCheckReport {
+    /// Whether the bundle can be provisioned without `--force`
+    pub valid: bool,
+    /// `live` (this machine) or `snapshot`
+    pub hardware: &'static str,
+    pub errors: Vec<ConfigCheckError>,
+    /// PWM paths that would move to where their bindings were found
+    pub repointed: Vec<ProvisionRepoint>,
+}
+
+/// One reason a bundle does not validate
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigCheckError {
+    /// `unreadable`, `invalid`, `missing_path` or `unmatched_binding`
+    pub code: &'static str,
+    pub message: String,
+    /// Hardware path the error is about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl ConfigCheckError {
+    fn new(code: &'static str, message: impl Into<String>, path: Option<String>) -> Self {
+        Self { code, message: message.into(), path }
+    }
+}
+
+/// Validate the bundle file or config directory at `path` against this
+/// machine, or against `snapshot` when given
+///
+/// Unlike [`ConfigBundle::load`] and [`check_bundle`], every problem is
+/// reported rather than the first. Nothing is written.
+pub fn check_config(path: &Path, snapshot: Option<&RawControllerSnapshot>) -> ConfigCheckReport {
+    let mut report = ConfigCheckReport {
+        valid: false,
+        hardware: if snapshot.is_some() { "snapshot" } else { "live" },
+        errors: Vec::new(),
+        repointed: Vec::new(),
+    };
+    let loaded = if path.is_dir() { ConfigBundle::load_dir(path) } else { ConfigBundle::read(path) };
+    let bundle = match loaded {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            report.errors.push(ConfigCheckError::new("unreadable", format!("{}: {}", path.display(), e), None));
+            return report;
+        }
+    };
+
+    if let Err(e) = bundle.validate_format() {
+        report.errors.push(ConfigCheckError::new("invalid", e.to_string(), None));
+    }
+    report.errors.extend(bundle.problems().into_iter().map(|problem| ConfigCheckError::new("invalid", problem, None)));
+
+    let check = match snapshot {
+        Some(snapshot) => check_bundle_with_snapshot(&bundle, snapshot),
+        None => check_bundle(&bundle),
+    };
+    for missing in check.missing {
+        let message = format!("{} does not exist", missing);
+        report.errors.push(ConfigCheckError::new("missing_path", message, Some(missing)));
+    }
+    for entry in check.bindings.iter().flat_map(|plan| &plan.entries).filter(|e| !e.importable()) {
+        let message = format!("binding '{}' ({}) matches no hardware", entry.name, entry.chip_name);
+        report.errors.push(ConfigCheckError::new("unmatched_binding", message, Some(entry.exported_pwm_path.clone())));
+    }
+    report.repointed = check.repointed;
+    report.valid = report.errors.is_empty();
+    report
+}
+
+/// Write the bundle's settings, curves and bindings as this machine's config
+///
+/// `check` must come from [`check_bundle`] for the same bundle. Unless
+/// `force` is set, a bundle that doesn't fit this machine completely is
+/// refused. The binding store is replaced by the bundle's matching bindings.
+/// All files are staged first; none is replaced unless all could be written.
+pub fn apply_bundle(bundle: &ConfigBundle, check: &ProvisionCheck, force: bool) -> Result<ProvisionOutcome> {
+    if !force && !check.is_clean() {
+        return Err(HyperfanError::config(format!(
+            "bundle doesn't match this machine: {} missing path(s), {} unmatched binding(s)",
+            check.missing.len(),
+            check.unmatched_bindings()
+        )));
+    }
+
+    let settings = provisioned_settings(bundle, check);
+    let bindings = match (&bundle.bindings, &check.bindings) {
+        (Some(store), Some(plan)) => {
+            let mut local = BindingStore::new();
+            let result = merge_importable(&mut local, &binding_export(bundle, store), plan);
+            Some((local, result))
+        }
+        _ => None,
+    };
+
+    let dir = paths::user_config_dir()
+        .ok_or_else(|| HyperfanError::config("Could not determine config directory"))?;
+    fs::create_dir_all(&dir).map_err(|e| HyperfanError::FileWrite { path: dir.clone(), source: e })?;
+
+    let format = settings.general.config_format;
+    let staged_settings = stage_config(&dir, "settings", format, &crate::settings::user_layer(&settings)?)?;
+    let staged_curves = stage_config(&dir, "curves", format, &crate::data::user_curves(&bundle.curves)?)?;
+    let staged_bindings = match &bindings {
+        Some((store, _)) => Some(stage_config(&dir, "bindings", ConfigFormat::Json, store)?),
+        None => None,
+    };
+
+    let settings_path = staged_settings.commit()?;
+    let curves_path = staged_curves.commit()?;
+    if let Some(staged) = staged_bindings {
+        staged.commit()?;
+    }
+    crate::settings::invalidate_settings_cache();
+
+    info!(
+        "Provisioned {} pairs and {} curves from bundle ({} PWM paths repointed)",
+        settings.active_pairs.len(),
+        bundle.curves.len(),
+        check.repointed.len()
+    );
+    Ok(ProvisionOutcome { settings_path, curves_path, bindings: bindings.map(|(_, result)| result) })
+}
+
+fn binding_export(bundle: &ConfigBundle, store: &BindingStore) -> BindingExport {
+    BindingExport::new(store.clone(), bundle.metadata.clone())
+}
+
+/// The bundle's settings with PWM paths moved to where their bindings are here
+fn provisioned_settings(bundle: &ConfigBundle, check: &ProvisionCheck) -> AppSettings {
+    let mut settings = bundle.settings.clone();
+    for repoint in &check.repointed {
+        settings.repoint_pwm(&repoint.from, &repoint.to, repoint.fan_path.as_deref());
+    }
+    settings
+}
+
+/// Every hardware or virtual path the settings and curves read or drive
+fn referenced_paths(settings: &AppSettings, curves: &CurveStore) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    for pair in &settings.active_pairs {
+        paths.insert(pair.temp_source_path.clone());
+        paths.insert(pair.fan_path.clone());
+        paths.extend(pair.fan_paths.iter().cloned());
+    }
+    for group in &settings.pwm_groups {
+        paths.insert(group.temp_source_path.clone());
+        paths.extend(group.members.iter().map(|m| m.pwm_path.clone()));
+    }
+    for pairing in &settings.pwm_fan_pairings {
+        paths.insert(pairing.pwm_path.clone());
+        paths.extend(pairing.fan_path.clone());
+    }
+    for sensor in &settings.virtual_sensors {
+        paths.extend(sensor.inputs().into_iter().map(str::to_string));
+    }
+    for curve in curves.all() {
+        paths.insert(curve.temp_source_path.clone());
+        paths.extend(curve.fallback_sources.iter().cloned());
+    }
+    paths.remove("");
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::PersistedCurve;
+    use crate::settings::FanCurvePair;
+
+    fn bundle() -> ConfigBundle {
+        let mut curves = CurveStore::new();
+        let curve: PersistedCurve = serde_json::from_value(serde_json::json!({
+            "id": "cpu",
+            "name": "CPU",
+            "temp_source_path": "/sys/class/hwmon/hwmon-missing/temp1_input",
+            "temp_source_label": "Tctl",
+            "points": [[30.0, 20.0], [80.0, 100.0]],
+        }))
+        .unwrap();
+        curves.upsert(curve);
+        let mut settings = AppSettings::default();
+        settings.active_pairs.push(FanCurvePair {
+            id: "pair-1".to_string(),
+            name: "CPU fan".to_string(),
+            curve_id: "cpu".to_string(),
+            temp_source_path: "/sys/class/hwmon/hwmon-missing/temp1_input".to_string(),
+            fan_path: "/sys/class/hwmon/hwmon-missing/pwm1".to_string(),
+            fan_paths: Vec::new(),
+            hysteresis_ms: 0,
+            active: true,
+            profile_curves: Default::default(),
+        });
+        ConfigBundle::new(settings, curves, None, BindingExportMetadata::default())
+    }
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bundle.json");
+        bundle().save(&path).unwrap();
+
+        let loaded = ConfigBundle::load(&path).unwrap();
+        assert_eq!(loaded.settings.active_pairs.len(), 1);
+        assert!(loaded.curves.get("cpu").is_some());
+        assert!(loaded.bindings.is_none());
+    }
+
+    #[test]
+    fn test_bundle_validation() {
+        let mut bundle = bundle();
+        assert!(bundle.validate().is_ok());
+
+        bundle.settings.active_pairs[0].profile_curves.insert("quiet".to_string(), "nope".to_string());
+        assert!(bundle.validate().is_err());
+
+        let mut bundle = self::bundle();
+        bundle.format = "hyperfan-binding-store".to_string();
+        assert!(bundle.validate().is_err());
+    }
+
+    #[test]
+    fn test_check_reports_missing_paths() {
+        let check = check_bundle(&bundle());
+        assert_eq!(check.missing, vec![
+            "/sys/class/hwmon/hwmon-missing/pwm1".to_string(),
+            "/sys/class/hwmon/hwmon-missing/temp1_input".to_string(),
+        ]);
+        assert!(!check.is_clean());
+        assert!(apply_bundle(&bundle(), &check, false).is_err());
+    }
+
+    #[test]
+    fn test_virtual_sources_resolve_within_bundle() {
+        let mut bundle = bundle();
+        bundle.settings.active_pairs[0].temp_source_path = "virtual:loop".to_string();
+        let check = check_bundle(&bundle);
+        assert!(check.missing.contains(&"virtual:loop".to_string()));
+    }
+
+    #[test]
+    fn test_check_config_against_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bundle.json");
+        bundle().save(&path).unwrap();
+        let snapshot: RawControllerSnapshot = serde_json::from_value(serde_json::json!({
+            "timestamp_ms": 0,
+            "chips": [{
+                "chip_name": "nct6798",
+                "chip_path": "/sys/class/hwmon/hwmon-missing",
+                "temperatures": [{
+                    "sensor_name": "temp1", "sensor_path": "/sys/class/hwmon/hwmon-missing/temp1_input",
+                    "label": null, "raw_value": null, "celsius": null,
+                }],
+                "fans": [],
+                "pwms": [],
+            }],
+        }))
+        .unwrap();
+
+        let report = check_config(&path, Some(&snapshot));
+        assert!(!report.valid);
+        assert_eq!(report.hardware, "snapshot");
+        assert_eq!(report.errors, vec![ConfigCheckError::new(
+            "missing_path",
+            "/sys/class/hwmon/hwmon-missing/pwm1 does not exist",
+            Some("/sys/class/hwmon/hwmon-missing/pwm1".to_string()),
+        )]);
+    }
+
+    #[test]
+    fn test_check_config_dir_collects_every_problem() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = check_config(dir.path(), None);
+        assert_eq!(report.errors[0].code, "unreadable");
+
+        // Only the keys that differ from the defaults, as in /etc/hyperfan
+        std::fs::write(dir.path().join("settings.toml"), "active_profile = \"quiet\"\n").unwrap();
+        let report = check_config(dir.path(), None);
+        assert!(report.valid, "{:?}", report.errors);
+
+        let mut curves = CurveStore::new();
+        curves.upsert(serde_json::from_value(serde_json::json!({
+            "id": "bad", "name": "Bad", "temp_source_path": "", "temp_source_label": "",
+            "points": [[80.0, 20.0], [30.0, 100.0]],
+        }))
+        .unwrap());
+        std::fs::write(dir.path().join("curves.json"), serde_json::to_string(&curves).unwrap()).unwrap();
+        std::fs::write(dir.path().join("settings.toml"), "active_profile = \"\"\n").unwrap();
+        let report = check_config(dir.path(), None);
+        assert_eq!(report.errors.iter().filter(|e| e.code == "invalid").count(), 2);
+    }
+}
@@ -0,0 +1,383 @@
+//! Session recording and replay
+//!
+//! Captures timestamped sensor snapshots (temperatures, fan RPM, PWM) to a
+//! JSONL file: one [`RecordingHeader`] line followed by one [`RecordingSample`]
+//! per line. The daemon writes recordings on `StartRecording`/`StopRecording`;
+//! clients load them with [`load_recording`] to replay a session offline,
+//! e.g. to diagnose curve oscillation.
+//!
+//! For benchmark runs the same samples can be written as CSV instead: a
+//! header row naming every sensor, then one row per sample. CSV recordings
+//! are meant for spreadsheets and plotting tools and cannot be replayed.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::constants::recording;
+use crate::data::HwmonChip;
+use crate::error::{HyperfanError, Result};
+use crate::hw::{enumerate_hwmon_chips, read_fan_rpm, read_pwm_value, read_temperature};
+use hf_protocol::RecordingFormat;
+
+/// First line of a recording file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    /// File format version
+    pub version: u32,
+    /// Unix timestamp in milliseconds when recording started
+    pub started_ms: u64,
+    /// Requested sample interval
+    pub interval_ms: u32,
+}
+
+/// One snapshot of all sensors (keyed by sysfs path)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordingSample {
+    /// Milliseconds since the recording started
+    pub t_ms: u64,
+    /// Temperatures in °C
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub temps: BTreeMap<String, f32>,
+    /// Fan speeds in RPM
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rpm: BTreeMap<String, u32>,
+    /// Raw PWM values (0-255)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pwm: BTreeMap<String, u8>,
+}
+
+/// Sensor paths discovered when a recording starts
+#[derive(Debug, Default)]
+struct SensorPaths {
+    temps: Vec<PathBuf>,
+    fans: Vec<PathBuf>,
+    pwms: Vec<PathBuf>,
+    /// CSV column names: temperatures, then fans, then PWMs
+    columns: Vec<String>,
+}
+
+impl SensorPaths {
+    fn from_chips(chips: &[HwmonChip]) -> Self {
+        let mut paths = Self::default();
+        for chip in chips {
+            for temp in &chip.temperatures {
+                paths.temps.push(temp.input_path.clone());
+                paths.columns.push(column_name(&chip.name, &temp.name, temp.label.as_deref(), "°C"));
+            }
+        }
+        for chip in chips {
+            for fan in &chip.fans {
+                paths.fans.push(fan.input_path.clone());
+                paths.columns.push(column_name(&chip.name, &fan.name, fan.label.as_deref(), "RPM"));
+            }
+        }
+        for chip in chips {
+            for pwm in &chip.pwms {
+                paths.pwms.push(pwm.pwm_path.clone());
+                paths.columns.push(column_name(&chip.name, &pwm.name, pwm.label.as_deref(), "PWM"));
+            }
+        }
+        paths
+    }
+}
+
+/// CSV column of a sensor ("nct6798/temp1 CPUTIN (°C)")
+fn column_name(chip: &str, name: &str, label: Option<&str>, unit: &str) -> String {
+    match label {
+        Some(label) => format!("{}/{} {} ({})", chip, name, label, unit),
+        None => format!("{}/{} ({})", chip, name, unit),
+    }
+}
+
+/// Quote a CSV field when it needs it
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One CSV row: wall-clock and elapsed time, then each column's value (empty if unreadable)
+fn csv_row(sensors: &SensorPaths, started_ms: u64, sample: &RecordingSample) -> String {
+    let mut row = format!("{},{}", started_ms + sample.t_ms, sample.t_ms);
+    let key = |path: &PathBuf| path.to_string_lossy().into_owned();
+    for path in &sensors.temps {
+        row.push(',');
+        if let Some(temp) = sample.temps.get(&key(path)) {
+            row.push_str(&format!("{:.2}", temp));
+        }
+    }
+    for path in &sensors.fans {
+        row.push(',');
+        if let Some(rpm) = sample.rpm.get(&key(path)) {
+            row.push_str(&rpm.to_string());
+        }
+    }
+    for path in &sensors.pwms {
+        row.push(',');
+        if let Some(pwm) = sample.pwm.get(&key(path)) {
+            row.push_str(&pwm.to_string());
+        }
+    }
+    row.push('\n');
+    row
+}
+
+/// Writes samples to a recording file
+pub struct RecordingWriter {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    format: RecordingFormat,
+    sensors: SensorPaths,
+    started: Instant,
+    started_ms: u64,
+    samples: u64,
+    bytes: u64,
+}
+
+impl RecordingWriter {
+    /// Create a new JSONL recording file (fails if it already exists)
+    pub fn create(path: &Path, interval_ms: u32) -> Result<Self> {
+        Self::create_with_format(path, interval_ms, RecordingFormat::Jsonl)
+    }
+
+    /// Create a new recording file in `format` (fails if it already exists)
+    /// Sensors are enumerated once here; each sample only re-reads their values
+    pub fn create_with_format(path: &Path, interval_ms: u32, format: RecordingFormat) -> Result<Self> {
+        let chips = enumerate_hwmon_chips()?;
+
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .map_err(|e| HyperfanError::FileWrite { path: path.to_path_buf(), source: e })?;
+        // Readable by unprivileged clients for replay
+        fs::set_permissions(path, fs::Permissions::from_mode(0o644))?;
+
+        let started_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let mut writer = Self {
+            writer: BufWriter::new(file),
+            path: path.to_path_buf(),
+            format,
+            sensors: SensorPaths::from_chips(&chips),
+            started: Instant::now(),
+            started_ms,
+            samples: 0,
+            bytes: 0,
+        };
+
+        match format {
+            RecordingFormat::Jsonl => {
+                let header = RecordingHeader { version: recording::FORMAT_VERSION, started_ms, interval_ms };
+                writer.write_line(&header)?;
+            }
+            RecordingFormat::Csv => {
+                let mut header = String::from("unix_ms,elapsed_ms");
+                for column in &writer.sensors.columns {
+                    header.push(',');
+                    header.push_str(&csv_field(column));
+                }
+                header.push('\n');
+                writer.write_bytes(header.as_bytes())?;
+            }
+        }
+
+        debug!(
+            path = %path.display(),
+            temps = writer.sensors.temps.len(),
+            fans = writer.sensors.fans.len(),
+            pwms = writer.sensors.pwms.len(),
+            "Started recording"
+        );
+        Ok(writer)
+    }
+
+    /// Read all sensors and append a sample
+    /// Sensors that fail to read are omitted from the sample
+    pub fn capture(&mut self) -> Result<()> {
+        let mut sample = RecordingSample {
+            t_ms: self.started.elapsed().as_millis() as u64,
+            ..RecordingSample::default()
+        };
+        for path in &self.sensors.temps {
+            if let Ok(temp) = read_temperature(path) {
+                sample.temps.insert(path.to_string_lossy().into_owned(), temp);
+            }
+        }
+        for path in &self.sensors.fans {
+            if let Ok(rpm) = read_fan_rpm(path) {
+                sample.rpm.insert(path.to_string_lossy().into_owned(), rpm);
+            }
+        }
+        for path in &self.sensors.pwms {
+            if let Ok(pwm) = read_pwm_value(path) {
+                sample.pwm.insert(path.to_string_lossy().into_owned(), pwm);
+            }
+        }
+        self.write_sample(&sample)
+    }
+
+    /// Append a sample
+    pub fn write_sample(&mut self, sample: &RecordingSample) -> Result<()> {
+        match self.format {
+            RecordingFormat::Jsonl => self.write_line(sample)?,
+            RecordingFormat::Csv => {
+                let row = csv_row(&self.sensors, self.started_ms, sample);
+                self.write_bytes(row.as_bytes())?;
+            }
+        }
+        self.samples += 1;
+        Ok(())
+    }
+
+    fn write_line<T: Serialize>(&mut self, value: &T) -> Result<()> {
+        let mut line = serde_json::to_vec(value)?;
+        line.push(b'\n');
+        self.write_bytes(&line)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.writer.write_all(bytes)?;
+        self.bytes += bytes.len() as u64;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn sample_count(&self) -> u64 {
+        self.samples
+    }
+
+    pub fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    /// File size so far (including buffered output)
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Flush and close the file
+    pub fn finish(mut self) -> Result<PathBuf> {
+        self.writer.flush()?;
+        Ok(self.path)
+    }
+}
+
+/// A loaded recording
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub header: RecordingHeader,
+    /// Samples in recording order
+    pub samples: Vec<RecordingSample>,
+}
+
+impl Recording {
+    /// Time covered by the recording
+    pub fn duration_ms(&self) -> u64 {
+        self.samples.last().map(|s| s.t_ms).unwrap_or(0)
+    }
+
+    /// Latest sample at or before `t_ms` (for replay at a given position)
+    pub fn sample_at(&self, t_ms: u64) -> Option<&RecordingSample> {
+        let idx = self.samples.partition_point(|s| s.t_ms <= t_ms);
+        idx.checked_sub(1).map(|i| &self.samples[i])
+    }
+}
+
+/// Load a recording file
+/// A truncated last line (daemon stopped mid-write) is skipped
+pub fn load_recording(path: &Path) -> Result<Recording> {
+    let metadata = fs::metadata(path)
+        .map_err(|e| HyperfanError::FileRead { path: path.to_path_buf(), source: e })?;
+    if metadata.len() > recording::MAX_FILE_SIZE {
+        return Err(HyperfanError::FileTooLarge {
+            path: path.to_path_buf(),
+            size: metadata.len(),
+            max_size: recording::MAX_FILE_SIZE,
+        });
+    }
+
+    let file = File::open(path)
+        .map_err(|e| HyperfanError::FileRead { path: path.to_path_buf(), source: e })?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header_line = lines
+        .next()
+        .ok_or_else(|| HyperfanError::config("recording file is empty"))??;
+    let header: RecordingHeader = serde_json::from_str(&header_line)?;
+    if header.version > recording::FORMAT_VERSION {
+        return Err(HyperfanError::config(format!(
+            "unsupported recording version {} (max {})",
+            header.version,
+            recording::FORMAT_VERSION
+        )));
+    }
+
+    let mut samples = Vec::new();
+    for (index, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<RecordingSample>(&line) {
+            Ok(sample) => samples.push(sample),
+            Err(e) => {
+                warn!("Skipping malformed recording line {}: {}", index + 2, e);
+            }
+        }
+    }
+
+    Ok(Recording { header, samples })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_at() {
+        let recording = Recording {
+            header: RecordingHeader { version: 1, started_ms: 0, interval_ms: 1000 },
+            samples: [0, 1000, 2000]
+                .into_iter()
+                .map(|t_ms| RecordingSample { t_ms, ..RecordingSample::default() })
+                .collect(),
+        };
+
+        assert_eq!(recording.sample_at(0).map(|s| s.t_ms), Some(0));
+        assert_eq!(recording.sample_at(1500).map(|s| s.t_ms), Some(1000));
+        assert_eq!(recording.sample_at(5000).map(|s| s.t_ms), Some(2000));
+        assert_eq!(recording.duration_ms(), 2000);
+    }
+
+    #[test]
+    fn test_csv_row() {
+        let sensors = SensorPaths {
+            temps: vec![PathBuf::from("/sys/class/hwmon/hwmon0/temp1_input")],
+            fans: vec![PathBuf::from("/sys/class/hwmon/hwmon0/fan1_input")],
+            pwms: vec![PathBuf::from("/sys/class/hwmon/hwmon0/pwm1")],
+            columns: Vec::new(),
+        };
+        let mut sample = RecordingSample { t_ms: 1500, ..RecordingSample::default() };
+        sample.temps.insert("/sys/class/hwmon/hwmon0/temp1_input".into(), 45.126);
+        sample.pwm.insert("/sys/class/hwmon/hwmon0/pwm1".into(), 128);
+
+        // The unreadable fan leaves an empty field
+        assert_eq!(csv_row(&sensors, 1_000_000, &sample), "1001500,1500,45.13,,128\n");
+        assert_eq!(csv_field("nct6798/temp1 CPUTIN (°C)"), "nct6798/temp1 CPUTIN (°C)");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,217 @@
+//! Scheduled profile switching
+//!
+//! A [`ProfileSchedule`] maps local time-of-day windows to curve profiles,
+//! e.g. `quiet` from 22:00 to 08:00. The daemon evaluates the schedule in its
+//! control loop and switches `AppSettings::active_profile` when the scheduled
+//! profile changes, so a manual switch holds until the next window boundary.
+//!
+//! ```json
+//! "profile_schedule": {
+//!   "enabled": true,
+//!   "default_profile": "normal",
+//!   "entries": [
+//!     { "profile": "quiet", "start": "22:00", "end": "08:00" },
+//!     { "profile": "performance", "start": "19:00", "end": "22:00", "days": ["fri", "sat"] }
+//!   ]
+//! }
+//! ```
+//!
+//! Entries are checked in order; the first match wins. Outside every window
+//! the schedule selects `default_profile`. A window that ends before it starts
+//! runs past midnight and belongs to the day it starts on.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::profiles;
+
+/// Day abbreviations, indexed by `tm_wday` (0 = Sunday)
+pub const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Profile schedule stored in settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSchedule {
+    /// Whether the daemon follows the schedule
+    #[serde(default)]
+    pub enabled: bool,
+    /// Profile used outside every window
+    #[serde(default = "default_profile")]
+    pub default_profile: String,
+    #[serde(default)]
+    pub entries: Vec<ScheduleEntry>,
+}
+
+/// One time-of-day window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    /// Profile active during the window
+    pub profile: String,
+    /// Window start, "HH:MM" local time
+    pub start: String,
+    /// Window end, "HH:MM" local time (exclusive)
+    pub end: String,
+    /// Days the window starts on ("mon".."sun"); empty = every day
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<String>,
+}
+
+fn default_profile() -> String {
+    profiles::DEFAULT.to_string()
+}
+
+impl Default for ProfileSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_profile: default_profile(),
+            entries: Vec::new(),
+        }
+    }
+}
+
+/// Local wall-clock time used to evaluate a schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTime {
+    /// 0 = Sunday .. 6 = Saturday
+    pub weekday: u8,
+    /// Minutes since local midnight
+    pub minute: u16,
+}
+
+impl LocalTime {
+    /// Current local time (falls back to UTC if the timezone cannot be read)
+    pub fn now() -> Self {
+        let now = unsafe { libc::time(std::ptr::null_mut()) };
+        let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+        if unsafe { libc::localtime_r(&now, &mut tm) }.is_null() {
+            let secs = now.max(0) as u64;
+            return Self {
+                // 1970-01-01 was a Thursday
+                weekday: ((secs / 86_400 + 4) % 7) as u8,
+                minute: ((secs % 86_400) / 60) as u16,
+            };
+        }
+        Self {
+            weekday: tm.tm_wday as u8,
+            minute: (tm.tm_hour * 60 + tm.tm_min) as u16,
+        }
+    }
+}
+
+/// Parse "HH:MM" into minutes since midnight
+pub fn parse_time_of_day(s: &str) -> Option<u16> {
+    let (h, m) = s.trim().split_once(':')?;
+    let (h, m): (u16, u16) = (h.parse().ok()?, m.parse().ok()?);
+    (h < 24 && m < 60).then_some(h * 60 + m)
+}
+
+fn weekday_index(day: &str) -> Option<u8> {
+    let day = day.trim().to_ascii_lowercase();
+    WEEKDAYS.iter().position(|d| *d == day).map(|i| i as u8)
+}
+
+impl ScheduleEntry {
+    /// Check that times, days and profile name are valid
+    pub fn validate(&self) -> Result<(), String> {
+        hf_protocol::validate_profile_name(&self.profile)?;
+        for time in [&self.start, &self.end] {
+            if parse_time_of_day(time).is_none() {
+                return Err(format!("Invalid time {:?} (expected HH:MM)", time));
+            }
+        }
+        if let Some(day) = self.days.iter().find(|d| weekday_index(d).is_none()) {
+            return Err(format!("Invalid day {:?} (expected mon..sun)", day));
+        }
+        Ok(())
+    }
+
+    fn starts_on(&self, weekday: u8) -> bool {
+        self.days.is_empty() || self.days.iter().any(|d| weekday_index(d) == Some(weekday))
+    }
+
+    /// Whether the window covers `time` (invalid entries never match)
+    pub fn matches(&self, time: LocalTime) -> bool {
+        let (Some(start), Some(end)) = (parse_time_of_day(&self.start), parse_time_of_day(&self.end)) else {
+            return false;
+        };
+        let yesterday = (time.weekday + 6) % 7;
+        if start < end {
+            self.starts_on(time.weekday) && (start..end).contains(&time.minute)
+        } else if start > end {
+            // Overnight: the evening part today or the morning part of yesterday's window
+            (self.starts_on(time.weekday) && time.minute >= start)
+                || (self.starts_on(yesterday) && time.minute < end)
+        } else {
+            // start == end covers the whole day
+            self.starts_on(time.weekday)
+        }
+    }
+}
+
+impl ProfileSchedule {
+    /// Profile the schedule selects at `time`, or None if scheduling is disabled
+    pub fn profile_at(&self, time: LocalTime) -> Option<&str> {
+        if !self.enabled {
+            return None;
+        }
+        let profile = self.entries
+            .iter()
+            .find(|e| e.matches(time))
+            .map(|e| e.profile.as_str())
+            .unwrap_or(&self.default_profile);
+        Some(profile)
+    }
+
+    /// Check every entry and the default profile
+    pub fn validate(&self) -> Result<(), String> {
+        hf_protocol::validate_profile_name(&self.default_profile)?;
+        for (index, entry) in self.entries.iter().enumerate() {
+            entry.validate().map_err(|e| format!("Schedule entry {}: {}", index + 1, e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(weekday: u8, hhmm: &str) -> LocalTime {
+        LocalTime { weekday, minute: parse_time_of_day(hhmm).unwrap() }
+    }
+
+    #[test]
+    fn test_schedule_windows() {
+        let schedule = ProfileSchedule {
+            enabled: true,
+            default_profile: "normal".to_string(),
+            entries: vec![
+                ScheduleEntry {
+                    profile: "performance".to_string(),
+                    start: "19:00".to_string(),
+                    end: "22:00".to_string(),
+                    days: vec!["fri".to_string()],
+                },
+                ScheduleEntry {
+                    profile: "quiet".to_string(),
+                    start: "22:00".to_string(),
+                    end: "08:00".to_string(),
+                    days: Vec::new(),
+                },
+            ],
+        };
+        assert!(schedule.validate().is_ok());
+
+        assert_eq!(schedule.profile_at(at(1, "12:00")), Some("normal"));
+        assert_eq!(schedule.profile_at(at(1, "23:30")), Some("quiet"));
+        assert_eq!(schedule.profile_at(at(2, "07:59")), Some("quiet"));
+        assert_eq!(schedule.profile_at(at(2, "08:00")), Some("normal"));
+        assert_eq!(schedule.profile_at(at(5, "20:00")), Some("performance"));
+        assert_eq!(schedule.profile_at(at(4, "20:00")), Some("normal"));
+
+        let disabled = ProfileSchedule { enabled: false, ..schedule };
+        assert_eq!(disabled.profile_at(at(1, "23:30")), None);
+
+        assert_eq!(parse_time_of_day("24:00"), None);
+        assert_eq!(parse_time_of_day("7:05"), Some(425));
+    }
+}
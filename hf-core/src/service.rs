@@ -10,12 +10,21 @@
 //!
 //! The daemon provides secure IPC for hardware access without requiring
 //! the GUI to run with elevated privileges.
+//!
+//! Installing or updating the service also installs the daemon's polkit
+//! actions (`io.github.hyperfan1.policy`), which it checks before any change
+//! made by a non-root client.
 
 use std::path::Path;
 use std::process::Command;
 
 const DAEMON_BINARY: &str = "hyperfand";
 
+/// Polkit actions checked by the daemon
+const POLKIT_POLICY: &str = include_str!("../../hf-daemon/io.github.hyperfan1.policy");
+const POLKIT_POLICY_NAME: &str = "io.github.hyperfan1.policy";
+const POLKIT_POLICY_TEMP: &str = "/tmp/io.github.hyperfan1.policy";
+
 /// Get socket path based on detected OS (runtime detection)
 pub fn get_socket_path() -> &'static str {
     if is_bsd() {
@@ -27,6 +36,17 @@ pub fn get_socket_path() -> &'static str {
     }
 }
 
+/// Get the monitoring socket path (read-only sensor access for any local process)
+pub fn get_monitor_socket_path() -> &'static str {
+    if is_bsd() {
+        "/var/run/hyperfan-monitor.sock"
+    } else if Path::new("/run").exists() {
+        "/run/hyperfan-monitor.sock"
+    } else {
+        "/var/run/hyperfan-monitor.sock"
+    }
+}
+
 /// Detect if running on BSD at runtime
 pub fn is_bsd() -> bool {
     // Check for BSD-specific paths and files
@@ -119,10 +139,15 @@ Documentation=https://github.com/hyperfan/hyperfan
 After=local-fs.target
 
 [Service]
-Type=simple
-ExecStart={} --foreground
+Type=notify
+NotifyAccess=main
+ExecStart={0} --foreground
+# Restore original fan modes even if the daemon was killed
+ExecStopPost={0} restore-state
 Restart=on-failure
 RestartSec=5
+# Restart if the fan control loop stops sending heartbeats
+WatchdogSec=30
 
 # Security hardening
 NoNewPrivileges=false
@@ -130,6 +155,10 @@ ProtectSystem=strict
 ProtectHome=read-only
 PrivateTmp=true
 ReadWritePaths=/sys/class/hwmon /sys/devices /run
+# /var/lib/hyperfan (session recordings, sensor history)
+StateDirectory=hyperfan
+# /var/log/hyperfan (audit log)
+LogsDirectory=hyperfan
 
 [Install]
 WantedBy=multi-user.target
@@ -145,7 +174,7 @@ fn openrc_service(daemon_path: &str) -> String {
 
 name="hyperfand"
 description="Hyperfan privileged daemon for fan control"
-command="{}"
+command="{0}"
 command_args="--foreground"
 command_background=true
 pidfile="/run/hyperfand.pid"
@@ -158,6 +187,11 @@ depend() {{
 start_pre() {{
     checkpath --directory --mode 0755 /run
 }}
+
+stop_post() {{
+    # Restore original fan modes even if the daemon was killed
+    {0} restore-state
+}}
 "#,
         daemon_path
     )
@@ -173,11 +207,16 @@ exec {} --foreground 2>&1
     )
 }
 
-fn runit_finish_script() -> &'static str {
-    r#"#!/bin/sh
+fn runit_finish_script(daemon_path: &str) -> String {
+    format!(
+        r#"#!/bin/sh
 # Cleanup on stop
-rm -f /run/hyperfan.sock /run/hyperfand.pid
-"#
+rm -f /run/hyperfan.sock /run/hyperfan-monitor.sock /run/hyperfand.pid
+# Restore original fan modes even if the daemon was killed
+{} restore-state
+"#,
+        daemon_path
+    )
 }
 
 /// BSD rc.d script (works on FreeBSD, OpenBSD, NetBSD, DragonFlyBSD)
@@ -268,6 +307,25 @@ pub fn is_service_running() -> bool {
     }
 }
 
+/// Check if the daemon service starts at boot
+pub fn is_service_enabled() -> bool {
+    match detect_init_system() {
+        InitSystem::Systemd => Command::new("systemctl")
+            .args(["is-enabled", "--quiet", "hyperfan.service"])
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false),
+        InitSystem::OpenRC => Path::new("/etc/runlevels/default/hyperfand").exists(),
+        InitSystem::Runit => Path::new("/var/service/hyperfand").exists(),
+        InitSystem::BsdRc => Command::new("sysrc")
+            .args(["-n", "hyperfand_enable"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().eq_ignore_ascii_case("yes"))
+            .unwrap_or(false),
+        InitSystem::Unknown => false,
+    }
+}
+
 /// Check if the daemon socket is available
 pub fn is_socket_available() -> bool {
     Path::new(get_socket_path()).exists()
@@ -324,6 +382,26 @@ fn find_local_daemon_binary() -> Option<String> {
 // Installation
 // ============================================================================
 
+/// Polkit actions directory (ports install polkit under /usr/local on BSD)
+fn polkit_actions_dir() -> &'static str {
+    if is_bsd() {
+        "/usr/local/share/polkit-1/actions"
+    } else {
+        "/usr/share/polkit-1/actions"
+    }
+}
+
+/// Stage the polkit policy and return the script step that installs it
+fn polkit_policy_script() -> Result<String, String> {
+    std::fs::write(POLKIT_POLICY_TEMP, POLKIT_POLICY)
+        .map_err(|e| format!("Failed to write polkit policy: {}", e))?;
+    let dest = format!("{}/{}", polkit_actions_dir(), POLKIT_POLICY_NAME);
+    Ok(format!(
+        "mkdir -p '{}' && cp '{}' '{}' && chmod 644 '{}' && ",
+        polkit_actions_dir(), POLKIT_POLICY_TEMP, dest, dest
+    ))
+}
+
 /// Install the daemon binary and service (requires root via pkexec)
 /// Uses a SINGLE pkexec call to install binary + service + start daemon
 pub fn install_service() -> Result<(), String> {
@@ -358,13 +436,18 @@ pub fn install_service() -> Result<(), String> {
         daemon_dest.to_string()
     };
 
-    match init {
-        InitSystem::Systemd => install_systemd_combined(&binary_install_script, &daemon_path),
-        InitSystem::OpenRC => install_openrc_combined(&binary_install_script, &daemon_path),
-        InitSystem::Runit => install_runit_combined(&binary_install_script, &daemon_path),
-        InitSystem::BsdRc => install_bsd_rc_combined(&binary_install_script, &daemon_path),
+    // Binary (if needed) and polkit policy, then the init system's service
+    let install_script = format!("{}{}", binary_install_script, polkit_policy_script()?);
+
+    let result = match init {
+        InitSystem::Systemd => install_systemd_combined(&install_script, &daemon_path),
+        InitSystem::OpenRC => install_openrc_combined(&install_script, &daemon_path),
+        InitSystem::Runit => install_runit_combined(&install_script, &daemon_path),
+        InitSystem::BsdRc => install_bsd_rc_combined(&install_script, &daemon_path),
         InitSystem::Unknown => Err(crate::error::HyperfanError::UnsupportedInitSystem("Unknown init system. Cannot install service.".to_string()).to_string()),
-    }
+    };
+    let _ = std::fs::remove_file(POLKIT_POLICY_TEMP);
+    result
 }
 
 fn install_systemd_combined(binary_script: &str, daemon_path: &str) -> Result<(), String> {
@@ -418,14 +501,14 @@ fn install_openrc_combined(binary_script: &str, daemon_path: &str) -> Result<(),
 
 fn install_runit_combined(binary_script: &str, daemon_path: &str) -> Result<(), String> {
     let run_script = runit_run_script(daemon_path);
-    let finish_script = runit_finish_script();
+    let finish_script = runit_finish_script(daemon_path);
 
     let temp_run = "/tmp/hyperfand-run";
     let temp_finish = "/tmp/hyperfand-finish";
 
     std::fs::write(temp_run, &run_script)
         .map_err(|e| format!("Failed to write run script: {}", e))?;
-    std::fs::write(temp_finish, finish_script)
+    std::fs::write(temp_finish, &finish_script)
         .map_err(|e| format!("Failed to write finish script: {}", e))?;
 
     // Combined script: install binary (if needed) + install service
@@ -496,8 +579,9 @@ fn uninstall_systemd() -> Result<(), String> {
         systemctl disable hyperfan.service 2>/dev/null || true
         rm -f /etc/systemd/system/hyperfan.service
         systemctl daemon-reload
-        rm -f /run/hyperfan.sock /run/hyperfand.pid
+        rm -f /run/hyperfan.sock /run/hyperfan-monitor.sock /run/hyperfand.pid
         rm -f /usr/local/bin/hyperfand /usr/bin/hyperfand
+        rm -f /usr/share/polkit-1/actions/io.github.hyperfan1.policy
     "#;
     run_pkexec(script)
 }
@@ -507,8 +591,9 @@ fn uninstall_openrc() -> Result<(), String> {
         rc-service hyperfand stop 2>/dev/null || true
         rc-update del hyperfand default 2>/dev/null || true
         rm -f /etc/init.d/hyperfand
-        rm -f /run/hyperfan.sock /run/hyperfand.pid
+        rm -f /run/hyperfan.sock /run/hyperfan-monitor.sock /run/hyperfand.pid
         rm -f /usr/local/bin/hyperfand /usr/bin/hyperfand
+        rm -f /usr/share/polkit-1/actions/io.github.hyperfan1.policy
     "#;
     run_pkexec(script)
 }
@@ -518,8 +603,9 @@ fn uninstall_runit() -> Result<(), String> {
         rm -f /var/service/hyperfand
         sv stop hyperfand 2>/dev/null || true
         rm -rf /etc/sv/hyperfand
-        rm -f /run/hyperfan.sock /run/hyperfand.pid
+        rm -f /run/hyperfan.sock /run/hyperfan-monitor.sock /run/hyperfand.pid
         rm -f /usr/local/bin/hyperfand /usr/bin/hyperfand
+        rm -f /usr/share/polkit-1/actions/io.github.hyperfan1.policy
     "#;
     run_pkexec(script)
 }
@@ -530,8 +616,9 @@ fn uninstall_bsd_rc() -> Result<(), String> {
         rm -f /usr/local/etc/rc.d/hyperfand
         rm -f /etc/rc.d/hyperfand
         sed -i '' '/hyperfand_enable/d' /etc/rc.conf 2>/dev/null || true
-        rm -f /var/run/hyperfan.sock /var/run/hyperfand.pid
+        rm -f /var/run/hyperfan.sock /var/run/hyperfan-monitor.sock /var/run/hyperfand.pid
         rm -f /usr/local/bin/hyperfand
+        rm -f /usr/local/share/polkit-1/actions/io.github.hyperfan1.policy
     "#;
     run_pkexec_bsd(script)
 }
@@ -551,18 +638,19 @@ pub fn reinstall_service() -> Result<(), String> {
     })?;
     
     let dest = get_system_daemon_path();
+    let policy_script = polkit_policy_script()?;
     
-    match init {
+    let result = match init {
         InitSystem::Systemd => {
             let script = format!(
                 r#"
                 systemctl stop hyperfan.service 2>/dev/null || true
-                cp '{}' '{}' && \
+                {}cp '{}' '{}' && \
                 chmod 755 '{}' && \
                 chown root:root '{}' && \
                 systemctl start hyperfan.service
             "#,
-                local_daemon, dest, dest, dest
+                policy_script, local_daemon, dest, dest, dest
             );
             run_pkexec(&script)
         }
@@ -570,12 +658,12 @@ pub fn reinstall_service() -> Result<(), String> {
             let script = format!(
                 r#"
                 rc-service hyperfand stop 2>/dev/null || true
-                cp '{}' '{}' && \
+                {}cp '{}' '{}' && \
                 chmod 755 '{}' && \
                 chown root:root '{}' && \
                 rc-service hyperfand start
             "#,
-                local_daemon, dest, dest, dest
+                policy_script, local_daemon, dest, dest, dest
             );
             run_pkexec(&script)
         }
@@ -583,12 +671,12 @@ pub fn reinstall_service() -> Result<(), String> {
             let script = format!(
                 r#"
                 sv stop hyperfand 2>/dev/null || true
-                cp '{}' '{}' && \
+                {}cp '{}' '{}' && \
                 chmod 755 '{}' && \
                 chown root:root '{}' && \
                 sv start hyperfand
             "#,
-                local_daemon, dest, dest, dest
+                policy_script, local_daemon, dest, dest, dest
             );
             run_pkexec(&script)
         }
@@ -596,17 +684,20 @@ pub fn reinstall_service() -> Result<(), String> {
             let script = format!(
                 r#"
                 service hyperfand stop 2>/dev/null || true
-                cp '{}' '{}' && \
+                {}cp '{}' '{}' && \
                 chmod 755 '{}' && \
                 chown root:wheel '{}' && \
                 service hyperfand start
             "#,
-                local_daemon, dest, dest, dest
+                policy_script, local_daemon, dest, dest, dest
             );
             run_pkexec_bsd(&script)
         }
         InitSystem::Unknown => Err("Unknown init system. Cannot reinstall service.".to_string()),
-    }
+    };
+
+    let _ = std::fs::remove_file(POLKIT_POLICY_TEMP);
+    result
 }
 
 // ============================================================================
@@ -628,6 +719,15 @@ fn run_pkexec(script: &str) -> Result<(), String> {
     }
 }
 
+/// Run a root shell script with the init system's usual privilege escalation
+pub(crate) fn run_privileged(script: &str) -> Result<(), String> {
+    if is_bsd() {
+        run_pkexec_bsd(script)
+    } else {
+        run_pkexec(script)
+    }
+}
+
 /// Run privileged command on BSD (uses doas or sudo)
 fn run_pkexec_bsd(script: &str) -> Result<(), String> {
     // BSD systems typically use doas or sudo instead of pkexec
@@ -703,6 +803,25 @@ pub fn start_service() -> Result<(), String> {
     }
 }
 
+/// Enable the daemon service at boot and start it
+pub fn enable_service() -> Result<(), String> {
+    match detect_init_system() {
+        InitSystem::Systemd => {
+            run_pkexec("systemctl enable --now hyperfan.service")
+        }
+        InitSystem::OpenRC => {
+            run_pkexec("rc-update add hyperfand default && rc-service hyperfand start")
+        }
+        InitSystem::Runit => {
+            run_pkexec("ln -sf /etc/sv/hyperfand /var/service/ && sv start hyperfand")
+        }
+        InitSystem::BsdRc => {
+            run_pkexec_bsd("sysrc hyperfand_enable=YES && service hyperfand start")
+        }
+        InitSystem::Unknown => Err("Unknown init system".to_string()),
+    }
+}
+
 /// Stop the daemon service
 pub fn stop_service() -> Result<(), String> {
     match detect_init_system() {
@@ -1,13 +1,16 @@
 //! Application Settings
 //!
-//! Persistent settings stored as JSON in ~/.config/hyperfan/settings.json
+//! Persistent settings stored in ~/.config/hyperfan/settings.json, or
+//! settings.toml when `general.config_format` selects TOML, over an optional
+//! system layer in /etc/hyperfan (see `data::layers`)
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::{OnceLock, RwLock};
 
-use crate::data::FanMapping;
+use crate::data::{ConfigFormat, FanMapping};
 use crate::error::Result;
 use crate::hw::binding::BindingStore;
 use crate::HyperfanError;
@@ -88,10 +91,22 @@ pub struct AppSettings {
     #[serde(default)]
     pub advanced: AdvancedSettings,
     
+    /// Desktop notification settings
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+    
     /// Active fan-curve pairs
     #[serde(default)]
     pub active_pairs: Vec<FanCurvePair>,
     
+    /// PWM groups: one curve driving several outputs with per-output offsets
+    #[serde(default)]
+    pub pwm_groups: Vec<hf_protocol::PwmGroup>,
+    
+    /// User-defined derived temperature channels (evaluated by the daemon)
+    #[serde(default)]
+    pub virtual_sensors: Vec<crate::VirtualSensor>,
+    
     /// Detected PWM-to-fan mappings (from calibration)
     #[serde(default)]
     pub pwm_fan_mappings: Vec<FanMapping>,
@@ -107,6 +122,22 @@ pub struct AppSettings {
     /// User-defined friendly names for temperature sensors
     #[serde(default)]
     pub sensor_friendly_names: Vec<SensorFriendlyName>,
+    
+    /// Spike rejection / median / EMA filters per temperature source
+    #[serde(default)]
+    pub sensor_filters: Vec<crate::SensorFilter>,
+
+    /// Hwmon chips and PWM channels never enumerated or controlled
+    #[serde(default)]
+    pub controller_filter: crate::ControllerFilter,
+    
+    /// Active curve profile (see `FanCurvePair::profile_curves`)
+    #[serde(default = "default_profile")]
+    pub active_profile: String,
+    
+    /// Time-of-day profile switching (evaluated by the daemon)
+    #[serde(default)]
+    pub profile_schedule: crate::schedule::ProfileSchedule,
 }
 
 /// General application settings
@@ -132,10 +163,60 @@ pub struct GeneralSettings {
     /// Applied to both client and daemon when changed
     #[serde(default = "default_rate_limit")]
     pub rate_limit: u32,
+    
+    /// Seconds a fan may read 0 RPM while driven above its minimum PWM
+    /// before the daemon flags it as failed (0 disables stall detection)
+    #[serde(default = "default_fan_stall_timeout")]
+    pub fan_stall_timeout_secs: u32,
+
+    /// Vary the daemon poll rate with how fast temperatures change
+    #[serde(default)]
+    pub adaptive_polling: AdaptivePollSettings,
+
+    /// Run fans at full speed while a fan or temperature alarm is raised
+    #[serde(default)]
+    pub alarm_boost: AlarmBoostSettings,
+
+    /// Format settings and curves are written in
+    #[serde(default)]
+    pub config_format: ConfigFormat,
+}
+
+/// Adaptive polling for the daemon control loop
+/// When enabled, replaces `poll_interval_ms` in the daemon: polling backs off
+/// towards `max_interval_ms` while temperatures are stable and drops to
+/// `min_interval_ms` when any sensor changes faster than `slope_threshold`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AdaptivePollSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Poll interval while temperatures are changing quickly
+    #[serde(default = "default_adaptive_min_interval")]
+    pub min_interval_ms: u32,
+    /// Poll interval once temperatures are stable
+    #[serde(default = "default_adaptive_max_interval")]
+    pub max_interval_ms: u32,
+    /// Temperature slope (°C per second) that counts as volatile
+    #[serde(default = "default_adaptive_slope_threshold")]
+    pub slope_threshold: f32,
+}
+
+/// Full speed on hwmon alarms
+/// While any `fanN_alarm` or `tempN_alarm` of a chip is raised, the daemon
+/// drives every controlled fan at 100%, and keeps doing so for
+/// `cooldown_secs` after the last alarm cleared. Chassis intrusion alarms are
+/// reported but never boost: they stay latched until cleared by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlarmBoostSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds fans stay at full speed after the last alarm cleared
+    #[serde(default = "default_alarm_boost_cooldown")]
+    pub cooldown_secs: u32,
 }
 
 /// Advanced settings (dangerous features)
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdvancedSettings {
     /// Enable direct EC (Embedded Controller) access
     /// WARNING: This is EXTREMELY DANGEROUS and can damage hardware
@@ -149,6 +230,56 @@ pub struct AdvancedSettings {
     /// Timestamp when EC was enabled (for audit)
     #[serde(default)]
     pub ec_enabled_at: Option<u64>,
+
+    /// Stage raw EC writes: watch fans and temperatures after each write and
+    /// restore the original value if a fan stops or temperatures run away
+    #[serde(default = "default_true")]
+    pub ec_write_guard: bool,
+
+    /// Log EC writes without performing them
+    #[serde(default)]
+    pub ec_dry_run: bool,
+
+    /// Allow the daemon to change GPU power limits
+    #[serde(default)]
+    pub gpu_power_limits_enabled: bool,
+}
+
+impl Default for AdvancedSettings {
+    fn default() -> Self {
+        Self {
+            ec_direct_control_enabled: false,
+            ec_danger_acknowledged: false,
+            ec_enabled_at: None,
+            ec_write_guard: true,
+            ec_dry_run: false,
+            gpu_power_limits_enabled: false,
+        }
+    }
+}
+
+/// Desktop notification settings (one toggle per event category)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    /// Notify when the daemon stops responding
+    #[serde(default = "default_true")]
+    pub daemon_down: bool,
+    
+    /// Notify when a temperature sensor exceeds `thermal_threshold_celsius`
+    #[serde(default = "default_true")]
+    pub thermal_threshold: bool,
+    
+    /// Temperature (Celsius) above which a thermal notification is raised
+    #[serde(default = "default_thermal_threshold")]
+    pub thermal_threshold_celsius: f32,
+    
+    /// Notify when the daemon flags a stalled fan
+    #[serde(default = "default_true")]
+    pub fan_failure: bool,
+    
+    /// Notify when hardware fingerprints no longer match (hwmon drift)
+    #[serde(default = "default_true")]
+    pub fingerprint_drift: bool,
 }
 
 /// Display settings
@@ -195,6 +326,23 @@ pub struct DisplaySettings {
     #[serde(default = "default_frame_rate")]
     pub frame_rate: u32,
     
+    /// Time span of the graphs page overview in seconds (5 minutes to 24 hours)
+    #[serde(default = "default_graph_window")]
+    pub graph_window_secs: u32,
+    
+    /// Overview series the user has hidden ("temp:", "rpm:" or "duty:" + source path)
+    #[serde(default)]
+    pub graph_hidden_series: Vec<String>,
+    
+    /// Saved order of the chip cards on the sensors page, by card key
+    /// (cards missing from it follow in detection order)
+    #[serde(default)]
+    pub sensor_card_order: Vec<String>,
+    
+    /// Chip cards the user has hidden from the sensors page
+    #[serde(default)]
+    pub hidden_sensor_cards: Vec<String>,
+    
     /// Window width (saved on close)
     #[serde(default)]
     pub window_width: Option<i32>,
@@ -214,6 +362,11 @@ pub struct DisplaySettings {
     /// Window maximized state (saved on close)
     #[serde(default)]
     pub window_maximized: Option<bool>,
+    
+    /// Colors assigned to sensors and fans (source path -> "#rrggbb"),
+    /// used for them on the dashboard, graphs and curve overlays
+    #[serde(default)]
+    pub sensor_colors: BTreeMap<String, String>,
 }
 
 /// A manual PWM-to-fan pairing (user-defined)
@@ -224,14 +377,14 @@ pub struct PwmFanPairing {
     #[serde(default = "generate_guid")]
     pub id: String,
     
-    /// Stable UUID for the PWM control (derived from chip+sensor name, survives hwmon reindexing)
+    /// Stable UUID for the PWM control (see `hw::sensor_uuid`, survives hwmon reindexing)
     #[serde(default)]
     pub pwm_uuid: Option<String>,
     
     /// PWM control path (may change on reboot due to hwmon reindexing)
     pub pwm_path: String,
     
-    /// Stable UUID for the fan sensor (derived from chip+sensor name, survives hwmon reindexing)
+    /// Stable UUID for the fan sensor (see `hw::sensor_uuid`, survives hwmon reindexing)
     #[serde(default)]
     pub fan_uuid: Option<String>,
     
@@ -245,6 +398,26 @@ pub struct PwmFanPairing {
     #[serde(default)]
     pub friendly_name: Option<String>,
     
+    /// Response tuning that overrides the curve's hysteresis/ramp/smoothing
+    #[serde(default)]
+    pub tuning: Option<hf_protocol::CurveTuning>,
+    
+    /// Duty-cycle or closed-loop RPM control (RPM needs a paired fan)
+    #[serde(default)]
+    pub control_mode: hf_protocol::PairingControlMode,
+    
+    /// Let the curve stop the fan; otherwise outputs are held at the calibrated stall duty
+    #[serde(default)]
+    pub zero_rpm: bool,
+    
+    /// Response to another program writing the PWM (re-assert, yield or alert only)
+    #[serde(default)]
+    pub enforcement: hf_protocol::PwmEnforcement,
+    
+    /// How loud the fan is at each duty (None = generic estimate)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub noise: Option<crate::NoiseProfile>,
+    
     // ========================================================================
     // HARDWARE IDENTIFICATION (stable across reboots)
     // ========================================================================
@@ -369,6 +542,20 @@ pub struct FanCurvePair {
     /// Whether this pair is currently active
     #[serde(default = "default_true")]
     pub active: bool,
+    
+    /// Curve ID per profile name (profiles without an entry use `curve_id`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profile_curves: BTreeMap<String, String>,
+}
+
+impl FanCurvePair {
+    /// Curve ID to use while `profile` is active
+    pub fn curve_for_profile(&self, profile: &str) -> &str {
+        self.profile_curves
+            .get(profile)
+            .map(String::as_str)
+            .unwrap_or(&self.curve_id)
+    }
 }
 
 // Default value functions
@@ -382,8 +569,16 @@ fn default_display_backend() -> String { "auto".to_string() }
 fn default_window_manager() -> String { "auto".to_string() }
 fn default_graph_smoothing() -> String { "direct".to_string() }
 fn default_frame_rate() -> u32 { 60 }
+fn default_graph_window() -> u32 { 900 }
 fn default_page() -> String { "dashboard".to_string() }
+fn default_profile() -> String { crate::constants::profiles::DEFAULT.to_string() }
 fn default_rate_limit() -> u32 { 1500 }
+fn default_fan_stall_timeout() -> u32 { 10 }
+fn default_adaptive_min_interval() -> u32 { 250 }
+fn default_adaptive_max_interval() -> u32 { 3000 }
+fn default_adaptive_slope_threshold() -> f32 { 0.5 }
+fn default_alarm_boost_cooldown() -> u32 { 60 }
+fn default_thermal_threshold() -> f32 { crate::constants::temperature::HIGH_THRESHOLD }
 
 /// Check if a string is a valid UUID format (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx)
 fn is_valid_uuid(s: &str) -> bool {
@@ -427,12 +622,82 @@ impl Default for AppSettings {
             general: GeneralSettings::default(),
             display: DisplaySettings::default(),
             advanced: AdvancedSettings::default(),
+            notifications: NotificationSettings::default(),
             active_pairs: Vec::new(),
+            pwm_groups: Vec::new(),
+            virtual_sensors: Vec::new(),
             pwm_fan_mappings: Vec::new(),
             detection_completed: false,
             pwm_fan_pairings: Vec::new(),
             sensor_friendly_names: Vec::new(),
+            sensor_filters: Vec::new(),
+            controller_filter: crate::ControllerFilter::default(),
+            active_profile: default_profile(),
+            profile_schedule: Default::default(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Built-in profiles followed by any other profile a pair or the schedule uses
+    pub fn available_profiles(&self) -> Vec<String> {
+        let mut profiles: Vec<String> = crate::constants::profiles::BUILTIN
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let scheduled = self.profile_schedule.entries.iter().map(|e| &e.profile);
+        let paired = self.active_pairs.iter().flat_map(|p| p.profile_curves.keys());
+        for name in paired.chain(scheduled) {
+            if !profiles.contains(name) {
+                profiles.push(name.clone());
+            }
+        }
+        profiles
+    }
+
+    /// Noise profiles of the fans the active profile drives from `curve_id`
+    /// Fans without a profile of their own get the generic estimate
+    pub fn noise_profiles_for_curve(&self, curve_id: &str) -> Vec<crate::NoiseProfile> {
+        self.active_pairs.iter()
+            .filter(|p| p.active && p.curve_for_profile(&self.active_profile) == curve_id)
+            .flat_map(|p| if p.fan_paths.is_empty() { vec![p.fan_path.clone()] } else { p.fan_paths.clone() })
+            .filter(|pwm_path| !pwm_path.is_empty())
+            .map(|pwm_path| {
+                self.pwm_fan_pairings.iter()
+                    .find(|p| p.pwm_path == pwm_path)
+                    .and_then(|p| p.noise.clone())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Move everything configured for PWM `old` to `new` (a rebound binding)
+    /// `fan_path` replaces the fan of its pairing when given
+    /// Returns whether anything referred to `old`
+    pub fn repoint_pwm(&mut self, old: &str, new: &str, fan_path: Option<&str>) -> bool {
+        let mut changed = false;
+        for pair in &mut self.active_pairs {
+            for path in std::iter::once(&mut pair.fan_path).chain(pair.fan_paths.iter_mut()) {
+                if path == old {
+                    *path = new.to_string();
+                    changed = true;
+                }
+            }
+        }
+        for member in self.pwm_groups.iter_mut().flat_map(|g| g.members.iter_mut()) {
+            if member.pwm_path == old {
+                member.pwm_path = new.to_string();
+                changed = true;
+            }
         }
+        for pairing in self.pwm_fan_pairings.iter_mut().filter(|p| p.pwm_path == old) {
+            pairing.pwm_path = new.to_string();
+            if let Some(fan_path) = fan_path {
+                pairing.fan_path = Some(fan_path.to_string());
+            }
+            changed = true;
+        }
+        changed
     }
 }
 
@@ -444,6 +709,42 @@ impl Default for GeneralSettings {
             apply_curves_on_startup: true,
             default_page: "dashboard".to_string(),
             rate_limit: 1500,
+            fan_stall_timeout_secs: 10,
+            adaptive_polling: AdaptivePollSettings::default(),
+            alarm_boost: AlarmBoostSettings::default(),
+            config_format: ConfigFormat::default(),
+        }
+    }
+}
+
+impl Default for AdaptivePollSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_interval_ms: default_adaptive_min_interval(),
+            max_interval_ms: default_adaptive_max_interval(),
+            slope_threshold: default_adaptive_slope_threshold(),
+        }
+    }
+}
+
+impl Default for AlarmBoostSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cooldown_secs: default_alarm_boost_cooldown(),
+        }
+    }
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            daemon_down: true,
+            thermal_threshold: true,
+            thermal_threshold_celsius: crate::constants::temperature::HIGH_THRESHOLD,
+            fan_failure: true,
+            fingerprint_drift: true,
         }
     }
 }
@@ -460,17 +761,50 @@ impl Default for DisplaySettings {
             window_manager: "auto".to_string(),
             graph_smoothing: "direct".to_string(),
             frame_rate: 60,
+            graph_window_secs: 900,
+            graph_hidden_series: Vec::new(),
+            sensor_card_order: Vec::new(),
+            hidden_sensor_cards: Vec::new(),
             window_width: None,
             window_height: None,
             window_x: None,
             window_y: None,
             window_maximized: None,
+            sensor_colors: BTreeMap::new(),
+        }
+    }
+}
+
+impl DisplaySettings {
+    /// Sort sensor card keys into their saved order
+    pub fn arrange_sensor_cards(&self, keys: &mut [String]) {
+        keys.sort_by_key(|key| {
+            self.sensor_card_order.iter().position(|k| k == key).unwrap_or(usize::MAX)
+        });
+    }
+
+    /// Save the order of a set of cards, keeping the place of cards not in `keys`
+    pub fn set_sensor_card_order(&mut self, keys: &[String]) {
+        self.sensor_card_order.retain(|k| !keys.contains(k));
+        self.sensor_card_order.extend(keys.iter().cloned());
+    }
+
+    /// Whether the user has hidden a sensor card
+    pub fn is_sensor_card_hidden(&self, key: &str) -> bool {
+        self.hidden_sensor_cards.iter().any(|k| k == key)
+    }
+
+    /// Hide or show a sensor card
+    pub fn set_sensor_card_hidden(&mut self, key: &str, hidden: bool) {
+        self.hidden_sensor_cards.retain(|k| k != key);
+        if hidden {
+            self.hidden_sensor_cards.push(key.to_string());
         }
     }
 }
 
 /// Get the settings file path
-/// Linux/BSD: ~/.config/hyperfan/settings.json
+/// Linux/BSD: ~/.config/hyperfan/settings.json (or settings.toml)
 /// 
 /// Uses the centralized user_config_dir() from constants.rs which handles:
 /// - SUDO_USER for sudo-elevated processes
@@ -490,25 +824,34 @@ pub fn get_settings_path() -> Result<PathBuf> {
         })?;
     }
     
-    Ok(hyperfan_dir.join("settings.json"))
+    Ok(crate::data::config_file_path(&hyperfan_dir, "settings", ConfigFormat::Json))
 }
 
-/// Load settings from JSON file
+/// Load settings from the JSON or TOML settings file
 pub fn load_settings() -> Result<AppSettings> {
     let path = get_settings_path()?;
     
-    if !path.exists() {
+    if !path.exists() && crate::data::system_config_path("settings").is_none() {
         // Return defaults if no settings file exists
         return Ok(AppSettings::default());
     }
     
-    let content = fs::read_to_string(&path).map_err(|e| {
-        HyperfanError::config(format!("Failed to read settings file: {}", e))
-    })?;
-    
-    let mut settings: AppSettings = serde_json::from_str(&content).map_err(|e| {
-        HyperfanError::config(format!("Failed to parse settings JSON: {}", e))
-    })?;
+    let mut settings: AppSettings = match system_layer()? {
+        None => crate::data::read_config(&path).map_err(|e| {
+            HyperfanError::config(format!("Failed to load settings: {}", e))
+        })?,
+        Some(mut merged) => {
+            if path.exists() {
+                let user = crate::data::read_config(&path).map_err(|e| {
+                    HyperfanError::config(format!("Failed to load settings: {}", e))
+                })?;
+                crate::data::merge_layer(&mut merged, user);
+            }
+            serde_json::from_value(merged).map_err(|e| {
+                HyperfanError::config(format!("Failed to load settings: {}", e))
+            })?
+        }
+    };
     
     // MIGRATION: Ensure all entities have valid UUIDs
     // This handles legacy configs with empty IDs or old-style "pair_timestamp" IDs
@@ -533,54 +876,70 @@ pub fn load_settings() -> Result<AppSettings> {
     // Save migrated settings if any changes were made
     if needs_save {
         // Use a separate save to avoid recursion - write directly
-        if let Ok(json) = serde_json::to_string_pretty(&settings) {
-            let _ = fs::write(&path, json);
+        if let (Some(dir), Ok(layer)) = (path.parent(), user_layer(&settings)) {
+            let _ = crate::data::write_config(dir, "settings", settings.general.config_format, &layer);
         }
     }
     
     Ok(settings)
 }
 
-/// Save settings to JSON file
+/// Save settings in the configured format
 /// Uses atomic write (temp file + rename) to prevent corruption on crash
 pub fn save_settings(settings: &AppSettings) -> Result<()> {
-    use std::io::Write;
-    
     let path = get_settings_path()?;
-    
-    let json = serde_json::to_string_pretty(settings).map_err(|e| {
-        HyperfanError::config(format!("Failed to serialize settings: {}", e))
-    })?;
-    
-    // CRITICAL: Atomic write - write to temp file then rename
-    // This prevents corruption if process crashes during write
-    let temp_path = path.with_extension("json.tmp");
-    
-    let mut file = fs::File::create(&temp_path).map_err(|e| {
-        HyperfanError::config(format!("Failed to create temp file: {}", e))
-    })?;
-    
-    file.write_all(json.as_bytes()).map_err(|e| {
-        HyperfanError::config(format!("Failed to write to temp file: {}", e))
-    })?;
-    
-    file.sync_all().map_err(|e| {
-        HyperfanError::config(format!("Failed to sync temp file: {}", e))
-    })?;
-    
-    drop(file);
-    
-    // Atomic rename - this is the critical operation that makes the write atomic
-    fs::rename(&temp_path, &path).map_err(|e| {
-        HyperfanError::config(format!("Failed to rename temp file: {}", e))
+    let dir = path.parent().ok_or_else(|| HyperfanError::config("Invalid settings path"))?;
+    let format = settings.general.config_format;
+
+    crate::data::write_config(dir, "settings", format, &user_layer(settings)?).map_err(|e| {
+        HyperfanError::config(format!("Failed to save settings: {}", e))
     })?;
-    
+
     // Update cache after successful save
     update_cache(settings);
+
+    // Move curves over when the format changes
+    if ConfigFormat::of_path(&crate::data::get_curves_path()) != format {
+        crate::data::save_curves(&crate::data::load_curves()?)?;
+    }
     
     Ok(())
 }
 
+/// Defaults with the system settings layer merged over them, if there is one
+fn system_layer() -> Result<Option<serde_json::Value>> {
+    let Some(system) = crate::data::load_system_layer("settings")? else {
+        return Ok(None);
+    };
+    let mut merged = serde_json::to_value(AppSettings::default())?;
+    crate::data::merge_layer(&mut merged, system);
+    Ok(Some(merged))
+}
+
+/// What to write to the user settings file: everything, or with a system
+/// layer only the keys that differ from it
+pub(crate) fn user_layer(settings: &AppSettings) -> Result<serde_json::Value> {
+    let settings = serde_json::to_value(settings)?;
+    let mut layer = match system_layer()? {
+        Some(base) => crate::data::layer_overrides(&base, &settings),
+        None => settings,
+    };
+    // TOML has no null; an unset option is written as a missing key
+    remove_nulls(&mut layer);
+    Ok(layer)
+}
+
+fn remove_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(remove_nulls);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
+}
+
 /// Update a single setting value and save
 pub fn update_setting<F>(updater: F) -> Result<AppSettings>
 where
@@ -602,6 +961,25 @@ pub fn save_pair(pair: FanCurvePair) -> Result<()> {
     Ok(())
 }
 
+/// Set (or with `None`, clear) the curve a pair uses for a profile
+pub fn set_pair_profile_curve(pair_id: &str, profile: &str, curve_id: Option<&str>) -> Result<()> {
+    hf_protocol::validate_profile_name(profile).map_err(HyperfanError::config)?;
+    let mut found = false;
+    update_setting(|settings| {
+        if let Some(pair) = settings.active_pairs.iter_mut().find(|p| p.id == pair_id) {
+            found = true;
+            match curve_id {
+                Some(id) => { pair.profile_curves.insert(profile.to_string(), id.to_string()); }
+                None => { pair.profile_curves.remove(profile); }
+            }
+        }
+    })?;
+    if !found {
+        return Err(HyperfanError::config(format!("Pair not found: {}", pair_id)));
+    }
+    Ok(())
+}
+
 /// Remove a fan-curve pair
 pub fn delete_pair(pair_id: &str) -> Result<()> {
     update_setting(|settings| {
@@ -610,6 +988,26 @@ pub fn delete_pair(pair_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Add a virtual sensor, or replace the one with the same ID
+pub fn save_virtual_sensor(sensor: crate::VirtualSensor) -> Result<()> {
+    sensor.validate().map_err(HyperfanError::config)?;
+    update_setting(|settings| {
+        match settings.virtual_sensors.iter_mut().find(|s| s.id == sensor.id) {
+            Some(existing) => *existing = sensor,
+            None => settings.virtual_sensors.push(sensor),
+        }
+    })?;
+    Ok(())
+}
+
+/// Delete a virtual sensor (pairs still using it run at the fallback speed)
+pub fn delete_virtual_sensor(id: &str) -> Result<()> {
+    update_setting(|settings| {
+        settings.virtual_sensors.retain(|s| s.id != id);
+    })?;
+    Ok(())
+}
+
 /// Get all active pairs
 pub fn get_active_pairs() -> Result<Vec<FanCurvePair>> {
     let settings = load_settings()?;
@@ -779,6 +1177,118 @@ pub fn get_all_sensor_friendly_names() -> Result<Vec<SensorFriendlyName>> {
     Ok(settings.sensor_friendly_names)
 }
 
+// ============================================================================
+// Sensor Filters
+// ============================================================================
+
+/// Get the filter configured for a temperature source, if any
+pub fn get_sensor_filter(path: &str) -> Result<Option<crate::SensorFilter>> {
+    let settings = load_settings()?;
+    Ok(settings.sensor_filters.into_iter().find(|f| f.path == path))
+}
+
+/// Set the filter of a temperature source (a pass-through filter removes it)
+pub fn set_sensor_filter(filter: crate::SensorFilter) -> Result<()> {
+    filter.validate().map_err(HyperfanError::config)?;
+    update_setting(|settings| {
+        settings.sensor_filters.retain(|f| f.path != filter.path);
+        if !filter.is_passthrough() {
+            settings.sensor_filters.push(filter);
+        }
+    })?;
+    Ok(())
+}
+
+// ============================================================================
+// Controller Filter
+// ============================================================================
+
+/// Add a chip or PWM channel to the controller filter (no-op if listed)
+pub fn add_controller_filter_entry(entry: crate::ControllerFilterEntry) -> Result<()> {
+    let settings = update_setting(|settings| {
+        let entries = &mut settings.controller_filter.entries;
+        if !entries.iter().any(|e| e.chip_id == entry.chip_id && e.channel == entry.channel) {
+            entries.push(entry);
+        }
+    })?;
+    crate::set_controller_filter(settings.controller_filter);
+    Ok(())
+}
+
+/// Remove a chip or PWM channel from the controller filter
+pub fn remove_controller_filter_entry(chip_id: &str, channel: Option<&str>) -> Result<()> {
+    let settings = update_setting(|settings| {
+        settings.controller_filter.entries.retain(|e| !(e.chip_id == chip_id && e.channel.as_deref() == channel));
+    })?;
+    crate::set_controller_filter(settings.controller_filter);
+    Ok(())
+}
+
+/// Switch the controller filter between blacklist and allowlist
+pub fn set_controller_filter_mode(mode: crate::ControllerFilterMode) -> Result<()> {
+    let settings = update_setting(|settings| settings.controller_filter.mode = mode)?;
+    crate::set_controller_filter(settings.controller_filter);
+    Ok(())
+}
+
+// ============================================================================
+// Sensor Colors
+// ============================================================================
+
+/// Largest theme preset file accepted on import
+const MAX_THEME_PRESET_BYTES: u64 = 256 * 1024;
+
+/// Parse a "#rrggbb" color
+pub fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Sensor colors saved to a file, to share a theme between machines or users
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThemePreset {
+    /// Name shown after importing
+    #[serde(default)]
+    pub name: String,
+    /// Source path -> "#rrggbb", as in `DisplaySettings::sensor_colors`
+    #[serde(default)]
+    pub sensor_colors: BTreeMap<String, String>,
+}
+
+impl ThemePreset {
+    /// Check every color is a "#rrggbb" value
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        match self.sensor_colors.iter().find(|(_, color)| parse_hex_color(color).is_none()) {
+            Some((path, color)) => Err(format!("invalid color '{}' for {}", color, path)),
+            None => Ok(()),
+        }
+    }
+
+    /// Read and validate a preset file
+    pub fn load(path: &std::path::Path) -> Result<Self> {
+        let metadata = fs::metadata(path)?;
+        if metadata.len() > MAX_THEME_PRESET_BYTES {
+            return Err(HyperfanError::config("Theme preset file too large"));
+        }
+        let preset: Self = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| HyperfanError::config(format!("Invalid theme preset: {}", e)))?;
+        preset.validate().map_err(HyperfanError::config)?;
+        Ok(preset)
+    }
+
+    /// Write the preset as JSON
+    pub fn save(&self, path: &std::path::Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| HyperfanError::config(format!("Failed to serialize theme preset: {}", e)))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
 // ============================================================================
 // Window Manager Detection
 // ============================================================================
@@ -1130,6 +1640,11 @@ pub fn create_fingerprinted_pairing(
         fan_path: fan_path.map(|s| s.to_string()),
         fan_name: fan_name.map(|s| s.to_string()),
         friendly_name: friendly_name.map(|s| s.to_string()),
+        tuning: None,
+        control_mode: hf_protocol::PairingControlMode::default(),
+        zero_rpm: false,
+        enforcement: hf_protocol::PwmEnforcement::default(),
+        noise: None,
         // Hardware identification
         driver_name: hw_id.driver_name,
         device_path: hw_id.device_path,
@@ -1586,4 +2101,109 @@ mod tests {
             .expect("Serialized settings should deserialize back to AppSettings");
         assert_eq!(parsed.general.poll_interval_ms, settings.general.poll_interval_ms);
     }
+
+    #[test]
+    fn test_settings_toml_roundtrip() {
+        let mut settings = AppSettings::default();
+        settings.general.config_format = ConfigFormat::Toml;
+        settings.active_pairs.push(serde_json::from_str(r#"{
+            "id": "pair_1", "name": "CPU", "curve_id": "balanced",
+            "temp_source_path": "/sys/class/hwmon/hwmon0/temp1_input",
+            "fan_path": "/sys/class/hwmon/hwmon0/pwm1"
+        }"#).expect("Pair should deserialize"));
+
+        let text = toml::to_string(&settings).expect("Settings should serialize to TOML");
+        let parsed: AppSettings = toml::from_str(&text).expect("TOML settings should deserialize");
+        assert_eq!(parsed.general.config_format, ConfigFormat::Toml);
+        assert_eq!(parsed.active_pairs.len(), 1);
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), serde_json::to_value(&settings).unwrap());
+
+        // The user layer is written as a Value, where unset options are null
+        let mut layer = serde_json::to_value(&settings).unwrap();
+        remove_nulls(&mut layer);
+        let text = toml::to_string(&layer).expect("User layer should serialize to TOML");
+        let parsed: AppSettings = toml::from_str(&text).expect("User layer should deserialize");
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), serde_json::to_value(&settings).unwrap());
+    }
+
+    #[test]
+    fn test_theme_preset_colors() {
+        assert_eq!(parse_hex_color("#3584e4"), Some((0x35, 0x84, 0xe4)));
+        assert_eq!(parse_hex_color("3584e4"), None);
+        assert_eq!(parse_hex_color("#3584e"), None);
+        assert_eq!(parse_hex_color("#35g4e4"), None);
+
+        let mut preset = ThemePreset::default();
+        preset.sensor_colors.insert("/sys/class/hwmon/hwmon0/temp1_input".into(), "#e01b24".into());
+        assert!(preset.validate().is_ok());
+        preset.sensor_colors.insert("/sys/class/hwmon/hwmon0/fan1_input".into(), "red".into());
+        assert!(preset.validate().is_err());
+    }
+
+    #[test]
+    fn test_sensor_card_layout() {
+        let mut display = DisplaySettings::default();
+        let keys = |names: &[&str]| names.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        display.set_sensor_card_order(&keys(&["nct6798", "nvme", "drivetemp"]));
+        display.set_sensor_card_order(&keys(&["drivetemp", "nvme"]));
+        assert_eq!(display.sensor_card_order, keys(&["nct6798", "drivetemp", "nvme"]));
+
+        // Unknown cards keep their detection order after the saved ones
+        let mut detected = keys(&["acpitz", "nvme", "it8686", "nct6798"]);
+        display.arrange_sensor_cards(&mut detected);
+        assert_eq!(detected, keys(&["nct6798", "nvme", "acpitz", "it8686"]));
+
+        display.set_sensor_card_hidden("nvme", true);
+        display.set_sensor_card_hidden("nvme", true);
+        assert!(display.is_sensor_card_hidden("nvme"));
+        assert_eq!(display.hidden_sensor_cards.len(), 1);
+        display.set_sensor_card_hidden("nvme", false);
+        assert!(!display.is_sensor_card_hidden("nvme"));
+    }
+
+    #[test]
+    fn test_profile_curves() {
+        let pair: FanCurvePair = serde_json::from_str(r#"{
+            "id": "pair_1", "name": "CPU", "curve_id": "balanced",
+            "temp_source_path": "/sys/class/hwmon/hwmon0/temp1_input",
+            "fan_path": "/sys/class/hwmon/hwmon0/pwm1",
+            "profile_curves": { "performance": "aggressive", "gaming": "max" }
+        }"#).expect("Pair with profile curves should deserialize");
+
+        assert_eq!(pair.curve_for_profile("performance"), "aggressive");
+        assert_eq!(pair.curve_for_profile("quiet"), "balanced");
+
+        let settings = AppSettings { active_pairs: vec![pair], ..AppSettings::default() };
+        assert_eq!(settings.active_profile, "normal");
+        assert_eq!(settings.available_profiles(), ["quiet", "normal", "performance", "gaming"]);
+    }
+
+    #[test]
+    fn test_repoint_pwm() {
+        let mut settings = AppSettings::default();
+        settings.active_pairs.push(serde_json::from_str(r#"{
+            "id": "pair_1", "name": "CPU", "curve_id": "balanced",
+            "temp_source_path": "/sys/class/hwmon/hwmon0/temp1_input",
+            "fan_path": "/sys/class/hwmon/hwmon2/pwm2",
+            "fan_paths": ["/sys/class/hwmon/hwmon2/pwm2", "/sys/class/hwmon/hwmon2/pwm3"]
+        }"#).expect("Pair should deserialize"));
+        settings.pwm_fan_pairings.push(create_fingerprinted_pairing(
+            "/sys/class/hwmon/hwmon2/pwm2", Some("/sys/class/hwmon/hwmon2/fan2_input"), None, None,
+        ));
+
+        assert!(settings.repoint_pwm(
+            "/sys/class/hwmon/hwmon2/pwm2",
+            "/sys/class/hwmon/hwmon3/pwm2",
+            Some("/sys/class/hwmon/hwmon3/fan2_input"),
+        ));
+        let pair = &settings.active_pairs[0];
+        assert_eq!(pair.fan_path, "/sys/class/hwmon/hwmon3/pwm2");
+        assert_eq!(pair.fan_paths, ["/sys/class/hwmon/hwmon3/pwm2", "/sys/class/hwmon/hwmon2/pwm3"]);
+        let pairing = &settings.pwm_fan_pairings[0];
+        assert_eq!(pairing.pwm_path, "/sys/class/hwmon/hwmon3/pwm2");
+        assert_eq!(pairing.fan_path.as_deref(), Some("/sys/class/hwmon/hwmon3/fan2_input"));
+
+        assert!(!settings.repoint_pwm("/sys/class/hwmon/hwmon9/pwm1", "/sys/class/hwmon/hwmon9/pwm2", None));
+    }
 }
@@ -0,0 +1,161 @@
+//! First-run setup
+//!
+//! The GUI's setup assistant collects the user's choices into a
+//! [`SetupPlan`]: the detected PWM-fan mappings, the fans the user confirmed
+//! with a temperature source each, and a curve preset. [`apply_setup`] turns
+//! the plan into fingerprinted pairings, one preset curve and a fan-curve pair
+//! per confirmed fan. Settings and curves are both staged before either is
+//! moved into place, so an interrupted or failed setup leaves the previous
+//! config untouched.
+
+use std::fs;
+use tracing::info;
+
+use crate::constants::{curve, paths};
+use crate::data::{load_curves, stage_config, CurveStore, FanMapping, PersistedCurve};
+use crate::engine::CurvePreset;
+use crate::error::{HyperfanError, Result};
+use crate::settings::{create_fingerprinted_pairing, generate_guid, load_settings, AppSettings, FanCurvePair};
+
+/// A fan the user confirmed during setup
+#[derive(Debug, Clone)]
+pub struct SetupFan {
+    /// PWM output driving the fan
+    pub pwm_path: String,
+    /// Tachometer the detection matched to it
+    pub fan_path: String,
+    /// Display name of the fan (also the pair's name)
+    pub name: String,
+    /// Temperature the fan follows
+    pub temp_source_path: String,
+}
+
+/// Everything the setup assistant writes
+#[derive(Debug, Clone)]
+pub struct SetupPlan {
+    /// Every mapping detection found, confirmed or not
+    pub mappings: Vec<FanMapping>,
+    /// Fans to pair and put on the preset curve
+    pub fans: Vec<SetupFan>,
+    pub preset: CurvePreset,
+}
+
+impl SetupPlan {
+    /// Add the plan's pairings, curve and pairs to `settings` and `curves`
+    ///
+    /// Pairings and pairs already driving one of the plan's PWM outputs are
+    /// replaced. Returns the ID of the new curve.
+    pub fn apply_to(&self, settings: &mut AppSettings, curves: &mut CurveStore) -> String {
+        let curve_id = generate_guid();
+        curves.upsert(PersistedCurve {
+            id: curve_id.clone(),
+            name: self.preset.label().to_string(),
+            temp_source_path: String::new(),
+            temp_source_label: String::new(),
+            points: self.preset.points().iter().map(|p| (p.temperature, p.fan_percent)).collect(),
+            created_at: 0,
+            updated_at: 0,
+            hysteresis: curve::DEFAULT_HYSTERESIS_CELSIUS,
+            delay_ms: curve::DEFAULT_DELAY_MS,
+            ramp_up_speed: curve::DEFAULT_RAMP_UP_SPEED,
+            ramp_down_speed: curve::DEFAULT_RAMP_DOWN_SPEED,
+            interpolation: Default::default(),
+            battery_offset: None,
+            fallback_sources: Vec::new(),
+        });
+
+        settings.pwm_fan_mappings = self.mappings.clone();
+        settings.detection_completed = true;
+
+        for fan in &self.fans {
+            settings.pwm_fan_pairings.retain(|p| p.pwm_path != fan.pwm_path);
+            settings.pwm_fan_pairings.push(create_fingerprinted_pairing(
+                &fan.pwm_path,
+                Some(&fan.fan_path),
+                Some(&fan.name),
+                None,
+            ));
+
+            settings.active_pairs.retain(|p| p.fan_path != fan.pwm_path && !p.fan_paths.contains(&fan.pwm_path));
+            settings.active_pairs.push(FanCurvePair {
+                id: generate_guid(),
+                name: fan.name.clone(),
+                curve_id: curve_id.clone(),
+                temp_source_path: fan.temp_source_path.clone(),
+                fan_path: fan.pwm_path.clone(),
+                fan_paths: vec![fan.pwm_path.clone()],
+                hysteresis_ms: 0,
+                active: true,
+                profile_curves: Default::default(),
+            });
+        }
+        curve_id
+    }
+}
+
+/// Write the setup result into the user's settings and curves
+///
+/// Both files are staged first; neither is replaced unless both could be written.
+pub fn apply_setup(plan: &SetupPlan) -> Result<()> {
+    let mut settings = load_settings()?;
+    let mut curves = load_curves()?;
+    plan.apply_to(&mut settings, &mut curves);
+
+    let dir = paths::user_config_dir()
+        .ok_or_else(|| HyperfanError::config("Could not determine config directory"))?;
+    fs::create_dir_all(&dir).map_err(|e| HyperfanError::FileWrite { path: dir.clone(), source: e })?;
+
+    let format = settings.general.config_format;
+    let staged_settings = stage_config(&dir, "settings", format, &crate::settings::user_layer(&settings)?)?;
+    let staged_curves = stage_config(&dir, "curves", format, &crate::data::user_curves(&curves)?)?;
+    staged_settings.commit()?;
+    staged_curves.commit()?;
+    crate::settings::invalidate_settings_cache();
+
+    info!(
+        "First-run setup wrote {} pairs on the {} curve",
+        plan.fans.len(),
+        plan.preset.label()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fan(index: u32) -> SetupFan {
+        SetupFan {
+            pwm_path: format!("/sys/class/hwmon/hwmon-missing/pwm{}", index),
+            fan_path: format!("/sys/class/hwmon/hwmon-missing/fan{}_input", index),
+            name: format!("Fan {}", index),
+            temp_source_path: "/sys/class/hwmon/hwmon-missing/temp1_input".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_to_replaces_existing_pairs() {
+        let mut settings = AppSettings::default();
+        settings.active_pairs.push(FanCurvePair {
+            id: "old".to_string(),
+            name: "Old".to_string(),
+            curve_id: "old-curve".to_string(),
+            temp_source_path: String::new(),
+            fan_path: fan(1).pwm_path,
+            fan_paths: Vec::new(),
+            hysteresis_ms: 0,
+            active: true,
+            profile_curves: Default::default(),
+        });
+        let mut curves = CurveStore::new();
+        let plan = SetupPlan { mappings: Vec::new(), fans: vec![fan(1), fan(2)], preset: CurvePreset::Quiet };
+
+        let curve_id = plan.apply_to(&mut settings, &mut curves);
+
+        assert!(settings.detection_completed);
+        assert_eq!(curves.get(&curve_id).map(|c| c.name.as_str()), Some("Quiet"));
+        assert_eq!(settings.pwm_fan_pairings.len(), 2);
+        assert_eq!(settings.active_pairs.len(), 2);
+        assert!(settings.active_pairs.iter().all(|p| p.curve_id == curve_id));
+    }
+}
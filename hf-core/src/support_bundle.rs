@@ -0,0 +1,244 @@
+//! Bug report bundles
+//!
+//! [`create_support_bundle`] collects what a bug report needs into one
+//! `.tar.gz`: version and platform info, the diagnostic checks, a raw hardware
+//! snapshot, the user's config files (settings, curves, graphs and the
+//! fingerprint binding store), the daemon config and its recent log.
+//!
+//! Everything is redacted before it is written: the hostname and home
+//! directory are replaced in every file, values of keys that name serial
+//! numbers or credentials are blanked, and embedded-controller settings and
+//! EC log entries are left out, since EC register data identifies the machine
+//! and is only meaningful to someone already poking at it. The archive is
+//! packed with the system `tar`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use tracing::{debug, info};
+
+use crate::constants::paths;
+use crate::error::{HyperfanError, Result};
+
+/// Replacement for redacted values
+const REDACTED: &str = "[redacted]";
+
+/// Key fragments whose values are blanked wherever they appear
+const SENSITIVE_KEYS: &[&str] = &["serial", "password", "token", "secret", "hostname", "mac_address"];
+
+/// Prefix of embedded-controller settings, left out entirely
+const EC_KEY_PREFIX: &str = "ec_";
+
+/// Daemon log entries kept (the daemon's ring buffer holds more)
+const MAX_LOG_ENTRIES: usize = 2000;
+
+/// Default file name, `hyperfan-bug-report-<unix time>.tar.gz`
+pub fn default_bundle_name() -> String {
+    format!("{}.tar.gz", bundle_dir_name())
+}
+
+fn bundle_dir_name() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    format!("hyperfan-bug-report-{}", secs)
+}
+
+/// Replaces identifying strings in bundle contents
+pub struct Redactor {
+    /// (text, replacement), longest first so a home path wins over its parts
+    replacements: Vec<(String, &'static str)>,
+}
+
+impl Redactor {
+    /// Redactor for this machine and user
+    pub fn for_current_system() -> Self {
+        let hostname = crate::system::get_system_summary().map(|s| s.hostname).unwrap_or_default();
+        let home = dirs::home_dir().map(|h| h.to_string_lossy().into_owned()).unwrap_or_default();
+        Self::new(hostname.trim(), &home)
+    }
+
+    pub fn new(hostname: &str, home: &str) -> Self {
+        let mut replacements = Vec::new();
+        // Short names would mangle unrelated words
+        if hostname.len() >= 3 {
+            replacements.push((hostname.to_string(), "<hostname>"));
+        }
+        if home.len() > 1 {
+            replacements.push((home.trim_end_matches('/').to_string(), "~"));
+        }
+        replacements.sort_by_key(|(text, _)| std::cmp::Reverse(text.len()));
+        Self { replacements }
+    }
+
+    pub fn redact_text(&self, text: &str) -> String {
+        self.replacements
+            .iter()
+            .fold(text.to_string(), |text, (from, to)| text.replace(from.as_str(), to))
+    }
+
+    /// Blank sensitive values, drop EC settings and redact every string
+    pub fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                map.retain(|key, _| !key.to_ascii_lowercase().starts_with(EC_KEY_PREFIX));
+                for (key, value) in map.iter_mut() {
+                    let key = key.to_ascii_lowercase();
+                    if SENSITIVE_KEYS.iter().any(|s| key.contains(s)) && !value.is_null() {
+                        *value = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_json(value);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            Value::String(text) => *text = self.redact_text(text),
+            _ => {}
+        }
+    }
+}
+
+/// Collect a redacted bug report bundle into `output` (a `.tar.gz`)
+///
+/// Parts that cannot be collected (no daemon, no config yet) are noted in
+/// `missing.txt` instead of failing the bundle. Returns the archive path.
+pub fn create_support_bundle(output: &Path) -> Result<PathBuf> {
+    let name = bundle_dir_name();
+    let staging = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+    let root = staging.join(&name);
+    fs::create_dir_all(root.join("config")).map_err(|e| HyperfanError::FileWrite { path: root.clone(), source: e })?;
+
+    let result = collect(&root, &Redactor::for_current_system()).and_then(|()| pack(&staging, &name, output));
+    if let Err(e) = fs::remove_dir_all(&staging) {
+        debug!("Failed to remove {:?}: {}", staging, e);
+    }
+    result?;
+
+    info!("Wrote bug report bundle to {:?}", output);
+    Ok(output.to_path_buf())
+}
+
+fn collect(root: &Path, redactor: &Redactor) -> Result<()> {
+    let mut missing = Vec::new();
+
+    let mut version = serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "os": crate::system::get_os_name(),
+        "init_system": crate::service::detect_init_system().to_string(),
+        "desktop": crate::settings::detect_desktop_environment().to_string(),
+        "service": crate::service::get_service_status(),
+    });
+    match crate::system::get_system_summary() {
+        Ok(summary) => {
+            version["kernel"] = summary.kernel_version.into();
+            version["cpu"] = summary.cpu_model.into();
+            version["motherboard"] = summary.motherboard_name.into();
+        }
+        Err(e) => missing.push(format!("system summary: {}", e)),
+    }
+    write_json(root, "version.json", version, redactor)?;
+
+    let diagnostics = serde_json::to_value(crate::diagnostics::run_diagnostics())?;
+    write_json(root, "diagnostics.json", diagnostics, redactor)?;
+
+    match crate::hw::capture_raw_snapshot() {
+        Ok(snapshot) => write_json(root, "hardware.json", serde_json::to_value(snapshot)?, redactor)?,
+        Err(e) => missing.push(format!("hardware snapshot: {}", e)),
+    }
+
+    match paths::user_config_dir() {
+        Some(dir) => copy_configs(&dir, &root.join("config"), redactor, &mut missing)?,
+        None => missing.push("user config: could not determine config directory".to_string()),
+    }
+    let system_config = root.join("config").join("system");
+    fs::create_dir_all(&system_config).map_err(|e| HyperfanError::FileWrite { path: system_config.clone(), source: e })?;
+    copy_configs(Path::new(paths::CONFIG_DIR), &system_config, redactor, &mut missing)?;
+
+    match crate::daemon_client::daemon_get_logs(0, None) {
+        Ok(entries) => {
+            let lines: Vec<String> = entries
+                .iter()
+                .filter(|e| !e.subsystem.eq_ignore_ascii_case("EC") && !e.message.contains("EC register"))
+                .map(|e| format!("{} {:?} [{}] {}", e.timestamp_ms, e.level, e.subsystem, e.message))
+                .collect();
+            let start = lines.len().saturating_sub(MAX_LOG_ENTRIES);
+            write_text(root, "daemon.log", &lines[start..].join("\n"), redactor)?;
+        }
+        Err(e) => missing.push(format!("daemon log: {}", e)),
+    }
+
+    if !missing.is_empty() {
+        write_text(root, "missing.txt", &missing.join("\n"), redactor)?;
+    }
+    Ok(())
+}
+
+/// Copy every JSON and TOML file of `dir` into `dest` as redacted JSON
+fn copy_configs(dir: &Path, dest: &Path, redactor: &Redactor, missing: &mut Vec<String>) -> Result<()> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        missing.push(format!("{}: not readable", dir.display()));
+        return Ok(());
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        let is_config = path.extension().is_some_and(|e| e == "json" || e == "toml");
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()).filter(|_| is_config && path.is_file()) else {
+            continue;
+        };
+        let loaded = crate::data::validate_file_size(&path).and_then(|()| crate::data::read_config::<Value>(&path));
+        match loaded {
+            Ok(value) => write_json(dest, &format!("{}.json", stem), value, redactor)?,
+            Err(e) => missing.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    Ok(())
+}
+
+fn write_json(dir: &Path, name: &str, mut value: Value, redactor: &Redactor) -> Result<()> {
+    redactor.redact_json(&mut value);
+    let path = dir.join(name);
+    fs::write(&path, serde_json::to_string_pretty(&value)?).map_err(|e| HyperfanError::FileWrite { path, source: e })
+}
+
+fn write_text(dir: &Path, name: &str, text: &str, redactor: &Redactor) -> Result<()> {
+    let path = dir.join(name);
+    fs::write(&path, redactor.redact_text(text)).map_err(|e| HyperfanError::FileWrite { path, source: e })
+}
+
+fn pack(staging: &Path, name: &str, output: &Path) -> Result<()> {
+    let status = Command::new("tar")
+        .arg("-czf")
+        .arg(output)
+        .arg("-C")
+        .arg(staging)
+        .arg(name)
+        .status()
+        .map_err(|e| HyperfanError::Generic(format!("Failed to run tar: {}", e)))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(HyperfanError::Generic(format!("tar exited with {}", status)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_json() {
+        let redactor = Redactor::new("workstation", "/home/alex");
+        let mut value = serde_json::json!({
+            "advanced": { "ec_direct_control_enabled": true, "debug_logging": false },
+            "board_serial": "ABC123",
+            "paths": ["/home/alex/.config/hyperfan/curves.json"],
+            "note": "built on workstation",
+        });
+        redactor.redact_json(&mut value);
+
+        assert_eq!(value["advanced"], serde_json::json!({ "debug_logging": false }));
+        assert_eq!(value["board_serial"], REDACTED);
+        assert_eq!(value["paths"][0], "~/.config/hyperfan/curves.json");
+        assert_eq!(value["note"], "built on <hostname>");
+    }
+}
@@ -349,6 +349,78 @@ fn read_mb_name() -> String {
     "Unknown Motherboard".to_string()
 }
 
+// ============================================================================
+// Power Supply
+// ============================================================================
+
+/// Where the system is drawing power from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PowerSource {
+    /// Mains power (also desktops without a battery)
+    Ac,
+    /// Running on battery
+    Battery,
+    /// Could not be determined
+    Unknown,
+}
+
+impl std::fmt::Display for PowerSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowerSource::Ac => write!(f, "AC"),
+            PowerSource::Battery => write!(f, "battery"),
+            PowerSource::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Detect the current power source
+/// - Linux: /sys/class/power_supply (Mains/USB `online`, Battery `status`)
+/// - FreeBSD: sysctl hw.acpi.acline
+pub fn get_power_source() -> PowerSource {
+    if let Ok(entries) = fs::read_dir("/sys/class/power_supply") {
+        let mut mains_seen = false;
+        let mut battery_seen = false;
+        let mut discharging = false;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let read = |name: &str| fs::read_to_string(path.join(name)).map(|s| s.trim().to_string()).unwrap_or_default();
+            match read("type").as_str() {
+                "Mains" | "USB" => {
+                    if read("online") == "1" {
+                        return PowerSource::Ac;
+                    }
+                    mains_seen = true;
+                }
+                // Peripheral batteries (mice, keyboards) report scope=Device
+                "Battery" if read("scope") != "Device" => {
+                    battery_seen = true;
+                    discharging |= read("status") == "Discharging";
+                }
+                _ => {}
+            }
+        }
+        // Offline USB-C ports on desktops don't mean battery power
+        if discharging || (mains_seen && battery_seen) {
+            return PowerSource::Battery;
+        }
+        return PowerSource::Ac;
+    }
+
+    if let Ok(output) = Command::new("sysctl").args(["-n", "hw.acpi.acline"]).output() {
+        if output.status.success() {
+            return match String::from_utf8_lossy(&output.stdout).trim() {
+                "1" => PowerSource::Ac,
+                "0" => PowerSource::Battery,
+                _ => PowerSource::Unknown,
+            };
+        }
+    }
+
+    PowerSource::Unknown
+}
+
 // ============================================================================
 // Platform Detection
 // ============================================================================
@@ -0,0 +1,118 @@
+//! Adaptive Polling
+//!
+//! Picks the control loop sleep from how fast temperatures are moving. Any
+//! sensor changing faster than the slope threshold drops the interval to the
+//! minimum immediately; while everything is stable the interval backs off
+//! gradually towards the maximum. Reduces wakeups on idle laptops without
+//! slowing the response to load spikes.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use hf_core::AdaptivePollSettings;
+
+/// Lower bound regardless of settings (matches the fixed poll minimum)
+const MIN_INTERVAL_MS: u64 = 50;
+
+/// Upper bound regardless of settings (must stay well below WatchdogSec / 2)
+const MAX_INTERVAL_MS: u64 = 10_000;
+
+/// Backoff factor per stable iteration
+const BACKOFF_FACTOR: f32 = 1.5;
+
+/// Minimum time between the readings a slope is computed from, so 1°C
+/// sensor quantization at fast poll rates doesn't look like a spike
+const SLOPE_WINDOW_SECS: f32 = 1.0;
+
+/// Last slope reference for one sensor
+struct SensorSlope {
+    temp: f32,
+    at: Instant,
+    /// °C per second over the last window
+    slope: f32,
+}
+
+/// Tracks per-sensor temperature slopes between iterations
+#[derive(Default)]
+pub struct AdaptivePoller {
+    sensors: HashMap<String, SensorSlope>,
+    interval_ms: Option<u64>,
+}
+
+impl AdaptivePoller {
+    /// Record a temperature read during the current iteration
+    pub fn observe(&mut self, path: &str, temp: f32, now: Instant) {
+        match self.sensors.get_mut(path) {
+            Some(sensor) => {
+                let secs = now.duration_since(sensor.at).as_secs_f32();
+                if secs >= SLOPE_WINDOW_SECS {
+                    sensor.slope = (temp - sensor.temp).abs() / secs;
+                    sensor.temp = temp;
+                    sensor.at = now;
+                }
+            }
+            None => {
+                self.sensors.insert(path.to_string(), SensorSlope { temp, at: now, slope: 0.0 });
+            }
+        }
+    }
+
+    /// Interval to sleep before the next iteration
+    /// Returns `fixed_ms` unchanged when adaptive polling is disabled
+    pub fn next_interval_ms(&mut self, settings: &AdaptivePollSettings, fixed_ms: u64) -> u64 {
+        // Forget sensors that are no longer read (pair removed or failing)
+        let stale = std::time::Duration::from_millis(MAX_INTERVAL_MS * 2);
+        self.sensors.retain(|_, s| s.at.elapsed() < stale);
+        let slope = self.sensors.values().map(|s| s.slope).fold(0.0, f32::max);
+        if !settings.enabled {
+            self.interval_ms = None;
+            return fixed_ms;
+        }
+
+        let min = (settings.min_interval_ms as u64).clamp(MIN_INTERVAL_MS, MAX_INTERVAL_MS);
+        let max = (settings.max_interval_ms as u64).clamp(min, MAX_INTERVAL_MS);
+        let interval = if slope >= settings.slope_threshold {
+            min
+        } else {
+            let current = self.interval_ms.unwrap_or(min);
+            ((current as f32 * BACKOFF_FACTOR) as u64).clamp(min, max)
+        };
+        self.interval_ms = Some(interval);
+        interval
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_adaptive_interval() {
+        let settings = AdaptivePollSettings {
+            enabled: true,
+            min_interval_ms: 250,
+            max_interval_ms: 1000,
+            slope_threshold: 1.0,
+        };
+        let mut poller = AdaptivePoller::default();
+        let start = Instant::now();
+
+        // Stable temperatures back off to the maximum
+        poller.observe("temp1", 40.0, start);
+        assert_eq!(poller.next_interval_ms(&settings, 100), 375);
+        poller.observe("temp1", 40.1, start + Duration::from_secs(1));
+        assert_eq!(poller.next_interval_ms(&settings, 100), 562);
+        for _ in 0..5 {
+            poller.next_interval_ms(&settings, 100);
+        }
+        assert_eq!(poller.next_interval_ms(&settings, 100), 1000);
+
+        // A fast rise drops straight to the minimum
+        poller.observe("temp1", 45.0, start + Duration::from_secs(2));
+        assert_eq!(poller.next_interval_ms(&settings, 100), 250);
+
+        let disabled = AdaptivePollSettings { enabled: false, ..settings };
+        assert_eq!(poller.next_interval_ms(&disabled, 100), 100);
+    }
+}
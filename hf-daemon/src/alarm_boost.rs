@@ -0,0 +1,118 @@
+//! Alarm Boost
+//!
+//! Super I/O and other hwmon chips raise `fanN_alarm` when a fan drops below
+//! its minimum speed and `tempN_alarm` when a temperature passes a limit
+//! programmed into the chip, often by the BIOS. With `general.alarm_boost`
+//! enabled, the control loop reads those flags every iteration and drives
+//! every controlled output at full speed while any is raised, and for
+//! `cooldown_secs` after the last one cleared.
+//!
+//! Intrusion alarms are listed with the hardware but never boost: they stay
+//! latched until cleared by hand and say nothing about cooling.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use hf_core::{AlarmBoostSettings, DaemonAlarmKind, HwmonChip};
+
+/// Alarm flags watched by the control loop and the boost they cause
+#[derive(Default)]
+pub struct AlarmBoost {
+    settings: AlarmBoostSettings,
+    /// ("chip/channel", `_alarm` file) of every fan and temperature alarm
+    flags: Vec<(String, PathBuf)>,
+    /// Flags raised at the last check
+    raised: Vec<String>,
+    /// Boosting until this time (while raised: until the alarms clear)
+    boost_until: Option<Instant>,
+}
+
+impl AlarmBoost {
+    /// Apply the settings and collect the alarm flags of `chips`
+    pub fn configure(&mut self, settings: AlarmBoostSettings, chips: &[HwmonChip]) {
+        self.settings = settings;
+        self.flags = if settings.enabled {
+            chips
+                .iter()
+                .flat_map(|chip| {
+                    chip.alarms
+                        .iter()
+                        .filter(|a| a.kind != DaemonAlarmKind::Intrusion)
+                        .map(|a| (format!("{}/{}", chip.name, a.name), a.alarm_path.clone()))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if settings.enabled {
+            info!("Alarm boost watching {} alarm flags (cool-down: {}s)", self.flags.len(), settings.cooldown_secs);
+        } else if self.boost_until.take().is_some() {
+            info!("ALARM: boost disabled - resuming curve control");
+        }
+        self.raised.retain(|name| self.flags.iter().any(|(flag, _)| flag == name));
+    }
+
+    /// Read every flag; returns whether outputs should run at full speed
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if self.flags.is_empty() && self.boost_until.is_none() {
+            return false;
+        }
+        let raised = self.flags
+            .iter()
+            .filter(|(_, path)| hf_core::read_alarm_flag(path))
+            .map(|(name, _)| name.clone())
+            .collect();
+        self.update(raised, now)
+    }
+
+    fn update(&mut self, raised: Vec<String>, now: Instant) -> bool {
+        let cooldown = Duration::from_secs(self.settings.cooldown_secs as u64);
+        if !raised.is_empty() {
+            if raised != self.raised {
+                warn!("ALARM: {} raised - fans at full speed", raised.join(", "));
+            }
+            self.boost_until = Some(now + cooldown);
+        } else if !self.raised.is_empty() {
+            info!("ALARM: {} cleared - full speed for another {}s", self.raised.join(", "), cooldown.as_secs());
+            self.boost_until = Some(now + cooldown);
+        }
+        self.raised = raised;
+
+        match self.boost_until {
+            Some(until) if now < until => true,
+            Some(_) => {
+                info!("ALARM: cool-down over - resuming curve control");
+                self.boost_until = None;
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boost_holds_for_cooldown() {
+        let mut boost = AlarmBoost {
+            settings: AlarmBoostSettings { enabled: true, cooldown_secs: 60 },
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let fan2 = || vec!["nct6798/fan2".to_string()];
+
+        assert!(!boost.update(Vec::new(), start));
+        assert!(boost.update(fan2(), start));
+        // Still raised well past the cool-down
+        assert!(boost.update(fan2(), start + Duration::from_secs(300)));
+
+        let cleared = start + Duration::from_secs(310);
+        assert!(boost.update(Vec::new(), cleared));
+        assert!(boost.update(Vec::new(), cleared + Duration::from_secs(59)));
+        assert!(!boost.update(Vec::new(), cleared + Duration::from_secs(60)));
+        assert!(boost.boost_until.is_none());
+    }
+}
@@ -0,0 +1,176 @@
+//! Control Write Audit Log
+//!
+//! Every request that changes fan behavior (PWM and GPU writes, EC writes,
+//! mode and profile changes, pairing edits, detection and calibration runs)
+//! is appended as one JSON line to `/var/log/hyperfan/audit.jsonl`, with the
+//! requesting peer's credentials and the outcome. Denied requests are
+//! recorded too.
+//!
+//! Unlike the journal's `AUDIT:` lines this file survives journal rotation
+//! and is easy to query (`jq 'select(.uid == 1001)'`). It is rotated by size,
+//! keeping `MAX_ROTATED_FILES` older files.
+
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::warn;
+
+use hf_core::constants::audit;
+use hf_protocol::{Request, Response};
+
+use crate::server::PeerCredentials;
+
+/// What happened to an audited request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Ok,
+    Failed,
+    Denied,
+}
+
+#[derive(Serialize)]
+struct AuditRecord<'a> {
+    timestamp_ms: u64,
+    request: &'a str,
+    /// Request parameters as sent by the client
+    params: serde_json::Value,
+    uid: u32,
+    gid: u32,
+    pid: i32,
+    outcome: Outcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+/// Append-only JSONL writer with size-based rotation
+struct AuditLog {
+    dir: PathBuf,
+    file: Option<File>,
+    /// Whether the last write failed (warn once, not on every request)
+    failing: bool,
+}
+
+static LOG: Mutex<Option<AuditLog>> = Mutex::new(None);
+
+/// Record a control request and its response (read-only requests are skipped)
+pub fn record(request: &Request, cred: &PeerCredentials, response: &Response) {
+    match response {
+        Response::Ok(_) => record_outcome(request, cred, Outcome::Ok, None),
+        Response::Error { message } => record_outcome(request, cred, Outcome::Failed, Some(message)),
+    }
+}
+
+/// Record a control request with an explicit outcome
+pub fn record_outcome(request: &Request, cred: &PeerCredentials, outcome: Outcome, error: Option<&str>) {
+    if request.is_read_only() {
+        return;
+    }
+    let record = AuditRecord {
+        timestamp_ms: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        request: request.type_name(),
+        params: serde_json::to_value(request)
+            .ok()
+            .and_then(|v| v.get("data").cloned())
+            .unwrap_or(serde_json::Value::Null),
+        uid: cred.uid,
+        gid: cred.gid,
+        pid: cred.pid,
+        outcome,
+        error,
+    };
+    let Ok(line) = serde_json::to_string(&record) else {
+        return;
+    };
+
+    let mut log = LOG.lock().unwrap_or_else(|e| e.into_inner());
+    let log = log.get_or_insert_with(|| AuditLog::new(Path::new(audit::AUDIT_LOG_DIR)));
+    match log.append(&line) {
+        Ok(()) => log.failing = false,
+        Err(e) => {
+            if !log.failing {
+                warn!("AUDIT: Failed to write audit log in {}: {}", log.dir.display(), e);
+            }
+            log.failing = true;
+            log.file = None;
+        }
+    }
+}
+
+impl AuditLog {
+    fn new(dir: &Path) -> Self {
+        Self { dir: dir.to_path_buf(), file: None, failing: false }
+    }
+
+    fn path(&self, index: u32) -> PathBuf {
+        match index {
+            0 => self.dir.join(audit::AUDIT_LOG_FILE),
+            n => self.dir.join(format!("{}.{}", audit::AUDIT_LOG_FILE, n)),
+        }
+    }
+
+    fn append(&mut self, line: &str) -> std::io::Result<()> {
+        let current = self.path(0);
+        let size = fs::metadata(&current).map(|m| m.len()).unwrap_or(0);
+        if size + line.len() as u64 + 1 > audit::MAX_FILE_SIZE && size > 0 {
+            self.rotate()?;
+        }
+
+        if self.file.is_none() || !current.exists() {
+            fs::create_dir_all(&self.dir)?;
+            self.file = Some(OpenOptions::new().create(true).append(true).open(&current)?);
+        }
+        if let Some(file) = self.file.as_mut() {
+            writeln!(file, "{}", line)?;
+            file.sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Shift audit.jsonl -> .1 -> .2 ..., dropping the oldest
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file = None;
+        let oldest = self.path(audit::MAX_ROTATED_FILES);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        for index in (0..audit::MAX_ROTATED_FILES).rev() {
+            let from = self.path(index);
+            if from.exists() {
+                fs::rename(&from, self.path(index + 1))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation() {
+        let dir = std::env::temp_dir().join(format!("hyperfan-audit-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut log = AuditLog::new(&dir);
+
+        log.append("{\"request\":\"SetPwm\"}").unwrap();
+        for _ in 0..=audit::MAX_ROTATED_FILES {
+            log.rotate().unwrap();
+            log.append("{\"request\":\"SetGlobalMode\"}").unwrap();
+        }
+
+        // The first entry has been rotated out; only MAX_ROTATED_FILES remain
+        assert!(log.path(audit::MAX_ROTATED_FILES).exists());
+        assert!(!log.path(audit::MAX_ROTATED_FILES + 1).exists());
+        for index in 0..=audit::MAX_ROTATED_FILES {
+            assert_eq!(fs::read_to_string(log.path(index)).unwrap(), "{\"request\":\"SetGlobalMode\"}\n");
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
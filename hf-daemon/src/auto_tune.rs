@@ -0,0 +1,248 @@
+//! Curve Auto-Tune Jobs
+//!
+//! `StartAutoTune` holds every output following a curve at a series of
+//! duties, highest first, while the user runs a steady load. Each step waits
+//! for the curve's temperature source to settle and records where it ended
+//! up; the job stops early once a step runs past the target, since lower
+//! duties would only run hotter. Clients poll `GetAutoTuneProgress` and, once
+//! the job has completed, review the suggested curve before saving it - the
+//! daemon never changes the saved curve itself.
+//!
+//! Outputs are held with rolling overrides, like calibration sweeps, so the
+//! control loop picks its curves back up as soon as the job ends.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use hf_core::constants::auto_tune;
+use hf_protocol::{AutoTuneProgress, AutoTuneStep, DetectionJobState, FanCalibration};
+
+use crate::fan_control::{FanControlState, PwmOverride};
+
+/// How long each hold outlives the sample it covers
+const OVERRIDE_GRACE: Duration = Duration::from_secs(3);
+
+/// The current (or last) auto-tune job
+#[derive(Default)]
+pub struct AutoTuneJobs {
+    next_id: u64,
+    current: Option<AutoTuneJob>,
+}
+
+struct AutoTuneJob {
+    cancel: Arc<AtomicBool>,
+    progress: Arc<Mutex<AutoTuneProgress>>,
+}
+
+impl AutoTuneJobs {
+    /// Whether a job is holding outputs
+    pub fn is_running(&self) -> bool {
+        self.current.as_ref().is_some_and(|job| snapshot(&job.progress).state == DetectionJobState::Running)
+    }
+
+    fn job(&self, job_id: u64) -> Result<&AutoTuneJob, String> {
+        self.current
+            .as_ref()
+            .filter(|job| snapshot(&job.progress).job_id == job_id)
+            .ok_or_else(|| format!("Unknown auto-tune job {}", job_id))
+    }
+}
+
+/// PWM output held during a job, with its calibration for duty conversion
+struct Output {
+    pwm_path: String,
+    calibration: Option<FanCalibration>,
+}
+
+impl Output {
+    fn pwm_value(&self, duty: f32) -> u8 {
+        match self.calibration.as_ref() {
+            Some(calibration) => hf_core::calibrated_pwm(calibration, duty),
+            None => (duty / 100.0 * 255.0).clamp(0.0, 255.0).round() as u8,
+        }
+    }
+}
+
+fn snapshot(progress: &Mutex<AutoTuneProgress>) -> AutoTuneProgress {
+    progress.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn update(progress: &Mutex<AutoTuneProgress>, f: impl FnOnce(&mut AutoTuneProgress)) {
+    f(&mut progress.lock().unwrap_or_else(|e| e.into_inner()));
+}
+
+/// Outputs following `curve_id` (directly or through a group) and the temperature source they follow
+async fn curve_outputs(state: &FanControlState, curve_id: &str) -> (Vec<Output>, Option<String>) {
+    let groups = state.groups.read().await;
+    let pairs = state.pairs.read().await;
+    let mut temp_source = None;
+    let outputs = pairs.values()
+        .filter(|runtime| match runtime.group.as_ref() {
+            Some(membership) => groups.get(&membership.group_id).is_some_and(|g| g.curve_id == curve_id),
+            None => runtime.curve_id == curve_id,
+        })
+        .map(|runtime| {
+            let source = match runtime.group.as_ref().and_then(|m| groups.get(&m.group_id)) {
+                Some(group) => &group.temp_source_path,
+                None => &runtime.pair.temp_source_path,
+            };
+            temp_source.get_or_insert_with(|| source.clone());
+            Output { pwm_path: runtime.pair.pwm_path.clone(), calibration: runtime.calibration.clone() }
+        })
+        .collect();
+    (outputs, temp_source)
+}
+
+/// Start auto-tuning `curve_id` unless a job, detection, or calibration is running
+pub async fn start(state: &Arc<FanControlState>, curve_id: String, target_temp: f32) -> Result<AutoTuneProgress, String> {
+    if state.detection.read().await.is_running() {
+        return Err("Fan detection is running".to_string());
+    }
+    if state.calibration.read().await.is_running() {
+        return Err("A fan calibration is running".to_string());
+    }
+    let (outputs, temp_source) = curve_outputs(state, &curve_id).await;
+    let Some(temp_source) = temp_source else {
+        return Err(format!("No active fans use curve {}", curve_id));
+    };
+
+    let mut jobs = state.auto_tune.write().await;
+    if let Some(job) = jobs.current.as_ref() {
+        let current = snapshot(&job.progress);
+        if current.state == DetectionJobState::Running {
+            return Err(format!("Auto-tune is already running (job {})", current.job_id));
+        }
+    }
+
+    jobs.next_id += 1;
+    let progress = Arc::new(Mutex::new(AutoTuneProgress {
+        job_id: jobs.next_id,
+        curve_id,
+        target_temp,
+        state: DetectionJobState::Running,
+        step: 0,
+        total_steps: auto_tune::DUTY_STEPS.len() as u32,
+        current_duty: None,
+        current_temp: None,
+        steps: Vec::new(),
+        points: Vec::new(),
+        error: None,
+    }));
+    let cancel = Arc::new(AtomicBool::new(false));
+    jobs.current = Some(AutoTuneJob { cancel: Arc::clone(&cancel), progress: Arc::clone(&progress) });
+    let started = snapshot(&progress);
+    drop(jobs);
+
+    info!("AUTO-TUNE: Job {} started for curve {} ({} outputs, target {:.0}°C from {})",
+          started.job_id, started.curve_id, outputs.len(), target_temp, temp_source);
+    let task_state = Arc::clone(state);
+    tokio::spawn(async move {
+        let outcome = run(&task_state, &outputs, &temp_source, target_temp, &cancel, &progress).await;
+        for output in &outputs {
+            task_state.clear_pwm_override(&output.pwm_path).await;
+        }
+
+        update(&progress, |p| {
+            p.current_duty = None;
+            match outcome.and_then(|()| hf_core::auto_tune_curve(&p.steps, target_temp).map_err(|e| e.to_string())) {
+                Ok(points) => {
+                    info!("AUTO-TUNE: Job {} suggests {:?}", p.job_id, points);
+                    p.state = DetectionJobState::Completed;
+                    p.points = points;
+                }
+                Err(e) if cancel.load(Ordering::Relaxed) => {
+                    info!("AUTO-TUNE: Job {} cancelled", p.job_id);
+                    p.state = DetectionJobState::Cancelled;
+                    p.error = Some(e);
+                }
+                Err(e) => {
+                    warn!("AUTO-TUNE: Job {} failed: {}", p.job_id, e);
+                    p.state = DetectionJobState::Failed;
+                    p.error = Some(e);
+                }
+            }
+        });
+    });
+
+    Ok(started)
+}
+
+/// Measure each duty step until one runs past the target
+async fn run(
+    state: &FanControlState,
+    outputs: &[Output],
+    temp_source: &str,
+    target_temp: f32,
+    cancel: &AtomicBool,
+    progress: &Mutex<AutoTuneProgress>,
+) -> Result<(), String> {
+    let window = (auto_tune::SETTLE_WINDOW.as_millis() / auto_tune::SAMPLE_INTERVAL.as_millis()) as usize + 1;
+
+    for (index, &duty) in auto_tune::DUTY_STEPS.iter().enumerate() {
+        update(progress, |p| {
+            p.step = index as u32 + 1;
+            p.current_duty = Some(duty);
+        });
+        let step_started = Instant::now();
+        let mut samples: VecDeque<f32> = VecDeque::with_capacity(window);
+
+        let step = loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Err("Cancelled".to_string());
+            }
+            let expires_at = Instant::now() + auto_tune::SAMPLE_INTERVAL + OVERRIDE_GRACE;
+            {
+                let mut overrides = state.pwm_overrides.write().await;
+                for output in outputs {
+                    overrides.insert(output.pwm_path.clone(), PwmOverride { value: output.pwm_value(duty), expires_at, set_by: None });
+                }
+            }
+            tokio::time::sleep(auto_tune::SAMPLE_INTERVAL).await;
+
+            let temp = crate::fan_control::read_temperature_async(temp_source).await?;
+            update(progress, |p| p.current_temp = Some(temp));
+            if samples.len() == window {
+                samples.pop_front();
+            }
+            samples.push_back(temp);
+
+            if temp >= target_temp + auto_tune::ABORT_MARGIN_C {
+                info!("AUTO-TUNE: {:.0}% reached {:.1}°C - not trying lower duties", duty, temp);
+                break AutoTuneStep { duty_percent: duty, temp, settled: false };
+            }
+            let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+            if samples.len() == window && hf_core::is_settled(samples.make_contiguous(), auto_tune::SETTLE_TOLERANCE_C) {
+                info!("AUTO-TUNE: {:.0}% settled at {:.1}°C", duty, mean);
+                break AutoTuneStep { duty_percent: duty, temp: mean, settled: true };
+            }
+            if step_started.elapsed() >= auto_tune::MAX_STEP {
+                warn!("AUTO-TUNE: {:.0}% did not settle - using {:.1}°C", duty, mean);
+                break AutoTuneStep { duty_percent: duty, temp: mean, settled: false };
+            }
+        };
+
+        let past_target = step.temp > target_temp;
+        update(progress, |p| p.steps.push(step));
+        if past_target {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Progress of `job_id`
+pub async fn progress(state: &FanControlState, job_id: u64) -> Result<AutoTuneProgress, String> {
+    let jobs = state.auto_tune.read().await;
+    jobs.job(job_id).map(|job| snapshot(&job.progress))
+}
+
+/// Ask `job_id` to stop; it reports `Cancelled` at its next reading
+pub async fn cancel(state: &FanControlState, job_id: u64) -> Result<AutoTuneProgress, String> {
+    let jobs = state.auto_tune.read().await;
+    let job = jobs.job(job_id)?;
+    job.cancel.store(true, Ordering::Relaxed);
+    Ok(snapshot(&job.progress))
+}
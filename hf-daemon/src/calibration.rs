@@ -0,0 +1,111 @@
+//! Fan Calibration Jobs
+//!
+//! `CalibrateFan` starts a PWM sweep on a blocking thread and returns straight
+//! away: the sweep takes about a minute, far longer than clients wait for a
+//! response, so they poll `GetFanCalibration` instead. While it runs the PWM
+//! is held with a rolling override so the control loop leaves it alone.
+//!
+//! The finished table is persisted in the binding store and the configuration
+//! is reloaded so pairs on that PWM pick it up.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use hf_core::constants::calibration;
+use hf_protocol::CalibrationStatus;
+
+use crate::fan_control::{FanControlState, PwmOverride};
+
+/// How long each step's override outlives the step
+const OVERRIDE_GRACE: Duration = Duration::from_secs(5);
+
+/// Running sweeps and the last failure per PWM path
+#[derive(Default)]
+pub struct CalibrationJobs {
+    running: HashSet<String>,
+    errors: HashMap<String, String>,
+}
+
+impl CalibrationJobs {
+    /// Whether any sweep is in progress
+    pub fn is_running(&self) -> bool {
+        !self.running.is_empty()
+    }
+}
+
+/// Start calibrating `pwm_path` against `fan_path`
+pub async fn start(state: &Arc<FanControlState>, pwm_path: String, fan_path: String) -> Result<CalibrationStatus, String> {
+    if !pwm_path.starts_with('/') {
+        return Err("Calibration needs a hwmon PWM output".to_string());
+    }
+    if state.detection.read().await.is_running() {
+        return Err("Fan detection is running".to_string());
+    }
+    if state.auto_tune.read().await.is_running() {
+        return Err("Curve auto-tuning is running".to_string());
+    }
+    {
+        let mut jobs = state.calibration.write().await;
+        if !jobs.running.insert(pwm_path.clone()) {
+            return Err(format!("Calibration of {} is already running", pwm_path));
+        }
+        jobs.errors.remove(&pwm_path);
+    }
+
+    let task_state = Arc::clone(state);
+    let task_pwm = pwm_path.clone();
+    tokio::spawn(async move {
+        let hold_state = Arc::clone(&task_state);
+        let hold_pwm = task_pwm.clone();
+        let sweep = tokio::task::spawn_blocking(move || {
+            hf_core::calibrate_fan(&PathBuf::from(&hold_pwm), &PathBuf::from(&fan_path), |value| {
+                let expires_at = Instant::now() + calibration::SPIN_UP + OVERRIDE_GRACE;
+                hold_state.pwm_overrides.blocking_write().insert(hold_pwm.clone(), PwmOverride { value, expires_at, set_by: None });
+            })
+        })
+        .await;
+        task_state.clear_pwm_override(&task_pwm).await;
+
+        let outcome = match sweep {
+            Ok(Ok(result)) => save(result),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(e) => Err(format!("Calibration task panicked: {}", e)),
+        };
+
+        let mut jobs = task_state.calibration.write().await;
+        jobs.running.remove(&task_pwm);
+        match outcome {
+            Ok(()) => {
+                info!("CALIBRATION: {} calibrated", task_pwm);
+                task_state.signal_reload();
+            }
+            Err(e) => {
+                warn!("CALIBRATION: {} failed: {}", task_pwm, e);
+                jobs.errors.insert(task_pwm, e);
+            }
+        }
+    });
+
+    Ok(status(state, &pwm_path).await)
+}
+
+/// Stored calibration of `pwm_path` and the state of its sweep
+pub async fn status(state: &FanControlState, pwm_path: &str) -> CalibrationStatus {
+    let jobs = state.calibration.read().await;
+    CalibrationStatus {
+        running: jobs.running.contains(pwm_path),
+        calibration: hf_core::BindingStore::load()
+            .ok()
+            .and_then(|store| store.calibration(pwm_path).cloned()),
+        error: jobs.errors.get(pwm_path).cloned(),
+    }
+}
+
+fn save(result: hf_protocol::FanCalibration) -> Result<(), String> {
+    let mut store = hf_core::BindingStore::load()?;
+    store.set_calibration(result);
+    store.save()
+}
@@ -0,0 +1,182 @@
+//! Other Fan Control Software
+//!
+//! fancontrol, NBFC, CoolerControl and similar daemons fight the control loop
+//! over the same outputs, and users hear fans hunting between two speeds.
+//! Two signs are watched:
+//!
+//! - A known fan control daemon among the running processes
+//!   (`hf_core::detect_competing_software`), checked at startup and on
+//!   `GetCompetingSoftware`.
+//! - A `pwmN_enable` mode that changed since the control loop last set it.
+//!   The loop re-asserts the mode on every write, so a flip (typically back to
+//!   automatic) means another writer.
+//!
+//! Both are logged with the service to disable; clients show them via
+//! `GetCompetingSoftware`.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use hf_protocol::{CompetingSoftwareReport, PwmEnableFlip};
+
+/// A flipped output is logged again at most this often
+const FLIP_LOG_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Flips older than this are no longer reported
+const FLIP_REPORT_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone, Copy)]
+struct EnableFlip {
+    mode: u8,
+    count: u32,
+    last_seen: Instant,
+    last_logged: Instant,
+}
+
+/// `pwmN_enable` modes the control loop set, and flips found since
+struct EnableWatch {
+    set_modes: BTreeMap<String, u8>,
+    flips: BTreeMap<String, EnableFlip>,
+}
+
+impl EnableWatch {
+    const fn new() -> Self {
+        Self { set_modes: BTreeMap::new(), flips: BTreeMap::new() }
+    }
+
+    /// Compare the mode found before a write with the one last set
+    /// Returns whether a flip should be logged
+    fn observe(&mut self, pwm_path: &str, current: u8, now: Instant) -> bool {
+        match self.set_modes.get(pwm_path) {
+            Some(&set) if set != current => {}
+            _ => return false,
+        }
+        match self.flips.get_mut(pwm_path) {
+            Some(flip) => {
+                flip.mode = current;
+                flip.count += 1;
+                flip.last_seen = now;
+                if now.duration_since(flip.last_logged) < FLIP_LOG_INTERVAL {
+                    return false;
+                }
+                flip.last_logged = now;
+            }
+            None => {
+                let flip = EnableFlip { mode: current, count: 1, last_seen: now, last_logged: now };
+                self.flips.insert(pwm_path.to_string(), flip);
+            }
+        }
+        true
+    }
+
+    fn record(&mut self, pwm_path: &str, mode: u8) {
+        self.set_modes.insert(pwm_path.to_string(), mode);
+    }
+
+    fn recent_flips(&self, now: Instant) -> Vec<PwmEnableFlip> {
+        self.flips
+            .iter()
+            .filter(|(_, flip)| now.duration_since(flip.last_seen) < FLIP_REPORT_WINDOW)
+            .map(|(path, flip)| PwmEnableFlip {
+                pwm_path: path.clone(),
+                mode: flip.mode,
+                count: flip.count,
+                last_seen_secs_ago: now.duration_since(flip.last_seen).as_secs(),
+            })
+            .collect()
+    }
+}
+
+static WATCH: Mutex<EnableWatch> = Mutex::new(EnableWatch::new());
+
+/// Check the `pwmN_enable` mode read before a write against the one last set
+pub fn check_enable_mode(pwm_path: &str, current: u8) {
+    let log = WATCH.lock().map(|mut w| w.observe(pwm_path, current, Instant::now())).unwrap_or(false);
+    if !log {
+        return;
+    }
+    let running: Vec<_> = hf_core::detect_competing_software().iter().map(|d| d.software.name).collect();
+    if running.is_empty() {
+        warn!(
+            "CONFLICT: {} enable mode was changed to {} by another program - re-asserting manual control",
+            pwm_path, current
+        );
+    } else {
+        warn!(
+            "CONFLICT: {} enable mode was changed to {} by another program (running: {}) - re-asserting manual control",
+            pwm_path,
+            current,
+            running.join(", ")
+        );
+    }
+}
+
+/// Remember the `pwmN_enable` mode the control loop left `pwm_path` in
+pub fn record_enable_mode(pwm_path: &str, mode: u8) {
+    if let Ok(mut watch) = WATCH.lock() {
+        watch.record(pwm_path, mode);
+    }
+}
+
+/// Forget the mode of `pwm_path` after the daemon itself changed it outside the loop
+pub fn forget_enable_mode(pwm_path: &str) {
+    if let Ok(mut watch) = WATCH.lock() {
+        watch.set_modes.remove(pwm_path);
+    }
+}
+
+/// Forget every mode, e.g. after the original state was restored
+pub fn forget_enable_modes() {
+    if let Ok(mut watch) = WATCH.lock() {
+        watch.set_modes.clear();
+    }
+}
+
+/// Running fan control software and recent enable flips
+pub fn report() -> CompetingSoftwareReport {
+    let software = hf_core::detect_competing_software().into_iter().map(Into::into).collect();
+    let enable_flips = WATCH.lock().map(|w| w.recent_flips(Instant::now())).unwrap_or_default();
+    CompetingSoftwareReport { software, enable_flips }
+}
+
+/// Log running fan control software before the control loop starts
+pub fn warn_at_startup() {
+    for detected in hf_core::detect_competing_software() {
+        warn!(
+            "CONFLICT: {} is running (pid {:?}) and will fight Hyperfan over the fans - disable it: {}",
+            detected.software.name,
+            detected.pids,
+            detected.disable_hint()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enable_flip_detection() {
+        let mut watch = EnableWatch::new();
+        let start = Instant::now();
+        let path = "/sys/class/hwmon/hwmon2/pwm1";
+
+        // Nothing set yet, then the loop's own mode
+        assert!(!watch.observe(path, 2, start));
+        watch.record(path, 1);
+        assert!(!watch.observe(path, 1, start));
+
+        // Flipped back to automatic: logged once per interval
+        assert!(watch.observe(path, 2, start));
+        watch.record(path, 1);
+        assert!(!watch.observe(path, 2, start + Duration::from_secs(5)));
+        assert!(watch.observe(path, 2, start + FLIP_LOG_INTERVAL + Duration::from_secs(5)));
+
+        let flips = watch.recent_flips(start + FLIP_LOG_INTERVAL + Duration::from_secs(5));
+        assert_eq!(flips.len(), 1);
+        assert_eq!((flips[0].mode, flips[0].count), (2, 3));
+        assert!(watch.recent_flips(start + FLIP_REPORT_WINDOW * 2).is_empty());
+    }
+}
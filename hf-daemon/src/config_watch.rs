@@ -0,0 +1,438 @@
+//! Config File Watcher
+//!
+//! Watches the system config directory (`/etc/hyperfan`) and the user config
+//! directory the daemon loads from with inotify, so settings and curves edited
+//! by hand or by configuration management apply without a `ReloadConfig`.
+//!
+//! Editors and config management write in several steps (temp file, rename,
+//! chmod), so the control loop waits until no event has arrived for the
+//! settle delay. It then loads the files and checks them with
+//! `config_problems`. If they fail to load or add a problem the applied config
+//! did not have, every file is put back to its last good contents (the
+//! rejected edit is kept beside it as `<file>.rejected`) and the running
+//! config stays. Otherwise the daemon reloads and announces `ConfigChanged`
+//! for the sections that differ.
+//!
+//! Changes made through the daemon (pairing requests, `ReloadConfig`) are
+//! taken as applied when the request succeeds, so they are not announced
+//! twice. Directories that don't exist when the daemon starts are not watched.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use hf_core::ConfigFormat;
+use hf_protocol::ConfigSection;
+use serde_json::Value;
+
+use crate::fan_control::FanControlState;
+
+/// Quiet period after the last file event before loading the new config
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Config files that are watched, by stem (each in every config format)
+const WATCHED_STEMS: &[&str] = &["settings", "curves"];
+
+/// Settings key holding the PWM-to-fan pairings (its own config section)
+const PAIRINGS_KEY: &str = "pwm_fan_pairings";
+
+/// Suffix of the copy kept of a rejected edit
+const REJECTED_SUFFIX: &str = ".rejected";
+
+/// Whether `name` is a watched config file ("settings.toml", not "settings.toml.tmp")
+pub fn is_config_file(name: &str) -> bool {
+    name.rsplit_once('.').is_some_and(|(stem, extension)| {
+        WATCHED_STEMS.contains(&stem) && ConfigFormat::ALL.iter().any(|f| f.extension() == extension)
+    })
+}
+
+/// Config files and what they load to, as last applied
+#[derive(Debug, Clone)]
+struct Snapshot {
+    /// Contents of every watched file path (None: file absent)
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+    settings: Value,
+    curves: Value,
+    /// What `config_problems` reported; existing problems don't block later edits
+    problems: Vec<String>,
+}
+
+impl Snapshot {
+    fn capture(dirs: &[PathBuf]) -> Result<Self, String> {
+        hf_core::invalidate_settings_cache();
+        let settings = hf_core::load_settings().map_err(|e| e.to_string())?;
+        let curves = hf_core::load_curves().map_err(|e| e.to_string())?;
+        let problems = hf_core::diagnostics::config_problems(&settings, &curves);
+
+        // Read after loading, which may have migrated and rewritten the files
+        let files = config_paths(dirs)
+            .into_iter()
+            .map(|path| {
+                let contents = fs::read(&path).ok();
+                (path, contents)
+            })
+            .collect();
+        Ok(Self {
+            files,
+            settings: serde_json::to_value(&settings).map_err(|e| e.to_string())?,
+            curves: serde_json::to_value(&curves).map_err(|e| e.to_string())?,
+            problems,
+        })
+    }
+}
+
+fn config_paths(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    dirs.iter()
+        .flat_map(|dir| {
+            WATCHED_STEMS.iter().flat_map(move |stem| {
+                ConfigFormat::ALL.iter().map(move |f| dir.join(format!("{}.{}", stem, f.extension())))
+            })
+        })
+        .collect()
+}
+
+/// Section that differs between two loaded configs (`All` if more than one)
+fn changed_section(old: &Snapshot, new: &Snapshot) -> Option<ConfigSection> {
+    let split = |settings: &Value| {
+        let mut rest = settings.clone();
+        let pairings = rest.as_object_mut().and_then(|map| map.remove(PAIRINGS_KEY));
+        (rest, pairings)
+    };
+    let (old_settings, old_pairings) = split(&old.settings);
+    let (new_settings, new_pairings) = split(&new.settings);
+
+    let mut changed = [
+        (old_settings != new_settings, ConfigSection::Settings),
+        (old.curves != new.curves, ConfigSection::Curves),
+        (old_pairings != new_pairings, ConfigSection::Pairings),
+    ]
+    .into_iter()
+    .filter_map(|(changed, section)| changed.then_some(section));
+    match (changed.next(), changed.next()) {
+        (None, _) => None,
+        (Some(section), None) => Some(section),
+        (Some(_), Some(_)) => Some(ConfigSection::All),
+    }
+}
+
+/// Put every file back to its contents in `good`, keeping edits as `<file>.rejected`
+fn roll_back(good: &Snapshot) {
+    for (path, contents) in &good.files {
+        let current = fs::read(path).ok();
+        if current == *contents {
+            continue;
+        }
+        if let Some(current) = current {
+            let mut rejected = path.clone().into_os_string();
+            rejected.push(REJECTED_SUFFIX);
+            if let Err(e) = fs::write(&rejected, current) {
+                warn!("CONFIG: Failed to keep rejected {:?}: {}", rejected, e);
+            }
+        }
+        let restored = match contents {
+            Some(contents) => fs::write(path, contents),
+            None => fs::remove_file(path),
+        };
+        match restored {
+            Ok(()) => info!("CONFIG: Restored {:?}", path),
+            Err(e) => warn!("CONFIG: Failed to restore {:?}: {}", path, e),
+        }
+    }
+    hf_core::invalidate_settings_cache();
+}
+
+/// What a settled batch of file events led to
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    /// The files load to the applied config (a save already reloaded, a comment edit)
+    Unchanged,
+    Applied(ConfigSection),
+    /// Rolled back, with the reason
+    Rejected(String),
+}
+
+/// File event state shared between the inotify listener and the control loop
+pub struct ConfigWatch {
+    /// Watched directories (empty until the listener starts)
+    dirs: Vec<PathBuf>,
+    /// Config last applied
+    good: Option<Snapshot>,
+    /// Time of the last file event not yet acted on
+    pending_since: Option<Instant>,
+}
+
+impl ConfigWatch {
+    pub fn new() -> Self {
+        Self { dirs: Vec::new(), good: None, pending_since: None }
+    }
+
+    /// Note a file event and schedule a load
+    pub fn record(&mut self, now: Instant) {
+        self.pending_since = Some(now);
+    }
+
+    /// Whether file events have settled and should be acted on
+    /// Clears the pending state when it returns true
+    pub fn take_settled(&mut self, now: Instant) -> bool {
+        match self.pending_since {
+            Some(at) if now.duration_since(at) >= SETTLE_DELAY => {
+                self.pending_since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Take the config on disk as applied (it was changed through the daemon)
+    pub fn accept_current(&mut self) {
+        if self.dirs.is_empty() {
+            return;
+        }
+        match Snapshot::capture(&self.dirs) {
+            Ok(snapshot) => self.good = Some(snapshot),
+            Err(e) => debug!("CONFIG: Keeping the previous good config: {}", e),
+        }
+    }
+
+    fn apply(&mut self) -> Outcome {
+        let current = Snapshot::capture(&self.dirs);
+        let Some(good) = &self.good else {
+            // Nothing good to return to (the config was broken at startup)
+            return match current {
+                Ok(current) => {
+                    self.good = Some(current);
+                    Outcome::Applied(ConfigSection::All)
+                }
+                Err(e) => Outcome::Rejected(e),
+            };
+        };
+
+        let current = match current {
+            Ok(current) => current,
+            Err(e) => {
+                roll_back(good);
+                return Outcome::Rejected(e);
+            }
+        };
+        let added: Vec<&String> = current.problems.iter().filter(|p| !good.problems.contains(p)).collect();
+        if !added.is_empty() {
+            roll_back(good);
+            return Outcome::Rejected(added.iter().map(|p| p.as_str()).collect::<Vec<_>>().join("; "));
+        }
+
+        let section = changed_section(good, &current);
+        self.good = Some(current);
+        section.map_or(Outcome::Unchanged, Outcome::Applied)
+    }
+}
+
+impl Default for ConfigWatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Apply or roll back config edits once file events have settled (call from control loop)
+pub async fn tick(state: &FanControlState) {
+    let outcome = {
+        let mut watch = state.config_watch.write().await;
+        if !watch.take_settled(Instant::now()) {
+            return;
+        }
+        watch.apply()
+    };
+
+    match outcome {
+        Outcome::Unchanged => debug!("CONFIG: Files changed but load to the applied config"),
+        Outcome::Applied(section) => {
+            info!("CONFIG: {:?} edited on disk - reloading", section);
+            state.signal_reload();
+            crate::events::publish_config_changed(section);
+        }
+        Outcome::Rejected(reason) => {
+            warn!("CONFIG: Edit rejected, running config kept: {}", reason);
+        }
+    }
+}
+
+/// Start the inotify listener thread
+/// Watching is skipped (with a warning) if inotify is unavailable
+pub fn spawn_listener(state: Arc<FanControlState>) {
+    let mut dirs: Vec<PathBuf> = std::iter::once(PathBuf::from(hf_core::constants::paths::CONFIG_DIR))
+        .chain(hf_core::constants::paths::get_resolved_config_path())
+        .filter(|dir| dir.is_dir())
+        .collect();
+    dirs.dedup();
+    if dirs.is_empty() {
+        info!("CONFIG: No config directory exists yet - edits need ReloadConfig");
+        return;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let fd = match open_inotify(&dirs) {
+            Ok(fd) => fd,
+            Err(e) => {
+                warn!("CONFIG: Cannot watch config files ({}) - edits need ReloadConfig", e);
+                return;
+            }
+        };
+
+        let spawned = std::thread::Builder::new()
+            .name("hf-config-watch".to_string())
+            .spawn(move || {
+                {
+                    let mut watch = state.config_watch.blocking_write();
+                    watch.dirs = dirs;
+                    watch.accept_current();
+                }
+                run_listener(fd, state)
+            });
+        match spawned {
+            Ok(_) => info!("CONFIG: Watching config files for edits"),
+            Err(e) => warn!("CONFIG: Failed to start watcher thread: {}", e),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = state;
+        debug!("CONFIG: inotify is only available on Linux");
+    }
+}
+
+/// Whether an inotify read names a watched file (or lost events to an overflow)
+#[cfg(target_os = "linux")]
+pub fn touches_config(buf: &[u8]) -> bool {
+    let header = std::mem::size_of::<libc::inotify_event>();
+    let mut offset = 0;
+    while offset + header <= buf.len() {
+        // SAFETY: the header lies within buf; read_unaligned has no alignment requirement.
+        let event = unsafe { std::ptr::read_unaligned(buf[offset..].as_ptr() as *const libc::inotify_event) };
+        if event.mask & libc::IN_Q_OVERFLOW != 0 {
+            return true;
+        }
+        let name_start = offset + header;
+        let name_end = (name_start + event.len as usize).min(buf.len());
+        // The name is NUL-padded
+        let name = buf[name_start..name_end].split(|&b| b == 0).next().unwrap_or_default();
+        if std::str::from_utf8(name).is_ok_and(is_config_file) {
+            return true;
+        }
+        offset = name_end;
+    }
+    false
+}
+
+#[cfg(target_os = "linux")]
+fn open_inotify(dirs: &[PathBuf]) -> std::io::Result<std::os::fd::OwnedFd> {
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::os::unix::ffi::OsStrExt;
+
+    // SAFETY: inotify_init1() has no memory-safety preconditions; the result is checked before use.
+    let raw = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+    if raw < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: raw is a valid, newly created descriptor owned by nothing else.
+    let fd = unsafe { OwnedFd::from_raw_fd(raw) };
+
+    // Written in place, renamed over (editors, atomic saves) or removed
+    let mask = libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO | libc::IN_MOVED_FROM | libc::IN_DELETE;
+    for dir in dirs {
+        let path = std::ffi::CString::new(dir.as_os_str().as_bytes())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        // SAFETY: path is a valid NUL-terminated string for the duration of the call.
+        if unsafe { libc::inotify_add_watch(fd.as_raw_fd(), path.as_ptr(), mask) } < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        debug!("CONFIG: Watching {:?}", dir);
+    }
+    Ok(fd)
+}
+
+#[cfg(target_os = "linux")]
+fn run_listener(fd: std::os::fd::OwnedFd, state: Arc<FanControlState>) {
+    use std::os::fd::AsRawFd;
+
+    // Room for many events with names up to NAME_MAX
+    let mut buf = vec![0u8; 16 * (std::mem::size_of::<libc::inotify_event>() + 256)];
+    loop {
+        // SAFETY: buf is valid for writes of buf.len() bytes for the duration of the call.
+        let len = unsafe { libc::read(fd.as_raw_fd(), buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+        if len < 0 {
+            let err = std::io::Error::last_os_error();
+            if err.kind() != std::io::ErrorKind::Interrupted {
+                warn!("CONFIG: inotify read failed ({}) - config watching stopped", err);
+                return;
+            }
+            continue;
+        }
+
+        if touches_config(&buf[..len as usize]) {
+            debug!("CONFIG: Config file changed on disk");
+            state.config_watch.blocking_write().record(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn snapshot(settings: Value, curves: Value) -> Snapshot {
+        Snapshot { files: Vec::new(), settings, curves, problems: Vec::new() }
+    }
+
+    #[test]
+    fn test_is_config_file() {
+        assert!(is_config_file("settings.json"));
+        assert!(is_config_file("curves.toml"));
+        assert!(!is_config_file("settings.json.tmp"));
+        assert!(!is_config_file("settings.json.rejected"));
+        assert!(!is_config_file("daemon.json"));
+        assert!(!is_config_file("settings"));
+    }
+
+    #[test]
+    fn test_changed_section() {
+        let base = snapshot(json!({ "active_profile": "Normal", PAIRINGS_KEY: [] }), json!({ "curves": {} }));
+        assert_eq!(changed_section(&base, &base.clone()), None);
+
+        let profile = snapshot(json!({ "active_profile": "Quiet", PAIRINGS_KEY: [] }), base.curves.clone());
+        assert_eq!(changed_section(&base, &profile), Some(ConfigSection::Settings));
+
+        let paired = snapshot(json!({ "active_profile": "Normal", PAIRINGS_KEY: [{ "pwm_path": "pwm1" }] }), base.curves.clone());
+        assert_eq!(changed_section(&base, &paired), Some(ConfigSection::Pairings));
+
+        let curves = snapshot(base.settings.clone(), json!({ "curves": { "quiet": {} } }));
+        assert_eq!(changed_section(&base, &curves), Some(ConfigSection::Curves));
+        assert_eq!(changed_section(&profile, &curves), Some(ConfigSection::All));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_touches_config() {
+        fn event(mask: u32, name: &str) -> Vec<u8> {
+            // Names are NUL-padded to a multiple of the header alignment
+            let len = (name.len() + 1).next_multiple_of(4);
+            let mut buf = Vec::new();
+            buf.extend_from_slice(&1i32.to_ne_bytes());
+            buf.extend_from_slice(&mask.to_ne_bytes());
+            buf.extend_from_slice(&0u32.to_ne_bytes());
+            buf.extend_from_slice(&(len as u32).to_ne_bytes());
+            buf.extend_from_slice(name.as_bytes());
+            buf.resize(buf.len() + len - name.len(), 0);
+            buf
+        }
+
+        let temp = event(libc::IN_CLOSE_WRITE, "settings.json.tmp");
+        assert!(!touches_config(&temp));
+        assert!(touches_config(&[temp.clone(), event(libc::IN_MOVED_TO, "settings.json")].concat()));
+        assert!(!touches_config(&event(libc::IN_DELETE, "daemon.json")));
+        assert!(touches_config(&event(libc::IN_Q_OVERFLOW, "")));
+    }
+}
@@ -0,0 +1,411 @@
+//! D-Bus Control Interface
+//!
+//! Exposes the daemon on the system bus as `io.github.hyperfan1` so scripts,
+//! desktop widgets, and third-party tools can integrate without implementing
+//! the socket protocol.
+//!
+//! # Interface
+//! Object `/io/github/hyperfan1`, interface `io.github.hyperfan1.Daemon`:
+//! - `Version() -> s`
+//! - `ListHardware() -> s` (JSON-encoded `HardwareInfo`)
+//! - `GetGlobalMode() -> s` (`manual` while a PWM override is active, else `auto`)
+//! - `GetStatus() -> s` (JSON status document, see below)
+//! - `GetActiveProfile() -> (s active, as available)`
+//! - `SetActiveProfile(s name)`
+//! - `SetPwm(s path, y value)`
+//! - `ReloadConfig()`
+//! - `ReloadConfigSection(s section)` (`settings`, `curves` or `pairings`)
+//!
+//! Callers are subject to the same client tiers as socket clients, which
+//! alone decide the read methods. `SetActiveProfile`/`SetPwm`/`ReloadConfig`/
+//! `ReloadConfigSection` also require the polkit `io.github.hyperfan1.control`
+//! action.
+//!
+//! # Stability
+//! This interface is what desktop integrations (such as the Plasma applet in
+//! `plasma/`) build on. Methods and their signatures are not changed or
+//! removed under this interface name; new methods may be added. `GetStatus`
+//! returns one object in a single call, for widgets that poll:
+//!
+//! ```json
+//! {
+//!   "format": 1,
+//!   "profile": "normal",
+//!   "profiles": ["quiet", "normal", "performance"],
+//!   "temperatures": [{ "path": "/sys/class/hwmon/hwmon2/temp1_input", "label": "k10temp Tctl", "celsius": 45.5 }],
+//!   "fans": [{ "path": "/sys/class/hwmon/hwmon3/fan1_input", "label": "nct6798 fan1", "rpm": 820 }]
+//! }
+//! ```
+//!
+//! Fields may be added to the document; `format` is raised only when an
+//! existing field changes meaning. `rpm` is `null` for a fan that cannot be read.
+//!
+//! Every call is translated into a protocol [`Request`] and executed through the
+//! same dispatch as the Unix socket (validation, rate limiting, audit logging).
+//! The bus connection runs on its own thread; calls are handed to the async
+//! runtime over a bounded channel.
+//!
+//! Built only with the `dbus` cargo feature.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use dbus::blocking::LocalConnection;
+use dbus::{Message, MethodErr};
+use dbus_tree::Factory;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tracing::{error, info, warn};
+
+use hf_protocol::{GlobalMode, HardwareInfo, ProfileInfo, Request, Response, ResponseData};
+
+use crate::polkit;
+use crate::rate_limit::RateLimiter;
+use crate::server::PeerCredentials;
+
+/// Well-known bus name
+pub const BUS_NAME: &str = "io.github.hyperfan1";
+
+const OBJECT_PATH: &str = "/io/github/hyperfan1";
+const INTERFACE: &str = "io.github.hyperfan1.Daemon";
+
+const ERROR_FAILED: &str = "io.github.hyperfan1.Error.Failed";
+const ERROR_NOT_AUTHORIZED: &str = "io.github.hyperfan1.Error.NotAuthorized";
+
+/// Timeout for calls to the bus daemon (credential lookups)
+const BUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum calls queued for the async runtime
+const MAX_PENDING_CALLS: usize = 16;
+
+/// Senders whose polkit decisions are remembered (the cache starts over when full)
+const MAX_CACHED_SENDERS: usize = 64;
+
+/// Version of the `GetStatus` document
+const STATUS_FORMAT: u32 = 1;
+
+/// Document returned by `GetStatus`
+#[derive(serde::Serialize)]
+struct Status {
+    format: u32,
+    profile: String,
+    profiles: Vec<String>,
+    temperatures: Vec<StatusTemperature>,
+    fans: Vec<StatusFan>,
+}
+
+#[derive(serde::Serialize)]
+struct StatusTemperature {
+    path: String,
+    label: String,
+    celsius: f32,
+}
+
+#[derive(serde::Serialize)]
+struct StatusFan {
+    path: String,
+    label: String,
+    rpm: Option<u32>,
+}
+
+impl Status {
+    fn new(hardware: &HardwareInfo, profile: ProfileInfo) -> Self {
+        let label = |chip: &str, name: &str, label: &Option<String>| {
+            format!("{} {}", chip, label.as_deref().unwrap_or(name))
+        };
+        Self {
+            format: STATUS_FORMAT,
+            profile: profile.active,
+            profiles: profile.available,
+            temperatures: hardware.chips.iter()
+                .flat_map(|chip| chip.temperatures.iter().map(move |t| StatusTemperature {
+                    path: t.path.clone(),
+                    label: label(&chip.name, &t.name, &t.label),
+                    celsius: t.value,
+                }))
+                .collect(),
+            fans: hardware.chips.iter()
+                .flat_map(|chip| chip.fans.iter().map(move |f| StatusFan {
+                    path: f.path.clone(),
+                    label: label(&chip.name, &f.name, &f.label),
+                    rpm: f.rpm,
+                }))
+                .collect(),
+        }
+    }
+}
+
+/// A D-Bus method call forwarded to the async runtime
+struct DbusCall {
+    request: Request,
+    cred: PeerCredentials,
+    reply: oneshot::Sender<Response>,
+}
+
+/// Start the D-Bus interface
+/// Failure to claim the bus name is logged; the Unix socket keeps working
+pub fn start(
+    fan_control_state: Arc<crate::fan_control::FanControlState>,
+    rate_limiter: Arc<Mutex<RateLimiter>>,
+) {
+    let (tx, mut rx) = mpsc::channel::<DbusCall>(MAX_PENDING_CALLS);
+
+    tokio::spawn(async move {
+        while let Some(call) = rx.recv().await {
+            let allowed = rate_limiter.lock().await.check(call.cred.uid, call.request.class());
+            let response = if allowed {
+                crate::server::dispatch_request(call.request, 0, &call.cred, &fan_control_state, &rate_limiter).await
+            } else {
+                warn!("Rate limit exceeded for D-Bus uid={}, pid={}", call.cred.uid, call.cred.pid);
+                Response::error("Rate limit exceeded")
+            };
+            let _ = call.reply.send(response);
+        }
+    });
+
+    let spawned = std::thread::Builder::new()
+        .name("hyperfand-dbus".to_string())
+        .spawn(move || {
+            if let Err(e) = run_bus(tx) {
+                warn!("D-Bus interface unavailable: {}", e);
+            }
+        });
+    if let Err(e) = spawned {
+        error!("Failed to start D-Bus thread: {}", e);
+    }
+}
+
+/// Forwards calls from the bus thread into the daemon
+struct Bridge {
+    tx: mpsc::Sender<DbusCall>,
+    /// Separate connection for credential lookups made from inside method handlers
+    bus: LocalConnection,
+    /// Polkit decisions by unique sender name (never reused on a bus)
+    auth: RefCell<HashMap<String, polkit::ClientAuthorization>>,
+}
+
+impl Bridge {
+    /// Authorize the caller with polkit and execute the request
+    fn call(&self, msg: &Message, request: Request) -> Result<ResponseData, MethodErr> {
+        let cred = self.authorize(msg, std::slice::from_ref(&request))?;
+        self.dispatch(request, cred)
+    }
+
+    /// Check the caller's tier and polkit once for all requests of a method call
+    fn authorize(&self, msg: &Message, requests: &[Request]) -> Result<PeerCredentials, MethodErr> {
+        let sender = msg.sender().ok_or_else(|| MethodErr::failed("Message has no sender"))?;
+        let cred = self.peer_credentials(&sender)?;
+
+        let tier = crate::permissions::DaemonConfig::load().tier_for(cred.uid, cred.gid, cred.pid);
+        for request in requests {
+            if !tier.allows(request) {
+                let reason = tier.description();
+                warn!(
+                    "AUDIT: D-Bus {} denied for {} uid={}, pid={}",
+                    request.type_name(), reason, cred.uid, cred.pid
+                );
+                return Err((ERROR_NOT_AUTHORIZED, format!("Permission denied: {}", reason)).into());
+            }
+
+            if let Some(action) = polkit::action_for(request) {
+                if !self.check_polkit(&sender, &cred, action) {
+                    warn!(
+                        "AUDIT: D-Bus {} denied for uid={}, pid={} (polkit {})",
+                        request.type_name(), cred.uid, cred.pid, action
+                    );
+                    return Err((ERROR_NOT_AUTHORIZED, format!("Not authorized: {}", action)).into());
+                }
+            }
+        }
+        Ok(cred)
+    }
+
+    /// Execute an authorized request on the async runtime
+    fn dispatch(&self, request: Request, cred: PeerCredentials) -> Result<ResponseData, MethodErr> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .blocking_send(DbusCall { request, cred, reply: reply_tx })
+            .map_err(|_| MethodErr::failed("Daemon is shutting down"))?;
+
+        match reply_rx.blocking_recv() {
+            Ok(Response::Ok(data)) => Ok(data),
+            Ok(Response::Error { message }) => Err((ERROR_FAILED, message).into()),
+            Err(_) => Err(MethodErr::failed("Request was dropped")),
+        }
+    }
+
+    /// Check `action` for a sender, reusing earlier grants and denials the way
+    /// socket connections do
+    fn check_polkit(&self, sender: &str, cred: &PeerCredentials, action: &'static str) -> bool {
+        let mut cache = self.auth.borrow_mut();
+        let (pid, uid) = (cred.pid as u32, cred.uid);
+        if !cache.get(sender).is_some_and(|auth| auth.is_for(pid, uid)) {
+            if cache.len() >= MAX_CACHED_SENDERS {
+                cache.clear();
+            }
+            cache.insert(sender.to_string(), polkit::ClientAuthorization::new(pid, uid));
+        }
+        cache.get_mut(sender).is_some_and(|auth| auth.check_blocking(action))
+    }
+
+    /// Look up the caller's UID and PID from the bus daemon
+    fn peer_credentials(&self, sender: &str) -> Result<PeerCredentials, MethodErr> {
+        let proxy = self.bus.with_proxy("org.freedesktop.DBus", "/org/freedesktop/DBus", BUS_TIMEOUT);
+        let (uid,): (u32,) = proxy.method_call("org.freedesktop.DBus", "GetConnectionUnixUser", (sender,))?;
+        let (pid,): (u32,) = proxy.method_call("org.freedesktop.DBus", "GetConnectionUnixProcessID", (sender,))?;
+
+        Ok(PeerCredentials {
+            uid,
+            gid: primary_gid(pid).unwrap_or(u32::MAX),
+            pid: pid as i32,
+        })
+    }
+}
+
+/// Real GID of a process (from /proc/<pid>/status)
+fn primary_gid(pid: u32) -> Option<u32> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Gid:"))
+        .and_then(|ids| ids.split_whitespace().next())
+        .and_then(|gid| gid.parse().ok())
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, MethodErr> {
+    serde_json::to_string(value).map_err(|e| MethodErr::failed(&e))
+}
+
+/// Claim the bus name and serve method calls until the connection fails
+fn run_bus(tx: mpsc::Sender<DbusCall>) -> Result<(), dbus::Error> {
+    let conn = LocalConnection::new_system()?;
+    conn.request_name(BUS_NAME, false, true, true)?;
+
+    let bridge = Rc::new(Bridge {
+        tx,
+        bus: LocalConnection::new_system()?,
+        auth: RefCell::new(HashMap::new()),
+    });
+    let f = Factory::new_fn::<()>();
+
+    let version = {
+        let bridge = bridge.clone();
+        f.method("Version", (), move |m| {
+            let data = bridge.call(m.msg, Request::Version)?;
+            Ok(vec![m.msg.method_return().append1(data.value.unwrap_or_default())])
+        })
+        .outarg::<&str, _>("version")
+    };
+
+    let list_hardware = {
+        let bridge = bridge.clone();
+        f.method("ListHardware", (), move |m| {
+            let data = bridge.call(m.msg, Request::ListHardware)?;
+            Ok(vec![m.msg.method_return().append1(to_json(&data.hardware)?)])
+        })
+        .outarg::<&str, _>("hardware_json")
+    };
+
+    let get_global_mode = {
+        let bridge = bridge.clone();
+        f.method("GetGlobalMode", (), move |m| {
+            let data = bridge.call(m.msg, Request::GetGlobalMode)?;
+            let mode = match data.global_mode {
+                Some(GlobalMode::Manual) => "manual",
+                _ => "auto",
+            };
+            Ok(vec![m.msg.method_return().append1(mode)])
+        })
+        .outarg::<&str, _>("mode")
+    };
+
+    let get_status = {
+        let bridge = bridge.clone();
+        f.method("GetStatus", (), move |m| {
+            let cred = bridge.authorize(m.msg, &[Request::ListHardware, Request::GetActiveProfile])?;
+            let hardware = bridge.dispatch(Request::ListHardware, cred)?.hardware
+                .ok_or_else(|| MethodErr::failed("Daemon returned no hardware"))?;
+            let profile = bridge.dispatch(Request::GetActiveProfile, cred)?.profile
+                .ok_or_else(|| MethodErr::failed("Daemon returned no profile"))?;
+            Ok(vec![m.msg.method_return().append1(to_json(&Status::new(&hardware, profile))?)])
+        })
+        .outarg::<&str, _>("status_json")
+    };
+
+    let get_active_profile = {
+        let bridge = bridge.clone();
+        f.method("GetActiveProfile", (), move |m| {
+            let profile = bridge.call(m.msg, Request::GetActiveProfile)?.profile
+                .ok_or_else(|| MethodErr::failed("Daemon returned no profile"))?;
+            Ok(vec![m.msg.method_return().append2(profile.active, profile.available)])
+        })
+        .outarg::<&str, _>("active")
+        .outarg::<Vec<&str>, _>("available")
+    };
+
+    let set_active_profile = {
+        let bridge = bridge.clone();
+        f.method("SetActiveProfile", (), move |m| {
+            let name: &str = m.msg.read1()?;
+            bridge.call(m.msg, Request::SetActiveProfile { name: name.to_string() })?;
+            Ok(vec![m.msg.method_return()])
+        })
+        .inarg::<&str, _>("name")
+    };
+
+    let set_pwm = {
+        let bridge = bridge.clone();
+        f.method("SetPwm", (), move |m| {
+            let (path, value): (&str, u8) = m.msg.read2()?;
+            let request = Request::SetPwm { path: path.to_string(), value };
+            bridge.call(m.msg, request)?;
+            Ok(vec![m.msg.method_return()])
+        })
+        .inarg::<&str, _>("path")
+        .inarg::<u8, _>("value")
+    };
+
+    let reload_config = {
+        let bridge = bridge.clone();
+        f.method("ReloadConfig", (), move |m| {
+            bridge.call(m.msg, Request::ReloadConfig)?;
+            Ok(vec![m.msg.method_return()])
+        })
+    };
+
+    let reload_config_section = {
+        let bridge = bridge.clone();
+        f.method("ReloadConfigSection", (), move |m| {
+            let name: &str = m.msg.read1()?;
+            let section = serde_json::from_value(serde_json::Value::from(name))
+                .map_err(|_| MethodErr::invalid_arg(name))?;
+            bridge.call(m.msg, Request::ReloadConfigSection { section })?;
+            Ok(vec![m.msg.method_return()])
+        })
+        .inarg::<&str, _>("section")
+    };
+
+    let tree = f.tree(()).add(
+        f.object_path(OBJECT_PATH, ()).introspectable().add(
+            f.interface(INTERFACE, ())
+                .add_m(version)
+                .add_m(list_hardware)
+                .add_m(get_global_mode)
+                .add_m(get_status)
+                .add_m(get_active_profile)
+                .add_m(set_active_profile)
+                .add_m(set_pwm)
+                .add_m(reload_config)
+                .add_m(reload_config_section),
+        ),
+    );
+    tree.start_receive(&conn);
+
+    info!("D-Bus interface registered as {} on the system bus", BUS_NAME);
+
+    loop {
+        conn.process(Duration::from_millis(1000))?;
+    }
+}
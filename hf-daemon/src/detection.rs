@@ -0,0 +1,168 @@
+//! Fan Detection Jobs
+//!
+//! Active detection pulses every PWM output for several seconds each, far
+//! longer than clients wait for a response. `StartDetection` runs it on a
+//! blocking thread and returns a job id straight away; clients poll
+//! `GetDetectionProgress` for the PWM being tested and the final mappings.
+//! `CancelDetection` stops the probe at its next wait, after which the
+//! original PWM values and enable modes are restored.
+//!
+//! Curve control is paused while an active probe runs so the control loop
+//! does not fight it, and the configuration is reloaded once it ends. Passive
+//! jobs only watch: the control loop keeps running, and its PWM changes are
+//! what the job correlates fan speeds against.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use hf_core::HyperfanError;
+use hf_core::constants::timing;
+use hf_protocol::{DetectionJobState, DetectionProgress, DetectionStrategy};
+
+use crate::fan_control::FanControlState;
+
+/// The current (or last) detection job
+#[derive(Default)]
+pub struct DetectionJobs {
+    next_id: u64,
+    current: Option<DetectionJob>,
+}
+
+struct DetectionJob {
+    cancel: Arc<AtomicBool>,
+    progress: Arc<Mutex<DetectionProgress>>,
+}
+
+impl DetectionJobs {
+    /// Whether a job of either strategy is running
+    pub fn is_running(&self) -> bool {
+        self.current.as_ref().is_some_and(|job| snapshot(&job.progress).state == DetectionJobState::Running)
+    }
+
+    /// Whether an active probe is driving the PWM outputs
+    pub fn is_probing(&self) -> bool {
+        self.current.as_ref().is_some_and(|job| {
+            let progress = snapshot(&job.progress);
+            progress.state == DetectionJobState::Running && progress.strategy == DetectionStrategy::Active
+        })
+    }
+
+    fn job(&self, job_id: u64) -> Result<&DetectionJob, String> {
+        self.current
+            .as_ref()
+            .filter(|job| snapshot(&job.progress).job_id == job_id)
+            .ok_or_else(|| format!("Unknown detection job {}", job_id))
+    }
+}
+
+fn snapshot(progress: &Mutex<DetectionProgress>) -> DetectionProgress {
+    progress.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn update(progress: &Mutex<DetectionProgress>, f: impl FnOnce(&mut DetectionProgress)) {
+    f(&mut progress.lock().unwrap_or_else(|e| e.into_inner()));
+}
+
+/// Start a detection job unless one is already running
+pub async fn start(state: &Arc<FanControlState>, strategy: DetectionStrategy) -> Result<DetectionProgress, String> {
+    if state.calibration.read().await.is_running() {
+        return Err("A fan calibration is running".to_string());
+    }
+    if state.auto_tune.read().await.is_running() {
+        return Err("Curve auto-tuning is running".to_string());
+    }
+    let mut jobs = state.detection.write().await;
+    if let Some(job) = jobs.current.as_ref() {
+        let current = snapshot(&job.progress);
+        if current.state == DetectionJobState::Running {
+            return Err(format!("Fan detection is already running (job {})", current.job_id));
+        }
+    }
+
+    jobs.next_id += 1;
+    let progress = Arc::new(Mutex::new(DetectionProgress {
+        job_id: jobs.next_id,
+        strategy,
+        state: DetectionJobState::Running,
+        step: 0,
+        total_steps: 0,
+        current_pwm: None,
+        mappings: Vec::new(),
+        error: None,
+    }));
+    let cancel = Arc::new(AtomicBool::new(false));
+    jobs.current = Some(DetectionJob { cancel: Arc::clone(&cancel), progress: Arc::clone(&progress) });
+    let started = snapshot(&progress);
+    drop(jobs);
+
+    match strategy {
+        DetectionStrategy::Active => info!("DETECTION: Job {} started - pausing curve control", started.job_id),
+        DetectionStrategy::Passive => info!("DETECTION: Passive job {} started", started.job_id),
+    }
+    let task_state = Arc::clone(state);
+    tokio::spawn(async move {
+        let probe_progress = Arc::clone(&progress);
+        let probe = tokio::task::spawn_blocking(move || {
+            let on_step = |step: usize, total: usize, pwm_name: &str| {
+                update(&probe_progress, |p| {
+                    p.step = step as u32;
+                    p.total_steps = total as u32;
+                    p.current_pwm = (!pwm_name.is_empty()).then(|| pwm_name.to_string());
+                });
+            };
+            match strategy {
+                DetectionStrategy::Active => hf_core::autodetect_fan_pwm_mappings_observed(&cancel, on_step),
+                DetectionStrategy::Passive => {
+                    hf_core::autodetect_fan_pwm_mappings_passive(timing::PASSIVE_DETECTION_WINDOW, &cancel, on_step)
+                }
+            }
+        })
+        .await;
+
+        let outcome = match probe {
+            Ok(Ok(mappings)) => {
+                if let Err(e) = hf_core::save_pwm_fan_mappings(mappings.clone()) {
+                    warn!("Failed to persist detected mappings to settings: {}", e);
+                }
+                crate::server::resolve_fan_mappings(mappings).map_err(|e| (DetectionJobState::Failed, e))
+            }
+            Ok(Err(HyperfanError::Cancelled(_))) => Err((DetectionJobState::Cancelled, "Cancelled".to_string())),
+            Ok(Err(e)) => Err((DetectionJobState::Failed, e.to_string())),
+            Err(e) => Err((DetectionJobState::Failed, format!("Detection task panicked: {}", e))),
+        };
+
+        update(&progress, |p| {
+            p.current_pwm = None;
+            match outcome {
+                Ok(mappings) => {
+                    info!("DETECTION: Job {} found {} mappings", p.job_id, mappings.len());
+                    p.state = DetectionJobState::Completed;
+                    p.mappings = mappings;
+                }
+                Err((state, e)) => {
+                    warn!("DETECTION: Job {} ended ({:?}): {}", p.job_id, state, e);
+                    p.state = state;
+                    p.error = (state == DetectionJobState::Failed).then_some(e);
+                }
+            }
+        });
+        task_state.signal_reload();
+    });
+
+    Ok(started)
+}
+
+/// Progress of `job_id`
+pub async fn progress(state: &FanControlState, job_id: u64) -> Result<DetectionProgress, String> {
+    let jobs = state.detection.read().await;
+    jobs.job(job_id).map(|job| snapshot(&job.progress))
+}
+
+/// Ask `job_id` to stop; it reports `Cancelled` once the PWM state is restored
+pub async fn cancel(state: &FanControlState, job_id: u64) -> Result<DetectionProgress, String> {
+    let jobs = state.detection.read().await;
+    let job = jobs.job(job_id)?;
+    job.cancel.store(true, Ordering::Relaxed);
+    Ok(snapshot(&job.progress))
+}
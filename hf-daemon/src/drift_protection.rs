@@ -66,6 +66,7 @@ impl DriftProtection {
             let report = generate_drift_report(&result.drift_result);
             info!("Drift corrections applied:\n{}", report);
         }
+        publish_drift(&result.drift_result);
         
         Ok(result)
     }
@@ -216,6 +217,11 @@ impl DriftProtection {
                 report.degraded_count,
                 report.unsafe_count
             );
+            crate::events::publish(hf_protocol::Event::DriftDetected {
+                corrected: 0,
+                degraded: report.degraded_count as u32,
+                unsafe_bindings: report.unsafe_count as u32,
+            });
             
             // Rebuild cache to pick up any path changes
             drop(store);
@@ -252,11 +258,24 @@ impl DriftProtection {
             
             info!("Drift corrections applied and cache rebuilt");
         }
+        publish_drift(&result);
         
         Ok(result)
     }
 }
 
+/// Tell subscribers about drift found by a detection pass
+fn publish_drift(result: &DriftDetectionResult) {
+    if result.correctable_drift_count == 0 && result.uncorrectable_drift_count == 0 {
+        return;
+    }
+    crate::events::publish(hf_protocol::Event::DriftDetected {
+        corrected: result.correctable_drift_count as u32,
+        degraded: 0,
+        unsafe_bindings: result.uncorrectable_drift_count as u32,
+    });
+}
+
 /// Convert fingerprint binding to daemon control pair
 pub async fn binding_to_control_pair(
     drift_protection: &DriftProtection,
@@ -286,6 +305,7 @@ pub async fn binding_to_control_pair(
         name: info.user_label.unwrap_or_else(|| info.pwm_name.clone()),
         pwm_path: pwm_path.to_string_lossy().to_string(),
         temp_source_path: temp_path.to_string_lossy().to_string(),
+        temp_fallback_paths: Vec::new(),
         curve_points,
         active: true,
     })
@@ -0,0 +1,87 @@
+//! Dry-Run Mode
+//!
+//! With dry-run on (`hyperfand --dry-run` or `SetDryRun`), the control loop
+//! reads sensors and evaluates curves as usual, but PWM writes stop in
+//! `fan_control::set_pwm_inner`. Instead the loop logs the value it would
+//! give each output, with the sensor reading and curve behind it, whenever
+//! that value changes. This validates a new config on a production machine
+//! before it is allowed to touch the fans.
+//!
+//! Fans are left under firmware control meanwhile: started in dry-run mode,
+//! the daemon never takes them over; switched to it at runtime, the original
+//! PWM state is restored first. Switching back re-saves that state and the
+//! next iteration takes control again.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether PWM writes are only logged
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Set the mode at startup, before the control loop takes over any fan
+pub fn enable_at_startup() {
+    ENABLED.store(true, Ordering::SeqCst);
+    warn!("DRY-RUN: PWM writes are only logged - fans stay under firmware control");
+}
+
+/// Switch dry-run mode at runtime
+/// Returns whether the mode changed
+pub fn set_enabled(enabled: bool, intended: &mut IntendedWrites) -> bool {
+    if enabled == is_enabled() {
+        return false;
+    }
+
+    if enabled {
+        // Stop writes before handing the fans back, so the loop can't retake them
+        ENABLED.store(true, Ordering::SeqCst);
+        match crate::original_state::restore() {
+            Ok(count) => info!("DRY-RUN: on - restored original state of {} PWM channels", count),
+            Err(e) => warn!("DRY-RUN: on - failed to restore original PWM state: {}", e),
+        }
+    } else {
+        crate::fan_control::save_original_state();
+        ENABLED.store(false, Ordering::SeqCst);
+        info!("DRY-RUN: off - resuming fan control");
+    }
+    intended.clear();
+    true
+}
+
+/// Last intended value logged per PWM output, so only changes are logged
+#[derive(Debug, Default)]
+pub struct IntendedWrites {
+    last: HashMap<String, u8>,
+}
+
+impl IntendedWrites {
+    /// Remember `value` for `pwm_path`; returns whether it differs from the last one
+    pub fn record(&mut self, pwm_path: &str, value: u8) -> bool {
+        self.last.insert(pwm_path.to_string(), value) != Some(value)
+    }
+
+    pub fn clear(&mut self) {
+        self.last.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intended_writes_log_changes_only() {
+        let mut intended = IntendedWrites::default();
+        assert!(intended.record("/sys/class/hwmon/hwmon2/pwm1", 128));
+        assert!(!intended.record("/sys/class/hwmon/hwmon2/pwm1", 128));
+        assert!(intended.record("/sys/class/hwmon/hwmon2/pwm1", 140));
+        assert!(intended.record("/sys/class/hwmon/hwmon2/pwm2", 140));
+
+        intended.clear();
+        assert!(intended.record("/sys/class/hwmon/hwmon2/pwm1", 140));
+    }
+}
@@ -0,0 +1,213 @@
+//! EC Write Guard
+//!
+//! Raw `WriteEcRegister` writes are staged when `advanced.ec_write_guard` is
+//! set: the register's original value is read first and every fan and
+//! temperature reading is snapshotted. For `GUARD_PERIOD` after the write the
+//! readings are compared against that snapshot, and the original value is
+//! written back if a fan that was spinning stops or a temperature climbs by
+//! more than `MAX_TEMP_RISE_C` or past `MAX_TEMP_C`. Only sensors that were
+//! below `MAX_TEMP_C` in the snapshot are held to it, and readings outside
+//! 0..=`MAX_PLAUSIBLE_TEMP_C` are ignored (unconnected inputs such as floating
+//! nct67xx AUXTIN channels read 100°C and more).
+//!
+//! A second write to a register that is still being guarded extends the
+//! guard; a revert then restores the value from before the first write.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::server::write_ec_register_value;
+
+/// How long readings are watched after a write
+const GUARD_PERIOD: Duration = Duration::from_secs(10);
+
+/// Interval between guard readings
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Temperature rise over the snapshot that triggers a revert
+const MAX_TEMP_RISE_C: f32 = 10.0;
+
+/// Absolute temperature that triggers a revert
+const MAX_TEMP_C: f32 = 95.0;
+
+/// Hottest reading taken as real; above it the input is not connected
+const MAX_PLAUSIBLE_TEMP_C: f32 = 125.0;
+
+/// Fan and temperature readings at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct Readings {
+    fans: HashMap<PathBuf, u32>,
+    temps: HashMap<PathBuf, f32>,
+}
+
+impl Readings {
+    /// Read every hwmon fan and temperature sensor
+    pub fn capture() -> Self {
+        let mut readings = Readings::default();
+        let Ok(chips) = hf_core::enumerate_hwmon_chips() else {
+            return readings;
+        };
+        for chip in chips {
+            for fan in chip.fans {
+                if let Ok(rpm) = hf_core::read_fan_rpm(&fan.input_path) {
+                    readings.fans.insert(fan.input_path, rpm);
+                }
+            }
+            for temp in chip.temperatures {
+                if let Ok(celsius) = hf_core::read_temperature(&temp.input_path) {
+                    readings.temps.insert(temp.input_path, celsius);
+                }
+            }
+        }
+        readings
+    }
+
+    /// Re-read the sensors of a snapshot
+    fn refresh(&self) -> Self {
+        Readings {
+            fans: self
+                .fans
+                .keys()
+                .filter_map(|path| hf_core::read_fan_rpm(path).ok().map(|rpm| (path.clone(), rpm)))
+                .collect(),
+            temps: self
+                .temps
+                .keys()
+                .filter_map(|path| hf_core::read_temperature(path).ok().map(|c| (path.clone(), c)))
+                .collect(),
+        }
+    }
+}
+
+/// Why `now` is out of bounds relative to `baseline`, if it is
+fn violation(baseline: &Readings, now: &Readings) -> Option<String> {
+    for (path, &rpm) in &now.fans {
+        if rpm == 0 && baseline.fans.get(path).is_some_and(|&before| before > 0) {
+            return Some(format!("fan {} stopped", path.display()));
+        }
+    }
+    let plausible = |celsius: f32| (0.0..=MAX_PLAUSIBLE_TEMP_C).contains(&celsius);
+    for (path, &celsius) in &now.temps {
+        let before = baseline.temps.get(path).copied().unwrap_or(celsius);
+        if !plausible(celsius) || !plausible(before) {
+            continue;
+        }
+        if celsius >= MAX_TEMP_C && before < MAX_TEMP_C {
+            return Some(format!("{} reached {:.1}°C", path.display(), celsius));
+        }
+        if celsius - before > MAX_TEMP_RISE_C {
+            return Some(format!("{} rose from {:.1}°C to {:.1}°C", path.display(), before, celsius));
+        }
+    }
+    None
+}
+
+/// Guard of a staged register: the value to restore and the latest write
+struct Staged {
+    original: u8,
+    generation: u64,
+}
+
+static STAGED: Mutex<Option<HashMap<(String, u8), Staged>>> = Mutex::new(None);
+
+/// Start guarding `register` after it was changed from `original`
+///
+/// `baseline` must have been captured before the write.
+pub fn watch(chip_path: String, register: u8, original: u8, baseline: Readings) {
+    let key = (chip_path, register);
+    let generation = {
+        let mut staged = STAGED.lock().unwrap_or_else(|e| e.into_inner());
+        let staged = staged.get_or_insert_with(HashMap::new);
+        let entry = staged.entry(key.clone()).or_insert(Staged { original, generation: 0 });
+        entry.generation += 1;
+        entry.generation
+    };
+
+    tokio::spawn(async move {
+        let (chip_path, register) = &key;
+        let mut ticker = tokio::time::interval(SAMPLE_INTERVAL);
+        ticker.tick().await;
+        let deadline = tokio::time::Instant::now() + GUARD_PERIOD;
+
+        while tokio::time::Instant::now() < deadline {
+            ticker.tick().await;
+            let current = |staged: &Option<HashMap<(String, u8), Staged>>| {
+                staged.as_ref().and_then(|s| s.get(&key)).filter(|s| s.generation == generation).map(|s| s.original)
+            };
+            if current(&STAGED.lock().unwrap_or_else(|e| e.into_inner())).is_none() {
+                // A newer write took over the guard
+                return;
+            }
+
+            let now = baseline.refresh();
+            let Some(reason) = violation(&baseline, &now) else { continue };
+
+            let mut staged = STAGED.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(original) = current(&staged) else { return };
+            warn!("AUDIT: Reverting EC register 0x{:02X} on {} to 0x{:02X}: {}", register, chip_path, original, reason);
+            if let Err(e) = write_ec_register_value(std::path::Path::new(chip_path), *register, original) {
+                error!("Failed to revert EC register 0x{:02X} on {}: {}", register, chip_path, e);
+            }
+            if let Some(staged) = staged.as_mut() {
+                staged.remove(&key);
+            }
+            return;
+        }
+
+        let mut staged = STAGED.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(staged) = staged.as_mut() {
+            if staged.get(&key).is_some_and(|s| s.generation == generation) {
+                staged.remove(&key);
+                info!("EC register 0x{:02X} on {} passed the write guard", register, chip_path);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_violation() {
+        let fan = PathBuf::from("/sys/class/hwmon/hwmon2/fan1_input");
+        let temp = PathBuf::from("/sys/class/hwmon/hwmon1/temp1_input");
+        let baseline = Readings {
+            fans: HashMap::from([(fan.clone(), 1200)]),
+            temps: HashMap::from([(temp.clone(), 55.0)]),
+        };
+        assert_eq!(violation(&baseline, &baseline), None);
+
+        let mut stopped = baseline.clone();
+        stopped.fans.insert(fan, 0);
+        assert!(violation(&baseline, &stopped).unwrap().contains("stopped"));
+
+        let mut warmer = baseline.clone();
+        warmer.temps.insert(temp.clone(), 62.0);
+        assert_eq!(violation(&baseline, &warmer), None);
+        warmer.temps.insert(temp.clone(), 66.0);
+        assert!(violation(&baseline, &warmer).unwrap().contains("rose"));
+
+        let mut hot = baseline.clone();
+        hot.temps.insert(temp.clone(), 90.0);
+        let mut hotter = hot.clone();
+        hotter.temps.insert(temp, 96.0);
+        assert!(violation(&hot, &hotter).unwrap().contains("reached"));
+
+        // An unconnected input reading high before the write, or reading garbage
+        let floating = PathBuf::from("/sys/class/hwmon/hwmon1/temp7_input");
+        let mut noisy = baseline.clone();
+        noisy.temps.insert(floating.clone(), 100.0);
+        let mut now = noisy.clone();
+        now.temps.insert(floating.clone(), 104.0);
+        assert_eq!(violation(&noisy, &now), None);
+        noisy.temps.insert(floating.clone(), 127.0);
+        now.temps.insert(floating.clone(), 98.0);
+        assert_eq!(violation(&noisy, &now), None);
+        now.temps.insert(floating, f32::NAN);
+        assert_eq!(violation(&baseline, &now), None);
+    }
+}
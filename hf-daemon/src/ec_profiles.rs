@@ -0,0 +1,255 @@
+//! Known Laptop EC Fan Layouts
+//!
+//! Laptop fans are usually driven by the embedded controller rather than a
+//! hwmon PWM channel. For models whose EC register layout is known (from the
+//! msi-ec, isw and clevo-indicator projects), the machine is matched by DMI
+//! and the layout is offered through `ListEcChips`, so clients can drive the
+//! fans with `SetEcFanDuty` instead of poking registers by hand.
+//!
+//! `SetEcFanDuty` only writes the layout's mode and duty registers, with
+//! duties scaled into the range the layout declares. Clevo boards take fan
+//! duty through an EC command rather than a register write, so their layout
+//! is read-only. Fans switched to manual control are handed back to the EC
+//! on shutdown.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use tracing::{info, warn};
+
+use hf_core::constants::paths::dmi;
+use hf_protocol::{EcFanMap, EcFanProfile, EcRegisterLabel};
+
+use crate::server::write_ec_register_value;
+
+/// Machines a layout applies to
+struct DmiMatch {
+    /// Substring of `sys_vendor`
+    vendor: &'static str,
+    /// Substrings of `product_name` or `board_name`
+    models: &'static [&'static str],
+}
+
+struct FanLayout {
+    label: &'static str,
+    duty_register: u8,
+    duty_register_count: u8,
+    duty_min: u8,
+    duty_max: u8,
+    rpm_register: Option<u8>,
+    mode_register: Option<u8>,
+    manual_value: u8,
+    auto_value: u8,
+}
+
+struct ModelProfile {
+    name: &'static str,
+    dmi: DmiMatch,
+    fans: &'static [FanLayout],
+    read_registers: &'static [(u8, &'static str)],
+}
+
+/// MSI fan curves are 7 points of 0-150%; writing every point holds one speed
+const fn msi_fans(mode_register: u8, manual_value: u8, auto_value: u8) -> [FanLayout; 2] {
+    [
+        FanLayout {
+            label: "CPU Fan",
+            duty_register: 0x72,
+            duty_register_count: 7,
+            duty_min: 0,
+            duty_max: 150,
+            rpm_register: Some(0xC8),
+            mode_register: Some(mode_register),
+            manual_value,
+            auto_value,
+        },
+        FanLayout {
+            label: "GPU Fan",
+            duty_register: 0x8A,
+            duty_register_count: 7,
+            duty_min: 0,
+            duty_max: 150,
+            rpm_register: Some(0xCA),
+            mode_register: Some(mode_register),
+            manual_value,
+            auto_value,
+        },
+    ]
+}
+
+const MSI_TEMPERATURES: &[(u8, &str)] = &[(0x68, "CPU Temperature"), (0x80, "GPU Temperature")];
+
+const PROFILES: &[ModelProfile] = &[
+    ModelProfile {
+        name: "MSI G-series (fan mode 0xF4)",
+        dmi: DmiMatch {
+            vendor: "Micro-Star",
+            models: &["GF63", "GF75", "GL63", "GL73", "GP63", "GP73", "GS63", "GS73", "GT63"],
+        },
+        fans: &msi_fans(0xF4, 0x8C, 0x0C),
+        read_registers: MSI_TEMPERATURES,
+    },
+    ModelProfile {
+        name: "MSI Katana/Sword (fan mode 0xD4)",
+        dmi: DmiMatch {
+            vendor: "Micro-Star",
+            models: &["Katana", "Sword", "GF65", "Alpha 15", "Bravo 15", "Delta 15"],
+        },
+        fans: &msi_fans(0xD4, 0x8D, 0x0D),
+        read_registers: MSI_TEMPERATURES,
+    },
+    ModelProfile {
+        name: "Clevo (EC command fan control)",
+        dmi: DmiMatch {
+            vendor: "Notebook",
+            models: &["N85_N87", "N95_N96", "P95_P96", "PB50_PB70", "NH50_70", "NH5x_7x"],
+        },
+        fans: &[FanLayout {
+            label: "Fan",
+            duty_register: 0xCE,
+            duty_register_count: 1,
+            duty_min: 0,
+            duty_max: 255,
+            rpm_register: Some(0xD0),
+            mode_register: None,
+            manual_value: 0,
+            auto_value: 0,
+        }],
+        read_registers: &[(0x07, "CPU Temperature"), (0xCD, "GPU Temperature")],
+    },
+];
+
+/// Fans currently under manual control, as (chip path, fan index)
+static MANUAL_FANS: Mutex<BTreeSet<(String, u8)>> = Mutex::new(BTreeSet::new());
+
+fn find_profile(vendor: &str, product: &str, board: &str) -> Option<&'static ModelProfile> {
+    let vendor = vendor.to_lowercase();
+    let product = product.to_lowercase();
+    let board = board.to_lowercase();
+    PROFILES.iter().find(|profile| {
+        vendor.contains(&profile.dmi.vendor.to_lowercase())
+            && profile.dmi.models.iter().any(|model| {
+                let model = model.to_lowercase();
+                product.contains(&model) || board.contains(&model)
+            })
+    })
+}
+
+/// Layout of this machine, matched by DMI once
+fn detected() -> Option<&'static ModelProfile> {
+    static DETECTED: OnceLock<Option<&'static ModelProfile>> = OnceLock::new();
+    *DETECTED.get_or_init(|| {
+        let read = |path: &str| std::fs::read_to_string(path).map(|s| s.trim().to_string()).unwrap_or_default();
+        let profile = find_profile(&read(dmi::SYS_VENDOR), &read(dmi::PRODUCT_NAME), &read(dmi::BOARD_NAME));
+        if let Some(profile) = profile {
+            info!("EC: Matched known fan layout \"{}\"", profile.name);
+        }
+        profile
+    })
+}
+
+/// Layout of this machine for `ListEcChips`
+pub fn detected_profile() -> Option<EcFanProfile> {
+    detected().map(|profile| EcFanProfile {
+        name: profile.name.to_string(),
+        fans: profile
+            .fans
+            .iter()
+            .map(|fan| EcFanMap {
+                label: fan.label.to_string(),
+                duty_register: fan.duty_register,
+                duty_register_count: fan.duty_register_count,
+                duty_min: fan.duty_min,
+                duty_max: fan.duty_max,
+                rpm_register: fan.rpm_register,
+                mode_register: fan.mode_register,
+                manual_value: fan.manual_value,
+                auto_value: fan.auto_value,
+            })
+            .collect(),
+        read_registers: profile
+            .read_registers
+            .iter()
+            .map(|(register, label)| EcRegisterLabel { register: *register, label: label.to_string() })
+            .collect(),
+    })
+}
+
+impl FanLayout {
+    /// Raw register value for `percent` (0-100)
+    fn duty_for(&self, percent: u32) -> u8 {
+        let span = self.duty_max.saturating_sub(self.duty_min) as u32;
+        self.duty_min + ((span * percent.min(100) + 50) / 100) as u8
+    }
+}
+
+/// Drive `fan` at `percent`, or hand it back to the EC when `percent` is None
+///
+/// Fans sharing a mode register are handed back together.
+pub fn set_fan_duty(chip_path: &str, fan: u8, percent: Option<u32>) -> Result<(), String> {
+    let profile = detected().ok_or("No known EC fan layout for this model")?;
+    let layout = profile
+        .fans
+        .get(fan as usize)
+        .ok_or_else(|| format!("{} has no fan {}", profile.name, fan))?;
+    let Some(mode_register) = layout.mode_register else {
+        return Err(format!("{} fans cannot be driven through EC registers", profile.name));
+    };
+    let chip_dir = Path::new(chip_path);
+
+    let mut manual = MANUAL_FANS.lock().unwrap_or_else(|e| e.into_inner());
+    match percent {
+        Some(percent) => {
+            write_ec_register_value(chip_dir, mode_register, layout.manual_value)?;
+            manual.insert((chip_path.to_string(), fan));
+            let duty = layout.duty_for(percent);
+            for offset in 0..layout.duty_register_count {
+                write_ec_register_value(chip_dir, layout.duty_register + offset, duty)?;
+            }
+        }
+        None => {
+            write_ec_register_value(chip_dir, mode_register, layout.auto_value)?;
+            manual.retain(|(path, other)| {
+                path != chip_path
+                    || profile.fans.get(*other as usize).and_then(|f| f.mode_register) != Some(mode_register)
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Hand every manually driven EC fan back to the EC (called on shutdown)
+pub fn restore_auto() {
+    let Some(profile) = detected() else {
+        return;
+    };
+    let manual = std::mem::take(&mut *MANUAL_FANS.lock().unwrap_or_else(|e| e.into_inner()));
+    let mut restored = BTreeSet::new();
+    for (chip_path, fan) in manual {
+        let Some(layout) = profile.fans.get(fan as usize) else { continue };
+        let Some(mode_register) = layout.mode_register else { continue };
+        if !restored.insert((chip_path.clone(), mode_register)) {
+            continue;
+        }
+        match write_ec_register_value(Path::new(&chip_path), mode_register, layout.auto_value) {
+            Ok(()) => info!("EC: Returned {} on {} to automatic control", layout.label, chip_path),
+            Err(e) => warn!("EC: Failed to return {} on {} to automatic control: {}", layout.label, chip_path, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_profile() {
+        let profile = find_profile("Micro-Star International Co., Ltd.", "Katana GF66 11UE", "MS-1582").unwrap();
+        assert_eq!(profile.name, "MSI Katana/Sword (fan mode 0xD4)");
+        assert!(find_profile("Notebook", "N8xEJEK", "N85_N87,HJ,HJ1,HK1").is_some());
+        assert!(find_profile("Dell Inc.", "XPS 15 9500", "0XRH4X").is_none());
+
+        let fan = &profile.fans[0];
+        assert_eq!((fan.duty_for(0), fan.duty_for(50), fan.duty_for(100)), (0, 75, 150));
+    }
+}
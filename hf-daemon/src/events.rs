@@ -0,0 +1,150 @@
+//! Pushed Event Channel
+//!
+//! Thermal alerts, fan failures, hardware changes, profile switches, binding
+//! drift and config changes are published here and written to every connection that sent
+//! `Subscribe`, so clients don't have to poll `GetFanAlerts` and friends.
+//!
+//! Events carry a daemon-wide sequence number. The most recent
+//! `MAX_RETAINED_EVENTS` are kept so a client that reconnects can resubscribe
+//! with the last sequence number it saw and receive what it missed.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tokio::sync::broadcast;
+use tracing::debug;
+
+use hf_protocol::{ConfigSection, Event, EventEnvelope};
+
+/// Events retained for replay (keeps the Subscribe response under MAX_RESPONSE_SIZE)
+const MAX_RETAINED_EVENTS: usize = 100;
+
+/// Events queued per subscriber before it lags and is caught up from the replay buffer
+const CHANNEL_CAPACITY: usize = 64;
+
+struct EventLog {
+    next_seq: u64,
+    events: VecDeque<EventEnvelope>,
+}
+
+impl EventLog {
+    const fn new() -> Self {
+        Self { next_seq: 1, events: VecDeque::new() }
+    }
+
+    fn push(&mut self, event: Event, timestamp_ms: u64) -> EventEnvelope {
+        let envelope = EventEnvelope { seq: self.next_seq, timestamp_ms, event };
+        self.next_seq += 1;
+        self.events.push_back(envelope.clone());
+        while self.events.len() > MAX_RETAINED_EVENTS {
+            self.events.pop_front();
+        }
+        envelope
+    }
+
+    /// Retained events after `since_seq`, oldest first
+    /// A sequence number this daemon never issued (it restarted) replays everything
+    fn since(&self, since_seq: u64) -> Vec<EventEnvelope> {
+        let since_seq = if since_seq >= self.next_seq { 0 } else { since_seq };
+        self.events.iter().filter(|e| e.seq > since_seq).cloned().collect()
+    }
+}
+
+static LOG: Mutex<EventLog> = Mutex::new(EventLog::new());
+
+fn channel() -> &'static broadcast::Sender<EventEnvelope> {
+    static CHANNEL: OnceLock<broadcast::Sender<EventEnvelope>> = OnceLock::new();
+    CHANNEL.get_or_init(|| broadcast::channel(CHANNEL_CAPACITY).0)
+}
+
+/// Send `event` to every subscriber and retain it for replay
+pub fn publish(event: Event) {
+    let timestamp_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    let mut log = LOG.lock().unwrap_or_else(|e| e.into_inner());
+    let envelope = log.push(event, timestamp_ms);
+    debug!("EVENT: #{} published to {} subscribers", envelope.seq, channel().receiver_count());
+    // No receivers is not an error - the event is still retained for replay
+    let _ = channel().send(envelope);
+}
+
+/// Revision of the last `ConfigChanged` event
+static CONFIG_REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// Publish `ConfigChanged` for `section` with the next config revision
+pub fn publish_config_changed(section: ConfigSection) {
+    let revision = CONFIG_REVISION.fetch_add(1, Ordering::SeqCst) + 1;
+    publish(Event::ConfigChanged { section, revision });
+}
+
+/// Live event feed of one connection
+pub struct Subscription {
+    receiver: broadcast::Receiver<EventEnvelope>,
+    last_seq: u64,
+}
+
+/// Subscribe to new events, returning the replay for `since_seq` first
+pub fn subscribe(since_seq: Option<u64>) -> (Vec<EventEnvelope>, Subscription) {
+    // Holding the log lock while subscribing means no event is missed or sent twice
+    let log = LOG.lock().unwrap_or_else(|e| e.into_inner());
+    let replay = since_seq.map(|seq| log.since(seq)).unwrap_or_default();
+    let subscription = Subscription {
+        receiver: channel().subscribe(),
+        last_seq: log.next_seq - 1,
+    };
+    (replay, subscription)
+}
+
+impl Subscription {
+    /// Wait for the next events (more than one after catching up from a lag)
+    pub async fn next(&mut self) -> Vec<EventEnvelope> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(envelope) if envelope.seq > self.last_seq => {
+                    self.last_seq = envelope.seq;
+                    return vec![envelope];
+                }
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!("EVENT: Subscriber lagged by {} events - replaying", skipped);
+                    let missed = LOG.lock().unwrap_or_else(|e| e.into_inner()).since(self.last_seq);
+                    if let Some(last) = missed.last() {
+                        self.last_seq = last.seq;
+                        return missed;
+                    }
+                }
+                // The sender is static and never dropped
+                Err(broadcast::error::RecvError::Closed) => std::future::pending::<()>().await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile_switch(name: &str) -> Event {
+        Event::ProfileSwitched { previous: "normal".to_string(), name: name.to_string(), scheduled: false }
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut log = EventLog::new();
+        for i in 0..(MAX_RETAINED_EVENTS as u64 + 5) {
+            log.push(profile_switch("quiet"), i);
+        }
+
+        let all = log.since(0);
+        assert_eq!(all.len(), MAX_RETAINED_EVENTS);
+        assert_eq!(all[0].seq, 6);
+        assert_eq!(log.since(all[10].seq).len(), MAX_RETAINED_EVENTS - 11);
+        assert!(log.since(log.next_seq - 1).is_empty());
+
+        // Sequence numbers from before a daemon restart replay everything
+        assert_eq!(log.since(10_000).len(), MAX_RETAINED_EVENTS);
+    }
+}
@@ -10,6 +10,14 @@
 //! - **Error counting**: Tracks consecutive failures per PWM
 //! - **Hysteresis**: Prevents rapid fan oscillation via FanCurve engine
 //! - **Smoothing**: Gradual speed changes for quieter operation
+//! - **Stall detection**: Fans stuck at 0 RPM are flagged and their pair compensates
+//! - **Write verification**: Outputs that do not keep their PWM are flagged the same way
+//! - **Profile schedule**: Time-of-day profile switching via `ProfileScheduler`
+//! - **Hotplug**: hwmon/DRM add/remove events trigger re-enumeration and a reload
+//! - **Plausibility**: Impossible temperatures and fan speeds are rejected as read errors (see `plausibility`)
+//! - **Dry run**: Intended PWM writes are logged instead of made (see `dry_run`)
+//! - **Safe mode**: Every output is handed to firmware control and left alone (see `safe_mode`)
+//! - **GPU fans**: Writes are coalesced and idle fan-stop is left to the card (see `gpu_fans`)
 
 use std::collections::HashMap;
 use std::time::Instant;
@@ -19,8 +27,13 @@ use std::time::Duration;
 use tokio::sync::{Notify, RwLock};
 use tracing::{debug, error, info, warn};
 
+use hf_core::constants::temperature;
 use hf_core::{FanCurve, CurvePoint};
-use hf_protocol::{validate_hwmon_path, validate_pwm_target_path};
+use hf_protocol::{
+    validate_hwmon_path, validate_pwm_target_path, PairingControlMode, IPMI_PATH_PREFIX, SMART_PATH_PREFIX, SYSCTL_PATH_PREFIX,
+};
+
+use crate::stall_monitor::{StallMonitor, StallWatch, DEFAULT_MIN_PWM};
 
 /// Default fan speed percentage when config fails to load (safety fallback)
 const FALLBACK_FAN_PERCENT: f32 = 50.0;
@@ -43,8 +56,14 @@ pub struct FanControlState {
     pub enabled: AtomicBool,
     /// Poll interval in milliseconds
     pub poll_interval_ms: AtomicU64,
+    /// Adaptive polling bounds (overrides poll_interval_ms when enabled)
+    pub adaptive_polling: RwLock<hf_core::AdaptivePollSettings>,
+    /// System is on battery power (curve battery offsets apply)
+    pub on_battery: AtomicBool,
     /// Active control pairs with runtime state (pwm_path -> ControlPairRuntime)
     pub pairs: RwLock<HashMap<String, ControlPairRuntime>>,
+    /// Curves of active PWM groups (group id -> GroupCurve); members are in `pairs`
+    pub groups: RwLock<HashMap<String, GroupCurve>>,
     /// Signal to reload configuration
     pub reload_signal: AtomicBool,
     /// Notify to wake up control loop immediately on reload
@@ -66,12 +85,53 @@ pub struct FanControlState {
     
     /// Last drift validation timestamp
     pub last_drift_validation: RwLock<Option<Instant>>,
+    
+    /// Fan stall detection and alert queue
+    pub stall_monitor: RwLock<StallMonitor>,
+
+    /// Full speed while a hwmon fan or temperature alarm is raised
+    pub alarm_boost: RwLock<crate::alarm_boost::AlarmBoost>,
+
+    /// hwmon/DRM hotplug event queue (filled by the uevent listener thread)
+    pub hotplug: RwLock<crate::hotplug::HotplugMonitor>,
+
+    /// Config file edits waiting to settle (filled by the inotify listener thread)
+    pub config_watch: RwLock<crate::config_watch::ConfigWatch>,
+
+    /// Sensor recording session (StartRecording/StopRecording)
+    pub recorder: tokio::sync::Mutex<crate::recorder::Recorder>,
+
+    /// Long-term sensor history (sampled by `history::spawn_sampler`)
+    pub history: tokio::sync::Mutex<crate::history::HistoryDb>,
+
+    /// Fan calibration sweeps (CalibrateFan)
+    pub calibration: RwLock<crate::calibration::CalibrationJobs>,
+
+    /// Background fan detection (StartDetection); active probing pauses curve control
+    pub detection: RwLock<crate::detection::DetectionJobs>,
+
+    /// Curve auto-tuning under a load test (StartAutoTune)
+    pub auto_tune: RwLock<crate::auto_tune::AutoTuneJobs>,
+
+    /// Per-source temperature filters applied before curve evaluation
+    pub filters: RwLock<HashMap<String, hf_core::FilterState>>,
+
+    /// Intended PWM values last logged in dry-run mode
+    pub intended_writes: RwLock<crate::dry_run::IntendedWrites>,
+
+    /// Rejected implausible sensor readings (GetControlStatus)
+    pub plausibility: RwLock<crate::plausibility::Plausibility>,
+
+    /// Health of temperature sources with fallbacks, and which one each chain uses
+    pub source_health: RwLock<crate::source_failover::SourceHealth>,
 }
 
 #[derive(Clone, Copy, Debug)]
 pub struct PwmOverride {
     pub value: u8,
     pub expires_at: Instant,
+    /// uid and pid of the client that set it; none for the daemon's own
+    pub set_by: Option<(u32, i32)>,
 }
 
 /// A single fan-curve control pair with integrated FanCurve engine
@@ -81,6 +141,8 @@ pub struct ControlPair {
     pub name: String,
     pub pwm_path: String,
     pub temp_source_path: String,
+    /// Curve's fallback sources, tried in order when `temp_source_path` fails
+    pub temp_fallback_paths: Vec<String>,
     /// Raw curve points for serialization/display
     pub curve_points: Vec<(f32, f32)>,
     pub active: bool,
@@ -92,6 +154,88 @@ pub struct ControlPairRuntime {
     pub pair: ControlPair,
     /// FanCurve engine with hysteresis and smoothing
     pub curve_engine: FanCurve,
+    /// Temperature forcing full speed, from the source's driver limits
+    pub failsafe_temp: f32,
+    /// Failsafe is currently overriding the curve
+    pub failsafe_active: bool,
+    /// Closed-loop RPM state when the pairing targets RPM instead of duty
+    pub rpm_loop: Option<RpmLoop>,
+    /// Measured PWM-to-RPM table of this output (CalibrateFan)
+    pub calibration: Option<hf_protocol::FanCalibration>,
+    /// Calibrated stall duty curve outputs are held at (None with zero-RPM allowed)
+    pub min_pwm: Option<u8>,
+    /// Uncalibrated DC output: curve output above 0% is scaled from this PWM up
+    pub dc_floor: Option<u8>,
+    /// PWM group this output follows instead of its own curve engine
+    pub group: Option<GroupMembership>,
+    /// Saved curve the engine was built from
+    pub curve_id: String,
+    /// Unsaved curve tried out in place of the engine (PreviewCurve)
+    pub preview: Option<CurvePreview>,
+    /// Response to another program writing the output
+    pub enforcement: hf_protocol::PwmEnforcement,
+    /// Write gating when the output is a GPU fan
+    pub gpu: Option<crate::gpu_fans::GpuFanControl>,
+}
+
+/// Temporary curve engine used until `expires_at`, then dropped for the saved one
+pub struct CurvePreview {
+    pub curve_engine: FanCurve,
+    pub expires_at: Instant,
+}
+
+impl CurvePreview {
+    /// Copy of `engine` (same tuning and current output) following `points` instead
+    fn new(
+        engine: &FanCurve,
+        points: &[(f32, f32)],
+        interpolation: hf_core::CurveInterpolation,
+        expires_at: Instant,
+    ) -> Self {
+        let mut curve_engine = engine.clone().with_interpolation(interpolation);
+        curve_engine.set_points(points.iter()
+            .map(|(temp, percent)| CurvePoint { temperature: *temp, fan_percent: *percent })
+            .collect());
+        Self { curve_engine, expires_at }
+    }
+
+    /// Engine to evaluate: the preview while it runs, otherwise the saved one
+    /// Drops an expired preview
+    fn engine<'a>(preview: &'a mut Option<CurvePreview>, saved: &'a mut FanCurve, name: &str) -> &'a mut FanCurve {
+        if preview.as_ref().is_some_and(|p| p.expires_at <= Instant::now()) {
+            info!("CONTROL: Curve preview on '{}' ended - back to the saved curve", name);
+            *preview = None;
+        }
+        match preview {
+            Some(preview) => &mut preview.curve_engine,
+            None => saved,
+        }
+    }
+}
+
+/// Membership of a PWM output in a group
+#[derive(Clone, Debug)]
+pub struct GroupMembership {
+    pub group_id: String,
+    /// Added to the group's curve output (percent)
+    pub offset_percent: f32,
+}
+
+/// Curve engine of a PWM group, evaluated once per iteration for all members
+pub struct GroupCurve {
+    pub name: String,
+    pub temp_source_path: String,
+    pub temp_fallback_paths: Vec<String>,
+    pub curve_engine: FanCurve,
+    pub curve_id: String,
+    pub preview: Option<CurvePreview>,
+}
+
+/// Feedback loop of a pairing in RPM mode
+pub struct RpmLoop {
+    pub controller: hf_core::RpmController,
+    /// Tachometer compared against the target
+    pub fan_path: String,
 }
 
 impl FanControlState {
@@ -99,7 +243,10 @@ impl FanControlState {
         Self {
             enabled: AtomicBool::new(true),
             poll_interval_ms: AtomicU64::new(1000), // Default 1 second
+            adaptive_polling: RwLock::new(hf_core::AdaptivePollSettings::default()),
+            on_battery: AtomicBool::new(false),
             pairs: RwLock::new(HashMap::new()),
+            groups: RwLock::new(HashMap::new()),
             reload_signal: AtomicBool::new(false),
             reload_notify: Notify::new(),
             config_loaded: AtomicBool::new(false),
@@ -108,6 +255,19 @@ impl FanControlState {
             pwm_overrides: tokio::sync::RwLock::new(HashMap::new()),
             drift_protection: None, // Initialized later if fingerprints are available
             last_drift_validation: RwLock::new(None),
+            stall_monitor: RwLock::new(StallMonitor::new(Duration::from_secs(10))),
+            alarm_boost: RwLock::new(crate::alarm_boost::AlarmBoost::default()),
+            hotplug: RwLock::new(crate::hotplug::HotplugMonitor::new()),
+            config_watch: RwLock::new(crate::config_watch::ConfigWatch::new()),
+            recorder: tokio::sync::Mutex::new(crate::recorder::Recorder::default()),
+            history: tokio::sync::Mutex::new(crate::history::HistoryDb::default()),
+            calibration: RwLock::new(crate::calibration::CalibrationJobs::default()),
+            detection: RwLock::new(crate::detection::DetectionJobs::default()),
+            auto_tune: RwLock::new(crate::auto_tune::AutoTuneJobs::default()),
+            filters: RwLock::new(HashMap::new()),
+            intended_writes: RwLock::new(crate::dry_run::IntendedWrites::default()),
+            plausibility: RwLock::new(crate::plausibility::Plausibility::default()),
+            source_health: RwLock::new(crate::source_failover::SourceHealth::default()),
         }
     }
     
@@ -151,11 +311,30 @@ impl FanControlState {
         }
     }
 
-    pub async fn set_pwm_override(&self, pwm_path: String, value: u8, ttl_ms: u32) {
+    pub async fn set_pwm_override(&self, pwm_path: String, value: u8, ttl_ms: u32, set_by: Option<(u32, i32)>) {
         let ttl = Duration::from_millis(ttl_ms.max(50) as u64);
         let expires_at = Instant::now() + ttl;
         let mut guard = self.pwm_overrides.write().await;
-        guard.insert(pwm_path, PwmOverride { value, expires_at });
+        guard.insert(pwm_path, PwmOverride { value, expires_at, set_by });
+    }
+
+    /// Overrides that haven't expired yet, by PWM path
+    pub async fn active_overrides(&self) -> Vec<hf_protocol::ActiveOverride> {
+        let now = Instant::now();
+        let guard = self.pwm_overrides.read().await;
+        let mut active: Vec<_> = guard
+            .iter()
+            .filter(|(_, ov)| ov.expires_at > now)
+            .map(|(path, ov)| hf_protocol::ActiveOverride {
+                pwm_path: path.clone(),
+                value: ov.value,
+                remaining_ms: (ov.expires_at - now).as_millis() as u64,
+                uid: ov.set_by.map(|(uid, _)| uid),
+                pid: ov.set_by.map(|(_, pid)| pid),
+            })
+            .collect();
+        active.sort_by(|a, b| a.pwm_path.cmp(&b.pwm_path));
+        active
     }
 
     pub async fn clear_pwm_override(&self, pwm_path: &str) {
@@ -163,6 +342,43 @@ impl FanControlState {
         guard.remove(pwm_path);
     }
 
+    /// Follow `points` instead of saved curve `curve_id` for `duration_ms`
+    /// Returns how many pairs and groups use the curve; a config reload ends the preview
+    pub async fn set_curve_preview(
+        &self,
+        curve_id: &str,
+        points: &[(f32, f32)],
+        interpolation: hf_core::CurveInterpolation,
+        duration_ms: u32,
+    ) -> usize {
+        let mut points = points.to_vec();
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        let expires_at = Instant::now() + Duration::from_millis(duration_ms as u64);
+
+        let mut previewed = 0;
+        for runtime in self.pairs.write().await.values_mut()
+            .filter(|r| r.curve_id == curve_id && r.group.is_none())
+        {
+            runtime.preview = Some(CurvePreview::new(&runtime.curve_engine, &points, interpolation, expires_at));
+            previewed += 1;
+        }
+        for group in self.groups.write().await.values_mut().filter(|g| g.curve_id == curve_id) {
+            group.preview = Some(CurvePreview::new(&group.curve_engine, &points, interpolation, expires_at));
+            previewed += 1;
+        }
+        previewed
+    }
+
+    /// Return the outputs following curve `curve_id` to the saved curve
+    pub async fn clear_curve_preview(&self, curve_id: &str) {
+        for runtime in self.pairs.write().await.values_mut().filter(|r| r.curve_id == curve_id) {
+            runtime.preview = None;
+        }
+        for group in self.groups.write().await.values_mut().filter(|g| g.curve_id == curve_id) {
+            group.preview = None;
+        }
+    }
+
     /// Signal the control loop to reload configuration
     /// This wakes up the control loop immediately to apply changes
     pub fn signal_reload(&self) {
@@ -253,7 +469,7 @@ fn discover_all_pwm_controllers() -> Vec<PwmInfo> {
             pwm_name: gpu.name.clone(),
         });
         
-        info!("Discovered GPU PWM controller: {} ({})", gpu.name, gpu.id);
+        info!("Discovered GPU PWM controller: {} ({}) via {}", gpu.name, gpu.id, gpu.backend);
     }
     
     controllers
@@ -278,6 +494,27 @@ pub async fn initialize_pwm_controls(state: &FanControlState) {
     }
     
     info!("Found {} PWM controllers", controllers.len());
+
+    // Safe mode: hand everything to firmware control and never take it over
+    if crate::safe_mode::is_enabled() {
+        *state.known_pwm_paths.write().await = controllers.iter().map(|p| p.pwm_path.clone()).collect();
+        let outputs: Vec<(String, String)> = controllers
+            .iter()
+            .map(|pwm| (pwm.pwm_path.clone(), pwm.enable_path.clone()))
+            .collect();
+        crate::safe_mode::force_auto(&outputs);
+        return;
+    }
+
+    // Phase 0: Remember the pre-hyperfand mode/value so it can be restored on exit
+    save_channel_state(&controllers);
+
+    // Dry run: leave every output as it is; the loop only logs what it would write
+    if crate::dry_run::is_enabled() {
+        *state.known_pwm_paths.write().await = controllers.iter().map(|p| p.pwm_path.clone()).collect();
+        info!("DRY-RUN: leaving {} PWM controls under firmware control", controllers.len());
+        return;
+    }
     
     // Phase 1: Enable manual control and set safe initial value (50%) for all PWMs
     // We use 50% as the boot default - this is safe for cooling while not being too loud
@@ -318,10 +555,10 @@ pub async fn initialize_pwm_controls(state: &FanControlState) {
             continue;
         }
         
-        // Standard sysfs PWM control (motherboard, AMD GPU, Intel GPU)
-        // Enable manual control mode (1 = manual)
-        let enable_result = if !pwm.enable_path.is_empty() && std::path::Path::new(&pwm.enable_path).exists() {
-            std::fs::write(&pwm.enable_path, "1")
+        // Standard sysfs PWM control (motherboard, AMD GPU, Intel GPU) or
+        // FreeBSD sysctl fan levels; enable manual control mode first
+        let enable_result = if !pwm.enable_path.is_empty() {
+            hf_core::enable_manual_pwm(std::path::Path::new(&pwm.enable_path))
         } else {
             Ok(()) // No enable file means always manual
         };
@@ -329,7 +566,7 @@ pub async fn initialize_pwm_controls(state: &FanControlState) {
         match enable_result {
             Ok(()) => {
                 // Set initial PWM to 50% (127) - safe default before user config loads
-                match std::fs::write(&pwm.pwm_path, FALLBACK_PWM_VALUE.to_string()) {
+                match hf_core::set_pwm_value(std::path::Path::new(&pwm.pwm_path), FALLBACK_PWM_VALUE) {
                     Ok(()) => {
                         debug!(
                             chip = %pwm.chip_name,
@@ -425,6 +662,20 @@ pub async fn initialize_pwm_controls(state: &FanControlState) {
     info!("PWM control initialization complete");
 }
 
+/// Save the current mode and value of every sysfs PWM channel (see `original_state`)
+pub(crate) fn save_original_state() {
+    save_channel_state(&discover_all_pwm_controllers());
+}
+
+fn save_channel_state(controllers: &[PwmInfo]) {
+    let sysfs_channels: Vec<(String, String)> = controllers
+        .iter()
+        .filter(|pwm| !pwm.pwm_path.starts_with("nvidia:"))
+        .map(|pwm| (pwm.pwm_path.clone(), pwm.enable_path.clone()))
+        .collect();
+    crate::original_state::save(&sysfs_channels);
+}
+
 /// Apply fallback fan speed (50%) to all known PWM controllers
 async fn apply_fallback_speed(state: &FanControlState) {
     let paths = state.known_pwm_paths.read().await;
@@ -470,15 +721,15 @@ pub async fn load_config(state: &FanControlState) -> Result<(), String> {
         Some(config_path) => {
             info!("Loading config from: {:?}", config_path);
             // Also log if the settings file exists
-            let settings_path = config_path.join("settings.json");
-            let curves_path = config_path.join("curves.json");
-            info!("  settings.json exists: {}", settings_path.exists());
-            info!("  curves.json exists: {}", curves_path.exists());
+            let settings_path = hf_core::data::config_file_path(&config_path, "settings", Default::default());
+            let curves_path = hf_core::get_curves_path();
+            info!("  {:?} exists: {}", settings_path, settings_path.exists());
+            info!("  {:?} exists: {}", curves_path, curves_path.exists());
             
             // Log file contents for debugging
             if settings_path.exists() {
                 if let Ok(content) = std::fs::read_to_string(&settings_path) {
-                    debug!("  settings.json size: {} bytes", content.len());
+                    debug!("  settings file size: {} bytes", content.len());
                 }
             }
         }
@@ -494,9 +745,10 @@ pub async fn load_config(state: &FanControlState) -> Result<(), String> {
     // Load settings
     let settings = match hf_core::load_settings() {
         Ok(s) => {
-            info!("Settings loaded: {} active_pairs total, {} active", 
+            info!("Settings loaded: {} active_pairs total, {} active, profile '{}'", 
                   s.active_pairs.len(),
-                  s.active_pairs.iter().filter(|p| p.active).count());
+                  s.active_pairs.iter().filter(|p| p.active).count(),
+                  s.active_profile);
             s
         }
         Err(e) => {
@@ -508,9 +760,14 @@ pub async fn load_config(state: &FanControlState) -> Result<(), String> {
         }
     };
 
+    set_virtual_sensors(&settings.virtual_sensors);
+    set_sensor_filters(state, &settings.sensor_filters).await;
+    hf_core::set_controller_filter(settings.controller_filter.clone());
+
     // Update poll interval
     let poll_ms = settings.general.poll_interval_ms as u64;
     state.poll_interval_ms.store(poll_ms.max(50), Ordering::SeqCst); // Min 50ms
+    *state.adaptive_polling.write().await = settings.general.adaptive_polling;
 
     // Load curves
     let curve_store = match hf_core::load_curves() {
@@ -530,9 +787,21 @@ pub async fn load_config(state: &FanControlState) -> Result<(), String> {
         }
     };
 
+    let on_battery = state.on_battery.load(Ordering::SeqCst);
+
     // Build control pairs from active_pairs
     let mut pairs = HashMap::new();
     let mut pwm_paths = Vec::new();
+    let mut stall_watches = HashMap::new();
+
+    // Calibrated PWM-to-RPM tables linearize the curve output
+    let calibrations = hf_core::BindingStore::load()
+        .map(|store| store.calibrations)
+        .unwrap_or_default();
+
+    // Check if stepped mode is enabled via graph_style setting
+    // (use already-loaded settings to avoid redundant disk I/O)
+    let stepped = settings.display.graph_style == "stepped";
 
     for pair in settings.active_pairs.iter().filter(|p| p.active) {
         // Collect all fan paths - use fan_paths if available, otherwise fall back to fan_path
@@ -544,70 +813,104 @@ pub async fn load_config(state: &FanControlState) -> Result<(), String> {
             warn!("Pair '{}' has no fan paths configured, skipping", pair.name);
             continue;
         };
-        
+
+        // The active profile may select a different curve for this pair
+        let curve_id = pair.curve_for_profile(&settings.active_profile);
         info!("Processing active pair '{}': curve_id='{}', fan_paths={:?}, temp_source='{}'",
-              pair.name, pair.curve_id, all_fan_paths, pair.temp_source_path);
-        
+              pair.name, curve_id, all_fan_paths, pair.temp_source_path);
+
         // Look up the curve
-        if let Some(curve) = curve_store.get(&pair.curve_id) {
+        if let Some(curve) = curve_store.get(curve_id) {
             info!("  Found curve '{}' with {} points", curve.name, curve.points.len());
+            let curve_points = prepared_curve_points(curve, on_battery);
 
-            let mut curve_points = curve.points.clone();
-            curve_points.sort_by(|a, b| {
-                a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal)
-            });
-            for point in &mut curve_points {
-                point.1 = point.1.clamp(0.0, 100.0);
-            }
-            
             // Create a control pair for EACH fan path in this pair
             // This allows multiple fans to be controlled by the same curve
             for (idx, fan_path) in all_fan_paths.iter().enumerate() {
+                if hf_core::is_pwm_excluded(std::path::Path::new(fan_path)) {
+                    warn!("Pair '{}': {} is excluded by the controller filter - skipping it", pair.name, fan_path);
+                    continue;
+                }
                 let control_pair = ControlPair {
                     id: if idx == 0 { pair.id.clone() } else { format!("{}_{}", pair.id, idx) },
-                    name: if all_fan_paths.len() > 1 { 
-                        format!("{} [Fan {}]", pair.name, idx + 1) 
-                    } else { 
-                        pair.name.clone() 
+                    name: if all_fan_paths.len() > 1 {
+                        format!("{} [Fan {}]", pair.name, idx + 1)
+                    } else {
+                        pair.name.clone()
                     },
                     pwm_path: fan_path.clone(),
                     temp_source_path: pair.temp_source_path.clone(),
+                    temp_fallback_paths: curve.fallback_sources.clone(),
                     curve_points: curve_points.clone(),
                     active: pair.active,
                 };
-                
-                // Create FanCurve engine with hysteresis, delay, and ramp speeds from curve config
-                // Convert (f32, f32) tuples to CurvePoint structs
-                let curve_point_structs: Vec<CurvePoint> = curve_points.iter()
-                    .map(|(temp, percent)| CurvePoint { temperature: *temp, fan_percent: *percent })
-                    .collect();
-                // Check if stepped mode is enabled via graph_style setting
-                // (use already-loaded settings to avoid redundant disk I/O)
-                let stepped = settings.display.graph_style == "stepped";
-                
-                let curve_engine = FanCurve::new(curve_point_structs)
-                    .with_hysteresis(curve.hysteresis)
-                    .with_smoothing(hf_core::constants::curve::DEFAULT_SMOOTHING_FACTOR)
-                    .with_delay(curve.delay_ms)
-                    .with_ramp_speeds(curve.ramp_up_speed, curve.ramp_down_speed)
-                    .with_stepped(stepped);
-                
-                let runtime = ControlPairRuntime {
-                    pair: control_pair,
-                    curve_engine,
-                };
-                
+                let runtime = build_runtime(
+                    control_pair, curve, stepped, &settings, &calibrations, &pair.id, &mut stall_watches,
+                );
+
                 pwm_paths.push(fan_path.clone());
                 pairs.insert(fan_path.clone(), runtime);
                 debug!("  Added control for fan path: {} (with FanCurve engine)", fan_path);
             }
         } else {
-            error!("Curve '{}' not found for pair '{}' - available curves: {:?}", 
-                   pair.curve_id, pair.name, 
+            error!("Curve '{}' not found for pair '{}' - available curves: {:?}",
+                   curve_id, pair.name,
                    curve_store.all().iter().map(|c| &c.id).collect::<Vec<_>>());
         }
     }
 
+    // PWM groups: one curve engine per group, members follow it with their offsets
+    let mut groups = HashMap::new();
+    for group in settings.pwm_groups.iter().filter(|g| g.active) {
+        let Some(curve) = curve_store.get(&group.curve_id) else {
+            error!("Curve '{}' not found for group '{}'", group.curve_id, group.name);
+            continue;
+        };
+        info!("Processing group '{}': curve_id='{}', {} members, temp_source='{}'",
+              group.name, group.curve_id, group.members.len(), group.temp_source_path);
+        let curve_points = prepared_curve_points(curve, on_battery);
+
+        for (idx, member) in group.members.iter().enumerate() {
+            // A pair already driving the output keeps it
+            if pairs.contains_key(&member.pwm_path) {
+                warn!("Group '{}': {} is already controlled by a pair - skipping it", group.name, member.pwm_path);
+                continue;
+            }
+            if hf_core::is_pwm_excluded(std::path::Path::new(&member.pwm_path)) {
+                warn!("Group '{}': {} is excluded by the controller filter - skipping it", group.name, member.pwm_path);
+                continue;
+            }
+            let control_pair = ControlPair {
+                id: format!("{}_{}", group.id, idx),
+                name: format!("{} [{}]", group.name, idx + 1),
+                pwm_path: member.pwm_path.clone(),
+                temp_source_path: group.temp_source_path.clone(),
+                temp_fallback_paths: curve.fallback_sources.clone(),
+                curve_points: curve_points.clone(),
+                active: true,
+            };
+            let mut runtime = build_runtime(
+                control_pair, curve, stepped, &settings, &calibrations, &group.id, &mut stall_watches,
+            );
+            runtime.group = Some(GroupMembership {
+                group_id: group.id.clone(),
+                offset_percent: member.offset_percent,
+            });
+            pwm_paths.push(member.pwm_path.clone());
+            pairs.insert(member.pwm_path.clone(), runtime);
+        }
+
+        groups.insert(group.id.clone(), GroupCurve {
+            name: group.name.clone(),
+            temp_source_path: group.temp_source_path.clone(),
+            temp_fallback_paths: curve.fallback_sources.clone(),
+            curve_engine: curve_engine(curve, &curve_points, stepped, None),
+            curve_id: curve.id.clone(),
+            preview: None,
+        });
+    }
+    *state.groups.write().await = groups;
+
     // Update state
     let pairs_count = pairs.len();
     {
@@ -616,6 +919,17 @@ pub async fn load_config(state: &FanControlState) -> Result<(), String> {
     }
     info!("Stored {} control pairs in state", pairs_count);
     
+    let stall_timeout = Duration::from_secs(settings.general.fan_stall_timeout_secs as u64);
+    info!("Stall detection watching {} fans (timeout: {}s)", stall_watches.len(), stall_timeout.as_secs());
+    state.stall_monitor.write().await.configure(stall_watches, stall_timeout);
+
+    let alarm_chips = if settings.general.alarm_boost.enabled {
+        hf_core::enumerate_hwmon_chips().unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    state.alarm_boost.write().await.configure(settings.general.alarm_boost, &alarm_chips);
+    
     // Update known PWM paths (for fallback)
     {
         let mut known_paths = state.known_pwm_paths.write().await;
@@ -629,6 +943,8 @@ pub async fn load_config(state: &FanControlState) -> Result<(), String> {
                 known_paths.push(path);
             }
         }
+        // The failsafe never drives excluded outputs either
+        known_paths.retain(|path| !hf_core::is_pwm_excluded(std::path::Path::new(path)));
     }
 
     // Mark config as successfully loaded
@@ -645,6 +961,160 @@ pub async fn load_config(state: &FanControlState) -> Result<(), String> {
     Ok(())
 }
 
+/// Curve points with the battery offset applied, sorted and clamped to 0-100%
+fn prepared_curve_points(curve: &hf_core::PersistedCurve, on_battery: bool) -> Vec<(f32, f32)> {
+    let mut curve_points = match curve.battery_offset {
+        Some(offset) if on_battery => {
+            info!("  On battery: offsetting curve by {:+}% / {:+}°C", offset.duty_percent, offset.temp_celsius);
+            offset.apply(&curve.points)
+        }
+        _ => curve.points.clone(),
+    };
+    curve_points.sort_by(|a, b| {
+        a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    for point in &mut curve_points {
+        point.1 = point.1.clamp(0.0, 100.0);
+    }
+    curve_points
+}
+
+/// FanCurve engine with hysteresis, delay, and ramp speeds from the curve config
+/// (or from the pairing's tuning when it has one)
+fn curve_engine(
+    curve: &hf_core::PersistedCurve,
+    curve_points: &[(f32, f32)],
+    stepped: bool,
+    tuning: Option<hf_protocol::CurveTuning>,
+) -> FanCurve {
+    // Convert (f32, f32) tuples to CurvePoint structs
+    let curve_point_structs: Vec<CurvePoint> = curve_points.iter()
+        .map(|(temp, percent)| CurvePoint { temperature: *temp, fan_percent: *percent })
+        .collect();
+
+    let curve_engine = FanCurve::new(curve_point_structs)
+        .with_delay(curve.delay_ms)
+        .with_stepped(stepped)
+        .with_interpolation(curve.interpolation);
+
+    match tuning {
+        Some(tuning) => curve_engine
+            .with_hysteresis(tuning.hysteresis_c)
+            .with_smoothing_window(tuning.smoothing_window)
+            .with_ramp_speeds(tuning.ramp_up_pct_per_s, tuning.ramp_down_pct_per_s),
+        None => curve_engine
+            .with_hysteresis(curve.hysteresis)
+            .with_smoothing(hf_core::constants::curve::DEFAULT_SMOOTHING_FACTOR)
+            .with_ramp_speeds(curve.ramp_up_speed, curve.ramp_down_speed),
+    }
+}
+
+/// Runtime state for one PWM output following `curve`
+/// Registers a stall watch for the paired tachometer under `stall_group`
+fn build_runtime(
+    control_pair: ControlPair,
+    curve: &hf_core::PersistedCurve,
+    stepped: bool,
+    settings: &hf_core::AppSettings,
+    calibrations: &HashMap<String, hf_protocol::FanCalibration>,
+    stall_group: &str,
+    stall_watches: &mut HashMap<String, StallWatch>,
+) -> ControlPairRuntime {
+    let fan_path = &control_pair.pwm_path;
+    let pwm_pairing = settings.pwm_fan_pairings.iter()
+        .find(|p| &p.pwm_path == fan_path);
+
+    // Watch the paired tachometer (if any) for stalls
+    let paired_fan = pwm_pairing.and_then(|p| p.fan_path.clone());
+    if let Some(fan_input) = paired_fan {
+        let min_pwm = calibrations.get(fan_path).map(|c| c.min_pwm)
+            .or_else(|| learned_min_pwm(&settings.pwm_fan_mappings, fan_path))
+            .unwrap_or(DEFAULT_MIN_PWM);
+        stall_watches.insert(fan_path.clone(), StallWatch {
+            name: control_pair.name.clone(),
+            fan_path: fan_input,
+            group_id: stall_group.to_string(),
+            min_pwm,
+        });
+    }
+
+    // Per-PWM tuning from the pairing overrides the curve's response parameters
+    let tuning = pwm_pairing.and_then(|p| p.tuning);
+    if let Some(tuning) = tuning {
+        debug!("  Using pairing tuning for {}: {:?}", fan_path, tuning);
+    }
+    let curve_engine = curve_engine(curve, &control_pair.curve_points, stepped, tuning);
+
+    // RPM mode tracks the paired tachometer; falls back to duty cycle without one
+    let rpm_loop = match pwm_pairing.map(|p| (p.control_mode, p.fan_path.clone())) {
+        Some((PairingControlMode::Rpm { max_rpm }, Some(fan_input))) => {
+            match max_rpm.or_else(|| learned_max_rpm(&settings.pwm_fan_mappings, fan_path)) {
+                Some(max_rpm) => {
+                    debug!("  RPM control for {} (max {} RPM via {})", fan_path, max_rpm, fan_input);
+                    Some(RpmLoop { controller: hf_core::RpmController::new(max_rpm), fan_path: fan_input })
+                }
+                None => {
+                    warn!("RPM control for {} has no maximum RPM (run fan detection or set one) - using duty cycle", fan_path);
+                    None
+                }
+            }
+        }
+        Some((PairingControlMode::Rpm { .. }, None)) => {
+            warn!("RPM control for {} needs a paired fan - using duty cycle", fan_path);
+            None
+        }
+        _ => None,
+    };
+
+    // Paths without driver limits (GPU, sysctl, smartctl) get the default threshold
+    let limits = hf_core::read_temperature_limits(std::path::Path::new(&control_pair.temp_source_path));
+    let failsafe_temp = limits.failsafe_threshold();
+    debug!("  Failsafe for {} at {:.1}°C (limits: {:?})", control_pair.name, failsafe_temp, limits);
+
+    // Without zero-RPM the curve never takes the fan below its stall duty
+    let calibration = calibrations.get(fan_path).cloned();
+    let zero_rpm = pwm_pairing.is_some_and(|p| p.zero_rpm);
+    let min_pwm = calibration.as_ref()
+        .map(|c| c.min_pwm)
+        .filter(|&min_pwm| min_pwm > 0 && !zero_rpm);
+    if let Some(min_pwm) = min_pwm {
+        debug!("  Holding {} at or above its stall duty PWM {}", fan_path, min_pwm);
+    }
+
+    // 3-pin fans on a DC output stall at low voltage; until a calibration
+    // measures where, curve output is scaled above a typical stall duty
+    let dc_floor = (calibration.is_none() && hf_core::is_dc_mode(std::path::Path::new(fan_path)))
+        .then(|| hf_core::constants::pwm::from_percent(hf_core::constants::pwm::DC_FLOOR_PERCENT));
+    if dc_floor.is_some() {
+        info!("  {} is a DC (voltage) output - curve output scaled to {:.0}-100%",
+              fan_path, hf_core::constants::pwm::DC_FLOOR_PERCENT);
+    }
+    if calibration.as_ref().is_some_and(hf_core::ignores_pwm) {
+        warn!("{} runs at the same speed at every duty - a 3-pin fan needs the output in DC mode", fan_path);
+    }
+
+    let gpu = crate::gpu_fans::GpuFanControl::for_output(fan_path);
+    if gpu.is_some() {
+        debug!("  {} is a GPU fan - writes coalesced, idle fan-stop left to the card", fan_path);
+    }
+
+    ControlPairRuntime {
+        pair: control_pair,
+        curve_engine,
+        failsafe_temp,
+        failsafe_active: false,
+        rpm_loop,
+        calibration,
+        min_pwm,
+        dc_floor,
+        group: None,
+        curve_id: curve.id.clone(),
+        preview: None,
+        enforcement: pwm_pairing.map(|p| p.enforcement).unwrap_or_default(),
+        gpu,
+    }
+}
+
 /// Run the fan control loop with panic recovery and fallback handling
 pub async fn run_control_loop(state: Arc<FanControlState>, shutdown: Arc<AtomicBool>) {
     info!("Fan control loop starting (hardened mode)");
@@ -652,6 +1122,17 @@ pub async fn run_control_loop(state: Arc<FanControlState>, shutdown: Arc<AtomicB
     // Phase 0: Initialize ALL PWM controls (enable manual mode, run matching if needed)
     initialize_pwm_controls(&state).await;
 
+    if crate::safe_mode::is_enabled() {
+        run_safe_mode_loop(&state, &shutdown).await;
+        return;
+    }
+
+    let mut poller = crate::adaptive_poll::AdaptivePoller::default();
+
+    // Detect AC/battery before the first config load so offsets apply immediately
+    let mut power_monitor = crate::power_monitor::PowerMonitor::default();
+    power_monitor.tick(&state);
+
     // Initial config load - apply fallback if it fails
     if let Err(e) = load_config(&state).await {
         error!("Failed to load initial config: {} - applying fallback fan speed", e);
@@ -660,13 +1141,15 @@ pub async fn run_control_loop(state: Arc<FanControlState>, shutdown: Arc<AtomicB
         // Config loaded successfully - immediately apply fan curves
         // This ensures fans are set to correct speeds right after daemon starts
         info!("Applying initial fan curves immediately after config load");
-        if let Err(e) = process_control_iteration(&state).await {
+        if let Err(e) = process_control_iteration(&state, &mut poller).await {
             warn!("Initial curve application failed: {} - will retry in control loop", e);
         }
     }
 
     let mut consecutive_loop_errors: u32 = 0;
     let mut loop_iteration: u64 = 0;
+    let mut scheduler = crate::scheduler::ProfileScheduler::default();
+    let mut watchdog = crate::sd_notify::Watchdog::from_env();
 
     loop {
         loop_iteration += 1;
@@ -682,9 +1165,24 @@ pub async fn run_control_loop(state: Arc<FanControlState>, shutdown: Arc<AtomicB
         if loop_iteration % 60 == 0 {
             state.run_drift_validation().await;
         }
+        
+        // Switch profiles on schedule (rate-limited internally)
+        scheduler.tick(&state);
+
+        // Reload curves when switching between AC and battery (rate-limited internally)
+        power_monitor.tick(&state);
+
+        // Re-enumerate once hotplug events have settled
+        crate::hotplug::tick(&state).await;
+
+        // Apply config files edited on disk once writes have settled
+        crate::config_watch::tick(&state).await;
+
+        // Heartbeat for the systemd watchdog (stops if this loop stalls)
+        watchdog.tick();
 
         // Wrap the main loop body in catch_unwind equivalent via result handling
-        let loop_result = process_control_iteration(&state).await;
+        let loop_result = process_control_iteration(&state, &mut poller).await;
         
         match loop_result {
             Ok(()) => {
@@ -710,7 +1208,9 @@ pub async fn run_control_loop(state: Arc<FanControlState>, shutdown: Arc<AtomicB
         }
 
         // Sleep for poll interval, but wake up immediately if reload is signaled
-        let poll_ms = state.poll_interval_ms.load(Ordering::SeqCst);
+        let fixed_poll_ms = state.poll_interval_ms.load(Ordering::SeqCst);
+        let adaptive = *state.adaptive_polling.read().await;
+        let poll_ms = poller.next_interval_ms(&adaptive, fixed_poll_ms);
         tokio::select! {
             _ = tokio::time::sleep(Duration::from_millis(poll_ms)) => {}
             _ = state.reload_notify.notified() => {
@@ -722,8 +1222,24 @@ pub async fn run_control_loop(state: Arc<FanControlState>, shutdown: Arc<AtomicB
     info!("Fan control loop stopped");
 }
 
+/// Safe mode: no curves, no writes - only keep the watchdog fed until shutdown
+async fn run_safe_mode_loop(state: &FanControlState, shutdown: &AtomicBool) {
+    info!("SAFE MODE: control loop idle - restart without --safe-mode (and remove {}) to resume",
+          crate::safe_mode::SENTINEL_PATH);
+    let mut watchdog = crate::sd_notify::Watchdog::from_env();
+    while !shutdown.load(Ordering::SeqCst) {
+        watchdog.tick();
+        let poll_ms = state.poll_interval_ms.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(poll_ms)).await;
+    }
+    info!("Fan control loop stopped");
+}
+
 /// Process a single control iteration - separated for error handling
-async fn process_control_iteration(state: &FanControlState) -> Result<(), String> {
+async fn process_control_iteration(
+    state: &FanControlState,
+    poller: &mut crate::adaptive_poll::AdaptivePoller,
+) -> Result<(), String> {
     // Check for reload signal
     if state.check_reload_signal() {
         info!("SIGNAL: Configuration reload requested");
@@ -741,6 +1257,12 @@ async fn process_control_iteration(state: &FanControlState) -> Result<(), String
         return Ok(());
     }
 
+    // Active detection drives the PWM outputs itself
+    if state.detection.read().await.is_probing() {
+        debug!("Fan detection running, skipping iteration");
+        return Ok(());
+    }
+
     // Apply temporary overrides first (works even if there are no active pairs)
     // Also prune expired entries.
     let now = Instant::now();
@@ -784,6 +1306,40 @@ async fn process_control_iteration(state: &FanControlState) -> Result<(), String
             .collect()
     };
 
+    // Each source is read and filtered once per iteration, however many curves use it
+    let mut readings: HashMap<String, Result<f32, String>> = HashMap::new();
+    // Same for the health checks of sources with fallbacks
+    let mut checked: HashMap<String, Result<f32, String>> = HashMap::new();
+
+    // Evaluate each group's curve once; members add their offsets below
+    let mut group_outputs: HashMap<String, Option<(f32, f32)>> = HashMap::new();
+    for (group_id, group) in state.groups.write().await.iter_mut() {
+        let reading = read_source_chain(
+            state, &group.name, &group.temp_source_path, &group.temp_fallback_paths, &mut readings, &mut checked,
+        ).await;
+        let output = match reading {
+            Ok((t, source)) if t.is_finite() => {
+                debug!("READ: group {} temp={:.1}°C from {}", group.name, t, source);
+                poller.observe(&source, t, Instant::now());
+                let engine = CurvePreview::engine(&mut group.preview, &mut group.curve_engine, &group.name);
+                Some((t, engine.calculate(t)))
+            }
+            Ok((t, _)) => {
+                warn!("CONTROL: Non-finite temperature {} for group '{}' ({}); applying fallback {}%",
+                      t, group.name, group.temp_source_path, FALLBACK_FAN_PERCENT);
+                None
+            }
+            Err(e) => {
+                warn!("ACTION: Failed to read temp for group {} ({}): {} - applying fallback {}%",
+                      group.name, group.temp_source_path, e, FALLBACK_FAN_PERCENT);
+                None
+            }
+        };
+        group_outputs.insert(group_id.clone(), output);
+    }
+
+    let alarm_boost = state.alarm_boost.write().await.tick(Instant::now());
+
     // Process all active pairs
     for (pwm_path, runtime) in pairs.iter_mut() {
         // If overridden, skip curve control for this PWM.
@@ -797,50 +1353,210 @@ async fn process_control_iteration(state: &FanControlState) -> Result<(), String
             continue;
         }
 
-        // Read temperature - use fallback on failure
-        // FIX: Check for non-finite temperature IMMEDIATELY after reading, before any processing
-        let temp = match read_temperature_async(&runtime.pair.temp_source_path).await {
-            Ok(t) => {
-                // FIX: Non-finite check moved here, before interpolation
-                if !t.is_finite() {
-                    warn!(
-                        "CONTROL: Non-finite temperature read for '{}' ({}); applying fallback {}%",
-                        runtime.pair.name, runtime.pair.temp_source_path, FALLBACK_FAN_PERCENT
-                    );
+        // Group members follow the group's curve output plus their offset
+        let (temp, fan_percent) = if let Some(group) = runtime.group.as_ref() {
+            match group_outputs.get(&group.group_id).copied().flatten() {
+                Some((t, percent)) => (t, (percent + group.offset_percent).clamp(0.0, PERCENT_MAX)),
+                None => {
+                    // Group temperature unavailable (logged once per group above)
                     if let Err(pwm_err) = set_pwm_async(pwm_path, FALLBACK_PWM_VALUE).await {
                         error!("ACTION: Failed to set fallback PWM for {}: {}", runtime.pair.name, pwm_err);
                     }
                     continue;
                 }
-                debug!("READ: {} temp={:.1}°C from {}", runtime.pair.name, t, runtime.pair.temp_source_path);
-                t
             }
-            Err(e) => {
-                warn!("ACTION: Failed to read temp for {} ({}): {} - applying fallback {}%", 
-                      runtime.pair.name, runtime.pair.temp_source_path, e, FALLBACK_FAN_PERCENT);
-                // Use fallback speed for this fan
-                if let Err(pwm_err) = set_pwm_async(pwm_path, FALLBACK_PWM_VALUE).await {
-                    error!("ACTION: Failed to set fallback PWM for {}: {}", runtime.pair.name, pwm_err);
-                } else {
-                    info!("ACTION: Set fallback PWM {} ({}%) on {}", 
-                          FALLBACK_PWM_VALUE, FALLBACK_FAN_PERCENT, runtime.pair.name);
+        } else {
+            // Read temperature - use fallback on failure
+            // FIX: Check for non-finite temperature IMMEDIATELY after reading, before any processing
+            let reading = read_source_chain(
+                state, &runtime.pair.name, &runtime.pair.temp_source_path, &runtime.pair.temp_fallback_paths,
+                &mut readings, &mut checked,
+            ).await;
+            let temp = match reading {
+                Ok((t, source)) => {
+                    // FIX: Non-finite check moved here, before interpolation
+                    if !t.is_finite() {
+                        warn!(
+                            "CONTROL: Non-finite temperature read for '{}' ({}); applying fallback {}%",
+                            runtime.pair.name, runtime.pair.temp_source_path, FALLBACK_FAN_PERCENT
+                        );
+                        if let Err(pwm_err) = set_pwm_async(pwm_path, FALLBACK_PWM_VALUE).await {
+                            error!("ACTION: Failed to set fallback PWM for {}: {}", runtime.pair.name, pwm_err);
+                        }
+                        continue;
+                    }
+                    debug!("READ: {} temp={:.1}°C from {}", runtime.pair.name, t, source);
+                    poller.observe(&source, t, Instant::now());
+                    t
                 }
-                continue;
-            }
+                Err(e) => {
+                    warn!("ACTION: Failed to read temp for {} ({}): {} - applying fallback {}%", 
+                          runtime.pair.name, runtime.pair.temp_source_path, e, FALLBACK_FAN_PERCENT);
+                    // Use fallback speed for this fan
+                    if let Err(pwm_err) = set_pwm_async(pwm_path, FALLBACK_PWM_VALUE).await {
+                        error!("ACTION: Failed to set fallback PWM for {}: {}", runtime.pair.name, pwm_err);
+                    } else {
+                        info!("ACTION: Set fallback PWM {} ({}%) on {}", 
+                              FALLBACK_PWM_VALUE, FALLBACK_FAN_PERCENT, runtime.pair.name);
+                    }
+                    continue;
+                }
+            };
+
+            // Use FanCurve engine with hysteresis and smoothing (replaces raw interpolation)
+            // The engine maintains state for smooth transitions and prevents oscillation
+            let engine = CurvePreview::engine(&mut runtime.preview, &mut runtime.curve_engine, &runtime.pair.name);
+            (temp, engine.calculate(temp))
         };
 
-        // Use FanCurve engine with hysteresis and smoothing (replaces raw interpolation)
-        // The engine maintains state for smooth transitions and prevents oscillation
-        let fan_percent = runtime.curve_engine.calculate(temp);
+        // Convert percent to PWM value (linearized by the calibration table if there is one),
+        // or in RPM mode let the feedback loop pick it
+        let duty_value = match (runtime.calibration.as_ref(), runtime.dc_floor) {
+            (Some(calibration), _) => hf_core::calibrated_pwm(calibration, fan_percent),
+            (None, Some(floor)) => hf_core::dc_output_pwm(fan_percent, floor),
+            (None, None) => ((fan_percent / PERCENT_MAX) * PWM_MAX).clamp(0.0, PWM_MAX).round() as u8,
+        };
+        let mut pwm_value = match runtime.rpm_loop.as_mut() {
+            Some(rpm_loop) => match read_fan_rpm_async(state, &rpm_loop.fan_path).await {
+                Ok(rpm) => {
+                    let value = rpm_loop.controller.update(fan_percent, rpm);
+                    debug!("CONTROL: '{}' at {} RPM, target {} RPM -> PWM {}",
+                           runtime.pair.name, rpm, rpm_loop.controller.target_rpm(fan_percent), value);
+                    value
+                }
+                Err(e) => {
+                    debug!("READ: RPM for '{}' unavailable ({}) - using duty cycle", runtime.pair.name, e);
+                    duty_value
+                }
+            },
+            None => duty_value,
+        };
 
-        // Convert percent to PWM value
-        let pwm_value = ((fan_percent / PERCENT_MAX) * PWM_MAX).clamp(0.0, PWM_MAX).round() as u8;
+        // Keep a spinning fan from stalling (curve points below its stop duty)
+        if let Some(min_pwm) = runtime.min_pwm {
+            if pwm_value < min_pwm {
+                debug!("CONTROL: '{}' PWM {} is below stall duty - raised to {}",
+                       runtime.pair.name, pwm_value, min_pwm);
+                pwm_value = min_pwm;
+            }
+        }
+
+        // Source at its failsafe threshold - full speed regardless of the curve,
+        // released once it has cooled by the margin
+        let release_temp = runtime.failsafe_temp - temperature::FAILSAFE_MARGIN;
+        let over_limit = temp >= if runtime.failsafe_active { release_temp } else { runtime.failsafe_temp };
+        if over_limit != runtime.failsafe_active {
+            runtime.failsafe_active = over_limit;
+            if over_limit {
+                warn!("FAILSAFE: '{}' at {:.1}°C (limit {:.1}°C) - forcing full speed",
+                      runtime.pair.name, temp, runtime.failsafe_temp);
+            } else {
+                info!("FAILSAFE: '{}' back to {:.1}°C - resuming curve control", runtime.pair.name, temp);
+            }
+            crate::events::publish(hf_protocol::Event::ThermalAlert {
+                name: runtime.pair.name.clone(),
+                temp_source_path: runtime.pair.temp_source_path.clone(),
+                celsius: temp,
+                limit_celsius: runtime.failsafe_temp,
+                active: over_limit,
+            });
+        }
+        if over_limit {
+            pwm_value = PWM_MAX as u8;
+        }
+
+        // Another fan in this pair has stalled - run at full speed to compensate
+        // (the monitor is only locked briefly: GetFanAlerts reads it between writes)
+        let compensate = state.stall_monitor.read().await.needs_compensation(pwm_path);
+        if compensate {
+            debug!("CONTROL: Compensating for stalled fan in '{}' - forcing full speed", runtime.pair.name);
+            pwm_value = PWM_MAX as u8;
+        }
+
+        // A chip alarm is raised (or cooling down after one) - full speed
+        if alarm_boost {
+            pwm_value = PWM_MAX as u8;
+        }
+
+        // Dry run: log what would be written (on change) instead of writing it
+        if crate::dry_run::is_enabled() {
+            if state.intended_writes.write().await.record(pwm_path, pwm_value) {
+                let reason = if over_limit {
+                    " - failsafe"
+                } else if compensate {
+                    " - stall compensation"
+                } else if alarm_boost {
+                    " - alarm boost"
+                } else if runtime.preview.is_some() {
+                    " - curve preview"
+                } else {
+                    ""
+                };
+                info!("DRY-RUN: would set PWM {} ({:.0}%) on '{}' ({}) from {:.1}°C at {} via curve '{}'{}",
+                      pwm_value, fan_percent, runtime.pair.name, pwm_path, temp,
+                      runtime.pair.temp_source_path, runtime.curve_id, reason);
+            }
+            continue;
+        }
+
+        let force = over_limit || compensate || alarm_boost;
+
+        // GPU fan: skip small or too frequent changes, leave idle to the card's fan-stop
+        if let Some(gpu) = runtime.gpu.as_mut() {
+            match gpu.plan(pwm_value, force, Instant::now()) {
+                crate::gpu_fans::GpuWrite::Write => {}
+                crate::gpu_fans::GpuWrite::Hold => continue,
+                crate::gpu_fans::GpuWrite::Firmware => {
+                    let path = pwm_path.to_string();
+                    match tokio::task::spawn_blocking(move || crate::gpu_fans::hand_to_firmware(&path)).await {
+                        Ok(Ok(())) => {
+                            info!("CONTROL: '{}' at {:.0}% - GPU fan handed to the card's idle fan-stop",
+                                  runtime.pair.name, fan_percent);
+                            gpu.handed_to_firmware();
+                        }
+                        Ok(Err(e)) => warn!("CONTROL: Failed to hand '{}' to the card: {}", runtime.pair.name, e),
+                        Err(e) => warn!("CONTROL: GPU fan reset task panicked: {}", e),
+                    }
+                    continue;
+                }
+            }
+        }
+
+        // Another program wrote the output since the last write: the pairing's policy decides
+        if !crate::foreign_writes::allow_write(&runtime.pair.name, pwm_path, pwm_value, runtime.enforcement, force).await {
+            continue;
+        }
 
         // Set PWM - log at debug level (too verbose for info at 200ms intervals)
         match set_pwm_async(pwm_path, pwm_value).await {
             Ok(()) => {
+                state.stall_monitor.write().await.record_write(pwm_path, &runtime.pair.name, pwm_value, true);
+                if let Some(gpu) = runtime.gpu.as_mut() {
+                    if gpu.is_idle() {
+                        info!("CONTROL: '{}' at {:.0}% - resuming GPU fan curve control", runtime.pair.name, fan_percent);
+                    }
+                    gpu.written(pwm_value, Instant::now());
+                }
                 debug!("CONTROL: Set PWM {} ({}%) on '{}' (temp={:.1}°C)", 
                        pwm_value, fan_percent as u8, runtime.pair.name, temp);
+
+                let fan_input = {
+                    let stall_monitor = state.stall_monitor.read().await;
+                    stall_monitor.watch(pwm_path).filter(|_| stall_monitor.is_enabled()).map(|w| w.fan_path.clone())
+                };
+                if let Some(fan_input) = fan_input {
+                    match read_fan_rpm_async(state, &fan_input).await {
+                        Ok(rpm) => {
+                            state.stall_monitor.write().await.observe(pwm_path, pwm_value, rpm, Instant::now());
+                        }
+                        Err(e) => debug!("READ: Failed to read RPM for '{}': {}", runtime.pair.name, e),
+                    }
+                }
+            }
+            Err(PwmWriteError::Unverified(e)) => {
+                // Flags the output so the rest of its pair compensates
+                debug!("CONTROL: PWM {} on '{}' did not stick: {}", pwm_value, runtime.pair.name, e);
+                state.stall_monitor.write().await.record_write(pwm_path, &runtime.pair.name, pwm_value, false);
             }
             Err(e) => {
                 // Errors are always logged at warn/error level
@@ -854,13 +1570,114 @@ async fn process_control_iteration(state: &FanControlState) -> Result<(), String
 
 /// Read temperature from a sensor path (async version - doesn't block the executor)
 /// Uses spawn_blocking to run file I/O on a separate thread pool
-async fn read_temperature_async(path: &str) -> Result<f32, String> {
+pub(crate) async fn read_temperature_async(path: &str) -> Result<f32, String> {
     let path = path.to_string();
     tokio::task::spawn_blocking(move || read_temperature_inner(&path))
         .await
         .map_err(|e| format!("Temperature read task panicked: {}", e))?
 }
 
+/// Read a curve's temperature source through its filter
+/// The first read of a path in an iteration is stored in `readings` and reused,
+/// so a filter advances once per iteration. Spike rejection delays a real jump
+/// by a few samples at most, so the failsafe still sees sustained overheating.
+async fn read_filtered_temperature(
+    state: &FanControlState,
+    path: &str,
+    readings: &mut HashMap<String, Result<f32, String>>,
+) -> Result<f32, String> {
+    if let Some(reading) = readings.get(path) {
+        return reading.clone();
+    }
+    let mut reading = read_temperature_async(path).await;
+    // Implausible readings never reach the filter (or its history)
+    if let Ok(raw) = reading {
+        reading = state.plausibility.write().await.check_temperature(path, raw);
+    }
+    if let Ok(raw) = reading {
+        if let Some(filter) = state.filters.write().await.get_mut(path) {
+            let filtered = filter.apply(raw);
+            if filtered != raw {
+                debug!("FILTER: {} {:.1}°C -> {:.1}°C", path, raw, filtered);
+            }
+            reading = Ok(filtered);
+        }
+    }
+    readings.insert(path.to_string(), reading.clone());
+    reading
+}
+
+/// Read a pair's or group's temperature, failing over along its curve's fallback sources
+/// Returns the temperature and the source it came from. Health checks run once per
+/// source and iteration (`checked`); see `source_failover`.
+async fn read_source_chain(
+    state: &FanControlState,
+    name: &str,
+    primary: &str,
+    fallbacks: &[String],
+    readings: &mut HashMap<String, Result<f32, String>>,
+    checked: &mut HashMap<String, Result<f32, String>>,
+) -> Result<(f32, String), String> {
+    if fallbacks.is_empty() {
+        return read_filtered_temperature(state, primary, readings).await.map(|t| (t, primary.to_string()));
+    }
+
+    let chain: Vec<&str> = std::iter::once(primary).chain(fallbacks.iter().map(String::as_str)).collect();
+    let mut failures = Vec::new();
+    for (index, path) in chain.iter().enumerate() {
+        let reading = match checked.get(*path) {
+            Some(reading) => reading.clone(),
+            None => {
+                let raw = read_filtered_temperature(state, path, readings).await;
+                let reading = state.source_health.write().await.check(path, raw);
+                checked.insert(path.to_string(), reading.clone());
+                reading
+            }
+        };
+        let t = match reading {
+            Ok(t) => t,
+            Err(e) => {
+                failures.push(e);
+                continue;
+            }
+        };
+        if let Some(previous) = state.source_health.write().await.select(&chain.join("|"), index) {
+            // Every source before this one failed just now
+            let reason = failures.get(previous).cloned().unwrap_or_else(|| "recovered".to_string());
+            warn!("CONTROL: '{}' switched temperature source {} -> {} ({})", name, chain[previous], path, reason);
+            crate::events::publish(hf_protocol::Event::TemperatureSourceSwitched {
+                name: name.to_string(),
+                from: chain[previous].to_string(),
+                to: path.to_string(),
+                reason,
+            });
+        }
+        return Ok((t, path.to_string()));
+    }
+    Err(format!("all {} sources failed: {}", chain.len(), failures.join(", ")))
+}
+
+/// Rebuild the per-source filters, keeping the state of those that did not change
+async fn set_sensor_filters(state: &FanControlState, filters: &[hf_core::SensorFilter]) {
+    let mut current = state.filters.write().await;
+    let mut next = HashMap::new();
+    for filter in filters {
+        if let Err(e) = filter.validate() {
+            warn!("Ignoring filter for {}: {}", filter.path, e);
+            continue;
+        }
+        let filter_state = match current.remove(&filter.path) {
+            Some(existing) if existing.filter() == filter => existing,
+            _ => hf_core::FilterState::new(filter),
+        };
+        next.insert(filter.path.clone(), filter_state);
+    }
+    if !next.is_empty() {
+        info!("Sensor filters: {}", next.keys().cloned().collect::<Vec<_>>().join(", "));
+    }
+    *current = next;
+}
+
 /// Read temperature from a sensor path (safe version with panic protection)
 /// DEPRECATED: Use read_temperature_async for non-blocking I/O
 #[allow(dead_code)]
@@ -869,16 +1686,59 @@ fn read_temperature_safe(path: &str) -> Result<f32, String> {
         .map_err(|_| "Panic during temperature read".to_string())?
 }
 
+/// Virtual sensor definitions from the last config load (shared with the server)
+static VIRTUAL_SENSORS: std::sync::RwLock<Vec<hf_core::VirtualSensor>> = std::sync::RwLock::new(Vec::new());
+
+fn set_virtual_sensors(sensors: &[hf_core::VirtualSensor]) {
+    let valid: Vec<_> = sensors.iter()
+        .filter(|s| match s.validate() {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Ignoring virtual sensor '{}': {}", s.id, e);
+                false
+            }
+        })
+        .cloned()
+        .collect();
+    if !valid.is_empty() {
+        info!("Virtual sensors: {}", valid.iter().map(|s| s.name.as_str()).collect::<Vec<_>>().join(", "));
+    }
+    *VIRTUAL_SENSORS.write().unwrap_or_else(|e| e.into_inner()) = valid;
+}
+
+/// Virtual sensors currently defined
+pub(crate) fn virtual_sensors() -> Vec<hf_core::VirtualSensor> {
+    VIRTUAL_SENSORS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
 /// Inner temperature read function (blocking - run via spawn_blocking)
-fn read_temperature_inner(path: &str) -> Result<f32, String> {
+pub(crate) fn read_temperature_inner(path: &str) -> Result<f32, String> {
+    // Virtual sensors are computed from their (hardware) inputs
+    if let Some(id) = hf_core::virtual_sensor_id(path) {
+        let sensors = VIRTUAL_SENSORS.read().unwrap_or_else(|e| e.into_inner());
+        let sensor = sensors.iter().find(|s| s.id == id)
+            .ok_or_else(|| format!("Unknown virtual sensor '{}'", id))?;
+        return sensor.evaluate(read_temperature_inner);
+    }
+
+    // External provider readings are cached by their runner
+    if path.starts_with(hf_protocol::PROVIDER_PATH_PREFIX) {
+        return crate::sensor_providers::read_temperature(path);
+    }
+
     // Handle GPU temperature paths (gpu:N:name format)
+    // "GPU" (or no name) selects the primary sensor, anything else a sensor by name
     if path.starts_with("gpu:") {
-        let parts: Vec<&str> = path.split(':').collect();
+        let parts: Vec<&str> = path.splitn(3, ':').collect();
         if parts.len() >= 2 {
             if let Ok(index) = parts[1].parse::<u32>() {
                 if let Ok(gpus) = hf_core::enumerate_gpus() {
                     if let Some(gpu) = gpus.iter().find(|g| g.index == index) {
-                        if let Some(temp) = gpu.temperatures.first().and_then(|t| t.current_temp) {
+                        let sensor = match parts.get(2) {
+                            Some(name) if *name != "GPU" => gpu.temperatures.iter().find(|t| t.name == *name),
+                            _ => gpu.temperatures.first(),
+                        };
+                        if let Some(temp) = sensor.and_then(|t| t.current_temp) {
                             return Ok(temp);
                         }
                     }
@@ -893,6 +1753,12 @@ fn read_temperature_inner(path: &str) -> Result<f32, String> {
         return Err(e);
     }
 
+    // FreeBSD sysctl sensors, smartctl drive temperatures and IPMI sensors
+    // report degrees, not millidegrees
+    if path.starts_with(SYSCTL_PATH_PREFIX) || path.starts_with(SMART_PATH_PREFIX) || path.starts_with(IPMI_PATH_PREFIX) {
+        return hf_core::read_temperature(std::path::Path::new(path)).map_err(|e| e.to_string());
+    }
+
     let content = std::fs::read_to_string(path)
         .map_err(|e| format!("Failed to read {}: {}", path, e))?;
 
@@ -908,13 +1774,73 @@ fn read_temperature_inner(path: &str) -> Result<f32, String> {
     Ok(c)
 }
 
+/// Read fan RPM from a tachometer path (async - runs file I/O via spawn_blocking)
+/// Speeds above `plausibility::MAX_FAN_RPM` are rejected as read errors
+async fn read_fan_rpm_async(state: &FanControlState, path: &str) -> Result<u32, String> {
+    let owned = path.to_string();
+    let rpm = tokio::task::spawn_blocking(move || {
+        validate_hwmon_path(&owned)?;
+        if owned.starts_with(SYSCTL_PATH_PREFIX) || owned.starts_with(IPMI_PATH_PREFIX) {
+            return hf_core::read_fan_rpm(std::path::Path::new(&owned)).map_err(|e| e.to_string());
+        }
+        let content = std::fs::read_to_string(&owned)
+            .map_err(|e| format!("Failed to read {}: {}", owned, e))?;
+        content.trim().parse::<u32>()
+            .map_err(|e| format!("Failed to parse RPM: {}", e))
+    })
+    .await
+    .map_err(|e| format!("RPM read task panicked: {}", e))??;
+    state.plausibility.write().await.check_rpm(path, rpm)
+}
+
+/// Look up the detection result for a sysfs PWM path
+/// Detection stores mappings as "chip_name/pwmN", so resolve the chip name first
+fn learned_mapping<'a>(mappings: &'a [hf_core::FanMapping], pwm_path: &str) -> Option<&'a hf_core::FanMapping> {
+    let path = std::path::Path::new(pwm_path);
+    let chip_name = std::fs::read_to_string(path.parent()?.join("name")).ok()?;
+    let pwm_name = format!("{}/{}", chip_name.trim(), path.file_name()?.to_string_lossy());
+    mappings.iter().find(|m| m.pwm_name == pwm_name)
+}
+
+/// Minimum PWM learned during detection for a sysfs PWM path
+fn learned_min_pwm(mappings: &[hf_core::FanMapping], pwm_path: &str) -> Option<u8> {
+    learned_mapping(mappings, pwm_path).and_then(|m| m.min_pwm)
+}
+
+/// Fan RPM at full duty learned during detection for a sysfs PWM path
+fn learned_max_rpm(mappings: &[hf_core::FanMapping], pwm_path: &str) -> Option<u32> {
+    learned_mapping(mappings, pwm_path).and_then(|m| m.max_rpm)
+}
+
 // NOTE: interpolate_curve() has been REMOVED - we now use hf_core::FanCurve::calculate()
 // which provides hysteresis and smoothing for better fan control behavior.
 // The FanCurve engine is integrated into ControlPairRuntime.
 
+/// Why a PWM write failed
+#[derive(Debug)]
+enum PwmWriteError {
+    /// The write was accepted but never read back as written: the channel is unhealthy
+    Unverified(String),
+    Failed(String),
+}
+
+impl std::fmt::Display for PwmWriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PwmWriteError::Unverified(msg) | PwmWriteError::Failed(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl From<String> for PwmWriteError {
+    fn from(msg: String) -> Self {
+        PwmWriteError::Failed(msg)
+    }
+}
+
 /// Set PWM value (async version - doesn't block the executor)
 /// Uses spawn_blocking to run file I/O on a separate thread pool
-async fn set_pwm_async(pwm_path: &str, value: u8) -> Result<(), String> {
+async fn set_pwm_async(pwm_path: &str, value: u8) -> Result<(), PwmWriteError> {
     let pwm_path = pwm_path.to_string();
     tokio::task::spawn_blocking(move || set_pwm_inner(&pwm_path, value))
         .await
@@ -923,15 +1849,29 @@ async fn set_pwm_async(pwm_path: &str, value: u8) -> Result<(), String> {
 
 /// Set PWM value with enable check (safe version with panic protection)
 /// Handles both sysfs PWM (motherboard, AMD, Intel) and NVIDIA GPU fans
-fn set_pwm_safe(pwm_path: &str, value: u8) -> Result<(), String> {
+fn set_pwm_safe(pwm_path: &str, value: u8) -> Result<(), PwmWriteError> {
     std::panic::catch_unwind(|| set_pwm_inner(pwm_path, value))
         .map_err(|_| "Panic during PWM write".to_string())?
 }
 
 /// Inner PWM set function - handles both sysfs and NVIDIA GPU fans
-fn set_pwm_inner(pwm_path: &str, value: u8) -> Result<(), String> {
-    if let Err(e) = validate_pwm_target_path(pwm_path) {
-        return Err(e);
+fn set_pwm_inner(pwm_path: &str, value: u8) -> Result<(), PwmWriteError> {
+    validate_pwm_target_path(pwm_path)?;
+
+    // Don't re-enable manual mode after the original state was restored
+    if crate::SHUTDOWN.load(Ordering::SeqCst) {
+        return Err("Daemon is shutting down".to_string().into());
+    }
+
+    // Safe mode: fans stay under firmware control, whoever asks
+    if crate::safe_mode::is_enabled() {
+        return Err("Daemon is in safe mode".to_string().into());
+    }
+
+    // Curve outputs are logged by the control loop; fallbacks and overrides end here
+    if crate::dry_run::is_enabled() {
+        debug!("DRY-RUN: skipped PWM write {} = {}", pwm_path, value);
+        return Ok(());
     }
 
     // Handle NVIDIA GPU fans (virtual path format: nvidia:gpu_index:fan_index)
@@ -944,9 +1884,10 @@ fn set_pwm_inner(pwm_path: &str, value: u8) -> Result<(), String> {
             
             hf_core::set_nvidia_fan_speed(gpu_idx, fan_idx, percent)
                 .map_err(|e| format!("NVIDIA fan control failed: {}", e))?;
+            crate::gpu_fans::record_write(pwm_path);
             return Ok(());
         }
-        return Err("Invalid NVIDIA PWM path format".to_string());
+        return Err("Invalid NVIDIA PWM path format".to_string().into());
     }
     
     // Handle AMD/Intel GPU fans (virtual path format: amd:card:fan or intel:card:fan)
@@ -954,8 +1895,18 @@ fn set_pwm_inner(pwm_path: &str, value: u8) -> Result<(), String> {
         let percent = ((value as f32 / 255.0) * 100.0).round() as u32;
         hf_core::set_gpu_fan_speed_by_id(pwm_path, percent)
             .map_err(|e| format!("GPU fan control failed: {}", e))?;
+        crate::gpu_fans::record_write(pwm_path);
         return Ok(());
     }
+
+    // FreeBSD sysctl fan levels and IPMI fan zones (the backend switches the
+    // fan to manual itself) and thermal cooling devices (scaled to their cur_state range)
+    if pwm_path.starts_with(SYSCTL_PATH_PREFIX)
+        || pwm_path.starts_with(IPMI_PATH_PREFIX)
+        || hf_core::is_cooling_device_path(std::path::Path::new(pwm_path)) {
+        return hf_core::set_pwm_value(std::path::Path::new(pwm_path), value)
+            .map_err(|e| PwmWriteError::Failed(format!("Failed to write PWM: {}", e)));
+    }
     
     // Standard sysfs PWM control (motherboard SuperIO chips)
     let path = std::path::Path::new(pwm_path);
@@ -965,12 +1916,12 @@ fn set_pwm_inner(pwm_path: &str, value: u8) -> Result<(), String> {
         .ok_or_else(|| "Invalid PWM path".to_string())?;
 
     if !file_name.starts_with("pwm") {
-        return Err("PWM path does not point to a pwmN control file".to_string());
+        return Err("PWM path does not point to a pwmN control file".to_string().into());
     }
 
     let suffix = &file_name[3..];
     if suffix.is_empty() || !suffix.chars().all(|c| c.is_ascii_digit()) {
-        return Err("PWM path does not point to a pwmN control file".to_string());
+        return Err("PWM path does not point to a pwmN control file".to_string().into());
     }
 
     let enable_path = path
@@ -984,20 +1935,35 @@ fn set_pwm_inner(pwm_path: &str, value: u8) -> Result<(), String> {
     // Many fans won't stop at PWM 0 with enable=1, they just spin at minimum RPM
     if std::path::Path::new(&enable_path).exists() {
         let target_mode = if value == 0 { 0 } else { 1 };
-        let current_mode = std::fs::read_to_string(&enable_path)
-            .ok()
-            .and_then(|s| s.trim().parse::<u8>().ok())
-            .unwrap_or(0);
+        let read_mode = || {
+            std::fs::read_to_string(&enable_path)
+                .ok()
+                .and_then(|s| s.trim().parse::<u8>().ok())
+                .unwrap_or(0)
+        };
+        let current_mode = read_mode();
+        crate::competing_software::check_enable_mode(pwm_path, current_mode);
 
         if current_mode != target_mode {
             std::fs::write(&enable_path, target_mode.to_string())
                 .map_err(|e| format!("Failed to set PWM enable mode {}: {}", target_mode, e))?;
+            // Some drivers substitute another mode; compare later reads with what stuck
+            crate::competing_software::record_enable_mode(pwm_path, read_mode());
+        } else {
+            crate::competing_software::record_enable_mode(pwm_path, current_mode);
         }
     }
 
-    // Set PWM value (even when disabled, set to 0 for consistency)
-    std::fs::write(pwm_path, value.to_string())
-        .map_err(|e| format!("Failed to write PWM: {}", e))?;
+    // Set PWM value (even when disabled, set to 0 for consistency); hf-core
+    // sequences the write on chips with write quirks and reads it back
+    hf_core::set_pwm_value(path, value).map_err(|e| {
+        crate::foreign_writes::forget(pwm_path);
+        match e {
+            hf_core::HyperfanError::PwmWriteVerifyFailed { .. } => PwmWriteError::Unverified(e.to_string()),
+            e => PwmWriteError::Failed(format!("Failed to write PWM: {}", e)),
+        }
+    })?;
+    crate::foreign_writes::record_write(pwm_path, value);
 
     Ok(())
 }
@@ -1057,6 +2023,7 @@ mod tests {
             name: "Test Fan".to_string(),
             pwm_path: "/sys/class/hwmon/hwmon0/pwm1".to_string(),
             temp_source_path: "/sys/class/hwmon/hwmon0/temp1_input".to_string(),
+            temp_fallback_paths: Vec::new(),
             curve_points: points.iter().map(|p| (p.temperature, p.fan_percent)).collect(),
             active: true,
         };
@@ -1068,9 +2035,36 @@ mod tests {
         let runtime = ControlPairRuntime {
             pair,
             curve_engine,
+            failsafe_temp: hf_core::TemperatureLimits::default().failsafe_threshold(),
+            failsafe_active: false,
+            rpm_loop: None,
+            calibration: None,
+            min_pwm: None,
+            dc_floor: None,
+            group: None,
+            curve_id: "test".to_string(),
+            preview: None,
+            enforcement: hf_protocol::PwmEnforcement::default(),
+            gpu: None,
         };
         
         assert!(runtime.pair.active);
         assert_eq!(runtime.pair.name, "Test Fan");
     }
+
+    #[test]
+    fn test_curve_preview_expires() {
+        let mut saved = FanCurve::new(vec![CurvePoint { temperature: 30.0, fan_percent: 20.0 }]);
+        let now = Instant::now();
+        let mut preview = Some(CurvePreview::new(
+            &saved, &[(30.0, 80.0)], hf_core::CurveInterpolation::Linear, now + Duration::from_secs(60),
+        ));
+        let engine = CurvePreview::engine(&mut preview, &mut saved, "test");
+        assert_eq!(engine.points()[0].fan_percent, 80.0);
+
+        preview.as_mut().unwrap().expires_at = now;
+        let engine = CurvePreview::engine(&mut preview, &mut saved, "test");
+        assert_eq!(engine.points()[0].fan_percent, 20.0);
+        assert!(preview.is_none());
+    }
 }
@@ -0,0 +1,252 @@
+//! Foreign PWM Writes
+//!
+//! Every value the daemon writes to a sysfs PWM output is remembered as it
+//! read back. Before the control loop writes a curve value it reads the
+//! output again; a value that moved since means another program (a vendor
+//! tool, a script, a second fan daemon) wrote it. That is logged with a
+//! `ForeignPwmWrite` event, and the pairing's enforcement policy decides what
+//! happens next:
+//!
+//! - `Reassert`: the curve value is written as usual.
+//! - `Yield`: the foreign value is left alone until the output has stopped
+//!   changing for [`YIELD_QUIET_TIME`].
+//! - `AlertOnly`: the foreign value is left alone until the curve output
+//!   itself moves.
+//!
+//! The failsafe and stall compensation always write. Only hwmon outputs are
+//! watched: GPU, IPMI and sysctl backends manage their own state.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use hf_protocol::PwmEnforcement;
+
+/// Read-back differences up to this much are driver rounding, not another writer
+const TOLERANCE: u8 = 3;
+
+/// A foreign write on the same output is logged again at most this often
+const LOG_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `Yield` resumes once the foreign value has been left unchanged this long
+const YIELD_QUIET_TIME: Duration = Duration::from_secs(5 * 60);
+
+/// Foreign value currently left in place (`Yield` and `AlertOnly`)
+#[derive(Debug, Clone, Copy)]
+struct Yielded {
+    found: u8,
+    /// Curve value when the foreign write was found
+    target: u8,
+    last_change: Instant,
+}
+
+/// What to do about one output this iteration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Observation {
+    write: bool,
+    /// (expected, found) when a foreign write should be logged
+    log: Option<(u8, u8)>,
+}
+
+/// Values the daemon left in each output
+struct WriteMonitor {
+    written: BTreeMap<String, u8>,
+    yielded: BTreeMap<String, Yielded>,
+    last_logged: BTreeMap<String, Instant>,
+}
+
+fn differs(a: u8, b: u8) -> bool {
+    a.abs_diff(b) > TOLERANCE
+}
+
+impl WriteMonitor {
+    const fn new() -> Self {
+        Self { written: BTreeMap::new(), yielded: BTreeMap::new(), last_logged: BTreeMap::new() }
+    }
+
+    fn record(&mut self, pwm_path: &str, value: u8) {
+        self.written.insert(pwm_path.to_string(), value);
+        self.yielded.remove(pwm_path);
+    }
+
+    fn forget(&mut self, pwm_path: &str) {
+        self.written.remove(pwm_path);
+        self.yielded.remove(pwm_path);
+    }
+
+    /// Compare the value found in an output with the last one written
+    fn observe(&mut self, pwm_path: &str, current: u8, target: u8, policy: PwmEnforcement, now: Instant) -> Observation {
+        let write = Observation { write: true, log: None };
+        let Some(&expected) = self.written.get(pwm_path) else {
+            return write;
+        };
+        if !differs(current, expected) {
+            self.yielded.remove(pwm_path);
+            return write;
+        }
+
+        // A new foreign value (or the first one) is logged, rate-limited per output
+        let changed = self.yielded.get(pwm_path).is_none_or(|y| differs(current, y.found));
+        let log = (changed && self.last_logged.get(pwm_path).is_none_or(|t| now.duration_since(*t) >= LOG_INTERVAL))
+            .then_some((expected, current));
+        if log.is_some() {
+            self.last_logged.insert(pwm_path.to_string(), now);
+        }
+
+        let resume = match policy {
+            PwmEnforcement::Reassert => true,
+            PwmEnforcement::Yield | PwmEnforcement::AlertOnly => {
+                let yielded = self.yielded
+                    .entry(pwm_path.to_string())
+                    .or_insert(Yielded { found: current, target, last_change: now });
+                if differs(current, yielded.found) {
+                    yielded.found = current;
+                    yielded.last_change = now;
+                }
+                match policy {
+                    PwmEnforcement::Yield => now.duration_since(yielded.last_change) >= YIELD_QUIET_TIME,
+                    _ => differs(target, yielded.target),
+                }
+            }
+        };
+        Observation { write: resume, log }
+    }
+}
+
+static MONITOR: Mutex<WriteMonitor> = Mutex::new(WriteMonitor::new());
+
+/// Whether writes to `pwm_path` are compared with later reads
+fn is_monitored(pwm_path: &str) -> bool {
+    pwm_path.starts_with("/sys/") && !hf_core::is_cooling_device_path(std::path::Path::new(pwm_path))
+}
+
+/// Remember what a write of `requested` to `pwm_path` left in the output
+/// Outputs set to 0 may be switched off or to full speed by their driver, so
+/// they are not compared until the next non-zero write.
+pub fn record_write(pwm_path: &str, requested: u8) {
+    if !is_monitored(pwm_path) {
+        return;
+    }
+    let read_back = (requested > 0)
+        .then(|| hf_core::read_pwm_value(std::path::Path::new(pwm_path)).unwrap_or(requested));
+    if let Ok(mut monitor) = MONITOR.lock() {
+        match read_back {
+            Some(value) => monitor.record(pwm_path, value),
+            None => monitor.forget(pwm_path),
+        }
+    }
+}
+
+/// Stop comparing `pwm_path`, e.g. after a write that didn't stick
+pub fn forget(pwm_path: &str) {
+    if let Ok(mut monitor) = MONITOR.lock() {
+        monitor.forget(pwm_path);
+    }
+}
+
+/// Stop comparing every output, e.g. after the original state was restored
+pub fn forget_all() {
+    if let Ok(mut monitor) = MONITOR.lock() {
+        monitor.written.clear();
+        monitor.yielded.clear();
+    }
+}
+
+/// Check a controlled output for a foreign write before writing `target`
+/// Returns whether the control loop should write; `force` (failsafe, stall
+/// compensation) writes regardless of the policy
+pub async fn allow_write(name: &str, pwm_path: &str, target: u8, policy: PwmEnforcement, force: bool) -> bool {
+    if !is_monitored(pwm_path) {
+        return true;
+    }
+    let path = pwm_path.to_string();
+    let current = match tokio::task::spawn_blocking(move || hf_core::read_pwm_value(std::path::Path::new(&path))).await {
+        Ok(Ok(value)) => value,
+        _ => return true,
+    };
+
+    let observation = match MONITOR.lock() {
+        Ok(mut monitor) => monitor.observe(pwm_path, current, target, policy, Instant::now()),
+        Err(_) => return true,
+    };
+    let write = force || observation.write;
+
+    if let Some((expected, found)) = observation.log {
+        let action = match (write, policy) {
+            (true, _) => "re-asserting the curve value",
+            (false, PwmEnforcement::Yield) => "yielding until it stops changing",
+            (false, _) => "leaving it until the curve output changes",
+        };
+        warn!(
+            "CONFLICT: '{}' ({}) was set to {} by another program (daemon wrote {}) - {}",
+            name, pwm_path, found, expected, action
+        );
+        crate::events::publish(hf_protocol::Event::ForeignPwmWrite {
+            name: name.to_string(),
+            pwm_path: pwm_path.to_string(),
+            expected,
+            found,
+            policy,
+        });
+    } else if !write {
+        debug!("CONTROL: Leaving foreign PWM value {} on '{}' ({:?})", current, name, policy);
+    }
+    write
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATH: &str = "/sys/class/hwmon/hwmon2/pwm1";
+
+    #[test]
+    fn test_reassert_logs_once_per_interval() {
+        let mut monitor = WriteMonitor::new();
+        let start = Instant::now();
+
+        // Nothing written yet, then the daemon's own value (with driver rounding)
+        assert_eq!(monitor.observe(PATH, 200, 120, PwmEnforcement::Reassert, start), Observation { write: true, log: None });
+        monitor.record(PATH, 120);
+        assert_eq!(monitor.observe(PATH, 122, 120, PwmEnforcement::Reassert, start).log, None);
+
+        let first = monitor.observe(PATH, 255, 120, PwmEnforcement::Reassert, start);
+        assert_eq!(first, Observation { write: true, log: Some((120, 255)) });
+        monitor.record(PATH, 120);
+        assert_eq!(monitor.observe(PATH, 255, 120, PwmEnforcement::Reassert, start + Duration::from_secs(1)).log, None);
+        monitor.record(PATH, 120);
+        assert!(monitor.observe(PATH, 255, 120, PwmEnforcement::Reassert, start + LOG_INTERVAL).log.is_some());
+    }
+
+    #[test]
+    fn test_yield_waits_for_quiet_output() {
+        let mut monitor = WriteMonitor::new();
+        let start = Instant::now();
+        monitor.record(PATH, 120);
+
+        assert!(!monitor.observe(PATH, 60, 120, PwmEnforcement::Yield, start).write);
+        // Changed again: the quiet time starts over
+        let changed = start + Duration::from_secs(60);
+        assert!(!monitor.observe(PATH, 90, 120, PwmEnforcement::Yield, changed).write);
+        assert!(!monitor.observe(PATH, 90, 120, PwmEnforcement::Yield, start + YIELD_QUIET_TIME).write);
+        assert!(monitor.observe(PATH, 90, 120, PwmEnforcement::Yield, changed + YIELD_QUIET_TIME).write);
+    }
+
+    #[test]
+    fn test_alert_only_waits_for_curve_change() {
+        let mut monitor = WriteMonitor::new();
+        let start = Instant::now();
+        monitor.record(PATH, 120);
+
+        assert!(!monitor.observe(PATH, 60, 120, PwmEnforcement::AlertOnly, start).write);
+        assert!(!monitor.observe(PATH, 60, 122, PwmEnforcement::AlertOnly, start + YIELD_QUIET_TIME).write);
+        assert!(monitor.observe(PATH, 60, 140, PwmEnforcement::AlertOnly, start + YIELD_QUIET_TIME).write);
+
+        // Back at the daemon's value: nothing left to yield to
+        monitor.record(PATH, 140);
+        assert!(!monitor.observe(PATH, 60, 140, PwmEnforcement::AlertOnly, start).write);
+        assert!(monitor.observe(PATH, 141, 140, PwmEnforcement::AlertOnly, start).write);
+        assert!(monitor.yielded.is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+//! GPU Fans
+//!
+//! Graphics cards run their own fan curve with an idle fan-stop (zero-RPM)
+//! mode. Writing the daemon's curve to them every iteration defeats that mode,
+//! and on NVIDIA each write spawns `nvidia-settings`. Curve outputs on NVIDIA
+//! and AMD GPU fans are therefore handled differently from hwmon outputs:
+//!
+//! - Changes smaller than [`MIN_DELTA`] are not written.
+//! - At most one write per [`MIN_WRITE_INTERVAL`]; the latest curve value
+//!   is written once it has passed.
+//! - A curve output at or below [`IDLE_STOP_PERCENT`] hands the fan back to
+//!   the card's automatic profile, so it can stop at idle. Curve control
+//!   resumes above [`IDLE_RESUME_PERCENT`].
+//! - Fans the daemon wrote are handed back to the automatic profile on exit.
+//!
+//! The failsafe, stall compensation and alarm boost always write at once.
+//! Intel cards have no automatic profile to return to and are only rate-limited.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use hf_core::constants::pwm;
+
+/// Smaller changes of the curve output are not written (~3%)
+const MIN_DELTA: u8 = 8;
+
+/// A GPU fan is written at most this often
+const MIN_WRITE_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Curve outputs at or below this leave the fan to the card's idle fan-stop
+const IDLE_STOP_PERCENT: f32 = 30.0;
+
+/// Curve control resumes above this (hysteresis against toggling)
+const IDLE_RESUME_PERCENT: f32 = 35.0;
+
+/// Vendor backend of a GPU fan output
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum GpuFan {
+    /// `nvidia:gpu:fan`; the automatic profile is per GPU
+    Nvidia { gpu_index: u32 },
+    /// amdgpu hwmon `pwmN`, or an `amd:card:fan` id
+    Amd,
+    /// `intel:card:fan`
+    Intel,
+}
+
+impl GpuFan {
+    fn classify(pwm_path: &str) -> Option<Self> {
+        if let Some(rest) = pwm_path.strip_prefix("nvidia:") {
+            let gpu_index = rest.split(':').next()?.parse().ok()?;
+            return Some(Self::Nvidia { gpu_index });
+        }
+        if pwm_path.starts_with("amd:") {
+            return Some(Self::Amd);
+        }
+        if pwm_path.starts_with("intel:") {
+            return Some(Self::Intel);
+        }
+        let chip_name = Path::new(pwm_path).parent().map(|dir| dir.join("name"))?;
+        std::fs::read_to_string(chip_name)
+            .is_ok_and(|name| name.trim() == "amdgpu")
+            .then_some(Self::Amd)
+    }
+
+    fn has_auto_profile(&self) -> bool {
+        !matches!(self, Self::Intel)
+    }
+}
+
+/// What the control loop does with a GPU fan this iteration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuWrite {
+    Write,
+    /// Too small a change, or too soon after the last write
+    Hold,
+    /// Hand the fan to the card's automatic profile
+    Firmware,
+}
+
+/// Write gating for one GPU fan pairing (rebuilt with its runtime)
+#[derive(Debug, Clone)]
+pub struct GpuFanControl {
+    fan: GpuFan,
+    last_written: Option<u8>,
+    last_write: Option<Instant>,
+    /// Fan is under the card's automatic profile
+    idle: bool,
+}
+
+impl GpuFanControl {
+    /// Gating for `pwm_path`, or None for a fan that isn't on a GPU
+    pub fn for_output(pwm_path: &str) -> Option<Self> {
+        GpuFan::classify(pwm_path).map(|fan| Self { fan, last_written: None, last_write: None, idle: false })
+    }
+
+    /// Decide whether the curve value `target` is written
+    pub fn plan(&mut self, target: u8, force: bool, now: Instant) -> GpuWrite {
+        if force {
+            return GpuWrite::Write;
+        }
+        if self.fan.has_auto_profile() {
+            let percent = pwm::to_percent(target);
+            if self.idle {
+                if percent <= IDLE_RESUME_PERCENT {
+                    return GpuWrite::Hold;
+                }
+                return GpuWrite::Write;
+            }
+            if percent <= IDLE_STOP_PERCENT {
+                return GpuWrite::Firmware;
+            }
+        }
+        let Some(last) = self.last_written else {
+            return GpuWrite::Write;
+        };
+        if target.abs_diff(last) < MIN_DELTA
+            || self.last_write.is_some_and(|t| now.duration_since(t) < MIN_WRITE_INTERVAL)
+        {
+            return GpuWrite::Hold;
+        }
+        GpuWrite::Write
+    }
+
+    /// Record a successful write of `value`
+    pub fn written(&mut self, value: u8, now: Instant) {
+        self.last_written = Some(value);
+        self.last_write = Some(now);
+        self.idle = false;
+    }
+
+    /// Record that the fan was handed to the card's automatic profile
+    pub fn handed_to_firmware(&mut self) {
+        self.last_written = None;
+        self.idle = true;
+    }
+
+    /// Whether the fan is under the card's automatic profile
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+}
+
+/// GPU fans the daemon has put under manual control
+static MANUAL: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Remember that the `nvidia:`/`amd:` fan `pwm_path` was written, so it is
+/// handed back on exit (amdgpu hwmon outputs are restored with the original state)
+pub fn record_write(pwm_path: &str) {
+    if !GpuFan::classify(pwm_path).is_some_and(|fan| fan.has_auto_profile()) {
+        return;
+    }
+    if let Ok(mut manual) = MANUAL.lock() {
+        if !manual.contains(pwm_path) {
+            manual.insert(pwm_path.to_string());
+        }
+    }
+}
+
+/// Return the fan at `pwm_path` to the card's automatic profile
+pub fn hand_to_firmware(pwm_path: &str) -> Result<(), String> {
+    match GpuFan::classify(pwm_path) {
+        Some(GpuFan::Nvidia { gpu_index }) => hf_core::reset_nvidia_fan_auto(gpu_index)
+            .map_err(|e| format!("Failed to reset NVIDIA fan auto: {}", e))?,
+        Some(GpuFan::Amd) => {
+            let sysfs_path = if pwm_path.starts_with("amd:") {
+                hf_core::enumerate_gpu_pwm_controllers()
+                    .into_iter()
+                    .find(|c| c.id == pwm_path)
+                    .map(|c| c.pwm_path)
+                    .ok_or_else(|| format!("AMD GPU fan {} not found", pwm_path))?
+            } else {
+                pwm_path.to_string()
+            };
+            hf_core::reset_amd_fan_auto(Path::new(&sysfs_path))
+                .map_err(|e| format!("Failed to reset AMD fan auto: {}", e))?
+        }
+        Some(GpuFan::Intel) | None => return Err(format!("{} has no automatic fan profile", pwm_path)),
+    }
+
+    // The card now writes the output itself
+    crate::foreign_writes::forget(pwm_path);
+    crate::competing_software::forget_enable_mode(pwm_path);
+    if let Ok(mut manual) = MANUAL.lock() {
+        manual.remove(pwm_path);
+    }
+    debug!("GPU: {} handed to the card's automatic fan profile", pwm_path);
+    Ok(())
+}
+
+/// Hand every GPU fan the daemon wrote back to its automatic profile (exit)
+pub fn restore_auto() {
+    let manual: Vec<String> = match MANUAL.lock() {
+        Ok(manual) => manual.iter().cloned().collect(),
+        Err(_) => return,
+    };
+    let mut nvidia_reset = BTreeSet::new();
+    for pwm_path in manual {
+        // One reset covers every fan of an NVIDIA GPU
+        if let Some(GpuFan::Nvidia { gpu_index }) = GpuFan::classify(&pwm_path) {
+            if !nvidia_reset.insert(gpu_index) {
+                continue;
+            }
+        }
+        match hand_to_firmware(&pwm_path) {
+            Ok(()) => info!("Restored automatic fan control on GPU fan {}", pwm_path),
+            Err(e) => warn!("Failed to restore automatic fan control on {}: {}", pwm_path, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nvidia() -> GpuFanControl {
+        GpuFanControl::for_output("nvidia:0:0").unwrap()
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(GpuFan::classify("nvidia:1:0"), Some(GpuFan::Nvidia { gpu_index: 1 }));
+        assert_eq!(GpuFan::classify("amd:0:0"), Some(GpuFan::Amd));
+        assert_eq!(GpuFan::classify("intel:0:1"), Some(GpuFan::Intel));
+        assert_eq!(GpuFan::classify("nvidia:x:0"), None);
+
+        let dir = std::env::temp_dir().join(format!("hf-gpu-fans-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("name"), "amdgpu\n").unwrap();
+        let pwm1 = dir.join("pwm1");
+        assert_eq!(GpuFan::classify(&pwm1.to_string_lossy()), Some(GpuFan::Amd));
+        std::fs::write(dir.join("name"), "nct6798\n").unwrap();
+        assert_eq!(GpuFan::classify(&pwm1.to_string_lossy()), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_delta_and_interval() {
+        let mut gpu = nvidia();
+        let start = Instant::now();
+        assert_eq!(gpu.plan(150, false, start), GpuWrite::Write);
+        gpu.written(150, start);
+
+        let later = start + MIN_WRITE_INTERVAL;
+        assert_eq!(gpu.plan(155, false, later), GpuWrite::Hold);
+        assert_eq!(gpu.plan(170, false, start + Duration::from_secs(1)), GpuWrite::Hold);
+        assert_eq!(gpu.plan(170, true, start + Duration::from_secs(1)), GpuWrite::Write);
+        assert_eq!(gpu.plan(170, false, later), GpuWrite::Write);
+    }
+
+    #[test]
+    fn test_idle_fan_stop_hysteresis() {
+        let mut gpu = nvidia();
+        let start = Instant::now();
+        gpu.written(150, start);
+
+        assert_eq!(gpu.plan(pwm::from_percent(25.0), false, start), GpuWrite::Firmware);
+        gpu.handed_to_firmware();
+        assert!(gpu.is_idle());
+        assert_eq!(gpu.plan(pwm::from_percent(33.0), false, start), GpuWrite::Hold);
+        // Resuming ignores the write interval
+        assert_eq!(gpu.plan(pwm::from_percent(40.0), false, start), GpuWrite::Write);
+
+        // Intel has no automatic profile to hand back to
+        let mut intel = GpuFanControl::for_output("intel:0:0").unwrap();
+        assert_eq!(intel.plan(0, false, start), GpuWrite::Write);
+    }
+}
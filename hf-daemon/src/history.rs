@@ -0,0 +1,622 @@
+//! Long-Term Sensor History
+//!
+//! Samples every hwmon temperature, fan tachometer and PWM output every
+//! [`SAMPLE_INTERVAL`] and keeps them under `/var/lib/hyperfan/history/`, so
+//! graphs survive GUI and daemon restarts. Clients read it with `QueryHistory`.
+//!
+//! Each series has one fixed-size ring file per tier. Tiers get progressively
+//! coarser: every completed bucket of a tier is folded into the next one,
+//! keeping its min, max and average. A slot is addressed by bucket number
+//! modulo the ring size and stores that number, so files never grow and slots
+//! left over from an earlier lap are recognised and skipped.
+//!
+//! Buckets still being filled are kept in memory and lost when the daemon
+//! stops; at most one bucket per tier goes missing.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+use hf_protocol::{HistoryData, HistoryPoint, HistorySeries, HistorySeriesKind, MAX_HISTORY_POINTS};
+
+use crate::fan_control::FanControlState;
+
+/// Directory holding one subdirectory of ring files per tier
+const HISTORY_DIR: &str = "/var/lib/hyperfan/history";
+
+/// How often every series is sampled (the finest tier's resolution)
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Chip layouts are re-enumerated this often (values are read every sample)
+const LAYOUT_TTL: Duration = Duration::from_secs(60);
+
+/// Series tracked at most (about 340KB of disk each)
+const MAX_SERIES: usize = 256;
+
+/// Ring file header: magic, resolution, slot count, kind, key length, key
+const MAGIC: &[u8; 8] = b"HFHIST01";
+const HEADER_SIZE: usize = 256;
+const KEY_OFFSET: usize = 20;
+const MAX_KEY_LENGTH: usize = HEADER_SIZE - KEY_OFFSET;
+
+/// Slot: bucket number, min, max, average
+const RECORD_SIZE: usize = 16;
+
+/// Resolution and length of one ring
+struct Tier {
+    dir: &'static str,
+    resolution_secs: u32,
+    slots: u32,
+}
+
+impl Tier {
+    /// Time covered by a full ring
+    fn span_secs(&self) -> u64 {
+        self.resolution_secs as u64 * self.slots as u64
+    }
+
+    fn file_size(&self) -> u64 {
+        (HEADER_SIZE + self.slots as usize * RECORD_SIZE) as u64
+    }
+}
+
+const TIER_COUNT: usize = 3;
+
+const TIERS: [Tier; TIER_COUNT] = [
+    Tier { dir: "10s", resolution_secs: 10, slots: 8640 },  // 24 hours
+    Tier { dir: "1m", resolution_secs: 60, slots: 10080 },  // 7 days
+    Tier { dir: "15m", resolution_secs: 900, slots: 2880 }, // 30 days
+];
+
+// ============================================================================
+// Records and Buckets
+// ============================================================================
+
+/// One stored slot
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Record {
+    /// Unix time / tier resolution (0 = empty slot)
+    bucket: u32,
+    min: f32,
+    max: f32,
+    avg: f32,
+}
+
+impl Record {
+    fn encode(&self) -> [u8; RECORD_SIZE] {
+        let mut buf = [0u8; RECORD_SIZE];
+        buf[0..4].copy_from_slice(&self.bucket.to_le_bytes());
+        buf[4..8].copy_from_slice(&self.min.to_le_bytes());
+        buf[8..12].copy_from_slice(&self.max.to_le_bytes());
+        buf[12..16].copy_from_slice(&self.avg.to_le_bytes());
+        buf
+    }
+
+    fn decode(buf: &[u8]) -> Option<Self> {
+        let word = |i: usize| [buf[i], buf[i + 1], buf[i + 2], buf[i + 3]];
+        let record = Self {
+            bucket: u32::from_le_bytes(word(0)),
+            min: f32::from_le_bytes(word(4)),
+            max: f32::from_le_bytes(word(8)),
+            avg: f32::from_le_bytes(word(12)),
+        };
+        (record.bucket != 0).then_some(record)
+    }
+}
+
+/// Bucket being filled
+#[derive(Debug, Clone, Copy)]
+struct Accumulator {
+    bucket: u32,
+    min: f32,
+    max: f32,
+    sum: f64,
+    /// Raw samples covered
+    count: u32,
+}
+
+impl Accumulator {
+    fn sample(value: f32) -> Self {
+        Self { bucket: 0, min: value, max: value, sum: value as f64, count: 1 }
+    }
+
+    fn merge(&mut self, other: &Accumulator) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+        self.count += other.count;
+    }
+
+    fn record(&self) -> Record {
+        Record {
+            bucket: self.bucket,
+            min: self.min,
+            max: self.max,
+            avg: (self.sum / self.count.max(1) as f64) as f32,
+        }
+    }
+}
+
+/// Add a sample taken at `secs` to the tiers' open buckets
+///
+/// Returns the buckets this completed as (tier, record). A completed bucket is
+/// folded into the next tier, which may complete that tier's bucket in turn.
+fn fold(pending: &mut [Option<Accumulator>; TIER_COUNT], secs: u64, value: f32) -> Vec<(usize, Record)> {
+    let mut completed = Vec::new();
+    let mut input = Accumulator::sample(value);
+    let mut input_secs = secs;
+
+    for (index, tier) in TIERS.iter().enumerate() {
+        let bucket = (input_secs / tier.resolution_secs as u64) as u32;
+        match &mut pending[index] {
+            Some(open) if open.bucket == bucket => {
+                open.merge(&input);
+                break;
+            }
+            slot => {
+                let finished = slot.replace(Accumulator { bucket, ..input });
+                let Some(finished) = finished else { break };
+                completed.push((index, finished.record()));
+                input_secs = finished.bucket as u64 * tier.resolution_secs as u64;
+                input = finished;
+            }
+        }
+    }
+    completed
+}
+
+/// Finest tier that reaches back to `start_secs` and may serve `resolution_secs`
+///
+/// Coarser tiers are preferred when the requested resolution allows it (fewer
+/// slots to read); a range older than every ring falls back to the coarsest.
+fn choose_tier(start_secs: u64, now_secs: u64, resolution_secs: u32) -> usize {
+    let age = now_secs.saturating_sub(start_secs);
+    let covering = TIERS.iter().position(|t| t.span_secs() >= age).unwrap_or(TIER_COUNT - 1);
+    let allowed = TIERS.iter().rposition(|t| t.resolution_secs <= resolution_secs).unwrap_or(0);
+    covering.max(allowed)
+}
+
+/// Bucket width for a query: at least the tier's and the requested resolution,
+/// wide enough to keep `series` series under `MAX_HISTORY_POINTS`, and a
+/// multiple of the tier's resolution
+fn effective_resolution(tier: &Tier, range_secs: u64, resolution_secs: u32, series: usize) -> u32 {
+    let budget = (MAX_HISTORY_POINTS / series.max(1)).max(1) as u64;
+    let needed = range_secs.div_ceil(budget).min(u32::MAX as u64) as u32;
+    let width = resolution_secs.max(needed).max(tier.resolution_secs);
+    width.div_ceil(tier.resolution_secs) * tier.resolution_secs
+}
+
+/// Merge records (keyed by their start, Unix seconds) into buckets of `width_secs`
+fn downsample(records: impl Iterator<Item = (u64, Record)>, width_secs: u32) -> Vec<HistoryPoint> {
+    let mut buckets: BTreeMap<u64, Accumulator> = BTreeMap::new();
+    for (secs, record) in records {
+        let start = secs - secs % width_secs as u64;
+        let sample = Accumulator {
+            bucket: 0,
+            min: record.min,
+            max: record.max,
+            sum: record.avg as f64,
+            count: 1,
+        };
+        buckets.entry(start).and_modify(|b| b.merge(&sample)).or_insert(sample);
+    }
+    buckets
+        .into_iter()
+        .map(|(start, bucket)| {
+            let record = bucket.record();
+            HistoryPoint {
+                timestamp_ms: start * 1000,
+                min: record.min,
+                max: record.max,
+                avg: record.avg,
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Ring Files
+// ============================================================================
+
+fn kind_code(kind: HistorySeriesKind) -> u8 {
+    match kind {
+        HistorySeriesKind::Temperature => 0,
+        HistorySeriesKind::FanRpm => 1,
+        HistorySeriesKind::PwmDuty => 2,
+    }
+}
+
+fn kind_from_code(code: u8) -> Option<HistorySeriesKind> {
+    match code {
+        0 => Some(HistorySeriesKind::Temperature),
+        1 => Some(HistorySeriesKind::FanRpm),
+        2 => Some(HistorySeriesKind::PwmDuty),
+        _ => None,
+    }
+}
+
+/// File name of a series (FNV-1a of its path, stable across builds)
+fn file_name(key: &str) -> String {
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}.bin", hash)
+}
+
+fn encode_header(tier: &Tier, key: &str, kind: HistorySeriesKind) -> [u8; HEADER_SIZE] {
+    let mut header = [0u8; HEADER_SIZE];
+    header[0..8].copy_from_slice(MAGIC);
+    header[8..12].copy_from_slice(&tier.resolution_secs.to_le_bytes());
+    header[12..16].copy_from_slice(&tier.slots.to_le_bytes());
+    header[16] = kind_code(kind);
+    header[18..20].copy_from_slice(&(key.len() as u16).to_le_bytes());
+    header[KEY_OFFSET..KEY_OFFSET + key.len()].copy_from_slice(key.as_bytes());
+    header
+}
+
+/// Series path and kind of a ring file written for `tier`
+fn decode_header(header: &[u8], tier: &Tier) -> Option<(String, HistorySeriesKind)> {
+    if header.len() < HEADER_SIZE || &header[0..8] != MAGIC {
+        return None;
+    }
+    let resolution = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+    let slots = u32::from_le_bytes([header[12], header[13], header[14], header[15]]);
+    if resolution != tier.resolution_secs || slots != tier.slots {
+        return None;
+    }
+    let kind = kind_from_code(header[16])?;
+    let key_len = u16::from_le_bytes([header[18], header[19]]) as usize;
+    if key_len > MAX_KEY_LENGTH {
+        return None;
+    }
+    let key = std::str::from_utf8(&header[KEY_OFFSET..KEY_OFFSET + key_len]).ok()?;
+    Some((key.to_string(), kind))
+}
+
+/// Open a series' ring file, creating it (or replacing one of another layout)
+fn open_ring(path: &Path, tier: &Tier, key: &str, kind: HistorySeriesKind) -> std::io::Result<File> {
+    let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+
+    let mut header = [0u8; HEADER_SIZE];
+    let valid = file.metadata()?.len() == tier.file_size()
+        && file.read_exact(&mut header).is_ok()
+        && decode_header(&header, tier).is_some_and(|(k, _)| k == key);
+    if !valid {
+        file.set_len(0)?;
+        file.set_len(tier.file_size())?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&encode_header(tier, key, kind))?;
+    }
+    Ok(file)
+}
+
+/// Series path, kind and records keyed by their start (Unix seconds)
+type StoredSeries = (String, HistorySeriesKind, Vec<(u64, Record)>);
+
+/// Read a ring file's series and the records within `[start_secs, end_secs]`
+fn read_ring(path: &Path, tier: &Tier, start_secs: u64, end_secs: u64) -> Option<StoredSeries> {
+    let mut data = Vec::with_capacity(tier.file_size() as usize);
+    File::open(path).ok()?.take(tier.file_size()).read_to_end(&mut data).ok()?;
+    let (key, kind) = decode_header(&data, tier)?;
+
+    let mut records: Vec<(u64, Record)> = data[HEADER_SIZE..]
+        .chunks_exact(RECORD_SIZE)
+        .filter_map(Record::decode)
+        .map(|r| (r.bucket as u64 * tier.resolution_secs as u64, r))
+        .filter(|(secs, _)| (start_secs..=end_secs).contains(secs))
+        .collect();
+    records.sort_by_key(|(secs, _)| *secs);
+    Some((key, kind, records))
+}
+
+// ============================================================================
+// Database
+// ============================================================================
+
+/// A series' open buckets and ring files
+struct Series {
+    kind: HistorySeriesKind,
+    pending: [Option<Accumulator>; TIER_COUNT],
+    files: [Option<File>; TIER_COUNT],
+}
+
+/// On-disk history shared by the sampler and the IPC server
+pub struct HistoryDb {
+    dir: PathBuf,
+    series: HashMap<String, Series>,
+}
+
+impl Default for HistoryDb {
+    fn default() -> Self {
+        Self::new(Path::new(HISTORY_DIR))
+    }
+}
+
+impl HistoryDb {
+    pub fn new(dir: &Path) -> Self {
+        Self { dir: dir.to_path_buf(), series: HashMap::new() }
+    }
+
+    fn tier_dir(&self, tier: &Tier) -> PathBuf {
+        self.dir.join(tier.dir)
+    }
+
+    /// Record a sample of series `key` taken at `secs` (Unix time)
+    pub fn record(&mut self, key: &str, kind: HistorySeriesKind, value: f32, secs: u64) {
+        if !value.is_finite() || key.len() > MAX_KEY_LENGTH {
+            return;
+        }
+        if !self.series.contains_key(key) && self.series.len() >= MAX_SERIES {
+            return;
+        }
+
+        let series = self.series.entry(key.to_string()).or_insert_with(|| Series {
+            kind,
+            pending: [None; TIER_COUNT],
+            files: [None, None, None],
+        });
+        let completed = fold(&mut series.pending, secs, value);
+
+        for (index, record) in completed {
+            let tier = &TIERS[index];
+            let dir = self.dir.join(tier.dir);
+            let slot = &mut series.files[index];
+            if slot.is_none() {
+                let opened = std::fs::create_dir_all(&dir)
+                    .and_then(|_| open_ring(&dir.join(file_name(key)), tier, key, series.kind));
+                match opened {
+                    Ok(file) => *slot = Some(file),
+                    Err(e) => {
+                        warn!("HISTORY: Failed to open {} ring for {}: {}", tier.dir, key, e);
+                        continue;
+                    }
+                }
+            }
+
+            let offset = (HEADER_SIZE + (record.bucket % tier.slots) as usize * RECORD_SIZE) as u64;
+            let written = slot.as_mut().map(|file| {
+                file.seek(SeekFrom::Start(offset)).and_then(|_| file.write_all(&record.encode()))
+            });
+            if let Some(Err(e)) = written {
+                warn!("HISTORY: Failed to write {} ring for {}: {}", tier.dir, key, e);
+                // Re-opened (and re-validated) on the next write
+                *slot = None;
+            }
+        }
+    }
+
+    /// Stored history between `start_ms` and `end_ms`, plus buckets still being filled
+    pub fn query(&self, start_ms: u64, end_ms: u64, resolution_secs: u32, paths: &[String], now_secs: u64) -> HistoryData {
+        let start_secs = start_ms / 1000;
+        let end_secs = end_ms.div_ceil(1000);
+        let index = choose_tier(start_secs, now_secs, resolution_secs);
+        let tier = &TIERS[index];
+        let dir = self.tier_dir(tier);
+
+        let files: Vec<PathBuf> = if paths.is_empty() {
+            std::fs::read_dir(&dir)
+                .map(|entries| {
+                    entries
+                        .filter_map(|e| e.ok())
+                        .map(|e| e.path())
+                        .filter(|p| p.extension().is_some_and(|ext| ext == "bin"))
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            paths.iter().map(|p| dir.join(file_name(p))).collect()
+        };
+
+        let mut stored: Vec<StoredSeries> = files
+            .iter()
+            .filter_map(|path| read_ring(path, tier, start_secs, end_secs))
+            .filter(|(key, _, _)| paths.is_empty() || paths.contains(key))
+            .collect();
+
+        // Series sampled since the daemon started but with nothing on disk yet
+        for (key, series) in &self.series {
+            let wanted = paths.is_empty() || paths.contains(key);
+            if wanted && !stored.iter().any(|(k, _, _)| k == key) {
+                stored.push((key.clone(), series.kind, Vec::new()));
+            }
+        }
+
+        for (key, _, records) in &mut stored {
+            let open = self.series.get(key.as_str()).and_then(|s| s.pending[index]);
+            if let Some(open) = open {
+                let secs = open.bucket as u64 * tier.resolution_secs as u64;
+                if (start_secs..=end_secs).contains(&secs) {
+                    records.push((secs, open.record()));
+                }
+            }
+        }
+        stored.retain(|(_, _, records)| !records.is_empty());
+        stored.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let width = effective_resolution(tier, end_secs.saturating_sub(start_secs), resolution_secs, stored.len());
+        HistoryData {
+            resolution_secs: width,
+            series: stored
+                .into_iter()
+                .map(|(path, kind, records)| HistorySeries {
+                    path,
+                    kind,
+                    points: downsample(records.into_iter(), width),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Delete ring files not written for longer than the coarsest ring spans
+fn prune_stale(dir: &Path) {
+    let max_age = Duration::from_secs(TIERS[TIER_COUNT - 1].span_secs());
+    let mut removed = 0;
+    for tier in &TIERS {
+        let Ok(entries) = std::fs::read_dir(dir.join(tier.dir)) else { continue };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let stale = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > max_age);
+            if stale && std::fs::remove_file(entry.path()).is_ok() {
+                removed += 1;
+            }
+        }
+    }
+    if removed > 0 {
+        info!("HISTORY: Removed {} ring files of sensors gone for over 30 days", removed);
+    }
+}
+
+/// Unix time in seconds
+pub fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Sample every hwmon sensor and PWM output into the history forever
+pub fn spawn_sampler(state: Arc<FanControlState>) {
+    tokio::spawn(async move {
+        prune_stale(Path::new(HISTORY_DIR));
+
+        let mut ticker = tokio::time::interval(SAMPLE_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let chips = match hf_core::cached_hwmon_chips(LAYOUT_TTL) {
+                Ok(chips) => chips,
+                Err(e) => {
+                    debug!("HISTORY: Failed to read sensors: {}", e);
+                    continue;
+                }
+            };
+
+            let secs = now_secs();
+            let mut db = state.history.lock().await;
+            for chip in &chips {
+                for temp in &chip.temperatures {
+                    if let Some(value) = temp.current_temp {
+                        db.record(&temp.input_path.to_string_lossy(), HistorySeriesKind::Temperature, value, secs);
+                    }
+                }
+                for fan in &chip.fans {
+                    if let Some(rpm) = fan.current_rpm {
+                        db.record(&fan.input_path.to_string_lossy(), HistorySeriesKind::FanRpm, rpm as f32, secs);
+                    }
+                }
+                for pwm in &chip.pwms {
+                    let percent = pwm.current_percent.or(pwm.current_value.map(|v| v as f32 * 100.0 / 255.0));
+                    if let Some(percent) = percent {
+                        db.record(&pwm.pwm_path.to_string_lossy(), HistorySeriesKind::PwmDuty, percent, secs);
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_roundtrip() {
+        let record = Record { bucket: 176_000_000, min: 41.5, max: 47.0, avg: 44.25 };
+        assert_eq!(Record::decode(&record.encode()), Some(record));
+        assert_eq!(Record::decode(&[0u8; RECORD_SIZE]), None);
+    }
+
+    #[test]
+    fn test_fold_cascades_completed_buckets() {
+        let mut pending = [None; TIER_COUNT];
+        // Six 10s buckets fill a minute, which completes once the first bucket
+        // of the next minute does
+        let mut completed = Vec::new();
+        for i in 0..8u64 {
+            completed.extend(fold(&mut pending, 600 + i * 10, 40.0 + i as f32));
+        }
+
+        let tier0: Vec<_> = completed.iter().filter(|(t, _)| *t == 0).collect();
+        assert_eq!(tier0.len(), 7);
+        let minute: Vec<_> = completed.iter().filter(|(t, _)| *t == 1).collect();
+        assert_eq!(minute.len(), 1);
+        let record = minute[0].1;
+        assert_eq!(record.bucket, 10);
+        assert_eq!(record.min, 40.0);
+        assert_eq!(record.max, 45.0);
+        assert!((record.avg - 42.5).abs() < 1e-4);
+        assert_eq!(pending[2].map(|a| a.count), Some(6));
+    }
+
+    #[test]
+    fn test_query_reads_rings_back() {
+        let dir = std::env::temp_dir().join(format!("hf-history-test-{}", std::process::id()));
+        let key = "/sys/class/hwmon/hwmon1/fan1_input";
+        let start = 1_800_000_000u64;
+        {
+            let mut db = HistoryDb::new(&dir);
+            for i in 0..40u64 {
+                db.record(key, HistorySeriesKind::FanRpm, 1000.0 + i as f32, start + i * 10);
+            }
+        }
+
+        // A fresh database (daemon restart) only has the rings on disk
+        let db = HistoryDb::new(&dir);
+        let now = start + 400;
+        let history = db.query(start * 1000, now * 1000, 60, &[], now);
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(history.resolution_secs, 60);
+        assert_eq!(history.series.len(), 1);
+        let series = &history.series[0];
+        assert_eq!(series.path, key);
+        assert_eq!(series.kind, HistorySeriesKind::FanRpm);
+        assert!(!series.points.is_empty());
+        assert!(series.points.iter().all(|p| p.min >= 1000.0 && p.max <= 1039.0));
+    }
+
+    #[test]
+    fn test_header_roundtrip() {
+        let key = "/sys/class/hwmon/hwmon2/temp1_input";
+        let header = encode_header(&TIERS[1], key, HistorySeriesKind::Temperature);
+        assert_eq!(decode_header(&header, &TIERS[1]), Some((key.to_string(), HistorySeriesKind::Temperature)));
+        // Written for another tier
+        assert_eq!(decode_header(&header, &TIERS[0]), None);
+    }
+
+    #[test]
+    fn test_choose_tier() {
+        let now = 10_000_000;
+        assert_eq!(choose_tier(now - 3600, now, 0), 0);
+        assert_eq!(choose_tier(now - 3600, now, 60), 1);
+        assert_eq!(choose_tier(now - 3 * 24 * 3600, now, 0), 1);
+        assert_eq!(choose_tier(now - 20 * 24 * 3600, now, 0), 2);
+        assert_eq!(choose_tier(now - 90 * 24 * 3600, now, 0), 2);
+    }
+
+    #[test]
+    fn test_effective_resolution_bounds_points() {
+        // 24h of 10s buckets for 10 series would be 86,400 points
+        let width = effective_resolution(&TIERS[0], 24 * 3600, 0, 10);
+        assert_eq!(width % 10, 0);
+        assert!((24 * 3600 / width as usize) * 10 <= MAX_HISTORY_POINTS);
+        assert_eq!(effective_resolution(&TIERS[0], 600, 0, 1), 10);
+        assert_eq!(effective_resolution(&TIERS[1], 600, 90, 1), 120);
+    }
+
+    #[test]
+    fn test_downsample_merges_buckets() {
+        let records = [(0u64, 40.0f32), (10, 44.0), (20, 42.0), (30, 50.0)]
+            .map(|(secs, v)| (secs, Record { bucket: 1, min: v, max: v, avg: v }));
+        let points = downsample(records.into_iter(), 20);
+        assert_eq!(points.len(), 2);
+        assert_eq!((points[0].min, points[0].max, points[0].avg), (40.0, 44.0, 42.0));
+        assert_eq!(points[1].timestamp_ms, 20_000);
+        assert_eq!(points[1].max, 50.0);
+    }
+}
@@ -0,0 +1,326 @@
+//! Hardware Hotplug Monitor
+//!
+//! Listens for kernel uevents on a `NETLINK_KOBJECT_UEVENT` socket and picks
+//! out hwmon chips and DRM cards being added or removed (USB sensors, eGPU
+//! docks, driver module loads). Each one is queued as a [`HardwareChange`]
+//! that clients poll via `GetHardwareChanges`.
+//!
+//! Uevents arrive in bursts (a module load registers several devices), so the
+//! control loop waits until no event has arrived for the settle delay, then
+//! drops the hwmon layout cache, re-validates fingerprinted bindings and
+//! reloads the configuration so pairs resolve to the new paths without a
+//! daemon restart.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use hf_protocol::{HardwareChange, HardwareChangeKind};
+
+use crate::fan_control::FanControlState;
+
+/// Quiet period after the last uevent before re-enumerating
+const SETTLE_DELAY: Duration = Duration::from_secs(1);
+
+/// Maximum events retained for polling clients (keeps responses small)
+const MAX_RETAINED_CHANGES: usize = 32;
+
+/// Kernel uevent multicast group (raw kernel events, no udevd required)
+#[cfg(target_os = "linux")]
+const UEVENT_KERNEL_GROUP: u32 = 1;
+
+/// Largest uevent message the kernel sends
+#[cfg(target_os = "linux")]
+const UEVENT_BUFFER_SIZE: usize = 8192;
+
+/// hwmon/DRM uevent relevant to fan control
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Uevent {
+    pub kind: HardwareChangeKind,
+    pub subsystem: String,
+    pub devpath: String,
+}
+
+impl Uevent {
+    /// Last devpath component ("hwmon4", "card1")
+    pub fn name(&self) -> &str {
+        self.devpath.rsplit('/').next().unwrap_or(&self.devpath)
+    }
+}
+
+/// Parse a kernel uevent ("add@/devices/...\0ACTION=add\0SUBSYSTEM=hwmon\0...")
+/// Returns `None` for anything other than hwmon chips and DRM cards being added or removed
+pub fn parse_uevent(buf: &[u8]) -> Option<Uevent> {
+    let mut action = None;
+    let mut subsystem = None;
+    let mut devpath = None;
+    for field in buf.split(|&b| b == 0).filter_map(|f| std::str::from_utf8(f).ok()) {
+        if let Some(value) = field.strip_prefix("ACTION=") {
+            action = Some(value);
+        } else if let Some(value) = field.strip_prefix("SUBSYSTEM=") {
+            subsystem = Some(value);
+        } else if let Some(value) = field.strip_prefix("DEVPATH=") {
+            devpath = Some(value);
+        }
+    }
+
+    let kind = match action? {
+        "add" => HardwareChangeKind::Added,
+        "remove" => HardwareChangeKind::Removed,
+        _ => return None,
+    };
+    let event = Uevent {
+        kind,
+        subsystem: subsystem?.to_string(),
+        devpath: devpath?.to_string(),
+    };
+
+    let name = event.name();
+    let relevant = match event.subsystem.as_str() {
+        "hwmon" => name.starts_with("hwmon"),
+        // Cards only, not connectors ("card1-DP-2") or render nodes
+        "drm" => name.starts_with("card") && !name.contains('-'),
+        _ => false,
+    };
+    relevant.then_some(event)
+}
+
+/// Hotplug event queue shared between the listener, control loop and IPC server
+pub struct HotplugMonitor {
+    /// Recent changes, oldest first
+    changes: VecDeque<HardwareChange>,
+    /// ID assigned to the next change
+    next_id: u64,
+    /// Time of the last change not yet acted on
+    pending_since: Option<Instant>,
+}
+
+impl HotplugMonitor {
+    pub fn new() -> Self {
+        Self {
+            changes: VecDeque::new(),
+            next_id: 1,
+            pending_since: None,
+        }
+    }
+
+    /// Queue a change and schedule re-enumeration
+    pub fn record(&mut self, event: &Uevent, now: Instant) {
+        let timestamp_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let change = HardwareChange {
+            id: self.next_id,
+            kind: event.kind,
+            subsystem: event.subsystem.clone(),
+            name: event.name().to_string(),
+            devpath: event.devpath.clone(),
+            timestamp_ms,
+        };
+        crate::events::publish(hf_protocol::Event::HardwareChanged(change.clone()));
+        self.changes.push_back(change);
+        self.next_id += 1;
+        self.pending_since = Some(now);
+
+        while self.changes.len() > MAX_RETAINED_CHANGES {
+            self.changes.pop_front();
+        }
+    }
+
+    /// Changes newer than `since_id`, oldest first
+    pub fn changes_since(&self, since_id: u64) -> Vec<HardwareChange> {
+        self.changes.iter().filter(|c| c.id > since_id).cloned().collect()
+    }
+
+    /// Whether a burst of changes has settled and should be acted on
+    /// Clears the pending state when it returns true
+    pub fn take_settled(&mut self, now: Instant) -> bool {
+        match self.pending_since {
+            Some(at) if now.duration_since(at) >= SETTLE_DELAY => {
+                self.pending_since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for HotplugMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Re-enumerate after hotplug events have settled (call from control loop)
+pub async fn tick(state: &FanControlState) {
+    if !state.hotplug.write().await.take_settled(Instant::now()) {
+        return;
+    }
+
+    info!("HOTPLUG: Hardware changed - re-enumerating hwmon chips and reloading config");
+    hf_core::invalidate_hwmon_cache(None);
+
+    if let Some(ref dp) = state.drift_protection {
+        match dp.force_drift_correction().await {
+            Ok(result) if result.corrections_applied => info!("HOTPLUG: Fingerprinted bindings updated to new hwmon paths"),
+            Ok(_) => debug!("HOTPLUG: Fingerprinted bindings unchanged"),
+            Err(e) => warn!("HOTPLUG: Binding revalidation failed: {}", e),
+        }
+    }
+
+    state.signal_reload();
+}
+
+/// Start the uevent listener thread
+/// Hotplug detection is skipped (with a warning) if the socket cannot be opened
+pub fn spawn_listener(state: Arc<FanControlState>) {
+    #[cfg(target_os = "linux")]
+    {
+        let fd = match open_uevent_socket() {
+            Ok(fd) => fd,
+            Err(e) => {
+                warn!("HOTPLUG: Cannot open uevent socket ({}) - hardware changes need a daemon restart", e);
+                return;
+            }
+        };
+
+        let spawned = std::thread::Builder::new()
+            .name("hf-hotplug".to_string())
+            .spawn(move || run_listener(fd, state));
+        match spawned {
+            Ok(_) => info!("HOTPLUG: Watching for hwmon and DRM device changes"),
+            Err(e) => warn!("HOTPLUG: Failed to start listener thread: {}", e),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = state;
+        debug!("HOTPLUG: uevent monitoring is only available on Linux");
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn open_uevent_socket() -> std::io::Result<std::os::fd::OwnedFd> {
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    // SAFETY: socket() has no memory-safety preconditions; the result is checked before use.
+    let raw = unsafe {
+        libc::socket(libc::AF_NETLINK, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, libc::NETLINK_KOBJECT_UEVENT)
+    };
+    if raw < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: raw is a valid, newly created descriptor owned by nothing else.
+    let fd = unsafe { OwnedFd::from_raw_fd(raw) };
+
+    // SAFETY: sockaddr_nl is plain data; all-zero is a valid initial value.
+    let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+    addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+    addr.nl_groups = UEVENT_KERNEL_GROUP;
+
+    // SAFETY: addr is a properly initialized sockaddr_nl and the length matches its size.
+    let result = unsafe {
+        libc::bind(
+            fd.as_raw_fd(),
+            &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        )
+    };
+    if result < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(fd)
+}
+
+#[cfg(target_os = "linux")]
+fn run_listener(fd: std::os::fd::OwnedFd, state: Arc<FanControlState>) {
+    use std::os::fd::AsRawFd;
+
+    let mut buf = vec![0u8; UEVENT_BUFFER_SIZE];
+    loop {
+        // SAFETY: buf is valid for writes of buf.len() bytes for the duration of the call.
+        let len = unsafe { libc::recv(fd.as_raw_fd(), buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) };
+        if len < 0 {
+            let err = std::io::Error::last_os_error();
+            if err.raw_os_error() == Some(libc::ENOBUFS) {
+                // Events were dropped under load - re-enumerate to be safe
+                warn!("HOTPLUG: uevent buffer overrun - forcing re-enumeration");
+                hf_core::invalidate_hwmon_cache(None);
+                state.signal_reload();
+            } else if err.kind() != std::io::ErrorKind::Interrupted {
+                warn!("HOTPLUG: uevent socket failed ({}) - hotplug detection stopped", err);
+                return;
+            }
+            continue;
+        }
+
+        let Some(event) = parse_uevent(&buf[..len as usize]) else {
+            continue;
+        };
+        info!("HOTPLUG: {} {:?} ({})", event.subsystem, event.kind, event.devpath);
+
+        if event.subsystem == "hwmon" {
+            let entry = std::path::Path::new(hf_core::constants::paths::HWMON_BASE).join(event.name());
+            hf_core::invalidate_hwmon_cache(Some(&entry));
+        }
+        state.hotplug.blocking_write().record(&event, Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uevent(fields: &[&str]) -> Vec<u8> {
+        fields.join("\0").into_bytes()
+    }
+
+    #[test]
+    fn test_parse_uevent() {
+        let add = uevent(&[
+            "add@/devices/platform/nct6775.656/hwmon/hwmon4",
+            "ACTION=add",
+            "DEVPATH=/devices/platform/nct6775.656/hwmon/hwmon4",
+            "SUBSYSTEM=hwmon",
+            "SEQNUM=4242",
+        ]);
+        let event = parse_uevent(&add).unwrap();
+        assert_eq!(event.kind, HardwareChangeKind::Added);
+        assert_eq!(event.name(), "hwmon4");
+
+        let card = uevent(&["ACTION=remove", "DEVPATH=/devices/pci0000:00/0000:00:01.0/drm/card1", "SUBSYSTEM=drm"]);
+        assert_eq!(parse_uevent(&card).unwrap().kind, HardwareChangeKind::Removed);
+
+        // Connectors, change events and other subsystems are ignored
+        let connector = uevent(&["ACTION=add", "DEVPATH=/devices/pci0000:00/drm/card1/card1-DP-2", "SUBSYSTEM=drm"]);
+        let change = uevent(&["ACTION=change", "DEVPATH=/devices/pci0000:00/drm/card1", "SUBSYSTEM=drm"]);
+        let usb = uevent(&["ACTION=add", "DEVPATH=/devices/pci0000:00/usb1/1-1", "SUBSYSTEM=usb"]);
+        assert!(parse_uevent(&connector).is_none());
+        assert!(parse_uevent(&change).is_none());
+        assert!(parse_uevent(&usb).is_none());
+    }
+
+    #[test]
+    fn test_changes_settle() {
+        let mut monitor = HotplugMonitor::new();
+        let start = Instant::now();
+        let event = Uevent {
+            kind: HardwareChangeKind::Added,
+            subsystem: "hwmon".to_string(),
+            devpath: "/devices/virtual/hwmon/hwmon7".to_string(),
+        };
+
+        monitor.record(&event, start);
+        monitor.record(&event, start + Duration::from_millis(500));
+        assert!(!monitor.take_settled(start + Duration::from_millis(1000)));
+        assert!(monitor.take_settled(start + Duration::from_millis(1500)));
+        assert!(!monitor.take_settled(start + Duration::from_millis(3000)));
+
+        assert_eq!(monitor.changes_since(0).len(), 2);
+        assert_eq!(monitor.changes_since(1)[0].name, "hwmon7");
+    }
+}
@@ -0,0 +1,138 @@
+//! Runtime Log Ring Buffer
+//!
+//! A tracing layer that keeps the most recent daemon log events in memory so
+//! clients can show them via `GetLogs`. Unprivileged users often cannot read
+//! the system journal, but they can reach the daemon socket.
+//!
+//! The layer sits behind the same `HYPERFAN_LOG` filter as the journal or
+//! stdout output, so it sees exactly what is logged there.
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use hf_protocol::{LogEntry, LogLevel};
+
+/// Maximum entries retained for polling clients (keeps responses under MAX_RESPONSE_SIZE)
+const MAX_RETAINED_ENTRIES: usize = 500;
+
+/// Longer messages are truncated
+const MAX_MESSAGE_LENGTH: usize = 512;
+
+struct LogBuffer {
+    next_id: u64,
+    entries: VecDeque<LogEntry>,
+}
+
+static BUFFER: Mutex<LogBuffer> = Mutex::new(LogBuffer { next_id: 1, entries: VecDeque::new() });
+
+/// Entries newer than `since_id` at `min_level` or more severe, oldest first
+pub fn entries_since(since_id: u64, min_level: Option<LogLevel>) -> Vec<LogEntry> {
+    let buffer = BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+    buffer
+        .entries
+        .iter()
+        .filter(|e| e.id > since_id && min_level.is_none_or(|min| e.level <= min))
+        .cloned()
+        .collect()
+}
+
+/// Tracing layer feeding the ring buffer
+pub struct LogBufferLayer;
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let mut message = visitor.message;
+        if !visitor.fields.is_empty() {
+            message.push_str(&visitor.fields);
+        }
+
+        let metadata = event.metadata();
+        let (subsystem, mut message) = split_subsystem(metadata.target(), message);
+        if message.len() > MAX_MESSAGE_LENGTH {
+            let mut end = MAX_MESSAGE_LENGTH;
+            while !message.is_char_boundary(end) {
+                end -= 1;
+            }
+            message.truncate(end);
+            message.push('…');
+        }
+
+        let level = match *metadata.level() {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warn,
+            Level::INFO => LogLevel::Info,
+            Level::DEBUG => LogLevel::Debug,
+            Level::TRACE => LogLevel::Trace,
+        };
+        let timestamp_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let mut buffer = BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+        let id = buffer.next_id;
+        buffer.next_id += 1;
+        buffer.entries.push_back(LogEntry { id, level, timestamp_ms, subsystem, message });
+        while buffer.entries.len() > MAX_RETAINED_ENTRIES {
+            buffer.entries.pop_front();
+        }
+    }
+}
+
+/// Collects the `message` field and appends any others as `key=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Use an upper-case message prefix ("AUDIT: ...") as the subsystem, else the
+/// last segment of the target module path
+fn split_subsystem(target: &str, message: String) -> (String, String) {
+    if let Some((prefix, rest)) = message.split_once(": ") {
+        if !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_uppercase() || c == '_') {
+            return (prefix.to_string(), rest.to_string());
+        }
+    }
+    let module = target.rsplit("::").next().unwrap_or(target);
+    (module.to_string(), message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_subsystem() {
+        let (subsystem, message) = split_subsystem("hyperfand::server", "AUDIT: SetPwm by uid=1000".to_string());
+        assert_eq!((subsystem.as_str(), message.as_str()), ("AUDIT", "SetPwm by uid=1000"));
+
+        // Ordinary messages take the module name
+        let (subsystem, message) = split_subsystem("hyperfand::fan_control", "Loaded 3 pairs: ok".to_string());
+        assert_eq!((subsystem.as_str(), message.as_str()), ("fan_control", "Loaded 3 pairs: ok"));
+    }
+}
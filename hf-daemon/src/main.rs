@@ -10,16 +10,20 @@
 //! # Security Model
 //! - **Privilege**: Runs as root for /sys hardware access only
 //! - **Socket**: Unix domain socket with owner-only permissions (0600)
+//! - **D-Bus** (optional `dbus` feature): `io.github.hyperfan1` on the system bus, writes gated by polkit
 //! - **Authentication**: Validates client executable path (Linux) or socket permissions (BSD)
+//! - **Authorization**: Per-client tiers by UID/GID (/etc/hyperfan/daemon.json) and per-request polkit checks
 //! - **Validation**: Strict allowlist-based path validation
 //! - **Defense**: Path traversal, injection, and symlink attack prevention
-//! - **Audit**: Peer credential logging (UID/GID/PID) for all operations
+//! - **Audit**: Peer credential logging (UID/GID/PID) for all operations; control writes
+//!   are also appended to /var/log/hyperfan/audit.jsonl
 //! - **Limits**: Connection limits, message size limits, rate limiting
 //! - **Isolation**: Restrictive umask, working directory set to /
 //! - **Signals**: Graceful shutdown with resource cleanup
 //!
 //! # Hardening Measures
 //! - Environment sanitization (clear dangerous env vars)
+//! - systemd watchdog (`Type=notify`): restarted if the control loop stalls
 //! - Resource limits (RLIMIT_NOFILE, RLIMIT_CORE)
 //! - Restrictive umask (0077)
 //! - No core dumps in production
@@ -29,10 +33,42 @@
 //! - Per-client rate limiting
 
 mod server;
+mod rate_limit;
+mod audit_log;
 mod fan_control;
 mod drift_protection;
-
-use std::path::Path;
+mod stall_monitor;
+mod alarm_boost;
+mod recorder;
+mod history;
+mod calibration;
+mod detection;
+mod auto_tune;
+mod scheduler;
+mod adaptive_poll;
+mod power_monitor;
+mod original_state;
+mod dry_run;
+mod safe_mode;
+mod competing_software;
+mod foreign_writes;
+mod gpu_fans;
+mod ec_profiles;
+mod ec_guard;
+mod hotplug;
+mod config_watch;
+mod log_buffer;
+mod events;
+mod sensor_providers;
+mod plausibility;
+mod source_failover;
+mod sd_notify;
+#[cfg(feature = "dbus")]
+mod dbus_service;
+mod polkit;
+mod permissions;
+
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tracing::{info, error, warn, debug};
@@ -353,11 +389,21 @@ fn write_pid_file() -> std::io::Result<()> {
 
 fn cleanup(socket_path: &str) {
     debug!("Starting cleanup...");
-    
-    // Remove socket
-    if Path::new(socket_path).exists() {
-        if let Err(e) = std::fs::remove_file(socket_path) {
-            warn!("Failed to remove socket: {}", e);
+    sd_notify::stopping();
+
+    // Hand fans back to firmware/driver control
+    if let Err(e) = original_state::restore() {
+        warn!("Failed to restore original PWM state: {}", e);
+    }
+    ec_profiles::restore_auto();
+    gpu_fans::restore_auto();
+    
+    // Remove sockets
+    for path in [socket_path.to_string(), server::monitor_socket_path(socket_path)] {
+        if Path::new(&path).exists() {
+            if let Err(e) = std::fs::remove_file(&path) {
+                warn!("Failed to remove socket: {}", e);
+            }
         }
     }
     
@@ -381,10 +427,31 @@ fn print_help() {
     eprintln!();
     eprintln!("USAGE:");
     eprintln!("    hyperfand [OPTIONS]");
+    eprintln!("    hyperfand restore-state");
+    eprintln!("    hyperfand --provision BUNDLE [--dry-run] [--force]");
+    eprintln!("    hyperfand --check-config PATH [--hardware SNAPSHOT]");
+    eprintln!();
+    eprintln!("COMMANDS:");
+    eprintln!("    restore-state       Restore pre-hyperfand PWM modes/values and exit");
+    eprintln!("                        (systemd ExecStopPost, e.g. after a crash)");
     eprintln!();
     eprintln!("OPTIONS:");
     eprintln!("    -f, --foreground    Run in foreground (don't daemonize)");
     eprintln!("    -s, --socket PATH   Socket path (auto-detected per OS)");
+    eprintln!("    --provision BUNDLE  Check a config bundle against this machine, write");
+    eprintln!("                        its settings, curves and bindings, and exit");
+    eprintln!("    --dry-run           Run the control loop but only log the PWM writes it");
+    eprintln!("                        would make; with --provision: only report what");
+    eprintln!("                        would be written");
+    eprintln!("    --safe-mode         Hand every fan to firmware control and only monitor");
+    eprintln!("                        (also while /etc/hyperfan/DISABLE exists)");
+    eprintln!("    --force             With --provision: write even if paths or bindings");
+    eprintln!("                        don't match this machine");
+    eprintln!("    --check-config PATH Validate a config bundle or config directory against");
+    eprintln!("                        this machine, print a JSON report and exit (0 valid,");
+    eprintln!("                        2 invalid); needs no root");
+    eprintln!("    --hardware SNAPSHOT With --check-config: check against the output of");
+    eprintln!("                        `hyperfan hardware snapshot` instead of this machine");
     eprintln!("    -v, --version       Print version");
     eprintln!("    -h, --help          Print this help");
     eprintln!();
@@ -402,6 +469,78 @@ fn print_version() {
     println!("hyperfand {}", VERSION);
 }
 
+/// Apply a config bundle for `--provision` and return the exit code
+fn provision(path: &str, dry_run: bool, force: bool) -> i32 {
+    let bundle = match hf_core::ConfigBundle::load(Path::new(path)) {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+    let check = hf_core::check_bundle(&bundle);
+
+    if let Some(plan) = &check.bindings {
+        println!("Bindings: {} of {} match this machine", plan.importable_count(), plan.entries.len());
+        for entry in plan.entries.iter().filter(|e| !e.importable()) {
+            println!("  not matched: {} ({}, was {})", entry.name, entry.chip_name, entry.exported_pwm_path);
+        }
+    }
+    for repoint in &check.repointed {
+        println!("Moved: {} -> {}", repoint.from, repoint.to);
+    }
+    for missing in &check.missing {
+        println!("Missing: {}", missing);
+    }
+
+    if dry_run {
+        println!("Dry run: nothing written{}", if check.is_clean() { "" } else { " (bundle does not fit this machine)" });
+        return if check.is_clean() { 0 } else { 2 };
+    }
+    match hf_core::apply_bundle(&bundle, &check, force) {
+        Ok(outcome) => {
+            println!("Wrote {} and {}", outcome.settings_path.display(), outcome.curves_path.display());
+            if let Some(bindings) = outcome.bindings {
+                println!("Bound {} outputs, skipped {}", bindings.imported.len(), bindings.skipped.len());
+            }
+            println!("Restart hyperfand if it is running to apply the new config");
+            0
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+/// Validate a bundle or config directory for `--check-config` and return the exit code
+///
+/// The report goes to stdout as JSON so CI can parse it; only a snapshot
+/// that cannot be read is reported as text on stderr.
+fn check_config(path: &Path, snapshot: Option<&Path>) -> i32 {
+    let snapshot = match snapshot.map(read_snapshot).transpose() {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+    let report = hf_core::check_config(path, snapshot.as_ref());
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    }
+    if report.valid { 0 } else { 2 }
+}
+
+fn read_snapshot(path: &Path) -> Result<hf_core::RawControllerSnapshot, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("{}: not a hardware snapshot: {}", path.display(), e))
+}
+
 // ============================================================================
 // Main Entry Point
 // ============================================================================
@@ -430,6 +569,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Don't abort - let the panic unwind and be caught by catch_unwind if used
     }));
     
+    // Relative paths in arguments are relative to where we were started,
+    // not to the "/" we chdir to below
+    let invocation_dir = std::env::current_dir().unwrap_or_default();
+
     // PHASE 1: Pre-initialization security hardening
     // These must happen before ANY other code runs
     sanitize_environment();
@@ -440,6 +583,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // PHASE 2: Parse arguments (minimal code, no allocations if possible)
     let args: Vec<String> = std::env::args().collect();
     let mut socket_path = get_default_socket_path().to_string();
+    let mut restore_state = false;
+    let mut provision_bundle: Option<String> = None;
+    let mut dry_run = false;
+    let mut safe_mode = false;
+    let mut force = false;
+    let mut check_config_path: Option<PathBuf> = None;
+    let mut hardware_snapshot: Option<PathBuf> = None;
     
     let mut i = 1;
     while i < args.len() {
@@ -452,6 +602,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 print_version();
                 return Ok(());
             }
+            "restore-state" => {
+                restore_state = true;
+            }
             "-f" | "--foreground" => {
                 // Foreground mode is always on (no daemonization implemented)
             }
@@ -463,6 +616,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 socket_path = args[i].clone();
             }
+            "--provision" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("Error: --provision requires a bundle path");
+                    std::process::exit(1);
+                }
+                provision_bundle = Some(args[i].clone());
+            }
+            "--dry-run" => {
+                dry_run = true;
+            }
+            "--safe-mode" => {
+                safe_mode = true;
+            }
+            "--force" => {
+                force = true;
+            }
+            "--check-config" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("Error: --check-config requires a bundle or directory path");
+                    std::process::exit(1);
+                }
+                check_config_path = Some(invocation_dir.join(&args[i]));
+            }
+            "--hardware" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("Error: --hardware requires a snapshot path");
+                    std::process::exit(1);
+                }
+                hardware_snapshot = Some(invocation_dir.join(&args[i]));
+            }
             arg => {
                 eprintln!("Unknown argument: {}", arg);
                 print_help();
@@ -472,7 +658,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         i += 1;
     }
 
+    // Read-only and scripted: no logging on stdout, no root needed
+    if let Some(path) = check_config_path {
+        std::process::exit(check_config(&path, hardware_snapshot.as_deref()));
+    }
+
     // PHASE 3: Initialize logging to systemd journal
+    // Recent events are also kept in memory for GetLogs
+    use tracing_subscriber::prelude::*;
     let log_level = std::env::var("HYPERFAN_LOG")
         .unwrap_or_else(|_| "info".to_string());
     
@@ -483,10 +676,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Use journald for system logging
         match tracing_journald::layer() {
             Ok(journald_layer) => {
-                use tracing_subscriber::prelude::*;
                 tracing_subscriber::registry()
                     .with(journald_layer)
                     .with(tracing_subscriber::EnvFilter::new(&log_level))
+                    .with(log_buffer::LogBufferLayer)
                     .init();
             }
             Err(e) => {
@@ -497,6 +690,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     .with_target(false)
                     .with_level(true)
                     .with_env_filter(&log_level)
+                    .finish()
+                    .with(log_buffer::LogBufferLayer)
                     .init();
             }
         }
@@ -506,6 +701,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .with_target(false)
             .with_level(true)
             .with_env_filter(&log_level)
+            .finish()
+            .with(log_buffer::LogBufferLayer)
             .init();
     }
 
@@ -518,6 +715,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         error!("{}", e);
         std::process::exit(1);
     }
+
+    if let Some(path) = provision_bundle {
+        std::process::exit(provision(&path, dry_run, force));
+    }
+    if dry_run {
+        crate::dry_run::enable_at_startup();
+    }
+    if safe_mode {
+        crate::safe_mode::enable_at_startup("--safe-mode");
+    } else if crate::safe_mode::sentinel_present() {
+        crate::safe_mode::enable_at_startup(crate::safe_mode::SENTINEL_PATH);
+    }
+
+    if restore_state {
+        return match original_state::restore() {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(1);
+            }
+        };
+    }
     
     if let Err(e) = validate_socket_path(&socket_path) {
         error!("Invalid socket path: {}", e);
@@ -556,6 +775,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let fan_control_state = Arc::new(fan_control::FanControlState::new());
     let shutdown_flag = Arc::new(AtomicBool::new(false));
 
+    // PHASE 7.5: Watch for hwmon/DRM hotplug (USB sensors, eGPU docks, module loads)
+    hotplug::spawn_listener(fan_control_state.clone());
+
+    // PHASE 7.55: Apply settings and curves edited by hand or config management
+    config_watch::spawn_listener(fan_control_state.clone());
+
+    // PHASE 7.6: External sensor providers from daemon.json (IPMI, switches, ...)
+    sensor_providers::spawn_providers(permissions::DaemonConfig::load().sensor_providers());
+
+    // PHASE 7.7: Long-term sensor history on disk (QueryHistory)
+    history::spawn_sampler(fan_control_state.clone());
+
+    // PHASE 7.8: Other fan control software would fight the control loop
+    competing_software::warn_at_startup();
+
     // PHASE 8: Start fan control loop in background
     let fan_state_clone = fan_control_state.clone();
     let shutdown_clone = shutdown_flag.clone();
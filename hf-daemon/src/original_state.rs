@@ -0,0 +1,140 @@
+//! Original PWM State
+//!
+//! Before hyperfand takes over a PWM channel, its `pwm_enable` mode and raw
+//! `pwm` value are saved to `/run/hyperfan/original-state.json`. They are
+//! restored on clean shutdown, and by `hyperfand restore-state` (systemd
+//! `ExecStopPost`) after a crash or OOM kill, so fans go back to firmware or
+//! driver control instead of sticking at the last manual value. Values are
+//! read and written through hf-core, so FreeBSD sysctl fans are covered too.
+//!
+//! An existing file is never overwritten: after a crash it still holds the
+//! values from before the first instance started. `/run` is a tmpfs, so the
+//! file does not survive a reboot (when hwmon paths may change).
+
+use std::io::Write;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+/// Runtime state file (cleared on reboot)
+pub const STATE_PATH: &str = "/run/hyperfan/original-state.json";
+
+/// Current file format version
+const FORMAT_VERSION: u32 = 1;
+
+/// Saved state of one sysfs PWM channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedChannel {
+    pwm_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    enable_path: Option<String>,
+    /// Raw `pwmN_enable` contents (e.g. "2" = automatic)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    enable: Option<String>,
+    /// Raw `pwmN` value (0-255)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pwm: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OriginalState {
+    version: u32,
+    channels: Vec<SavedChannel>,
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    hf_core::read_control_value(Path::new(path)).ok()
+}
+
+fn exists(path: &str) -> bool {
+    hf_core::control_path_exists(Path::new(path))
+}
+
+/// Save the current mode and value of each `(pwm_path, enable_path)` channel
+/// Does nothing if a state file already exists
+pub fn save(channels: &[(String, String)]) {
+    let path = Path::new(STATE_PATH);
+    if path.exists() {
+        info!("STATE: Keeping existing original PWM state from a previous run ({})", STATE_PATH);
+        return;
+    }
+
+    let state = OriginalState {
+        version: FORMAT_VERSION,
+        channels: channels
+            .iter()
+            .filter(|(pwm_path, _)| exists(pwm_path))
+            .map(|(pwm_path, enable_path)| {
+                let enable_path = (!enable_path.is_empty() && exists(enable_path))
+                    .then(|| enable_path.clone());
+                SavedChannel {
+                    pwm_path: pwm_path.clone(),
+                    enable: enable_path.as_deref().and_then(read_trimmed),
+                    enable_path,
+                    pwm: read_trimmed(pwm_path).and_then(|v| v.parse().ok()),
+                }
+            })
+            .collect(),
+    };
+
+    match write_state(path, &state) {
+        Ok(()) => info!("STATE: Saved original state of {} PWM channels to {}", state.channels.len(), STATE_PATH),
+        Err(e) => warn!("STATE: Failed to save original PWM state: {}", e),
+    }
+}
+
+fn write_state(path: &Path, state: &OriginalState) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    // Write-then-rename so a crash never leaves a truncated file
+    let tmp = path.with_extension("json.tmp");
+    let mut file = std::fs::File::create(&tmp)?;
+    file.write_all(&serde_json::to_vec_pretty(state)?)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Restore saved channels and remove the state file
+/// Returns the number of channels restored
+pub fn restore() -> Result<usize, String> {
+    let path = Path::new(STATE_PATH);
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            debug!("STATE: No original PWM state to restore");
+            return Ok(0);
+        }
+        Err(e) => return Err(format!("Failed to read {}: {}", STATE_PATH, e)),
+    };
+    let state: OriginalState = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid {}: {}", STATE_PATH, e))?;
+
+    // Modes change below; the control loop must not take that for another writer
+    crate::competing_software::forget_enable_modes();
+    crate::foreign_writes::forget_all();
+
+    let mut restored = 0;
+    for channel in &state.channels {
+        // Value first: switching to automatic mode may ignore later pwm writes
+        if let Some(value) = channel.pwm {
+            if let Err(e) = hf_core::write_control_value(Path::new(&channel.pwm_path), &value.to_string()) {
+                warn!("STATE: Failed to restore {} = {}: {}", channel.pwm_path, value, e);
+            }
+        }
+        if let (Some(enable_path), Some(mode)) = (&channel.enable_path, &channel.enable) {
+            if let Err(e) = hf_core::write_control_value(Path::new(enable_path), mode) {
+                warn!("STATE: Failed to restore {} = {}: {}", enable_path, mode, e);
+                continue;
+            }
+        }
+        debug!("STATE: Restored {} (enable={:?}, pwm={:?})", channel.pwm_path, channel.enable, channel.pwm);
+        restored += 1;
+    }
+
+    if let Err(e) = std::fs::remove_file(path) {
+        warn!("STATE: Failed to remove {}: {}", STATE_PATH, e);
+    }
+    info!("STATE: Restored original state of {}/{} PWM channels", restored, state.channels.len());
+    Ok(restored)
+}
@@ -0,0 +1,261 @@
+//! Client Permission Tiers
+//!
+//! Assigns every client a capability tier from its peer UID/GID:
+//! - **monitor**: sensor readings and status (`Request::is_monitoring`)
+//! - **read_only**: sensors, graphs, hardware lists, EC reads, logs
+//! - **control**: everything else (SetPwm, curves, GPU fans, EC writes)
+//!
+//! Tiers are configured in `/etc/hyperfan/daemon.json`:
+//!
+//! ```json
+//! {
+//!   "default_tier": "read_only",
+//!   "clients": [
+//!     { "uid": 1000, "tier": "control" },
+//!     { "gid": 10, "tier": "control" }
+//!   ],
+//!   "monitor_socket": { "enabled": true, "gid": 1001 }
+//! }
+//! ```
+//!
+//! The same file lists external sensor providers (`sensor_providers`, see
+//! `crate::sensor_providers`).
+//!
+//! A UID rule takes precedence over GID rules; among matching GID rules
+//! (primary or supplementary groups) the highest tier wins. Root is always
+//! `control`. Without a config file every client gets `default_tier`
+//! (`control`), so polkit remains the only gate for changes (and there is
+//! none where polkit is not installed).
+//!
+//! The file must be owned by root and not group/world-writable; otherwise it is
+//! ignored and all non-root clients are read-only.
+//!
+//! Connections on the monitoring socket skip executable-path validation and
+//! always get the `monitor` tier. The socket is world-accessible (0666)
+//! unless `monitor_socket.gid` restricts it to a group (0660).
+
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use hf_core::constants::{limits, paths};
+
+/// Client capability tier (ordered: higher tiers include lower ones)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientTier {
+    Monitor,
+    ReadOnly,
+    Control,
+}
+
+impl ClientTier {
+    /// Whether this tier may execute the request
+    pub fn allows(self, request: &hf_protocol::Request) -> bool {
+        match self {
+            ClientTier::Monitor => request.is_monitoring(),
+            ClientTier::ReadOnly => request.is_read_only(),
+            ClientTier::Control => true,
+        }
+    }
+
+    /// Who a denied request came from, for errors and the audit log
+    pub fn description(self) -> &'static str {
+        match self {
+            ClientTier::Monitor => "monitoring client",
+            ClientTier::ReadOnly => "read-only client",
+            ClientTier::Control => "control client",
+        }
+    }
+}
+
+/// Monitoring socket settings
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MonitorSocketConfig {
+    #[serde(default = "default_monitor_enabled")]
+    pub enabled: bool,
+    /// Group allowed to connect (socket mode 0660); everyone when unset (0666)
+    #[serde(default)]
+    pub gid: Option<u32>,
+}
+
+fn default_monitor_enabled() -> bool {
+    true
+}
+
+impl Default for MonitorSocketConfig {
+    fn default() -> Self {
+        Self { enabled: default_monitor_enabled(), gid: None }
+    }
+}
+
+/// A single UID or GID rule
+#[derive(Debug, Clone, Deserialize)]
+struct ClientRule {
+    #[serde(default)]
+    uid: Option<u32>,
+    #[serde(default)]
+    gid: Option<u32>,
+    tier: ClientTier,
+}
+
+/// Daemon configuration file contents
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DaemonConfig {
+    /// Tier for clients that match no rule
+    #[serde(default = "default_tier")]
+    default_tier: ClientTier,
+    #[serde(default)]
+    clients: Vec<ClientRule>,
+    #[serde(default)]
+    monitor_socket: MonitorSocketConfig,
+    #[serde(default)]
+    sensor_providers: Vec<crate::sensor_providers::ProviderConfig>,
+}
+
+fn default_tier() -> ClientTier {
+    ClientTier::Control
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            default_tier: default_tier(),
+            clients: Vec::new(),
+            monitor_socket: MonitorSocketConfig::default(),
+            sensor_providers: Vec::new(),
+        }
+    }
+}
+
+impl DaemonConfig {
+    /// Load the daemon config from /etc/hyperfan
+    /// Missing file -> defaults; unsafe or invalid file -> read-only for everyone but root
+    pub fn load() -> Self {
+        let path = Path::new(paths::CONFIG_DIR).join(paths::DAEMON_CONFIG_FILE);
+        if !path.exists() {
+            return Self::default();
+        }
+        match Self::load_from(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Ignoring {}: {} - non-root clients are read-only", path.display(), e);
+                Self::locked_down()
+            }
+        }
+    }
+
+    fn load_from(path: &Path) -> Result<Self, String> {
+        let metadata = std::fs::symlink_metadata(path).map_err(|e| e.to_string())?;
+        if !metadata.file_type().is_file() {
+            return Err("not a regular file".to_string());
+        }
+        if metadata.uid() != 0 || metadata.mode() & 0o022 != 0 {
+            return Err("must be owned by root and not group/world-writable".to_string());
+        }
+        if metadata.len() > limits::MAX_DAEMON_CONFIG_SIZE {
+            return Err("file too large".to_string());
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&content).map_err(|e| format!("invalid JSON: {}", e))
+    }
+
+    fn locked_down() -> Self {
+        Self {
+            default_tier: ClientTier::ReadOnly,
+            clients: Vec::new(),
+            monitor_socket: MonitorSocketConfig::default(),
+            sensor_providers: Vec::new(),
+        }
+    }
+
+    pub fn monitor_socket(&self) -> MonitorSocketConfig {
+        self.monitor_socket
+    }
+
+    pub fn sensor_providers(&self) -> &[crate::sensor_providers::ProviderConfig] {
+        &self.sensor_providers
+    }
+
+    /// Resolve the tier for a peer
+    pub fn tier_for(&self, uid: u32, gid: u32, pid: i32) -> ClientTier {
+        if uid == 0 {
+            return ClientTier::Control;
+        }
+
+        if let Some(rule) = self.clients.iter().find(|r| r.uid == Some(uid)) {
+            return rule.tier;
+        }
+
+        let mut groups = supplementary_groups(pid);
+        groups.push(gid);
+        self.clients
+            .iter()
+            .filter(|r| r.gid.is_some_and(|g| groups.contains(&g)))
+            .map(|r| r.tier)
+            .max()
+            .unwrap_or(self.default_tier)
+    }
+}
+
+/// Supplementary groups of a process (from /proc/<pid>/status)
+fn supplementary_groups(pid: i32) -> Vec<u32> {
+    if pid <= 0 {
+        return Vec::new();
+    }
+    let Ok(status) = std::fs::read_to_string(format!("/proc/{}/status", pid)) else {
+        debug!("Could not read groups for pid={}", pid);
+        return Vec::new();
+    };
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Groups:"))
+        .map(|ids| ids.split_whitespace().filter_map(|g| g.parse().ok()).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hf_protocol::Request;
+
+    #[test]
+    fn test_tier_resolution() {
+        let config: DaemonConfig = serde_json::from_str(
+            r#"{
+                "default_tier": "read_only",
+                "clients": [
+                    { "uid": 1000, "tier": "control" },
+                    { "uid": 1001, "tier": "read_only" },
+                    { "gid": 10, "tier": "control" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.tier_for(0, 0, 0), ClientTier::Control);
+        assert_eq!(config.tier_for(1000, 1000, 0), ClientTier::Control);
+        // UID rule wins over a matching group
+        assert_eq!(config.tier_for(1001, 10, 0), ClientTier::ReadOnly);
+        assert_eq!(config.tier_for(1002, 10, 0), ClientTier::Control);
+        assert_eq!(config.tier_for(1003, 1003, 0), ClientTier::ReadOnly);
+
+        let write = Request::WriteEcRegister { chip_path: "/dev/port".to_string(), register: 0, value: 0 };
+        assert!(!ClientTier::ReadOnly.allows(&write));
+        assert!(ClientTier::ReadOnly.allows(&Request::ListHardware));
+        assert!(ClientTier::Control.allows(&write));
+
+        // The monitor tier gets sensor readings but not EC reads or logs
+        let ec_read = Request::ReadEcRegister { chip_path: "/dev/port".to_string(), register: 0 };
+        assert!(ClientTier::Monitor.allows(&Request::ListAll));
+        assert!(ClientTier::Monitor.allows(&Request::Subscribe { since_seq: None }));
+        assert!(!ClientTier::Monitor.allows(&ec_read));
+        assert!(!ClientTier::Monitor.allows(&Request::GetLogs { since_id: 0, min_level: None }));
+        assert!(ClientTier::ReadOnly.allows(&ec_read));
+    }
+}
@@ -0,0 +1,129 @@
+//! Sensor Plausibility
+//!
+//! Readings the control loop takes from hardware pass through here before
+//! filters, curves or the failsafe see them. Physically implausible values are
+//! turned into read errors, so a pair falls back (or fails over to its next
+//! source) exactly as if the sensor could not be read:
+//!
+//! - temperatures below 0°C or not finite (virtual sensors may be negative:
+//!   a delta between two sources is)
+//! - temperatures more than `MAX_TEMP_JUMP_CELSIUS` from the last accepted
+//!   reading of the same source, i.e. within one tick
+//! - fan speeds above `MAX_FAN_RPM`
+//!
+//! Rejections are counted per source and reported by `GetControlStatus`.
+
+use std::collections::{BTreeMap, HashMap};
+use hf_protocol::ImplausibleReadings;
+use tracing::warn;
+
+/// Largest change between two readings of a source taken as real
+pub const MAX_TEMP_JUMP_CELSIUS: f32 = 125.0;
+
+/// Fastest fan speed taken as real; above it a tachometer reading is noise
+pub const MAX_FAN_RPM: u32 = 20_000;
+
+#[derive(Debug, Default)]
+struct SourceState {
+    last_accepted: Option<f32>,
+    out_of_range: u64,
+    jumps: u64,
+    last_rejected: f32,
+}
+
+impl SourceState {
+    fn reject_range(&mut self, path: &str, value: f32, unit: &str) -> String {
+        if self.out_of_range == 0 {
+            warn!("PLAUSIBILITY: {} read {}{} - rejecting implausible readings", path, value, unit);
+        }
+        self.out_of_range += 1;
+        self.last_rejected = value;
+        format!("implausible reading {}{}", value, unit)
+    }
+}
+
+/// Per-source validation state and rejection counters
+#[derive(Debug, Default)]
+pub struct Plausibility {
+    sources: HashMap<String, SourceState>,
+}
+
+impl Plausibility {
+    /// Validate a temperature reading of `path`; call once per source and iteration
+    pub fn check_temperature(&mut self, path: &str, celsius: f32) -> Result<f32, String> {
+        let source = self.sources.entry(path.to_string()).or_default();
+        let negative_allowed = hf_core::virtual_sensor_id(path).is_some();
+        if !celsius.is_finite() || (celsius < 0.0 && !negative_allowed) {
+            return Err(source.reject_range(path, celsius, "°C"));
+        }
+        if let Some(last) = source.last_accepted {
+            if (celsius - last).abs() > MAX_TEMP_JUMP_CELSIUS {
+                if source.jumps == 0 {
+                    warn!("PLAUSIBILITY: {} jumped from {:.1}°C to {:.1}°C in one reading - rejected", path, last, celsius);
+                }
+                source.jumps += 1;
+                source.last_rejected = celsius;
+                return Err(format!("implausible jump from {:.1}°C to {:.1}°C", last, celsius));
+            }
+        }
+        source.last_accepted = Some(celsius);
+        Ok(celsius)
+    }
+
+    /// Validate a fan speed reading of `path`
+    pub fn check_rpm(&mut self, path: &str, rpm: u32) -> Result<u32, String> {
+        if rpm <= MAX_FAN_RPM {
+            return Ok(rpm);
+        }
+        let source = self.sources.entry(path.to_string()).or_default();
+        Err(source.reject_range(path, rpm as f32, " RPM"))
+    }
+
+    /// Rejection counters of every source that had a reading rejected, by path
+    pub fn counters(&self) -> Vec<ImplausibleReadings> {
+        self.sources
+            .iter()
+            .filter(|(_, s)| s.out_of_range > 0 || s.jumps > 0)
+            .map(|(path, s)| (path.clone(), ImplausibleReadings {
+                path: path.clone(),
+                out_of_range: s.out_of_range,
+                jumps: s.jumps,
+                last_rejected: s.last_rejected,
+            }))
+            .collect::<BTreeMap<_, _>>()
+            .into_values()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMP: &str = "/sys/class/hwmon/hwmon2/temp1_input";
+
+    #[test]
+    fn test_temperature_checks() {
+        let mut plausibility = Plausibility::default();
+        assert_eq!(plausibility.check_temperature(TEMP, 40.0), Ok(40.0));
+        assert!(plausibility.check_temperature(TEMP, -5.0).is_err());
+        assert!(plausibility.check_temperature(TEMP, f32::NAN).is_err());
+        assert!(plausibility.check_temperature(TEMP, 200.0).is_err());
+        // Jumps are measured from the last accepted reading
+        assert_eq!(plausibility.check_temperature(TEMP, 90.0), Ok(90.0));
+
+        let counters = plausibility.counters();
+        assert_eq!(counters.len(), 1);
+        assert_eq!(counters[0].out_of_range, 2);
+        assert_eq!(counters[0].jumps, 1);
+        assert_eq!(counters[0].last_rejected, 200.0);
+    }
+
+    #[test]
+    fn test_rpm_checks() {
+        let mut plausibility = Plausibility::default();
+        assert_eq!(plausibility.check_rpm("/sys/class/hwmon/hwmon2/fan1_input", 1200), Ok(1200));
+        assert!(plausibility.check_rpm("/sys/class/hwmon/hwmon2/fan1_input", 65535).is_err());
+        assert_eq!(plausibility.counters()[0].out_of_range, 1);
+    }
+}
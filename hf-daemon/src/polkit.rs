@@ -0,0 +1,225 @@
+//! Polkit Authorization
+//!
+//! Asks polkit (via `pkcheck`) whether a client process may perform a Hyperfan
+//! action. Actions are declared in `io.github.hyperfan1.policy`, installed to
+//! `/usr/share/polkit-1/actions/`.
+//!
+//! Requests that change fan speeds, EC registers, or configuration need the
+//! **control** action. Read-only requests are decided by the client's tier
+//! alone: the policy's **monitor** action is granted to everyone, so asking
+//! would only cost a `pkcheck` run per connection.
+//!
+//! Where polkit cannot answer (no `pkcheck`, no polkitd, or the policy is not
+//! installed), the tier decides as well, so systems without polkit keep
+//! working with `/etc/hyperfan/daemon.json` as the only gate.
+//!
+//! The subject is identified by `pid,start-time,uid` so a recycled PID cannot
+//! inherit another process's authorization.
+
+use std::collections::{HashMap, HashSet};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use hf_protocol::Request;
+
+/// Change fan speeds, EC registers, or daemon configuration
+pub const ACTION_CONTROL: &str = "io.github.hyperfan1.control";
+
+/// How long a denial is remembered per client (avoids re-prompting on every write)
+const DENIAL_BACKOFF: Duration = Duration::from_secs(30);
+
+/// `pkcheck` exit status when it could not ask (action not registered, no polkitd)
+const PKCHECK_ERROR: i32 = 127;
+
+/// Polkit action required to execute a request (None: the tier decides)
+pub fn action_for(request: &Request) -> Option<&'static str> {
+    (!request.is_read_only()).then_some(ACTION_CONTROL)
+}
+
+/// What polkit answered for a subject and action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Authorization {
+    Granted,
+    Denied,
+    /// Polkit could not be asked; the client tier decides
+    Unavailable,
+}
+
+impl Authorization {
+    pub fn allows(self) -> bool {
+        self != Authorization::Denied
+    }
+
+    /// Map a `pkcheck` exit code (None: killed by a signal)
+    fn from_exit_code(code: Option<i32>) -> Self {
+        match code {
+            Some(0) => Authorization::Granted,
+            Some(PKCHECK_ERROR) => Authorization::Unavailable,
+            _ => Authorization::Denied,
+        }
+    }
+}
+
+/// Authorization state for one client (a socket connection or a D-Bus sender)
+/// Grants last as long as the client; denials are retried after [`DENIAL_BACKOFF`]
+pub struct ClientAuthorization {
+    pid: u32,
+    uid: u32,
+    granted: HashSet<&'static str>,
+    denied_at: HashMap<&'static str, Instant>,
+}
+
+impl ClientAuthorization {
+    pub fn new(pid: u32, uid: u32) -> Self {
+        Self {
+            pid,
+            uid,
+            granted: HashSet::new(),
+            denied_at: HashMap::new(),
+        }
+    }
+
+    /// Whether this state belongs to the given process
+    #[cfg(feature = "dbus")]
+    pub fn is_for(&self, pid: u32, uid: u32) -> bool {
+        self.pid == pid && self.uid == uid
+    }
+
+    /// Check `action`, asking polkit on a blocking thread if not already decided
+    /// The polkit agent may show an authentication dialog, so this can take a while
+    pub async fn check(&mut self, action: &'static str) -> bool {
+        if let Some(allowed) = self.decided(action) {
+            return allowed;
+        }
+
+        let (pid, uid) = (self.pid, self.uid);
+        let allowed = tokio::task::spawn_blocking(move || check_authorization(pid, uid, action))
+            .await
+            .is_ok_and(Authorization::allows);
+        self.record(action, allowed);
+        allowed
+    }
+
+    /// Like [`check`](Self::check), for callers outside the async runtime (the D-Bus thread)
+    #[cfg(feature = "dbus")]
+    pub fn check_blocking(&mut self, action: &'static str) -> bool {
+        if let Some(allowed) = self.decided(action) {
+            return allowed;
+        }
+
+        let allowed = check_authorization(self.pid, self.uid, action).allows();
+        self.record(action, allowed);
+        allowed
+    }
+
+    /// Decision that doesn't need polkit (None: ask)
+    fn decided(&self, action: &'static str) -> Option<bool> {
+        if self.uid == 0 || self.granted.contains(action) {
+            return Some(true);
+        }
+        // Polkit subjects need a PID; BSD peers have none and rely on socket permissions
+        if !cfg!(target_os = "linux") {
+            return Some(true);
+        }
+        if self.denied_at.get(action).is_some_and(|at| at.elapsed() < DENIAL_BACKOFF) {
+            return Some(false);
+        }
+        None
+    }
+
+    fn record(&mut self, action: &'static str, allowed: bool) {
+        if allowed {
+            self.granted.insert(action);
+            self.denied_at.remove(action);
+        } else {
+            self.denied_at.insert(action, Instant::now());
+        }
+    }
+}
+
+/// Check whether the process is authorized for `action`
+/// Root is always authorized; a process whose start time cannot be read is denied
+pub fn check_authorization(pid: u32, uid: u32, action: &str) -> Authorization {
+    if uid == 0 {
+        return Authorization::Granted;
+    }
+
+    let Some(start_time) = process_start_time(pid) else {
+        warn!("POLKIT: Cannot read start time of pid={}, denying {}", pid, action);
+        return Authorization::Denied;
+    };
+
+    let subject = format!("{},{},{}", pid, start_time, uid);
+    match Command::new("pkcheck")
+        .args(["--action-id", action, "--process", &subject, "--allow-user-interaction"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+    {
+        Ok(status) => {
+            let answer = Authorization::from_exit_code(status.code());
+            match answer {
+                Authorization::Granted => debug!("POLKIT: {} granted to uid={}, pid={}", action, uid, pid),
+                Authorization::Denied => warn!("POLKIT: {} denied to uid={}, pid={} ({})", action, uid, pid, status),
+                Authorization::Unavailable => {
+                    warn!("POLKIT: pkcheck could not check {} (policy not installed?) - client tier decides", action)
+                }
+            }
+            answer
+        }
+        Err(e) => {
+            warn!("POLKIT: Could not run pkcheck ({}) - client tier decides {}", e, action);
+            Authorization::Unavailable
+        }
+    }
+}
+
+/// Process start time in clock ticks since boot (field 22 of /proc/<pid>/stat)
+fn process_start_time(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name (field 2) may contain spaces, so count fields after its closing ')'
+    let after_comm = &stat[stat.rfind(')')? + 1..];
+    after_comm.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_for() {
+        assert_eq!(action_for(&Request::ListHardware), None);
+        assert_eq!(action_for(&Request::SetPwm { path: "/sys/class/hwmon/hwmon0/pwm1".to_string(), value: 128 }),
+                   Some(ACTION_CONTROL));
+    }
+
+    #[test]
+    fn test_pkcheck_exit_codes() {
+        assert_eq!(Authorization::from_exit_code(Some(0)), Authorization::Granted);
+        // Not authorized, no agent, dialog dismissed
+        for code in [1, 2, 3] {
+            assert_eq!(Authorization::from_exit_code(Some(code)), Authorization::Denied);
+        }
+        assert_eq!(Authorization::from_exit_code(Some(PKCHECK_ERROR)), Authorization::Unavailable);
+        assert_eq!(Authorization::from_exit_code(None), Authorization::Denied);
+        assert!(Authorization::Unavailable.allows());
+    }
+
+    #[test]
+    fn test_cached_decisions() {
+        let mut auth = ClientAuthorization::new(1234, 1000);
+        assert_eq!(auth.decided(ACTION_CONTROL), if cfg!(target_os = "linux") { None } else { Some(true) });
+
+        auth.record(ACTION_CONTROL, false);
+        if cfg!(target_os = "linux") {
+            assert_eq!(auth.decided(ACTION_CONTROL), Some(false));
+        }
+        auth.record(ACTION_CONTROL, true);
+        assert_eq!(auth.decided(ACTION_CONTROL), Some(true));
+
+        // Root never asks
+        assert_eq!(ClientAuthorization::new(1, 0).decided(ACTION_CONTROL), Some(true));
+    }
+}
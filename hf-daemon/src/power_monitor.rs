@@ -0,0 +1,48 @@
+//! Power Source Monitor
+//!
+//! Polls `/sys/class/power_supply` from the control loop and reloads the
+//! configuration when the system switches between AC and battery, so curves
+//! with a `battery_offset` are rebuilt for the new power source.
+
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+use hf_core::constants::power;
+use hf_core::PowerSource;
+
+use crate::fan_control::FanControlState;
+
+/// Tracks the last observed power source
+#[derive(Default)]
+pub struct PowerMonitor {
+    last_check: Option<Instant>,
+    source: Option<PowerSource>,
+}
+
+impl PowerMonitor {
+    /// Re-read the power source if the check interval has elapsed
+    pub fn tick(&mut self, state: &FanControlState) {
+        let interval = Duration::from_secs(power::CHECK_INTERVAL_SECS);
+        if self.last_check.is_some_and(|at| at.elapsed() < interval) {
+            return;
+        }
+        self.last_check = Some(Instant::now());
+
+        let source = hf_core::get_power_source();
+        if self.source == Some(source) {
+            return;
+        }
+        let previous = self.source.replace(source);
+
+        let on_battery = source == PowerSource::Battery;
+        state.on_battery.store(on_battery, Ordering::SeqCst);
+        match previous {
+            None => info!("POWER: Running on {}", source),
+            Some(previous) => {
+                info!("POWER: Power source changed from {} to {}", previous, source);
+                state.signal_reload();
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Request Rate Limiting
+//!
+//! Every client UID has a token bucket. Requests take tokens according to
+//! their class (`Request::class`), so a burst of cheap sensor reads costs far
+//! less than a single fan detection run. Buckets refill continuously at
+//! `limit` tokens per `RATE_LIMIT_WINDOW`, up to `burst`.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use hf_protocol::{RateLimitConfig, RequestClass, RequestCosts};
+
+/// Rate limit: default tokens refilled per window (matches client default)
+pub const DEFAULT_RATE_LIMIT_REQUESTS: u32 = 1500;
+
+/// Window the rate limit is expressed over
+pub const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token buckets keyed by client UID
+pub(crate) struct RateLimiter {
+    buckets: HashMap<u32, Bucket>,
+    /// Tokens refilled per window (configurable at runtime)
+    limit: u32,
+    /// Bucket capacity; follows `limit` until set explicitly
+    burst: Option<u32>,
+    costs: RequestCosts,
+}
+
+impl RateLimiter {
+    pub(crate) fn new() -> Self {
+        Self {
+            buckets: HashMap::new(),
+            limit: DEFAULT_RATE_LIMIT_REQUESTS,
+            burst: None,
+            costs: RequestCosts::default(),
+        }
+    }
+
+    fn capacity(&self) -> f64 {
+        self.burst.unwrap_or(self.limit) as f64
+    }
+
+    /// Take the tokens a request of `class` costs from `uid`'s bucket
+    /// Returns false (taking nothing) if the bucket does not hold enough
+    pub(crate) fn check(&mut self, uid: u32, class: RequestClass) -> bool {
+        self.take(uid, self.costs.cost(class), Instant::now())
+    }
+
+    fn take(&mut self, uid: u32, cost: u32, now: Instant) -> bool {
+        let capacity = self.capacity();
+        let refill_per_sec = self.limit as f64 / RATE_LIMIT_WINDOW.as_secs_f64();
+
+        let bucket = self.buckets.entry(uid).or_insert(Bucket { tokens: capacity, last_refill: now });
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens < cost as f64 {
+            return false;
+        }
+        bucket.tokens -= cost as f64;
+        true
+    }
+
+    /// Apply a new configuration (limits clamped to the valid range)
+    /// `burst` and `costs` keep their current values when `None`
+    pub(crate) fn configure(&mut self, limit: u32, burst: Option<u32>, costs: Option<RequestCosts>) -> RateLimitConfig {
+        self.limit = limit.clamp(hf_protocol::MIN_RATE_LIMIT, hf_protocol::MAX_RATE_LIMIT);
+        if let Some(burst) = burst {
+            self.burst = Some(burst.clamp(hf_protocol::MIN_RATE_LIMIT, hf_protocol::MAX_RATE_LIMIT));
+        }
+        if let Some(costs) = costs {
+            self.costs = costs;
+        }
+        // Buckets over the new capacity are trimmed on their next refill
+        self.config()
+    }
+
+    pub(crate) fn config(&self) -> RateLimitConfig {
+        RateLimitConfig {
+            limit: self.limit,
+            burst: self.capacity() as u32,
+            costs: self.costs,
+        }
+    }
+
+    /// Drop buckets that have refilled completely (they would start full anyway)
+    pub(crate) fn cleanup(&mut self) {
+        let now = Instant::now();
+        let capacity = self.capacity();
+        let refill_per_sec = self.limit as f64 / RATE_LIMIT_WINDOW.as_secs_f64();
+        self.buckets.retain(|_, bucket| {
+            let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+            bucket.tokens + elapsed * refill_per_sec < capacity
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_costs_per_class() {
+        let mut limiter = RateLimiter::new();
+        let now = Instant::now();
+        let heavy = limiter.costs.heavy;
+
+        // A full bucket allows limit / heavy detection runs, then nothing else
+        for _ in 0..(DEFAULT_RATE_LIMIT_REQUESTS / heavy) {
+            assert!(limiter.take(1000, heavy, now));
+        }
+        assert!(!limiter.take(1000, heavy, now));
+        assert!(!limiter.take(1000, limiter.costs.read, now));
+
+        // Other UIDs have their own bucket
+        assert!(limiter.take(1001, heavy, now));
+    }
+
+    #[test]
+    fn test_refill() {
+        let mut limiter = RateLimiter::new();
+        limiter.configure(2000, Some(3000), None);
+        let start = Instant::now();
+
+        assert!(limiter.take(1000, 3000, start));
+        assert!(!limiter.take(1000, 1, start));
+
+        // 2000 tokens per 10s = 200 per second
+        assert!(limiter.take(1000, 200, start + Duration::from_secs(1)));
+        assert!(!limiter.take(1000, 1, start + Duration::from_secs(1)));
+
+        // Refill stops at the burst capacity
+        let later = start + Duration::from_secs(60);
+        assert!(limiter.take(1000, 3000, later));
+        assert!(!limiter.take(1000, 1, later));
+    }
+}
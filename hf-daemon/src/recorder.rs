@@ -0,0 +1,191 @@
+//! Session Recorder
+//!
+//! Runs one recording at a time for `StartRecording`/`StopRecording`. Samples
+//! are captured by a background task using [`hf_core::RecordingWriter`] and
+//! written to `/var/lib/hyperfan/recordings/`, where clients can load them
+//! for offline replay or copy them (`hyperfan log` saves CSV benchmark runs
+//! this way; the daemon never writes to client-chosen paths).
+//!
+//! Recordings stop automatically after `MAX_SAMPLES` to bound disk usage, or
+//! earlier at the duration or size limit the client asked for.
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use hf_core::constants::recording;
+use hf_core::RecordingWriter;
+use hf_protocol::{RecordingFormat, RecordingInfo};
+
+/// Final state reported by a finished recording task
+struct FinishedRecording {
+    path: PathBuf,
+    samples: u64,
+    duration_ms: u64,
+    bytes: u64,
+}
+
+/// Progress published by a recording task
+#[derive(Clone, Copy, Default)]
+struct Progress {
+    samples: u64,
+    duration_ms: u64,
+    bytes: u64,
+}
+
+/// An in-progress recording
+struct ActiveRecording {
+    path: PathBuf,
+    stop: watch::Sender<bool>,
+    /// Samples written so far (updated by the task)
+    progress: watch::Receiver<Progress>,
+    task: JoinHandle<FinishedRecording>,
+}
+
+impl ActiveRecording {
+    fn info(&self) -> RecordingInfo {
+        let progress = *self.progress.borrow();
+        RecordingInfo {
+            path: self.path.to_string_lossy().into_owned(),
+            samples: progress.samples,
+            duration_ms: progress.duration_ms,
+            bytes: progress.bytes,
+            active: !self.task.is_finished(),
+        }
+    }
+}
+
+/// Recording session manager shared with the IPC server
+#[derive(Default)]
+pub struct Recorder {
+    active: Option<ActiveRecording>,
+    /// Result of the last recording stopped
+    last: Option<RecordingInfo>,
+}
+
+impl Recorder {
+    /// Start a new recording
+    pub fn start(
+        &mut self,
+        interval_ms: u32,
+        format: RecordingFormat,
+        max_duration_ms: Option<u64>,
+        max_bytes: Option<u64>,
+    ) -> Result<RecordingInfo, String> {
+        if let Some(active) = &self.active {
+            if !active.task.is_finished() {
+                return Err(format!("Recording already in progress: {}", active.path.display()));
+            }
+        }
+
+        let dir = Path::new(recording::RECORDINGS_DIR);
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create recordings directory: {}", e))?;
+        // Clients need to list and read recordings (daemon umask is 0077)
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to set recordings directory permissions: {}", e))?;
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = dir.join(format!("recording-{}.{}", timestamp, format.extension()));
+
+        let mut writer = RecordingWriter::create_with_format(&path, interval_ms, format)
+            .map_err(|e| format!("Failed to start recording: {}", e))?;
+
+        let (stop_tx, mut stop_rx) = watch::channel(false);
+        let (progress_tx, progress_rx) = watch::channel(Progress::default());
+
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_millis(interval_ms as u64));
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = stop_rx.changed() => break,
+                }
+                if let Err(e) = writer.capture() {
+                    error!("RECORDING: Failed to write sample, stopping: {}", e);
+                    break;
+                }
+                let _ = progress_tx.send(Progress {
+                    samples: writer.sample_count(),
+                    duration_ms: writer.elapsed_ms(),
+                    bytes: writer.bytes_written(),
+                });
+                if writer.sample_count() >= recording::MAX_SAMPLES {
+                    warn!("RECORDING: Reached {} samples, stopping automatically", recording::MAX_SAMPLES);
+                    break;
+                }
+                if max_duration_ms.is_some_and(|max| writer.elapsed_ms() >= max) {
+                    info!("RECORDING: Requested duration reached, stopping");
+                    break;
+                }
+                if max_bytes.is_some_and(|max| writer.bytes_written() >= max) {
+                    info!("RECORDING: Size limit of {} bytes reached, stopping", writer.bytes_written());
+                    break;
+                }
+            }
+
+            let samples = writer.sample_count();
+            let duration_ms = writer.elapsed_ms();
+            let bytes = writer.bytes_written();
+            let path = writer.path().to_path_buf();
+            if let Err(e) = writer.finish() {
+                error!("RECORDING: Failed to flush {}: {}", path.display(), e);
+            }
+            info!("RECORDING: Finished {} ({} samples)", path.display(), samples);
+            FinishedRecording { path, samples, duration_ms, bytes }
+        });
+
+        info!("RECORDING: Started {} (interval {}ms, {:?})", path.display(), interval_ms, format);
+        self.active = Some(ActiveRecording {
+            path: path.clone(),
+            stop: stop_tx,
+            progress: progress_rx,
+            task,
+        });
+
+        Ok(RecordingInfo {
+            path: path.to_string_lossy().into_owned(),
+            samples: 0,
+            duration_ms: 0,
+            bytes: 0,
+            active: true,
+        })
+    }
+
+    /// Stop the active recording and wait for its file to be flushed
+    pub async fn stop(&mut self) -> Result<RecordingInfo, String> {
+        let active = self.active.take().ok_or_else(|| "No recording in progress".to_string())?;
+        let progress = active.info();
+        let _ = active.stop.send(true);
+
+        let info = match active.task.await {
+            Ok(finished) => RecordingInfo {
+                path: finished.path.to_string_lossy().into_owned(),
+                samples: finished.samples,
+                duration_ms: finished.duration_ms,
+                bytes: finished.bytes,
+                active: false,
+            },
+            Err(e) => {
+                error!("RECORDING: Task failed: {}", e);
+                RecordingInfo { active: false, ..progress }
+            }
+        };
+        self.last = Some(info.clone());
+        Ok(info)
+    }
+
+    /// Progress of the active recording, or the result of the last one stopped
+    pub fn status(&self) -> Result<RecordingInfo, String> {
+        match (&self.active, &self.last) {
+            (Some(active), _) => Ok(active.info()),
+            (None, Some(last)) => Ok(last.clone()),
+            (None, None) => Err("No recording has been made".to_string()),
+        }
+    }
+}
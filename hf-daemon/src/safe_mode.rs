@@ -0,0 +1,93 @@
+//! Safe Mode
+//!
+//! Recovery mode for a config that makes the fans misbehave. Started with
+//! `hyperfand --safe-mode`, or while the sentinel file `/etc/hyperfan/DISABLE`
+//! exists, the daemon hands every PWM output back to firmware automatic
+//! control and then only monitors: curves are not evaluated, no PWM is written,
+//! and requests that would drive an output are refused. Reading sensors and
+//! editing the config keep working, so the config can be fixed from the GUI or
+//! CLI; restarting without the flag (and sentinel) resumes fan control.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use hf_protocol::{Request, IPMI_PATH_PREFIX, SYSCTL_PATH_PREFIX};
+use tracing::{debug, info, warn};
+
+/// Creating this file (e.g. from a rescue shell) starts the daemon in safe mode
+pub const SENTINEL_PATH: &str = "/etc/hyperfan/DISABLE";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the daemon runs in monitoring-only safe mode
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Whether the safe-mode sentinel file exists
+pub fn sentinel_present() -> bool {
+    Path::new(SENTINEL_PATH).exists()
+}
+
+/// Set the mode at startup, before the control loop takes over any fan
+pub fn enable_at_startup(reason: &str) {
+    ENABLED.store(true, Ordering::SeqCst);
+    warn!("SAFE MODE: enabled by {} - fans are under firmware control, nothing is written", reason);
+}
+
+/// Requests refused in safe mode: anything that drives a fan or pulses outputs
+/// Resets to automatic control and config edits are still served.
+pub fn refuses(request: &Request) -> bool {
+    matches!(
+        request,
+        Request::SetPwm { .. } | Request::EnableManualPwm { .. } | Request::SetPwmOverride { .. }
+        | Request::PreviewCurve { .. } | Request::SetDryRun { .. }
+        | Request::SetGpuFan { .. } | Request::SetGpuFanCurve { .. } | Request::SetGpuPowerLimit { .. }
+        | Request::DetectFanMappings | Request::StartDetection { .. } | Request::StartAutoTune { .. }
+        | Request::CalibrateFan { .. }
+        | Request::WriteEcRegister { .. } | Request::SetEcFanDuty { .. }
+    )
+}
+
+/// Hand each `(pwm_path, enable_path)` output to firmware automatic control
+pub fn force_auto(outputs: &[(String, String)]) {
+    // A crashed instance may have left its original state behind
+    if let Err(e) = crate::original_state::restore() {
+        warn!("SAFE MODE: failed to restore original PWM state: {}", e);
+    }
+
+    let mut restored = 0;
+    for (pwm_path, enable_path) in outputs {
+        let result = if pwm_path.starts_with("nvidia:") || pwm_path.starts_with("amd:") || pwm_path.starts_with("intel:") {
+            crate::gpu_fans::hand_to_firmware(pwm_path)
+        } else if pwm_path.starts_with(SYSCTL_PATH_PREFIX) || pwm_path.starts_with(IPMI_PATH_PREFIX) {
+            hf_core::enable_auto_pwm(Path::new(pwm_path)).map_err(|e| e.to_string())
+        } else if hf_core::is_cooling_device_path(Path::new(pwm_path)) {
+            // Thermal cooling devices have no automatic mode to return to
+            continue;
+        } else if enable_path.is_empty() || !hf_core::control_path_exists(Path::new(enable_path)) {
+            debug!("SAFE MODE: {} has no enable file", pwm_path);
+            continue;
+        } else {
+            hf_core::enable_auto_pwm(Path::new(enable_path)).map_err(|e| e.to_string())
+        };
+        match result {
+            Ok(()) => restored += 1,
+            Err(e) => warn!("SAFE MODE: failed to return {} to automatic control: {}", pwm_path, e),
+        }
+    }
+    info!("SAFE MODE: {} of {} PWM outputs returned to automatic control", restored, outputs.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refuses_control_writes_only() {
+        assert!(refuses(&Request::SetPwm { path: "/sys/class/hwmon/hwmon2/pwm1".into(), value: 255 }));
+        assert!(refuses(&Request::DetectFanMappings));
+        assert!(!refuses(&Request::DisableManualPwm { path: "/sys/class/hwmon/hwmon2/pwm1".into() }));
+        assert!(!refuses(&Request::ReloadConfig));
+        assert!(!refuses(&Request::ListHardware));
+    }
+}
@@ -0,0 +1,70 @@
+//! Profile Scheduler
+//!
+//! Applies `profile_schedule` from settings (e.g. `quiet` from 22:00 to 08:00)
+//! from the control loop. The active profile is only changed when the
+//! scheduled profile changes, so a manual switch from the tray or CLI holds
+//! until the next window boundary.
+
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use hf_core::constants::profiles;
+use hf_core::LocalTime;
+
+use crate::fan_control::FanControlState;
+
+/// Tracks the last profile the schedule selected
+#[derive(Default)]
+pub struct ProfileScheduler {
+    last_check: Option<Instant>,
+    scheduled: Option<String>,
+}
+
+impl ProfileScheduler {
+    /// Evaluate the schedule if the check interval has elapsed
+    pub fn tick(&mut self, state: &FanControlState) {
+        let interval = Duration::from_secs(profiles::SCHEDULE_CHECK_INTERVAL_SECS);
+        if self.last_check.is_some_and(|at| at.elapsed() < interval) {
+            return;
+        }
+        self.last_check = Some(Instant::now());
+
+        let settings = match hf_core::load_settings() {
+            Ok(s) => s,
+            Err(e) => {
+                debug!("SCHEDULE: Could not load settings: {}", e);
+                return;
+            }
+        };
+
+        let Some(profile) = settings.profile_schedule.profile_at(LocalTime::now()) else {
+            self.scheduled = None;
+            return;
+        };
+        if self.scheduled.as_deref() == Some(profile) {
+            return;
+        }
+        self.scheduled = Some(profile.to_string());
+
+        if settings.active_profile == profile {
+            return;
+        }
+        if let Err(e) = hf_protocol::validate_profile_name(profile) {
+            warn!("SCHEDULE: Ignoring invalid profile {:?}: {}", profile, e);
+            return;
+        }
+
+        match hf_core::update_setting(|s| s.active_profile = profile.to_string()) {
+            Ok(_) => {
+                info!("SCHEDULE: Switched from profile '{}' to '{}'", settings.active_profile, profile);
+                crate::events::publish(hf_protocol::Event::ProfileSwitched {
+                    previous: settings.active_profile.clone(),
+                    name: profile.to_string(),
+                    scheduled: true,
+                });
+                state.signal_reload();
+            }
+            Err(e) => warn!("SCHEDULE: Failed to switch to profile '{}': {}", profile, e),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! systemd Notification Protocol
+//!
+//! Minimal `sd_notify(3)` client for `Type=notify` units: `READY=1` once the
+//! socket is listening, `WATCHDOG=1` heartbeats from the fan control loop and
+//! `STOPPING=1` on shutdown. If the control loop stops iterating (deadlock,
+//! stuck sysfs read), heartbeats stop and systemd restarts the daemon after
+//! `WatchdogSec`.
+//!
+//! Every call is a no-op when `NOTIFY_SOCKET` is unset (not started by
+//! systemd, OpenRC, BSD rc.d).
+
+use std::os::unix::net::UnixDatagram;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+/// Send a state string to the service manager
+/// Returns false if no service manager is listening or the send failed
+pub fn notify(state: &str) -> bool {
+    let Some(socket_path) = std::env::var_os("NOTIFY_SOCKET") else {
+        return false;
+    };
+    let Ok(socket) = UnixDatagram::unbound() else {
+        return false;
+    };
+
+    let path = socket_path.to_string_lossy();
+    let result = match path.strip_prefix('@') {
+        #[cfg(target_os = "linux")]
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            std::os::unix::net::SocketAddr::from_abstract_name(name.as_bytes())
+                .and_then(|addr| socket.send_to_addr(state.as_bytes(), &addr))
+        }
+        #[cfg(not(target_os = "linux"))]
+        Some(_) => return false,
+        None => socket.send_to(state.as_bytes(), path.as_ref()),
+    };
+
+    match result {
+        Ok(_) => true,
+        Err(e) => {
+            debug!("sd_notify {:?} failed: {}", state, e);
+            false
+        }
+    }
+}
+
+/// Tell systemd the daemon finished starting up
+pub fn ready() {
+    if notify("READY=1") {
+        info!("STARTUP: Notified systemd (READY=1)");
+    }
+}
+
+/// Tell systemd the daemon is shutting down
+pub fn stopping() {
+    notify("STOPPING=1");
+}
+
+/// Watchdog heartbeat sender, rate limited to half of `WatchdogSec`
+pub struct Watchdog {
+    interval: Option<Duration>,
+    last_ping: Option<Instant>,
+}
+
+impl Watchdog {
+    /// Read `WATCHDOG_USEC`/`WATCHDOG_PID` set by systemd for this process
+    pub fn from_env() -> Self {
+        let pid_matches = std::env::var("WATCHDOG_PID")
+            .ok()
+            .and_then(|pid| pid.parse::<u32>().ok())
+            .is_none_or(|pid| pid == std::process::id());
+        let interval = std::env::var("WATCHDOG_USEC")
+            .ok()
+            .and_then(|usec| usec.parse::<u64>().ok())
+            .filter(|usec| *usec > 0 && pid_matches)
+            .map(|usec| Duration::from_micros(usec / 2));
+
+        if let Some(interval) = interval {
+            info!("STARTUP: systemd watchdog enabled (heartbeat every {:?})", interval);
+        }
+        Self { interval, last_ping: None }
+    }
+
+    /// Send `WATCHDOG=1` if the heartbeat interval has elapsed
+    pub fn tick(&mut self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        if self.last_ping.is_some_and(|at| at.elapsed() < interval) {
+            return;
+        }
+        self.last_ping = Some(Instant::now());
+        notify("WATCHDOG=1");
+    }
+}
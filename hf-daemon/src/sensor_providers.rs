@@ -0,0 +1,427 @@
+//! External Sensor Providers
+//!
+//! Scripts or binaries listed under `sensor_providers` in
+//! `/etc/hyperfan/daemon.json` report temperatures the daemon cannot read
+//! itself (IPMI, network switches, ...). Each provider is run every
+//! `interval_secs` and its readings appear under a `provider:<name>` chip as
+//! `provider:<name>/<sensor>` temperature sources, usable by curves like any
+//! other sensor:
+//!
+//! ```json
+//! "sensor_providers": [
+//!   { "name": "ipmi", "command": "/usr/local/libexec/hyperfan-ipmi", "interval_secs": 10 }
+//! ]
+//! ```
+//!
+//! A provider prints a single JSON object on stdout and exits 0:
+//!
+//! ```json
+//! { "sensors": [ { "name": "cpu1", "label": "CPU1 Temp", "value": 48.0 } ] }
+//! ```
+//!
+//! Providers run sandboxed:
+//! - the command must be an absolute path to an executable owned by root and
+//!   not group/world-writable (the rule daemon.json itself follows), in
+//!   directories that follow the same rule; the resolved path that was
+//!   checked is what gets run, so a symlink swapped in later is not followed
+//! - it runs as `uid`/`gid` (default nobody; root is refused) without
+//!   supplementary groups or new privileges, in its own process group, with
+//!   an empty environment, `/` as working directory, and no stdin or stderr
+//! - the whole process group is killed after `timeout_ms`, and output over
+//!   `MAX_OUTPUT_SIZE` or not matching the schema is rejected
+//!
+//! Readings older than `STALE_INTERVALS` intervals fail to read, so pairs
+//! using them run at the fallback speed. Providers are started with the
+//! daemon; changes to the list take effect on restart.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use hf_protocol::{HwmonChip, TempSensor, PROVIDER_PATH_PREFIX};
+
+/// Shortest run interval
+const MIN_INTERVAL_SECS: u64 = 2;
+
+/// Longest a provider may run before it is killed
+const MAX_TIMEOUT_MS: u64 = 10_000;
+
+/// Largest accepted output (stdout)
+const MAX_OUTPUT_SIZE: usize = 64 * 1024;
+
+/// Most sensors one provider may report
+const MAX_SENSORS_PER_PROVIDER: usize = 32;
+
+/// Longest sensor label
+const MAX_LABEL_LENGTH: usize = 64;
+
+/// Reported temperatures outside this range are rejected (Celsius)
+const VALUE_RANGE: std::ops::RangeInclusive<f32> = -50.0..=200.0;
+
+/// Readings expire after this many missed intervals
+const STALE_INTERVALS: u32 = 3;
+
+/// Unprivileged user providers run as by default
+const NOBODY: u32 = 65534;
+
+/// PATH given to providers
+const PROVIDER_PATH: &str = "/usr/local/bin:/usr/bin:/bin";
+
+/// A provider entry in daemon.json
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProviderConfig {
+    pub name: String,
+    /// Absolute path of the executable
+    pub command: PathBuf,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default = "default_id")]
+    pub uid: u32,
+    #[serde(default = "default_id")]
+    pub gid: u32,
+}
+
+fn default_interval_secs() -> u64 {
+    10
+}
+
+fn default_timeout_ms() -> u64 {
+    2000
+}
+
+fn default_id() -> u32 {
+    NOBODY
+}
+
+impl ProviderConfig {
+    fn validate(&self) -> Result<(), String> {
+        hf_protocol::validate_provider_name(&self.name)?;
+        if !self.command.is_absolute() {
+            return Err("command must be an absolute path".to_string());
+        }
+        if self.interval_secs < MIN_INTERVAL_SECS {
+            return Err(format!("interval_secs must be at least {}", MIN_INTERVAL_SECS));
+        }
+        if self.timeout_ms == 0 || self.timeout_ms > MAX_TIMEOUT_MS.min(self.interval_secs * 1000) {
+            return Err(format!("timeout_ms must be 1-{} and shorter than the interval", MAX_TIMEOUT_MS));
+        }
+        if self.uid == 0 || self.gid == 0 {
+            return Err("providers may not run as root".to_string());
+        }
+        Ok(())
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+}
+
+/// One value reported by a provider
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProviderSensor {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    pub value: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProviderOutput {
+    sensors: Vec<ProviderSensor>,
+}
+
+/// Check a provider's stdout against the output schema
+fn parse_output(output: &[u8]) -> Result<Vec<ProviderSensor>, String> {
+    if output.len() > MAX_OUTPUT_SIZE {
+        return Err(format!("output exceeds {} bytes", MAX_OUTPUT_SIZE));
+    }
+    let output: ProviderOutput = serde_json::from_slice(output).map_err(|e| format!("invalid output: {}", e))?;
+    if output.sensors.len() > MAX_SENSORS_PER_PROVIDER {
+        return Err(format!("more than {} sensors", MAX_SENSORS_PER_PROVIDER));
+    }
+    for (idx, sensor) in output.sensors.iter().enumerate() {
+        hf_protocol::validate_provider_name(&sensor.name)?;
+        if output.sensors[..idx].iter().any(|s| s.name == sensor.name) {
+            return Err(format!("duplicate sensor '{}'", sensor.name));
+        }
+        if sensor.label.as_ref().is_some_and(|l| l.len() > MAX_LABEL_LENGTH) {
+            return Err(format!("label of '{}' exceeds {} characters", sensor.name, MAX_LABEL_LENGTH));
+        }
+        if !VALUE_RANGE.contains(&sensor.value) {
+            return Err(format!("'{}' reported implausible value {}", sensor.name, sensor.value));
+        }
+    }
+    Ok(output.sensors)
+}
+
+/// Refuse executables that a non-root user could replace, returning the
+/// symlink-free path that was checked (the one to run)
+fn check_executable(path: &Path) -> Result<PathBuf, String> {
+    let resolved = std::fs::canonicalize(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let metadata = std::fs::metadata(&resolved).map_err(|e| e.to_string())?;
+    if !metadata.is_file() || metadata.mode() & 0o111 == 0 {
+        return Err(format!("{} is not an executable file", resolved.display()));
+    }
+    // Every directory above it too: whoever can write one can rename the file away
+    for checked in resolved.ancestors() {
+        let metadata = std::fs::metadata(checked).map_err(|e| format!("{}: {}", checked.display(), e))?;
+        if metadata.uid() != 0 || metadata.mode() & 0o022 != 0 {
+            return Err(format!("{} must be owned by root and not group/world-writable", checked.display()));
+        }
+    }
+    Ok(resolved)
+}
+
+/// Run a provider once (blocking)
+fn run(config: &ProviderConfig) -> Result<Vec<ProviderSensor>, String> {
+    let executable = check_executable(&config.command)?;
+
+    let mut command = Command::new(executable);
+    command
+        .args(&config.args)
+        .env_clear()
+        .env("PATH", PROVIDER_PATH)
+        .current_dir("/")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .uid(config.uid)
+        .gid(config.gid)
+        .process_group(0);
+    // SAFETY: prctl is async-signal-safe and touches no memory of the parent
+    #[cfg(target_os = "linux")]
+    unsafe {
+        command.pre_exec(|| {
+            if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let mut child = command.spawn().map_err(|e| format!("failed to start: {}", e))?;
+
+    // Read stdout on a helper thread so the timeout also covers a provider that never closes it
+    let mut stdout = child.stdout.take().ok_or("no stdout")?;
+    let reader = std::thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = (&mut stdout).take(MAX_OUTPUT_SIZE as u64 + 1).read_to_end(&mut output);
+        output
+    });
+
+    // Done once stdout is closed and the provider exited. The child is only
+    // reaped on success, so its PID (the process group ID) cannot be reused
+    // before a timeout kills the group.
+    let deadline = Instant::now() + Duration::from_millis(config.timeout_ms);
+    let status = loop {
+        if reader.is_finished() {
+            if let Ok(Some(status)) = child.try_wait() {
+                break status;
+            }
+        }
+        if Instant::now() >= deadline {
+            // SAFETY: plain syscall; the unreaped child leads the process group (process_group(0))
+            unsafe { libc::kill(-(child.id() as i32), libc::SIGKILL) };
+            let _ = child.wait();
+            // The reader is not joined: a descendant that left the group may still hold stdout
+            return Err(format!("timed out after {} ms", config.timeout_ms));
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+    let output = reader.join().map_err(|_| "output reader panicked")?;
+
+    if !status.success() {
+        return Err(format!("exited with {}", status));
+    }
+    parse_output(&output)
+}
+
+struct Readings {
+    sensors: Vec<ProviderSensor>,
+    updated: Instant,
+    stale_after: Duration,
+}
+
+impl Readings {
+    fn is_stale(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.updated) > self.stale_after
+    }
+}
+
+/// Latest readings by provider name
+static READINGS: Mutex<Option<HashMap<String, Readings>>> = Mutex::new(None);
+
+fn store(config: &ProviderConfig, sensors: Vec<ProviderSensor>) {
+    let mut readings = READINGS.lock().unwrap_or_else(|e| e.into_inner());
+    readings.get_or_insert_with(HashMap::new).insert(config.name.clone(), Readings {
+        sensors,
+        updated: Instant::now(),
+        stale_after: config.interval() * STALE_INTERVALS,
+    });
+}
+
+/// Start the providers configured in daemon.json
+pub fn spawn_providers(configs: &[ProviderConfig]) {
+    let mut names: Vec<&str> = Vec::new();
+    for config in configs {
+        if let Err(e) = config.validate() {
+            warn!("PROVIDER: Ignoring '{}': {}", config.name, e);
+            continue;
+        }
+        if names.contains(&config.name.as_str()) {
+            warn!("PROVIDER: Ignoring duplicate provider '{}'", config.name);
+            continue;
+        }
+        names.push(&config.name);
+        info!("PROVIDER: Starting '{}' ({} every {}s)", config.name, config.command.display(), config.interval_secs);
+
+        let config = config.clone();
+        tokio::spawn(async move {
+            let mut failing = false;
+            loop {
+                let job = config.clone();
+                match tokio::task::spawn_blocking(move || run(&job)).await {
+                    Ok(Ok(sensors)) => {
+                        if failing {
+                            info!("PROVIDER: '{}' recovered", config.name);
+                            failing = false;
+                        }
+                        debug!("PROVIDER: '{}' reported {} sensors", config.name, sensors.len());
+                        store(&config, sensors);
+                    }
+                    // Log the first failure only, until the provider recovers
+                    Ok(Err(e)) if !failing => {
+                        warn!("PROVIDER: '{}' failed: {}", config.name, e);
+                        failing = true;
+                    }
+                    Ok(Err(e)) => debug!("PROVIDER: '{}' failed: {}", config.name, e),
+                    Err(e) => warn!("PROVIDER: '{}' task failed: {}", config.name, e),
+                }
+                tokio::time::sleep(config.interval()).await;
+            }
+        });
+    }
+}
+
+/// Read a `provider:<name>/<sensor>` temperature source
+pub(crate) fn read_temperature(path: &str) -> Result<f32, String> {
+    let (provider, sensor) = path
+        .strip_prefix(PROVIDER_PATH_PREFIX)
+        .and_then(|rest| rest.split_once('/'))
+        .ok_or_else(|| format!("Invalid provider sensor path '{}'", path))?;
+
+    let readings = READINGS.lock().unwrap_or_else(|e| e.into_inner());
+    let readings = readings
+        .as_ref()
+        .and_then(|r| r.get(provider))
+        .ok_or_else(|| format!("No readings from provider '{}'", provider))?;
+    if readings.is_stale(Instant::now()) {
+        return Err(format!("Readings from provider '{}' are stale", provider));
+    }
+    readings
+        .sensors
+        .iter()
+        .find(|s| s.name == sensor)
+        .map(|s| s.value)
+        .ok_or_else(|| format!("Provider '{}' did not report '{}'", provider, sensor))
+}
+
+/// One pseudo-chip per provider with fresh readings
+pub(crate) fn provider_chips() -> Vec<HwmonChip> {
+    let now = Instant::now();
+    let readings = READINGS.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(readings) = readings.as_ref() else {
+        return Vec::new();
+    };
+
+    let mut chips: Vec<HwmonChip> = readings
+        .iter()
+        .filter(|(_, r)| !r.is_stale(now))
+        .map(|(provider, r)| HwmonChip {
+            name: format!("{}{}", PROVIDER_PATH_PREFIX, provider),
+            path: format!("{}{}", PROVIDER_PATH_PREFIX, provider),
+            temperatures: r
+                .sensors
+                .iter()
+                .map(|s| TempSensor {
+                    name: s.name.clone(),
+                    label: s.label.clone(),
+                    path: format!("{}{}/{}", PROVIDER_PATH_PREFIX, provider, s.name),
+                    value: s.value,
+                    max: None,
+                    crit: None,
+                    crit_alarm: false,
+                })
+                .collect(),
+            fans: Vec::new(),
+            pwms: Vec::new(),
+            sensors: Vec::new(),
+            alarms: Vec::new(),
+        })
+        .collect();
+    chips.sort_by(|a, b| a.name.cmp(&b.name));
+    chips
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output() {
+        let sensors = parse_output(br#"{ "sensors": [ { "name": "cpu1", "label": "CPU1 Temp", "value": 48.0 } ] }"#)
+            .unwrap();
+        assert_eq!(sensors.len(), 1);
+        assert_eq!(sensors[0].value, 48.0);
+
+        assert!(parse_output(b"48.0").is_err());
+        assert!(parse_output(br#"{ "sensors": [ { "name": "cpu 1", "value": 48.0 } ] }"#).is_err());
+        assert!(parse_output(br#"{ "sensors": [ { "name": "cpu1", "value": 900.0 } ] }"#).is_err());
+        let duplicate = br#"{ "sensors": [ { "name": "a", "value": 1.0 }, { "name": "a", "value": 2.0 } ] }"#;
+        assert!(parse_output(duplicate).is_err());
+        // Unknown fields are schema violations
+        assert!(parse_output(br#"{ "sensors": [], "extra": 1 }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let config: ProviderConfig =
+            serde_json::from_str(r#"{ "name": "ipmi", "command": "/usr/libexec/hyperfan-ipmi" }"#).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.uid, NOBODY);
+
+        assert!(ProviderConfig { uid: 0, ..config.clone() }.validate().is_err());
+        assert!(ProviderConfig { command: PathBuf::from("ipmi.sh"), ..config.clone() }.validate().is_err());
+        assert!(ProviderConfig { timeout_ms: 60_000, ..config }.validate().is_err());
+    }
+
+    #[test]
+    fn test_check_executable() {
+        // Symlinks are resolved to the file that gets run
+        let sh = check_executable(Path::new("/bin/sh")).unwrap();
+        assert_eq!(sh, std::fs::canonicalize("/bin/sh").unwrap());
+
+        // A directory others can write to makes the binary replaceable
+        let dir = std::env::temp_dir().join(format!("hf-providers-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("provider");
+        std::fs::copy(&sh, &script).unwrap();
+        std::fs::set_permissions(&dir, std::os::unix::fs::PermissionsExt::from_mode(0o777)).unwrap();
+        assert!(check_executable(&script).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
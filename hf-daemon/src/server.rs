@@ -8,6 +8,9 @@
 //! - **Path validation**: Strict allowlist prevents traversal and injection
 //! - **Connection limits**: Maximum concurrent connections enforced
 //! - **Rate limiting**: Per-client request rate limiting
+//! - **Authorization**: Per-client tiers (read-only/control) plus polkit for requests that change anything
+//! - **Monitoring socket**: A second socket for status bars and widgets that
+//!   skips executable validation but only serves sensor/status reads
 //! - **Timeouts**: Read/write timeouts prevent resource exhaustion
 //! - **Message limits**: Maximum message size prevents memory exhaustion
 //! - **Input validation**: All parameters sanitized before processing
@@ -17,23 +20,24 @@
 //! - Zero-copy parsing where possible
 //! - Bounded buffers prevent memory exhaustion
 
-use std::collections::HashMap;
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::net::{UnixListener, UnixStream};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::sync::Mutex;
 use tokio::time::timeout;
 use tracing::{info, warn, error, debug, trace};
 
+use crate::rate_limit::{RateLimiter, DEFAULT_RATE_LIMIT_REQUESTS, RATE_LIMIT_WINDOW};
+
 use hf_protocol::{
-    Request, Response, ResponseData, HardwareInfo, HwmonChip, TempSensor,
-    FanSensor, PwmControl, GpuInfo, ManualPwmFanPairing, validate_hwmon_path,
-    validate_pwm_target_path, AllHardwareData,
-    EcChipInfo, EcRegisterValue,
+    Request, Response, ResponseData, HardwareInfo, HwmonChip, TempSensor, HwSensor, SensorKind,
+    FanSensor, PwmControl, GpuInfo, GpuTempInfo, GpuFanCurveInfo, GpuFanCurvePoint, GpuPowerLimitInfo, ManualPwmFanPairing, validate_hwmon_path,
+    validate_pwm_target_path, AllHardwareData, IPMI_PATH_PREFIX, SYSCTL_PATH_PREFIX,
+    EcChipInfo, EcRegisterValue, EcRegisterChange, GlobalMode, frame,
 };
 
 // ============================================================================
@@ -43,6 +47,10 @@ use hf_protocol::{
 /// Maximum concurrent client connections
 const MAX_CONNECTIONS: usize = 64;
 
+/// Maximum concurrent monitoring socket connections (counted separately so
+/// widgets cannot crowd out the GUI and CLI)
+const MAX_MONITOR_CONNECTIONS: usize = 16;
+
 /// Maximum message size in bytes
 const MAX_MESSAGE_SIZE: usize = hf_protocol::MAX_MESSAGE_SIZE;
 
@@ -52,26 +60,17 @@ const READ_TIMEOUT: Duration = Duration::from_secs(30);
 /// Write timeout per message
 const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
 
-/// Rate limit: default maximum requests per window (matches client default)
-const DEFAULT_RATE_LIMIT_REQUESTS: u32 = 1500;
-
-/// Minimum rate limit (cannot go below this)
-pub const MIN_RATE_LIMIT: u32 = 1500;
-
-/// Maximum rate limit (cannot exceed this)
-pub const MAX_RATE_LIMIT: u32 = 9999;
-
-/// Rate limit window duration
-const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
-
 /// Socket permissions (0666 = world read/write)
 /// Client validation via executable path check provides security
 const SOCKET_MODE: u32 = 0o666;
 
+/// Monitoring socket permissions restricted to `monitor_socket.gid`
+const MONITOR_GROUP_SOCKET_MODE: u32 = 0o660;
+
 /// Default TTL for SetPwm override to prevent control loop fighting (3 seconds)
 const DEFAULT_PWM_OVERRIDE_TTL_MS: u32 = 3000;
 
-/// How often to refresh the hwmon chip structure cache (seconds)
+/// How often to re-scan each hwmon chip's layout (seconds)
 /// The chip structure (paths, names) rarely changes - only values need refreshing
 const CHIP_CACHE_TTL_SECS: u64 = 30;
 
@@ -81,53 +80,34 @@ const CHIP_CACHE_TTL_SECS: u64 = 30;
 
 /// Global connection counter
 static ACTIVE_CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+static ACTIVE_MONITOR_CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Which socket a connection arrived on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SocketKind {
+    /// Full protocol for validated Hyperfan clients
+    Control,
+    /// Sensor and status reads for any local process
+    Monitor,
+}
+
+impl SocketKind {
+    fn connections(self) -> (&'static AtomicUsize, usize) {
+        match self {
+            SocketKind::Control => (&ACTIVE_CONNECTIONS, MAX_CONNECTIONS),
+            SocketKind::Monitor => (&ACTIVE_MONITOR_CONNECTIONS, MAX_MONITOR_CONNECTIONS),
+        }
+    }
+}
 
 // ============================================================================
 // Hwmon Chip Cache (PERF: avoid re-enumerating filesystem on every request)
 // ============================================================================
 
-use std::sync::OnceLock;
-use std::sync::RwLock as StdRwLock;
-
-/// Cached hwmon chip structure with timestamp
-struct ChipCache {
-    chips: Vec<hf_core::HwmonChip>,
-    cached_at: Instant,
-}
-
-static CHIP_CACHE: OnceLock<StdRwLock<Option<ChipCache>>> = OnceLock::new();
-
-fn get_chip_cache() -> &'static StdRwLock<Option<ChipCache>> {
-    CHIP_CACHE.get_or_init(|| StdRwLock::new(None))
-}
-
-/// Get hwmon chips with caching - avoids filesystem enumeration on every poll
+/// Get hwmon chips with fresh values - chip layouts are cached per chip by hf-core
 fn get_cached_chips() -> Result<Vec<hf_core::HwmonChip>, String> {
-    let cache = get_chip_cache();
-    
-    // Fast path: check if cache is valid
-    {
-        let guard = cache.read().map_err(|_| "Cache lock poisoned")?;
-        if let Some(ref cached) = *guard {
-            if cached.cached_at.elapsed().as_secs() < CHIP_CACHE_TTL_SECS {
-                return Ok(cached.chips.clone());
-            }
-        }
-    }
-    
-    // Slow path: refresh cache
-    let chips = hf_core::enumerate_hwmon_chips()
-        .map_err(|e| format!("Failed to enumerate hardware: {}", e))?;
-    
-    {
-        let mut guard = cache.write().map_err(|_| "Cache lock poisoned")?;
-        *guard = Some(ChipCache {
-            chips: chips.clone(),
-            cached_at: Instant::now(),
-        });
-    }
-    
-    Ok(chips)
+    hf_core::cached_hwmon_chips(Duration::from_secs(CHIP_CACHE_TTL_SECS))
+        .map_err(|e| format!("Failed to enumerate hardware: {}", e))
 }
 
 async fn read_line_bounded<R: tokio::io::AsyncBufRead + Unpin>(
@@ -170,11 +150,43 @@ async fn read_line_bounded<R: tokio::io::AsyncBufRead + Unpin>(
     }
 }
 
-/// Rate limiter state per client (keyed by UID)
-struct RateLimiter {
-    clients: HashMap<u32, ClientState>,
-    /// Current rate limit (configurable at runtime)
-    max_requests: u32,
+/// Read one request, either a length-prefixed frame or a JSON line
+/// `framed` is set to the format of the message being read before any error
+async fn read_message<R: tokio::io::AsyncBufRead + Unpin>(
+    reader: &mut R,
+    out: &mut Vec<u8>,
+    framed: &mut bool,
+) -> std::io::Result<usize> {
+    let Some(&first) = reader.fill_buf().await?.first() else {
+        return Ok(0);
+    };
+    *framed = first == frame::FRAME_MAGIC;
+    if !*framed {
+        return read_line_bounded(reader, out, MAX_MESSAGE_SIZE).await;
+    }
+
+    let mut header = [0u8; frame::FRAME_HEADER_LEN];
+    reader.read_exact(&mut header).await?;
+    let header = match frame::parse_header(&header, frame::MAX_FRAMED_REQUEST_SIZE) {
+        Ok(header) => header,
+        Err(frame::FrameError::TooLarge { .. }) => {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Message too large"));
+        }
+        Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+    };
+
+    out.clear();
+    out.resize(header.len, 0);
+    reader.read_exact(out).await?;
+    Ok(header.len)
+}
+
+/// Framings this daemon speaks (reported by Version)
+fn capabilities() -> hf_protocol::Capabilities {
+    hf_protocol::Capabilities {
+        framing: vec![frame::Framing::JsonLines, frame::Framing::LengthPrefixed],
+        max_request_size: frame::MAX_FRAMED_REQUEST_SIZE as u32,
+    }
 }
 
 fn pwm_enable_path_from_pwm_path(pwm_path: &str) -> Result<String, String> {
@@ -230,6 +242,17 @@ fn apply_pwm_value_immediately(path: &str, value: u8) -> Response {
         };
     }
 
+    // sysctl fan levels and cooling device states are scaled by hf-core
+    if path.starts_with(SYSCTL_PATH_PREFIX)
+        || path.starts_with(IPMI_PATH_PREFIX)
+        || hf_core::is_cooling_device_path(std::path::Path::new(path))
+    {
+        return match hf_core::set_pwm_value(std::path::Path::new(path), value) {
+            Ok(()) => Response::ok(),
+            Err(e) => Response::error(format!("Failed to set PWM: {}", e)),
+        };
+    }
+
     let enable_path = match pwm_enable_path_from_pwm_path(path) {
         Ok(p) => p,
         Err(e) => return Response::error(e),
@@ -239,70 +262,17 @@ fn apply_pwm_value_immediately(path: &str, value: u8) -> Response {
         if let Err(e) = validate_hwmon_path(&enable_path) {
             return Response::error(e);
         }
-        if let Err(e) = std::fs::write(&enable_path, "1") {
+        if let Err(e) = hf_core::enable_manual_pwm(Path::new(&enable_path)) {
             return Response::error(format!("Failed to enable manual PWM: {}", e));
         }
     }
 
-    match std::fs::write(path, value.to_string()) {
-        Ok(_) => Response::ok(),
-        Err(e) => Response::error(format!("Failed to set PWM: {}", e)),
-    }
-}
-
-struct ClientState {
-    request_count: u32,
-    window_start: Instant,
-}
-
-impl RateLimiter {
-    fn new() -> Self {
-        Self {
-            clients: HashMap::new(),
-            max_requests: DEFAULT_RATE_LIMIT_REQUESTS,
-        }
-    }
-    
-    /// Check if a client is rate limited. Returns true if allowed, false if limited.
-    fn check_and_increment(&mut self, uid: u32) -> bool {
-        let now = Instant::now();
-        
-        let state = self.clients.entry(uid).or_insert(ClientState {
-            request_count: 0,
-            window_start: now,
-        });
-        
-        // Reset window if expired
-        if now.duration_since(state.window_start) > RATE_LIMIT_WINDOW {
-            state.request_count = 0;
-            state.window_start = now;
-        }
-        
-        if state.request_count >= self.max_requests {
-            return false;
+    match hf_core::set_pwm_value(Path::new(path), value) {
+        Ok(_) => {
+            crate::foreign_writes::record_write(path, value);
+            Response::ok()
         }
-        
-        state.request_count += 1;
-        true
-    }
-    
-    /// Set the rate limit (clamped to valid range)
-    fn set_rate_limit(&mut self, limit: u32) -> u32 {
-        self.max_requests = limit.clamp(MIN_RATE_LIMIT, MAX_RATE_LIMIT);
-        self.max_requests
-    }
-    
-    /// Get current rate limit
-    fn get_rate_limit(&self) -> u32 {
-        self.max_requests
-    }
-    
-    /// Cleanup old entries to prevent memory growth
-    fn cleanup(&mut self) {
-        let now = Instant::now();
-        self.clients.retain(|_, state| {
-            now.duration_since(state.window_start) < RATE_LIMIT_WINDOW * 2
-        });
+        Err(e) => Response::error(format!("Failed to set PWM: {}", e)),
     }
 }
 
@@ -310,8 +280,16 @@ impl RateLimiter {
 // Server
 // ============================================================================
 
-/// Run the Unix socket server with full security hardening
-pub async fn run_server(socket_path: &str, fan_control_state: Arc<crate::fan_control::FanControlState>) -> Result<(), Box<dyn std::error::Error>> {
+/// Path of the monitoring socket next to `socket_path` (hyperfan.sock -> hyperfan-monitor.sock)
+pub(crate) fn monitor_socket_path(socket_path: &str) -> String {
+    match socket_path.strip_suffix(".sock") {
+        Some(stem) => format!("{}-monitor.sock", stem),
+        None => format!("{}-monitor", socket_path),
+    }
+}
+
+/// Bind a Unix socket at `socket_path`, replacing a stale socket file
+fn bind_socket(socket_path: &str, mode: u32) -> Result<UnixListener, Box<dyn std::error::Error>> {
     let path = Path::new(socket_path);
     
     // SECURITY: Remove existing socket only if it's actually a socket
@@ -331,11 +309,56 @@ pub async fn run_server(socket_path: &str, fan_control_state: Arc<crate::fan_con
     let listener = UnixListener::bind(socket_path)?;
     
     // Set socket permissions
-    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(SOCKET_MODE))?;
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(mode))?;
+    
+    info!("Listening on {} (mode {:o})", socket_path, mode);
+    Ok(listener)
+}
+
+/// Bind the monitoring socket as configured in daemon.json
+/// Failure is logged; the control socket keeps working
+fn bind_monitor_socket(socket_path: &str) -> Option<UnixListener> {
+    let config = crate::permissions::DaemonConfig::load().monitor_socket();
+    if !config.enabled {
+        info!("Monitoring socket disabled in daemon config");
+        return None;
+    }
+
+    let mode = if config.gid.is_some() { MONITOR_GROUP_SOCKET_MODE } else { SOCKET_MODE };
+    let bound = bind_socket(socket_path, mode).and_then(|listener| {
+        if let Some(gid) = config.gid {
+            std::os::unix::fs::chown(socket_path, None, Some(gid))?;
+            info!("Monitoring socket restricted to gid={}", gid);
+        }
+        Ok(listener)
+    });
+    match bound {
+        Ok(listener) => Some(listener),
+        Err(e) => {
+            warn!("Monitoring socket {} unavailable: {}", socket_path, e);
+            let _ = std::fs::remove_file(socket_path);
+            None
+        }
+    }
+}
+
+/// Accept on the monitoring socket, or never when it is not bound
+async fn accept_monitor(listener: &Option<UnixListener>) -> std::io::Result<(UnixStream, tokio::net::unix::SocketAddr)> {
+    match listener {
+        Some(listener) => listener.accept().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Run the Unix socket server with full security hardening
+pub async fn run_server(socket_path: &str, fan_control_state: Arc<crate::fan_control::FanControlState>) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = bind_socket(socket_path, SOCKET_MODE)?;
+    let monitor_path = monitor_socket_path(socket_path);
+    let monitor_listener = bind_monitor_socket(&monitor_path);
     
-    info!("Listening on {} (mode {:o})", socket_path, SOCKET_MODE);
     info!("Security: max_conn={}, max_msg={}, rate_limit={}/{:?}", 
           MAX_CONNECTIONS, MAX_MESSAGE_SIZE, DEFAULT_RATE_LIMIT_REQUESTS, RATE_LIMIT_WINDOW);
+    crate::sd_notify::ready();
     
     // Shared rate limiter
     let rate_limiter = Arc::new(Mutex::new(RateLimiter::new()));
@@ -349,46 +372,56 @@ pub async fn run_server(socket_path: &str, fan_control_state: Arc<crate::fan_con
         }
     });
     
+    // Optional D-Bus interface shares the rate limiter and request dispatch
+    #[cfg(feature = "dbus")]
+    crate::dbus_service::start(fan_control_state.clone(), rate_limiter.clone());
+    
     // Handle shutdown signal
     let shutdown = tokio::signal::ctrl_c();
     tokio::pin!(shutdown);
     
     loop {
-        tokio::select! {
-            result = listener.accept() => {
-                match result {
-                    Ok((stream, _addr)) => {
-                        // Check connection limit
-                        let current = ACTIVE_CONNECTIONS.load(Ordering::SeqCst);
-                        if current >= MAX_CONNECTIONS {
-                            warn!("Connection limit reached ({}), rejecting new connection", current);
-                            drop(stream);
-                            continue;
-                        }
-                        
-                        ACTIVE_CONNECTIONS.fetch_add(1, Ordering::SeqCst);
-                        let rate_limiter = rate_limiter.clone();
-                        let fan_state = fan_control_state.clone();
-                        
-                        tokio::spawn(async move {
-                            handle_client(stream, rate_limiter, fan_state).await;
-                            ACTIVE_CONNECTIONS.fetch_sub(1, Ordering::SeqCst);
-                        });
-                    }
-                    Err(e) => {
-                        error!("Accept error: {}", e);
-                    }
-                }
-            }
+        let (result, kind) = tokio::select! {
+            result = listener.accept() => (result, SocketKind::Control),
+            result = accept_monitor(&monitor_listener) => (result, SocketKind::Monitor),
             _ = &mut shutdown => {
                 info!("Shutdown signal received");
                 break;
             }
+        };
+
+        let stream = match result {
+            Ok((stream, _addr)) => stream,
+            Err(e) => {
+                error!("Accept error: {}", e);
+                continue;
+            }
+        };
+
+        // Check connection limit
+        let (active, max) = kind.connections();
+        let current = active.load(Ordering::SeqCst);
+        if current >= max {
+            warn!("Connection limit reached ({} on {:?} socket), rejecting new connection", current, kind);
+            drop(stream);
+            continue;
         }
+
+        active.fetch_add(1, Ordering::SeqCst);
+        let rate_limiter = rate_limiter.clone();
+        let fan_state = fan_control_state.clone();
+
+        tokio::spawn(async move {
+            handle_client(stream, kind, rate_limiter, fan_state).await;
+            active.fetch_sub(1, Ordering::SeqCst);
+        });
     }
     
-    // Cleanup socket
+    // Cleanup sockets
     let _ = std::fs::remove_file(socket_path);
+    if monitor_listener.is_some() {
+        let _ = std::fs::remove_file(&monitor_path);
+    }
     info!("Server stopped (handled {} total connections)", 
           ACTIVE_CONNECTIONS.load(Ordering::SeqCst));
     
@@ -397,15 +430,16 @@ pub async fn run_server(socket_path: &str, fan_control_state: Arc<crate::fan_con
 
 /// Client credentials from Unix socket peer
 #[derive(Debug, Clone, Copy)]
-struct PeerCredentials {
-    uid: u32,
-    gid: u32,
-    pid: i32,
+pub(crate) struct PeerCredentials {
+    pub(crate) uid: u32,
+    pub(crate) gid: u32,
+    pub(crate) pid: i32,
 }
 
 /// Handle a single client connection with full security enforcement
 async fn handle_client(
     stream: UnixStream, 
+    kind: SocketKind,
     rate_limiter: Arc<Mutex<RateLimiter>>,
     fan_control_state: Arc<crate::fan_control::FanControlState>,
 ) {
@@ -419,7 +453,12 @@ async fn handle_client(
     };
     
     // Validate that the client is a legitimate Hyperfan binary
-    if let Err(e) = validate_client(&cred) {
+    // (monitoring clients are restricted by tier instead)
+    let validated = match kind {
+        SocketKind::Control => validate_client(&cred),
+        SocketKind::Monitor => Ok(()),
+    };
+    if let Err(e) = validated {
         error!("Client validation failed: {}", e);
         // Send error response and close connection
         let mut writer = stream;
@@ -431,20 +470,63 @@ async fn handle_client(
         return;
     }
     
+    // Capability tier from /etc/hyperfan/daemon.json (re-read per connection)
+    let tier = match kind {
+        SocketKind::Control => crate::permissions::DaemonConfig::load().tier_for(cred.uid, cred.gid, cred.pid),
+        SocketKind::Monitor => crate::permissions::ClientTier::Monitor,
+    };
+    
     info!(
-        "Validated connection from uid={}, gid={}, pid={}",
-        cred.uid, cred.gid, cred.pid
+        "Validated connection from uid={}, gid={}, pid={} (tier={:?})",
+        cred.uid, cred.gid, cred.pid, tier
     );
     
+    // Polkit decisions are cached for the lifetime of the connection; the
+    // monitoring socket only serves reads, so it never consults polkit
+    let mut auth = match kind {
+        SocketKind::Control => Some(crate::polkit::ClientAuthorization::new(cred.pid as u32, cred.uid)),
+        SocketKind::Monitor => None,
+    };
+    
     let (reader, mut writer) = stream.into_split();
     let mut reader = BufReader::new(reader);
     let mut line: Vec<u8> = Vec::with_capacity(MAX_MESSAGE_SIZE);
     let mut request_count: u64 = 0;
     let connection_start = Instant::now();
+    let mut subscription: Option<crate::events::Subscription> = None;
+    // Responses (and events) use the framing of the latest request
+    let mut framed = false;
     
     loop {
+        // Subscribed connections stay open without requests; events are written
+        // between responses while waiting for the next line
+        if let Some(sub) = subscription.as_mut() {
+            tokio::select! {
+                // fill_buf is cancel-safe: buffered input stays for read_message
+                ready = reader.fill_buf() => {
+                    if let Err(e) = ready {
+                        error!("Read error from uid={}, pid={}: {}", cred.uid, cred.pid, e);
+                        break;
+                    }
+                }
+                events = sub.next() => {
+                    let mut failed = false;
+                    for event in &events {
+                        if send_event(&mut writer, event, framed).await.is_err() {
+                            failed = true;
+                            break;
+                        }
+                    }
+                    if failed {
+                        break;
+                    }
+                    continue;
+                }
+            }
+        }
+
         // Apply read timeout
-        let read_result = timeout(READ_TIMEOUT, read_line_bounded(&mut reader, &mut line, MAX_MESSAGE_SIZE)).await;
+        let read_result = timeout(READ_TIMEOUT, read_message(&mut reader, &mut line, &mut framed)).await;
         
         match read_result {
             Ok(Ok(0)) => {
@@ -454,23 +536,8 @@ async fn handle_client(
                 break;
             }
             Ok(Ok(n)) => {
-                // read_line_bounded enforces MAX_MESSAGE_SIZE before buffering the full line.
-                
-                // Check rate limit
-                {
-                    let mut limiter = rate_limiter.lock().await;
-                    if !limiter.check_and_increment(cred.uid) {
-                        warn!("Rate limit exceeded for uid={}, pid={}", cred.uid, cred.pid);
-                        let response_envelope = hf_protocol::ResponseEnvelope::new(
-                            0,
-                            Response::error("Rate limit exceeded")
-                        );
-                        let _ = send_response(&mut writer, &response_envelope).await;
-                        // Don't break - just reject this request
-                        continue;
-                    }
-                }
-                
+                // read_message enforces the size limits before buffering the full message.
+                // The rate limit is checked in process_request once the request class is known.
                 request_count += 1;
                 trace!("Request #{} from uid={}: {} bytes", request_count, cred.uid, n);
 
@@ -482,34 +549,39 @@ async fn handle_client(
                             0,
                             Response::error("Invalid request encoding")
                         );
-                        let _ = send_response(&mut writer, &response_envelope).await;
+                        let _ = send_response(&mut writer, &response_envelope, framed).await;
                         break;
                     }
                 };
 
                 // Process request with audit logging
-                let response_envelope = process_request(line_str, &cred, &fan_control_state, &rate_limiter).await;
+                let response_envelope = process_request(
+                    line_str, &cred, tier, auth.as_mut(), &fan_control_state, &rate_limiter, &mut subscription,
+                ).await;
                 
                 // Send response with timeout
-                if send_response(&mut writer, &response_envelope).await.is_err() {
+                if send_response(&mut writer, &response_envelope, framed).await.is_err() {
                     break;
                 }
             }
             Ok(Err(e)) => {
-                if e.kind() == std::io::ErrorKind::InvalidData
-                    && e.to_string().contains("Message too large")
-                {
-                    warn!(
-                        "Message too large (>{} bytes) from uid={}, pid={}",
-                        MAX_MESSAGE_SIZE,
-                        cred.uid,
-                        cred.pid
-                    );
+                if e.kind() == std::io::ErrorKind::InvalidData {
+                    let message = e.to_string();
+                    if message.contains("Message too large") {
+                        warn!(
+                            "Message too large (>{} bytes) from uid={}, pid={}",
+                            if framed { frame::MAX_FRAMED_REQUEST_SIZE } else { MAX_MESSAGE_SIZE },
+                            cred.uid,
+                            cred.pid
+                        );
+                    } else {
+                        warn!("Invalid frame from uid={}, pid={}: {}", cred.uid, cred.pid, message);
+                    }
                     let response_envelope = hf_protocol::ResponseEnvelope::new(
                         0,
-                        Response::error("Message too large")
+                        Response::error(message)
                     );
-                    let _ = send_response(&mut writer, &response_envelope).await;
+                    let _ = send_response(&mut writer, &response_envelope, framed).await;
                 } else {
                     error!("Read error from uid={}, pid={}: {}", cred.uid, cred.pid, e);
                 }
@@ -521,27 +593,40 @@ async fn handle_client(
                     0,
                     Response::error("Read timeout")
                 );
-                let _ = send_response(&mut writer, &response_envelope).await;
+                let _ = send_response(&mut writer, &response_envelope, framed).await;
                 break;
             }
         }
     }
 }
 
+/// Write one message as a frame or a JSON line, within WRITE_TIMEOUT
+async fn write_message(
+    writer: &mut tokio::net::unix::OwnedWriteHalf,
+    json: &str,
+    framed: bool,
+) -> Result<std::io::Result<()>, tokio::time::error::Elapsed> {
+    timeout(WRITE_TIMEOUT, async {
+        if framed {
+            writer.write_all(&frame::encode_frame(json.as_bytes())).await
+        } else {
+            writer.write_all(json.as_bytes()).await?;
+            writer.write_all(b"\n").await
+        }
+    }).await
+}
+
 /// Send response with timeout
 async fn send_response(
     writer: &mut tokio::net::unix::OwnedWriteHalf,
     response_envelope: &hf_protocol::ResponseEnvelope,
+    framed: bool,
 ) -> Result<(), ()> {
     let response_json = serde_json::to_string(response_envelope).unwrap_or_else(|_| {
         r#"{"id":0,"status":"Error","data":{"message":"Serialization error"}}"#.to_string()
     });
     
-    let write_result = timeout(WRITE_TIMEOUT, async {
-        writer.write_all(response_json.as_bytes()).await?;
-        writer.write_all(b"\n").await?;
-        Ok::<_, std::io::Error>(())
-    }).await;
+    let write_result = write_message(writer, &response_json, framed).await;
     
     match write_result {
         Ok(Ok(_)) => Ok(()),
@@ -556,6 +641,29 @@ async fn send_response(
     }
 }
 
+/// Write a pushed event to a subscribed connection
+async fn send_event(
+    writer: &mut tokio::net::unix::OwnedWriteHalf,
+    event: &hf_protocol::EventEnvelope,
+    framed: bool,
+) -> Result<(), ()> {
+    let event_json = serde_json::to_string(event).map_err(|e| error!("Event serialization error: {}", e))?;
+
+    let write_result = write_message(writer, &event_json, framed).await;
+
+    match write_result {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => {
+            debug!("Event write error: {}", e);
+            Err(())
+        }
+        Err(_) => {
+            warn!("Event write timeout - dropping subscriber");
+            Err(())
+        }
+    }
+}
+
 /// Send response synchronously (for early rejection before async setup)
 async fn send_response_sync(
     writer: &mut UnixStream,
@@ -674,11 +782,25 @@ fn get_peer_credentials(stream: &UnixStream) -> Option<PeerCredentials> {
 async fn process_request(
     line: &str, 
     cred: &PeerCredentials,
+    tier: crate::permissions::ClientTier,
+    auth: Option<&mut crate::polkit::ClientAuthorization>,
     fan_control_state: &Arc<crate::fan_control::FanControlState>,
     rate_limiter: &Arc<Mutex<RateLimiter>>,
+    subscription: &mut Option<crate::events::Subscription>,
 ) -> hf_protocol::ResponseEnvelope {
     // Parse request envelope with strict validation
-    let envelope: hf_protocol::RequestEnvelope = match serde_json::from_str(line.trim()) {
+    let parsed: Result<hf_protocol::RequestEnvelope, _> = serde_json::from_str(line.trim());
+
+    // Requests take tokens by class; unparseable ones cost as much as a read
+    let class = parsed.as_ref().map(|e| e.request.class()).unwrap_or(hf_protocol::RequestClass::Read);
+    if !rate_limiter.lock().await.check(cred.uid, class) {
+        warn!("Rate limit exceeded for uid={}, pid={}", cred.uid, cred.pid);
+        let request_id = parsed.as_ref().map(|e| e.id).unwrap_or(0);
+        // Don't disconnect - just reject this request
+        return hf_protocol::ResponseEnvelope::new(request_id, Response::error("Rate limit exceeded"));
+    }
+
+    let envelope = match parsed {
         Ok(e) => e,
         Err(e) => {
             debug!("Invalid JSON from uid={}: {}", cred.uid, e);
@@ -692,15 +814,79 @@ async fn process_request(
     let request_id = envelope.id;
     let request = envelope.request;
     
+    // Capability tier: read-only clients cannot change anything,
+    // monitoring clients only read sensors and status
+    if !tier.allows(&request) {
+        let reason = tier.description();
+        warn!("AUDIT: {} (id={}) denied for {} uid={}, pid={}",
+              request.type_name(), request_id, reason, cred.uid, cred.pid);
+        crate::audit_log::record_outcome(&request, cred, crate::audit_log::Outcome::Denied, Some(reason));
+        return hf_protocol::ResponseEnvelope::new(
+            request_id,
+            Response::error(format!("Permission denied: {}", reason))
+        );
+    }
+    
+    // Polkit for requests that change anything (reads are decided by the tier above)
+    // (without a polkit session, as on the monitoring socket, nothing may change)
+    if let Some(action) = crate::polkit::action_for(&request) {
+        let authorized = match auth {
+            Some(auth) => auth.check(action).await,
+            None => false,
+        };
+        if !authorized {
+            warn!("AUDIT: {} (id={}) denied for uid={}, pid={} (polkit {})",
+                  request.type_name(), request_id, cred.uid, cred.pid, action);
+            crate::audit_log::record_outcome(&request, cred, crate::audit_log::Outcome::Denied, Some(action));
+            return hf_protocol::ResponseEnvelope::new(
+                request_id,
+                Response::error(format!("Not authorized: {}", action))
+            );
+        }
+    }
+    
+    // Subscriptions belong to the connection, so they are handled here rather than in dispatch
+    if let Request::Subscribe { since_seq } = request {
+        let (replay, sub) = crate::events::subscribe(since_seq);
+        debug!("Subscribed uid={}, pid={} to events (replaying {})", cred.uid, cred.pid, replay.len());
+        *subscription = Some(sub);
+        return hf_protocol::ResponseEnvelope::new(request_id, Response::Ok(ResponseData::events(replay)));
+    }
+
+    let response = dispatch_request(request, request_id, cred, fan_control_state, rate_limiter).await;
+    hf_protocol::ResponseEnvelope::new(request_id, response)
+}
+
+/// Validate and execute a parsed request with audit logging
+/// Shared by the Unix socket and the D-Bus interface so both behave identically
+pub(crate) async fn dispatch_request(
+    request: Request,
+    request_id: u64,
+    cred: &PeerCredentials,
+    fan_control_state: &Arc<crate::fan_control::FanControlState>,
+    rate_limiter: &Arc<Mutex<RateLimiter>>,
+) -> Response {
     // Double validation: validate request parameters server-side
     if let Err(e) = request.validate() {
         warn!("Request validation failed from uid={}: {}", cred.uid, e);
         // Sanitize error message to prevent path leakage
-        return hf_protocol::ResponseEnvelope::new(
-            request_id,
-            Response::error(sanitize_validation_error(&e))
-        );
+        let response = Response::error(sanitize_validation_error(&e));
+        crate::audit_log::record(&request, cred, &response);
+        return response;
     };
+
+    // Safe mode: nothing may take a fan away from firmware control
+    if crate::safe_mode::is_enabled() && crate::safe_mode::refuses(&request) {
+        warn!("AUDIT: {} (id={}) refused in safe mode for uid={}, pid={}",
+              request.type_name(), request_id, cred.uid, cred.pid);
+        let response = Response::error("Daemon is in safe mode: fans are under firmware control");
+        crate::audit_log::record(&request, cred, &response);
+        return response;
+    }
+
+    // Control writes are also recorded in the audit log file
+    let audited = (!request.is_read_only()).then(|| request.clone());
+    let config_section = request.config_section();
     
     // Log the request type for audit trail
     let request_type = request.type_name();
@@ -709,7 +895,7 @@ async fn process_request(
     let response = match request {
         Request::Ping => Response::ok_string("pong"),
         
-        Request::Version => Response::ok_string(env!("CARGO_PKG_VERSION")),
+        Request::Version => Response::Ok(ResponseData::version(env!("CARGO_PKG_VERSION").to_string(), capabilities())),
         
         Request::ListHardware => list_hardware(),
         
@@ -726,25 +912,35 @@ async fn process_request(
         Request::SetPwm { path, value } => {
             info!("AUDIT: SetPwm path={} value={} by uid={}, pid={}", 
                   path, value, cred.uid, cred.pid);
-            let resp = set_pwm(&path, value);
-            if matches!(resp, Response::Ok(_)) {
-                // Prevent the control loop from immediately fighting a manual set.
-                // Keep it short so curves re-take control automatically.
-                fan_control_state.set_pwm_override(path, value, DEFAULT_PWM_OVERRIDE_TTL_MS).await;
+            if let Some(refused) = refuse_excluded(&path) {
+                refused
+            } else {
+                let resp = set_pwm(&path, value);
+                if matches!(resp, Response::Ok(_)) {
+                    // Prevent the control loop from immediately fighting a manual set.
+                    // Keep it short so curves re-take control automatically.
+                    fan_control_state
+                        .set_pwm_override(path, value, DEFAULT_PWM_OVERRIDE_TTL_MS, Some((cred.uid, cred.pid)))
+                        .await;
+                }
+                resp
             }
-            resp
         }
         
         Request::EnableManualPwm { path } => {
             info!("AUDIT: EnableManualPwm path={} by uid={}, pid={}", 
                   path, cred.uid, cred.pid);
-            enable_manual_pwm(&path)
+            refuse_excluded(&path).unwrap_or_else(|| enable_manual_pwm(&path))
         }
         
         Request::DisableManualPwm { path } => {
             info!("AUDIT: DisableManualPwm path={} by uid={}, pid={}", 
                   path, cred.uid, cred.pid);
-            disable_manual_pwm(&path)
+            refuse_excluded(&path).unwrap_or_else(|| {
+                crate::competing_software::forget_enable_mode(&path);
+                crate::foreign_writes::forget(&path);
+                disable_manual_pwm(&path)
+            })
         }
 
         Request::SetPwmOverride { path, value, ttl_ms } => {
@@ -756,8 +952,12 @@ async fn process_request(
                 cred.uid,
                 cred.pid
             );
-            set_pwm_override(&path, value, ttl_ms, fan_control_state).await;
-            Response::ok()
+            if let Some(refused) = refuse_excluded(&path) {
+                refused
+            } else {
+                set_pwm_override(&path, value, ttl_ms, cred, fan_control_state).await;
+                Response::ok()
+            }
         }
 
         Request::ClearPwmOverride { path } => {
@@ -765,7 +965,58 @@ async fn process_request(
             clear_pwm_override(&path, fan_control_state).await;
             Response::ok()
         }
+
+        Request::ListActiveOverrides => {
+            Response::Ok(ResponseData::active_overrides(fan_control_state.active_overrides().await))
+        }
+
+        Request::GetCompetingSoftware => {
+            match tokio::task::spawn_blocking(crate::competing_software::report).await {
+                Ok(report) => Response::Ok(ResponseData::competing_software(report)),
+                Err(e) => Response::error(format!("Competing software check failed: {}", e)),
+            }
+        }
+
+        Request::PreviewCurve { curve_id, points, interpolation, duration_ms } => {
+            info!(
+                "AUDIT: PreviewCurve curve={} points={} duration_ms={} by uid={}, pid={}",
+                curve_id, points.len(), duration_ms, cred.uid, cred.pid
+            );
+            match fan_control_state.set_curve_preview(&curve_id, &points, interpolation, duration_ms).await {
+                0 => Response::error(format!("No active pair or group uses curve {}", curve_id)),
+                outputs => {
+                    info!("Previewing curve {} on {} pairs/groups for {}ms", curve_id, outputs, duration_ms);
+                    Response::ok()
+                }
+            }
+        }
+
+        Request::ClearCurvePreview { curve_id } => {
+            info!("AUDIT: ClearCurvePreview curve={} by uid={}, pid={}", curve_id, cred.uid, cred.pid);
+            fan_control_state.clear_curve_preview(&curve_id).await;
+            Response::ok()
+        }
         
+        Request::GetDryRun => Response::Ok(ResponseData::dry_run(crate::dry_run::is_enabled())),
+
+        Request::GetSafeMode => Response::Ok(ResponseData::safe_mode(crate::safe_mode::is_enabled())),
+
+        Request::GetControlStatus => Response::Ok(ResponseData::control_status(hf_protocol::ControlStatus {
+            controlled_outputs: fan_control_state.pairs.read().await.len() as u32,
+            dry_run: crate::dry_run::is_enabled(),
+            safe_mode: crate::safe_mode::is_enabled(),
+            implausible_readings: fan_control_state.plausibility.read().await.counters(),
+        })),
+
+        Request::SetDryRun { enabled } => {
+            info!("AUDIT: SetDryRun enabled={} by uid={}, pid={}", enabled, cred.uid, cred.pid);
+            let mut intended = fan_control_state.intended_writes.write().await;
+            if crate::dry_run::set_enabled(enabled, &mut intended) {
+                fan_control_state.signal_reload();
+            }
+            Response::Ok(ResponseData::dry_run(enabled))
+        }
+
         Request::ListGpus => list_gpus(),
         
         Request::SetGpuFan { index, fan_index, percent } => {
@@ -789,21 +1040,118 @@ async fn process_request(
             );
             reset_gpu_fan_auto(index)
         }
+
+        Request::GetGpuFanCurve { index } => {
+            debug!("GetGpuFanCurve index={} by uid={}, pid={}", index, cred.uid, cred.pid);
+            get_gpu_fan_curve(index)
+        }
+
+        Request::SetGpuFanCurve { index, points } => {
+            info!("AUDIT: SetGpuFanCurve index={} points={:?} by uid={}, pid={}", index, points, cred.uid, cred.pid);
+            set_gpu_fan_curve(index, &points)
+        }
+
+        Request::ResetGpuFanCurve { index } => {
+            info!("AUDIT: ResetGpuFanCurve index={} by uid={}, pid={}", index, cred.uid, cred.pid);
+            match hf_core::reset_amd_fan_curve(index) {
+                Ok(()) => get_gpu_fan_curve(index),
+                Err(e) => Response::error(format!("Failed to reset firmware fan curve: {}", e)),
+            }
+        }
+
+        Request::GetGpuPowerLimit { index } => {
+            debug!("GetGpuPowerLimit index={} by uid={}, pid={}", index, cred.uid, cred.pid);
+            get_gpu_power_limit(index)
+        }
+
+        Request::SetGpuPowerLimit { index, watts } => {
+            info!("AUDIT: SetGpuPowerLimit index={} watts={} by uid={}, pid={}", index, watts, cred.uid, cred.pid);
+            set_gpu_power_limit(index, Some(watts))
+        }
+
+        Request::ResetGpuPowerLimit { index } => {
+            info!("AUDIT: ResetGpuPowerLimit index={} by uid={}, pid={}", index, cred.uid, cred.pid);
+            set_gpu_power_limit(index, None)
+        }
         
         Request::DetectFanMappings => {
             warn!("AUDIT: DetectFanMappings by uid={}, pid={}", cred.uid, cred.pid);
             detect_fan_mappings().await
         }
         
+        Request::StartDetection { strategy } => {
+            warn!("AUDIT: StartDetection ({:?}) by uid={}, pid={}", strategy, cred.uid, cred.pid);
+            match crate::detection::start(fan_control_state, strategy).await {
+                Ok(progress) => Response::Ok(ResponseData::detection(progress)),
+                Err(e) => Response::error(e),
+            }
+        }
+        
+        Request::GetDetectionProgress { job_id } => {
+            debug!("GetDetectionProgress job={} by uid={}, pid={}", job_id, cred.uid, cred.pid);
+            match crate::detection::progress(fan_control_state, job_id).await {
+                Ok(progress) => Response::Ok(ResponseData::detection(progress)),
+                Err(e) => Response::error(e),
+            }
+        }
+        
+        Request::CancelDetection { job_id } => {
+            info!("AUDIT: CancelDetection job={} by uid={}, pid={}", job_id, cred.uid, cred.pid);
+            match crate::detection::cancel(fan_control_state, job_id).await {
+                Ok(progress) => Response::Ok(ResponseData::detection(progress)),
+                Err(e) => Response::error(e),
+            }
+        }
+        
+        Request::StartAutoTune { curve_id, target_temp } => {
+            warn!("AUDIT: StartAutoTune curve={} target={:.0}°C by uid={}, pid={}",
+                  curve_id, target_temp, cred.uid, cred.pid);
+            match crate::auto_tune::start(fan_control_state, curve_id, target_temp).await {
+                Ok(progress) => Response::Ok(ResponseData::auto_tune(progress)),
+                Err(e) => Response::error(e),
+            }
+        }
+        
+        Request::GetAutoTuneProgress { job_id } => {
+            debug!("GetAutoTuneProgress job={} by uid={}, pid={}", job_id, cred.uid, cred.pid);
+            match crate::auto_tune::progress(fan_control_state, job_id).await {
+                Ok(progress) => Response::Ok(ResponseData::auto_tune(progress)),
+                Err(e) => Response::error(e),
+            }
+        }
+        
+        Request::CancelAutoTune { job_id } => {
+            info!("AUDIT: CancelAutoTune job={} by uid={}, pid={}", job_id, cred.uid, cred.pid);
+            match crate::auto_tune::cancel(fan_control_state, job_id).await {
+                Ok(progress) => Response::Ok(ResponseData::auto_tune(progress)),
+                Err(e) => Response::error(e),
+            }
+        }
+        
+        Request::GetBindingConflicts => {
+            debug!("GetBindingConflicts by uid={}, pid={}", cred.uid, cred.pid);
+            get_binding_conflicts()
+        }
+        
+        Request::AcceptBindingMatch { pwm_id, pwm_path, fan_path } => {
+            warn!("AUDIT: AcceptBindingMatch binding={} pwm={} fan={:?} by uid={}, pid={}",
+                  pwm_id, pwm_path, fan_path, cred.uid, cred.pid);
+            let resp = accept_binding_match(&pwm_id, &pwm_path, fan_path.as_deref());
+            if matches!(resp, Response::Ok(_)) {
+                fan_control_state.signal_reload();
+            }
+            resp
+        }
+        
         Request::GetManualPairings => {
             debug!("GetManualPairings by uid={}, pid={}", cred.uid, cred.pid);
             get_manual_pairings()
         }
         
-        Request::SetManualPairing { pwm_uuid, pwm_path, fan_uuid, fan_path } => {
-            info!("AUDIT: SetManualPairing pwm_uuid={} pwm={} fan_uuid={:?} fan={:?} by uid={}, pid={}", 
-                  pwm_uuid, pwm_path, fan_uuid, fan_path, cred.uid, cred.pid);
-            let resp = set_manual_pairing(&pwm_uuid, &pwm_path, fan_uuid.as_deref(), fan_path.as_deref());
+        Request::SetManualPairing { pwm_uuid, pwm_path, fan_uuid, fan_path, tuning } => {
+            info!("AUDIT: SetManualPairing pwm_uuid={} pwm={} fan_uuid={:?} fan={:?} tuning={:?} by uid={}, pid={}", 
+                  pwm_uuid, pwm_path, fan_uuid, fan_path, tuning, cred.uid, cred.pid);
+            let resp = set_manual_pairing(&pwm_uuid, &pwm_path, fan_uuid.as_deref(), fan_path.as_deref(), tuning);
             if matches!(resp, Response::Ok(_)) {
                 fan_control_state.signal_reload();
             }
@@ -820,12 +1168,94 @@ async fn process_request(
             resp
         }
         
+        Request::GetPwmGroups => {
+            debug!("GetPwmGroups by uid={}, pid={}", cred.uid, cred.pid);
+            match hf_core::load_settings() {
+                Ok(settings) => Response::Ok(ResponseData::pwm_groups(settings.pwm_groups)),
+                Err(e) => Response::error(format!("Failed to load settings: {}", e)),
+            }
+        }
+
+        Request::SetPwmGroup { group } => {
+            info!("AUDIT: SetPwmGroup id={} name={} curve={} members={} by uid={}, pid={}",
+                  group.id, group.name, group.curve_id, group.members.len(), cred.uid, cred.pid);
+            let resp = set_pwm_group(group);
+            if matches!(resp, Response::Ok(_)) {
+                fan_control_state.signal_reload();
+            }
+            resp
+        }
+
+        Request::DeletePwmGroup { id } => {
+            info!("AUDIT: DeletePwmGroup id={} by uid={}, pid={}", id, cred.uid, cred.pid);
+            let resp = delete_pwm_group(&id);
+            if matches!(resp, Response::Ok(_)) {
+                fan_control_state.signal_reload();
+            }
+            resp
+        }
+        
+        Request::SetPairingControlMode { pwm_path, mode } => {
+            info!("AUDIT: SetPairingControlMode pwm={} mode={:?} by uid={}, pid={}",
+                  pwm_path, mode, cred.uid, cred.pid);
+            let resp = set_pairing_control_mode(&pwm_path, mode);
+            if matches!(resp, Response::Ok(_)) {
+                fan_control_state.signal_reload();
+            }
+            resp
+        }
+        
+        Request::SetPairingZeroRpm { pwm_path, enabled } => {
+            info!("AUDIT: SetPairingZeroRpm pwm={} enabled={} by uid={}, pid={}",
+                  pwm_path, enabled, cred.uid, cred.pid);
+            let resp = set_pairing_zero_rpm(&pwm_path, enabled);
+            if matches!(resp, Response::Ok(_)) {
+                fan_control_state.signal_reload();
+            }
+            resp
+        }
+        
+        Request::SetPairingEnforcement { pwm_path, policy } => {
+            info!("AUDIT: SetPairingEnforcement pwm={} policy={:?} by uid={}, pid={}",
+                  pwm_path, policy, cred.uid, cred.pid);
+            let resp = set_pairing_enforcement(&pwm_path, policy);
+            if matches!(resp, Response::Ok(_)) {
+                fan_control_state.signal_reload();
+            }
+            resp
+        }
+        
+        Request::CalibrateFan { pwm_path, fan_path } => {
+            warn!("AUDIT: CalibrateFan pwm={} fan={} by uid={}, pid={}", pwm_path, fan_path, cred.uid, cred.pid);
+            match crate::calibration::start(fan_control_state, pwm_path, fan_path).await {
+                Ok(status) => Response::Ok(ResponseData::calibration(status)),
+                Err(e) => Response::error(e),
+            }
+        }
+
+        Request::GetFanCalibration { pwm_path } => {
+            debug!("GetFanCalibration pwm={} by uid={}, pid={}", pwm_path, cred.uid, cred.pid);
+            Response::Ok(ResponseData::calibration(crate::calibration::status(fan_control_state, &pwm_path).await))
+        }
+        
         Request::ReloadConfig => {
             info!("AUDIT: ReloadConfig by uid={}, pid={}", cred.uid, cred.pid);
+            hf_core::invalidate_settings_cache();
             // Signal the fan control loop to reload its configuration
             fan_control_state.signal_reload();
             Response::ok_string("Configuration reload signaled")
         }
+
+        Request::ReloadConfigSection { section } => {
+            info!("AUDIT: ReloadConfigSection {:?} by uid={}, pid={}", section, cred.uid, cred.pid);
+            if matches!(section, hf_protocol::ConfigSection::Settings | hf_protocol::ConfigSection::All) {
+                hf_core::invalidate_settings_cache();
+            }
+            // The control loop builds its outputs from settings, curves and pairings
+            // together, so it reloads in full; the section narrows what clients re-read
+            fan_control_state.signal_reload();
+            Response::ok_string("Configuration reload signaled")
+        }
         
         // ====================================================================
         // EC Direct Control (DANGEROUS)
@@ -854,6 +1284,18 @@ async fn process_request(
                    chip_path, start_register, count, cred.uid, cred.pid);
             read_ec_register_range(&chip_path, start_register, count)
         }
+
+        Request::WatchEcRegisters { chip_path, start_register, count, interval_ms, samples } => {
+            debug!("WatchEcRegisters chip={} start=0x{:02X} count={} interval={}ms samples={} by uid={}, pid={}",
+                   chip_path, start_register, count, interval_ms, samples, cred.uid, cred.pid);
+            watch_ec_registers(chip_path, start_register, count, interval_ms, samples).await
+        }
+
+        Request::SetEcFanDuty { chip_path, fan, percent } => {
+            warn!("AUDIT: SetEcFanDuty chip={} fan={} percent={:?} by uid={}, pid={}",
+                  chip_path, fan, percent, cred.uid, cred.pid);
+            set_ec_fan_duty(&chip_path, fan, percent)
+        }
         
         Request::SetGlobalMode { mode: _ } => {
             // Not implemented - manual mode is handled via PWM overrides
@@ -861,21 +1303,86 @@ async fn process_request(
         }
         
         Request::GetGlobalMode => {
-            // Not implemented - manual mode is handled via PWM overrides
-            Response::error("GetGlobalMode not implemented")
+            // Manual mode is handled via PWM overrides: manual while any is active
+            let mode = if fan_control_state.active_overrides().await.is_empty() {
+                GlobalMode::Auto
+            } else {
+                GlobalMode::Manual
+            };
+            Response::Ok(ResponseData::mode(mode))
         }
         
         Request::GetRateLimit => {
             let limiter = rate_limiter.lock().await;
-            let limit = limiter.get_rate_limit();
-            Response::Ok(ResponseData::rate_limit(limit))
+            Response::Ok(ResponseData::rate_limit(limiter.config()))
         }
         
-        Request::SetRateLimit { limit } => {
+        Request::SetRateLimit { limit, burst, costs } => {
             let mut limiter = rate_limiter.lock().await;
-            let actual_limit = limiter.set_rate_limit(limit);
-            info!("Rate limit changed to {} by uid={}", actual_limit, cred.uid);
-            Response::Ok(ResponseData::rate_limit(actual_limit))
+            let config = limiter.configure(limit, burst, costs);
+            info!("Rate limit changed to {} per {:?} (burst {}, costs {:?}) by uid={}",
+                  config.limit, RATE_LIMIT_WINDOW, config.burst, config.costs, cred.uid);
+            Response::Ok(ResponseData::rate_limit(config))
+        }
+        
+        Request::GetFanAlerts { since_id } => {
+            let monitor = fan_control_state.stall_monitor.read().await;
+            Response::Ok(ResponseData::alerts(monitor.alerts_since(since_id)))
+        }
+
+        Request::GetHardwareChanges { since_id } => {
+            let hotplug = fan_control_state.hotplug.read().await;
+            Response::Ok(ResponseData::hw_changes(hotplug.changes_since(since_id)))
+        }
+
+        Request::GetLogs { since_id, min_level } => {
+            Response::Ok(ResponseData::logs(crate::log_buffer::entries_since(since_id, min_level)))
+        }
+
+        // Handled by the socket connection (process_request); D-Bus has no event stream
+        Request::Subscribe { .. } => Response::error("Event subscriptions are only available on the daemon socket"),
+        
+        Request::StartRecording { interval_ms, format, max_duration_ms, max_bytes } => {
+            info!("AUDIT: StartRecording interval_ms={} format={:?} max_duration_ms={:?} max_bytes={:?} by uid={}, pid={}",
+                  interval_ms, format, max_duration_ms, max_bytes, cred.uid, cred.pid);
+            match fan_control_state.recorder.lock().await.start(interval_ms, format, max_duration_ms, max_bytes) {
+                Ok(info) => Response::Ok(ResponseData::recording(info)),
+                Err(e) => Response::error(e),
+            }
+        }
+        
+        Request::StopRecording => {
+            info!("AUDIT: StopRecording by uid={}, pid={}", cred.uid, cred.pid);
+            match fan_control_state.recorder.lock().await.stop().await {
+                Ok(info) => Response::Ok(ResponseData::recording(info)),
+                Err(e) => Response::error(e),
+            }
+        }
+        
+        Request::QueryHistory { start_ms, end_ms, resolution_secs, paths } => {
+            debug!("QueryHistory {}..{} by uid={}, pid={}", start_ms, end_ms, cred.uid, cred.pid);
+            let db = fan_control_state.history.lock().await;
+            let history = db.query(start_ms, end_ms, resolution_secs, &paths, crate::history::now_secs());
+            Response::Ok(ResponseData::history(history))
+        }
+        
+        Request::GetRecording => match fan_control_state.recorder.lock().await.status() {
+            Ok(info) => Response::Ok(ResponseData::recording(info)),
+            Err(e) => Response::error(e),
+        },
+        
+        Request::GetActiveProfile => {
+            debug!("GetActiveProfile by uid={}, pid={}", cred.uid, cred.pid);
+            get_active_profile()
+        }
+        
+        Request::SetActiveProfile { name } => {
+            info!("AUDIT: SetActiveProfile name={} by uid={}, pid={}", name, cred.uid, cred.pid);
+            let resp = set_active_profile(&name);
+            if matches!(resp, Response::Ok(_)) {
+                fan_control_state.signal_reload();
+            }
+            resp
         }
     };
     
@@ -883,30 +1390,17 @@ async fn process_request(
     if let Response::Error { ref message } = response {
         warn!("Request {} (id={}) failed for uid={}: {}", request_type, request_id, cred.uid, message);
     }
+    if let Some(request) = &audited {
+        crate::audit_log::record(request, cred, &response);
+    }
+    // Other clients re-read what changed
+    if let (Some(section), Response::Ok(_)) = (config_section, &response) {
+        // Already applied; the file watcher should not announce it again
+        fan_control_state.config_watch.write().await.accept_current();
+        crate::events::publish_config_changed(section);
+    }
     
-    hf_protocol::ResponseEnvelope::new(request_id, response)
-}
-
-/// Generate a stable UUID for a sensor based on chip name, sensor name, and type
-/// This UUID is deterministic and will be the same across reboots for the same hardware
-fn generate_sensor_uuid(chip_name: &str, sensor_name: &str, sensor_type: &str) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    
-    let mut hasher = DefaultHasher::new();
-    chip_name.hash(&mut hasher);
-    sensor_name.hash(&mut hasher);
-    sensor_type.hash(&mut hasher);
-    let hash = hasher.finish();
-    
-    // Format as UUID-like string (not a real UUID, but stable and unique)
-    format!("{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
-        (hash >> 32) as u32,
-        ((hash >> 16) & 0xFFFF) as u16,
-        (hash & 0xFFFF) as u16,
-        ((hash >> 48) & 0xFFFF) as u16,
-        hash & 0xFFFFFFFFFFFF
-    )
+    response
 }
 
 /// Sanitize validation error messages to prevent information leakage
@@ -937,18 +1431,21 @@ fn chips_to_protocol(chips: &[hf_core::HwmonChip]) -> Vec<HwmonChip> {
         HwmonChip {
             name: c.name.clone(),
             path: c.path.to_string_lossy().to_string(),
+            // Values were just re-read by get_cached_chips
             temperatures: c.temperatures.iter().map(|t| {
-                let value = hf_core::read_temperature(&t.input_path).unwrap_or(f32::NAN);
                 TempSensor {
                     name: t.name.clone(),
                     label: t.label.clone(),
                     path: t.input_path.to_string_lossy().to_string(),
-                    value,
+                    value: t.current_temp.unwrap_or(f32::NAN),
+                    max: t.limits.max,
+                    crit: t.limits.crit,
+                    crit_alarm: t.limits.crit_alarm,
                 }
             }).collect(),
             fans: c.fans.iter().map(|f| {
-                let rpm = hf_core::read_fan_rpm(&f.input_path).ok();
-                let uuid = generate_sensor_uuid(&c.name, &f.name, "fan");
+                let rpm = f.current_rpm;
+                let uuid = hf_core::sensor_uuid(&c.path, &c.name, &f.name, hf_core::ChannelType::Fan);
                 FanSensor {
                     uuid,
                     name: f.name.clone(),
@@ -958,28 +1455,47 @@ fn chips_to_protocol(chips: &[hf_core::HwmonChip]) -> Vec<HwmonChip> {
                 }
             }).collect(),
             pwms: c.pwms.iter().map(|p| {
-                let value = std::fs::read_to_string(&p.pwm_path)
-                    .ok()
-                    .and_then(|s| s.trim().parse().ok())
-                    .unwrap_or(0);
+                let value = p.current_value.unwrap_or(0);
                 let enabled = std::fs::read_to_string(&p.enable_path)
                     .ok()
                     .and_then(|s| s.trim().parse::<u8>().ok())
                     .map(|v| v == 1)
                     .unwrap_or(false);
-                let uuid = generate_sensor_uuid(&c.name, &p.name, "pwm");
+                let uuid = hf_core::sensor_uuid(&c.path, &c.name, &p.name, hf_core::ChannelType::Pwm);
                 PwmControl {
                     uuid,
                     name: p.name.clone(),
                     path: p.pwm_path.to_string_lossy().to_string(),
                     value,
-                    enabled,
+                    // Cooling devices have no enable file and are always writable
+                    enabled: enabled || p.cooling_device.is_some(),
+                    max_state: p.cooling_device.as_ref().map(|c| c.max_state),
+                    dc_mode: p.dc_mode,
                 }
             }).collect(),
+            sensors: c.sensors.iter().map(|s| {
+                HwSensor {
+                    name: s.name.clone(),
+                    label: s.label.clone(),
+                    path: s.input_path.to_string_lossy().to_string(),
+                    kind: sensor_kind_to_protocol(s.kind),
+                    value: s.current_value.unwrap_or(f32::NAN),
+                }
+            }).collect(),
+            alarms: c.alarms.iter().map(Into::into).collect(),
         }
     }).collect()
 }
 
+fn sensor_kind_to_protocol(kind: hf_core::SensorKind) -> SensorKind {
+    match kind {
+        hf_core::SensorKind::Voltage => SensorKind::Voltage,
+        hf_core::SensorKind::Current => SensorKind::Current,
+        hf_core::SensorKind::Power => SensorKind::Power,
+        hf_core::SensorKind::Humidity => SensorKind::Humidity,
+    }
+}
+
 /// Convert GPUs to protocol format (shared by list_gpus and list_all)
 fn gpus_to_protocol(gpus: &[hf_gpu::GpuDevice]) -> Vec<GpuInfo> {
     gpus.iter().map(|g| {
@@ -990,15 +1506,78 @@ fn gpus_to_protocol(gpus: &[hf_gpu::GpuDevice]) -> Vec<GpuInfo> {
             temp: g.temperatures.first().and_then(|t| t.current_temp),
             fan_percent: g.fans.first().and_then(|f| f.speed_percent),
             fan_rpm: g.fans.first().and_then(|f| f.rpm),
+            read_only: g.read_only,
+            temperatures: g.temperatures.iter().filter_map(|t| {
+                Some(GpuTempInfo {
+                    name: t.name.clone(),
+                    value: t.current_temp?,
+                    max: t.max_temp,
+                    critical: t.critical_temp,
+                })
+            }).collect(),
+            power_watts: g.power_watts,
+            power_limit_watts: g.power_limit_watts,
         }
     }).collect()
 }
 
+/// Virtual sensors as a pseudo-chip, evaluated from the chips just listed
+fn virtual_chip(chips: &[HwmonChip]) -> Option<HwmonChip> {
+    let sensors = crate::fan_control::virtual_sensors();
+    if sensors.is_empty() {
+        return None;
+    }
+
+    let listed: std::collections::HashMap<&str, f32> = chips.iter()
+        .flat_map(|c| c.temperatures.iter())
+        .map(|t| (t.path.as_str(), t.value))
+        .collect();
+    let temperatures = sensors.iter().map(|sensor| {
+        let value = sensor
+            .evaluate(|path| match listed.get(path) {
+                Some(&value) => Ok(value),
+                // GPU and drive inputs are not part of the hwmon listing
+                None => crate::fan_control::read_temperature_inner(path),
+            })
+            .unwrap_or_else(|e| {
+                debug!("Virtual sensor unavailable: {}", e);
+                f32::NAN
+            });
+        TempSensor {
+            name: sensor.id.clone(),
+            label: Some(sensor.name.clone()),
+            path: sensor.path(),
+            value,
+            max: None,
+            crit: None,
+            crit_alarm: false,
+        }
+    }).collect();
+
+    Some(HwmonChip {
+        name: "virtual".to_string(),
+        path: "virtual".to_string(),
+        temperatures,
+        fans: Vec::new(),
+        pwms: Vec::new(),
+        sensors: Vec::new(),
+        alarms: Vec::new(),
+    })
+}
+
+/// Hardware chips followed by the sensor provider chips and the virtual sensor chip
+fn hardware_info(chips: &[hf_core::HwmonChip]) -> HardwareInfo {
+    let mut chips = chips_to_protocol(chips);
+    chips.extend(crate::sensor_providers::provider_chips());
+    if let Some(chip) = virtual_chip(&chips) {
+        chips.push(chip);
+    }
+    HardwareInfo { chips }
+}
+
 fn list_hardware() -> Response {
     match get_cached_chips() {
-        Ok(chips) => {
-            Response::Ok(ResponseData::hw(HardwareInfo { chips: chips_to_protocol(&chips) }))
-        }
+        Ok(chips) => Response::Ok(ResponseData::hw(hardware_info(&chips))),
         Err(e) => Response::error(e),
     }
 }
@@ -1006,7 +1585,7 @@ fn list_hardware() -> Response {
 /// Batched hardware + GPU enumeration (single IPC call for polling)
 fn list_all() -> Response {
     let hardware = match get_cached_chips() {
-        Ok(chips) => HardwareInfo { chips: chips_to_protocol(&chips) },
+        Ok(chips) => hardware_info(&chips),
         Err(e) => return Response::error(e),
     };
     
@@ -1019,6 +1598,13 @@ fn list_all() -> Response {
 }
 
 fn read_temperature(path: &str) -> Response {
+    if hf_core::virtual_sensor_id(path).is_some() || path.starts_with(hf_protocol::PROVIDER_PATH_PREFIX) {
+        return match crate::fan_control::read_temperature_inner(path) {
+            Ok(temp) => Response::ok_temp(temp),
+            Err(e) => Response::error(format!("Failed to read temperature: {}", e)),
+        };
+    }
+
     if let Err(e) = validate_hwmon_path(path) {
         return Response::error(e);
     }
@@ -1044,6 +1630,16 @@ fn read_pwm(path: &str) -> Response {
     if let Err(e) = validate_hwmon_path(path) {
         return Response::error(e);
     }
+
+    if path.starts_with(SYSCTL_PATH_PREFIX)
+        || path.starts_with(IPMI_PATH_PREFIX)
+        || hf_core::is_cooling_device_path(std::path::Path::new(path))
+    {
+        return match hf_core::read_pwm_value(std::path::Path::new(path)) {
+            Ok(value) => Response::ok_pwm(value),
+            Err(e) => Response::error(format!("Failed to read PWM: {}", e)),
+        };
+    }
     
     match std::fs::read_to_string(path) {
         Ok(content) => {
@@ -1056,6 +1652,14 @@ fn read_pwm(path: &str) -> Response {
     }
 }
 
+/// Error response for writes to an output hidden by the controller filter
+fn refuse_excluded(path: &str) -> Option<Response> {
+    if !hf_core::is_pwm_excluded(std::path::Path::new(path)) {
+        return None;
+    }
+    Some(Response::error(format!("{} is excluded by the controller filter", path)))
+}
+
 fn set_pwm(path: &str, value: u8) -> Response {
     debug!("Setting PWM {} to {}", path, value);
 
@@ -1067,9 +1671,19 @@ fn enable_manual_pwm(path: &str) -> Response {
         return Response::error(e);
     }
 
-    if path.starts_with("nvidia:") || path.starts_with("amd:") || path.starts_with("intel:") {
+    // Cooling devices have no enable file
+    if path.starts_with("nvidia:") || path.starts_with("amd:") || path.starts_with("intel:")
+        || hf_core::is_cooling_device_path(std::path::Path::new(path))
+    {
         return Response::ok();
     }
+
+    if path.starts_with(SYSCTL_PATH_PREFIX) || path.starts_with(IPMI_PATH_PREFIX) {
+        return match hf_core::enable_manual_pwm(std::path::Path::new(path)) {
+            Ok(()) => Response::ok(),
+            Err(e) => Response::error(format!("Failed to enable manual PWM: {}", e)),
+        };
+    }
     
     let enable_path = match pwm_enable_path_from_pwm_path(path) {
         Ok(p) => p,
@@ -1086,7 +1700,7 @@ fn enable_manual_pwm(path: &str) -> Response {
     
     debug!("Enabling manual PWM control: {}", enable_path);
     
-    match std::fs::write(&enable_path, "1") {
+    match hf_core::enable_manual_pwm(Path::new(&enable_path)) {
         Ok(_) => Response::ok(),
         Err(e) => Response::error(format!("Failed to enable manual PWM: {}", e)),
     }
@@ -1097,9 +1711,19 @@ fn disable_manual_pwm(path: &str) -> Response {
         return Response::error(e);
     }
 
-    if path.starts_with("nvidia:") || path.starts_with("amd:") || path.starts_with("intel:") {
+    // Cooling devices have no enable file
+    if path.starts_with("nvidia:") || path.starts_with("amd:") || path.starts_with("intel:")
+        || hf_core::is_cooling_device_path(std::path::Path::new(path))
+    {
         return Response::ok();
     }
+
+    if path.starts_with(SYSCTL_PATH_PREFIX) || path.starts_with(IPMI_PATH_PREFIX) {
+        return match hf_core::enable_auto_pwm(std::path::Path::new(path)) {
+            Ok(()) => Response::ok(),
+            Err(e) => Response::error(format!("Failed to disable manual PWM: {}", e)),
+        };
+    }
     
     let enable_path = match pwm_enable_path_from_pwm_path(path) {
         Ok(p) => p,
@@ -1117,7 +1741,7 @@ fn disable_manual_pwm(path: &str) -> Response {
     debug!("Disabling manual PWM control: {}", enable_path);
     
     // Set to 2 for automatic control
-    match std::fs::write(&enable_path, "2") {
+    match hf_core::enable_auto_pwm(Path::new(&enable_path)) {
         Ok(_) => Response::ok(),
         Err(e) => Response::error(format!("Failed to disable manual PWM: {}", e)),
     }
@@ -1127,13 +1751,16 @@ async fn set_pwm_override(
     path: &str,
     value: u8,
     ttl_ms: u32,
+    cred: &PeerCredentials,
     fan_control_state: &Arc<crate::fan_control::FanControlState>,
 ) {
-    // Conservative TTL bounds: long enough for live drag, short enough to be safe.
-    let ttl_ms = ttl_ms.clamp(50, 30_000);
+    // Short TTLs follow a live slider drag; the longest is a timed manual speed.
+    let ttl_ms = ttl_ms.clamp(50, hf_protocol::MAX_PWM_OVERRIDE_TTL_MS);
 
     // Install override in fan control state
-    fan_control_state.set_pwm_override(path.to_string(), value, ttl_ms).await;
+    fan_control_state
+        .set_pwm_override(path.to_string(), value, ttl_ms, Some((cred.uid, cred.pid)))
+        .await;
 }
 
 fn reset_gpu_fan_auto(index: u32) -> Response {
@@ -1150,12 +1777,8 @@ fn reset_gpu_fan_auto(index: u32) -> Response {
                 }
             }
             hf_core::GpuVendor::Amd => {
-                // For AMD we currently reset using the resolved hwmon path to pwm1_enable.
-                // The controller pwm_path is a file path (e.g. .../pwm1), so use its parent directory.
-                let Some(hwmon_path) = std::path::Path::new(&controller.pwm_path).parent() else {
-                    return Response::error("Invalid AMD PWM path");
-                };
-                if let Err(e) = hf_core::reset_amd_fan_auto(hwmon_path) {
+                // Each channel (pwm1, pwm2, ...) has its own pwmN_enable
+                if let Err(e) = hf_core::reset_amd_fan_auto(std::path::Path::new(&controller.pwm_path)) {
                     return Response::error(format!("Failed to reset AMD fan auto: {}", e));
                 }
             }
@@ -1170,6 +1793,87 @@ fn reset_gpu_fan_auto(index: u32) -> Response {
     Response::ok()
 }
 
+fn get_gpu_fan_curve(index: u32) -> Response {
+    match hf_core::get_amd_fan_curve(index) {
+        Ok(curve) => Response::Ok(ResponseData::fan_curve(GpuFanCurveInfo {
+            index,
+            points: curve.points.iter().map(|p| GpuFanCurvePoint {
+                temp_c: p.temp_c,
+                speed_percent: p.speed_percent,
+            }).collect(),
+            temp_min: curve.temp_range.map(|(min, _)| min),
+            temp_max: curve.temp_range.map(|(_, max)| max),
+            speed_min: curve.speed_range.map(|(min, _)| min),
+            speed_max: curve.speed_range.map(|(_, max)| max),
+        })),
+        Err(e) => Response::error(format!("Failed to read firmware fan curve: {}", e)),
+    }
+}
+
+fn set_gpu_fan_curve(index: u32, points: &[GpuFanCurvePoint]) -> Response {
+    let points: Vec<hf_core::GpuFanCurvePoint> = points.iter().map(|p| hf_core::GpuFanCurvePoint {
+        temp_c: p.temp_c,
+        speed_percent: p.speed_percent,
+    }).collect();
+    match hf_core::set_amd_fan_curve(index, &points) {
+        Ok(()) => get_gpu_fan_curve(index),
+        Err(e) => Response::error(format!("Failed to set firmware fan curve: {}", e)),
+    }
+}
+
+/// Vendor of the writable GPU `index`
+fn gpu_vendor(index: u32) -> Result<hf_core::GpuVendor, String> {
+    let gpus = hf_core::enumerate_gpus().map_err(|e| format!("Failed to enumerate GPUs: {}", e))?;
+    gpus.iter()
+        .find(|g| g.index == index && !g.read_only)
+        .map(|g| g.vendor)
+        .ok_or_else(|| format!("GPU {} not found", index))
+}
+
+fn get_gpu_power_limit(index: u32) -> Response {
+    let vendor = match gpu_vendor(index) {
+        Ok(vendor) => vendor,
+        Err(e) => return Response::error(e),
+    };
+    match hf_core::get_gpu_power_limit(vendor, index) {
+        Ok(limit) => Response::Ok(ResponseData::power_limit(GpuPowerLimitInfo {
+            index,
+            watts: limit.current_watts,
+            default_watts: limit.default_watts,
+            min_watts: limit.min_watts,
+            max_watts: limit.max_watts,
+        })),
+        Err(e) => Response::error(format!("Failed to read power limit: {}", e)),
+    }
+}
+
+/// Set the power limit of GPU `index`, or restore its default with None
+fn set_gpu_power_limit(index: u32, watts: Option<u32>) -> Response {
+    match hf_core::load_settings() {
+        Ok(settings) if !settings.advanced.gpu_power_limits_enabled => {
+            return Response::error("GPU power limit control is not enabled in settings");
+        }
+        Ok(_) => {}
+        Err(e) => return Response::error(format!("Failed to load settings: {}", e)),
+    }
+    let vendor = match gpu_vendor(index) {
+        Ok(vendor) => vendor,
+        Err(e) => return Response::error(e),
+    };
+    let watts = match watts {
+        Some(watts) => watts,
+        None => match hf_core::get_gpu_power_limit(vendor, index) {
+            Ok(hf_core::GpuPowerLimit { default_watts: Some(default), .. }) => default.round() as u32,
+            Ok(_) => return Response::error("GPU reports no default power limit"),
+            Err(e) => return Response::error(format!("Failed to read power limit: {}", e)),
+        },
+    };
+    match hf_core::set_gpu_power_limit(vendor, index, watts) {
+        Ok(()) => get_gpu_power_limit(index),
+        Err(e) => Response::error(format!("Failed to set power limit: {}", e)),
+    }
+}
+
 async fn clear_pwm_override(
     path: &str,
     fan_control_state: &Arc<crate::fan_control::FanControlState>,
@@ -1235,11 +1939,17 @@ async fn detect_fan_mappings() -> Response {
         warn!("Failed to persist detected mappings to settings: {}", e);
     }
 
+    match resolve_fan_mappings(mappings) {
+        Ok(resolved) => Response::Ok(ResponseData::mappings(resolved)),
+        Err(e) => Response::error(e),
+    }
+}
+
+/// Resolve detected "chip/pwmN" -> "chip/fanN" mappings to sysfs paths and UUIDs
+pub(crate) fn resolve_fan_mappings(mappings: Vec<hf_core::FanMapping>) -> Result<Vec<hf_protocol::FanMapping>, String> {
     // Build lookup maps from chip/pwm names to sysfs paths.
-    let chips = match hf_core::enumerate_hwmon_chips() {
-        Ok(c) => c,
-        Err(e) => return Response::error(format!("Failed to enumerate hardware for mapping resolution: {}", e)),
-    };
+    let chips = hf_core::enumerate_hwmon_chips()
+        .map_err(|e| format!("Failed to enumerate hardware for mapping resolution: {}", e))?;
 
     // Build lookup maps: name -> (path, uuid)
     let mut pwm_name_to_info: std::collections::HashMap<String, (String, String)> = std::collections::HashMap::new();
@@ -1249,13 +1959,13 @@ async fn detect_fan_mappings() -> Response {
         for pwm in &chip.pwms {
             let key = format!("{}/{}", chip.name, pwm.name);
             let path = pwm.pwm_path.to_string_lossy().to_string();
-            let uuid = generate_sensor_uuid(&chip.name, &pwm.name, "pwm");
+            let uuid = hf_core::sensor_uuid(&chip.path, &chip.name, &pwm.name, hf_core::ChannelType::Pwm);
             pwm_name_to_info.insert(key, (path, uuid));
         }
         for fan in &chip.fans {
             let key = format!("{}/{}", chip.name, fan.name);
             let path = fan.input_path.to_string_lossy().to_string();
-            let uuid = generate_sensor_uuid(&chip.name, &fan.name, "fan");
+            let uuid = hf_core::sensor_uuid(&chip.path, &chip.name, &fan.name, hf_core::ChannelType::Fan);
             fan_name_to_info.insert(key, (path, uuid));
         }
     }
@@ -1280,7 +1990,7 @@ async fn detect_fan_mappings() -> Response {
         });
     }
 
-    Response::Ok(ResponseData::mappings(resolved))
+    Ok(resolved)
 }
 
 // ============================================================================
@@ -1297,8 +2007,23 @@ fn get_manual_pairings() -> Response {
     // We need to look up fan UUIDs from the saved pairings
     let mut pairings_by_pwm: std::collections::HashMap<String, (Option<String>, Option<String>, Option<String>)> =
         std::collections::HashMap::new();
+    let mut tuning_by_pwm: std::collections::HashMap<String, hf_protocol::CurveTuning> =
+        std::collections::HashMap::new();
+    let mut mode_by_pwm: std::collections::HashMap<String, hf_protocol::PairingControlMode> =
+        std::collections::HashMap::new();
+    let mut zero_rpm_pwms: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut enforcement_by_pwm: std::collections::HashMap<String, hf_protocol::PwmEnforcement> =
+        std::collections::HashMap::new();
     for p in &settings.pwm_fan_pairings {
         pairings_by_pwm.insert(p.pwm_path.clone(), (p.fan_path.clone(), p.fan_name.clone(), p.fan_uuid.clone()));
+        if let Some(tuning) = p.tuning {
+            tuning_by_pwm.insert(p.pwm_path.clone(), tuning);
+        }
+        mode_by_pwm.insert(p.pwm_path.clone(), p.control_mode);
+        if p.zero_rpm {
+            zero_rpm_pwms.insert(p.pwm_path.clone());
+        }
+        enforcement_by_pwm.insert(p.pwm_path.clone(), p.enforcement);
     }
 
     let chips = match hf_core::enumerate_hwmon_chips() {
@@ -1311,20 +2036,29 @@ fn get_manual_pairings() -> Response {
     for chip in &chips {
         for fan in &chip.fans {
             let path = fan.input_path.to_string_lossy().to_string();
-            let uuid = generate_sensor_uuid(&chip.name, &fan.name, "fan");
+            let uuid = hf_core::sensor_uuid(&chip.path, &chip.name, &fan.name, hf_core::ChannelType::Fan);
             fan_path_to_uuid.insert(path, uuid);
         }
     }
 
+    // GPU outputs that are also hwmon outputs are listed once, under the hwmon path
+    let gpus = hf_core::reconcile_gpu_pwms(chips.iter().map(|c| &c.path), hf_core::enumerate_gpu_pwm_controllers());
+
     let mut pairings: Vec<ManualPwmFanPairing> = Vec::new();
     for chip in &chips {
         for pwm in &chip.pwms {
             let pwm_path = pwm.pwm_path.to_string_lossy().to_string();
-            let pwm_name = format!("{} - {}", chip.name, pwm.name);
-            let pwm_uuid = generate_sensor_uuid(&chip.name, &pwm.name, "pwm");
+            let gpu = gpus.gpu_for(&pwm_path);
+            let pwm_name = match gpu {
+                Some(gpu) => format!("{} GPU - {}", gpu.vendor, gpu.name),
+                None => format!("{} - {}", chip.name, pwm.name),
+            };
+            let pwm_uuid = hf_core::sensor_uuid(&chip.path, &chip.name, &pwm.name, hf_core::ChannelType::Pwm);
             
+            // Pairings saved before the merge may use the GPU controller's path
             let (fan_path, fan_name, saved_fan_uuid) = pairings_by_pwm
                 .get(&pwm_path)
+                .or_else(|| gpu.and_then(|gpu| pairings_by_pwm.get(&gpu.pwm_path)))
                 .cloned()
                 .unwrap_or((None, None, None));
             
@@ -1333,6 +2067,11 @@ fn get_manual_pairings() -> Response {
                 fan_path.as_ref().and_then(|fp| fan_path_to_uuid.get(fp).cloned())
             });
             
+            let tuning = tuning_by_pwm.get(&pwm_path).copied();
+            let control_mode = mode_by_pwm.get(&pwm_path).copied().unwrap_or_default();
+            let zero_rpm = zero_rpm_pwms.contains(&pwm_path);
+            let enforcement = enforcement_by_pwm.get(&pwm_path).copied().unwrap_or_default();
+            
             pairings.push(ManualPwmFanPairing {
                 pwm_uuid,
                 pwm_path,
@@ -1340,15 +2079,18 @@ fn get_manual_pairings() -> Response {
                 fan_uuid,
                 fan_path,
                 fan_name,
+                tuning,
+                control_mode,
+                zero_rpm,
+                enforcement,
             });
         }
     }
 
-    for gpu in hf_core::enumerate_gpu_pwm_controllers() {
+    for gpu in gpus.controllers {
         let pwm_path = gpu.pwm_path.clone();
         let pwm_name = gpu.name.clone();
-        // Generate UUID for GPU PWM controls
-        let pwm_uuid = generate_sensor_uuid("gpu", &pwm_name, "pwm");
+        let pwm_uuid = hf_core::gpu_pwm_uuid(&gpu.id);
         
         let (fan_path, fan_name, saved_fan_uuid) = pairings_by_pwm
             .get(&pwm_path)
@@ -1359,6 +2101,11 @@ fn get_manual_pairings() -> Response {
             fan_path.as_ref().and_then(|fp| fan_path_to_uuid.get(fp).cloned())
         });
         
+        let tuning = tuning_by_pwm.get(&pwm_path).copied();
+        let control_mode = mode_by_pwm.get(&pwm_path).copied().unwrap_or_default();
+        let zero_rpm = zero_rpm_pwms.contains(&pwm_path);
+        let enforcement = enforcement_by_pwm.get(&pwm_path).copied().unwrap_or_default();
+        
         pairings.push(ManualPwmFanPairing {
             pwm_uuid,
             pwm_path,
@@ -1366,13 +2113,23 @@ fn get_manual_pairings() -> Response {
             fan_uuid,
             fan_path,
             fan_name,
+            tuning,
+            control_mode,
+            zero_rpm,
+            enforcement,
         });
     }
 
     Response::Ok(ResponseData::pairings(pairings))
 }
 
-fn set_manual_pairing(pwm_uuid: &str, pwm_path: &str, fan_uuid: Option<&str>, fan_path: Option<&str>) -> Response {
+fn set_manual_pairing(
+    pwm_uuid: &str,
+    pwm_path: &str,
+    fan_uuid: Option<&str>,
+    fan_path: Option<&str>,
+    tuning: Option<hf_protocol::CurveTuning>,
+) -> Response {
     // Validate PWM path
     if let Err(e) = validate_pwm_target_path(pwm_path) {
         return Response::error(format!("Invalid PWM path: {}", e));
@@ -1403,6 +2160,15 @@ fn set_manual_pairing(pwm_uuid: &str, pwm_path: &str, fan_uuid: Option<&str>, fa
     pairing.fan_uuid = fan_uuid.map(String::from);
     
     if let Err(e) = hf_core::update_setting(|s| {
+        // Tuning is optional in the request - keep the existing tuning if not given
+        let existing = s.pwm_fan_pairings.iter()
+            .find(|p| p.pwm_uuid.as_deref() == Some(pwm_uuid) || p.pwm_path == pwm_path);
+        pairing.tuning = tuning.or_else(|| existing.and_then(|p| p.tuning));
+        pairing.control_mode = existing.map(|p| p.control_mode).unwrap_or_default();
+        pairing.zero_rpm = existing.is_some_and(|p| p.zero_rpm);
+        pairing.enforcement = existing.map(|p| p.enforcement).unwrap_or_default();
+        pairing.noise = existing.and_then(|p| p.noise.clone());
+        
         // Remove by UUID first (primary key), then by path (fallback)
         s.pwm_fan_pairings.retain(|p| {
             p.pwm_uuid.as_deref() != Some(pwm_uuid) && p.pwm_path != pwm_path
@@ -1415,6 +2181,108 @@ fn set_manual_pairing(pwm_uuid: &str, pwm_path: &str, fan_uuid: Option<&str>, fa
     Response::ok()
 }
 
+fn get_binding_conflicts() -> Response {
+    let mut store = match hf_core::BindingStore::load() {
+        Ok(store) => store,
+        Err(e) => return Response::error(format!("Failed to load bindings: {}", e)),
+    };
+    Response::Ok(ResponseData::binding_conflicts(hf_core::find_binding_conflicts(&mut store)))
+}
+
+/// Rebind a fingerprinted binding and move its configuration to the new path
+fn accept_binding_match(pwm_id: &str, pwm_path: &str, fan_path: Option<&str>) -> Response {
+    let mut store = match hf_core::BindingStore::load() {
+        Ok(store) => store,
+        Err(e) => return Response::error(format!("Failed to load bindings: {}", e)),
+    };
+    let Some(old_path) = store.bindings.get(pwm_id)
+        .map(|b| b.pwm_fingerprint.channel.original_path.to_string_lossy().to_string())
+    else {
+        return Response::error(format!("Binding {} not found", pwm_id));
+    };
+
+    if let Err(e) = store.accept_match(pwm_id, std::path::Path::new(pwm_path), fan_path.map(std::path::Path::new)) {
+        return Response::error(e);
+    }
+    if let Err(e) = store.save() {
+        return Response::error(format!("Failed to save bindings: {}", e));
+    }
+
+    if let Err(e) = hf_core::update_setting(|s| {
+        if s.repoint_pwm(&old_path, pwm_path, fan_path) {
+            info!("Moved configuration of {} to {}", old_path, pwm_path);
+        }
+    }) {
+        return Response::error(format!("Failed to save settings: {}", e));
+    }
+
+    Response::ok()
+}
+
+fn set_pairing_control_mode(pwm_path: &str, mode: hf_protocol::PairingControlMode) -> Response {
+    let mut found = false;
+    let mut unpaired = false;
+    let update = hf_core::update_setting(|s| {
+        if let Some(pairing) = s.pwm_fan_pairings.iter_mut().find(|p| p.pwm_path == pwm_path) {
+            found = true;
+            unpaired = pairing.fan_path.is_none();
+            if !(unpaired && matches!(mode, hf_protocol::PairingControlMode::Rpm { .. })) {
+                pairing.control_mode = mode;
+            }
+        }
+    });
+
+    if let Err(e) = update {
+        return Response::error(format!("Failed to save: {}", e));
+    }
+    if !found {
+        return Response::error("No pairing found for this PWM");
+    }
+    if unpaired && matches!(mode, hf_protocol::PairingControlMode::Rpm { .. }) {
+        return Response::error("RPM control needs a paired fan tachometer");
+    }
+
+    Response::ok()
+}
+
+fn set_pairing_zero_rpm(pwm_path: &str, enabled: bool) -> Response {
+    let mut found = false;
+    let update = hf_core::update_setting(|s| {
+        if let Some(pairing) = s.pwm_fan_pairings.iter_mut().find(|p| p.pwm_path == pwm_path) {
+            found = true;
+            pairing.zero_rpm = enabled;
+        }
+    });
+
+    if let Err(e) = update {
+        return Response::error(format!("Failed to save: {}", e));
+    }
+    if !found {
+        return Response::error("No pairing found for this PWM");
+    }
+
+    Response::ok()
+}
+
+fn set_pairing_enforcement(pwm_path: &str, policy: hf_protocol::PwmEnforcement) -> Response {
+    let mut found = false;
+    let update = hf_core::update_setting(|s| {
+        if let Some(pairing) = s.pwm_fan_pairings.iter_mut().find(|p| p.pwm_path == pwm_path) {
+            found = true;
+            pairing.enforcement = policy;
+        }
+    });
+
+    if let Err(e) = update {
+        return Response::error(format!("Failed to save: {}", e));
+    }
+    if !found {
+        return Response::error("No pairing found for this PWM");
+    }
+
+    Response::ok()
+}
+
 fn delete_manual_pairing(pwm_path: &str) -> Response {
     let mut removed = false;
     let update = hf_core::update_setting(|s| {
@@ -1433,6 +2301,88 @@ fn delete_manual_pairing(pwm_path: &str) -> Response {
     Response::ok()
 }
 
+/// Add or replace a group; a PWM output can only belong to one group
+fn set_pwm_group(group: hf_protocol::PwmGroup) -> Response {
+    let mut conflict = None;
+    let update = hf_core::update_setting(|s| {
+        conflict = s.pwm_groups.iter()
+            .filter(|g| g.id != group.id)
+            .find_map(|g| {
+                group.members.iter()
+                    .find(|m| g.members.iter().any(|other| other.pwm_path == m.pwm_path))
+                    .map(|m| format!("{} already belongs to group '{}'", m.pwm_path, g.name))
+            });
+        if conflict.is_some() {
+            return;
+        }
+        match s.pwm_groups.iter_mut().find(|g| g.id == group.id) {
+            Some(existing) => *existing = group.clone(),
+            None => s.pwm_groups.push(group.clone()),
+        }
+    });
+
+    match (update, conflict) {
+        (Ok(_), Some(conflict)) => Response::error(conflict),
+        (Ok(settings), None) => Response::Ok(ResponseData::pwm_groups(settings.pwm_groups)),
+        (Err(e), _) => Response::error(format!("Failed to save: {}", e)),
+    }
+}
+
+fn delete_pwm_group(id: &str) -> Response {
+    let mut removed = false;
+    let update = hf_core::update_setting(|s| {
+        let before = s.pwm_groups.len();
+        s.pwm_groups.retain(|g| g.id != id);
+        removed = s.pwm_groups.len() != before;
+    });
+
+    match update {
+        Ok(_) if !removed => Response::error("No group found with this ID"),
+        Ok(settings) => Response::Ok(ResponseData::pwm_groups(settings.pwm_groups)),
+        Err(e) => Response::error(format!("Failed to save: {}", e)),
+    }
+}
+
+fn profile_info(settings: &hf_core::AppSettings) -> hf_protocol::ProfileInfo {
+    hf_protocol::ProfileInfo {
+        active: settings.active_profile.clone(),
+        available: settings.available_profiles(),
+    }
+}
+
+fn get_active_profile() -> Response {
+    match hf_core::load_settings() {
+        Ok(settings) => Response::Ok(ResponseData::profile(profile_info(&settings))),
+        Err(e) => Response::error(format!("Failed to load settings: {}", e)),
+    }
+}
+
+fn set_active_profile(name: &str) -> Response {
+    let mut known = false;
+    let mut previous = String::new();
+    let update = hf_core::update_setting(|s| {
+        known = s.available_profiles().iter().any(|p| p == name);
+        if known {
+            previous = std::mem::replace(&mut s.active_profile, name.to_string());
+        }
+    });
+
+    match update {
+        Ok(_) if !known => Response::error(format!("Unknown profile: {}", name)),
+        Ok(settings) => {
+            if previous != name {
+                crate::events::publish(hf_protocol::Event::ProfileSwitched {
+                    previous,
+                    name: name.to_string(),
+                    scheduled: false,
+                });
+            }
+            Response::Ok(ResponseData::profile(profile_info(&settings)))
+        }
+        Err(e) => Response::error(format!("Failed to save profile: {}", e)),
+    }
+}
+
 // ============================================================================
 // EC Direct Control Functions (DANGEROUS)
 // ============================================================================
@@ -1487,6 +2437,7 @@ fn list_ec_chips() -> Response {
                         chip_class,
                         register_count: Some(256), // Most EC chips have 256 registers
                         supports_direct_access: true,
+                        profile: crate::ec_profiles::detected_profile(),
                     }
                 })
                 .collect();
@@ -1556,7 +2507,7 @@ fn write_ec_register(chip_path: &str, register: u8, value: u8) -> Response {
     }
     
     // Check if EC control is enabled in settings
-    match hf_core::load_settings() {
+    let settings = match hf_core::load_settings() {
         Ok(settings) => {
             if !settings.advanced.ec_direct_control_enabled {
                 return Response::error("EC direct control is not enabled in settings");
@@ -1564,23 +2515,59 @@ fn write_ec_register(chip_path: &str, register: u8, value: u8) -> Response {
             if !settings.advanced.ec_danger_acknowledged {
                 return Response::error("EC danger warning has not been acknowledged");
             }
+            settings
         }
         Err(e) => {
             return Response::error(format!("Failed to load settings: {}", e));
         }
-    }
+    };
     
     let chip_dir = std::path::Path::new(chip_path);
+
+    // Stage the write: remember the original value and the sensor readings
+    let staged = if settings.advanced.ec_write_guard && !settings.advanced.ec_dry_run {
+        let original = match read_ec_register_value(chip_dir, register) {
+            Ok(original) => original,
+            Err(e) => {
+                return Response::error(format!(
+                    "Write guard could not read the original value of register 0x{:02X}: {}", register, e
+                ));
+            }
+        };
+        Some((original, crate::ec_guard::Readings::capture()))
+    } else {
+        None
+    };
     
     match write_ec_register_value(chip_dir, register, value) {
         Ok(()) => {
             info!("EC register 0x{:02X} written with value 0x{:02X}", register, value);
+            if let Some((original, baseline)) = staged {
+                crate::ec_guard::watch(chip_path.to_string(), register, original, baseline);
+            }
             Response::ok()
         }
         Err(e) => Response::error(format!("Failed to write register 0x{:02X}: {}", register, e)),
     }
 }
 
+/// Drive a fan through the model's known EC layout
+/// Needs EC control enabled, but not the danger acknowledgement for raw writes
+fn set_ec_fan_duty(chip_path: &str, fan: u8, percent: Option<u32>) -> Response {
+    match hf_core::load_settings() {
+        Ok(settings) if !settings.advanced.ec_direct_control_enabled => {
+            return Response::error("EC direct control is not enabled in settings");
+        }
+        Ok(_) => {}
+        Err(e) => return Response::error(format!("Failed to load settings: {}", e)),
+    }
+
+    match crate::ec_profiles::set_fan_duty(chip_path, fan, percent) {
+        Ok(()) => Response::ok(),
+        Err(e) => Response::error(format!("Failed to set EC fan {}: {}", fan, e)),
+    }
+}
+
 /// Read a range of EC registers
 fn read_ec_register_range(chip_path: &str, start: u8, count: u8) -> Response {
     // Validate path
@@ -1620,6 +2607,53 @@ fn read_ec_register_range(chip_path: &str, start: u8, count: u8) -> Response {
     Response::Ok(ResponseData::registers(registers))
 }
 
+/// Sample a range of EC registers and report the ones that changed
+/// Unreadable registers are left out
+async fn watch_ec_registers(chip_path: String, start: u8, count: u8, interval_ms: u32, samples: u32) -> Response {
+    if let Err(e) = validate_hwmon_path(&chip_path) {
+        return Response::error(format!("Invalid chip path: {}", e));
+    }
+    let count = count.min(64);
+
+    let sampled = tokio::task::spawn_blocking(move || {
+        let chip_dir = std::path::Path::new(&chip_path);
+        let mut series: Vec<Option<Vec<u8>>> = vec![Some(Vec::with_capacity(samples as usize)); count as usize];
+        for sample in 0..samples {
+            if sample > 0 {
+                std::thread::sleep(std::time::Duration::from_millis(interval_ms as u64));
+            }
+            for (i, values) in series.iter_mut().enumerate() {
+                let Some(list) = values else { continue };
+                match read_ec_register_value(chip_dir, start.wrapping_add(i as u8)) {
+                    Ok(value) => list.push(value),
+                    Err(_) => *values = None,
+                }
+            }
+        }
+        series
+    })
+    .await;
+
+    let series = match sampled {
+        Ok(series) => series,
+        Err(e) => return Response::error(format!("Task panicked: {}", e)),
+    };
+    let changes = series
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, values)| {
+            let values = values?;
+            let register = start.wrapping_add(i as u8);
+            values.windows(2).any(|w| w[0] != w[1]).then(|| EcRegisterChange {
+                register,
+                label: get_register_label(register),
+                values,
+            })
+        })
+        .collect();
+    Response::Ok(ResponseData::ec_changes(changes))
+}
+
 /// Read EC register value using available methods
 fn read_ec_register_value(chip_dir: &std::path::Path, register: u8) -> Result<u8, String> {
     // Method 1: Try device/ec_read interface (if available)
@@ -1654,7 +2688,13 @@ fn read_ec_register_value(chip_dir: &std::path::Path, register: u8) -> Result<u8
 }
 
 /// Write EC register value
-fn write_ec_register_value(chip_dir: &std::path::Path, register: u8, value: u8) -> Result<(), String> {
+/// Only logged when `advanced.ec_dry_run` is set
+pub(crate) fn write_ec_register_value(chip_dir: &std::path::Path, register: u8, value: u8) -> Result<(), String> {
+    if hf_core::load_settings().is_ok_and(|s| s.advanced.ec_dry_run) {
+        info!("EC DRY RUN: Would write 0x{:02X} to register 0x{:02X} on {}", value, register, chip_dir.display());
+        return Ok(());
+    }
+
     // Method 1: Try device/ec_write interface
     let ec_write_path = chip_dir.join("device/ec_write");
     if ec_write_path.exists() {
@@ -0,0 +1,96 @@
+//! Temperature Source Failover
+//!
+//! A curve can list fallback sensors (`PersistedCurve::fallback_sources`). A
+//! pair or group following it reads its own source first; when that read
+//! fails or looks bogus, the next source in the chain is used instead. Bogus
+//! means non-finite, at or below absolute zero, exactly 0°C (what several
+//! drivers report for a channel that vanished after a reload), or the same
+//! value for `FROZEN_TICKS` iterations in a row.
+//!
+//! The first healthy source wins every iteration, so the primary takes over
+//! again once it recovers. Each switch is logged and published as a
+//! `TemperatureSourceSwitched` event. Chains without fallbacks skip these
+//! checks: a failing source still drives its fans to the fallback speed.
+
+use std::collections::HashMap;
+
+/// Identical readings in a row before a source counts as frozen
+/// (5 minutes at the default 1s poll interval)
+pub const FROZEN_TICKS: u32 = 300;
+
+/// Readings at or below this are a driver error value, not a temperature
+const ABSOLUTE_ZERO_CELSIUS: f32 = -273.0;
+
+/// Health of the sources of every fallback chain
+#[derive(Debug, Default)]
+pub struct SourceHealth {
+    /// Last value of each source and how many readings in a row returned it
+    repeats: HashMap<String, (f32, u32)>,
+    /// Index of the source each chain currently uses
+    active: HashMap<String, usize>,
+}
+
+impl SourceHealth {
+    /// Check a reading of `path`; call once per source and iteration
+    pub fn check(&mut self, path: &str, reading: Result<f32, String>) -> Result<f32, String> {
+        let t = reading?;
+        if !t.is_finite() || t <= ABSOLUTE_ZERO_CELSIUS {
+            return Err(format!("bogus reading {}", t));
+        }
+        if t == 0.0 {
+            return Err("reads 0°C".to_string());
+        }
+        let repeats = self.repeats.entry(path.to_string()).or_insert((t, 0));
+        if repeats.0 == t {
+            repeats.1 += 1;
+        } else {
+            *repeats = (t, 1);
+        }
+        if repeats.1 >= FROZEN_TICKS {
+            return Err(format!("frozen at {:.1}°C for {} readings", t, repeats.1));
+        }
+        Ok(t)
+    }
+
+    /// Record that `chain` now reads its source at `index`
+    /// Returns the index it used before when that changed
+    pub fn select(&mut self, chain: &str, index: usize) -> Option<usize> {
+        let previous = self.active.insert(chain.to_string(), index).unwrap_or(0);
+        (previous != index).then_some(previous)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bogus_readings() {
+        let mut health = SourceHealth::default();
+        assert_eq!(health.check("a", Ok(42.0)), Ok(42.0));
+        assert!(health.check("a", Ok(0.0)).is_err());
+        assert!(health.check("a", Ok(-273.15)).is_err());
+        assert!(health.check("a", Ok(f32::NAN)).is_err());
+        assert!(health.check("a", Err("gone".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_frozen_source() {
+        let mut health = SourceHealth::default();
+        for _ in 1..FROZEN_TICKS {
+            assert!(health.check("a", Ok(45.0)).is_ok());
+        }
+        assert!(health.check("a", Ok(45.0)).is_err());
+        // Any change thaws it
+        assert!(health.check("a", Ok(46.0)).is_ok());
+    }
+
+    #[test]
+    fn test_select_reports_switches() {
+        let mut health = SourceHealth::default();
+        assert_eq!(health.select("chain", 0), None);
+        assert_eq!(health.select("chain", 1), Some(0));
+        assert_eq!(health.select("chain", 1), None);
+        assert_eq!(health.select("chain", 0), Some(1));
+    }
+}
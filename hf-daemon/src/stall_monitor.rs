@@ -0,0 +1,281 @@
+//! Fan Stall Detection
+//!
+//! Watches the tachometer of every curve-controlled fan. A fan is flagged as
+//! failed when its commanded PWM is above the learned minimum but it keeps
+//! reporting 0 RPM for longer than the stall timeout.
+//!
+//! PWM outputs whose writes do not read back as written (see
+//! `hf_core::set_pwm_value`) are flagged the same way, since their fan runs
+//! at whatever duty the chip kept.
+//!
+//! While a fan is flagged, the control loop drives the other fans of the same
+//! pair to full speed to compensate. Every state change is published as a
+//! `FanFailure` event and queued as a [`FanAlert`] for `GetFanAlerts`.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+use hf_protocol::{FanAlert, FanAlertKind};
+
+/// Minimum PWM assumed to spin a fan when detection has not learned one (~25%)
+pub const DEFAULT_MIN_PWM: u8 = 64;
+
+/// Maximum alerts retained for polling clients (keeps responses under MAX_MESSAGE_SIZE)
+const MAX_RETAINED_ALERTS: usize = 16;
+
+/// Tachometer attached to a controlled PWM output
+#[derive(Clone, Debug)]
+pub struct StallWatch {
+    /// Display name of the control pair
+    pub name: String,
+    /// Fan RPM input path (fanN_input)
+    pub fan_path: String,
+    /// Pair ID shared by all fans driven by the same curve
+    pub group_id: String,
+    /// PWM at or below which 0 RPM is expected rather than a failure
+    pub min_pwm: u8,
+}
+
+/// Per-fan stall tracking state
+#[derive(Clone, Copy, Debug, Default)]
+struct StallTracker {
+    /// When the fan first read 0 RPM while commanded above min_pwm
+    zero_since: Option<Instant>,
+    /// Whether the fan is currently flagged as failed
+    failed: bool,
+}
+
+/// Stall monitor shared between the control loop and the IPC server
+pub struct StallMonitor {
+    /// Watched fans (pwm_path -> watch)
+    watches: HashMap<String, StallWatch>,
+    /// Tracking state (pwm_path -> tracker)
+    trackers: HashMap<String, StallTracker>,
+    /// Outputs whose last write did not read back as written
+    unverified: HashSet<String>,
+    /// Recent alerts, oldest first
+    alerts: VecDeque<FanAlert>,
+    /// ID assigned to the next alert
+    next_alert_id: u64,
+    /// How long a fan may read 0 RPM before it is flagged (zero disables detection)
+    timeout: Duration,
+}
+
+impl StallMonitor {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            watches: HashMap::new(),
+            trackers: HashMap::new(),
+            unverified: HashSet::new(),
+            alerts: VecDeque::new(),
+            next_alert_id: 1,
+            timeout,
+        }
+    }
+
+    /// Replace the watched fans after a config reload
+    /// Tracking state is kept for fans that are still watched
+    pub fn configure(&mut self, watches: HashMap<String, StallWatch>, timeout: Duration) {
+        self.trackers.retain(|pwm_path, _| watches.contains_key(pwm_path));
+        self.watches = watches;
+        self.timeout = timeout;
+    }
+
+    /// Get the watch for a PWM path, if its fan has a tachometer
+    pub fn watch(&self, pwm_path: &str) -> Option<&StallWatch> {
+        self.watches.get(pwm_path)
+    }
+
+    /// Whether detection is enabled
+    pub fn is_enabled(&self) -> bool {
+        !self.timeout.is_zero()
+    }
+
+    /// Whether the fan on this PWM is currently flagged as failed (stalled or unwritable)
+    pub fn is_failed(&self, pwm_path: &str) -> bool {
+        self.trackers.get(pwm_path).is_some_and(|t| t.failed) || self.unverified.contains(pwm_path)
+    }
+
+    /// Whether another fan in this PWM's pair has failed (this fan should compensate)
+    pub fn needs_compensation(&self, pwm_path: &str) -> bool {
+        if self.is_failed(pwm_path) {
+            return false;
+        }
+        let Some(watch) = self.watches.get(pwm_path) else {
+            return false;
+        };
+        self.failed_groups().contains(watch.group_id.as_str())
+    }
+
+    /// Pair IDs that currently contain a failed fan
+    fn failed_groups(&self) -> HashSet<&str> {
+        self.watches
+            .iter()
+            .filter(|(path, _)| self.is_failed(path))
+            .map(|(_, w)| w.group_id.as_str())
+            .collect()
+    }
+
+    /// Record the outcome of a PWM write (`verified`: it read back as written)
+    /// Returns the alert kind if the output changed state
+    pub fn record_write(&mut self, pwm_path: &str, name: &str, pwm: u8, verified: bool) -> Option<FanAlertKind> {
+        if verified != self.unverified.contains(pwm_path) {
+            return None;
+        }
+        let kind = if verified {
+            self.unverified.remove(pwm_path);
+            info!("WRITE: '{}' ({}) accepts PWM writes again", name, pwm_path);
+            FanAlertKind::WriteRecovered
+        } else {
+            self.unverified.insert(pwm_path.to_string());
+            error!("WRITE: '{}' ({}) does not keep PWM {} - flagging as failed", name, pwm_path, pwm);
+            FanAlertKind::WriteFailed
+        };
+        let fan_path = self.watches.get(pwm_path).map(|w| w.fan_path.clone()).unwrap_or_default();
+        self.push_alert(kind, pwm_path, name, &fan_path, pwm);
+        Some(kind)
+    }
+
+    /// Record a tachometer reading taken after `pwm` was commanded
+    /// Returns the alert kind if the fan changed state
+    pub fn observe(&mut self, pwm_path: &str, pwm: u8, rpm: u32, now: Instant) -> Option<FanAlertKind> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let watch = self.watches.get(pwm_path)?.clone();
+        let tracker = self.trackers.entry(pwm_path.to_string()).or_default();
+
+        if rpm > 0 {
+            tracker.zero_since = None;
+            if tracker.failed {
+                tracker.failed = false;
+                info!("STALL: '{}' ({}) is spinning again at {} RPM", watch.name, watch.fan_path, rpm);
+                self.push_alert(FanAlertKind::Recovered, pwm_path, &watch.name, &watch.fan_path, pwm);
+                return Some(FanAlertKind::Recovered);
+            }
+            return None;
+        }
+
+        // 0 RPM at or below the minimum PWM is expected (fan stop / zero-RPM mode)
+        if pwm <= watch.min_pwm {
+            tracker.zero_since = None;
+            return None;
+        }
+
+        let since = *tracker.zero_since.get_or_insert(now);
+        if !tracker.failed && now.duration_since(since) >= self.timeout {
+            tracker.failed = true;
+            error!(
+                "STALL: '{}' ({}) reports 0 RPM at PWM {} for {}s - flagging as failed",
+                watch.name, watch.fan_path, pwm, self.timeout.as_secs()
+            );
+            self.push_alert(FanAlertKind::Stalled, pwm_path, &watch.name, &watch.fan_path, pwm);
+            return Some(FanAlertKind::Stalled);
+        }
+
+        None
+    }
+
+    /// Alerts newer than `since_id`, oldest first
+    pub fn alerts_since(&self, since_id: u64) -> Vec<FanAlert> {
+        self.alerts.iter().filter(|a| a.id > since_id).cloned().collect()
+    }
+
+    fn push_alert(&mut self, kind: FanAlertKind, pwm_path: &str, name: &str, fan_path: &str, pwm: u8) {
+        let timestamp_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let alert = FanAlert {
+            id: self.next_alert_id,
+            kind,
+            name: name.to_string(),
+            pwm_path: pwm_path.to_string(),
+            fan_path: fan_path.to_string(),
+            pwm,
+            timestamp_ms,
+        };
+        crate::events::publish(hf_protocol::Event::FanFailure(alert.clone()));
+        self.alerts.push_back(alert);
+        self.next_alert_id += 1;
+
+        while self.alerts.len() > MAX_RETAINED_ALERTS {
+            self.alerts.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor() -> StallMonitor {
+        let mut watches = HashMap::new();
+        for (pwm, fan) in [("pwm1", "fan1_input"), ("pwm2", "fan2_input")] {
+            watches.insert(pwm.to_string(), StallWatch {
+                name: format!("Case {}", pwm),
+                fan_path: fan.to_string(),
+                group_id: "case".to_string(),
+                min_pwm: 50,
+            });
+        }
+        let mut monitor = StallMonitor::new(Duration::from_secs(5));
+        monitor.configure(watches, Duration::from_secs(5));
+        monitor
+    }
+
+    #[test]
+    fn test_stall_flagged_after_timeout() {
+        let mut monitor = monitor();
+        let start = Instant::now();
+
+        assert_eq!(monitor.observe("pwm1", 200, 0, start), None);
+        assert_eq!(monitor.observe("pwm1", 200, 0, start + Duration::from_secs(4)), None);
+        assert_eq!(
+            monitor.observe("pwm1", 200, 0, start + Duration::from_secs(5)),
+            Some(FanAlertKind::Stalled)
+        );
+
+        assert!(monitor.is_failed("pwm1"));
+        assert!(monitor.needs_compensation("pwm2"));
+        assert!(!monitor.needs_compensation("pwm1"));
+
+        assert_eq!(
+            monitor.observe("pwm1", 200, 900, start + Duration::from_secs(6)),
+            Some(FanAlertKind::Recovered)
+        );
+        assert!(!monitor.needs_compensation("pwm2"));
+
+        let alerts = monitor.alerts_since(0);
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(monitor.alerts_since(alerts[0].id).len(), 1);
+    }
+
+    #[test]
+    fn test_unverified_write_flags_output() {
+        let mut monitor = monitor();
+
+        assert_eq!(monitor.record_write("pwm1", "Case pwm1", 200, true), None);
+        assert_eq!(monitor.record_write("pwm1", "Case pwm1", 200, false), Some(FanAlertKind::WriteFailed));
+        assert_eq!(monitor.record_write("pwm1", "Case pwm1", 200, false), None);
+        assert!(monitor.is_failed("pwm1"));
+        assert!(monitor.needs_compensation("pwm2"));
+
+        assert_eq!(monitor.record_write("pwm1", "Case pwm1", 180, true), Some(FanAlertKind::WriteRecovered));
+        assert!(!monitor.needs_compensation("pwm2"));
+        assert_eq!(monitor.alerts_since(0).len(), 2);
+    }
+
+    #[test]
+    fn test_zero_rpm_below_min_pwm_is_not_a_stall() {
+        let mut monitor = monitor();
+        let start = Instant::now();
+
+        monitor.observe("pwm1", 40, 0, start);
+        assert_eq!(monitor.observe("pwm1", 40, 0, start + Duration::from_secs(60)), None);
+        assert!(!monitor.is_failed("pwm1"));
+        assert!(monitor.alerts_since(0).is_empty());
+    }
+}
@@ -82,6 +82,15 @@ pub enum HyperfanError {
         reason: String,
     },
 
+    #[error("PWM write to {path} did not take effect after {attempts} attempts: wrote {written}, read back {}",
+        .read.map_or_else(|| "nothing".to_string(), |v| v.to_string()))]
+    PwmWriteVerifyFailed {
+        path: PathBuf,
+        written: u8,
+        read: Option<u8>,
+        attempts: u32,
+    },
+
     #[error("Hardware not found: {0}")]
     HardwareNotFound(String),
 
@@ -184,6 +193,9 @@ pub enum HyperfanError {
     #[error("Operation timed out: {0}")]
     Timeout(String),
 
+    #[error("Operation cancelled: {0}")]
+    Cancelled(String),
+
     #[error("Permission denied: {0}")]
     PermissionDenied(String),
 }
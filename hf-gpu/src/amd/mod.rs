@@ -1,9 +1,14 @@
 //! AMD GPU detection and control
 //!
 //! Detection via sysfs (amdgpu driver), fan control via PWM files
-//! Requires write access to `/sys/class/drm/card*/device/hwmon/*/pwm1`
+//! Requires write access to `/sys/class/drm/card*/device/hwmon/*/pwmN`
+//! (one channel per fan on dual-fan and some MBA cards, each with its own `pwmN_enable`)
+//! Firmware fan curve (PMFW) via `gpu_od/fan_ctrl/fan_curve` on RDNA3+
 
-use crate::{gpu_const, GpuDevice, GpuFan, GpuPwmController, GpuTemperature, GpuVendor, Result};
+use crate::{
+    gpu_const, GpuDevice, GpuFan, GpuFanBackend, GpuFanCurve, GpuFanCurvePoint, GpuPowerLimit, GpuPwmController,
+    GpuTemperature, GpuVendor, Result,
+};
 use hf_error::HyperfanError;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -89,8 +94,8 @@ pub fn enumerate_pwm_controllers() -> Result<Vec<GpuPwmController>> {
             continue;
         };
         
-        let pwm_path = hwmon_path.join("pwm1");
-        if !pwm_path.exists() {
+        let channels = pwm_channels(&hwmon_path);
+        if channels.is_empty() {
             debug!("AMD GPU at {:?} has no PWM control", card_path);
             continue;
         }
@@ -105,32 +110,42 @@ pub fn enumerate_pwm_controllers() -> Result<Vec<GpuPwmController>> {
             }
         };
         
-        let current_pwm = fs::read_to_string(&pwm_path).ok().and_then(|s| s.trim().parse::<u8>().ok());
-        let current_percent = current_pwm.map(|v| gpu_const::pwm::to_percent(v).round() as u32);
-        
-        let fan_input_path = hwmon_path.join("fan1_input");
-        let current_rpm = if fan_input_path.exists() {
-            fs::read_to_string(&fan_input_path).ok().and_then(|s| s.trim().parse::<u32>().ok())
-        } else {
-            None
-        };
-        
-        let pwm_enable = fs::read_to_string(hwmon_path.join("pwm1_enable")).ok().and_then(|s| s.trim().parse::<u8>().ok());
-        let manual_control = pwm_enable == Some(1);
-        
-        controllers.push(GpuPwmController {
-            id: format!("amd:{}:0", card_num),
-            name: format!("{} Fan", gpu_name),
-            vendor: GpuVendor::Amd,
-            gpu_index: card_num,
-            fan_index: 0,
-            pwm_path: pwm_path.to_string_lossy().to_string(),
-            fan_input_path: if fan_input_path.exists() { Some(fan_input_path.to_string_lossy().to_string()) } else { None },
-            current_percent,
-            current_rpm,
-            manual_control,
-            pci_bus_id: pci_bus_id.clone(),
-        });
+        // One controller per pwmN; dual-fan cards pair pwmN with fanN_input
+        for &channel in &channels {
+            let pwm_path = hwmon_path.join(format!("pwm{}", channel));
+            let current_pwm = fs::read_to_string(&pwm_path).ok().and_then(|s| s.trim().parse::<u8>().ok());
+            let current_percent = current_pwm.map(|v| gpu_const::pwm::to_percent(v).round() as u32);
+            
+            let fan_input_path = hwmon_path.join(format!("fan{}_input", channel));
+            let current_rpm = if fan_input_path.exists() {
+                fs::read_to_string(&fan_input_path).ok().and_then(|s| s.trim().parse::<u32>().ok())
+            } else {
+                None
+            };
+            
+            let pwm_enable = fs::read_to_string(enable_path_for(&pwm_path)).ok().and_then(|s| s.trim().parse::<u8>().ok());
+            let manual_control = pwm_enable == Some(1);
+            
+            let fan_index = channel - 1;
+            controllers.push(GpuPwmController {
+                id: format!("amd:{}:{}", card_num, fan_index),
+                name: if channels.len() > 1 {
+                    format!("{} Fan {}", gpu_name, channel)
+                } else {
+                    format!("{} Fan", gpu_name)
+                },
+                vendor: GpuVendor::Amd,
+                gpu_index: card_num,
+                fan_index,
+                pwm_path: pwm_path.to_string_lossy().to_string(),
+                fan_input_path: if fan_input_path.exists() { Some(fan_input_path.to_string_lossy().to_string()) } else { None },
+                current_percent,
+                current_rpm,
+                manual_control,
+                pci_bus_id: pci_bus_id.clone(),
+                backend: GpuFanBackend::Sysfs,
+            });
+        }
     }
     
     Ok(controllers)
@@ -140,9 +155,7 @@ pub fn set_fan_speed(hwmon_pwm_path: &str, percent: u32) -> Result<()> {
     let percent = percent.min(100);
     let pwm_path = Path::new(hwmon_pwm_path);
 
-    let pwm_enable_path = pwm_path.parent()
-        .ok_or_else(|| HyperfanError::GpuError("Invalid PWM path".to_string()))?
-        .join("pwm1_enable");
+    let pwm_enable_path = enable_path_for(pwm_path);
         
     if pwm_enable_path.exists() {
         fs::write(&pwm_enable_path, "1")
@@ -158,10 +171,7 @@ pub fn set_fan_speed(hwmon_pwm_path: &str, percent: u32) -> Result<()> {
 }
 
 pub fn reset_fan_auto(hwmon_pwm_path: &str) -> Result<()> {
-    let pwm_path = Path::new(hwmon_pwm_path);
-    let pwm_enable_path = pwm_path.parent()
-        .ok_or_else(|| HyperfanError::GpuError("Invalid PWM path".to_string()))?
-        .join("pwm1_enable");
+    let pwm_enable_path = enable_path_for(Path::new(hwmon_pwm_path));
         
     if pwm_enable_path.exists() {
         fs::write(&pwm_enable_path, "2")
@@ -171,6 +181,201 @@ pub fn reset_fan_auto(hwmon_pwm_path: &str) -> Result<()> {
     Ok(())
 }
 
+// ============================================================================
+// Firmware Fan Curve (PMFW)
+// ============================================================================
+//
+// RDNA3+ cards expose the PMFW fan curve at `device/gpu_od/fan_ctrl/fan_curve`:
+//
+//   OD_FAN_CURVE:
+//   0: 0C 0%
+//   ...
+//   OD_RANGE:
+//   FAN_CURVE(hotspot temp): 25C 100C
+//   FAN_CURVE(fan speed): 15% 100%
+//
+// Points are written as "<index> <temp> <speed>", then committed with "c";
+// "r" restores the firmware default. The curve only applies in automatic
+// mode (pwm1_enable = 2).
+
+/// Path of the firmware fan curve file for DRM card `card_index`, if supported
+pub fn fan_curve_path(card_index: u32) -> Option<PathBuf> {
+    let device_path = Path::new(gpu_const::DRM_PATH).join(format!("card{}", card_index)).join("device");
+    if !is_amd_gpu(&device_path) {
+        return None;
+    }
+    let path = device_path.join("gpu_od/fan_ctrl/fan_curve");
+    path.exists().then_some(path)
+}
+
+/// Read the firmware fan curve of DRM card `card_index`
+pub fn read_fan_curve(card_index: u32) -> Result<GpuFanCurve> {
+    let path = fan_curve_path(card_index)
+        .ok_or_else(|| HyperfanError::HardwareNotFound(format!("AMD GPU card{} has no firmware fan curve", card_index)))?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| HyperfanError::GpuError(format!("Failed to read firmware fan curve: {}", e)))?;
+    Ok(parse_fan_curve(&content))
+}
+
+/// Replace the firmware fan curve of DRM card `card_index`
+/// Points are sorted by temperature; if fewer points than firmware slots are
+/// given, the last point fills the remaining slots
+pub fn set_fan_curve(card_index: u32, points: &[GpuFanCurvePoint]) -> Result<()> {
+    let path = fan_curve_path(card_index)
+        .ok_or_else(|| HyperfanError::HardwareNotFound(format!("AMD GPU card{} has no firmware fan curve", card_index)))?;
+    let current = read_fan_curve(card_index)?;
+    let slots = current.points.len();
+
+    if points.is_empty() || points.len() > slots {
+        return Err(HyperfanError::InvalidConfig {
+            field: "points".to_string(),
+            reason: format!("Firmware fan curve needs 1-{} points, got {}", slots, points.len()),
+        });
+    }
+
+    let mut sorted = points.to_vec();
+    sorted.sort_by_key(|p| p.temp_c);
+    for point in &sorted {
+        if let Some((min, max)) = current.temp_range {
+            if point.temp_c < min || point.temp_c > max {
+                return Err(HyperfanError::InvalidConfig {
+                    field: "temp_c".to_string(),
+                    reason: format!("{}°C is outside the firmware range {}-{}°C", point.temp_c, min, max),
+                });
+            }
+        }
+        if let Some((min, max)) = current.speed_range {
+            if point.speed_percent < min || point.speed_percent > max {
+                return Err(HyperfanError::InvalidConfig {
+                    field: "speed_percent".to_string(),
+                    reason: format!("{}% is outside the firmware range {}-{}%", point.speed_percent, min, max),
+                });
+            }
+        }
+    }
+
+    // Firmware curve is ignored while the fan is under manual PWM control
+    enable_auto_mode(&path)?;
+
+    let last = *sorted.last().expect("points checked non-empty");
+    for slot in 0..slots {
+        let point = sorted.get(slot).copied().unwrap_or(last);
+        fs::write(&path, format!("{} {} {}\n", slot, point.temp_c, point.speed_percent))
+            .map_err(|e| HyperfanError::GpuError(format!("Failed to write fan curve point {}: {}", slot, e)))?;
+    }
+    fs::write(&path, "c\n")
+        .map_err(|e| HyperfanError::GpuError(format!("Failed to commit firmware fan curve: {}", e)))?;
+
+    info!("Set AMD GPU card{} firmware fan curve ({} points)", card_index, sorted.len());
+    Ok(())
+}
+
+/// Restore the factory firmware fan curve of DRM card `card_index`
+pub fn reset_fan_curve(card_index: u32) -> Result<()> {
+    let path = fan_curve_path(card_index)
+        .ok_or_else(|| HyperfanError::HardwareNotFound(format!("AMD GPU card{} has no firmware fan curve", card_index)))?;
+    fs::write(&path, "r\n")
+        .map_err(|e| HyperfanError::GpuError(format!("Failed to reset firmware fan curve: {}", e)))?;
+    info!("Reset AMD GPU card{} firmware fan curve to default", card_index);
+    Ok(())
+}
+
+/// Switch every hwmon PWM of the card owning `fan_curve` to automatic mode
+fn enable_auto_mode(fan_curve: &Path) -> Result<()> {
+    let Some(device_path) = fan_curve.ancestors().nth(3) else {
+        return Ok(());
+    };
+    let Some(hwmon_path) = find_hwmon(device_path) else {
+        return Ok(());
+    };
+    for fan in 1..=gpu_const::MAX_FANS_PER_GPU {
+        let enable_path = hwmon_path.join(format!("pwm{}_enable", fan));
+        if enable_path.exists() {
+            fs::write(&enable_path, "2")
+                .map_err(|e| HyperfanError::GpuError(format!("Failed to enable automatic fan control: {}", e)))?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_fan_curve(content: &str) -> GpuFanCurve {
+    // "25C 100C" / "15% 100%"
+    fn parse_range(values: &str, unit: char) -> Option<(u32, u32)> {
+        let mut parts = values.split_whitespace().map(|v| v.trim_end_matches(unit).parse::<u32>());
+        match (parts.next(), parts.next()) {
+            (Some(Ok(min)), Some(Ok(max))) => Some((min, max)),
+            _ => None,
+        }
+    }
+
+    let mut curve = GpuFanCurve { points: Vec::new(), temp_range: None, speed_range: None };
+    for line in content.lines().map(str::trim) {
+        if let Some((_, values)) = line.split_once("temp):") {
+            curve.temp_range = parse_range(values, 'C');
+        } else if let Some((_, values)) = line.split_once("(fan speed):") {
+            curve.speed_range = parse_range(values, '%');
+        } else if let Some((index, values)) = line.split_once(':') {
+            if index.parse::<u32>().is_err() {
+                continue;
+            }
+            let mut parts = values.split_whitespace();
+            let temp = parts.next().and_then(|t| t.trim_end_matches('C').parse().ok());
+            let speed = parts.next().and_then(|s| s.trim_end_matches('%').parse().ok());
+            if let (Some(temp_c), Some(speed_percent)) = (temp, speed) {
+                curve.points.push(GpuFanCurvePoint { temp_c, speed_percent });
+            }
+        }
+    }
+    curve
+}
+
+// ============================================================================
+// Power Limit
+// ============================================================================
+//
+// The board power cap is `power1_cap` (microwatts) in the card's hwmon
+// directory, bounded by `power1_cap_min`/`power1_cap_max`; `power1_cap_default`
+// is the factory value. Writes last until the driver is reloaded.
+
+/// hwmon directory of DRM card `card_index`, if it is an AMD GPU
+fn card_hwmon(card_index: u32) -> Option<PathBuf> {
+    let device_path = Path::new(gpu_const::DRM_PATH).join(format!("card{}", card_index)).join("device");
+    if !is_amd_gpu(&device_path) {
+        return None;
+    }
+    find_hwmon(&device_path)
+}
+
+/// Read the power cap of DRM card `card_index` and the range it accepts
+pub fn read_power_limit(card_index: u32) -> Result<GpuPowerLimit> {
+    let hwmon_path = card_hwmon(card_index)
+        .ok_or_else(|| HyperfanError::HardwareNotFound(format!("AMD GPU card{} not found", card_index)))?;
+    let read_watts = |file: &str| {
+        fs::read_to_string(hwmon_path.join(file)).ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(|microwatts| microwatts as f32 / gpu_const::MICROWATTS_PER_WATT)
+    };
+    let current_watts = read_watts("power1_cap")
+        .ok_or_else(|| HyperfanError::NotSupported(format!("AMD GPU card{} has no adjustable power cap", card_index)))?;
+    Ok(GpuPowerLimit {
+        current_watts,
+        default_watts: read_watts("power1_cap_default"),
+        min_watts: read_watts("power1_cap_min"),
+        max_watts: read_watts("power1_cap_max"),
+    })
+}
+
+/// Set the power cap of DRM card `card_index` to `watts`
+pub fn set_power_limit(card_index: u32, watts: u32) -> Result<()> {
+    let hwmon_path = card_hwmon(card_index)
+        .ok_or_else(|| HyperfanError::HardwareNotFound(format!("AMD GPU card{} not found", card_index)))?;
+    let microwatts = watts as u64 * gpu_const::MICROWATTS_PER_WATT as u64;
+    fs::write(hwmon_path.join("power1_cap"), microwatts.to_string())
+        .map_err(|e| HyperfanError::GpuError(format!("Failed to write power1_cap: {}", e)))?;
+    info!("Set AMD GPU card{} power cap to {}W", card_index, watts);
+    Ok(())
+}
+
 fn is_amd_gpu(device_path: &Path) -> bool {
     let vendor_path = device_path.join("vendor");
     if let Ok(vendor_id) = fs::read_to_string(&vendor_path) {
@@ -181,14 +386,26 @@ fn is_amd_gpu(device_path: &Path) -> bool {
 
 fn find_hwmon(device_path: &Path) -> Option<PathBuf> {
     let hwmon_dir = device_path.join("hwmon");
-    if hwmon_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&hwmon_dir) {
-            for entry in entries.flatten() {
-                return Some(entry.path());
-            }
-        }
-    }
-    None
+    fs::read_dir(&hwmon_dir).ok()?.flatten().next().map(|entry| entry.path())
+}
+
+/// PWM channel numbers (`pwmN`) present in a hwmon directory, sorted
+fn pwm_channels(hwmon_path: &Path) -> Vec<u32> {
+    let mut channels: Vec<u32> = fs::read_dir(hwmon_path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.strip_prefix("pwm")?.parse().ok())
+        .filter(|n| (1..=gpu_const::MAX_FANS_PER_GPU).contains(n))
+        .collect();
+    channels.sort_unstable();
+    channels
+}
+
+/// `pwmN_enable` next to `pwmN`
+fn enable_path_for(pwm_path: &Path) -> PathBuf {
+    let name = pwm_path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    pwm_path.with_file_name(format!("{}_enable", name))
 }
 
 fn read_gpu(index: u32, device_path: &Path, hwmon_path: Option<&Path>) -> Result<Option<GpuDevice>> {
@@ -224,6 +441,7 @@ fn read_gpu(index: u32, device_path: &Path, hwmon_path: Option<&Path>) -> Result
     Ok(Some(GpuDevice {
         index, name, vendor: GpuVendor::Amd, pci_bus_id, vram_total_mb, vram_used_mb,
         temperatures, fans, power_watts, power_limit_watts, utilization_percent,
+        read_only: false,
     }))
 }
 
@@ -264,16 +482,17 @@ fn read_temperatures(hwmon_path: &Path) -> Vec<GpuTemperature> {
 }
 
 fn read_fans(hwmon_path: &Path, _gpu_index: u32) -> Vec<GpuFan> {
-    let mut fans = Vec::new();
-    let pwm_path = hwmon_path.join("pwm1");
-    if !pwm_path.exists() { return fans; }
-    let pwm_value = fs::read_to_string(&pwm_path).ok().and_then(|s| s.trim().parse::<u8>().ok());
-    let speed_percent = pwm_value.map(|v| gpu_const::pwm::to_percent(v).round() as u32);
-    let rpm = fs::read_to_string(hwmon_path.join("fan1_input")).ok().and_then(|s| s.trim().parse::<u32>().ok());
-    let pwm_enable = fs::read_to_string(hwmon_path.join("pwm1_enable")).ok().and_then(|s| s.trim().parse::<u8>().ok());
-    let manual_control = pwm_enable == Some(1);
-    fans.push(GpuFan { index: 0, name: "GPU Fan".to_string(), speed_percent, rpm, target_percent: None, manual_control, min_percent: None, max_percent: None });
-    fans
+    let channels = pwm_channels(hwmon_path);
+    channels.iter().map(|&channel| {
+        let pwm_path = hwmon_path.join(format!("pwm{}", channel));
+        let pwm_value = fs::read_to_string(&pwm_path).ok().and_then(|s| s.trim().parse::<u8>().ok());
+        let speed_percent = pwm_value.map(|v| gpu_const::pwm::to_percent(v).round() as u32);
+        let rpm = fs::read_to_string(hwmon_path.join(format!("fan{}_input", channel))).ok().and_then(|s| s.trim().parse::<u32>().ok());
+        let pwm_enable = fs::read_to_string(enable_path_for(&pwm_path)).ok().and_then(|s| s.trim().parse::<u8>().ok());
+        let manual_control = pwm_enable == Some(1);
+        let name = if channels.len() > 1 { format!("GPU Fan {}", channel) } else { "GPU Fan".to_string() };
+        GpuFan { index: channel - 1, name, speed_percent, rpm, target_percent: None, manual_control, min_percent: None, max_percent: None }
+    }).collect()
 }
 
 fn read_power(hwmon_path: &Path) -> (Option<f32>, Option<f32>) {
@@ -12,8 +12,22 @@ pub const INTEL_VENDOR_ID: &str = "0x8086";
 /// Path to DRM (Direct Rendering Manager) devices
 pub const DRM_PATH: &str = "/sys/class/drm";
 
-/// Number of temperature sensors to scan on Intel GPUs
-pub const INTEL_TEMP_SENSOR_COUNT: usize = 3;
+/// Path to PCI devices (used for fallback detection)
+pub const PCI_DEVICES_PATH: &str = "/sys/bus/pci/devices";
+
+/// PCI class prefix for display controllers (VGA, 3D, other)
+pub const PCI_CLASS_DISPLAY_PREFIX: &str = "0x03";
+
+/// pci.ids database locations, in lookup order
+pub const PCI_IDS_PATHS: &[&str] = &[
+    "/usr/share/hwdata/pci.ids",
+    "/usr/share/misc/pci.ids",
+    "/usr/share/pci.ids",
+];
+
+/// Maximum number of temperature sensors reported per Intel GPU
+/// (xe exposes package, VRAM and per-channel memory sensors)
+pub const INTEL_TEMP_SENSOR_COUNT: usize = 8;
 
 /// Microwatts per watt (for power conversion)
 pub const MICROWATTS_PER_WATT: f32 = 1_000_000.0;
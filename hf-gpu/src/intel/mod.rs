@@ -1,14 +1,50 @@
 //! Intel GPU detection and control
 //!
 //! Basic temperature monitoring for iGPUs, fan control for Arc discrete GPUs
-//! Detection via i915 driver and sysfs
+//! Detection via i915 or xe driver and sysfs
+//!
+//! The xe driver (Arc/Battlemage on kernel 6.8+) uses a different hwmon
+//! layout from i915:
+//! - Temperatures: `temp2` (package) and `temp3` (VRAM), with more channels
+//!   on newer kernels; channel numbers are not contiguous
+//! - Power: no `power*_input`, only `energy*_input` counters (µJ), so power
+//!   is derived from the energy delta between reads; `power1` is the card,
+//!   `power2` the package
+//! - Fans: `fan1..fan3_input` tachometers; PWM nodes only on some kernels
 
-use crate::{gpu_const, GpuDevice, GpuPwmController, GpuTemperature, GpuVendor, Result};
+use crate::{gpu_const, GpuDevice, GpuFan, GpuFanBackend, GpuPwmController, GpuTemperature, GpuVendor, Result};
 use hf_error::HyperfanError;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
 use tracing::debug;
 
+/// Kernel driver bound to an Intel GPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntelDriver {
+    I915,
+    Xe,
+    Other,
+}
+
+impl IntelDriver {
+    fn detect(device_path: &Path) -> Self {
+        let driver = fs::read_link(device_path.join("driver"))
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()));
+        match driver.as_deref() {
+            Some("i915") => IntelDriver::I915,
+            Some("xe") => IntelDriver::Xe,
+            _ => IntelDriver::Other,
+        }
+    }
+}
+
+/// Previous energy counter sample per hwmon file, for deriving power
+static ENERGY_SAMPLES: Mutex<Option<HashMap<PathBuf, (u64, Instant)>>> = Mutex::new(None);
+
 pub fn enumerate_gpus() -> Result<Vec<GpuDevice>> {
     let mut gpus = Vec::new();
     let drm_path = Path::new(gpu_const::DRM_PATH);
@@ -36,19 +72,22 @@ pub fn enumerate_gpus() -> Result<Vec<GpuDevice>> {
             continue;
         }
 
-        // Intel GPUs typically don't have controllable fans
-        // but we can still report temperatures via hwmon
-
+        let driver = IntelDriver::detect(&device_path);
         let hwmon_path = find_hwmon(&device_path);
-        let temperatures = match hwmon_path.as_ref().map(|p| read_temperatures(p)) {
-            Some(temps) => temps,
+        let (temperatures, fans, power_watts, power_limit_watts) = match hwmon_path.as_deref() {
+            Some(hwmon) => (
+                read_temperatures(hwmon),
+                read_fans(hwmon),
+                read_power(hwmon),
+                read_power_limit(hwmon),
+            ),
             None => {
                 tracing::debug!("No hwmon path for Intel GPU, temperature data unavailable");
-                Vec::new()
+                (Vec::new(), Vec::new(), None, None)
             }
         };
 
-        let name = read_gpu_name(&device_path);
+        let name = read_gpu_name(&device_path, driver);
 
         gpus.push(GpuDevice {
             index: gpu_index,
@@ -61,10 +100,11 @@ pub fn enumerate_gpus() -> Result<Vec<GpuDevice>> {
             vram_total_mb: None,
             vram_used_mb: None,
             temperatures,
-            fans: Vec::new(), // Intel iGPUs typically don't have dedicated fans
-            power_watts: None,
-            power_limit_watts: None,
+            fans, // Only discrete cards report tachometers
+            power_watts,
+            power_limit_watts,
             utilization_percent: None,
+            read_only: false,
         });
 
         gpu_index += 1;
@@ -76,55 +116,32 @@ pub fn enumerate_gpus() -> Result<Vec<GpuDevice>> {
 pub fn enumerate_pwm_controllers() -> Result<Vec<GpuPwmController>> {
     let mut controllers = Vec::new();
     let drm_path = Path::new(gpu_const::DRM_PATH);
-    
+
     if !drm_path.exists() {
         return Err(HyperfanError::HardwareNotFound("DRM path not found".to_string()));
     }
-    
+
     for entry in fs::read_dir(drm_path)? {
         let entry = entry?;
         let name = entry.file_name();
         let name_str = name.to_string_lossy();
-        
+
         if !name_str.starts_with("card") || name_str.contains('-') {
             continue;
         }
-        
+
         let card_path = entry.path();
         let device_path = card_path.join("device");
-        
-        // Check if this is an Intel GPU
-        let vendor_path = device_path.join("vendor");
-        if let Ok(vendor_id) = fs::read_to_string(&vendor_path) {
-            if vendor_id.trim() != gpu_const::INTEL_VENDOR_ID {
-                continue;
-            }
-        } else {
+
+        if !is_intel_gpu(&device_path) {
             continue;
         }
-        
+
         // Intel Arc discrete GPUs may have fan control via hwmon
-        let hwmon_dir = device_path.join("hwmon");
-        if !hwmon_dir.exists() {
-            continue;
-        }
-        
-        let hwmon_path = fs::read_dir(&hwmon_dir)?
-            .filter_map(|e| e.ok())
-            .next()
-            .map(|e| e.path());
-        
-        let Some(hwmon_path) = hwmon_path else {
+        let Some(hwmon_path) = find_hwmon(&device_path) else {
             continue;
         };
-        
-        // Check for PWM control (Intel Arc GPUs)
-        let pwm_path = hwmon_path.join("pwm1");
-        if !pwm_path.exists() {
-            // Intel iGPUs don't have fan control - skip silently
-            continue;
-        }
-        
+
         let card_num = match name_str.replace("card", "").parse::<u32>() {
             Ok(num) => num,
             Err(e) => {
@@ -132,74 +149,88 @@ pub fn enumerate_pwm_controllers() -> Result<Vec<GpuPwmController>> {
                 continue;
             }
         };
-        
-        // Read current values
-        let current_pwm = fs::read_to_string(&pwm_path)
-            .ok()
-            .and_then(|s| s.trim().parse::<u8>().ok());
-        let current_percent = current_pwm.map(|v| gpu_const::pwm::to_percent(v).round() as u32);
-        
-        let fan_input_path = hwmon_path.join("fan1_input");
-        let current_rpm = if fan_input_path.exists() {
-            fs::read_to_string(&fan_input_path)
-                .ok()
-                .and_then(|s| s.trim().parse::<u32>().ok())
-        } else {
-            None
-        };
-        
+
         let pci_bus_id = device_path
             .file_name()
             .and_then(|n| n.to_str())
             .map(|s| s.to_string());
-        
-        controllers.push(GpuPwmController {
-            id: format!("intel:{}:0", card_num),
-            name: "Intel Arc GPU Fan".to_string(),
-            vendor: GpuVendor::Intel,
-            gpu_index: card_num,
-            fan_index: 0,
-            pwm_path: pwm_path.to_string_lossy().to_string(),
-            fan_input_path: if fan_input_path.exists() {
-                Some(fan_input_path.to_string_lossy().to_string())
+        let driver = IntelDriver::detect(&device_path);
+
+        // Check for PWM control (Intel Arc GPUs; iGPUs have none and are skipped silently)
+        for fan in 1..=gpu_const::MAX_FANS_PER_GPU {
+            let pwm_path = hwmon_path.join(format!("pwm{}", fan));
+            if !pwm_path.exists() {
+                continue;
+            }
+
+            // Read current values
+            let current_pwm = fs::read_to_string(&pwm_path)
+                .ok()
+                .and_then(|s| s.trim().parse::<u8>().ok());
+            let current_percent = current_pwm.map(|v| gpu_const::pwm::to_percent(v).round() as u32);
+
+            let fan_input_path = hwmon_path.join(format!("fan{}_input", fan));
+            let current_rpm = if fan_input_path.exists() {
+                read_u64(&fan_input_path).map(|v| v as u32)
             } else {
                 None
-            },
-            current_percent,
-            current_rpm,
-            manual_control: false,
-            pci_bus_id,
-        });
+            };
+            let manual_control = read_u64(&hwmon_path.join(format!("pwm{}_enable", fan))) == Some(1);
+
+            let fan_index = fan - 1;
+            controllers.push(GpuPwmController {
+                id: format!("intel:{}:{}", card_num, fan_index),
+                name: match driver {
+                    IntelDriver::Xe => format!("Intel Arc GPU Fan {} (xe)", fan),
+                    _ => format!("Intel Arc GPU Fan {}", fan),
+                },
+                vendor: GpuVendor::Intel,
+                gpu_index: card_num,
+                fan_index,
+                pwm_path: pwm_path.to_string_lossy().to_string(),
+                fan_input_path: if fan_input_path.exists() {
+                    Some(fan_input_path.to_string_lossy().to_string())
+                } else {
+                    None
+                },
+                current_percent,
+                current_rpm,
+                manual_control,
+                pci_bus_id: pci_bus_id.clone(),
+                backend: GpuFanBackend::Sysfs,
+            });
+        }
     }
-    
+
     Ok(controllers)
 }
 
 pub fn set_fan_speed_by_index(gpu_index: u32, fan_index: u32, percent: u32) -> Result<()> {
     let percent = percent.min(100);
-    
+
     // Find the controller
     let controllers = enumerate_pwm_controllers()?;
     let controller = controllers.iter()
         .find(|c| c.gpu_index == gpu_index && c.fan_index == fan_index)
         .ok_or_else(|| HyperfanError::HardwareNotFound(format!("Intel GPU {}:{} not found", gpu_index, fan_index)))?;
-    
-    // Enable manual control
+
+    // Enable manual control (pwmN_enable next to pwmN)
     let pwm_path = Path::new(&controller.pwm_path);
-    let enable_path = pwm_path.parent()
+    let pwm_name = pwm_path.file_name()
         .ok_or_else(|| HyperfanError::GpuError("Invalid PWM path".to_string()))?
-        .join("pwm1_enable");
-        
+        .to_string_lossy();
+    let enable_path = pwm_path.with_file_name(format!("{}_enable", pwm_name));
+
     if enable_path.exists() {
         fs::write(&enable_path, "1")
             .map_err(|e| HyperfanError::GpuError(format!("Failed to enable manual control: {}", e)))?;
     }
-    
+
     // Set PWM value
     let pwm_value = gpu_const::pwm::from_percent(percent as f32);
     fs::write(pwm_path, pwm_value.to_string())
         .map_err(|e| HyperfanError::GpuError(format!("Failed to set PWM: {}", e)))?;
-    
+
     debug!("Set Intel GPU fan to {}% (PWM: {})", percent, pwm_value);
     Ok(())
 }
@@ -218,23 +249,24 @@ fn is_intel_gpu(device_path: &Path) -> bool {
 
 fn find_hwmon(device_path: &Path) -> Option<PathBuf> {
     let hwmon_dir = device_path.join("hwmon");
-    if hwmon_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&hwmon_dir) {
-            for entry in entries.flatten() {
-                return Some(entry.path());
-            }
-        }
-    }
-    None
+    fs::read_dir(&hwmon_dir).ok()?.flatten().next().map(|entry| entry.path())
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok().and_then(|s| s.trim().parse().ok())
 }
 
-fn read_gpu_name(device_path: &Path) -> String {
-    // Try i915 driver info
+fn read_gpu_name(device_path: &Path, driver: IntelDriver) -> String {
+    match driver {
+        IntelDriver::Xe => return "Intel Arc Graphics (xe)".to_string(),
+        IntelDriver::I915 => return "Intel I915 Graphics".to_string(),
+        IntelDriver::Other => {}
+    }
+    // Fall back to whatever driver uevent reports
     let uevent_path = device_path.join("uevent");
     if let Ok(uevent) = fs::read_to_string(&uevent_path) {
         for line in uevent.lines() {
-            if line.starts_with("DRIVER=") {
-                let driver = line.replace("DRIVER=", "");
+            if let Some(driver) = line.strip_prefix("DRIVER=") {
                 return format!("Intel {} Graphics", driver.to_uppercase());
             }
         }
@@ -242,34 +274,116 @@ fn read_gpu_name(device_path: &Path) -> String {
     "Intel Graphics".to_string()
 }
 
+/// Friendly name for hwmon temperature labels
+fn temperature_name(label: &str) -> String {
+    match label {
+        "pkg" => "Package".to_string(),
+        "vram" => "VRAM".to_string(),
+        "mctrl" => "Memory Controller".to_string(),
+        "pcie" => "PCIe".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Channel numbers of `<prefix>N_input` files, sorted (xe channels may skip numbers)
+fn input_channels(hwmon_path: &Path, prefix: &str) -> Vec<u32> {
+    let mut channels: Vec<u32> = fs::read_dir(hwmon_path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let name = name.to_str()?;
+            name.strip_prefix(prefix)?.strip_suffix("_input")?.parse().ok()
+        })
+        .collect();
+    channels.sort_unstable();
+    channels
+}
+
+fn read_millidegrees(path: &Path) -> Option<f32> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| s.trim().parse::<i32>().ok())
+        .map(|millidegrees| millidegrees as f32 / gpu_const::MILLIDEGREE_DIVISOR)
+}
+
 fn read_temperatures(hwmon_path: &Path) -> Vec<GpuTemperature> {
-    let mut temps = Vec::new();
+    input_channels(hwmon_path, "temp")
+        .into_iter()
+        .take(gpu_const::INTEL_TEMP_SENSOR_COUNT)
+        .map(|i| {
+            // Temperature is in millidegrees Celsius
+            let current_temp = read_millidegrees(&hwmon_path.join(format!("temp{}_input", i)));
 
-    for i in 1..=gpu_const::INTEL_TEMP_SENSOR_COUNT {
-        let input_path = hwmon_path.join(format!("temp{}_input", i));
-        if !input_path.exists() {
-            continue;
-        }
+            let label_path = hwmon_path.join(format!("temp{}_label", i));
+            let name = fs::read_to_string(&label_path)
+                .map(|s| temperature_name(s.trim()))
+                .unwrap_or_else(|_| format!("Temp {}", i));
 
-        // Temperature is in millidegrees Celsius
-        let current_temp = fs::read_to_string(&input_path)
-            .ok()
-            .and_then(|s| s.trim().parse::<i32>().ok())
-            .map(|millidegrees| millidegrees as f32 / gpu_const::MILLIDEGREE_DIVISOR);
+            GpuTemperature {
+                name,
+                current_temp,
+                max_temp: read_millidegrees(&hwmon_path.join(format!("temp{}_max", i))),
+                critical_temp: read_millidegrees(&hwmon_path.join(format!("temp{}_crit", i))),
+                slowdown_temp: None,
+            }
+        })
+        .collect()
+}
 
-        let label_path = hwmon_path.join(format!("temp{}_label", i));
-        let name = fs::read_to_string(&label_path)
-            .map(|s| s.trim().to_string())
-            .unwrap_or_else(|_| format!("Temp {}", i));
+fn read_fans(hwmon_path: &Path) -> Vec<GpuFan> {
+    input_channels(hwmon_path, "fan")
+        .into_iter()
+        .take(gpu_const::MAX_FANS_PER_GPU as usize)
+        .map(|i| {
+            let pwm = read_u64(&hwmon_path.join(format!("pwm{}", i)));
+            GpuFan {
+                index: i - 1,
+                name: format!("Fan {}", i),
+                speed_percent: pwm.map(|v| gpu_const::pwm::to_percent(v.min(255) as u8).round() as u32),
+                rpm: read_u64(&hwmon_path.join(format!("fan{}_input", i))).map(|v| v as u32),
+                target_percent: None,
+                manual_control: read_u64(&hwmon_path.join(format!("pwm{}_enable", i))) == Some(1),
+                min_percent: None,
+                max_percent: None,
+            }
+        })
+        .collect()
+}
 
-        temps.push(GpuTemperature {
-            name,
-            current_temp,
-            max_temp: None,
-            critical_temp: None,
-            slowdown_temp: None,
-        });
+/// Current power draw in watts
+/// Uses `power1_input` if present, otherwise the average since the last read
+/// of the first energy counter (xe exposes energy only)
+fn read_power(hwmon_path: &Path) -> Option<f32> {
+    if let Some(uw) = read_u64(&hwmon_path.join("power1_input")) {
+        return Some(uw as f32 / gpu_const::MICROWATTS_PER_WATT);
     }
 
-    temps
+    let channel = *input_channels(hwmon_path, "energy").first()?;
+    let energy_path = hwmon_path.join(format!("energy{}_input", channel));
+    let energy_uj = read_u64(&energy_path)?;
+    let now = Instant::now();
+
+    let mut samples = ENERGY_SAMPLES.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = samples
+        .get_or_insert_with(HashMap::new)
+        .insert(energy_path, (energy_uj, now));
+    let (prev_uj, prev_at) = previous?;
+    let secs = now.duration_since(prev_at).as_secs_f32();
+    // Counter wrapped or reads too close together to be meaningful
+    if energy_uj < prev_uj || secs < 0.1 {
+        return None;
+    }
+    // µJ / s = µW
+    Some((energy_uj - prev_uj) as f32 / secs / gpu_const::MICROWATTS_PER_WATT)
+}
+
+/// Sustained power limit in watts (card limit, falling back to package)
+fn read_power_limit(hwmon_path: &Path) -> Option<f32> {
+    ["power1_max", "power2_max"]
+        .iter()
+        .filter_map(|name| read_u64(&hwmon_path.join(name)))
+        .find(|uw| *uw > 0)
+        .map(|uw| uw as f32 / gpu_const::MICROWATTS_PER_WATT)
 }
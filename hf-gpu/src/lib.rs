@@ -1,13 +1,14 @@
 //! GPU detection and control for Hyperfan
 //!
 //! Provides vendor-specific GPU detection and fan control for:
-//! - NVIDIA GPUs (via nvidia-smi and nvidia-settings)
+//! - NVIDIA GPUs (via nvidia-smi, and NVML or nvidia-settings)
 //! - AMD GPUs (via amdgpu driver and sysfs)
-//! - Intel Arc GPUs (via i915 driver and sysfs)
+//! - Intel Arc GPUs (via i915 or xe driver and sysfs)
 
 pub mod nvidia;
 pub mod amd;
 pub mod intel;
+pub mod pci;
 
 mod types;
 pub mod constants;
@@ -21,6 +22,7 @@ use tracing::{debug, info};
 pub type Result<T> = std::result::Result<T, HyperfanError>;
 
 /// Enumerate all detected GPUs (NVIDIA, AMD, and Intel)
+/// Cards without a working vendor backend are listed via PCI with `read_only` set
 pub fn enumerate_gpus() -> Result<Vec<GpuDevice>> {
     let mut gpus = Vec::new();
 
@@ -57,6 +59,19 @@ pub fn enumerate_gpus() -> Result<Vec<GpuDevice>> {
         }
     }
 
+    // List remaining display controllers (missing drivers/tools) as read-only
+    match pci::enumerate_gpus(&gpus) {
+        Ok(fallback_gpus) => {
+            if !fallback_gpus.is_empty() {
+                info!("Found {} GPU(s) via PCI fallback (read-only)", fallback_gpus.len());
+                gpus.extend(fallback_gpus);
+            }
+        }
+        Err(e) => {
+            debug!("PCI fallback GPU detection failed: {}", e);
+        }
+    }
+
     Ok(gpus)
 }
 
@@ -154,28 +169,33 @@ pub fn set_gpu_fan_speed_by_id(controller_id: &str, percent: u32) -> Result<()>
                 .ok_or_else(|| HyperfanError::HardwareNotFound(format!("AMD GPU {}:{} not found", gpu_index, fan_index)))?;
             amd::set_fan_speed(&controller.pwm_path, percent)
         }
-        "intel" => {
-            let controllers = intel::enumerate_pwm_controllers()?;
-            let controller = controllers.iter()
-                .find(|c| c.gpu_index == gpu_index && c.fan_index == fan_index)
-                .ok_or_else(|| HyperfanError::HardwareNotFound(format!("Intel GPU {}:{} not found", gpu_index, fan_index)))?;
-            
-            let pwm_path = std::path::Path::new(&controller.pwm_path);
-            let enable_path = pwm_path.parent()
-                .ok_or_else(|| HyperfanError::GpuError("Invalid PWM path".to_string()))?
-                .join("pwm1_enable");
-                
-            if enable_path.exists() {
-                std::fs::write(&enable_path, "1")
-                    .map_err(|e| HyperfanError::GpuError(format!("Failed to enable manual control: {}", e)))?;
-            }
-            
-            let pwm_value = gpu_const::pwm::from_percent(percent as f32);
-            std::fs::write(pwm_path, pwm_value.to_string())
-                .map_err(|e| HyperfanError::GpuError(format!("Failed to set PWM: {}", e)))?;
-            
-            Ok(())
-        }
+        "intel" => intel::set_fan_speed_by_index(gpu_index, fan_index, percent),
         _ => Err(HyperfanError::NotSupported(format!("Unknown vendor: {}", vendor))),
     }
 }
+
+/// Read the power limit of GPU `index` (NVIDIA index or AMD DRM card number)
+pub fn read_gpu_power_limit(vendor: GpuVendor, index: u32) -> Result<GpuPowerLimit> {
+    match vendor {
+        GpuVendor::Nvidia => nvidia::read_power_limit(index),
+        GpuVendor::Amd => amd::read_power_limit(index),
+        GpuVendor::Intel => Err(HyperfanError::NotSupported("Intel GPU power limits are not supported".to_string())),
+    }
+}
+
+/// Set the power limit of GPU `index` to `watts`, within the driver's range
+pub fn set_gpu_power_limit(vendor: GpuVendor, index: u32, watts: u32) -> Result<()> {
+    let limit = read_gpu_power_limit(vendor, index)?;
+    let (min, max) = (limit.min_watts.unwrap_or(0.0), limit.max_watts.unwrap_or(f32::MAX));
+    if (watts as f32) < min || (watts as f32) > max {
+        return Err(HyperfanError::InvalidConfig {
+            field: "watts".to_string(),
+            reason: format!("{}W is outside the allowed range {:.0}-{:.0}W", watts, min, max),
+        });
+    }
+    match vendor {
+        GpuVendor::Nvidia => nvidia::set_power_limit(index, watts),
+        GpuVendor::Amd => amd::set_power_limit(index, watts),
+        GpuVendor::Intel => Err(HyperfanError::NotSupported("Intel GPU power limits are not supported".to_string())),
+    }
+}
@@ -1,9 +1,12 @@
 //! NVIDIA GPU detection and control
 //!
-//! Detection via `nvidia-smi`, fan control via `nvidia-settings`
-//! Requires X11 and `nvidia-settings` with Coolbits enabled for fan control
+//! Detection via `nvidia-smi`. Fan control via NVML where the driver supports
+//! it (520+, works on Wayland and headless), otherwise via `nvidia-settings`,
+//! which requires X11 with Coolbits enabled
 
-use crate::{GpuDevice, GpuFan, GpuPwmController, GpuTemperature, GpuVendor, Result};
+pub mod nvml;
+
+use crate::{GpuDevice, GpuFan, GpuFanBackend, GpuPowerLimit, GpuPwmController, GpuTemperature, GpuVendor, Result};
 use hf_error::HyperfanError;
 use std::process::Command;
 use tracing::{debug, info, trace, warn};
@@ -73,7 +76,7 @@ pub fn enumerate_gpus() -> Result<Vec<GpuDevice>> {
         }
 
         // Build fan info - query actual fan count for multi-fan GPUs
-        let fan_count = get_fan_count(index).unwrap_or(1);
+        let fan_count = nvml::fan_count(index).unwrap_or_else(|| get_fan_count(index).unwrap_or(1));
         let mut fans = Vec::new();
         
         // Create fan entries for all detected fans
@@ -85,7 +88,8 @@ pub fn enumerate_gpus() -> Result<Vec<GpuDevice>> {
                 } else {
                     "GPU Fan".to_string()
                 },
-                speed_percent: if fan_idx == 0 { fan_speed } else { None }, // nvidia-smi only reports first fan
+                // nvidia-smi only reports the first fan
+                speed_percent: nvml::fan_speed(index, fan_idx).or(if fan_idx == 0 { fan_speed } else { None }),
                 rpm: None,
                 target_percent: None,
                 manual_control: false,
@@ -106,6 +110,7 @@ pub fn enumerate_gpus() -> Result<Vec<GpuDevice>> {
             power_watts,
             power_limit_watts,
             utilization_percent,
+            read_only: false,
         });
     }
 
@@ -166,8 +171,9 @@ pub fn enumerate_pwm_controllers() -> Result<Vec<GpuPwmController>> {
         }
         
         // NVIDIA GPUs can have multiple fans - query fan count
-        let fan_count = get_fan_count(gpu_index).unwrap_or(1);
-        info!("GPU {} ({}) has {} fan(s)", gpu_index, gpu_name, fan_count);
+        let fan_count = nvml::fan_count(gpu_index).unwrap_or_else(|| get_fan_count(gpu_index).unwrap_or(1));
+        let backend = if nvml::is_available() { GpuFanBackend::Nvml } else { GpuFanBackend::NvidiaSettings };
+        info!("GPU {} ({}) has {} fan(s), controlled via {}", gpu_index, gpu_name, fan_count, backend);
         
         for fan_idx in 0..fan_count {
             let controller_id = format!("nvidia:{}:{}", gpu_index, fan_idx);
@@ -184,10 +190,11 @@ pub fn enumerate_pwm_controllers() -> Result<Vec<GpuPwmController>> {
                 fan_index: fan_idx,
                 pwm_path: format!("nvidia:{}:{}", gpu_index, fan_idx), // Virtual path
                 fan_input_path: None, // NVIDIA doesn't expose RPM via sysfs
-                current_percent: fan_speed,
+                current_percent: nvml::fan_speed(gpu_index, fan_idx).or(fan_speed),
                 current_rpm: None,
                 manual_control: false,
                 pci_bus_id: pci_bus_id.clone(),
+                backend,
             });
         }
     }
@@ -203,6 +210,16 @@ pub fn enumerate_pwm_controllers() -> Result<Vec<GpuPwmController>> {
 pub fn set_fan_speed(gpu_index: u32, fan_index: u32, percent: u32) -> Result<()> {
     let percent = percent.min(100);
 
+    if nvml::is_available() {
+        match nvml::set_fan_speed(gpu_index, fan_index, percent) {
+            Ok(()) => {
+                debug!("Set NVIDIA GPU {} fan {} to {}% via NVML", gpu_index, fan_index, percent);
+                return Ok(());
+            }
+            Err(e) => warn!("{} - trying nvidia-settings", e),
+        }
+    }
+
     // First enable manual fan control
     let enable_result = Command::new("nvidia-settings")
         .args([
@@ -240,6 +257,16 @@ pub fn set_fan_speed(gpu_index: u32, fan_index: u32, percent: u32) -> Result<()>
 }
 
 pub fn reset_fan_auto(gpu_index: u32) -> Result<()> {
+    if nvml::is_available() {
+        match nvml::reset_fan_auto(gpu_index) {
+            Ok(()) => {
+                info!("Reset NVIDIA GPU {} fan to automatic control via NVML", gpu_index);
+                return Ok(());
+            }
+            Err(e) => warn!("{} - trying nvidia-settings", e),
+        }
+    }
+
     let output = Command::new("nvidia-settings")
         .args([
             "-a",
@@ -257,6 +284,51 @@ pub fn reset_fan_auto(gpu_index: u32) -> Result<()> {
     Ok(())
 }
 
+/// Read the board power limit and the range the driver accepts
+pub fn read_power_limit(gpu_index: u32) -> Result<GpuPowerLimit> {
+    let output = Command::new("nvidia-smi")
+        .args([
+            "-i",
+            &gpu_index.to_string(),
+            "--query-gpu=power.limit,power.default_limit,power.min_limit,power.max_limit",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .map_err(|e| HyperfanError::GpuError(format!("nvidia-smi not found: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(HyperfanError::GpuError("nvidia-smi failed".to_string()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parts: Vec<&str> = stdout.trim().split(',').map(|s| s.trim()).collect();
+    let current_watts = parts.first().and_then(|s| parse_nvidia_value_f32(s))
+        .ok_or_else(|| HyperfanError::NotSupported(format!("NVIDIA GPU {} does not report a power limit", gpu_index)))?;
+    Ok(GpuPowerLimit {
+        current_watts,
+        default_watts: parts.get(1).and_then(|s| parse_nvidia_value_f32(s)),
+        min_watts: parts.get(2).and_then(|s| parse_nvidia_value_f32(s)),
+        max_watts: parts.get(3).and_then(|s| parse_nvidia_value_f32(s)),
+    })
+}
+
+/// Set the board power limit (`nvidia-smi -pl`, needs root; lasts until reboot)
+pub fn set_power_limit(gpu_index: u32, watts: u32) -> Result<()> {
+    let output = Command::new("nvidia-smi")
+        .args(["-i", &gpu_index.to_string(), "-pl", &watts.to_string()])
+        .output()
+        .map_err(|e| HyperfanError::GpuError(format!("nvidia-smi not found: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        return Err(HyperfanError::GpuError(format!("nvidia-smi -pl failed: {}{}", stdout.trim(), stderr.trim())));
+    }
+
+    info!("Set NVIDIA GPU {} power limit to {}W", gpu_index, watts);
+    Ok(())
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -0,0 +1,174 @@
+//! NVIDIA fan control via NVML
+//!
+//! `nvidia-settings` goes through the X server, so it cannot set fan speeds on
+//! Wayland-only or headless systems. Drivers from the 520 branch on expose fan
+//! control in NVML (`nvmlDeviceSetFanSpeed_v2`), which needs no display.
+//! `libnvidia-ml.so.1` is loaded at runtime, so machines without the NVIDIA
+//! driver are unaffected; when it is missing or too old, callers fall back to
+//! `nvidia-settings`.
+
+use crate::Result;
+use hf_error::HyperfanError;
+use std::ffi::{c_char, c_int, c_uint, c_void, CStr};
+use std::sync::OnceLock;
+use tracing::{debug, info};
+
+type NvmlReturn = c_int;
+type NvmlDevice = *mut c_void;
+
+const NVML_SUCCESS: NvmlReturn = 0;
+
+const LIBRARY: &CStr = c"libnvidia-ml.so.1";
+
+/// First driver branch with `nvmlDeviceSetFanSpeed_v2`
+const MIN_DRIVER_MAJOR: u32 = 520;
+
+/// NVML entry points used for fan control (signatures from nvml.h)
+struct Nvml {
+    get_handle_by_index: unsafe extern "C" fn(c_uint, *mut NvmlDevice) -> NvmlReturn,
+    get_num_fans: unsafe extern "C" fn(NvmlDevice, *mut c_uint) -> NvmlReturn,
+    get_fan_speed: unsafe extern "C" fn(NvmlDevice, c_uint, *mut c_uint) -> NvmlReturn,
+    set_fan_speed: unsafe extern "C" fn(NvmlDevice, c_uint, c_uint) -> NvmlReturn,
+    set_default_fan_speed: unsafe extern "C" fn(NvmlDevice, c_uint) -> NvmlReturn,
+    error_string: unsafe extern "C" fn(NvmlReturn) -> *const c_char,
+}
+
+static NVML: OnceLock<Option<Nvml>> = OnceLock::new();
+
+fn nvml() -> Option<&'static Nvml> {
+    NVML.get_or_init(load).as_ref()
+}
+
+/// Look up `name` in `handle` as a function pointer of type `T`
+///
+/// # Safety
+/// `T` must be the function pointer type of the symbol.
+unsafe fn symbol<T: Copy>(handle: *mut c_void, name: &CStr) -> Option<T> {
+    let ptr = libc::dlsym(handle, name.as_ptr());
+    if ptr.is_null() {
+        debug!("NVML: {:?} not found", name);
+        return None;
+    }
+    Some(std::mem::transmute_copy::<*mut c_void, T>(&ptr))
+}
+
+fn load() -> Option<Nvml> {
+    // SAFETY: dlopen with a NUL-terminated name; the handle is never closed,
+    // so the symbols below stay valid for the life of the process
+    let handle = unsafe { libc::dlopen(LIBRARY.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    if handle.is_null() {
+        debug!("NVML not available ({:?} not found)", LIBRARY);
+        return None;
+    }
+
+    // SAFETY: each type matches the declaration in nvml.h
+    let (init, driver_version) = unsafe {
+        (
+            symbol::<unsafe extern "C" fn() -> NvmlReturn>(handle, c"nvmlInit_v2")?,
+            symbol::<unsafe extern "C" fn(*mut c_char, c_uint) -> NvmlReturn>(handle, c"nvmlSystemGetDriverVersion")?,
+        )
+    };
+    // SAFETY: nvmlInit_v2 takes no arguments and may be called repeatedly
+    let ret = unsafe { init() };
+    if ret != NVML_SUCCESS {
+        debug!("NVML: nvmlInit_v2 failed ({})", ret);
+        return None;
+    }
+
+    let mut buffer = [0 as c_char; 80];
+    // SAFETY: the buffer length is passed along; NVML NUL-terminates on success
+    let version = match unsafe { driver_version(buffer.as_mut_ptr(), buffer.len() as c_uint) } {
+        // SAFETY: NUL-terminated by NVML, within the buffer
+        NVML_SUCCESS => unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_string_lossy().into_owned(),
+        _ => String::new(),
+    };
+    let major = version.split('.').next().and_then(|m| m.parse::<u32>().ok()).unwrap_or(0);
+    if major < MIN_DRIVER_MAJOR {
+        info!("NVIDIA driver {} has no NVML fan control (needs {}+) - using nvidia-settings", version, MIN_DRIVER_MAJOR);
+        return None;
+    }
+
+    // SAFETY: each type matches the declaration in nvml.h
+    let nvml = unsafe {
+        Nvml {
+            get_handle_by_index: symbol(handle, c"nvmlDeviceGetHandleByIndex_v2")?,
+            get_num_fans: symbol(handle, c"nvmlDeviceGetNumFans")?,
+            get_fan_speed: symbol(handle, c"nvmlDeviceGetFanSpeed_v2")?,
+            set_fan_speed: symbol(handle, c"nvmlDeviceSetFanSpeed_v2")?,
+            set_default_fan_speed: symbol(handle, c"nvmlDeviceSetDefaultFanSpeed_v2")?,
+            error_string: symbol(handle, c"nvmlErrorString")?,
+        }
+    };
+    info!("NVML fan control available (driver {})", version);
+    Some(nvml)
+}
+
+impl Nvml {
+    fn check(&self, ret: NvmlReturn, what: &str) -> Result<()> {
+        if ret == NVML_SUCCESS {
+            return Ok(());
+        }
+        // SAFETY: nvmlErrorString returns a static NUL-terminated string for any code
+        let message = unsafe { CStr::from_ptr((self.error_string)(ret)) }.to_string_lossy().into_owned();
+        Err(HyperfanError::GpuError(format!("NVML {} failed: {}", what, message)))
+    }
+
+    fn device(&self, gpu_index: u32) -> Result<NvmlDevice> {
+        let mut device: NvmlDevice = std::ptr::null_mut();
+        // SAFETY: device is a valid out pointer
+        let ret = unsafe { (self.get_handle_by_index)(gpu_index, &mut device) };
+        self.check(ret, "device lookup")?;
+        Ok(device)
+    }
+
+    fn fan_count(&self, device: NvmlDevice) -> Result<u32> {
+        let mut count: c_uint = 0;
+        // SAFETY: device came from nvmlDeviceGetHandleByIndex_v2; count is a valid out pointer
+        let ret = unsafe { (self.get_num_fans)(device, &mut count) };
+        self.check(ret, "fan count")?;
+        Ok(count)
+    }
+}
+
+/// Whether fans can be controlled through NVML (no X server needed)
+pub fn is_available() -> bool {
+    nvml().is_some()
+}
+
+/// Number of fans on GPU `gpu_index`
+pub fn fan_count(gpu_index: u32) -> Option<u32> {
+    let nvml = nvml()?;
+    let device = nvml.device(gpu_index).ok()?;
+    nvml.fan_count(device).ok().map(|count| count.min(crate::constants::MAX_FANS_PER_GPU))
+}
+
+/// Current speed (percent of maximum) of fan `fan_index` on GPU `gpu_index`
+pub fn fan_speed(gpu_index: u32, fan_index: u32) -> Option<u32> {
+    let nvml = nvml()?;
+    let device = nvml.device(gpu_index).ok()?;
+    let mut speed: c_uint = 0;
+    // SAFETY: device came from nvmlDeviceGetHandleByIndex_v2; speed is a valid out pointer
+    let ret = unsafe { (nvml.get_fan_speed)(device, fan_index, &mut speed) };
+    nvml.check(ret, "fan speed").ok().map(|_| speed)
+}
+
+/// Set fan `fan_index` on GPU `gpu_index` to `percent` (manual control)
+pub fn set_fan_speed(gpu_index: u32, fan_index: u32, percent: u32) -> Result<()> {
+    let nvml = nvml().ok_or_else(|| HyperfanError::GpuError("NVML not available".to_string()))?;
+    let device = nvml.device(gpu_index)?;
+    // SAFETY: device came from nvmlDeviceGetHandleByIndex_v2
+    let ret = unsafe { (nvml.set_fan_speed)(device, fan_index, percent.min(100)) };
+    nvml.check(ret, "set fan speed")
+}
+
+/// Return every fan on GPU `gpu_index` to the driver's automatic control
+pub fn reset_fan_auto(gpu_index: u32) -> Result<()> {
+    let nvml = nvml().ok_or_else(|| HyperfanError::GpuError("NVML not available".to_string()))?;
+    let device = nvml.device(gpu_index)?;
+    for fan_index in 0..nvml.fan_count(device)? {
+        // SAFETY: device came from nvmlDeviceGetHandleByIndex_v2
+        let ret = unsafe { (nvml.set_default_fan_speed)(device, fan_index) };
+        nvml.check(ret, "reset fan speed")?;
+    }
+    Ok(())
+}
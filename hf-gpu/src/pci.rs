@@ -0,0 +1,254 @@
+//! PCI fallback GPU detection
+//!
+//! Lists display controllers from `/sys/bus/pci/devices` that no vendor
+//! backend reported (no nvidia-smi, nouveau, radeon, no driver bound at all),
+//! so the dashboard still shows the card. Names come from the system
+//! pci.ids database with an embedded vendor fallback; temperatures and fan
+//! tachometers are read from whatever hwmon the bound driver exposes.
+//!
+//! Devices found here are marked `read_only`: no PWM controllers are created
+//! for them.
+
+use crate::{gpu_const, GpuDevice, GpuFan, GpuTemperature, GpuVendor, Result};
+use hf_error::HyperfanError;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::debug;
+
+/// Resolved names per `vendor:device`, so pci.ids is scanned once per device
+static NAME_CACHE: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
+
+/// List GPUs not already present in `known` (matched by PCI bus ID)
+pub fn enumerate_gpus(known: &[GpuDevice]) -> Result<Vec<GpuDevice>> {
+    let pci_path = Path::new(gpu_const::PCI_DEVICES_PATH);
+    if !pci_path.exists() {
+        return Err(HyperfanError::HardwareNotFound(format!("PCI path {} not found", gpu_const::PCI_DEVICES_PATH)));
+    }
+
+    let known_ids: Vec<String> = known
+        .iter()
+        .filter_map(|g| g.pci_bus_id.as_deref())
+        .map(normalize_bus_id)
+        .collect();
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(pci_path)?.flatten().map(|e| e.path()).collect();
+    entries.sort();
+
+    let mut gpus = Vec::new();
+    let mut gpu_index = known.len() as u32;
+
+    for device_path in entries {
+        let Some(bus_id) = device_path.file_name().and_then(|n| n.to_str()).map(|s| s.to_string()) else {
+            continue;
+        };
+
+        let is_display = read_trimmed(&device_path.join("class"))
+            .is_some_and(|class| class.starts_with(gpu_const::PCI_CLASS_DISPLAY_PREFIX));
+        if !is_display || known_ids.contains(&normalize_bus_id(&bus_id)) {
+            continue;
+        }
+
+        let Some(vendor_id) = read_trimmed(&device_path.join("vendor")) else {
+            continue;
+        };
+        let vendor = match vendor_id.as_str() {
+            gpu_const::NVIDIA_VENDOR_ID => GpuVendor::Nvidia,
+            gpu_const::AMD_VENDOR_ID => GpuVendor::Amd,
+            gpu_const::INTEL_VENDOR_ID => GpuVendor::Intel,
+            _ => {
+                debug!("Skipping display controller {} from unsupported vendor {}", bus_id, vendor_id);
+                continue;
+            }
+        };
+        let device_id = read_trimmed(&device_path.join("device")).unwrap_or_default();
+
+        let hwmon_path = find_hwmon(&device_path);
+        let (temperatures, fans) = match hwmon_path.as_deref() {
+            Some(hwmon) => (read_temperatures(hwmon), read_fans(hwmon)),
+            None => (Vec::new(), Vec::new()),
+        };
+
+        debug!("PCI fallback: {} {} {} (hwmon: {:?})", bus_id, vendor_id, device_id, hwmon_path);
+
+        gpus.push(GpuDevice {
+            index: gpu_index,
+            name: lookup_name(vendor, &vendor_id, &device_id),
+            vendor,
+            pci_bus_id: Some(bus_id),
+            vram_total_mb: None,
+            vram_used_mb: None,
+            temperatures,
+            fans,
+            power_watts: None,
+            power_limit_watts: None,
+            utilization_percent: None,
+            read_only: true,
+        });
+
+        gpu_index += 1;
+    }
+
+    Ok(gpus)
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_lowercase())
+}
+
+/// Normalize bus IDs across sources ("00000000:01:00.0" from nvidia-smi,
+/// "0000:01:00.0" from sysfs)
+pub fn normalize_bus_id(bus_id: &str) -> String {
+    let bus_id = bus_id.trim().to_lowercase();
+    match bus_id.split_once(':') {
+        Some((domain, rest)) if rest.contains(':') => match u32::from_str_radix(domain, 16) {
+            Ok(domain) => format!("{:04x}:{}", domain, rest),
+            Err(_) => bus_id,
+        },
+        // No domain ("01:00.0")
+        _ => format!("0000:{}", bus_id),
+    }
+}
+
+fn find_hwmon(device_path: &Path) -> Option<PathBuf> {
+    let hwmon_dir = device_path.join("hwmon");
+    fs::read_dir(&hwmon_dir).ok()?.flatten().next().map(|entry| entry.path())
+}
+
+fn vendor_prefix(vendor: GpuVendor) -> &'static str {
+    match vendor {
+        GpuVendor::Nvidia => "NVIDIA",
+        GpuVendor::Amd => "AMD",
+        GpuVendor::Intel => "Intel",
+    }
+}
+
+/// Display name from pci.ids, e.g. "AD102 [GeForce RTX 4090]" becomes
+/// "NVIDIA GeForce RTX 4090"; falls back to "NVIDIA GPU [10de:2684]"
+fn lookup_name(vendor: GpuVendor, vendor_id: &str, device_id: &str) -> String {
+    let vendor_hex = vendor_id.trim_start_matches("0x");
+    let device_hex = device_id.trim_start_matches("0x");
+    let key = format!("{}:{}", vendor_hex, device_hex);
+
+    let mut cache = NAME_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(HashMap::new);
+    if let Some(name) = cache.get(&key) {
+        return name.clone();
+    }
+
+    let name = gpu_const::PCI_IDS_PATHS
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .and_then(|db| find_device_name(&db, vendor_hex, device_hex))
+        .map(|device| {
+            // Prefer the marketing name in brackets over the chip codename
+            let marketing = device
+                .split_once('[')
+                .and_then(|(_, rest)| rest.rsplit_once(']'))
+                .map(|(name, _)| name)
+                .unwrap_or(&device);
+            format!("{} {}", vendor_prefix(vendor), marketing)
+        })
+        .unwrap_or_else(|| format!("{} GPU [{}]", vendor_prefix(vendor), key));
+
+    cache.insert(key, name.clone());
+    name
+}
+
+/// Find a device name in pci.ids content
+/// Vendor lines are unindented, device lines have one tab, subsystems two
+fn find_device_name(db: &str, vendor_hex: &str, device_hex: &str) -> Option<String> {
+    let mut in_vendor = false;
+    for line in db.lines() {
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+        if !line.starts_with('\t') {
+            // Device class section follows the vendor list
+            if line.starts_with("C ") {
+                break;
+            }
+            let found = line.split_whitespace().next().is_some_and(|id| id.eq_ignore_ascii_case(vendor_hex));
+            if in_vendor && !found {
+                break;
+            }
+            in_vendor = found;
+            continue;
+        }
+        if !in_vendor || line.starts_with("\t\t") {
+            continue;
+        }
+        let Some((id, name)) = line.trim_start().split_once(char::is_whitespace) else {
+            continue;
+        };
+        if id.eq_ignore_ascii_case(device_hex) {
+            return Some(name.trim().to_string());
+        }
+    }
+    None
+}
+
+/// Channel numbers of `<prefix>N_input` files, sorted
+fn input_channels(hwmon_path: &Path, prefix: &str) -> Vec<u32> {
+    let mut channels: Vec<u32> = fs::read_dir(hwmon_path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let name = name.to_str()?;
+            name.strip_prefix(prefix)?.strip_suffix("_input")?.parse().ok()
+        })
+        .collect();
+    channels.sort_unstable();
+    channels
+}
+
+fn read_millidegrees(path: &Path) -> Option<f32> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| s.trim().parse::<i32>().ok())
+        .map(|millidegrees| millidegrees as f32 / gpu_const::MILLIDEGREE_DIVISOR)
+}
+
+fn read_temperatures(hwmon_path: &Path) -> Vec<GpuTemperature> {
+    input_channels(hwmon_path, "temp")
+        .into_iter()
+        .map(|i| GpuTemperature {
+            name: fs::read_to_string(hwmon_path.join(format!("temp{}_label", i)))
+                .map(|s| s.trim().to_string())
+                .unwrap_or_else(|_| if i == 1 { "GPU".to_string() } else { format!("Temp {}", i) }),
+            current_temp: read_millidegrees(&hwmon_path.join(format!("temp{}_input", i))),
+            max_temp: read_millidegrees(&hwmon_path.join(format!("temp{}_max", i))),
+            critical_temp: read_millidegrees(&hwmon_path.join(format!("temp{}_crit", i))),
+            slowdown_temp: None,
+        })
+        .collect()
+}
+
+fn read_fans(hwmon_path: &Path) -> Vec<GpuFan> {
+    input_channels(hwmon_path, "fan")
+        .into_iter()
+        .take(gpu_const::MAX_FANS_PER_GPU as usize)
+        .map(|i| GpuFan {
+            index: i.saturating_sub(1),
+            name: format!("Fan {}", i),
+            speed_percent: fs::read_to_string(hwmon_path.join(format!("pwm{}", i)))
+                .ok()
+                .and_then(|s| s.trim().parse::<u8>().ok())
+                .map(|v| gpu_const::pwm::to_percent(v).round() as u32),
+            rpm: fs::read_to_string(hwmon_path.join(format!("fan{}_input", i)))
+                .ok()
+                .and_then(|s| s.trim().parse().ok()),
+            target_percent: None,
+            manual_control: false,
+            min_percent: None,
+            max_percent: None,
+        })
+        .collect()
+}
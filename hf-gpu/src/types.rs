@@ -20,6 +20,27 @@ impl std::fmt::Display for GpuVendor {
     }
 }
 
+/// Interface a GPU fan is controlled through
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum GpuFanBackend {
+    /// hwmon `pwmN` of the GPU driver (amdgpu, i915, xe)
+    Sysfs,
+    /// NVML (NVIDIA driver 520+), works without an X server
+    Nvml,
+    /// `nvidia-settings`, requires X11 with Coolbits
+    NvidiaSettings,
+}
+
+impl std::fmt::Display for GpuFanBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GpuFanBackend::Sysfs => write!(f, "sysfs"),
+            GpuFanBackend::Nvml => write!(f, "NVML"),
+            GpuFanBackend::NvidiaSettings => write!(f, "nvidia-settings"),
+        }
+    }
+}
+
 /// Represents a detected GPU with its sensors and fans
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GpuDevice {
@@ -45,6 +66,9 @@ pub struct GpuDevice {
     pub power_limit_watts: Option<f32>,
     /// GPU utilization percentage
     pub utilization_percent: Option<u32>,
+    /// Detected via PCI fallback only (no vendor driver/tool): monitoring only, no fan control
+    #[serde(default)]
+    pub read_only: bool,
 }
 
 /// GPU temperature sensor
@@ -117,4 +141,39 @@ pub struct GpuPwmController {
     pub manual_control: bool,
     /// PCI bus ID for identification
     pub pci_bus_id: Option<String>,
+    /// Interface fan speed writes go through
+    pub backend: GpuFanBackend,
+}
+
+/// Point on a GPU firmware fan curve
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct GpuFanCurvePoint {
+    /// Temperature in Celsius (hotspot on AMD)
+    pub temp_c: u32,
+    /// Fan speed percentage (0-100)
+    pub speed_percent: u32,
+}
+
+/// Firmware fan curve stored on the GPU (AMD PMFW `gpu_od/fan_ctrl/fan_curve`)
+/// Stays active when hyperfand is not running
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GpuFanCurve {
+    /// Curve points, one per firmware slot
+    pub points: Vec<GpuFanCurvePoint>,
+    /// Allowed temperature range (min, max) in Celsius
+    pub temp_range: Option<(u32, u32)>,
+    /// Allowed fan speed range (min, max) in percent
+    pub speed_range: Option<(u32, u32)>,
+}
+
+/// Board power limit of a GPU (NVIDIA `power.limit`, AMD `power1_cap`)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct GpuPowerLimit {
+    /// Limit currently enforced, in watts
+    pub current_watts: f32,
+    /// Factory limit in watts
+    pub default_watts: Option<f32>,
+    /// Range the driver accepts, in watts
+    pub min_watts: Option<f32>,
+    pub max_watts: Option<f32>,
 }
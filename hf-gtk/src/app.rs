@@ -12,6 +12,7 @@ use std::time::Duration;
 use gtk4::IconTheme;
 
 use crate::window::HyperfanWindow;
+use crate::notifications;
 use crate::tray;
 
 /// Main Hyperfan GTK application
@@ -166,10 +167,14 @@ impl HyperfanApp {
             tray::start_tray();
         }
         
+        // Desktop notifications (daemon down, thermal, fan failure, drift)
+        notifications::start_notifications(app);
+        
         let hyperfan_window = HyperfanWindow::new(app);
         let window = hyperfan_window.window.clone();
         
-        window.present();
+        // Also shows the setup assistant on first run
+        hyperfan_window.present();
         
         // Setup periodic check for tray commands
         let window_for_tray = window.clone();
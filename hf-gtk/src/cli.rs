@@ -2,7 +2,9 @@
 //!
 //! Provides CLI access to all settings and core functionality.
 
-use clap::{Parser, Subcommand, Args};
+use clap::{CommandFactory, Parser, Subcommand, Args};
+
+use crate::completions::{self, Shell};
 
 #[derive(Parser)]
 #[command(name = "hyperfan")]
@@ -17,6 +19,8 @@ real-time temperature monitoring, and intelligent hardware detection.
 EXAMPLES:
     hyperfan                           Launch GUI (default)
     hyperfan status                    Show system status summary
+    hyperfan doctor                    Check the setup (paste the output into bug reports)
+    hyperfan bug-report                Collect a redacted bug report bundle (.tar.gz)
     hyperfan hardware temps            List all temperature sensors
     hyperfan hardware detect           Detect fan-to-PWM mappings
     hyperfan curves list               List all saved fan curves
@@ -25,6 +29,11 @@ EXAMPLES:
     hyperfan settings set display.temperature_unit fahrenheit
     hyperfan service status            Check daemon service status
     hyperfan fan set /sys/class/hwmon/hwmon3/pwm1 50
+    hyperfan profile set performance   Switch all pairs to performance curves
+    hyperfan log --duration 10m -o run.csv
+                                       Log every sensor during a benchmark run
+    hyperfan --json hardware temps     Sensor readings as JSON for scripts
+    hyperfan completions bash          Print bash completion script
 
 ENVIRONMENT VARIABLES:
     RUST_LOG=debug         Enable debug logging
@@ -34,8 +43,8 @@ ENVIRONMENT VARIABLES:
     GTK_DEBUG=renderer     Show which renderer is being used
 
 FILES:
-    ~/.config/hyperfan/settings.json      Application settings
-    ~/.config/hyperfan/curves.json        Fan curve definitions
+    ~/.config/hyperfan/settings.json      Application settings (settings.toml in TOML mode)
+    ~/.config/hyperfan/curves.json        Fan curve definitions (curves.toml in TOML mode)
     ~/.config/hyperfan/temp_graphs.json   Temperature graph configs
     ~/.config/hyperfan/bindings.json      Hardware binding store")]
 #[command(propagate_version = true)]
@@ -44,6 +53,10 @@ pub struct Cli {
     #[arg(long)]
     pub perf: bool,
 
+    /// Print machine-readable JSON instead of text (for scripting)
+    #[arg(long, global = true)]
+    pub json: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -77,6 +90,10 @@ pub enum Commands {
     #[command(subcommand, about = "Manage hardware fingerprint bindings")]
     Bindings(BindingCommands),
 
+    /// Config bundle export and headless provisioning
+    #[command(subcommand, about = "Export or apply a full config bundle (settings, curves, bindings)")]
+    Bundle(BundleCommands),
+
     /// Settings management
     #[command(subcommand, about = "View and modify application settings")]
     Settings(SettingsCommands),
@@ -93,6 +110,10 @@ pub enum Commands {
     #[command(subcommand, about = "Manage PWM-to-fan pairings")]
     Pairings(PairingCommands),
 
+    /// Curve profile switching
+    #[command(subcommand, about = "Show or switch the active curve profile")]
+    Profile(ProfileCommands),
+
     /// GPU fan control
     #[command(subcommand, about = "Control GPU fan speeds")]
     Gpu(GpuCommands),
@@ -103,6 +124,63 @@ pub enum Commands {
 
     /// Show system status summary
     Status,
+
+    /// Check the setup and print a report for bug reports
+    ///
+    /// Runs the checks of the GUI's Diagnostics page (daemon, service,
+    /// drivers, PWM permissions, NVIDIA Coolbits, other fan control software,
+    /// config) and lists the detected chips.
+    Doctor,
+
+    /// Collect a redacted bug report bundle
+    ///
+    /// Writes version info, the diagnostic checks, a hardware snapshot, the
+    /// config files, the binding store and the recent daemon log to one
+    /// .tar.gz. The hostname, home directory, serial numbers and EC settings
+    /// are redacted.
+    BugReport {
+        /// Output file (default: hyperfan-bug-report-<time>.tar.gz in the current directory)
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+
+    /// Log every sensor snapshot to a file during a benchmark run
+    ///
+    /// The daemon samples all temperatures, fan speeds and PWM values and
+    /// stops by itself after the duration or size limit; the file is then
+    /// copied to the output path.
+    Log {
+        /// How long to log ("90s", "10m", "1h30m"; plain numbers are seconds)
+        #[arg(short, long, value_parser = parse_duration_ms, required_unless_present = "stop")]
+        duration: Option<u64>,
+        /// Output file (JSON lines if it ends in .jsonl, CSV otherwise)
+        #[arg(short, long, required_unless_present = "stop")]
+        output: Option<std::path::PathBuf>,
+        /// Sample interval in milliseconds
+        #[arg(long, default_value_t = 1000)]
+        interval: u32,
+        /// Stop once the file reaches this size ("500K", "100M", "2G")
+        #[arg(long, value_parser = parse_size)]
+        max_size: Option<u64>,
+        /// Stop a running log early (the file stays in the daemon's recordings directory)
+        #[arg(long, conflicts_with_all = ["duration", "output", "max_size"])]
+        stop: bool,
+    },
+
+    /// Set a fan speed (shortcut for `fan set`)
+    SetPwm {
+        /// PWM control path
+        path: String,
+        /// Speed as percentage (0-100)
+        percent: f32,
+    },
+
+    /// Print a shell completion script
+    Completions {
+        /// Target shell
+        #[arg(value_enum)]
+        shell: Shell,
+    },
 }
 
 // ============================================================================
@@ -119,6 +197,14 @@ pub enum HardwareCommands {
     Fans,
     /// List all PWM controllers
     Pwm,
+    /// List voltage, current, power and humidity sensors
+    Sensors {
+        /// Only show one kind (voltage, current, power, humidity)
+        #[arg(long)]
+        kind: Option<String>,
+    },
+    /// List fan, temperature and chassis intrusion alarm flags
+    Alarms,
     /// List all GPUs
     Gpus,
     /// Show full hardware snapshot as JSON
@@ -128,6 +214,10 @@ pub enum HardwareCommands {
         /// Use heuristic detection (faster, less accurate)
         #[arg(long)]
         heuristic: bool,
+        /// Watch fan speeds follow the current curves instead of stopping
+        /// each fan (slower, only finds outputs that change)
+        #[arg(long, conflicts_with = "heuristic")]
+        passive: bool,
     },
     /// Show saved PWM-fan mappings
     Mappings,
@@ -139,6 +229,55 @@ pub enum HardwareCommands {
     },
     /// Check if detection has been completed
     DetectionStatus,
+    /// Show the controller filter (chips and PWM channels never touched)
+    Filter,
+    /// Add a hwmon chip directory or PWM output to the controller filter
+    FilterAdd {
+        /// e.g. /sys/class/hwmon/hwmon3 (whole chip) or /sys/class/hwmon/hwmon3/pwm2
+        path: String,
+    },
+    /// Remove an entry (numbered as in `hardware filter`)
+    FilterRemove {
+        entry: usize,
+    },
+    /// Switch between blacklist (listed outputs are left alone) and
+    /// allowlist (only listed outputs are controlled)
+    FilterMode {
+        /// blacklist or allowlist
+        mode: String,
+    },
+}
+
+/// Run an auto-tune job, printing each duty step as it is measured
+fn run_auto_tune_job(
+    curve_id: &str,
+    target: f32,
+    quiet: bool,
+) -> Result<hf_core::DaemonAutoTuneProgress, Box<dyn std::error::Error>> {
+    let job = hf_core::daemon_start_auto_tune(curve_id, target)?;
+    if !quiet {
+        println!("Auto-tune job {} started - start your load test now and keep it running", job.job_id);
+        println!("Each fan speed step waits for the temperature to settle (up to a few minutes)");
+    }
+    let mut reported = 0;
+    loop {
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        let progress = hf_core::daemon_get_auto_tune_progress(job.job_id)?;
+        if !quiet {
+            for step in &progress.steps[reported..] {
+                println!("  {:.0}% -> {:.1}°C{}", step.duty_percent, step.temp, if step.settled { "" } else { " (not settled)" });
+            }
+        }
+        reported = progress.steps.len();
+        match progress.state {
+            hf_core::DaemonDetectionJobState::Running => {}
+            hf_core::DaemonDetectionJobState::Completed => return Ok(progress),
+            hf_core::DaemonDetectionJobState::Cancelled => return Err("Auto-tune was cancelled".into()),
+            hf_core::DaemonDetectionJobState::Failed => {
+                return Err(format!("Auto-tune failed: {}", progress.error.unwrap_or_default()).into());
+            }
+        }
+    }
 }
 
 // ============================================================================
@@ -191,6 +330,109 @@ pub enum CurveCommands {
         /// Input file path
         path: String,
     },
+    /// Export curves as a shareable template with author/hardware notes
+    ExportTemplate {
+        /// Output file path
+        path: String,
+        /// Curve IDs or names to include (default: all)
+        curves: Vec<String>,
+        /// Export the curves every pair uses in this profile, with the assignments
+        #[arg(long, conflicts_with = "curves")]
+        profile: Option<String>,
+        #[arg(long)]
+        author: Option<String>,
+        /// Hardware the curves were made for (default: board name)
+        #[arg(long)]
+        hardware: Option<String>,
+        #[arg(long)]
+        notes: Option<String>,
+    },
+    /// Import a curve template, re-mapping its sensors to this system
+    ImportTemplate {
+        /// Template file path
+        path: String,
+        /// Use a local sensor for a template sensor (TEMPLATE_PATH=LOCAL_PATH)
+        #[arg(long = "map", value_name = "FROM=TO")]
+        map: Vec<String>,
+        /// Accept suggested sensors without asking
+        #[arg(long)]
+        yes: bool,
+        /// Skip the template's profile assignments
+        #[arg(long)]
+        no_profile: bool,
+    },
+    /// Use a curve for one or more fan-curve pairs
+    Apply {
+        /// Curve ID or name
+        curve: String,
+        /// Pair IDs
+        #[arg(required = true)]
+        pair_ids: Vec<String>,
+        /// Assign a copy with every point shifted by this many percent (e.g. -10)
+        #[arg(long, allow_hyphen_values = true, default_value_t = 0.0)]
+        offset: f32,
+        /// Only use the curve while this profile is active
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Adjust a curve while the system runs on battery
+    BatteryOffset {
+        /// Curve ID or name
+        id: String,
+        /// Fan speed offset in percent (e.g. -10)
+        #[arg(long, allow_hyphen_values = true, default_value_t = 0.0)]
+        duty: f32,
+        /// Temperature offset in °C (e.g. 5 = fans ramp up later)
+        #[arg(long, allow_hyphen_values = true, default_value_t = 0.0)]
+        temp: f32,
+        /// Remove the battery offset
+        #[arg(long, conflicts_with_all = ["duty", "temp"])]
+        clear: bool,
+    },
+    /// Set the sensors to fall back to, in order, when a control's source fails
+    Fallback {
+        /// Curve ID or name
+        id: String,
+        /// Temperature source paths, most preferred first
+        #[arg(required_unless_present = "clear")]
+        sources: Vec<String>,
+        /// Remove every fallback source
+        #[arg(long, conflicts_with = "sources")]
+        clear: bool,
+    },
+    /// Choose how fan speed is interpolated between curve points
+    Interpolation {
+        /// Curve ID or name
+        id: String,
+        /// linear, or monotone for a smooth cubic that never overshoots the points
+        #[arg(value_parser = ["linear", "monotone"])]
+        mode: String,
+    },
+    /// Cap a curve so its fans stay under a noise target below a temperature ceiling
+    Quiet {
+        /// Curve ID or name
+        id: String,
+        /// Loudest the curve's fans may get together, in dBA
+        #[arg(long)]
+        max_dba: f32,
+        /// Temperature (°C) from which the curve runs at its full speed again
+        #[arg(long)]
+        ceiling: f32,
+        /// Show the flattened curve without saving it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Measure temperatures under a load you run and suggest the quietest curve holding a target (via daemon)
+    AutoTune {
+        /// Curve ID or name
+        id: String,
+        /// Temperature (°C) the curve should hold under the load
+        #[arg(long)]
+        target: f32,
+        /// Save the suggested curve without asking
+        #[arg(long)]
+        apply: bool,
+    },
 }
 
 // ============================================================================
@@ -261,6 +503,32 @@ pub enum PairCommands {
         /// PWM controller path
         fan_path: String,
     },
+
+    /// Set the curve a pair uses for a profile
+    ProfileCurve {
+        /// Pair ID
+        id: String,
+        /// Profile name (e.g. quiet, performance)
+        profile: String,
+        /// Curve ID (omit to use the pair's default curve)
+        curve_id: Option<String>,
+    },
+}
+
+// ============================================================================
+// Profile Commands
+// ============================================================================
+
+#[derive(Subcommand)]
+pub enum ProfileCommands {
+    /// Show the active profile and available profiles
+    Show,
+    /// Switch the active profile
+    #[command(visible_alias = "switch")]
+    Set {
+        /// Profile name (e.g. quiet, normal, performance)
+        name: String,
+    },
 }
 
 // =========================================================================
@@ -291,6 +559,51 @@ pub enum PairingCommands {
         /// PWM path
         pwm_path: String,
     },
+
+    /// Drive a pairing by duty cycle or track a target RPM (via daemon)
+    Mode {
+        /// PWM path
+        pwm_path: String,
+        /// Control mode
+        #[arg(value_parser = ["duty", "rpm"])]
+        mode: String,
+        /// RPM at 100% curve output (default: maximum RPM found by detection)
+        #[arg(long)]
+        max_rpm: Option<u32>,
+    },
+
+    /// Let the curve stop a calibrated fan instead of holding it at its stall duty (via daemon)
+    ZeroRpm {
+        /// PWM path
+        pwm_path: String,
+        #[arg(value_parser = ["on", "off"])]
+        state: String,
+    },
+
+    /// What to do when another program writes the PWM output (via daemon)
+    Enforce {
+        /// PWM path
+        pwm_path: String,
+        /// reassert: write the curve value back; yield: wait until the other
+        /// program stops changing it; alert-only: wait until the curve output moves
+        #[arg(value_parser = ["reassert", "yield", "alert-only"])]
+        policy: String,
+    },
+
+    /// Describe how loud a fan is, for noise estimates and quiet curves
+    Noise {
+        /// PWM path
+        pwm_path: String,
+        /// Loudness at 100% duty in dBA (estimates lower speeds from the fan laws)
+        #[arg(long, conflicts_with_all = ["levels", "clear"])]
+        max_dba: Option<f32>,
+        /// Measured levels as DUTY:DBA pairs (e.g. "30:18,60:27,100:38")
+        #[arg(long, conflicts_with = "clear")]
+        levels: Option<String>,
+        /// Go back to the generic estimate
+        #[arg(long)]
+        clear: bool,
+    },
 }
 
 // ============================================================================
@@ -334,6 +647,32 @@ pub enum BindingCommands {
     Validate,
     /// Discover and fingerprint current system hardware
     Discover,
+    /// List bindings that no longer match the hardware, with likely matches
+    Conflicts,
+    /// Confirm that a channel is the same fan as a conflicting binding
+    Accept {
+        /// Binding ID (from `bindings conflicts`)
+        pwm_id: String,
+        /// Current PWM path (default: the most likely candidate)
+        #[arg(long)]
+        pwm: Option<String>,
+        /// Current fan input path (default: the candidate's fan input)
+        #[arg(long)]
+        fan: Option<String>,
+    },
+    /// Export the binding store to a portable file for identical machines
+    Export {
+        /// File to write
+        file: String,
+    },
+    /// Import a binding export, re-anchoring each binding on this machine
+    Import {
+        /// Export file to read
+        file: String,
+        /// Only report what would and wouldn't match
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Show binding store file path
     Path,
     /// Clear all bindings
@@ -344,6 +683,26 @@ pub enum BindingCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum BundleCommands {
+    /// Export settings, curves and bindings to one file for identical machines
+    Export {
+        /// File to write
+        file: String,
+    },
+    /// Check a bundle against this machine and replace the config with it
+    Apply {
+        /// Bundle file to read
+        file: String,
+        /// Only report what would be written
+        #[arg(long)]
+        dry_run: bool,
+        /// Write even if paths or bindings don't match this machine
+        #[arg(long)]
+        force: bool,
+    },
+}
+
 // ============================================================================
 // GPU Commands
 // ============================================================================
@@ -375,6 +734,28 @@ pub enum GpuCommands {
         #[arg(long)]
         fan: Option<u32>,
     },
+    /// Show or change the firmware fan curve (AMD RDNA3+, active when the daemon is stopped)
+    FanCurve {
+        /// DRM card number (as in GPU controller ids amd:CARD:FAN)
+        index: u32,
+        /// New curve as TEMP:PERCENT pairs, e.g. 40:20,60:40,80:80,90:100
+        #[arg(long, conflicts_with = "reset")]
+        set: Option<String>,
+        /// Restore the factory curve
+        #[arg(long)]
+        reset: bool,
+    },
+    /// Show or change the power limit (changes need advanced.gpu_power_limits_enabled)
+    PowerLimit {
+        /// GPU index
+        index: u32,
+        /// New power limit in watts
+        #[arg(long, conflicts_with = "reset")]
+        set: Option<u32>,
+        /// Restore the default power limit
+        #[arg(long)]
+        reset: bool,
+    },
 }
 
 // ============================================================================
@@ -406,13 +787,13 @@ pub enum SettingsCommands {
     /// Show all current settings as JSON
     Show,
     /// Get a specific setting value
-    #[command(after_help = "AVAILABLE KEYS:\n  general.start_at_boot\n  general.poll_interval_ms\n  general.apply_curves_on_startup\n  general.default_page\n  display.temperature_unit\n  display.fan_control_metric\n  display.show_tray_icon\n  display.graph_style\n  display.color_scheme\n  display.display_backend\n  display.window_manager\n  advanced.ec_direct_control_enabled")]
+    #[command(after_help = "AVAILABLE KEYS:\n  general.start_at_boot\n  general.poll_interval_ms\n  general.apply_curves_on_startup\n  general.default_page\n  general.config_format\n  general.adaptive_polling.enabled\n  general.adaptive_polling.min_interval_ms\n  general.adaptive_polling.max_interval_ms\n  general.adaptive_polling.slope_threshold\n  general.alarm_boost.enabled\n  general.alarm_boost.cooldown_secs\n  display.temperature_unit\n  display.fan_control_metric\n  display.show_tray_icon\n  display.graph_style\n  display.color_scheme\n  display.display_backend\n  display.window_manager\n  advanced.ec_direct_control_enabled\n  advanced.ec_write_guard\n  advanced.ec_dry_run\n  advanced.gpu_power_limits_enabled")]
     Get {
         /// Setting key (e.g., display.temperature_unit)
         key: String,
     },
     /// Set a setting value
-    #[command(after_help = "EXAMPLES:\n  hyperfan settings set display.temperature_unit fahrenheit\n  hyperfan settings set general.poll_interval_ms 200\n  hyperfan settings set display.color_scheme dark")]
+    #[command(after_help = "EXAMPLES:\n  hyperfan settings set display.temperature_unit fahrenheit\n  hyperfan settings set general.poll_interval_ms 200\n  hyperfan settings set display.color_scheme dark\n  hyperfan settings set general.config_format toml")]
     Set(SetSettingArgs),
     /// Reset all settings to defaults
     Reset {
@@ -427,7 +808,7 @@ pub enum SettingsCommands {
         /// Output file path
         path: String,
     },
-    /// Import settings from JSON file
+    /// Import settings from a JSON or TOML file
     Import {
         /// Input file path
         path: String,
@@ -455,19 +836,62 @@ pub enum ServiceCommands {
     /// Uninstall the hyperfand service
     Uninstall,
     /// Reload daemon configuration
-    Reload,
+    Reload {
+        /// Reload only this part, so other clients re-read only it
+        #[arg(long, value_parser = ["settings", "curves", "pairings"])]
+        section: Option<String>,
+    },
     /// List hardware via daemon
     ListHardware,
     /// Detect fan mappings via daemon
     DetectMappings,
     /// Start the service
     Start,
+    /// Start the service at boot, and now
+    Enable,
     /// Stop the service
     Stop,
     /// Restart the service
     Restart,
     /// Check if daemon is available
     Ping,
+    /// Show recent daemon log entries (no journal access needed)
+    Logs {
+        /// Only show entries at this level or more severe
+        #[arg(long, value_parser = ["error", "warn", "info", "debug", "trace"])]
+        level: Option<String>,
+        /// Keep printing new entries
+        #[arg(short, long)]
+        follow: bool,
+    },
+    /// Print daemon events (thermal alerts, fan failures, hardware changes) as they happen
+    Events {
+        /// Print the events the daemon retained before new ones
+        #[arg(long)]
+        replay: bool,
+    },
+    /// Show or change the daemon's per-user rate limit
+    RateLimit {
+        /// Tokens refilled per 10s window
+        #[arg(long)]
+        limit: Option<u32>,
+        /// Most tokens a client can spend at once
+        #[arg(long)]
+        burst: Option<u32>,
+        /// Tokens a request class costs, as CLASS=TOKENS (read, scan, write, heavy)
+        #[arg(long, value_name = "CLASS=TOKENS")]
+        cost: Vec<String>,
+    },
+    /// Show or switch dry-run mode (curves run, PWM writes are only logged)
+    DryRun {
+        /// New state (default: show the current one)
+        #[arg(value_parser = ["on", "off"])]
+        state: Option<String>,
+    },
+    /// Check for other fan control software fighting the daemon
+    Conflicts,
+    /// Show the control loop status and sensor readings rejected as implausible
+    ControlStatus,
 }
 
 // ============================================================================
@@ -520,6 +944,17 @@ pub enum FanCommands {
         /// PWM path (sysfs path or gpu virtual id)
         path: String,
     },
+
+    /// List the PWM overrides in effect and who set them
+    Overrides,
+
+    /// Sweep a PWM output and record its fan's RPM response (about a minute)
+    Calibrate {
+        /// PWM control path
+        pwm_path: String,
+        /// Fan input path driven by this PWM
+        fan_path: String,
+    },
 }
 
 // ============================================================================
@@ -530,36 +965,64 @@ pub fn run_cli(cli: &Cli) -> Result<bool, Box<dyn std::error::Error>> {
     match &cli.command {
         None | Some(Commands::Gui) => Ok(false), // Continue to GUI
         Some(cmd) => {
-            execute_command(cmd)?;
+            execute_command(cmd, cli.json)?;
             Ok(true) // CLI handled, exit
         }
     }
 }
 
-fn execute_command(cmd: &Commands) -> Result<(), Box<dyn std::error::Error>> {
+fn execute_command(cmd: &Commands, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     match cmd {
         Commands::Gui => Ok(()),
-        Commands::Status => cmd_status(),
-        Commands::Hardware(sub) => cmd_hardware(sub),
-        Commands::Curves(sub) => cmd_curves(sub),
+        Commands::Status => cmd_status(json),
+        Commands::Doctor => cmd_doctor(json),
+        Commands::BugReport { output } => {
+            let output = output.clone().unwrap_or_else(|| hf_core::default_bundle_name().into());
+            let path = hf_core::create_support_bundle(&output)?;
+            println!("Bug report written to {}", path.display());
+            println!("Check its contents before attaching it to an issue.");
+            Ok(())
+        }
+        Commands::Hardware(sub) => cmd_hardware(sub, json),
+        Commands::Curves(sub) => cmd_curves(sub, json),
         Commands::Graphs(sub) => cmd_graphs(sub),
-        Commands::Pairs(sub) => cmd_pairs(sub),
+        Commands::Pairs(sub) => cmd_pairs(sub, json),
         Commands::Sensors(sub) => cmd_sensors(sub),
         Commands::Bindings(sub) => cmd_bindings(sub),
+        Commands::Bundle(sub) => cmd_bundle(sub),
         Commands::Settings(sub) => cmd_settings(sub),
         Commands::Service(sub) => cmd_service(sub),
-        Commands::Fan(sub) => cmd_fan(sub),
+        Commands::Fan(sub) => cmd_fan(sub, json),
         Commands::Pairings(sub) => cmd_pairings(sub),
+        Commands::Profile(sub) => cmd_profile(sub, json),
         Commands::Gpu(sub) => cmd_gpu(sub),
         Commands::System(sub) => cmd_system(sub),
+        Commands::SetPwm { path, percent } => set_fan_percent(path, *percent),
+        Commands::Log { duration, output, interval, max_size, stop } => {
+            cmd_log(*duration, output.as_deref(), *interval, *max_size, *stop, json)
+        }
+        Commands::Completions { shell } => {
+            print!("{}", completions::generate(*shell, Cli::command()));
+            Ok(())
+        }
     }
 }
 
+/// Print a value as pretty JSON (for --json)
+fn print_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
 // ============================================================================
 // Status Command
 // ============================================================================
 
-fn cmd_status() -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_status(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if json {
+        return cmd_status_json();
+    }
+
     println!("Hyperfan Status");
     println!("===============");
     println!();
@@ -602,23 +1065,144 @@ fn cmd_status() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+fn cmd_status_json() -> Result<(), Box<dyn std::error::Error>> {
+    let daemon_available = hf_core::is_daemon_available();
+    let mut status = serde_json::json!({
+        "service": hf_core::get_service_status(),
+        "daemon": daemon_available,
+    });
+
+    if daemon_available {
+        if let Ok(hw) = hf_core::daemon_list_hardware() {
+            status["hardware"] = serde_json::json!({
+                "chips": hw.chips.len(),
+                "temps": hw.chips.iter().map(|c| c.temperatures.len()).sum::<usize>(),
+                "fans": hw.chips.iter().map(|c| c.fans.len()).sum::<usize>(),
+                "pwms": hw.chips.iter().map(|c| c.pwms.len()).sum::<usize>(),
+            });
+        }
+        if let Ok(gpus) = hf_core::daemon_list_gpus() {
+            status["gpus"] = gpus.len().into();
+        }
+        if let Ok(profile) = hf_core::daemon_get_active_profile() {
+            status["profile"] = profile.active.into();
+        }
+    }
+    if let Ok(curves) = hf_core::load_curves() {
+        status["curves"] = curves.all().len().into();
+    }
+    if let Ok(settings) = hf_core::load_settings() {
+        status["active_pairs"] = settings.active_pairs.iter().filter(|p| p.active).count().into();
+    }
+
+    print_json(&status)
+}
+
+// ============================================================================
+// Doctor Command
+// ============================================================================
+
+fn cmd_doctor(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let checks = hf_core::run_diagnostics();
+    // Read directly: the report must work when the daemon is the problem
+    let chips = hf_core::enumerate_hwmon_chips().unwrap_or_default();
+    let kernel = hf_core::get_system_summary().map(|s| s.kernel_version).unwrap_or_default();
+
+    if json {
+        let chips: Vec<_> = chips.iter().map(|c| serde_json::json!({
+            "name": c.name,
+            "path": c.path,
+            "temps": c.temperatures.len(),
+            "fans": c.fans.len(),
+            "pwms": c.pwms.len(),
+        })).collect();
+        return print_json(&serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "os": hf_core::get_os_name(),
+            "kernel": kernel,
+            "init_system": hf_core::detect_init_system().to_string(),
+            "checks": checks,
+            "chips": chips,
+        }));
+    }
+
+    println!("Hyperfan Doctor");
+    println!("===============");
+    println!();
+    println!("Version:     {}", env!("CARGO_PKG_VERSION"));
+    println!("OS:          {} {}", hf_core::get_os_name(), kernel);
+    println!("Init system: {}", hf_core::detect_init_system());
+    println!();
+
+    println!("Checks:");
+    for check in &checks {
+        let status = match check.status {
+            hf_core::DiagnosticStatus::Ok => "[ ok ]",
+            hf_core::DiagnosticStatus::Warning => "[warn]",
+            hf_core::DiagnosticStatus::Error => "[FAIL]",
+            hf_core::DiagnosticStatus::Skipped => "[skip]",
+        };
+        let mut summary = check.summary.lines();
+        println!("  {} {}: {}", status, check.title, summary.next().unwrap_or_default());
+        for line in summary {
+            println!("         {}", line);
+        }
+        if check.status == hf_core::DiagnosticStatus::Ok {
+            continue;
+        }
+        for line in check.hint.iter().flat_map(|h| h.lines()) {
+            println!("         -> {}", line);
+        }
+        let command = match check.fix {
+            Some(hf_core::DiagnosticFix::InstallService) => Some("hyperfan service install"),
+            Some(hf_core::DiagnosticFix::StartService) => Some("hyperfan service start"),
+            Some(hf_core::DiagnosticFix::EnableService) => Some("hyperfan service enable"),
+            _ => None,
+        };
+        if let Some(command) = command {
+            println!("         -> Fix: {}", command);
+        }
+    }
+    println!();
+
+    println!("Chips ({}):", chips.len());
+    for chip in &chips {
+        println!(
+            "  {} ({}): {} temps, {} fans, {} PWMs",
+            chip.name,
+            chip.path.display(),
+            chip.temperatures.len(),
+            chip.fans.len(),
+            chip.pwms.len()
+        );
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // Hardware Commands
 // ============================================================================
 
-fn cmd_hardware(cmd: &HardwareCommands) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_hardware(cmd: &HardwareCommands, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     match cmd {
         HardwareCommands::Chips => {
             if !hf_core::is_daemon_available() {
                 return Err("Daemon not available".into());
             }
             let hw = hf_core::daemon_list_hardware()?;
+            if json {
+                return print_json(&hw.chips);
+            }
             println!("Hwmon Chips ({}):", hw.chips.len());
             for chip in &hw.chips {
                 println!("  {} ({})", chip.name, chip.path);
                 println!("    Temps: {}", chip.temperatures.len());
                 println!("    Fans: {}", chip.fans.len());
                 println!("    PWMs: {}", chip.pwms.len());
+                if !chip.sensors.is_empty() {
+                    println!("    Other sensors: {}", chip.sensors.len());
+                }
             }
         }
         HardwareCommands::Temps => {
@@ -626,11 +1210,24 @@ fn cmd_hardware(cmd: &HardwareCommands) -> Result<(), Box<dyn std::error::Error>
                 return Err("Daemon not available".into());
             }
             let hw = hf_core::daemon_list_hardware()?;
+            if json {
+                let temps: Vec<_> = hw.chips.iter()
+                    .flat_map(|c| c.temperatures.iter().map(move |t| serde_json::json!({ "chip": c.name, "sensor": t })))
+                    .collect();
+                return print_json(&temps);
+            }
             println!("Temperature Sensors:");
             for chip in &hw.chips {
                 for temp in &chip.temperatures {
                     let label = temp.label.as_deref().unwrap_or(&temp.name);
-                    println!("  {} / {}: {:.1}°C ({})", chip.name, label, temp.value, temp.path);
+                    let mut limits: Vec<String> = Vec::new();
+                    limits.extend(temp.max.map(|v| format!("high {:.0}°C", v)));
+                    limits.extend(temp.crit.map(|v| format!("crit {:.0}°C", v)));
+                    if temp.crit_alarm {
+                        limits.push("ALARM".to_string());
+                    }
+                    let limits = if limits.is_empty() { String::new() } else { format!(" [{}]", limits.join(", ")) };
+                    println!("  {} / {}: {:.1}°C{} ({})", chip.name, label, temp.value, limits, temp.path);
                 }
             }
         }
@@ -639,6 +1236,12 @@ fn cmd_hardware(cmd: &HardwareCommands) -> Result<(), Box<dyn std::error::Error>
                 return Err("Daemon not available".into());
             }
             let hw = hf_core::daemon_list_hardware()?;
+            if json {
+                let fans: Vec<_> = hw.chips.iter()
+                    .flat_map(|c| c.fans.iter().map(move |f| serde_json::json!({ "chip": c.name, "sensor": f })))
+                    .collect();
+                return print_json(&fans);
+            }
             println!("Fan Sensors:");
             for chip in &hw.chips {
                 for fan in &chip.fans {
@@ -653,12 +1256,73 @@ fn cmd_hardware(cmd: &HardwareCommands) -> Result<(), Box<dyn std::error::Error>
                 return Err("Daemon not available".into());
             }
             let hw = hf_core::daemon_list_hardware()?;
+            if json {
+                let pwms: Vec<_> = hw.chips.iter()
+                    .flat_map(|c| c.pwms.iter().map(move |p| serde_json::json!({ "chip": c.name, "pwm": p })))
+                    .collect();
+                return print_json(&pwms);
+            }
             println!("PWM Controllers:");
             for chip in &hw.chips {
                 for pwm in &chip.pwms {
                     let label = pwm.name.as_str();
                     let pct = pwm.value as f32 / 255.0 * 100.0;
-                    println!("  {} / {}: {:.0}% ({})", chip.name, label, pct, pwm.path);
+                    match pwm.max_state {
+                        Some(max) => println!("  {} / {}: {:.0}%, cooling device 0-{} ({})", chip.name, label, pct, max, pwm.path),
+                        None if pwm.dc_mode => println!("  {} / {}: {:.0}%, DC/3-pin ({})", chip.name, label, pct, pwm.path),
+                        None => println!("  {} / {}: {:.0}% ({})", chip.name, label, pct, pwm.path),
+                    }
+                }
+            }
+        }
+        HardwareCommands::Sensors { kind } => {
+            let filter = match kind.as_deref() {
+                None => None,
+                Some(name) => Some(
+                    serde_json::from_value::<hf_core::DaemonSensorKind>(serde_json::json!(name.to_lowercase()))
+                        .map_err(|_| format!("Unknown sensor kind '{}' (expected voltage, current, power or humidity)", name))?,
+                ),
+            };
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let hw = hf_core::daemon_list_hardware()?;
+            let matches = |s: &&hf_core::DaemonHwSensor| filter.is_none_or(|k| k == s.kind);
+            if json {
+                let sensors: Vec<_> = hw.chips.iter()
+                    .flat_map(|c| c.sensors.iter().filter(matches).map(move |s| serde_json::json!({ "chip": c.name, "sensor": s })))
+                    .collect();
+                return print_json(&sensors);
+            }
+            println!("Sensors:");
+            for chip in &hw.chips {
+                for sensor in chip.sensors.iter().filter(matches) {
+                    let label = sensor.label.as_deref().unwrap_or(&sensor.name);
+                    println!("  {} / {}: {:.3} {} ({})", chip.name, label, sensor.value, sensor.kind.unit(), sensor.path);
+                }
+            }
+        }
+        HardwareCommands::Alarms => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let hw = hf_core::daemon_list_hardware()?;
+            if json {
+                let alarms: Vec<_> = hw.chips.iter()
+                    .flat_map(|c| c.alarms.iter().map(move |a| serde_json::json!({ "chip": c.name, "alarm": a })))
+                    .collect();
+                return print_json(&alarms);
+            }
+            println!("Alarms:");
+            for chip in &hw.chips {
+                for alarm in &chip.alarms {
+                    let state = if alarm.active { "ALARM" } else { "ok" };
+                    let beep = match alarm.beep {
+                        Some(true) => ", beeps",
+                        Some(false) => ", silent",
+                        None => "",
+                    };
+                    println!("  {} / {}: {}{} ({})", chip.name, alarm.name, state, beep, alarm.path);
                 }
             }
         }
@@ -667,6 +1331,9 @@ fn cmd_hardware(cmd: &HardwareCommands) -> Result<(), Box<dyn std::error::Error>
                 return Err("Daemon not available".into());
             }
             let gpus = hf_core::daemon_list_gpus()?;
+            if json {
+                return print_json(&gpus);
+            }
             println!("GPUs ({}):", gpus.len());
             for gpu in &gpus {
                 println!("  [{}] {} ({})", gpu.index, gpu.name, gpu.vendor);
@@ -679,13 +1346,18 @@ fn cmd_hardware(cmd: &HardwareCommands) -> Result<(), Box<dyn std::error::Error>
             let json = hf_core::snapshot_to_json(&snapshot)?;
             println!("{}", json);
         }
-        HardwareCommands::Detect { heuristic } => {
-            let _ = heuristic; // daemon currently implements heuristic detection
+        HardwareCommands::Detect { heuristic, passive } => {
             if !hf_core::is_daemon_available() {
                 return Err("Daemon not available".into());
             }
-            println!("Detecting fan mappings via daemon...");
-            let mappings = hf_core::daemon_detect_fan_mappings()?;
+            let mappings = if *heuristic {
+                println!("Detecting fan mappings via daemon...");
+                hf_core::daemon_detect_fan_mappings()?
+            } else if *passive {
+                run_detection_job(hf_core::DaemonDetectionStrategy::Passive)?
+            } else {
+                run_detection_job(hf_core::DaemonDetectionStrategy::Active)?
+            };
             println!("Found {} mappings:", mappings.len());
             for m in &mappings {
                 println!("  {} -> {} (confidence: {:.0}%)", m.pwm_path, m.fan_path, m.confidence * 100.0);
@@ -711,19 +1383,110 @@ fn cmd_hardware(cmd: &HardwareCommands) -> Result<(), Box<dyn std::error::Error>
             let completed = hf_core::is_detection_completed()?;
             println!("Detection completed: {}", completed);
         }
+        HardwareCommands::Filter => {
+            let filter = hf_core::load_settings()?.controller_filter;
+            if json {
+                return print_json(&filter);
+            }
+            println!("Controller filter ({:?}, {} entries):", filter.mode, filter.entries.len());
+            for (i, entry) in filter.entries.iter().enumerate() {
+                let channel = entry.channel.as_deref().unwrap_or("all channels");
+                println!("  [{}] {} {} ({})", i, entry.label, channel, entry.chip_id);
+            }
+        }
+        HardwareCommands::FilterAdd { path } => {
+            let path = std::path::Path::new(path);
+            let hwmon_dir = if path.is_dir() { path } else { path.parent().unwrap_or(path) };
+            let chip_name = std::fs::read_to_string(hwmon_dir.join("name")).unwrap_or_default();
+            let entry = hf_core::controller_filter_entry(path, chip_name.trim())
+                .ok_or_else(|| format!("{} is not a hwmon chip or PWM output", path.display()))?;
+            let description = format!("{} {}", entry.label, entry.channel.as_deref().unwrap_or("(all channels)"));
+            hf_core::add_controller_filter_entry(entry)?;
+            reload_daemon_config();
+            println!("Added to controller filter: {}", description);
+        }
+        HardwareCommands::FilterRemove { entry } => {
+            let filter = hf_core::load_settings()?.controller_filter;
+            let removed = filter.entries.get(*entry)
+                .ok_or_else(|| format!("No controller filter entry {}", entry))?;
+            hf_core::remove_controller_filter_entry(&removed.chip_id, removed.channel.as_deref())?;
+            reload_daemon_config();
+            println!("Removed from controller filter: {}", removed.label);
+        }
+        HardwareCommands::FilterMode { mode } => {
+            let mode = match mode.as_str() {
+                "blacklist" => hf_core::ControllerFilterMode::Blacklist,
+                "allowlist" => hf_core::ControllerFilterMode::Allowlist,
+                _ => return Err(format!("Unknown filter mode '{}' (blacklist or allowlist)", mode).into()),
+            };
+            hf_core::set_controller_filter_mode(mode)?;
+            reload_daemon_config();
+            println!("Controller filter mode: {:?}", mode);
+        }
     }
     Ok(())
 }
 
+/// Tell a running daemon to re-read the settings
+fn reload_daemon_config() {
+    if hf_core::is_daemon_available() {
+        if let Err(e) = hf_core::daemon_reload_config() {
+            eprintln!("Warning: Failed to signal daemon reload: {}", e);
+        }
+    }
+}
+
+/// Run detection as a daemon job, printing each PWM as it is tested
+fn run_detection_job(
+    strategy: hf_core::DaemonDetectionStrategy,
+) -> Result<Vec<hf_core::DaemonFanMapping>, Box<dyn std::error::Error>> {
+    let job = hf_core::daemon_start_detection(strategy)?;
+    match strategy {
+        hf_core::DaemonDetectionStrategy::Active => {
+            println!("Detection job {} started - fans will speed up and slow down", job.job_id)
+        }
+        hf_core::DaemonDetectionStrategy::Passive => {
+            println!("Passive detection job {} started - watching fan speeds for a few minutes", job.job_id)
+        }
+    }
+    let mut last_step = None;
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        let progress = hf_core::daemon_get_detection_progress(job.job_id)?;
+        match progress.state {
+            hf_core::DaemonDetectionJobState::Running => {
+                if let Some(pwm) = progress.current_pwm.as_ref().filter(|_| last_step != Some(progress.step)) {
+                    println!("  [{}/{}] Testing {}", progress.step, progress.total_steps, pwm);
+                    last_step = Some(progress.step);
+                } else if progress.strategy == hf_core::DaemonDetectionStrategy::Passive
+                    && progress.step % 30 == 0
+                    && last_step != Some(progress.step)
+                {
+                    println!("  [{}/{}] Observing", progress.step, progress.total_steps);
+                    last_step = Some(progress.step);
+                }
+            }
+            hf_core::DaemonDetectionJobState::Completed => return Ok(progress.mappings),
+            hf_core::DaemonDetectionJobState::Cancelled => return Err("Detection was cancelled".into()),
+            hf_core::DaemonDetectionJobState::Failed => {
+                return Err(format!("Detection failed: {}", progress.error.unwrap_or_default()).into());
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Curve Commands
 // ============================================================================
 
-fn cmd_curves(cmd: &CurveCommands) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_curves(cmd: &CurveCommands, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     match cmd {
         CurveCommands::List => {
             let store = hf_core::load_curves()?;
             let curves = store.all();
+            if json {
+                return print_json(&curves);
+            }
             println!("Fan Curves ({}):", curves.len());
             for curve in curves {
                 println!("  [{}] {} ({} points)", curve.id, curve.name, curve.points.len());
@@ -734,11 +1497,30 @@ fn cmd_curves(cmd: &CurveCommands) -> Result<(), Box<dyn std::error::Error>> {
             let curve = store.all().into_iter()
                 .find(|c| c.id == *id || c.name.to_lowercase() == id.to_lowercase())
                 .ok_or_else(|| format!("Curve not found: {}", id))?;
+            if json {
+                return print_json(&curve);
+            }
             println!("Curve: {} ({})", curve.name, curve.id);
+            if curve.interpolation == hf_core::CurveInterpolation::Monotone {
+                println!("Interpolation: monotone cubic");
+            }
             println!("Points:");
             for (temp, pct) in &curve.points {
                 println!("  {:.0}°C -> {:.0}%", temp, pct);
             }
+            if let Some(offset) = &curve.battery_offset {
+                println!("On battery: {:+.0}% fan speed, {:+.0}°C", offset.duty_percent, offset.temp_celsius);
+            }
+            if !curve.fallback_sources.is_empty() {
+                println!("Fallback sources: {}", curve.fallback_sources.join(" -> "));
+            }
+            let profiles = hf_core::load_settings()?.noise_profiles_for_curve(&curve.id);
+            if !profiles.is_empty() {
+                println!("Estimated noise ({} fans):", profiles.len());
+                for (temp, pct) in &curve.points {
+                    println!("  {:.0}°C -> {:.0} dBA", temp, hf_core::curve_dba(&profiles, *pct));
+                }
+            }
         }
         CurveCommands::Create { name, preset } => {
             let curve = match preset.to_lowercase().as_str() {
@@ -769,6 +1551,9 @@ fn cmd_curves(cmd: &CurveCommands) -> Result<(), Box<dyn std::error::Error>> {
                 delay_ms: hf_core::constants::curve::DEFAULT_DELAY_MS,
                 ramp_up_speed: hf_core::constants::curve::DEFAULT_RAMP_UP_SPEED,
                 ramp_down_speed: hf_core::constants::curve::DEFAULT_RAMP_DOWN_SPEED,
+                interpolation: hf_core::CurveInterpolation::default(),
+                battery_offset: None,
+                fallback_sources: Vec::new(),
             };
             hf_core::save_curve(persisted)?;
             if hf_core::is_daemon_available() {
@@ -839,25 +1624,284 @@ fn cmd_curves(cmd: &CurveCommands) -> Result<(), Box<dyn std::error::Error>> {
             }
             println!("Imported curves from: {}", path);
         }
-    }
-    Ok(())
-}
-
-// ============================================================================
-// Graph Commands
-// ============================================================================
-
-fn cmd_graphs(cmd: &GraphCommands) -> Result<(), Box<dyn std::error::Error>> {
-    match cmd {
-        GraphCommands::List => {
-            let graphs = hf_core::load_temp_graphs()?;
-            println!("Temperature Graphs ({}):", graphs.len());
-            for g in &graphs {
-                println!("  [{}] {} -> {}", g.id, g.name, g.temp_source_path);
+        CurveCommands::ExportTemplate { path, curves, profile, author, hardware, notes } => {
+            let metadata = hf_core::TemplateMetadata {
+                author: author.clone(),
+                hardware: hardware.clone(),
+                notes: notes.clone(),
+                created_at: 0,
+            };
+            let template = match profile {
+                Some(profile) => hf_core::export_profile_template(profile, metadata)?,
+                None => {
+                    let store = hf_core::load_curves()?;
+                    let ids = curves
+                        .iter()
+                        .map(|curve| {
+                            store.all().into_iter()
+                                .find(|c| c.id == *curve || c.name.to_lowercase() == curve.to_lowercase())
+                                .map(|c| c.id.clone())
+                                .ok_or_else(|| format!("Curve not found: {}", curve))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    hf_core::export_curves_template(&ids, metadata)?
+                }
+            };
+            template.save(std::path::Path::new(path))?;
+            println!("Exported {} curve(s) to: {}", template.curves.len(), path);
+        }
+        CurveCommands::ImportTemplate { path, map, yes, no_profile } => {
+            let template = hf_core::CurveTemplate::load(std::path::Path::new(path))?;
+            print_template_metadata(&template);
+            let mapping = choose_template_sensors(&template, map, *yes)?;
+            let imported = hf_core::import_template(&template, &mapping, !no_profile)?;
+            if hf_core::is_daemon_available() {
+                if let Err(e) = hf_core::daemon_reload_config() {
+                    eprintln!("Warning: Failed to signal daemon reload: {}", e);
+                }
+            }
+            println!("Imported {} curve(s)", imported.curve_ids.len());
+            if let Some(profile) = template.profile.as_ref().filter(|_| !no_profile) {
+                for pair in &imported.assigned_pairs {
+                    println!("  {} uses its template curve in profile {}", pair, profile.name);
+                }
+                for pair in &imported.unmatched_pairs {
+                    println!("  No pair named {} - assign its curve manually", pair);
+                }
             }
         }
-        GraphCommands::Add { name, source, label } => {
-            let id = format!("graph_{}", std::time::SystemTime::now()
+        CurveCommands::BatteryOffset { id, duty, temp, clear } => {
+            let store = hf_core::load_curves()?;
+            let curve = store.all().into_iter()
+                .find(|c| c.id == *id || c.name.to_lowercase() == id.to_lowercase())
+                .ok_or_else(|| format!("Curve not found: {}", id))?;
+            let offset = (!clear).then_some(hf_core::CurveOffset { duty_percent: *duty, temp_celsius: *temp });
+            hf_core::set_curve_battery_offset(&curve.id, offset)?;
+            if hf_core::is_daemon_available() {
+                if let Err(e) = hf_core::daemon_reload_config() {
+                    eprintln!("Warning: Failed to signal daemon reload: {}", e);
+                }
+            }
+            match offset {
+                Some(o) => println!("Curve {} on battery: {:+.0}% fan speed, {:+.0}°C", curve.name, o.duty_percent, o.temp_celsius),
+                None => println!("Cleared battery offset for curve {}", curve.name),
+            }
+        }
+        CurveCommands::Fallback { id, sources, clear } => {
+            let store = hf_core::load_curves()?;
+            let curve = store.all().into_iter()
+                .find(|c| c.id == *id || c.name.to_lowercase() == id.to_lowercase())
+                .ok_or_else(|| format!("Curve not found: {}", id))?;
+            let sources = if *clear { Vec::new() } else { sources.clone() };
+            hf_core::set_curve_fallback_sources(&curve.id, sources.clone())?;
+            if hf_core::is_daemon_available() {
+                if let Err(e) = hf_core::daemon_reload_config() {
+                    eprintln!("Warning: Failed to signal daemon reload: {}", e);
+                }
+            }
+            if sources.is_empty() {
+                println!("Cleared fallback sources for curve {}", curve.name);
+            } else {
+                println!("Curve {} falls back to: {}", curve.name, sources.join(" -> "));
+            }
+        }
+        CurveCommands::Interpolation { id, mode } => {
+            let store = hf_core::load_curves()?;
+            let mut curve = store.all().into_iter()
+                .find(|c| c.id == *id || c.name.to_lowercase() == id.to_lowercase())
+                .ok_or_else(|| format!("Curve not found: {}", id))?
+                .clone();
+            curve.interpolation = match mode.as_str() {
+                "monotone" => hf_core::CurveInterpolation::Monotone,
+                _ => hf_core::CurveInterpolation::Linear,
+            };
+            hf_core::save_curve(curve.clone())?;
+            if hf_core::is_daemon_available() {
+                if let Err(e) = hf_core::daemon_reload_config() {
+                    eprintln!("Warning: Failed to signal daemon reload: {}", e);
+                }
+            }
+            println!("Curve {} now uses {} interpolation", curve.name, mode);
+        }
+        CurveCommands::AutoTune { id, target, apply } => {
+            let store = hf_core::load_curves()?;
+            let curve = store.all().into_iter()
+                .find(|c| c.id == *id || c.name.to_lowercase() == id.to_lowercase())
+                .ok_or_else(|| format!("Curve not found: {}", id))?;
+            let progress = run_auto_tune_job(&curve.id, *target, json)?;
+            if json {
+                return print_json(&progress);
+            }
+
+            println!("Suggested curve for {}:", curve.name);
+            for (temp, pct) in &progress.points {
+                println!("  {:.0}°C -> {:.0}%", temp, pct);
+            }
+            let save = *apply || {
+                print!("Save it over the current curve? [y/N] ");
+                std::io::Write::flush(&mut std::io::stdout())?;
+                let mut answer = String::new();
+                std::io::stdin().read_line(&mut answer)?;
+                answer.trim().eq_ignore_ascii_case("y")
+            };
+            if !save {
+                println!("Curve not changed");
+                return Ok(());
+            }
+            hf_core::update_curve_points(&curve.id, progress.points)?;
+            if let Err(e) = hf_core::daemon_reload_config() {
+                eprintln!("Warning: Failed to signal daemon reload: {}", e);
+            }
+            println!("Saved {}", curve.name);
+        }
+        CurveCommands::Quiet { id, max_dba, ceiling, dry_run } => {
+            let store = hf_core::load_curves()?;
+            let curve = store.all().into_iter()
+                .find(|c| c.id == *id || c.name.to_lowercase() == id.to_lowercase())
+                .ok_or_else(|| format!("Curve not found: {}", id))?;
+            let profiles = hf_core::load_settings()?.noise_profiles_for_curve(&curve.id);
+            if profiles.is_empty() {
+                return Err(format!("Curve {} doesn't drive any active fans", curve.name).into());
+            }
+            let max_duty = hf_core::max_duty_for_noise(&profiles, *max_dba);
+            let points = hf_core::limit_curve_noise(&curve.points, max_duty, *ceiling);
+
+            if !*dry_run {
+                hf_core::update_curve_points(&curve.id, points.clone())?;
+                if hf_core::is_daemon_available() {
+                    if let Err(e) = hf_core::daemon_reload_config() {
+                        eprintln!("Warning: Failed to signal daemon reload: {}", e);
+                    }
+                }
+            }
+            if json {
+                return print_json(&serde_json::json!({
+                    "curve_id": curve.id,
+                    "max_duty": max_duty,
+                    "points": points,
+                    "saved": !*dry_run,
+                }));
+            }
+            println!("{} fans stay under {:.0} dBA up to {:.0}% ({} fans)", curve.name, max_dba, max_duty, profiles.len());
+            for (temp, pct) in &points {
+                println!("  {:.0}°C -> {:.0}% (~{:.0} dBA)", temp, pct, hf_core::curve_dba(&profiles, *pct));
+            }
+            if *dry_run {
+                println!("Dry run: curve not saved");
+            }
+        }
+        CurveCommands::Apply { curve, pair_ids, offset, profile } => {
+            let store = hf_core::load_curves()?;
+            let selected = store.all().into_iter()
+                .find(|c| c.id == *curve || c.name.to_lowercase() == curve.to_lowercase())
+                .ok_or_else(|| format!("Curve not found: {}", curve))?;
+            let copy = hf_core::copy_curve_to_pairs(&selected.id, pair_ids, *offset, profile.as_deref())?;
+            if hf_core::is_daemon_available() {
+                if let Err(e) = hf_core::daemon_reload_config() {
+                    eprintln!("Warning: Failed to signal daemon reload: {}", e);
+                }
+            }
+            if json {
+                return print_json(&serde_json::json!({
+                    "curve_id": copy.curve_id,
+                    "curve_name": copy.curve_name,
+                    "created": copy.created,
+                    "pairs": copy.assigned_pairs,
+                }));
+            }
+            if copy.created {
+                println!("Created curve '{}' with ID: {}", copy.curve_name, copy.curve_id);
+            }
+            for pair in &copy.assigned_pairs {
+                match profile {
+                    Some(profile) => println!("Pair {} uses curve {} in profile {}", pair, copy.curve_name, profile),
+                    None => println!("Pair {} uses curve {}", pair, copy.curve_name),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_template_metadata(template: &hf_core::CurveTemplate) {
+    let meta = &template.metadata;
+    println!("Template with {} curve(s)", template.curves.len());
+    if let Some(author) = &meta.author {
+        println!("  Author:   {}", author);
+    }
+    if let Some(hardware) = &meta.hardware {
+        println!("  Hardware: {}", hardware);
+    }
+    if let Some(notes) = &meta.notes {
+        println!("  Notes:    {}", notes);
+    }
+    if let Some(profile) = &template.profile {
+        println!("  Profile:  {} ({} pairs)", profile.name, profile.pairs.len());
+    }
+}
+
+/// Local sensor for every template sensor: `--map` entries first, then the
+/// suggestion, asking on a terminal unless `yes` (or the match is exact)
+fn choose_template_sensors(
+    template: &hf_core::CurveTemplate,
+    map: &[String],
+    yes: bool,
+) -> Result<hf_core::SensorMapping, Box<dyn std::error::Error>> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let mut mapping = hf_core::SensorMapping::new();
+    for entry in map {
+        let (from, to) = entry.split_once('=').ok_or_else(|| format!("Expected FROM=TO, got: {}", entry))?;
+        mapping.insert(from.to_string(), to.to_string());
+    }
+
+    let chips = hf_core::enumerate_hwmon_chips()?;
+    let interactive = !yes && std::io::stdin().is_terminal();
+    for suggestion in hf_core::suggest_sensor_mapping(template, &chips) {
+        let source = &suggestion.source;
+        if mapping.contains_key(&source.path) {
+            continue;
+        }
+        let mut choice = suggestion.suggested_path.clone();
+        if interactive && !suggestion.exact {
+            print!(
+                "Sensor '{}' ({}) -> [{}]: ",
+                source.label,
+                source.path,
+                choice.as_deref().unwrap_or("none")
+            );
+            std::io::stdout().flush()?;
+            let mut line = String::new();
+            std::io::stdin().lock().read_line(&mut line)?;
+            let line = line.trim();
+            if !line.is_empty() {
+                choice = Some(line.to_string());
+            }
+        }
+        let local = choice.ok_or_else(|| {
+            format!("No local sensor for '{}' ({}) - pass --map {}=<path>", source.label, source.path, source.path)
+        })?;
+        println!("  {} -> {}", source.label, local);
+        mapping.insert(source.path.clone(), local);
+    }
+    Ok(mapping)
+}
+
+// ============================================================================
+// Graph Commands
+// ============================================================================
+
+fn cmd_graphs(cmd: &GraphCommands) -> Result<(), Box<dyn std::error::Error>> {
+    match cmd {
+        GraphCommands::List => {
+            let graphs = hf_core::load_temp_graphs()?;
+            println!("Temperature Graphs ({}):", graphs.len());
+            for g in &graphs {
+                println!("  [{}] {} -> {}", g.id, g.name, g.temp_source_path);
+            }
+        }
+        GraphCommands::Add { name, source, label } => {
+            let id = format!("graph_{}", std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_millis());
@@ -918,8 +1962,7 @@ fn cmd_settings(cmd: &SettingsCommands) -> Result<(), Box<dyn std::error::Error>
             println!("Exported settings to: {}", path);
         }
         SettingsCommands::Import { path } => {
-            let content = std::fs::read_to_string(path)?;
-            let settings: hf_core::AppSettings = serde_json::from_str(&content)?;
+            let settings: hf_core::AppSettings = hf_core::data::read_config(std::path::Path::new(path))?;
             hf_core::save_settings(&settings)?;
             println!("Imported settings from: {}", path);
         }
@@ -934,6 +1977,13 @@ fn get_setting_value(settings: &hf_core::AppSettings, key: &str) -> Result<Strin
         ["general", "poll_interval_ms"] => Ok(settings.general.poll_interval_ms.to_string()),
         ["general", "apply_curves_on_startup"] => Ok(settings.general.apply_curves_on_startup.to_string()),
         ["general", "default_page"] => Ok(settings.general.default_page.clone()),
+        ["general", "config_format"] => Ok(settings.general.config_format.extension().to_string()),
+        ["general", "adaptive_polling", "enabled"] => Ok(settings.general.adaptive_polling.enabled.to_string()),
+        ["general", "adaptive_polling", "min_interval_ms"] => Ok(settings.general.adaptive_polling.min_interval_ms.to_string()),
+        ["general", "adaptive_polling", "max_interval_ms"] => Ok(settings.general.adaptive_polling.max_interval_ms.to_string()),
+        ["general", "adaptive_polling", "slope_threshold"] => Ok(settings.general.adaptive_polling.slope_threshold.to_string()),
+        ["general", "alarm_boost", "enabled"] => Ok(settings.general.alarm_boost.enabled.to_string()),
+        ["general", "alarm_boost", "cooldown_secs"] => Ok(settings.general.alarm_boost.cooldown_secs.to_string()),
         ["display", "temperature_unit"] => Ok(settings.display.temperature_unit.clone()),
         ["display", "fan_control_metric"] => Ok(settings.display.fan_control_metric.clone()),
         ["display", "show_tray_icon"] => Ok(settings.display.show_tray_icon.to_string()),
@@ -942,6 +1992,9 @@ fn get_setting_value(settings: &hf_core::AppSettings, key: &str) -> Result<Strin
         ["display", "display_backend"] => Ok(settings.display.display_backend.clone()),
         ["display", "window_manager"] => Ok(settings.display.window_manager.clone()),
         ["advanced", "ec_direct_control_enabled"] => Ok(settings.advanced.ec_direct_control_enabled.to_string()),
+        ["advanced", "ec_write_guard"] => Ok(settings.advanced.ec_write_guard.to_string()),
+        ["advanced", "ec_dry_run"] => Ok(settings.advanced.ec_dry_run.to_string()),
+        ["advanced", "gpu_power_limits_enabled"] => Ok(settings.advanced.gpu_power_limits_enabled.to_string()),
         _ => Err(format!("Unknown setting: {}", key).into()),
     }
 }
@@ -954,6 +2007,18 @@ fn set_setting_value(key: &str, value: &str) -> Result<(), Box<dyn std::error::E
             ["general", "poll_interval_ms"] => settings.general.poll_interval_ms = value.parse().unwrap_or(100),
             ["general", "apply_curves_on_startup"] => settings.general.apply_curves_on_startup = value.parse().unwrap_or(true),
             ["general", "default_page"] => settings.general.default_page = value.to_string(),
+            ["general", "config_format"] => {
+                settings.general.config_format = match value {
+                    "toml" => hf_core::ConfigFormat::Toml,
+                    _ => hf_core::ConfigFormat::Json,
+                }
+            }
+            ["general", "adaptive_polling", "enabled"] => settings.general.adaptive_polling.enabled = value.parse().unwrap_or(false),
+            ["general", "adaptive_polling", "min_interval_ms"] => settings.general.adaptive_polling.min_interval_ms = value.parse().unwrap_or(250),
+            ["general", "adaptive_polling", "max_interval_ms"] => settings.general.adaptive_polling.max_interval_ms = value.parse().unwrap_or(3000),
+            ["general", "adaptive_polling", "slope_threshold"] => settings.general.adaptive_polling.slope_threshold = value.parse().unwrap_or(0.5),
+            ["general", "alarm_boost", "enabled"] => settings.general.alarm_boost.enabled = value.parse().unwrap_or(false),
+            ["general", "alarm_boost", "cooldown_secs"] => settings.general.alarm_boost.cooldown_secs = value.parse().unwrap_or(60),
             ["display", "temperature_unit"] => settings.display.temperature_unit = value.to_string(),
             ["display", "fan_control_metric"] => settings.display.fan_control_metric = value.to_string(),
             ["display", "show_tray_icon"] => settings.display.show_tray_icon = value.parse().unwrap_or(false),
@@ -962,6 +2027,9 @@ fn set_setting_value(key: &str, value: &str) -> Result<(), Box<dyn std::error::E
             ["display", "display_backend"] => settings.display.display_backend = value.to_string(),
             ["display", "window_manager"] => settings.display.window_manager = value.to_string(),
             ["advanced", "ec_direct_control_enabled"] => settings.advanced.ec_direct_control_enabled = value.parse().unwrap_or(false),
+            ["advanced", "ec_write_guard"] => settings.advanced.ec_write_guard = value.parse().unwrap_or(true),
+            ["advanced", "ec_dry_run"] => settings.advanced.ec_dry_run = value.parse().unwrap_or(false),
+            ["advanced", "gpu_power_limits_enabled"] => settings.advanced.gpu_power_limits_enabled = value.parse().unwrap_or(false),
             _ => eprintln!("Unknown setting: {}", key),
         }
     })?;
@@ -983,6 +2051,14 @@ fn cmd_service(cmd: &ServiceCommands) -> Result<(), Box<dyn std::error::Error>>
             println!("Installed:   {}", installed);
             println!("Running:     {}", running);
             println!("Status:      {}", status);
+            let monitor_path = hf_core::get_monitor_socket_path();
+            if std::path::Path::new(monitor_path).exists() {
+                println!("Monitoring:  {}", monitor_path);
+            }
+            if hf_core::is_daemon_available() && hf_core::daemon_get_safe_mode().unwrap_or(false) {
+                println!("Safe mode:   on - fans are under firmware control; remove /etc/hyperfan/DISABLE");
+                println!("             and restart without --safe-mode to resume fan control");
+            }
         }
         ServiceCommands::Install => {
             hf_core::install_service()?;
@@ -996,6 +2072,10 @@ fn cmd_service(cmd: &ServiceCommands) -> Result<(), Box<dyn std::error::Error>>
             hf_core::start_service()?;
             println!("Service started");
         }
+        ServiceCommands::Enable => {
+            hf_core::enable_service()?;
+            println!("Service enabled");
+        }
         ServiceCommands::Stop => {
             hf_core::stop_service()?;
             println!("Service stopped");
@@ -1015,12 +2095,25 @@ fn cmd_service(cmd: &ServiceCommands) -> Result<(), Box<dyn std::error::Error>>
                 println!("Daemon not available");
             }
         }
-        ServiceCommands::Reload => {
+        ServiceCommands::Reload { section } => {
             if !hf_core::is_daemon_available() {
                 return Err("Daemon not available".into());
             }
-            hf_core::daemon_reload_config()?;
-            println!("Daemon configuration reloaded");
+            match section.as_deref() {
+                Some(name) => {
+                    let section = match name {
+                        "curves" => hf_core::DaemonConfigSection::Curves,
+                        "pairings" => hf_core::DaemonConfigSection::Pairings,
+                        _ => hf_core::DaemonConfigSection::Settings,
+                    };
+                    hf_core::daemon_reload_config_section(section)?;
+                    println!("Daemon {} reloaded", name);
+                }
+                None => {
+                    hf_core::daemon_reload_config()?;
+                    println!("Daemon configuration reloaded");
+                }
+            }
         }
         ServiceCommands::ListHardware => {
             if !hf_core::is_daemon_available() {
@@ -1045,21 +2138,219 @@ fn cmd_service(cmd: &ServiceCommands) -> Result<(), Box<dyn std::error::Error>>
                 println!("  {} -> {} (confidence: {:.0}%)", m.pwm_path, m.fan_path, m.confidence * 100.0);
             }
         }
+        ServiceCommands::Logs { level, follow } => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let min_level = match level.as_deref() {
+                Some("error") => Some(hf_core::DaemonLogLevel::Error),
+                Some("warn") => Some(hf_core::DaemonLogLevel::Warn),
+                Some("info") => Some(hf_core::DaemonLogLevel::Info),
+                Some("debug") => Some(hf_core::DaemonLogLevel::Debug),
+                Some(_) => Some(hf_core::DaemonLogLevel::Trace),
+                None => None,
+            };
+            let mut last_id = 0;
+            loop {
+                for entry in hf_core::daemon_get_logs(last_id, min_level)? {
+                    let time = chrono::DateTime::from_timestamp_millis(entry.timestamp_ms as i64)
+                        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                        .unwrap_or_default();
+                    println!("{} {:<5} [{}] {}", time, format!("{:?}", entry.level).to_uppercase(), entry.subsystem, entry.message);
+                    last_id = entry.id;
+                }
+                if !follow {
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_secs(1));
+            }
+        }
+        ServiceCommands::Events { replay } => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let mut subscription = hf_core::DaemonEventSubscription::new(replay.then_some(0));
+            let mut connected = true;
+            loop {
+                match subscription.next_event(std::time::Duration::from_secs(5)) {
+                    Ok(Some(event)) => {
+                        connected = true;
+                        let time = chrono::DateTime::from_timestamp_millis(event.timestamp_ms as i64)
+                            .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                            .unwrap_or_default();
+                        println!("{} #{} {}", time, event.seq, describe_event(&event.event));
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        // Reconnects on the next call and replays what was missed
+                        if connected {
+                            eprintln!("Lost daemon connection ({}) - reconnecting", e);
+                            connected = false;
+                        }
+                        std::thread::sleep(std::time::Duration::from_secs(2));
+                    }
+                }
+            }
+        }
+        ServiceCommands::RateLimit { limit, burst, cost } => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let mut config = hf_core::daemon_get_rate_limit_config()?;
+            if limit.is_some() || burst.is_some() || !cost.is_empty() {
+                let mut costs = config.costs;
+                for entry in cost {
+                    set_request_cost(&mut costs, entry)?;
+                }
+                let costs = (!cost.is_empty()).then_some(costs);
+                config = hf_core::daemon_set_rate_limit_config(limit.unwrap_or(config.limit), *burst, costs)?;
+            }
+            println!("Refill: {} tokens per 10s", config.limit);
+            println!("Burst:  {} tokens", config.burst);
+            println!("Costs:  read {}, scan {}, write {}, heavy {}",
+                     config.costs.read, config.costs.scan, config.costs.write, config.costs.heavy);
+        }
+        ServiceCommands::DryRun { state } => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let enabled = match state.as_deref() {
+                Some(state) => hf_core::daemon_set_dry_run(state == "on")?,
+                None => hf_core::daemon_get_dry_run()?,
+            };
+            if enabled {
+                println!("Dry run: on - intended PWM writes are logged (see `hyperfan service logs`), fans are under firmware control");
+            } else {
+                println!("Dry run: off");
+            }
+        }
+
+        ServiceCommands::ControlStatus => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let status = hf_core::daemon_get_control_status()?;
+            println!("Controlled outputs: {}", status.controlled_outputs);
+            println!("Dry run:            {}", if status.dry_run { "on" } else { "off" });
+            println!("Safe mode:          {}", if status.safe_mode { "on" } else { "off" });
+            if status.implausible_readings.is_empty() {
+                println!("No implausible sensor readings rejected");
+            } else {
+                println!("Rejected sensor readings:");
+                for source in &status.implausible_readings {
+                    println!("  {} - {} out of range, {} jumps (last {})",
+                             source.path, source.out_of_range, source.jumps, source.last_rejected);
+                }
+            }
+        }
+
+        ServiceCommands::Conflicts => {
+            // The daemon sees enable flips too; without it only processes can be checked
+            let report = if hf_core::is_daemon_available() {
+                hf_core::daemon_get_competing_software()?
+            } else {
+                hf_core::DaemonCompetingSoftwareReport {
+                    software: hf_core::detect_competing_software().into_iter().map(Into::into).collect(),
+                    enable_flips: Vec::new(),
+                }
+            };
+            if report.is_empty() {
+                println!("No other fan control software found");
+                return Ok(());
+            }
+            for software in &report.software {
+                println!("{} is running (pid {:?})", software.name, software.pids);
+                println!("  disable it: {}", software.disable_hint);
+            }
+            for flip in &report.enable_flips {
+                println!(
+                    "{}: enable mode changed to {} by another program ({} times, last {}s ago)",
+                    flip.pwm_path, flip.mode, flip.count, flip.last_seen_secs_ago
+                );
+            }
+        }
     }
     Ok(())
 }
 
+/// Apply a CLASS=TOKENS request cost
+fn set_request_cost(costs: &mut hf_core::DaemonRequestCosts, entry: &str) -> Result<(), String> {
+    let (class, tokens) = entry.split_once('=')
+        .ok_or_else(|| format!("Expected CLASS=TOKENS, got '{}'", entry))?;
+    let tokens: u32 = tokens.trim().parse()
+        .map_err(|_| format!("Invalid token count '{}'", tokens))?;
+    let slot = match class.trim() {
+        "read" => &mut costs.read,
+        "scan" => &mut costs.scan,
+        "write" => &mut costs.write,
+        "heavy" => &mut costs.heavy,
+        other => return Err(format!("Unknown request class '{}' (read, scan, write, heavy)", other)),
+    };
+    *slot = tokens;
+    Ok(())
+}
+
+/// One-line description of a daemon event
+fn describe_event(event: &hf_core::DaemonEvent) -> String {
+    match event {
+        hf_core::DaemonEvent::ThermalAlert { name, celsius, limit_celsius, active, .. } => {
+            if *active {
+                format!("THERMAL  '{}' at {:.1}°C (limit {:.1}°C) - fans at full speed", name, celsius, limit_celsius)
+            } else {
+                format!("THERMAL  '{}' back to {:.1}°C", name, celsius)
+            }
+        }
+        hf_core::DaemonEvent::FanFailure(alert) => match alert.kind {
+            hf_core::DaemonFanAlertKind::Stalled => format!("FAN      '{}' stalled ({})", alert.name, alert.fan_path),
+            hf_core::DaemonFanAlertKind::Recovered => format!("FAN      '{}' recovered ({})", alert.name, alert.fan_path),
+            hf_core::DaemonFanAlertKind::WriteFailed => format!("FAN      '{}' ignores PWM writes ({})", alert.name, alert.pwm_path),
+            hf_core::DaemonFanAlertKind::WriteRecovered => format!("FAN      '{}' accepts PWM writes again ({})", alert.name, alert.pwm_path),
+        },
+        hf_core::DaemonEvent::HardwareChanged(change) => {
+            let kind = match change.kind {
+                hf_core::DaemonHardwareChangeKind::Added => "added",
+                hf_core::DaemonHardwareChangeKind::Removed => "removed",
+            };
+            format!("HARDWARE {} {} ({})", change.name, kind, change.subsystem)
+        }
+        hf_core::DaemonEvent::ProfileSwitched { previous, name, scheduled } => {
+            format!("PROFILE  '{}' -> '{}'{}", previous, name, if *scheduled { " (schedule)" } else { "" })
+        }
+        hf_core::DaemonEvent::DriftDetected { corrected, degraded, unsafe_bindings } => {
+            format!("DRIFT    {} corrected, {} degraded, {} unsafe bindings", corrected, degraded, unsafe_bindings)
+        }
+        hf_core::DaemonEvent::ForeignPwmWrite { name, pwm_path, expected, found, policy } => {
+            format!("CONFLICT '{}' set to {} by another program, expected {} ({}, {:?})", name, found, expected, pwm_path, policy)
+        }
+        hf_core::DaemonEvent::TemperatureSourceSwitched { name, from, to, reason } => {
+            format!("SENSOR   '{}' switched from {} to {} ({})", name, from, to, reason)
+        }
+        hf_core::DaemonEvent::ConfigChanged { section, revision } => {
+            let section = match section {
+                hf_core::DaemonConfigSection::Settings => "settings",
+                hf_core::DaemonConfigSection::Curves => "curves",
+                hf_core::DaemonConfigSection::Pairings => "pairings",
+                hf_core::DaemonConfigSection::All => "configuration",
+            };
+            format!("CONFIG   {} changed (revision {})", section, revision)
+        }
+    }
+}
+
 // ============================================================================
 // Fan Commands
 // ============================================================================
 
-fn cmd_fan(cmd: &FanCommands) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_fan(cmd: &FanCommands, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     match cmd {
         FanCommands::Read { path } => {
             if !hf_core::is_daemon_available() {
                 return Err("Daemon not available".into());
             }
             let rpm = hf_core::daemon_read_fan_rpm(path)?;
+            if json {
+                return print_json(&serde_json::json!({ "path": path, "rpm": rpm }));
+            }
             println!("{} RPM", rpm);
         }
         FanCommands::ReadPwm { path } => {
@@ -1068,16 +2359,12 @@ fn cmd_fan(cmd: &FanCommands) -> Result<(), Box<dyn std::error::Error>> {
             }
             let value = hf_core::daemon_read_pwm(path)?;
             let percent = value as f32 / 255.0 * 100.0;
-            println!("{} ({:.1}%)", value, percent);
-        }
-        FanCommands::Set { path, percent } => {
-            if !hf_core::is_daemon_available() {
-                return Err("Daemon not available".into());
+            if json {
+                return print_json(&serde_json::json!({ "path": path, "value": value, "percent": percent }));
             }
-            let pwm_value = (*percent / 100.0 * 255.0) as u8;
-            hf_core::daemon_set_pwm(path, pwm_value)?;
-            println!("Set {} to {:.1}%", path, percent);
+            println!("{} ({:.1}%)", value, percent);
         }
+        FanCommands::Set { path, percent } => set_fan_percent(path, *percent)?,
         FanCommands::Manual { path } => {
             if !hf_core::is_daemon_available() {
                 return Err("Daemon not available".into());
@@ -1108,18 +2395,252 @@ fn cmd_fan(cmd: &FanCommands) -> Result<(), Box<dyn std::error::Error>> {
             hf_core::daemon_clear_pwm_override(path)?;
             println!("Override cleared for {}", path);
         }
+
+        FanCommands::Overrides => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let overrides = hf_core::daemon_list_active_overrides()?;
+            if json {
+                return print_json(&overrides);
+            }
+            if overrides.is_empty() {
+                println!("No active overrides");
+                return Ok(());
+            }
+            println!("{:<40} {:>6} {:>10}  SET BY", "PWM", "VALUE", "REMAINING");
+            for ov in &overrides {
+                let set_by = match (ov.uid, ov.pid) {
+                    (Some(uid), Some(pid)) => format!("uid {} (pid {})", uid, pid),
+                    (Some(uid), None) => format!("uid {}", uid),
+                    _ => "daemon".to_string(),
+                };
+                println!(
+                    "{:<40} {:>5}% {:>9}s  {}",
+                    ov.pwm_path,
+                    hf_core::display::pwm_to_percent(ov.value as u32),
+                    ov.remaining_ms.div_ceil(1000),
+                    set_by
+                );
+            }
+        }
+
+        FanCommands::Calibrate { pwm_path, fan_path } => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            hf_core::daemon_calibrate_fan(pwm_path, fan_path)?;
+            if !json {
+                println!("Calibrating {} - the fan will run through its full speed range...", pwm_path);
+            }
+            let status = loop {
+                std::thread::sleep(std::time::Duration::from_secs(2));
+                let status = hf_core::daemon_get_fan_calibration(pwm_path)?;
+                if !status.running {
+                    break status;
+                }
+            };
+            if let Some(error) = status.error {
+                return Err(format!("Calibration failed: {}", error).into());
+            }
+            let calibration = status.calibration.ok_or("Calibration finished without a result")?;
+            if json {
+                return print_json(&calibration);
+            }
+            println!("PWM  RPM");
+            for (value, rpm) in &calibration.points {
+                println!("{:>3}  {}", value, rpm);
+            }
+            println!("Max RPM: {}", calibration.max_rpm);
+            println!("Stops below PWM {}", calibration.min_pwm);
+            if let Some(start) = calibration.start_pwm {
+                println!("Starts at PWM {}", start);
+            }
+            for (low, high) in &calibration.dead_zones {
+                println!("Dead zone: PWM {}-{}", low, high);
+            }
+            if hf_core::ignores_pwm(&calibration) {
+                println!("The fan runs at the same speed at every duty. If it is a 3-pin fan, switch its header to DC mode in the firmware setup.");
+            } else if let Some(floor) = hf_core::response_floor(&calibration) {
+                println!("No slower below PWM {}", floor);
+            }
+        }
     }
     Ok(())
 }
 
+/// Set a PWM output to a percentage via the daemon
+fn set_fan_percent(path: &str, percent: f32) -> Result<(), Box<dyn std::error::Error>> {
+    if !hf_core::is_daemon_available() {
+        return Err("Daemon not available".into());
+    }
+    let pwm_value = (percent / 100.0 * 255.0) as u8;
+    hf_core::daemon_set_pwm(path, pwm_value)?;
+    println!("Set {} to {:.1}%", path, percent);
+    Ok(())
+}
+
+// ============================================================================
+// Log Command
+// ============================================================================
+
+fn cmd_log(
+    duration_ms: Option<u64>,
+    output: Option<&std::path::Path>,
+    interval_ms: u32,
+    max_bytes: Option<u64>,
+    stop: bool,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !hf_core::is_daemon_available() {
+        return Err("Daemon not available".into());
+    }
+    if stop {
+        let info = hf_core::daemon_stop_recording()?;
+        if json {
+            return print_json(&info);
+        }
+        println!("Stopped after {} samples: {}", info.samples, info.path);
+        return Ok(());
+    }
+    let (Some(duration_ms), Some(output)) = (duration_ms, output) else {
+        return Err("--duration and --output are required".into());
+    };
+
+    let format = if output.extension().is_some_and(|ext| ext == "jsonl") {
+        hf_core::DaemonRecordingFormat::Jsonl
+    } else {
+        hf_core::DaemonRecordingFormat::Csv
+    };
+    let started = hf_core::daemon_start_recording_with_limits(interval_ms, format, Some(duration_ms), max_bytes)?;
+    if !json {
+        println!(
+            "Logging every {} ms for {}s to {} (`hyperfan log --stop` ends it early)",
+            interval_ms,
+            duration_ms / 1000,
+            output.display()
+        );
+    }
+
+    // The daemon stops at the duration or size limit; the CLI only waits
+    loop {
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        let info = hf_core::daemon_get_recording()?;
+        if info.path != started.path {
+            return Err("The log was replaced by another recording".into());
+        }
+        if !info.active {
+            break;
+        }
+    }
+    // Collects the final counts; fails harmlessly if `--stop` already did
+    let info = hf_core::daemon_stop_recording().or_else(|_| hf_core::daemon_get_recording())?;
+
+    std::fs::copy(&info.path, output).map_err(|e| format!("Failed to copy {} to {}: {}", info.path, output.display(), e))?;
+    let saved = hf_core::DaemonRecordingInfo { path: output.display().to_string(), ..info };
+    if json {
+        return print_json(&saved);
+    }
+    println!(
+        "Saved {} samples over {:.1}s ({} KB) to {}",
+        saved.samples,
+        saved.duration_ms as f64 / 1000.0,
+        saved.bytes.div_ceil(1024),
+        saved.path
+    );
+    Ok(())
+}
+
+/// Parse "90s", "10m", "1h30m" or plain seconds into milliseconds
+fn parse_duration_ms(value: &str) -> Result<u64, String> {
+    let invalid = || format!("Invalid duration '{}' (use e.g. 90s, 10m, 1h30m)", value);
+    if let Ok(secs) = value.parse::<u64>() {
+        return Ok(secs * 1000);
+    }
+
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in value.trim().chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3_600_000,
+            'm' => 60_000,
+            's' => 1000,
+            _ => return Err(invalid()),
+        };
+        let amount: u64 = number.parse().map_err(|_| invalid())?;
+        total += amount * unit;
+        number.clear();
+    }
+    if !number.is_empty() || total == 0 {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+/// Parse "500K", "100M", "2G" or plain bytes
+fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let (digits, multiplier) = match value.chars().last().map(|c| c.to_ascii_uppercase()) {
+        Some('K') => (&value[..value.len() - 1], 1024),
+        Some('M') => (&value[..value.len() - 1], 1024 * 1024),
+        Some('G') => (&value[..value.len() - 1], 1024 * 1024 * 1024),
+        _ => (value, 1),
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .filter(|amount| *amount > 0)
+        .map(|amount| amount * multiplier)
+        .ok_or_else(|| format!("Invalid size '{}' (use e.g. 500K, 100M, 2G)", value))
+}
+
+// ============================================================================
+// Profile Commands
+// ============================================================================
+
+fn cmd_profile(cmd: &ProfileCommands, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if !hf_core::is_daemon_available() {
+        return Err("Daemon not available".into());
+    }
+    let info = match cmd {
+        ProfileCommands::Show => hf_core::daemon_get_active_profile()?,
+        ProfileCommands::Set { name } => hf_core::daemon_set_active_profile(name)?,
+    };
+    if json {
+        return print_json(&info);
+    }
+    println!("Active profile: {}", info.active);
+    println!("Available: {}", info.available.join(", "));
+    Ok(())
+}
+
 // ============================================================================
 // Pair Commands
 // ============================================================================
 
-fn cmd_pairs(cmd: &PairCommands) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_pairs(cmd: &PairCommands, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     match cmd {
+        PairCommands::ProfileCurve { id, profile, curve_id } => {
+            hf_core::set_pair_profile_curve(id, profile, curve_id.as_deref())?;
+            if hf_core::is_daemon_available() {
+                if let Err(e) = hf_core::daemon_reload_config() {
+                    eprintln!("Warning: Failed to signal daemon reload: {}", e);
+                }
+            }
+            match curve_id {
+                Some(curve_id) => println!("Pair {} uses curve {} in profile {}", id, curve_id, profile),
+                None => println!("Pair {} uses its default curve in profile {}", id, profile),
+            }
+        }
         PairCommands::List => {
             let settings = hf_core::load_settings()?;
+            if json {
+                return print_json(&settings.active_pairs);
+            }
             println!("Fan-Curve Pairs ({}):", settings.active_pairs.len());
             for pair in &settings.active_pairs {
                 let status = if pair.active { "active" } else { "disabled" };
@@ -1134,11 +2655,17 @@ fn cmd_pairs(cmd: &PairCommands) -> Result<(), Box<dyn std::error::Error>> {
             let pair = settings.active_pairs.iter()
                 .find(|p| p.id == *id)
                 .ok_or_else(|| format!("Pair not found: {}", id))?;
+            if json {
+                return print_json(pair);
+            }
             println!("Pair: {} ({})", pair.name, pair.id);
             println!("Active: {}", pair.active);
             println!("Curve ID: {}", pair.curve_id);
             println!("Temperature source: {}", pair.temp_source_path);
             println!("Fan path: {}", pair.fan_path);
+            for (profile, curve_id) in &pair.profile_curves {
+                println!("Curve ID ({}): {}", profile, curve_id);
+            }
         }
         PairCommands::Delete { id } => {
             hf_core::delete_pair(id)?;
@@ -1199,6 +2726,7 @@ fn cmd_pairs(cmd: &PairCommands) -> Result<(), Box<dyn std::error::Error>> {
                 fan_paths: vec![fan_path.clone()],
                 hysteresis_ms: 0,
                 active: true,
+                profile_curves: Default::default(),
             };
 
             hf_core::save_pair(pair)?;
@@ -1226,6 +2754,20 @@ fn cmd_pairings(cmd: &PairingCommands) -> Result<(), Box<dyn std::error::Error>>
                     println!("    Name: {}", name);
                 }
                 println!("    Fan:  {:?}", p.fan_path);
+                if let hf_core::DaemonPairingControlMode::Rpm { max_rpm } = p.control_mode {
+                    match max_rpm {
+                        Some(rpm) => println!("    Mode: RPM target (max {} RPM)", rpm),
+                        None => println!("    Mode: RPM target (detected max RPM)"),
+                    }
+                }
+                if p.zero_rpm {
+                    println!("    Zero RPM: allowed");
+                }
+                match p.enforcement {
+                    hf_core::DaemonPwmEnforcement::Reassert => {}
+                    hf_core::DaemonPwmEnforcement::Yield => println!("    Foreign writes: yield"),
+                    hf_core::DaemonPwmEnforcement::AlertOnly => println!("    Foreign writes: alert only"),
+                }
             }
         }
 
@@ -1273,6 +2815,76 @@ fn cmd_pairings(cmd: &PairingCommands) -> Result<(), Box<dyn std::error::Error>>
 
             println!("Deleted pairing for {}", pwm_path);
         }
+
+        PairingCommands::Mode { pwm_path, mode, max_rpm } => {
+            let (control_mode, description) = match mode.as_str() {
+                "rpm" => (hf_core::DaemonPairingControlMode::Rpm { max_rpm: *max_rpm }, "RPM target"),
+                _ => (hf_core::DaemonPairingControlMode::Duty, "duty cycle"),
+            };
+            hf_core::daemon_set_pairing_control_mode(pwm_path, control_mode)?;
+            println!("{} now uses {} control", pwm_path, description);
+        }
+
+        PairingCommands::ZeroRpm { pwm_path, state } => {
+            let enabled = state == "on";
+            hf_core::daemon_set_pairing_zero_rpm(pwm_path, enabled)?;
+            if enabled {
+                println!("{} may now stop below its stall duty", pwm_path);
+            } else {
+                println!("{} is now held at or above its stall duty", pwm_path);
+            }
+        }
+
+        PairingCommands::Enforce { pwm_path, policy } => {
+            let (policy, description) = match policy.as_str() {
+                "yield" => (hf_core::DaemonPwmEnforcement::Yield, "left alone until the other program stops changing them"),
+                "alert-only" => (hf_core::DaemonPwmEnforcement::AlertOnly, "logged and left alone until the curve output changes"),
+                _ => (hf_core::DaemonPwmEnforcement::Reassert, "overwritten with the curve value"),
+            };
+            hf_core::daemon_set_pairing_enforcement(pwm_path, policy)?;
+            println!("Foreign writes to {} are now {}", pwm_path, description);
+        }
+
+        PairingCommands::Noise { pwm_path, max_dba, levels, clear } => {
+            let noise = if *clear {
+                None
+            } else if let Some(levels) = levels {
+                Some(hf_core::NoiseProfile::measured(parse_noise_levels(levels)?)?)
+            } else if let Some(max_dba) = max_dba {
+                if !(0.0..=hf_core::constants::noise::MAX_DBA).contains(max_dba) {
+                    return Err(format!("--max-dba must be 0-{}", hf_core::constants::noise::MAX_DBA).into());
+                }
+                Some(hf_core::NoiseProfile::Generic { max_dba: *max_dba })
+            } else {
+                return Err("Give --max-dba, --levels or --clear".into());
+            };
+
+            let mut found = false;
+            hf_core::update_setting(|s| {
+                if let Some(pairing) = s.pwm_fan_pairings.iter_mut().find(|p| p.pwm_path == *pwm_path) {
+                    pairing.noise = noise.clone();
+                    found = true;
+                }
+            })?;
+            if !found {
+                return Err(format!("No pairing found for {}", pwm_path).into());
+            }
+
+            if hf_core::is_daemon_available() {
+                if let Err(e) = hf_core::daemon_reload_config() {
+                    eprintln!("Warning: Failed to signal daemon reload: {}", e);
+                }
+            }
+
+            match noise.unwrap_or_default() {
+                hf_core::NoiseProfile::Generic { max_dba } => {
+                    println!("{} is estimated at {:.0} dBA at full speed", pwm_path, max_dba);
+                }
+                hf_core::NoiseProfile::Measured { points } => {
+                    println!("{} uses {} measured noise levels", pwm_path, points.len());
+                }
+            }
+        }
     }
 
     Ok(())
@@ -1360,6 +2972,98 @@ fn cmd_bindings(cmd: &BindingCommands) -> Result<(), Box<dyn std::error::Error>>
             hf_core::save_binding_store(&store)?;
             println!("Discovered {} chips, {} bindings", store.chips.len(), store.bindings.len());
         }
+        BindingCommands::Conflicts => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let conflicts = hf_core::daemon_get_binding_conflicts()?;
+            if conflicts.is_empty() {
+                println!("All bindings match the current hardware");
+                return Ok(());
+            }
+            println!("Bindings needing attention ({}):", conflicts.len());
+            for conflict in &conflicts {
+                println!("  [{}] {:?}, {:.0}% at its old location", conflict.pwm_id, conflict.state, conflict.confidence * 100.0);
+                println!("      Was: {} ({}{})", conflict.previous_pwm_path, conflict.chip_name,
+                         conflict.label.as_deref().map(|l| format!(", {}", l)).unwrap_or_default());
+                if conflict.candidates.is_empty() {
+                    println!("      No likely match found");
+                }
+                for candidate in &conflict.candidates {
+                    println!("      {:>3.0}%  {} ({}){}", candidate.confidence * 100.0, candidate.pwm_path, candidate.chip_name,
+                             candidate.fan_path.as_deref().map(|f| format!(" + {}", f)).unwrap_or_default());
+                }
+            }
+            println!();
+            println!("Confirm a match with: hyperfan bindings accept <ID> [--pwm <path>] [--fan <path>]");
+        }
+        BindingCommands::Accept { pwm_id, pwm, fan } => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let (pwm_path, fan_path) = match pwm {
+                Some(pwm) => (pwm.clone(), fan.clone()),
+                None => {
+                    let conflicts = hf_core::daemon_get_binding_conflicts()?;
+                    let conflict = conflicts.iter().find(|c| &c.pwm_id == pwm_id)
+                        .ok_or_else(|| format!("Binding {} has no conflict to resolve", pwm_id))?;
+                    let candidate = conflict.candidates.first()
+                        .ok_or("No likely match found; pass --pwm")?;
+                    (candidate.pwm_path.clone(), fan.clone().or_else(|| candidate.fan_path.clone()))
+                }
+            };
+            hf_core::daemon_accept_binding_match(pwm_id, &pwm_path, fan_path.as_deref())?;
+            println!("Binding {} now points to {}", pwm_id, pwm_path);
+            if let Some(fan_path) = fan_path {
+                println!("Fan input: {}", fan_path);
+            }
+        }
+        BindingCommands::Export { file } => {
+            let export = hf_core::export_bindings()?;
+            export.save(std::path::Path::new(file))?;
+            println!("Exported {} bindings to {}", export.store.bindings.len(), file);
+        }
+        BindingCommands::Import { file, dry_run } => {
+            let export = hf_core::BindingExport::load(std::path::Path::new(file))?;
+            let source = [export.metadata.hostname.as_deref(), export.metadata.hardware.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(", ");
+            if !source.is_empty() {
+                println!("Exported from: {}", source);
+            }
+
+            let plan = hf_core::plan_binding_import(&export);
+            println!("Bindings ({} of {} match this machine):", plan.importable_count(), plan.entries.len());
+            for entry in &plan.entries {
+                let mark = if entry.importable() { "✓" } else { "✗" };
+                println!("  {} {} ({}) {:?}, {:.0}%", mark, entry.name, entry.chip_name, entry.state, entry.confidence * 100.0);
+                match &entry.pwm_path {
+                    Some(path) => println!("      {} -> {}", entry.exported_pwm_path, path.display()),
+                    None => println!("      {} -> not found", entry.exported_pwm_path),
+                }
+                if !entry.importable() {
+                    for reason in &entry.reasons {
+                        println!("      {}", reason);
+                    }
+                }
+            }
+
+            if *dry_run {
+                println!();
+                println!("Dry run: nothing was imported");
+                return Ok(());
+            }
+            let result = hf_core::import_bindings(&export, &plan)?;
+            println!();
+            println!("Imported {} bindings, skipped {}", result.imported.len(), result.skipped.len());
+            if !result.imported.is_empty() {
+                if let Err(e) = hf_core::daemon_reload_config() {
+                    eprintln!("Warning: Failed to signal daemon reload: {}", e);
+                }
+            }
+        }
         BindingCommands::Path => {
             match hf_core::get_binding_store_path() {
                 Ok(path) => println!("{}", path.display()),
@@ -1379,6 +3083,75 @@ fn cmd_bindings(cmd: &BindingCommands) -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+fn cmd_bundle(cmd: &BundleCommands) -> Result<(), Box<dyn std::error::Error>> {
+    match cmd {
+        BundleCommands::Export { file } => {
+            let bundle = hf_core::export_bundle()?;
+            bundle.save(std::path::Path::new(file))?;
+            println!(
+                "Exported {} pairs, {} curves and {} bindings to {}",
+                bundle.settings.active_pairs.len(),
+                bundle.curves.len(),
+                bundle.bindings.as_ref().map(|b| b.bindings.len()).unwrap_or(0),
+                file
+            );
+        }
+        BundleCommands::Apply { file, dry_run, force } => {
+            let bundle = hf_core::ConfigBundle::load(std::path::Path::new(file))?;
+            let source = [bundle.metadata.hostname.as_deref(), bundle.metadata.hardware.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(", ");
+            if !source.is_empty() {
+                println!("Exported from: {}", source);
+            }
+            println!(
+                "Bundle: {} pairs, {} groups, {} curves, profile '{}'",
+                bundle.settings.active_pairs.len(),
+                bundle.settings.pwm_groups.len(),
+                bundle.curves.len(),
+                bundle.settings.active_profile
+            );
+
+            let check = hf_core::check_bundle(&bundle);
+            if let Some(plan) = &check.bindings {
+                println!("Bindings ({} of {} match this machine):", plan.importable_count(), plan.entries.len());
+                for entry in &plan.entries {
+                    let mark = if entry.importable() { "✓" } else { "✗" };
+                    println!("  {} {} ({}) {:?}, {:.0}%", mark, entry.name, entry.chip_name, entry.state, entry.confidence * 100.0);
+                }
+            }
+            for repoint in &check.repointed {
+                println!("  moved: {} -> {}", repoint.from, repoint.to);
+            }
+            if !check.missing.is_empty() {
+                println!("Missing on this machine ({}):", check.missing.len());
+                for path in &check.missing {
+                    println!("  ✗ {}", path);
+                }
+            }
+
+            if *dry_run {
+                println!();
+                println!("Dry run: nothing was written");
+                return Ok(());
+            }
+            let outcome = hf_core::apply_bundle(&bundle, &check, *force)?;
+            println!();
+            println!("Wrote {}", outcome.settings_path.display());
+            println!("Wrote {}", outcome.curves_path.display());
+            if let Some(result) = outcome.bindings {
+                println!("Bound {} outputs, skipped {}", result.imported.len(), result.skipped.len());
+            }
+            if let Err(e) = hf_core::daemon_reload_config() {
+                eprintln!("Warning: Failed to signal daemon reload: {}", e);
+            }
+        }
+    }
+    Ok(())
+}
+
 // ============================================================================
 // GPU Commands
 // ============================================================================
@@ -1398,6 +3171,10 @@ fn cmd_gpu(cmd: &GpuCommands) -> Result<(), Box<dyn std::error::Error>> {
                 let rpm = gpu.fan_rpm.map(|v| format!("{} RPM", v)).unwrap_or_else(|| "N/A".into());
                 let pct = gpu.fan_percent.map(|v| format!("{}%", v)).unwrap_or_else(|| "".into());
                 println!("      Fan:  {} {}", rpm, pct);
+                if let Some(power) = gpu.power_watts {
+                    let limit = gpu.power_limit_watts.map(|l| format!(" / {:.0} W", l)).unwrap_or_default();
+                    println!("      Power: {:.1} W{}", power, limit);
+                }
             }
         }
         GpuCommands::Show { index } => {
@@ -1411,9 +3188,19 @@ fn cmd_gpu(cmd: &GpuCommands) -> Result<(), Box<dyn std::error::Error>> {
             println!("Vendor: {}", gpu.vendor);
             let value = gpu.temp.map(|v| format!("{:.1}°C", v)).unwrap_or_else(|| "N/A".into());
             println!("Temp: {}", value);
+            for sensor in &gpu.temperatures {
+                let crit = sensor.critical.map(|c| format!(" (crit {:.0}°C)", c)).unwrap_or_default();
+                println!("  {}: {:.1}°C{}", sensor.name, sensor.value, crit);
+            }
             let rpm = gpu.fan_rpm.map(|v| format!("{} RPM", v)).unwrap_or_else(|| "N/A".into());
             let pct = gpu.fan_percent.map(|v| format!("{}%", v)).unwrap_or_else(|| "".into());
             println!("Fan:  {} {}", rpm, pct);
+            if let Some(power) = gpu.power_watts {
+                println!("Power: {:.1} W", power);
+            }
+            if let Some(limit) = gpu.power_limit_watts {
+                println!("Power limit: {:.0} W", limit);
+            }
         }
         GpuCommands::Set { index, fan, percent } => {
             let gpus = hf_core::enumerate_gpus().unwrap_or_default();
@@ -1437,6 +3224,49 @@ fn cmd_gpu(cmd: &GpuCommands) -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
+        GpuCommands::FanCurve { index, set, reset } => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let curve = if *reset {
+                hf_core::daemon_reset_gpu_fan_curve(*index)?
+            } else if let Some(spec) = set {
+                hf_core::daemon_set_gpu_fan_curve(*index, parse_gpu_fan_curve(spec)?)?
+            } else {
+                hf_core::daemon_get_gpu_fan_curve(*index)?
+            };
+            println!("Firmware fan curve (card{}):", curve.index);
+            for point in &curve.points {
+                println!("  {:>3}°C -> {:>3}%", point.temp_c, point.speed_percent);
+            }
+            if let (Some(min), Some(max)) = (curve.temp_min, curve.temp_max) {
+                println!("Temperature range: {}-{}°C", min, max);
+            }
+            if let (Some(min), Some(max)) = (curve.speed_min, curve.speed_max) {
+                println!("Speed range: {}-{}%", min, max);
+            }
+        }
+
+        GpuCommands::PowerLimit { index, set, reset } => {
+            if !hf_core::is_daemon_available() {
+                return Err("Daemon not available".into());
+            }
+            let limit = if *reset {
+                hf_core::daemon_reset_gpu_power_limit(*index)?
+            } else if let Some(watts) = set {
+                hf_core::daemon_set_gpu_power_limit(*index, *watts)?
+            } else {
+                hf_core::daemon_get_gpu_power_limit(*index)?
+            };
+            println!("Power limit (GPU {}): {:.0} W", limit.index, limit.watts);
+            if let Some(default) = limit.default_watts {
+                println!("Default: {:.0} W", default);
+            }
+            if let (Some(min), Some(max)) = (limit.min_watts, limit.max_watts) {
+                println!("Range: {:.0}-{:.0} W", min, max);
+            }
+        }
+
         GpuCommands::Auto { index, fan: _ } => {
             let gpus = hf_core::enumerate_gpus().unwrap_or_default();
             let gpu = gpus
@@ -1461,6 +3291,31 @@ fn cmd_gpu(cmd: &GpuCommands) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Parse "30:18,100:38" into (duty %, dBA) noise levels
+fn parse_noise_levels(spec: &str) -> Result<Vec<(f32, f32)>, Box<dyn std::error::Error>> {
+    spec.split(',')
+        .map(|pair| {
+            let (duty, dba) = pair.trim().split_once(':')
+                .ok_or_else(|| format!("Invalid noise level '{}', expected DUTY:DBA", pair))?;
+            Ok((duty.trim().trim_end_matches('%').parse()?, dba.trim().trim_end_matches("dBA").parse()?))
+        })
+        .collect()
+}
+
+/// Parse "40:20,60:40" into firmware fan curve points
+fn parse_gpu_fan_curve(spec: &str) -> Result<Vec<hf_core::DaemonGpuFanCurvePoint>, Box<dyn std::error::Error>> {
+    spec.split(',')
+        .map(|pair| {
+            let (temp, speed) = pair.trim().split_once(':')
+                .ok_or_else(|| format!("Invalid curve point '{}', expected TEMP:PERCENT", pair))?;
+            Ok(hf_core::DaemonGpuFanCurvePoint {
+                temp_c: temp.trim().trim_end_matches('C').parse()?,
+                speed_percent: speed.trim().trim_end_matches('%').parse()?,
+            })
+        })
+        .collect()
+}
+
 // ============================================================================
 // System Commands
 // ============================================================================
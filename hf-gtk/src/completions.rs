@@ -0,0 +1,201 @@
+//! Shell Completions
+//!
+//! Generates bash, zsh, and fish completion scripts from the clap command
+//! tree, so completions always match the CLI definition in `cli.rs`.
+//!
+//! ```text
+//! hyperfan completions bash > /usr/share/bash-completion/completions/hyperfan
+//! hyperfan completions zsh  > /usr/share/zsh/site-functions/_hyperfan
+//! hyperfan completions fish > /usr/share/fish/vendor_completions.d/hyperfan.fish
+//! ```
+
+use clap::{Command, ValueEnum};
+use std::fmt::Write;
+
+/// Supported shells
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// A completable word with its help text
+struct Candidate {
+    word: String,
+    help: String,
+}
+
+/// A command in the tree, identified by its subcommand path
+struct Node {
+    /// Subcommand names from the root (empty for the root)
+    path: Vec<String>,
+    subcommands: Vec<Candidate>,
+    flags: Vec<Candidate>,
+}
+
+/// Render the completion script for `shell`
+pub fn generate(shell: Shell, mut cmd: Command) -> String {
+    cmd.build();
+    let bin = cmd.get_name().to_string();
+    let mut nodes = Vec::new();
+    collect(&cmd, Vec::new(), &mut nodes);
+
+    match shell {
+        Shell::Bash => bash(&bin, &nodes),
+        Shell::Zsh => zsh(&bin, &nodes),
+        Shell::Fish => fish(&bin, &nodes),
+    }
+}
+
+fn collect(cmd: &Command, path: Vec<String>, nodes: &mut Vec<Node>) {
+    let subcommands: Vec<&Command> = cmd.get_subcommands().filter(|c| !c.is_hide_set()).collect();
+    let flags = cmd
+        .get_arguments()
+        .filter(|a| !a.is_positional() && !a.is_hide_set())
+        .filter_map(|a| {
+            a.get_long().map(|long| Candidate {
+                word: format!("--{}", long),
+                help: a.get_help().map(|h| h.to_string()).unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    nodes.push(Node {
+        path: path.clone(),
+        subcommands: subcommands
+            .iter()
+            .map(|c| Candidate {
+                word: c.get_name().to_string(),
+                help: c.get_about().map(|h| h.to_string()).unwrap_or_default(),
+            })
+            .collect(),
+        flags,
+    });
+
+    for sub in subcommands {
+        let mut child = path.clone();
+        child.push(sub.get_name().to_string());
+        collect(sub, child, nodes);
+    }
+}
+
+fn key(bin: &str, path: &[String]) -> String {
+    std::iter::once(bin).chain(path.iter().map(String::as_str)).collect::<Vec<_>>().join("__")
+}
+
+/// Quote for a single-quoted bash/zsh string
+fn quote(s: &str) -> String {
+    s.replace('\'', r"'\''")
+}
+
+/// Quote for a single-quoted fish string
+fn quote_fish(s: &str) -> String {
+    s.replace('\\', r"\\").replace('\'', r"\'")
+}
+
+fn bash(bin: &str, nodes: &[Node]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "_{}() {{", bin);
+    let _ = writeln!(out, "    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\" cmd=\"{}\" opts leaf i", bin);
+    let _ = writeln!(out, "    for ((i = 1; i < COMP_CWORD; i++)); do");
+    let _ = writeln!(out, "        case \"${{cmd}}__${{COMP_WORDS[i]}}\" in");
+    for node in nodes.iter().filter(|n| !n.path.is_empty()) {
+        let _ = writeln!(out, "            {}) cmd=\"{}\" ;;", key(bin, &node.path), key(bin, &node.path));
+    }
+    let _ = writeln!(out, "        esac");
+    let _ = writeln!(out, "    done");
+    let _ = writeln!(out, "    case \"$cmd\" in");
+    for node in nodes {
+        let words: Vec<&str> = node.subcommands.iter().chain(&node.flags).map(|c| c.word.as_str()).collect();
+        let leaf = if node.subcommands.is_empty() { 1 } else { 0 };
+        let _ = writeln!(out, "        {}) opts='{}' leaf={} ;;", key(bin, &node.path), words.join(" "), leaf);
+    }
+    let _ = writeln!(out, "        *) opts='' leaf=1 ;;");
+    let _ = writeln!(out, "    esac");
+    let _ = writeln!(out, "    # Leaf commands take positional arguments (mostly sysfs paths)");
+    let _ = writeln!(out, "    if [[ $leaf == 1 && $cur != -* ]]; then");
+    let _ = writeln!(out, "        COMPREPLY=($(compgen -f -- \"$cur\"))");
+    let _ = writeln!(out, "    else");
+    let _ = writeln!(out, "        COMPREPLY=($(compgen -W \"$opts\" -- \"$cur\"))");
+    let _ = writeln!(out, "    fi");
+    let _ = writeln!(out, "}}");
+    let _ = writeln!(out, "complete -F _{} {}", bin, bin);
+    out
+}
+
+fn zsh(bin: &str, nodes: &[Node]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "#compdef {}", bin);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "_{}() {{", bin);
+    let _ = writeln!(out, "    local cmd=\"{}\" leaf=1 i", bin);
+    let _ = writeln!(out, "    local -a opts");
+    let _ = writeln!(out, "    for ((i = 2; i < CURRENT; i++)); do");
+    let _ = writeln!(out, "        case \"${{cmd}}__${{words[i]}}\" in");
+    for node in nodes.iter().filter(|n| !n.path.is_empty()) {
+        let _ = writeln!(out, "            {}) cmd=\"{}\" ;;", key(bin, &node.path), key(bin, &node.path));
+    }
+    let _ = writeln!(out, "        esac");
+    let _ = writeln!(out, "    done");
+    let _ = writeln!(out, "    case \"$cmd\" in");
+    for node in nodes {
+        let entries: Vec<String> = node
+            .subcommands
+            .iter()
+            .chain(&node.flags)
+            .map(|c| format!("'{}:{}'", c.word, quote(&c.help.replace(':', "\\:"))))
+            .collect();
+        let leaf = if node.subcommands.is_empty() { 1 } else { 0 };
+        let _ = writeln!(out, "        {}) opts=({}) leaf={} ;;", key(bin, &node.path), entries.join(" "), leaf);
+    }
+    let _ = writeln!(out, "    esac");
+    let _ = writeln!(out, "    if (( leaf )) && [[ $PREFIX != -* ]]; then");
+    let _ = writeln!(out, "        _files");
+    let _ = writeln!(out, "    else");
+    let _ = writeln!(out, "        _describe '{}' opts", bin);
+    let _ = writeln!(out, "    fi");
+    let _ = writeln!(out, "}}");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "_{} \"$@\"", bin);
+    out
+}
+
+fn fish(bin: &str, nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        // Offer a node's words once its own name was typed but none of its children yet
+        let children: Vec<&str> = node.subcommands.iter().map(|c| c.word.as_str()).collect();
+        let at_node = match node.path.last() {
+            None => "__fish_use_subcommand".to_string(),
+            Some(name) if children.is_empty() => format!("__fish_seen_subcommand_from {}", name),
+            Some(name) => format!(
+                "__fish_seen_subcommand_from {}; and not __fish_seen_subcommand_from {}",
+                name,
+                children.join(" ")
+            ),
+        };
+        for sub in &node.subcommands {
+            let _ = writeln!(
+                out,
+                "complete -c {} -f -n '{}' -a {} -d '{}'",
+                bin, at_node, sub.word, quote_fish(&sub.help)
+            );
+        }
+        for flag in &node.flags {
+            let condition = match node.path.last() {
+                None => String::new(),
+                Some(name) => format!(" -n '__fish_seen_subcommand_from {}'", name),
+            };
+            let _ = writeln!(
+                out,
+                "complete -c {}{} -l {} -d '{}'",
+                bin,
+                condition,
+                flag.word.trim_start_matches("--"),
+                quote_fish(&flag.help)
+            );
+        }
+    }
+    out
+}
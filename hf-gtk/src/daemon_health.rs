@@ -142,10 +142,3 @@ pub fn show_daemon_error_toast(window: &libadwaita::ApplicationWindow, error: &s
     // Error is logged for debugging
 }
 
-/// Show daemon unreachable warning banner
-pub fn create_daemon_warning_banner() -> libadwaita::Banner {
-    let banner = libadwaita::Banner::new("Daemon Unreachable");
-    banner.set_button_label(Some("Retry Connection"));
-    banner.set_revealed(false);
-    banner
-}
@@ -0,0 +1,75 @@
+//! Banner for failed setup checks
+//!
+//! A missing or stopped daemon service, another fan control program fighting
+//! hyperfand over the same outputs, or a driver that exposes no fans all stop
+//! fan control from working. `hf_core::run_diagnostics` checks for these in
+//! the background; when one fails, this banner names it and opens the
+//! Diagnostics page, which shows every check with its fix.
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use hf_core::{DiagnosticCheck, DiagnosticStatus};
+
+/// How often the checks run (they scan processes and query the init system)
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often a running check is polled for its result
+const RESULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Banner shown below the header while a setup check fails
+///
+/// The caller connects its button to open the Diagnostics page.
+pub fn create_banner() -> adw::Banner {
+    let banner = adw::Banner::new("");
+    banner.set_button_label(Some("Open Diagnostics"));
+    banner.set_revealed(false);
+
+    let banner_weak = glib::SendWeakRef::from(banner.downgrade());
+    let check = move || {
+        if banner_weak.upgrade().is_none() {
+            return glib::ControlFlow::Break;
+        }
+        // The read-only banner already covers a missing daemon
+        if crate::fallback::is_active() {
+            return glib::ControlFlow::Continue;
+        }
+
+        let (tx, rx) = mpsc::channel::<Vec<DiagnosticCheck>>();
+        thread::spawn(move || {
+            let _ = tx.send(hf_core::run_diagnostics());
+        });
+        let banner_weak = banner_weak.clone();
+        glib::timeout_add_local(RESULT_POLL_INTERVAL, move || {
+            let checks = match rx.try_recv() {
+                Ok(checks) => checks,
+                Err(mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                Err(mpsc::TryRecvError::Disconnected) => return glib::ControlFlow::Break,
+            };
+            if let Some(banner) = banner_weak.upgrade() {
+                let failed: Vec<&DiagnosticCheck> =
+                    checks.iter().filter(|c| c.status == DiagnosticStatus::Error).collect();
+                banner.set_title(&banner_title(&failed));
+                banner.set_revealed(!failed.is_empty());
+            }
+            glib::ControlFlow::Break
+        });
+        glib::ControlFlow::Continue
+    };
+    check();
+    glib::timeout_add_local(CHECK_INTERVAL, check);
+
+    banner
+}
+
+fn banner_title(failed: &[&DiagnosticCheck]) -> String {
+    match failed {
+        [] => String::new(),
+        [check] => format!("{}: {}", check.title, check.summary),
+        many => format!("{} setup checks failed - fans may not be under control", many.len()),
+    }
+}
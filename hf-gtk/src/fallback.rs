@@ -0,0 +1,242 @@
+//! Daemonless fallback mode
+//!
+//! Without the daemon every page would come up empty. When the daemon is not
+//! installed, the GUI reads sensors directly from sysfs through hf-core
+//! instead, so the dashboard, sensor list and graphs still work. Nothing is
+//! ever written in this mode: pages that control fans are gated behind
+//! [`require_daemon`], which offers to install the daemon.
+//!
+//! Readers call [`list_hardware`], [`list_gpus`], [`list_all`] and
+//! [`read_temperature`] in place of the daemon client; they forward to the
+//! daemon unless fallback mode is active.
+
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tracing::{debug, info};
+
+use hf_core::{
+    DaemonAllHardwareData, DaemonFanSensor, DaemonGpuInfo, DaemonGpuTempInfo, DaemonHardwareInfo, DaemonHwSensor,
+    DaemonHwmonChip, DaemonPwmControl, DaemonSensorKind, DaemonTempSensor,
+};
+
+/// How long chip layouts are re-used between reads (values are always re-read)
+const CHIP_LAYOUT_TTL_SECS: u64 = 30;
+
+/// Global flag set while the GUI reads hardware itself
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Opens the daemon install flow (registered by the main window)
+    static INSTALL_HANDLER: RefCell<Option<Rc<dyn Fn()>>> = const { RefCell::new(None) };
+}
+
+/// Check if the GUI is reading hardware without the daemon
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Enter fallback mode if the daemon is unreachable because it isn't installed
+///
+/// An installed daemon that is merely down keeps the normal mode, where the
+/// daemon banner and status indicator report the problem.
+pub fn detect() -> bool {
+    let active = hf_core::daemon_client::ping_daemon().is_err() && !hf_core::service::is_service_installed();
+    if active {
+        info!("Daemon not installed - reading sensors directly in read-only mode");
+    }
+    ACTIVE.store(active, Ordering::Relaxed);
+    active
+}
+
+/// Leave fallback mode once the daemon answers
+pub fn deactivate() {
+    if ACTIVE.swap(false, Ordering::Relaxed) {
+        info!("Daemon available - leaving read-only mode");
+    }
+}
+
+/// Register the action behind "Install Daemon" buttons
+pub fn connect_install<F: Fn() + 'static>(handler: F) {
+    INSTALL_HANDLER.with(|h| *h.borrow_mut() = Some(Rc::new(handler)));
+}
+
+fn open_install() {
+    if let Some(handler) = INSTALL_HANDLER.with(|h| h.borrow().clone()) {
+        handler();
+    }
+}
+
+/// Allow a control action, or explain that it needs the daemon
+///
+/// Returns `true` when the daemon is in charge. In fallback mode a dialog
+/// offers the install flow instead and `false` is returned.
+pub fn require_daemon(parent: &impl IsA<gtk4::Widget>) -> bool {
+    if !is_active() {
+        return true;
+    }
+
+    let dialog = adw::AlertDialog::builder()
+        .heading("Daemon Required")
+        .body(
+            "Hyperfan is running in read-only mode. Controlling fans needs the daemon, \
+             which applies curves and restores automatic control if anything goes wrong.",
+        )
+        .build();
+    dialog.add_response("cancel", "Not Now");
+    dialog.add_response("install", "Install Daemon");
+    dialog.set_response_appearance("install", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("install"));
+    dialog.set_close_response("cancel");
+    dialog.connect_response(None, |_dialog, response| {
+        if response == "install" {
+            open_install();
+        }
+    });
+    dialog.present(Some(parent));
+    false
+}
+
+/// Banner shown below the header while in fallback mode
+pub fn create_banner() -> adw::Banner {
+    let banner = adw::Banner::new("Read-only mode: the Hyperfan daemon is not installed, so fans can't be controlled");
+    banner.set_button_label(Some("Install Daemon"));
+    banner.set_revealed(is_active());
+    banner.connect_button_clicked(|_| open_install());
+    banner
+}
+
+/// Hardware listing from the daemon, or read directly in fallback mode
+pub fn list_hardware() -> Result<DaemonHardwareInfo, String> {
+    if !is_active() {
+        return hf_core::daemon_list_hardware();
+    }
+    let chips = hf_core::cached_hwmon_chips(Duration::from_secs(CHIP_LAYOUT_TTL_SECS)).map_err(|e| e.to_string())?;
+    Ok(DaemonHardwareInfo { chips: chips.iter().map(chip_to_protocol).collect() })
+}
+
+/// GPU listing from the daemon, or read directly in fallback mode
+pub fn list_gpus() -> Result<Vec<DaemonGpuInfo>, String> {
+    if !is_active() {
+        return hf_core::daemon_list_gpus();
+    }
+    let gpus = hf_core::enumerate_gpus().map_err(|e| e.to_string())?;
+    Ok(gpus.iter().map(gpu_to_protocol).collect())
+}
+
+/// Hardware and GPUs in one call
+pub fn list_all() -> Result<DaemonAllHardwareData, String> {
+    if !is_active() {
+        return hf_core::daemon_list_all();
+    }
+    let hardware = list_hardware()?;
+    let gpus = list_gpus().unwrap_or_else(|e| {
+        debug!("GPU enumeration failed: {}", e);
+        Vec::new()
+    });
+    Ok(DaemonAllHardwareData { hardware, gpus })
+}
+
+/// Read one temperature from the daemon, or directly in fallback mode
+pub fn read_temperature(path: &str) -> Result<f32, String> {
+    if !is_active() {
+        return hf_core::daemon_client::daemon_read_temperature(path);
+    }
+    hf_core::read_temperature(Path::new(path)).map_err(|e| e.to_string())
+}
+
+/// Same shape as the daemon's listing, so pages need no second code path
+fn chip_to_protocol(chip: &hf_core::HwmonChip) -> DaemonHwmonChip {
+    DaemonHwmonChip {
+        name: chip.name.clone(),
+        path: chip.path.to_string_lossy().to_string(),
+        temperatures: chip
+            .temperatures
+            .iter()
+            .map(|t| DaemonTempSensor {
+                name: t.name.clone(),
+                label: t.label.clone(),
+                path: t.input_path.to_string_lossy().to_string(),
+                value: t.current_temp.unwrap_or(f32::NAN),
+                max: t.limits.max,
+                crit: t.limits.crit,
+                crit_alarm: t.limits.crit_alarm,
+            })
+            .collect(),
+        // Same derivation as the daemon, so pairings match either way
+        fans: chip
+            .fans
+            .iter()
+            .map(|f| DaemonFanSensor {
+                uuid: hf_core::sensor_uuid(&chip.path, &chip.name, &f.name, hf_core::ChannelType::Fan),
+                name: f.name.clone(),
+                label: f.label.clone(),
+                path: f.input_path.to_string_lossy().to_string(),
+                rpm: f.current_rpm,
+            })
+            .collect(),
+        pwms: chip
+            .pwms
+            .iter()
+            .map(|p| DaemonPwmControl {
+                uuid: hf_core::sensor_uuid(&chip.path, &chip.name, &p.name, hf_core::ChannelType::Pwm),
+                name: p.name.clone(),
+                path: p.pwm_path.to_string_lossy().to_string(),
+                value: p.current_value.unwrap_or(0),
+                // Cooling devices have no enable file and are always writable
+                enabled: p.cooling_device.is_some()
+                    || hf_core::read_control_value(&p.enable_path).is_ok_and(|v| v.trim() == "1"),
+                max_state: p.cooling_device.as_ref().map(|c| c.max_state),
+                dc_mode: p.dc_mode,
+            })
+            .collect(),
+        sensors: chip
+            .sensors
+            .iter()
+            .map(|s| DaemonHwSensor {
+                name: s.name.clone(),
+                label: s.label.clone(),
+                path: s.input_path.to_string_lossy().to_string(),
+                kind: match s.kind {
+                    hf_core::SensorKind::Voltage => DaemonSensorKind::Voltage,
+                    hf_core::SensorKind::Current => DaemonSensorKind::Current,
+                    hf_core::SensorKind::Power => DaemonSensorKind::Power,
+                    hf_core::SensorKind::Humidity => DaemonSensorKind::Humidity,
+                },
+                value: s.current_value.unwrap_or(f32::NAN),
+            })
+            .collect(),
+        alarms: chip.alarms.iter().map(Into::into).collect(),
+    }
+}
+
+fn gpu_to_protocol(gpu: &hf_core::GpuDevice) -> DaemonGpuInfo {
+    DaemonGpuInfo {
+        index: gpu.index,
+        name: gpu.name.clone(),
+        vendor: gpu.vendor.to_string(),
+        temp: gpu.temperatures.first().and_then(|t| t.current_temp),
+        fan_percent: gpu.fans.first().and_then(|f| f.speed_percent),
+        fan_rpm: gpu.fans.first().and_then(|f| f.rpm),
+        read_only: gpu.read_only,
+        temperatures: gpu
+            .temperatures
+            .iter()
+            .filter_map(|t| {
+                Some(DaemonGpuTempInfo {
+                    name: t.name.clone(),
+                    value: t.current_temp?,
+                    max: t.max_temp,
+                    critical: t.critical_temp,
+                })
+            })
+            .collect(),
+        power_watts: gpu.power_watts,
+        power_limit_watts: gpu.power_limit_watts,
+    }
+}
@@ -1,6 +1,10 @@
 mod app;
 mod cli;
+mod completions;
 mod daemon_health;
+mod diagnostics;
+mod fallback;
+mod notifications;
 pub mod perf;
 pub mod runtime;
 pub mod tray;
@@ -45,7 +49,8 @@ fn main() -> glib::ExitCode {
     match effective_wm {
         hf_core::WindowManager::Kde => {
             warn!(
-                "KDE frontend is not implemented; falling back to GTK frontend (effective WM: {})",
+                "KDE frontend is not implemented; falling back to GTK frontend (effective WM: {}). \
+                 The Plasma applet in plasma/ adds panel integration",
                 effective_wm
             );
         }
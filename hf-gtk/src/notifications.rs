@@ -0,0 +1,261 @@
+//! Desktop notifications
+//!
+//! Raises desktop notifications (org.freedesktop.Notifications via GApplication)
+//! for daemon-down, thermal threshold, fan failure, and fingerprint drift events.
+//! Each category can be toggled in the Settings page.
+//!
+//! Daemon and fingerprint checks run on a background thread. Fan alerts are
+//! pushed by the daemon's event feed through the worker runtime. The GTK main
+//! thread drains both, checks temperatures from the worker runtime, and sends
+//! the notifications.
+
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use std::collections::HashSet;
+use std::sync::mpsc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::debug;
+
+/// How often the background thread polls the daemon
+const DAEMON_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Fingerprint drift is checked every N daemon polls (~1 minute)
+const DRIFT_CHECK_EVERY_POLLS: u32 = 12;
+
+/// How often the main thread drains events and checks temperatures
+const DRAIN_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Degrees below the threshold a sensor must drop before it can notify again
+const THERMAL_CLEAR_MARGIN_CELSIUS: f32 = 3.0;
+
+/// Notification category (each has its own enable toggle)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationCategory {
+    DaemonDown,
+    ThermalThreshold,
+    FanFailure,
+    FingerprintDrift,
+}
+
+impl NotificationCategory {
+    /// Whether notifications of this category are enabled in settings
+    pub fn is_enabled(self, settings: &hf_core::NotificationSettings) -> bool {
+        match self {
+            Self::DaemonDown => settings.daemon_down,
+            Self::ThermalThreshold => settings.thermal_threshold,
+            Self::FanFailure => settings.fan_failure,
+            Self::FingerprintDrift => settings.fingerprint_drift,
+        }
+    }
+
+    fn priority(self) -> gio::NotificationPriority {
+        match self {
+            Self::FanFailure | Self::ThermalThreshold => gio::NotificationPriority::Urgent,
+            Self::DaemonDown | Self::FingerprintDrift => gio::NotificationPriority::High,
+        }
+    }
+}
+
+/// A notification queued for the main thread
+struct PendingNotification {
+    category: NotificationCategory,
+    /// Notification ID - a later notification with the same ID replaces it
+    id: String,
+    title: String,
+    body: String,
+    /// Informational follow-ups (e.g. "recovered") use normal priority
+    resolved: bool,
+}
+
+/// Start all notification sources
+pub fn start_notifications(app: &adw::Application) {
+    let (tx, rx) = mpsc::channel::<PendingNotification>();
+
+    std::thread::spawn(move || run_background_checks(tx));
+
+    let app = app.clone();
+    let mut hot_sensors: HashSet<String> = HashSet::new();
+    let mut ui_rx = crate::runtime::subscribe_ui();
+    glib::timeout_add_local(DRAIN_INTERVAL, move || {
+        while let Ok(pending) = rx.try_recv() {
+            send(&app, &pending);
+        }
+        if let Some(ui_rx) = ui_rx.as_mut() {
+            loop {
+                match ui_rx.try_recv() {
+                    Ok(crate::runtime::UiUpdate::FanAlert(alert)) => send(&app, &fan_alert_notification(&alert)),
+                    Ok(_) | Err(broadcast::error::TryRecvError::Lagged(_)) => {}
+                    Err(_) => break,
+                }
+            }
+        }
+        check_thermal_thresholds(&app, &mut hot_sensors);
+        glib::ControlFlow::Continue
+    });
+}
+
+/// Background loop: daemon reachability and fingerprint drift
+fn run_background_checks(tx: mpsc::Sender<PendingNotification>) {
+    let mut daemon_reachable = true;
+    let mut drift_reported = false;
+    let mut polls: u32 = 0;
+
+    loop {
+        let mut pending = Vec::new();
+
+        let reachable = hf_core::ping_daemon().is_ok();
+        if reachable != daemon_reachable {
+            daemon_reachable = reachable;
+            pending.push(PendingNotification {
+                category: NotificationCategory::DaemonDown,
+                id: "daemon-down".to_string(),
+                title: if reachable { "Fan daemon reconnected" } else { "Fan daemon is not responding" }.to_string(),
+                body: if reachable {
+                    "Hyperfan is controlling your fans again.".to_string()
+                } else {
+                    "Fan curves are not being applied. Check the hyperfand service.".to_string()
+                },
+                resolved: reachable,
+            });
+        }
+
+        if polls.is_multiple_of(DRIFT_CHECK_EVERY_POLLS) {
+            let drifted = count_drifted_bindings();
+            if drifted > 0 && !drift_reported {
+                pending.push(PendingNotification {
+                    category: NotificationCategory::FingerprintDrift,
+                    id: "fingerprint-drift".to_string(),
+                    title: "Hardware fingerprint mismatch".to_string(),
+                    body: format!(
+                        "{} fan binding(s) no longer match the detected hardware. Review them on the Fan Pairing page.",
+                        drifted
+                    ),
+                    resolved: false,
+                });
+            }
+            drift_reported = drifted > 0;
+        }
+        polls = polls.wrapping_add(1);
+
+        for notification in pending {
+            if tx.send(notification).is_err() {
+                return; // GUI is gone
+            }
+        }
+
+        std::thread::sleep(DAEMON_POLL_INTERVAL);
+    }
+}
+
+fn fan_alert_notification(alert: &hf_core::DaemonFanAlert) -> PendingNotification {
+    // One notification per fan so a recovery replaces its failure notice
+    let id = format!("fan-alert-{}", alert.pwm_path);
+    match alert.kind {
+        hf_core::DaemonFanAlertKind::Stalled => PendingNotification {
+            category: NotificationCategory::FanFailure,
+            id,
+            title: format!("Fan failure: {}", alert.name),
+            body: format!(
+                "Fan is not spinning at {}% duty. Other fans in this pair were raised to full speed.",
+                hf_core::pwm_to_percent(alert.pwm as u32)
+            ),
+            resolved: false,
+        },
+        hf_core::DaemonFanAlertKind::Recovered => PendingNotification {
+            category: NotificationCategory::FanFailure,
+            id,
+            title: format!("Fan recovered: {}", alert.name),
+            body: "Fan is spinning again. Normal curve control has resumed.".to_string(),
+            resolved: true,
+        },
+        hf_core::DaemonFanAlertKind::WriteFailed => PendingNotification {
+            category: NotificationCategory::FanFailure,
+            id,
+            title: format!("Fan not responding: {}", alert.name),
+            body: format!(
+                "The fan controller does not keep the {}% duty it is given. Other fans in this pair were raised to full speed.",
+                hf_core::pwm_to_percent(alert.pwm as u32)
+            ),
+            resolved: false,
+        },
+        hf_core::DaemonFanAlertKind::WriteRecovered => PendingNotification {
+            category: NotificationCategory::FanFailure,
+            id,
+            title: format!("Fan responding again: {}", alert.name),
+            body: "The fan controller accepts speed changes again. Normal curve control has resumed.".to_string(),
+            resolved: true,
+        },
+    }
+}
+
+/// Count fingerprinted bindings that need a rebind or are unsafe
+fn count_drifted_bindings() -> usize {
+    if !hf_core::binding_store_exists() {
+        return 0;
+    }
+    match hf_core::load_binding_store() {
+        Ok(mut store) => {
+            let report = hf_core::validate_all_bindings(&mut store);
+            report.needs_rebind_count + report.unsafe_count
+        }
+        Err(e) => {
+            debug!("Could not load binding store for drift check: {}", e);
+            0
+        }
+    }
+}
+
+/// Notify once per sensor when it crosses the configured threshold
+fn check_thermal_thresholds(app: &adw::Application, hot_sensors: &mut HashSet<String>) {
+    let settings = hf_core::get_cached_settings().notifications;
+    if !settings.thermal_threshold {
+        hot_sensors.clear();
+        return;
+    }
+    let Some(sensors) = crate::runtime::get_sensors() else {
+        return;
+    };
+
+    let threshold = settings.thermal_threshold_celsius;
+    for temp in &sensors.temperatures {
+        if temp.temp_celsius >= threshold {
+            if hot_sensors.insert(temp.path.clone()) {
+                send(app, &PendingNotification {
+                    category: NotificationCategory::ThermalThreshold,
+                    id: format!("thermal-{}", temp.path),
+                    title: format!("{} is running hot", temp.label),
+                    body: format!(
+                        "{} ({}) reached {} (threshold {}).",
+                        temp.label,
+                        temp.chip_name,
+                        hf_core::format_temp(temp.temp_celsius),
+                        hf_core::format_temp(threshold)
+                    ),
+                    resolved: false,
+                });
+            }
+        } else if temp.temp_celsius < threshold - THERMAL_CLEAR_MARGIN_CELSIUS {
+            hot_sensors.remove(&temp.path);
+        }
+    }
+}
+
+fn send(app: &adw::Application, pending: &PendingNotification) {
+    let settings = hf_core::get_cached_settings().notifications;
+    if !pending.category.is_enabled(&settings) {
+        debug!("Suppressed {:?} notification: {}", pending.category, pending.title);
+        return;
+    }
+
+    let notification = gio::Notification::new(&pending.title);
+    notification.set_body(Some(&pending.body));
+    notification.set_priority(if pending.resolved {
+        gio::NotificationPriority::Normal
+    } else {
+        pending.category.priority()
+    });
+    app.send_notification(Some(&pending.id), &notification);
+}
@@ -30,6 +30,18 @@ use std::time::Duration;
 use tokio::runtime::Runtime;
 use tokio::sync::{broadcast, mpsc, RwLock};
 
+/// How often to ask the daemon for hwmon/DRM hotplug events
+const HOTPLUG_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// How often to refresh the active PWM overrides (drives the countdown badges)
+const OVERRIDE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait for a daemon event before checking for shutdown
+const CONFIG_EVENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Wait before resubscribing while the daemon is unreachable
+const CONFIG_RECONNECT_INTERVAL: Duration = Duration::from_secs(3);
+
 // ============================================================================
 // Message Types
 // ============================================================================
@@ -41,6 +53,8 @@ pub struct SensorData {
     pub temperatures: Vec<TempReading>,
     pub fans: Vec<FanReading>,
     pub gpus: Vec<GpuReading>,
+    /// Voltage, current, power and humidity channels
+    pub aux: Vec<AuxReading>,
 }
 
 #[derive(Clone, Debug)]
@@ -49,6 +63,8 @@ pub struct TempReading {
     pub label: String,
     pub chip_name: String,
     pub temp_celsius: f32,
+    /// Driver-reported thresholds (empty for GPU readings)
+    pub limits: hf_core::TemperatureLimits,
 }
 
 #[derive(Clone, Debug)]
@@ -61,6 +77,16 @@ pub struct FanReading {
     pub pwm_value: Option<u8>,
 }
 
+#[derive(Clone, Debug)]
+pub struct AuxReading {
+    pub path: String,
+    pub label: String,
+    pub chip_name: String,
+    pub kind: hf_core::DaemonSensorKind,
+    /// Scaled to V, A, W or %RH
+    pub value: f32,
+}
+
 #[derive(Clone, Debug)]
 pub struct GpuReading {
     pub index: u32,
@@ -96,6 +122,9 @@ pub struct FanTarget {
 pub enum UiUpdate {
     SensorData(u64),  // Just timestamp - actual data in shared state
     LogicOutput(u64),
+    HardwareChanged(u64), // Latest daemon hotplug event ID
+    ConfigChanged(hf_core::DaemonConfigSection), // Saved by this or another client
+    FanAlert(hf_core::DaemonFanAlert), // Fan stalled or ignores writes, or recovered
 }
 
 // ============================================================================
@@ -108,6 +137,8 @@ pub struct WorkerState {
     pub sensors: RwLock<SensorData>,
     /// Latest logic output
     pub logic: RwLock<LogicOutput>,
+    /// PWM overrides in effect on the daemon
+    pub overrides: RwLock<Vec<hf_core::DaemonActiveOverride>>,
     /// Atomic running flag for fast shutdown checks
     pub running: AtomicBool,
     /// Sensor read count for diagnostics
@@ -121,6 +152,7 @@ impl Default for WorkerState {
         Self {
             sensors: RwLock::new(SensorData::default()),
             logic: RwLock::new(LogicOutput::default()),
+            overrides: RwLock::new(Vec::new()),
             running: AtomicBool::new(true),
             sensor_reads: AtomicU64::new(0),
             logic_cycles: AtomicU64::new(0),
@@ -250,6 +282,96 @@ impl WorkerRuntime {
             tracing::info!("[Logic Worker] Stopped");
         });
 
+        // ================================================================
+        // WORKER 2.5: Hotplug Worker (daemon hwmon/DRM add/remove events)
+        // ================================================================
+        let state_hotplug = state.clone();
+        let ui_tx_hotplug = ui_tx.clone();
+
+        self.runtime.spawn(async move {
+            let mut interval = tokio::time::interval(HOTPLUG_POLL_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut last_change_id: u64 = 0;
+            // Only report changes that happen after the GUI started
+            let mut initialized = false;
+
+            loop {
+                interval.tick().await;
+                if !state_hotplug.running.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                let since_id = last_change_id;
+                let Ok(Ok(changes)) = tokio::task::spawn_blocking(move || hf_core::daemon_get_hardware_changes(since_id)).await else {
+                    // A restarted daemon numbers its events from 1 again
+                    last_change_id = 0;
+                    initialized = false;
+                    continue;
+                };
+
+                if let Some(latest) = changes.last().map(|c| c.id) {
+                    if initialized {
+                        tracing::info!("[Hotplug Worker] Hardware changed (event {})", latest);
+                        let _ = ui_tx_hotplug.send(UiUpdate::HardwareChanged(latest));
+                    }
+                    last_change_id = latest;
+                }
+                initialized = true;
+            }
+        });
+
+        // ================================================================
+        // WORKER 2.6: Override Worker (timed manual speeds set from any client)
+        // ================================================================
+        let state_overrides = state.clone();
+
+        self.runtime.spawn(async move {
+            let mut interval = tokio::time::interval(OVERRIDE_POLL_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+                if !state_overrides.running.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                let overrides = tokio::task::spawn_blocking(hf_core::daemon_list_active_overrides)
+                    .await
+                    .ok()
+                    .and_then(Result::ok)
+                    .unwrap_or_default();
+                *state_overrides.overrides.write().await = overrides;
+            }
+        });
+
+        // ================================================================
+        // WORKER 2.7: Event Worker (config saved by any client, fan alerts)
+        // ================================================================
+        let state_config = state.clone();
+        let ui_tx_config = ui_tx.clone();
+
+        self.runtime.spawn_blocking(move || {
+            let mut events = hf_core::DaemonEventSubscription::new(None);
+            while state_config.running.load(Ordering::Relaxed) {
+                match events.next_event(CONFIG_EVENT_TIMEOUT) {
+                    Ok(Some(envelope)) => match envelope.event {
+                        hf_core::DaemonEvent::ConfigChanged { section, revision } => {
+                            tracing::info!("[Event Worker] {:?} changed (revision {})", section, revision);
+                            let _ = ui_tx_config.send(UiUpdate::ConfigChanged(section));
+                        }
+                        hf_core::DaemonEvent::FanFailure(alert) => {
+                            tracing::info!("[Event Worker] Fan alert {:?} for '{}'", alert.kind, alert.name);
+                            let _ = ui_tx_config.send(UiUpdate::FanAlert(alert));
+                        }
+                        _ => {}
+                    },
+                    Ok(None) => {}
+                    // Reconnects on the next call
+                    Err(_) => std::thread::sleep(CONFIG_RECONNECT_INTERVAL),
+                }
+            }
+        });
+
         // ================================================================
         // WORKERS 3-4: UI Preparation Workers
         // ================================================================
@@ -283,6 +405,27 @@ impl WorkerRuntime {
                                         ts
                                     );
                                 }
+                                UiUpdate::HardwareChanged(id) => {
+                                    tracing::trace!(
+                                        "[UI Worker {}] Hardware changed id={}",
+                                        worker_id,
+                                        id
+                                    );
+                                }
+                                UiUpdate::ConfigChanged(section) => {
+                                    tracing::trace!(
+                                        "[UI Worker {}] Config changed section={:?}",
+                                        worker_id,
+                                        section
+                                    );
+                                }
+                                UiUpdate::FanAlert(alert) => {
+                                    tracing::trace!(
+                                        "[UI Worker {}] Fan alert id={}",
+                                        worker_id,
+                                        alert.id
+                                    );
+                                }
                             }
                         }
                         Err(broadcast::error::RecvError::Lagged(n)) => {
@@ -327,6 +470,11 @@ impl WorkerRuntime {
         self.state.sensors.try_read().ok().map(|guard| guard.clone())
     }
 
+    /// Get the latest active overrides (called from GTK main thread)
+    pub fn try_get_overrides(&self) -> Option<Vec<hf_core::DaemonActiveOverride>> {
+        self.state.overrides.try_read().ok().map(|guard| guard.clone())
+    }
+
     /// Get latest logic output (called from GTK main thread)
     /// Uses try_read to avoid blocking the GTK main thread
     pub fn try_get_logic(&self) -> Option<LogicOutput> {
@@ -389,15 +537,16 @@ fn read_all_sensors_blocking() -> SensorData {
 
     let mut temperatures = Vec::new();
     let mut fans = Vec::new();
+    let mut aux = Vec::new();
     let mut gpus: Vec<GpuReading> = Vec::new();
 
     // PERFORMANCE: Single batched IPC call for hardware + GPUs
     // This reduces IPC round-trips from 2 to 1 per poll cycle
     // Falls back to separate calls if daemon doesn't support ListAll yet
-    let all_data: Result<hf_core::DaemonAllHardwareData, String> = hf_core::daemon_list_all()
+    let all_data: Result<hf_core::DaemonAllHardwareData, String> = crate::fallback::list_all()
         .or_else(|_| {
             // Fallback for older daemon versions
-            let hardware = hf_core::daemon_list_hardware()?;
+            let hardware = crate::fallback::list_hardware()?;
             let gpus = hf_core::daemon_list_gpus().unwrap_or_default();
             Ok(hf_core::DaemonAllHardwareData { hardware, gpus })
         });
@@ -411,6 +560,11 @@ fn read_all_sensors_blocking() -> SensorData {
                     label: temp.label.unwrap_or(temp.name),
                     chip_name: chip.name.clone(),
                     temp_celsius: temp.value,
+                    limits: hf_core::TemperatureLimits {
+                        max: temp.max,
+                        crit: temp.crit,
+                        crit_alarm: temp.crit_alarm,
+                    },
                 });
             }
             
@@ -442,6 +596,16 @@ fn read_all_sensors_blocking() -> SensorData {
                     pwm_value,
                 });
             }
+
+            for sensor in chip.sensors {
+                aux.push(AuxReading {
+                    path: sensor.path,
+                    label: sensor.label.unwrap_or(sensor.name),
+                    chip_name: chip.name.clone(),
+                    kind: sensor.kind,
+                    value: sensor.value,
+                });
+            }
         }
         
         // Process GPUs from batched response
@@ -451,6 +615,9 @@ fn read_all_sensors_blocking() -> SensorData {
                 if let Some(t) = gpu.temp {
                     temperatures.insert("GPU".to_string(), t);
                 }
+                for sensor in &gpu.temperatures {
+                    temperatures.insert(sensor.name.clone(), sensor.value);
+                }
 
                 GpuReading {
                     index: gpu.index,
@@ -486,6 +653,16 @@ fn read_all_sensors_blocking() -> SensorData {
                 label: format!("{} GPU", gpu.name),
                 chip_name: format!("{} ({})", gpu.name, gpu.vendor),
                 temp_celsius: temp,
+                limits: hf_core::TemperatureLimits::default(),
+            });
+        }
+        for (name, temp) in gpu.temperatures.iter().filter(|(name, _)| name.as_str() != "GPU") {
+            temperatures.push(TempReading {
+                path: format!("gpu:{}:{}", gpu.index, name),
+                label: format!("{} {}", gpu.name, name),
+                chip_name: format!("{} ({})", gpu.name, gpu.vendor),
+                temp_celsius: *temp,
+                limits: hf_core::TemperatureLimits::default(),
             });
         }
     }
@@ -495,6 +672,7 @@ fn read_all_sensors_blocking() -> SensorData {
         temperatures,
         fans,
         gpus,
+        aux,
     }
 }
 
@@ -543,6 +721,11 @@ pub fn get_logic() -> Option<LogicOutput> {
     RUNTIME.get().and_then(|r| r.try_get_logic())
 }
 
+/// Get the active PWM overrides (non-blocking)
+pub fn get_overrides() -> Option<Vec<hf_core::DaemonActiveOverride>> {
+    RUNTIME.get().and_then(|r| r.try_get_overrides())
+}
+
 /// Subscribe to UI update notifications
 pub fn subscribe_ui() -> Option<broadcast::Receiver<UiUpdate>> {
     RUNTIME.get().map(|r| r.subscribe_ui())
@@ -2,19 +2,56 @@
 //!
 //! Provides a system tray icon using the StatusNotifierItem protocol (ksni).
 //! Works with KDE, GNOME (with extension), and other desktop environments.
+//!
+//! The icon shows the hottest sensor temperature and is refreshed from the
+//! worker runtime's sensor updates. The menu switches profiles and has an
+//! "All Fans 100%" panic toggle, which holds a daemon PWM override on every
+//! output. Overrides are renewed while the toggle is on and expire by
+//! themselves if the GUI exits, so fans never stay pinned at full speed.
 
 use ksni::{self, Tray, Handle};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
 use tracing::{debug, info, warn};
 
+use crate::runtime::{self, UiUpdate};
+use hf_core::constants::temperature::{HIGH_THRESHOLD, TARGET_THRESHOLD};
+
+/// Tray icon edge length in pixels
+const ICON_SIZE: usize = 24;
+
+/// Lifetime of each full-speed override
+const FULL_SPEED_TTL_MS: u32 = 15_000;
+
+/// How often full-speed overrides are renewed (well inside the TTL)
+const FULL_SPEED_REFRESH: Duration = Duration::from_secs(5);
+
+/// 3x5 digit bitmaps for the temperature icon (bit 2 is the left column)
+const DIGIT_FONT: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
 /// Global flags for communication between tray and GTK
 static TRAY_RUNNING: AtomicBool = AtomicBool::new(false);
 static SHOW_WINDOW_FLAG: AtomicBool = AtomicBool::new(false);
 static QUIT_FLAG: AtomicBool = AtomicBool::new(false);
 static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
 
+/// Set while the "All Fans 100%" toggle is on
+static FULL_SPEED: AtomicBool = AtomicBool::new(false);
+
 /// Global handle to control the tray service
 static TRAY_HANDLE: Mutex<Option<Handle<HyperfanTray>>> = Mutex::new(None);
 
@@ -34,9 +71,24 @@ pub fn is_tray_running() -> bool {
 }
 
 /// Hyperfan tray icon implementation
-struct HyperfanTray;
+#[derive(Default)]
+struct HyperfanTray {
+    /// Hottest sensor (label, Celsius) from the latest sensor update
+    hottest: Option<(String, f32)>,
+}
 
 impl HyperfanTray {
+    /// Pick the hottest valid reading from the runtime cache
+    fn refresh_hottest(&mut self) {
+        self.hottest = runtime::get_sensors().and_then(|data| {
+            data.temperatures
+                .iter()
+                .filter(|t| t.temp_celsius.is_finite())
+                .max_by(|a, b| a.temp_celsius.total_cmp(&b.temp_celsius))
+                .map(|t| (t.label.clone(), t.temp_celsius))
+        });
+    }
+
     /// Build menu items for active fan/temp pairs
     /// PERFORMANCE: Uses cached settings and runtime sensor data to avoid blocking I/O
     fn build_status_items() -> Vec<ksni::MenuItem<Self>> {
@@ -109,15 +161,77 @@ impl HyperfanTray {
         
         items
     }
+    
+    /// Build the profile quick-switch submenu
+    /// Selecting a profile asks the daemon to switch every pair to that profile's curves
+    fn build_profile_menu() -> ksni::MenuItem<Self> {
+        use ksni::menu::*;
+        
+        let settings = hf_core::get_cached_settings();
+        let profiles = settings.available_profiles();
+        let selected = profiles.iter()
+            .position(|p| *p == settings.active_profile)
+            .unwrap_or(0);
+        
+        let options = profiles.iter()
+            .map(|name| RadioItem {
+                label: name.clone(),
+                ..Default::default()
+            })
+            .collect();
+        
+        SubMenu {
+            label: "Profile".into(),
+            submenu: vec![RadioGroup {
+                selected,
+                select: Box::new(move |_, index| {
+                    let Some(name) = profiles.get(index) else { return };
+                    match hf_core::daemon_set_active_profile(name) {
+                        Ok(info) => info!("Switched to profile '{}'", info.active),
+                        Err(e) => warn!("Failed to switch profile to '{}': {}", name, e),
+                    }
+                    // The daemon saved the new profile to settings.json
+                    hf_core::invalidate_settings_cache();
+                }),
+                options,
+            }
+            .into()],
+            ..Default::default()
+        }
+        .into()
+    }
 }
 
 impl Tray for HyperfanTray {
     fn icon_name(&self) -> String {
+        // Hosts prefer a themed icon over the pixmap, so only name one until
+        // a temperature is known
+        if self.hottest.is_some() {
+            return String::new();
+        }
         "preferences-system-power-symbolic".to_string()
     }
 
+    fn icon_pixmap(&self) -> Vec<ksni::Icon> {
+        self.hottest.as_ref().map(|(_, temp)| temperature_icon(*temp)).into_iter().collect()
+    }
+
     fn title(&self) -> String {
-        "Hyperfan Fan Control".to_string()
+        match &self.hottest {
+            Some((_, temp)) => format!("Hyperfan - {}", hf_core::display::format_temp(*temp)),
+            None => "Hyperfan Fan Control".to_string(),
+        }
+    }
+
+    fn tool_tip(&self) -> ksni::ToolTip {
+        ksni::ToolTip {
+            title: "Hyperfan".to_string(),
+            description: match &self.hottest {
+                Some((label, temp)) => format!("Hottest: {} at {}", label, hf_core::display::format_temp(*temp)),
+                None => "No temperature readings yet".to_string(),
+            },
+            ..Default::default()
+        }
     }
 
     fn id(&self) -> String {
@@ -138,6 +252,19 @@ impl Tray for HyperfanTray {
             .into(),
         ];
         
+        items.push(MenuItem::Separator);
+        items.push(Self::build_profile_menu());
+        items.push(CheckmarkItem {
+            label: "All Fans 100%".into(),
+            checked: FULL_SPEED.load(Ordering::SeqCst),
+            // Overrides go through the daemon
+            enabled: !crate::fallback::is_active(),
+            activate: Box::new(|_| {
+                set_full_speed(!FULL_SPEED.load(Ordering::SeqCst));
+            }),
+            ..Default::default()
+        }.into());
+        
         // Add status items if any active pairs exist
         let status_items = Self::build_status_items();
         if !status_items.is_empty() {
@@ -174,12 +301,27 @@ pub fn start_tray() {
     TRAY_RUNNING.store(true, Ordering::SeqCst);
 
     thread::spawn(|| {
-        let service = ksni::TrayService::new(HyperfanTray);
+        let service = ksni::TrayService::new(HyperfanTray::default());
         let handle = service.handle();
         
         // Store handle globally for stop_tray() to use
         if let Ok(mut guard) = TRAY_HANDLE.lock() {
-            *guard = Some(handle);
+            *guard = Some(handle.clone());
+        }
+
+        // Follow the sensor stream so the icon and status items stay current
+        if let Some(mut rx) = runtime::subscribe_ui() {
+            thread::spawn(move || loop {
+                match rx.blocking_recv() {
+                    Ok(UiUpdate::SensorData(_)) => {}
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                }
+                if STOP_REQUESTED.load(Ordering::SeqCst) {
+                    break;
+                }
+                handle.update(HyperfanTray::refresh_hottest);
+            });
         }
 
         info!("System tray icon started");
@@ -204,6 +346,8 @@ pub fn stop_tray() {
     }
     
     STOP_REQUESTED.store(true, Ordering::SeqCst);
+    // The toggle disappears with the icon, so release the override too
+    set_full_speed(false);
     
     // Use the handle to shutdown the tray service
     // Hold lock for entire operation to prevent race condition
@@ -221,3 +365,92 @@ pub fn stop_tray() {
         }
     }
 }
+
+/// Turn the "All Fans 100%" override on or off
+///
+/// While on, a background thread renews a full-speed override on every PWM
+/// output the daemon lists; turning it off makes that thread clear them.
+fn set_full_speed(enabled: bool) {
+    if FULL_SPEED.swap(enabled, Ordering::SeqCst) == enabled || !enabled {
+        return;
+    }
+
+    let paths: Vec<String> = match hf_core::daemon_list_hardware() {
+        Ok(hw) => hw.chips.into_iter().flat_map(|c| c.pwms).map(|p| p.path).collect(),
+        Err(e) => {
+            warn!("Cannot force fans to full speed: {}", e);
+            FULL_SPEED.store(false, Ordering::SeqCst);
+            return;
+        }
+    };
+    warn!("Forcing {} fan outputs to 100% from the tray", paths.len());
+
+    thread::spawn(move || {
+        while FULL_SPEED.load(Ordering::SeqCst) {
+            for path in &paths {
+                if let Err(e) = hf_core::daemon_set_pwm_override(path, 255, FULL_SPEED_TTL_MS) {
+                    warn!("Failed to override {}: {}", path, e);
+                }
+            }
+            let renew_at = Instant::now() + FULL_SPEED_REFRESH;
+            while FULL_SPEED.load(Ordering::SeqCst) && Instant::now() < renew_at {
+                thread::sleep(Duration::from_millis(200));
+            }
+        }
+
+        for path in &paths {
+            if let Err(e) = hf_core::daemon_clear_pwm_override(path) {
+                warn!("Failed to clear override on {}: {}", path, e);
+            }
+        }
+        info!("Released full-speed override");
+    });
+}
+
+/// Render a temperature as a tray icon, tinted by how hot it is
+fn temperature_icon(celsius: f32) -> ksni::Icon {
+    let value = if hf_core::display::is_fahrenheit() {
+        hf_core::display::celsius_to_fahrenheit(celsius)
+    } else {
+        celsius
+    };
+    let digits: Vec<usize> = format!("{:.0}", value.clamp(0.0, 999.0))
+        .bytes()
+        .map(|b| (b - b'0') as usize)
+        .collect();
+
+    // ARGB, network byte order
+    let background = if celsius >= HIGH_THRESHOLD {
+        [0xff, 0xc0, 0x1c, 0x28]
+    } else if celsius >= TARGET_THRESHOLD {
+        [0xff, 0xc6, 0x46, 0x00]
+    } else {
+        [0xff, 0x3d, 0x38, 0x46]
+    };
+    let mut data: Vec<u8> = background.repeat(ICON_SIZE * ICON_SIZE);
+
+    // Three digits (Fahrenheit) need a smaller scale to fit
+    let scale = if digits.len() > 2 { 2 } else { 3 };
+    let text_width = (digits.len() * 4 - 1) * scale;
+    let left = (ICON_SIZE - text_width) / 2;
+    let top = (ICON_SIZE - 5 * scale) / 2;
+    for (i, digit) in digits.iter().enumerate() {
+        for (row, bits) in DIGIT_FONT[*digit].iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let x = left + (i * 4 + col) * scale + dx;
+                        let y = top + row * scale + dy;
+                        let offset = (y * ICON_SIZE + x) * 4;
+                        data[offset..offset + 4].copy_from_slice(&[0xff; 4]);
+                    }
+                }
+            }
+        }
+    }
+
+    ksni::Icon { width: ICON_SIZE as i32, height: ICON_SIZE as i32, data }
+}
@@ -44,6 +44,8 @@ pub struct CurveData {
     pub ramp_up_speed: f32,
     /// Ramp down speed in percent per second
     pub ramp_down_speed: f32,
+    /// How fan speed is interpolated between points
+    pub interpolation: hf_core::CurveInterpolation,
 }
 
 /// Add curve dialog
@@ -58,6 +60,7 @@ pub struct AddCurveDialog {
     delay_ms: Rc<RefCell<u32>>,
     ramp_up_speed: Rc<RefCell<f32>>,
     ramp_down_speed: Rc<RefCell<f32>>,
+    interpolation: Rc<RefCell<hf_core::CurveInterpolation>>,
 }
 
 impl AddCurveDialog {
@@ -82,6 +85,7 @@ impl AddCurveDialog {
         let delay_ms: Rc<RefCell<u32>> = Rc::new(RefCell::new(hf_core::constants::curve::DEFAULT_DELAY_MS));
         let ramp_up_speed: Rc<RefCell<f32>> = Rc::new(RefCell::new(hf_core::constants::curve::DEFAULT_RAMP_UP_SPEED));
         let ramp_down_speed: Rc<RefCell<f32>> = Rc::new(RefCell::new(hf_core::constants::curve::DEFAULT_RAMP_DOWN_SPEED));
+        let interpolation: Rc<RefCell<hf_core::CurveInterpolation>> = Rc::new(RefCell::new(hf_core::CurveInterpolation::default()));
 
         let content = GtkBox::builder()
             .orientation(Orientation::Vertical)
@@ -193,6 +197,14 @@ impl AddCurveDialog {
             .description("Fine-tune how the curve responds to temperature changes")
             .build();
 
+        // Interpolation row
+        let interpolation_row = adw::ComboRow::builder()
+            .title("Interpolation")
+            .subtitle("How fan speed moves between curve points")
+            .build();
+        interpolation_row.set_model(Some(&gtk4::StringList::new(&["Linear", "Smooth (monotone cubic)"])));
+        params_group.add(&interpolation_row);
+
         // Hysteresis row
         let hysteresis_adj = gtk4::Adjustment::new(
             hf_core::constants::curve::DEFAULT_HYSTERESIS_CELSIUS as f64,
@@ -279,7 +291,17 @@ impl AddCurveDialog {
 
         content.append(&params_group);
 
-        // Connect spin buttons to update state
+        // Connect controls to update state
+        let interpolation_for_row = interpolation.clone();
+        let drawing_for_interpolation = drawing_area.clone();
+        interpolation_row.connect_selected_notify(move |row| {
+            *interpolation_for_row.borrow_mut() = match row.selected() {
+                1 => hf_core::CurveInterpolation::Monotone,
+                _ => hf_core::CurveInterpolation::Linear,
+            };
+            drawing_for_interpolation.queue_draw();
+        });
+
         let hysteresis_for_spin = hysteresis.clone();
         hysteresis_spin.connect_value_changed(move |spin| {
             *hysteresis_for_spin.borrow_mut() = spin.value() as f32;
@@ -327,6 +349,7 @@ impl AddCurveDialog {
             delay_ms,
             ramp_up_speed,
             ramp_down_speed,
+            interpolation,
         });
 
         // Track which node is being dragged
@@ -433,8 +456,9 @@ impl AddCurveDialog {
 
         // Setup drawing
         let points_for_draw = this.points.clone();
+        let interpolation_for_draw = this.interpolation.clone();
         this.drawing_area.set_draw_func(move |_, cr, width, height| {
-            Self::draw_interactive_curve(cr, width, height, &points_for_draw.borrow());
+            Self::draw_interactive_curve(cr, width, height, &points_for_draw.borrow(), *interpolation_for_draw.borrow());
         });
 
         // Right-click gesture for removing points
@@ -593,6 +617,7 @@ impl AddCurveDialog {
             let delay_ms = *this_for_create.delay_ms.borrow();
             let ramp_up_speed = *this_for_create.ramp_up_speed.borrow();
             let ramp_down_speed = *this_for_create.ramp_down_speed.borrow();
+            let interpolation = *this_for_create.interpolation.borrow();
 
             if !name.is_empty() {
                 let data = CurveData {
@@ -603,6 +628,7 @@ impl AddCurveDialog {
                     delay_ms,
                     ramp_up_speed,
                     ramp_down_speed,
+                    interpolation,
                 };
 
                 if let Some(callback) = this_for_create.on_create.borrow().as_ref() {
@@ -616,7 +642,13 @@ impl AddCurveDialog {
         this
     }
 
-    fn draw_interactive_curve(cr: &cairo::Context, width: i32, height: i32, points: &[(f32, f32)]) {
+    fn draw_interactive_curve(
+        cr: &cairo::Context,
+        width: i32,
+        height: i32,
+        points: &[(f32, f32)],
+        interpolation: hf_core::CurveInterpolation,
+    ) {
         let w = width as f64;
         let h = height as f64;
         let margin = 20.0;
@@ -687,6 +719,13 @@ impl AddCurveDialog {
         let temp_to_x = |t: f32| margin + ((t - 20.0) / 80.0) as f64 * (w - 2.0 * margin);
         let pct_to_y = |p: f32| h - margin - (p / 100.0) as f64 * (h - 2.0 * margin);
 
+        // Stepped drawing ignores interpolation, as the daemon does
+        let path = if graph_style == "stepped" {
+            points.to_vec()
+        } else {
+            super::curve_card::line_points(points, interpolation)
+        };
+
         // Draw curve fill (only for "filled" style) - use accent color
         let accent = super::curve_card::theme_colors::curve_line();
         let fill = super::curve_card::theme_colors::curve_fill();
@@ -695,7 +734,7 @@ impl AddCurveDialog {
             cr.move_to(temp_to_x(20.0), pct_to_y(0.0));
             cr.line_to(temp_to_x(20.0), pct_to_y(points[0].1));
 
-            for (t, p) in points {
+            for (t, p) in &path {
                 cr.line_to(temp_to_x(*t), pct_to_y(*p));
             }
 
@@ -715,7 +754,7 @@ impl AddCurveDialog {
 
         cr.move_to(temp_to_x(20.0), pct_to_y(points[0].1));
         let mut prev_p = points[0].1;
-        for (t, p) in points {
+        for (t, p) in &path {
             match graph_style.as_str() {
                 "stepped" => {
                     cr.line_to(temp_to_x(*t), pct_to_y(prev_p));
@@ -799,6 +838,7 @@ impl Clone for AddCurveDialog {
             delay_ms: self.delay_ms.clone(),
             ramp_up_speed: self.ramp_up_speed.clone(),
             ramp_down_speed: self.ramp_down_speed.clone(),
+            interpolation: self.interpolation.clone(),
         }
     }
 }
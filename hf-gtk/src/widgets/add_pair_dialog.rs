@@ -965,16 +965,19 @@ impl AddPairDialog {
         if let Ok(daemon_gpus) = hf_core::daemon_list_gpus() {
             tracing::trace!(gpu_count = daemon_gpus.len(), "Found GPUs");
             for gpu in daemon_gpus {
-                if let Some(temp) = gpu.temp {
-                    let path = format!("gpu:{}:GPU", gpu.index);
+                // Primary sensor ("GPU") first, then junction/memory/etc. by name
+                let sensors = gpu.temp.map(|t| ("GPU".to_string(), t)).into_iter()
+                    .chain(gpu.temperatures.iter().skip(1).map(|t| (t.name.clone(), t.value)));
+                for (sensor, temp) in sensors {
+                    let path = format!("gpu:{}:{}", gpu.index, sensor);
                     let friendly_name = hf_core::get_sensor_friendly_name(&path)
                         .ok()
                         .flatten();
                     sources.push(TempSourceItem {
                         path,
                         chip_name: format!("{} ({})", gpu.name, gpu.vendor),
-                        sensor_name: "GPU".to_string(),
-                        label: Some("GPU".to_string()),
+                        sensor_name: sensor.clone(),
+                        label: Some(sensor),
                         friendly_name,
                         current_temp: Some(temp),
                     });
@@ -991,6 +994,12 @@ impl AddPairDialog {
                 if chip.name.contains("amdgpu") {
                     continue;
                 }
+                // Thermal zones are labelled with their type (x86_pkg_temp, acpitz)
+                let chip_name = if chip.name == hf_core::constants::thermal_zone::CHIP_NAME {
+                    "Thermal Zone".to_string()
+                } else {
+                    chip.name.clone()
+                };
                 for temp in chip.temperatures {
                     let path = temp.path.clone();
                     let friendly_name = hf_core::get_sensor_friendly_name(&path)
@@ -998,7 +1007,7 @@ impl AddPairDialog {
                         .flatten();
                     sources.push(TempSourceItem {
                         path,
-                        chip_name: chip.name.clone(),
+                        chip_name: chip_name.clone(),
                         sensor_name: temp.name,
                         label: temp.label,
                         friendly_name,
@@ -1089,9 +1098,21 @@ impl AddPairDialog {
             }
         };
         
+        // GPU outputs that are also hwmon outputs are listed once, under the hwmon path
+        let gpus = hf_core::reconcile_gpu_pwms(
+            hw.chips.iter().map(|chip| &chip.path),
+            hf_core::enumerate_gpu_pwm_controllers(),
+        );
+        tracing::info!(
+            "Enumerated {} GPU PWM controllers ({} merged into hwmon outputs)",
+            gpus.controllers.len() + gpus.merged_count(),
+            gpus.merged_count()
+        );
+
         for chip in hw.chips {
             for pwm in &chip.pwms {
                 let pwm_path = pwm.path.clone();
+                let gpu = gpus.gpu_for(&pwm_path);
                 
                 // Extract PWM number
                 let pwm_num: String = pwm.name.chars().filter(|c| c.is_ascii_digit()).collect();
@@ -1105,8 +1126,13 @@ impl AddPairDialog {
                 let paired_fan_name = pairing.and_then(|p| p.fan_name.clone());
                 
                 // Check if this fan is already assigned to an existing control
+                // (controls made before the merge may use the GPU controller ID)
                 let assigned_to_control = existing_pairs.iter()
-                    .find(|p| p.fan_path == pwm_path || p.fan_paths.contains(&pwm_path))
+                    .find(|p| {
+                        p.fan_path == pwm_path
+                            || p.fan_paths.contains(&pwm_path)
+                            || gpu.is_some_and(|gpu| p.fan_path == gpu.id || p.fan_paths.contains(&gpu.id))
+                    })
                     .map(|p| p.name.clone());
                 
                 // Get fan input path from saved pairing (authoritative source)
@@ -1130,27 +1156,25 @@ impl AddPairDialog {
 
                 fans.push(FanItem {
                     pwm_path,
-                    chip_name: chip.name.clone(),
-                    pwm_name: pwm.name.clone(),
+                    chip_name: gpu.map_or_else(|| chip.name.clone(), |gpu| format!("{} GPU", gpu.vendor)),
+                    pwm_name: gpu.map_or_else(|| pwm.name.clone(), |gpu| gpu.name.clone()),
                     pwm_num,
                     friendly_name,
                     paired_fan_name,
                     fan_input_path,
-                    current_rpm: rpm,
+                    current_rpm: rpm.or_else(|| gpu.and_then(|gpu| gpu.current_rpm)),
                     selected: Rc::new(RefCell::new(false)),
                     assigned_to_control,
                 });
             }
         }
 
-        // Load GPU fan controllers
-        let gpu_controllers = hf_core::enumerate_gpu_pwm_controllers();
-        tracing::info!("Enumerated {} GPU PWM controllers", gpu_controllers.len());
-        for gpu in &gpu_controllers {
+        // GPU fan controllers without a hwmon output (NVIDIA)
+        for gpu in &gpus.controllers {
             tracing::debug!("GPU controller: id={}, name={}, vendor={:?}", gpu.id, gpu.name, gpu.vendor);
         }
         
-        for gpu in gpu_controllers {
+        for gpu in gpus.controllers {
             let pwm_path = gpu.id.clone();
             
             // Extract GPU and fan index from ID (format: "vendor:gpu_index:fan_index")
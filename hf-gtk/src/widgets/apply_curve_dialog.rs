@@ -0,0 +1,122 @@
+//! Apply-to-pairs dialog
+//!
+//! Assigns one curve to several fan-curve pairs at once, so identical case
+//! fans don't need the same curve recreated for each of them. An optional
+//! fan speed offset assigns a shifted copy instead, and a profile limits the
+//! assignment to that profile.
+
+use gtk4::prelude::*;
+use gtk4::Label;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+use super::template_dialog::{dialog_window, show_error};
+
+/// Open the dialog for `curve`
+///
+/// `on_applied` runs after the pairs (and any offset copy) have been saved.
+pub fn show_apply_dialog(
+    parent: &impl IsA<gtk4::Widget>,
+    curve: &hf_core::PersistedCurve,
+    on_applied: impl Fn() + 'static,
+) {
+    let settings = hf_core::load_settings().unwrap_or_default();
+    let curve_names: HashMap<String, String> = hf_core::load_curves()
+        .map(|store| store.all().into_iter().map(|c| (c.id.clone(), c.name.clone())).collect())
+        .unwrap_or_default();
+
+    let (window, content, action_btn, error_label) =
+        dialog_window(parent, &format!("Apply \"{}\"", curve.name), "Apply");
+
+    // Pairs to assign
+    let pairs_group = adw::PreferencesGroup::builder()
+        .title("Pairs")
+        .description("Fans that should follow this curve")
+        .build();
+    let pair_rows: Vec<(String, adw::SwitchRow)> = settings
+        .active_pairs
+        .iter()
+        .map(|pair| {
+            let current = pair.curve_for_profile(&settings.active_profile);
+            let subtitle = if current == curve.id {
+                "Already uses this curve".to_string()
+            } else {
+                format!("Currently: {}", curve_names.get(current).map(String::as_str).unwrap_or("none"))
+            };
+            let row = adw::SwitchRow::builder()
+                .title(&pair.name)
+                .subtitle(&subtitle)
+                .build();
+            pairs_group.add(&row);
+            (pair.id.clone(), row)
+        })
+        .collect();
+    if pair_rows.is_empty() {
+        pairs_group.add(
+            &Label::builder()
+                .label("No fan-curve pairs yet. Create one on the Fan Pairing page.")
+                .css_classes(["dim-label"])
+                .xalign(0.0)
+                .build(),
+        );
+    }
+    content.append(&pairs_group);
+
+    // Options
+    let options_group = adw::PreferencesGroup::builder()
+        .title("Options")
+        .build();
+    let offset_row = adw::SpinRow::builder()
+        .title("Fan Speed Offset (%)")
+        .subtitle("Assign a copy shifted by this much (0 = share this curve)")
+        .adjustment(&gtk4::Adjustment::new(0.0, -50.0, 50.0, 5.0, 10.0, 0.0))
+        .digits(0)
+        .build();
+    let profiles = settings.available_profiles();
+    let mut choices = vec!["All profiles".to_string()];
+    choices.extend(profiles.iter().map(|p| format!("Only in {}", p)));
+    let choice_refs: Vec<&str> = choices.iter().map(String::as_str).collect();
+    let profile_row = adw::ComboRow::builder()
+        .title("Profile")
+        .subtitle("Replace the pairs' default curve, or only one profile's")
+        .model(&gtk4::StringList::new(&choice_refs))
+        .build();
+    options_group.add(&offset_row);
+    options_group.add(&profile_row);
+    content.append(&options_group);
+    content.append(&error_label);
+
+    let curve_id = curve.id.clone();
+    let window_for_apply = window.clone();
+    action_btn.connect_clicked(move |_| {
+        let pair_ids: Vec<String> = pair_rows
+            .iter()
+            .filter(|(_, row)| row.is_active())
+            .map(|(id, _)| id.clone())
+            .collect();
+        if pair_ids.is_empty() {
+            show_error(&error_label, "Select at least one pair");
+            return;
+        }
+        let profile = match profile_row.selected() {
+            0 => None,
+            n => profiles.get(n as usize - 1).map(String::as_str),
+        };
+
+        match hf_core::copy_curve_to_pairs(&curve_id, &pair_ids, offset_row.value() as f32, profile) {
+            Ok(copy) => {
+                info!("Applied curve {} to {} pairs", copy.curve_name, copy.assigned_pairs.len());
+                if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Settings) {
+                    debug!("Failed to signal daemon reload: {}", e);
+                }
+                on_applied();
+                window_for_apply.close();
+            }
+            Err(e) => show_error(&error_label, &e.to_string()),
+        }
+    });
+
+    window.present();
+}
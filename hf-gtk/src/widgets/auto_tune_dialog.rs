@@ -0,0 +1,210 @@
+//! Curve auto-tune dialog
+//!
+//! Runs a daemon auto-tune job for one curve while the user keeps a load
+//! test going, showing each measured duty step as it settles. Once the job
+//! completes the suggested curve is listed for review and only replaces the
+//! saved curve when the user saves it.
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use super::edit_history::{self, Scope};
+use super::template_dialog::{dialog_window, show_error};
+
+/// How often the job's progress is polled
+const PROGRESS_POLL: Duration = Duration::from_secs(1);
+
+/// Default temperature target (°C)
+const DEFAULT_TARGET: f64 = 75.0;
+
+/// Open the dialog for `curve`
+///
+/// `on_saved` runs after the suggested curve has been saved.
+pub fn show_auto_tune_dialog(
+    parent: &impl IsA<gtk4::Widget>,
+    curve: &hf_core::PersistedCurve,
+    on_saved: impl Fn() + 'static,
+) {
+    let (window, content, action_btn, error_label) =
+        dialog_window(parent, &format!("Auto-Tune \"{}\"", curve.name), "Start");
+
+    let setup_group = adw::PreferencesGroup::builder()
+        .title("Load Test")
+        .description(
+            "Start a steady load (a game, render, or stress test) before pressing Start and keep it running. \
+             The fans on this curve step down from full speed, waiting for the temperature to settle at each \
+             step; this can take half an hour.",
+        )
+        .build();
+    let target_row = adw::SpinRow::builder()
+        .title("Target Temperature (°C)")
+        .subtitle("Highest temperature the curve should hold under this load")
+        .adjustment(&gtk4::Adjustment::new(
+            DEFAULT_TARGET,
+            hf_core::MIN_AUTO_TUNE_TARGET as f64,
+            hf_core::MAX_AUTO_TUNE_TARGET as f64,
+            1.0,
+            5.0,
+            0.0,
+        ))
+        .digits(0)
+        .build();
+    setup_group.add(&target_row);
+    content.append(&setup_group);
+
+    let progress_bar = gtk4::ProgressBar::builder()
+        .show_text(true)
+        .visible(false)
+        .build();
+    content.append(&progress_bar);
+
+    let steps_group = adw::PreferencesGroup::builder()
+        .title("Measurements")
+        .visible(false)
+        .build();
+    content.append(&steps_group);
+
+    let result_group = adw::PreferencesGroup::builder()
+        .title("Suggested Curve")
+        .description("Review the points, then save them over the current curve")
+        .visible(false)
+        .build();
+    content.append(&result_group);
+    content.append(&error_label);
+
+    let job_id: Rc<Cell<Option<u64>>> = Rc::new(Cell::new(None));
+    let suggested: Rc<RefCell<Vec<(f32, f32)>>> = Rc::new(RefCell::new(Vec::new()));
+    let on_saved = Rc::new(on_saved);
+
+    let curve_id = curve.id.clone();
+    let curve_name = curve.name.clone();
+    let window_for_action = window.clone();
+    let job_for_action = job_id.clone();
+    action_btn.connect_clicked(move |btn| {
+        error_label.set_visible(false);
+
+        // Completed job: save what the user reviewed
+        if !suggested.borrow().is_empty() {
+            let before = edit_history::begin(Scope::Curves);
+            match hf_core::update_curve_points(&curve_id, suggested.borrow().clone()) {
+                Ok(_) => {
+                    info!("Saved auto-tuned curve {}", curve_id);
+                    edit_history::commit(before, format!("Auto-tune \"{}\"", curve_name));
+                    if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Curves) {
+                        debug!("Failed to signal daemon reload: {}", e);
+                    }
+                    on_saved();
+                    window_for_action.close();
+                }
+                Err(e) => show_error(&error_label, &e.to_string()),
+            }
+            return;
+        }
+
+        let progress = match hf_core::daemon_start_auto_tune(&curve_id, target_row.value() as f32) {
+            Ok(progress) => progress,
+            Err(e) => {
+                show_error(&error_label, &e);
+                return;
+            }
+        };
+        job_for_action.set(Some(progress.job_id));
+        target_row.set_sensitive(false);
+        btn.set_sensitive(false);
+        btn.set_label("Running…");
+        progress_bar.set_visible(true);
+        progress_bar.set_fraction(0.0);
+        progress_bar.set_text(Some("Starting…"));
+
+        let btn = btn.clone();
+        let error_label = error_label.clone();
+        let target_row = target_row.clone();
+        let progress_bar = progress_bar.clone();
+        let steps_group = steps_group.clone();
+        let result_group = result_group.clone();
+        let suggested = suggested.clone();
+        let job_for_poll = job_for_action.clone();
+        let shown_steps = Cell::new(0);
+        glib::timeout_add_local(PROGRESS_POLL, move || {
+            let Some(id) = job_for_poll.get() else { return glib::ControlFlow::Break };
+            let progress = match hf_core::daemon_get_auto_tune_progress(id) {
+                Ok(progress) => progress,
+                Err(e) => {
+                    warn!("Failed to poll auto-tune job {}: {}", id, e);
+                    return glib::ControlFlow::Continue;
+                }
+            };
+
+            for step in &progress.steps[shown_steps.get()..] {
+                let subtitle = if step.settled { "Settled".to_string() } else { "Did not settle".to_string() };
+                steps_group.add(
+                    &adw::ActionRow::builder()
+                        .title(format!("{:.0}% → {:.1}°C", step.duty_percent, step.temp))
+                        .subtitle(subtitle)
+                        .build(),
+                );
+                steps_group.set_visible(true);
+            }
+            shown_steps.set(progress.steps.len());
+
+            match progress.state {
+                hf_core::DaemonDetectionJobState::Running => {
+                    let text = match (progress.current_duty, progress.current_temp) {
+                        (Some(duty), Some(temp)) => format!(
+                            "Step {} of {}: {:.0}% fans, {:.1}°C",
+                            progress.step, progress.total_steps, duty, temp
+                        ),
+                        _ => "Starting…".to_string(),
+                    };
+                    progress_bar.set_fraction(progress.step.saturating_sub(1) as f64 / progress.total_steps.max(1) as f64);
+                    progress_bar.set_text(Some(&text));
+                    glib::ControlFlow::Continue
+                }
+                hf_core::DaemonDetectionJobState::Completed => {
+                    job_for_poll.set(None);
+                    progress_bar.set_fraction(1.0);
+                    progress_bar.set_text(Some("Done - fans are back on their curves"));
+                    for (temp, pct) in &progress.points {
+                        result_group.add(
+                            &adw::ActionRow::builder()
+                                .title(format!("{:.0}°C → {:.0}%", temp, pct))
+                                .build(),
+                        );
+                    }
+                    result_group.set_visible(true);
+                    *suggested.borrow_mut() = progress.points;
+                    btn.set_label("Save Curve");
+                    btn.set_sensitive(true);
+                    glib::ControlFlow::Break
+                }
+                hf_core::DaemonDetectionJobState::Cancelled | hf_core::DaemonDetectionJobState::Failed => {
+                    job_for_poll.set(None);
+                    progress_bar.set_visible(false);
+                    show_error(&error_label, &progress.error.unwrap_or_else(|| "Auto-tune stopped".to_string()));
+                    target_row.set_sensitive(true);
+                    btn.set_label("Start");
+                    btn.set_sensitive(true);
+                    glib::ControlFlow::Break
+                }
+            }
+        });
+    });
+
+    // Closing the dialog ends a running job so the fans go back to their curves
+    window.connect_close_request(move |_| {
+        if let Some(id) = job_id.take() {
+            if let Err(e) = hf_core::daemon_cancel_auto_tune(id) {
+                warn!("Failed to cancel auto-tune job {}: {}", id, e);
+            }
+        }
+        glib::Propagation::Proceed
+    });
+
+    window.present();
+}
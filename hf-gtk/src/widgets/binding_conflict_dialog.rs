@@ -0,0 +1,147 @@
+//! Hardware-changed reconciliation dialog
+//!
+//! After a BIOS update or a board swap some fingerprinted bindings only
+//! partially match and their outputs fall back to a safe speed. This dialog
+//! lists each of them with the current channels that could be the same fan
+//! (`GetBindingConflicts`) and rebinds the ones the user confirms
+//! (`AcceptBindingMatch`); the daemon moves their configuration along.
+
+use gtk4::prelude::*;
+use gtk4::Label;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use tracing::info;
+
+use hf_core::{DaemonBindingConflict, DaemonBindingConflictState};
+
+use super::template_dialog::{dialog_window, show_error};
+
+/// A candidate's radio button with the PWM path and fan input it stands for
+type CandidateChoice = (gtk4::CheckButton, String, Option<String>);
+
+/// Open the dialog for `conflicts`
+///
+/// `on_accepted` runs after at least one binding was rebound.
+pub fn show_binding_conflicts_dialog(
+    parent: &impl IsA<gtk4::Widget>,
+    conflicts: Vec<DaemonBindingConflict>,
+    on_accepted: impl Fn() + 'static,
+) {
+    let (window, content, action_btn, error_label) =
+        dialog_window(parent, "Hardware Changed", "Rebind");
+
+    content.append(
+        &Label::builder()
+            .label("These fans no longer match the hardware they were set up on. Pick the channel that is the same fan, or leave it for now.")
+            .css_classes(["dim-label"])
+            .wrap(true)
+            .xalign(0.0)
+            .build(),
+    );
+
+    // One radio group per conflict; no candidate selected means "leave it"
+    let mut choices: Vec<(String, Vec<CandidateChoice>)> = Vec::new();
+    for conflict in &conflicts {
+        let title = match &conflict.label {
+            Some(label) => format!("{} ({})", label, conflict.chip_name),
+            None => format!("{} ({})", file_name(&conflict.previous_pwm_path), conflict.chip_name),
+        };
+        let group = adw::PreferencesGroup::builder()
+            .title(&title)
+            .description(format!(
+                "Was {}. {} - {:.0}% match at its old location",
+                conflict.previous_pwm_path,
+                state_label(conflict.state),
+                conflict.confidence * 100.0
+            ))
+            .build();
+
+        let keep_check = gtk4::CheckButton::new();
+        let keep_row = adw::ActionRow::builder()
+            .title("Not sure - leave it")
+            .subtitle("The output stays at its fallback speed")
+            .activatable_widget(&keep_check)
+            .build();
+        keep_row.add_prefix(&keep_check);
+        group.add(&keep_row);
+
+        let mut candidates = Vec::new();
+        for candidate in &conflict.candidates {
+            let check = gtk4::CheckButton::builder().group(&keep_check).build();
+            let mut subtitle = format!("{:.0}% match", candidate.confidence * 100.0);
+            if let Some(fan_path) = &candidate.fan_path {
+                subtitle.push_str(&format!(" - fan input {}", file_name(fan_path)));
+            }
+            let row = adw::ActionRow::builder()
+                .title(format!("{} ({})", candidate.pwm_path, candidate.chip_name))
+                .subtitle(&subtitle)
+                .activatable_widget(&check)
+                .build();
+            row.set_tooltip_text(Some(&candidate.reasons.join("\n")));
+            row.add_prefix(&check);
+            group.add(&row);
+            candidates.push((check, candidate.pwm_path.clone(), candidate.fan_path.clone()));
+        }
+
+        // Preselect only a confident best match
+        match candidates.first() {
+            Some((check, _, _))
+                if conflict.candidates[0].confidence >= hf_core::constants::fingerprint::CONFIDENCE_DEGRADED =>
+            {
+                check.set_active(true)
+            }
+            _ => keep_check.set_active(true),
+        }
+        if candidates.is_empty() {
+            group.add(
+                &Label::builder()
+                    .label("No likely match found. Pair this fan again on the Fan Pairing page.")
+                    .css_classes(["dim-label"])
+                    .wrap(true)
+                    .xalign(0.0)
+                    .build(),
+            );
+        }
+
+        content.append(&group);
+        choices.push((conflict.pwm_id.clone(), candidates));
+    }
+    content.append(&error_label);
+
+    let window_for_accept = window.clone();
+    action_btn.connect_clicked(move |_| {
+        let mut accepted = 0;
+        for (pwm_id, candidates) in &choices {
+            let Some((_, pwm_path, fan_path)) = candidates.iter().find(|(check, _, _)| check.is_active()) else {
+                continue;
+            };
+            if let Err(e) = hf_core::daemon_accept_binding_match(pwm_id, pwm_path, fan_path.as_deref()) {
+                show_error(&error_label, &format!("Failed to rebind {}: {}", pwm_path, e));
+                if accepted > 0 {
+                    on_accepted();
+                }
+                return;
+            }
+            info!("Rebound binding {} to {}", pwm_id, pwm_path);
+            accepted += 1;
+        }
+        if accepted > 0 {
+            on_accepted();
+        }
+        window_for_accept.close();
+    });
+
+    window.present();
+}
+
+fn state_label(state: DaemonBindingConflictState) -> &'static str {
+    match state {
+        DaemonBindingConflictState::Degraded => "Partial match",
+        DaemonBindingConflictState::NeedsRebind => "Needs rebinding",
+        DaemonBindingConflictState::Unsafe => "Not found",
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
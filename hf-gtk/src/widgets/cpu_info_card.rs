@@ -12,8 +12,6 @@ use libadwaita::prelude::*;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use hf_core::daemon_client;
-
 // ============================================================================
 // Constants
 // ============================================================================
@@ -195,7 +193,7 @@ impl CpuInfoCard {
             .build();
 
         // Find CPU-related temperature sensors via daemon (authoritative)
-        if let Ok(hw) = hf_core::daemon_list_hardware() {
+        if let Ok(hw) = crate::fallback::list_hardware() {
             for chip in hw.chips {
                 // Only include CPU-related chips (coretemp, k10temp, zenpower, etc.)
                 let is_cpu_chip = chip.name.contains("coretemp")
@@ -237,7 +235,7 @@ impl CpuInfoCard {
         if let Some(first_sensor) = temp_sensors.borrow().first() {
             // Clone the label reference for primary display
             let primary_path = first_sensor.path.clone();
-            if let Ok(temp) = daemon_client::daemon_read_temperature(&primary_path) {
+            if let Ok(temp) = crate::fallback::read_temperature(&primary_path) {
                 primary_temp_label.set_label(&hf_core::display::format_temp(temp));
             }
         }
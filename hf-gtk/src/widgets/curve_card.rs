@@ -42,6 +42,19 @@ mod graph {
     pub const POINT_RADIUS: f64 = 4.0;
     pub const INDICATOR_RADIUS: f64 = 6.0;
     pub const LINE_WIDTH: f64 = 2.5;
+    /// Segments used to draw a monotone curve
+    pub const CURVE_SAMPLES: usize = 64;
+}
+
+/// Points to draw a curve's line through
+///
+/// Monotone curves are sampled densely so the polyline follows the cubic;
+/// linear curves are drawn straight through their points.
+pub fn line_points(points: &[(f32, f32)], interpolation: hf_core::CurveInterpolation) -> Vec<(f32, f32)> {
+    match interpolation {
+        hf_core::CurveInterpolation::Linear => points.to_vec(),
+        hf_core::CurveInterpolation::Monotone => hf_core::sample_curve(points, interpolation, graph::CURVE_SAMPLES),
+    }
 }
 
 /// Theme-aware colors for graph drawing using GNOME system accent color
@@ -54,6 +67,11 @@ pub mod theme_colors {
         style_manager.is_dark()
     }
     
+    /// Whether the high contrast style is in use
+    pub fn is_high_contrast() -> bool {
+        adw::StyleManager::default().is_high_contrast()
+    }
+    
     /// Get the GNOME system accent color as RGBA
     /// Uses libadwaita 1.6+ accent_color_rgba() for true system accent color
     pub fn accent_color() -> (f64, f64, f64, f64) {
@@ -64,7 +82,9 @@ pub mod theme_colors {
     
     /// Grid line color - subtle visibility
     pub fn grid_line() -> (f64, f64, f64, f64) {
-        if is_dark_mode() {
+        if is_high_contrast() {
+            if is_dark_mode() { (0.9, 0.9, 0.9, 0.6) } else { (0.0, 0.0, 0.0, 0.7) }
+        } else if is_dark_mode() {
             (0.5, 0.5, 0.5, 0.2)
         } else {
             (0.2, 0.2, 0.2, 0.5)  // WCAG AA: darker and more opaque for visibility
@@ -103,6 +123,38 @@ pub mod theme_colors {
             (0.75, 0.15, 0.15, 0.9)  // WCAG AA: darker and more opaque
         }
     }
+    
+    /// Color the user assigned to a sensor or fan, adapted to the current style
+    pub fn sensor_color(path: &str) -> Option<(f64, f64, f64)> {
+        let settings = hf_core::get_cached_settings();
+        let (r, g, b) = hf_core::parse_hex_color(settings.display.sensor_colors.get(path)?)?;
+        Some(adapt_to_style((r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0)))
+    }
+    
+    /// Temperature indicator of a curve following `path`: its sensor color if one is set
+    pub fn indicator_for(path: &str) -> (f64, f64, f64, f64) {
+        match sensor_color(path) {
+            Some((r, g, b)) => (r, g, b, 1.0),
+            None => indicator(),
+        }
+    }
+    
+    /// Lighten a color on dark backgrounds and darken it on light ones until it
+    /// keeps its contrast, so one assigned color works in both styles
+    pub fn adapt_to_style((r, g, b): (f64, f64, f64)) -> (f64, f64, f64) {
+        let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let (target, mix) = if is_dark_mode() {
+            // Mix towards white until bright enough
+            if luminance >= 0.4 { return (r, g, b) }
+            (1.0, (0.4 - luminance) / (1.0 - luminance))
+        } else {
+            // Mix towards black until dark enough
+            if luminance <= 0.45 { return (r, g, b) }
+            (0.0, 1.0 - 0.45 / luminance)
+        };
+        let blend = |c: f64| c + (target - c) * mix;
+        (blend(r), blend(g), blend(b))
+    }
 }
 
 // ============================================================================
@@ -301,6 +353,10 @@ impl CurveCard {
         let data_for_duplicate = data.clone();
         duplicate_button.connect_clicked(move |btn| {
             let original = data_for_duplicate.borrow();
+            // The card doesn't carry the offset or fallbacks; copy them from the stored curve
+            let stored = hf_core::load_curves()
+                .ok()
+                .and_then(|store| store.get(&original.id).cloned());
             let mut new_curve = hf_core::PersistedCurve {
                 id: uuid::Uuid::new_v4().to_string(),
                 name: format!("{} (Copy)", original.name),
@@ -313,6 +369,9 @@ impl CurveCard {
                 delay_ms: original.delay_ms,
                 ramp_up_speed: original.ramp_up_speed,
                 ramp_down_speed: original.ramp_down_speed,
+                interpolation: original.interpolation,
+                battery_offset: stored.as_ref().and_then(|c| c.battery_offset),
+                fallback_sources: stored.map(|c| c.fallback_sources).unwrap_or_default(),
             };
             
             // Ensure unique name
@@ -410,10 +469,10 @@ impl CurveCard {
                 
                 temp_label_for_tick.set_label(&hf_core::display::format_temp_precise(display_temp));
                 
-                let percent = Self::calculate_percent_static(
-                    &data_for_tick.borrow().points,
-                    display_temp
-                );
+                let percent = {
+                    let data = data_for_tick.borrow();
+                    Self::calculate_percent_static(&data.points, data.interpolation, display_temp)
+                };
                 percent_label_for_tick.set_label(&hf_core::display::format_fan_speed_f32(percent));
                 
                 drawing_area_for_tick.queue_draw();
@@ -464,6 +523,10 @@ impl CurveCard {
             return 100.0;
         }
 
+        if data.interpolation != hf_core::CurveInterpolation::Linear {
+            return hf_core::curve_value(points, data.interpolation, temp);
+        }
+
         // Below first point
         if temp <= points[0].0 {
             return points[0].1;
@@ -542,6 +605,13 @@ impl CurveCard {
             canvas_height - margin - normalized as f64 * drawable_height
         };
 
+        // Stepped drawing ignores interpolation, as the daemon does
+        let path = if graph_style == "stepped" {
+            data.points.clone()
+        } else {
+            line_points(&data.points, data.interpolation)
+        };
+
         // Draw curve fill (only for "filled" style)
         if graph_style == "filled" {
             let fill = theme_colors::curve_fill();
@@ -549,7 +619,7 @@ impl CurveCard {
             cr.move_to(temp_to_x(graph::TEMP_MIN), percent_to_y(0.0));
             cr.line_to(temp_to_x(graph::TEMP_MIN), percent_to_y(data.points[0].1));
 
-            for (temp, percent) in &data.points {
+            for (temp, percent) in &path {
                 cr.line_to(temp_to_x(*temp), percent_to_y(*percent));
             }
 
@@ -569,7 +639,7 @@ impl CurveCard {
         cr.move_to(temp_to_x(graph::TEMP_MIN), percent_to_y(data.points[0].1));
         
         let mut prev_percent = data.points[0].1;
-        for (temp, percent) in &data.points {
+        for (temp, percent) in &path {
             match graph_style.as_str() {
                 "stepped" => {
                     // Step function: horizontal then vertical
@@ -597,11 +667,14 @@ impl CurveCard {
 
         // Draw current temperature indicator (using animated display_temp)
         let indicator_x = temp_to_x(display_temp);
-        let indicator_percent = Self::calculate_percent_static(&data.points, display_temp);
+        let indicator_percent = Self::calculate_percent_static(&data.points, data.interpolation, display_temp);
         let indicator_y = percent_to_y(indicator_percent);
 
         // Vertical line at current temperature
-        let ind_line = theme_colors::indicator_line();
+        let ind_line = match theme_colors::sensor_color(&data.temp_source_path) {
+            Some((r, g, b)) => (r, g, b, 0.8),
+            None => theme_colors::indicator_line(),
+        };
         cr.set_source_rgba(ind_line.0, ind_line.1, ind_line.2, ind_line.3);
         cr.set_line_width(2.0);
         cr.move_to(indicator_x, margin);
@@ -609,7 +682,7 @@ impl CurveCard {
         let _ = cr.stroke();
 
         // Current position indicator dot
-        let ind = theme_colors::indicator();
+        let ind = theme_colors::indicator_for(&data.temp_source_path);
         cr.set_source_rgba(ind.0, ind.1, ind.2, ind.3);
         cr.arc(indicator_x, indicator_y, graph::INDICATOR_RADIUS, 0.0, 2.0 * std::f64::consts::PI);
         let _ = cr.fill();
@@ -621,7 +694,7 @@ impl CurveCard {
         let _ = cr.stroke();
     }
 
-    fn calculate_percent_static(points: &[(f32, f32)], temp: f32) -> f32 {
+    fn calculate_percent_static(points: &[(f32, f32)], interpolation: hf_core::CurveInterpolation, temp: f32) -> f32 {
         if points.is_empty() {
             return 100.0;
         }
@@ -629,6 +702,10 @@ impl CurveCard {
         let graph_style = hf_core::get_graph_style();
         let stepped = graph_style == "stepped";
 
+        if !stepped && interpolation != hf_core::CurveInterpolation::Linear {
+            return hf_core::curve_value(points, interpolation, temp);
+        }
+
         if temp <= points[0].0 {
             return points[0].1;
         }
@@ -15,6 +15,7 @@ use tracing::{debug, warn};
 
 use super::add_curve_dialog::{AddCurveDialog, CurveData};
 use super::edit_curve_dialog::EditCurveDialog;
+use super::edit_history::{self, Scope};
 use super::dashboard::CurveCardData;
 
 /// Fan curves page state
@@ -101,7 +102,20 @@ impl CurvesPage {
             .menu_model(&sort_menu)
             .build();
 
+        // Template sharing
+        let share_menu = gtk4::gio::Menu::new();
+        share_menu.append(Some("Import Template..."), Some("curves.import-template"));
+        share_menu.append(Some("Export Template..."), Some("curves.export-template"));
+
+        let share_btn = gtk4::MenuButton::builder()
+            .icon_name("document-send-symbolic")
+            .css_classes(["circular", "flat"])
+            .tooltip_text("Share curves")
+            .menu_model(&share_menu)
+            .build();
+
         page_header.append(&page_title);
+        page_header.append(&share_btn);
         page_header.append(&sort_btn);
         page_header.append(&add_btn);
         content.append(&page_header);
@@ -184,6 +198,16 @@ impl CurvesPage {
 
         // Build initial list
         page.rebuild_list();
+
+        // Ctrl+Z / Ctrl+Shift+Z undo and redo saved curve edits
+        let state_for_undo = page.state.clone();
+        let curves_list_for_undo = page.curves_list.clone();
+        let stack_for_undo = page.stack.clone();
+        super::edit_history::connect_shortcuts(&page.container, Scope::Curves, move || {
+            let persisted = hf_core::load_curves().unwrap_or_else(|_| hf_core::CurveStore::new());
+            state_for_undo.borrow_mut().curves = persisted.all().into_iter().cloned().collect();
+            Self::rebuild_list_static(&state_for_undo, &curves_list_for_undo, &stack_for_undo);
+        });
         
         // Wire up search entry for live filtering
         let state_for_search = page.state.clone();
@@ -237,6 +261,7 @@ impl CurvesPage {
                         delay_ms: curve.delay_ms,
                         ramp_up_speed: curve.ramp_up_speed,
                         ramp_down_speed: curve.ramp_down_speed,
+                        interpolation: curve.interpolation,
                     };
                     let card = super::curve_card::CurveCard::new(&card_data);
                     curves_list_for_search.append(card.widget());
@@ -245,6 +270,34 @@ impl CurvesPage {
             }
         });
 
+        // Wire up template import/export
+        let template_actions = gtk4::gio::SimpleActionGroup::new();
+
+        let export_action = gtk4::gio::SimpleAction::new("export-template", None);
+        let share_btn_for_export = share_btn.clone();
+        export_action.connect_activate(move |_, _| {
+            super::template_dialog::show_export_dialog(&share_btn_for_export);
+        });
+        template_actions.add_action(&export_action);
+
+        let import_action = gtk4::gio::SimpleAction::new("import-template", None);
+        let share_btn_for_import = share_btn.clone();
+        let state_for_import = page.state.clone();
+        let curves_list_for_import = page.curves_list.clone();
+        let stack_for_import = page.stack.clone();
+        import_action.connect_activate(move |_, _| {
+            let state = state_for_import.clone();
+            let curves_list = curves_list_for_import.clone();
+            let stack = stack_for_import.clone();
+            super::template_dialog::show_import_dialog(&share_btn_for_import, move || {
+                let persisted = hf_core::load_curves().unwrap_or_else(|_| hf_core::CurveStore::new());
+                state.borrow_mut().curves = persisted.all().into_iter().cloned().collect();
+                Self::rebuild_list_static(&state, &curves_list, &stack);
+            });
+        });
+        template_actions.add_action(&import_action);
+        page.container.insert_action_group("curves", Some(&template_actions));
+
         // Wire up add button
         let state_for_add = page.state.clone();
         let curves_list_for_add = page.curves_list.clone();
@@ -276,14 +329,19 @@ impl CurvesPage {
                     delay_ms: data.delay_ms,
                     ramp_up_speed: data.ramp_up_speed,
                     ramp_down_speed: data.ramp_down_speed,
+                    interpolation: data.interpolation,
+                    battery_offset: None,
+                    fallback_sources: Vec::new(),
                 };
 
+                let before = edit_history::begin(Scope::Curves);
                 if let Err(e) = hf_core::save_curve(persisted.clone()) {
                     warn!("Failed to save curve: {}", e);
                 } else {
                     debug!("Saved curve: {}", data.name);
+                    edit_history::commit(before, format!("Create \"{}\"", data.name));
                     // Signal daemon to reload config
-                    if let Err(e) = hf_core::daemon_reload_config() {
+                    if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Curves) {
                         debug!("Failed to signal daemon reload: {}", e);
                     }
                 }
@@ -380,8 +438,9 @@ impl CurvesPage {
             .build();
 
         let points = curve.points.clone();
+        let interpolation = curve.interpolation;
         preview.set_draw_func(move |_, cr, width, height| {
-            Self::draw_curve_preview(cr, width, height, &points);
+            Self::draw_curve_preview(cr, width, height, &points, interpolation);
         });
 
         content.append(&preview);
@@ -430,6 +489,23 @@ impl CurvesPage {
         info.append(&name);
         info.append(&points_info);
         info.append(&range_info);
+
+        // Estimated loudness of the fans this curve drives, quietest to loudest point
+        let profiles = hf_core::load_settings()
+            .map(|s| s.noise_profiles_for_curve(&curve.id))
+            .unwrap_or_default();
+        if !profiles.is_empty() && !curve.points.is_empty() {
+            let levels: Vec<f32> = curve.points.iter().map(|(_, duty)| hf_core::curve_dba(&profiles, *duty)).collect();
+            let quietest = levels.iter().copied().fold(f32::MAX, f32::min);
+            let loudest = levels.iter().copied().fold(f32::MIN, f32::max);
+            let noise_info = Label::builder()
+                .label(&format!("Noise: ≈{:.0} - {:.0} dBA", quietest, loudest))
+                .tooltip_text("Estimated from each fan's noise profile (set with hyperfan pairings noise)")
+                .css_classes(["dim-label", "caption"])
+                .halign(gtk4::Align::Start)
+                .build();
+            info.append(&noise_info);
+        }
         content.append(&info);
 
         // Actions
@@ -439,6 +515,49 @@ impl CurvesPage {
             .valign(gtk4::Align::Center)
             .build();
 
+        let apply_btn = Button::builder()
+            .icon_name("edit-copy-symbolic")
+            .css_classes(["flat", "circular"])
+            .tooltip_text("Apply to Pairs")
+            .build();
+
+        let curve_for_apply = curve.clone();
+        let state_for_apply = state.clone();
+        let curves_list_for_apply = curves_list.clone();
+        let stack_for_apply = stack.clone();
+        apply_btn.connect_clicked(move |btn| {
+            let state = state_for_apply.clone();
+            let curves_list = curves_list_for_apply.clone();
+            let stack = stack_for_apply.clone();
+            super::apply_curve_dialog::show_apply_dialog(btn, &curve_for_apply, move || {
+                // An offset creates a new curve
+                let persisted = hf_core::load_curves().unwrap_or_else(|_| hf_core::CurveStore::new());
+                state.borrow_mut().curves = persisted.all().into_iter().cloned().collect();
+                Self::rebuild_list_static(&state, &curves_list, &stack);
+            });
+        });
+
+        let tune_btn = Button::builder()
+            .icon_name("system-run-symbolic")
+            .css_classes(["flat", "circular"])
+            .tooltip_text("Auto-Tune Under Load")
+            .build();
+
+        let curve_for_tune = curve.clone();
+        let state_for_tune = state.clone();
+        let curves_list_for_tune = curves_list.clone();
+        let stack_for_tune = stack.clone();
+        tune_btn.connect_clicked(move |btn| {
+            let state = state_for_tune.clone();
+            let curves_list = curves_list_for_tune.clone();
+            let stack = stack_for_tune.clone();
+            super::auto_tune_dialog::show_auto_tune_dialog(btn, &curve_for_tune, move || {
+                let persisted = hf_core::load_curves().unwrap_or_else(|_| hf_core::CurveStore::new());
+                state.borrow_mut().curves = persisted.all().into_iter().cloned().collect();
+                Self::rebuild_list_static(&state, &curves_list, &stack);
+            });
+        });
+
         let delete_btn = Button::builder()
             .icon_name("user-trash-symbolic")
             .css_classes(["flat", "circular", "destructive-action"])
@@ -450,7 +569,7 @@ impl CurvesPage {
         delete_btn.connect_clicked(move |btn| {
             let confirm = libadwaita::AlertDialog::builder()
                 .heading("Delete Curve?")
-                .body(&format!("Are you sure you want to delete \"{}\"? This cannot be undone.", curve_name))
+                .body(&format!("Are you sure you want to delete \"{}\"? Press Ctrl+Z to undo.", curve_name))
                 .build();
 
             confirm.add_response("cancel", "Cancel");
@@ -460,6 +579,7 @@ impl CurvesPage {
             confirm.set_close_response("cancel");
 
             let curve_id = curve_id.clone();
+            let curve_name = curve_name.clone();
             let state = state.clone();
             let curves_list = curves_list.clone();
             let stack = stack.clone();
@@ -468,11 +588,13 @@ impl CurvesPage {
                 if let Some(window) = root.downcast_ref::<gtk4::Window>() {
                     confirm.choose(window, None::<&gtk4::gio::Cancellable>, move |response| {
                         if response == "delete" {
+                            let before = edit_history::begin(Scope::Curves);
                             if let Err(e) = hf_core::delete_curve(&curve_id) {
                                 warn!("Failed to delete curve: {}", e);
                             } else {
                                 debug!("Deleted curve: {}", curve_id);
-                                if let Err(e) = hf_core::daemon_reload_config() {
+                                edit_history::commit(before, format!("Delete \"{}\"", curve_name));
+                                if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Curves) {
                                     debug!("Failed to signal daemon reload: {}", e);
                                 }
                             }
@@ -484,6 +606,8 @@ impl CurvesPage {
             }
         });
 
+        actions.append(&apply_btn);
+        actions.append(&tune_btn);
         actions.append(&delete_btn);
         content.append(&actions);
 
@@ -510,6 +634,7 @@ impl CurvesPage {
             delay_ms: curve.delay_ms,
             ramp_up_speed: curve.ramp_up_speed,
             ramp_down_speed: curve.ramp_down_speed,
+            interpolation: curve.interpolation,
         };
         
         let dialog = EditCurveDialog::new(&data);
@@ -519,6 +644,8 @@ impl CurvesPage {
             dialog.set_transient_for(win);
         }
 
+        // Live edits save while dragging, so the whole session is one undo step
+        let before = edit_history::begin(Scope::Curves);
         let curve_id = curve.id.clone();
         let state_for_save = state.clone();
         let curves_list_for_save = curves_list.clone();
@@ -526,9 +653,11 @@ impl CurvesPage {
 
         dialog.connect_save(move |updated_data| {
             // Update in-memory state (disk save + daemon reload already done by EditCurveDialog)
+            edit_history::commit(before.clone(), format!("Edit \"{}\"", updated_data.name));
             if let Some(c) = state_for_save.borrow_mut().curves.iter_mut().find(|c| c.id == curve_id) {
                 c.name = updated_data.name.clone();
                 c.points = updated_data.points.clone();
+                c.interpolation = updated_data.interpolation;
                 debug!("Updated curve in state: {}", curve_id);
             }
 
@@ -539,7 +668,13 @@ impl CurvesPage {
         dialog.present();
     }
 
-    fn draw_curve_preview(cr: &gtk4::cairo::Context, width: i32, height: i32, points: &[(f32, f32)]) {
+    fn draw_curve_preview(
+        cr: &gtk4::cairo::Context,
+        width: i32,
+        height: i32,
+        points: &[(f32, f32)],
+        interpolation: hf_core::CurveInterpolation,
+    ) {
         let w = width as f64;
         let h = height as f64;
         let m = 8.0;
@@ -582,13 +717,18 @@ impl CurvesPage {
 
         // Get graph style from settings
         let graph_style = hf_core::get_graph_style();
+        let path = if graph_style == "stepped" {
+            points.to_vec()
+        } else {
+            super::curve_card::line_points(points, interpolation)
+        };
 
         // Fill area under curve - use accent color with transparency
         let accent = super::curve_card::theme_colors::accent_color();
         cr.set_source_rgba(accent.0, accent.1, accent.2, 0.2);
         cr.move_to(temp_to_x(20.0), pct_to_y(0.0));
         cr.line_to(temp_to_x(20.0), pct_to_y(points[0].1));
-        for (t, p) in points {
+        for (t, p) in &path {
             cr.line_to(temp_to_x(*t), pct_to_y(*p));
         }
         if let Some((_, last_p)) = points.last() {
@@ -603,7 +743,7 @@ impl CurvesPage {
         cr.set_line_width(2.0);
         cr.move_to(temp_to_x(20.0), pct_to_y(points[0].1));
         let mut prev_p = points[0].1;
-        for (t, p) in points {
+        for (t, p) in &path {
             match graph_style.as_str() {
                 "stepped" => {
                     cr.line_to(temp_to_x(*t), pct_to_y(prev_p));
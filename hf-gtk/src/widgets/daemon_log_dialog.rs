@@ -0,0 +1,156 @@
+//! Daemon log viewer
+//!
+//! Shows the daemon's in-memory log (`GetLogs`) for users who cannot read the
+//! system journal. New entries are polled once a second while the window is
+//! open; the level filter re-fetches everything retained at that level.
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+use tracing::debug;
+
+use hf_core::DaemonLogLevel;
+
+/// Filter choices, most verbose first
+const LEVELS: [(&str, Option<DaemonLogLevel>); 4] = [
+    ("All", None),
+    ("Info", Some(DaemonLogLevel::Info)),
+    ("Warnings", Some(DaemonLogLevel::Warn)),
+    ("Errors", Some(DaemonLogLevel::Error)),
+];
+
+/// Open the daemon log window
+pub fn show_daemon_log_dialog(parent: &impl IsA<gtk4::Widget>) {
+    let window = adw::Window::builder()
+        .title("Daemon Log")
+        .default_width(760)
+        .default_height(520)
+        .build();
+    if let Some(parent_window) = parent.root().and_downcast::<gtk4::Window>() {
+        window.set_transient_for(Some(&parent_window));
+    }
+
+    let header = adw::HeaderBar::new();
+    let level_names: Vec<&str> = LEVELS.iter().map(|(name, _)| *name).collect();
+    let level_dropdown = gtk4::DropDown::builder()
+        .model(&gtk4::StringList::new(&level_names))
+        .selected(1)
+        .tooltip_text("Minimum level")
+        .build();
+    header.pack_start(&level_dropdown);
+
+    let buffer = gtk4::TextBuffer::new(None);
+    let text_view = gtk4::TextView::builder()
+        .buffer(&buffer)
+        .editable(false)
+        .cursor_visible(false)
+        .monospace(true)
+        .wrap_mode(gtk4::WrapMode::WordChar)
+        .left_margin(12)
+        .right_margin(12)
+        .top_margin(8)
+        .bottom_margin(8)
+        .build();
+    let scroll = gtk4::ScrolledWindow::builder()
+        .vexpand(true)
+        .child(&text_view)
+        .build();
+
+    let status = adw::StatusPage::builder()
+        .icon_name("dialog-warning-symbolic")
+        .title("Daemon Unavailable")
+        .description("The daemon log can only be shown while hyperfand is running")
+        .visible(false)
+        .vexpand(true)
+        .build();
+
+    let content = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
+    content.append(&scroll);
+    content.append(&status);
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&header);
+    toolbar.set_content(Some(&content));
+    window.set_content(Some(&toolbar));
+
+    let last_id = Rc::new(Cell::new(0u64));
+    let refresh: Rc<dyn Fn()> = {
+        let buffer = buffer.clone();
+        let level_dropdown = level_dropdown.clone();
+        let last_id = last_id.clone();
+        let scroll = scroll.clone();
+        Rc::new(move || {
+            let min_level = LEVELS.get(level_dropdown.selected() as usize).and_then(|(_, level)| *level);
+            match hf_core::daemon_get_logs(last_id.get(), min_level) {
+                Ok(entries) => {
+                    scroll.set_visible(true);
+                    status.set_visible(false);
+                    if entries.is_empty() {
+                        return;
+                    }
+                    // Follow new entries only when already scrolled to the bottom
+                    let adj = scroll.vadjustment();
+                    let at_bottom = adj.value() + adj.page_size() >= adj.upper() - 1.0;
+                    for entry in &entries {
+                        buffer.insert(&mut buffer.end_iter(), &format_entry(entry));
+                        last_id.set(entry.id);
+                    }
+                    if at_bottom {
+                        let scroll = scroll.clone();
+                        glib::idle_add_local_once(move || {
+                            let adj = scroll.vadjustment();
+                            adj.set_value(adj.upper() - adj.page_size());
+                        });
+                    }
+                }
+                Err(e) => {
+                    debug!("Daemon log poll failed: {}", e);
+                    scroll.set_visible(false);
+                    status.set_visible(true);
+                }
+            }
+        })
+    };
+
+    let refresh_for_level = refresh.clone();
+    let buffer_for_level = buffer.clone();
+    let last_id_for_level = last_id.clone();
+    level_dropdown.connect_selected_notify(move |_| {
+        buffer_for_level.set_text("");
+        last_id_for_level.set(0);
+        refresh_for_level();
+    });
+
+    refresh();
+    let window_weak = window.downgrade();
+    glib::timeout_add_local(Duration::from_secs(1), move || {
+        let Some(window) = window_weak.upgrade() else {
+            return glib::ControlFlow::Break;
+        };
+        if window.is_visible() {
+            refresh();
+        }
+        glib::ControlFlow::Continue
+    });
+
+    window.present();
+}
+
+/// One line per entry: local time, level, subsystem, message
+fn format_entry(entry: &hf_core::DaemonLogEntry) -> String {
+    let time = chrono::DateTime::from_timestamp_millis(entry.timestamp_ms as i64)
+        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "--:--:--".to_string());
+    let level = match entry.level {
+        DaemonLogLevel::Error => "ERROR",
+        DaemonLogLevel::Warn => "WARN ",
+        DaemonLogLevel::Info => "INFO ",
+        DaemonLogLevel::Debug => "DEBUG",
+        DaemonLogLevel::Trace => "TRACE",
+    };
+    format!("{} {} [{}] {}\n", time, level, entry.subsystem, entry.message)
+}
@@ -8,12 +8,14 @@ use gtk4::{Button, Label, Orientation, ScrolledWindow};
 use gtk4::glib;
 use libadwaita as adw;
 use libadwaita::prelude::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use tracing::{debug, warn};
 
 use hf_core::daemon_client;
 
+use super::gpu_power_group::GpuPowerGroup;
+
 /// Theme-aware colors for graph drawing
 /// Uses system accent color from curve_card::theme_colors
 mod theme_colors {
@@ -84,6 +86,13 @@ mod animation {
     pub const VELOCITY_THRESHOLD: f32 = 0.02;
 }
 
+/// Manual speed durations offered on pair cards, with their override TTL
+const OVERRIDE_DURATIONS: [(&str, u32); 3] = [
+    ("5 minutes", 5 * 60 * 1000),
+    ("15 minutes", 15 * 60 * 1000),
+    ("1 hour", hf_core::MAX_PWM_OVERRIDE_TTL_MS),
+];
+
 /// Animation state for smooth temperature transitions
 struct TempAnimation {
     target: f32,
@@ -152,6 +161,8 @@ pub struct CurveCardData {
     pub ramp_up_speed: f32,
     /// Ramp down speed in percent per second
     pub ramp_down_speed: f32,
+    /// How fan speed is interpolated between points
+    pub interpolation: hf_core::CurveInterpolation,
 }
 
 /// Main dashboard widget
@@ -166,6 +177,7 @@ pub struct Dashboard {
     curves_stack: gtk4::Stack,
     pairs_stack: gtk4::Stack,
     add_pair_btn: Button,
+    gpu_power: GpuPowerGroup,
     on_navigate_curves: Rc<RefCell<Option<Box<dyn Fn()>>>>,
 }
 
@@ -335,6 +347,10 @@ impl Dashboard {
 
         main_content.append(&pairs_section);
 
+        // GPU power limits (only with advanced.gpu_power_limits_enabled)
+        let gpu_power = GpuPowerGroup::new();
+        main_content.append(&gpu_power.container);
+
         scroll.set_child(Some(&main_content));
         container.append(&scroll);
 
@@ -367,6 +383,7 @@ impl Dashboard {
             curves_stack,
             pairs_stack,
             add_pair_btn: add_pair_btn.clone(),
+            gpu_power,
             on_navigate_curves,
         };
 
@@ -380,6 +397,7 @@ impl Dashboard {
         let pairs_list_for_refresh = dashboard.pairs_list.clone();
         let pairs_stack_for_refresh = dashboard.pairs_stack.clone();
         let add_pair_btn_for_refresh = dashboard.add_pair_btn.clone();
+        let gpu_power_for_refresh = dashboard.gpu_power.clone();
         refresh_btn.connect_clicked(move |btn| {
             // Show loading state
             btn.set_sensitive(false);
@@ -429,6 +447,7 @@ impl Dashboard {
             
             // Rebuild UI
             Self::rebuild_pairs_list_static(&state_for_refresh, &pairs_list_for_refresh, &pairs_stack_for_refresh, &add_pair_btn_for_refresh);
+            gpu_power_for_refresh.refresh();
             
             let curve_count = state_for_refresh.borrow().curves.len();
             let pair_count = state_for_refresh.borrow().pairs.len();
@@ -498,6 +517,7 @@ impl Dashboard {
                     fan_paths: data.fan_paths.clone(),
                     hysteresis_ms: data.hysteresis_ms,
                     active: true,
+                    profile_curves: Default::default(),
                 };
                 
                 if let Err(e) = hf_core::save_pair(settings_pair) {
@@ -505,7 +525,7 @@ impl Dashboard {
                 } else {
                     debug!("Pair saved to settings: {}", data.name);
                     // Signal daemon to reload config
-                    if let Err(e) = hf_core::daemon_reload_config() {
+                    if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Settings) {
                         debug!("Failed to signal daemon reload: {}", e);
                     } else {
                         debug!("Daemon reload signaled");
@@ -705,15 +725,23 @@ impl Dashboard {
         let card = adw::Bin::builder()
             .css_classes(["card", "activatable"])
             .build();
+
+        let pwm_paths = if pair.fan_paths.is_empty() { vec![pair.fan_path.clone()] } else { pair.fan_paths.clone() };
+        let (override_btn, override_popover) = Self::create_override_button(&pwm_paths);
         
         // Make card clickable for editing
         let gesture = gtk4::GestureClick::new();
+        let override_popover_for_edit = override_popover.clone();
         let pair_for_edit = pair.clone();
         let state_for_edit = state.clone();
         let pairs_list_for_edit = pairs_list.clone();
         let pairs_stack_for_edit = pairs_stack.clone();
         let add_pair_btn_for_edit = add_pair_btn.clone();
         gesture.connect_released(move |gesture, _, _, _| {
+            // Clicks inside the manual speed popover bubble up to the card
+            if override_popover_for_edit.is_visible() {
+                return;
+            }
             if let Some(widget) = gesture.widget() {
                 Self::show_edit_pair_dialog(
                     &widget,
@@ -767,6 +795,12 @@ impl Dashboard {
             .css_classes(["title-2", "numeric", "accent"])
             .build();
 
+        // Countdown of a timed manual speed, from this or any other client
+        let override_badge = Label::builder()
+            .css_classes(["caption", "warning", "numeric"])
+            .visible(false)
+            .build();
+
         let delete_btn = Button::builder()
             .icon_name("user-trash-symbolic")
             .css_classes(["flat", "circular", "destructive-action"])
@@ -774,9 +808,11 @@ impl Dashboard {
             .build();
 
         header.append(&name);
+        header.append(&override_badge);
         header.append(&temp_label);
         header.append(&arrow);
         header.append(&percent_label);
+        header.append(&override_btn);
         header.append(&delete_btn);
         content.append(&header);
 
@@ -812,9 +848,10 @@ impl Dashboard {
         // Draw function uses animated temperature from RefCell
         let anim_for_draw = anim_state.clone();
         let points_for_draw = points.clone();
+        let temp_path_for_draw = temp_path.clone();
         preview.set_draw_func(move |_, cr, width, height| {
             let temp = anim_for_draw.borrow().display;
-            Self::draw_live_curve(cr, width, height, &points_for_draw, temp);
+            Self::draw_live_curve(cr, width, height, &points_for_draw, temp, &temp_path_for_draw);
         });
 
         content.append(&preview);
@@ -841,9 +878,13 @@ impl Dashboard {
         let poll_interval_ms = hf_core::get_cached_settings().general.poll_interval_ms as u64;
         let poll_interval_ms = poll_interval_ms.max(50);
         
+        // Overridden value shown instead of the curve's
+        let override_value: Rc<Cell<Option<u8>>> = Rc::new(Cell::new(None));
+
         let anim_for_poll = anim_state.clone();
         let temp_label_for_poll = temp_label.clone();
         let percent_label_for_poll = percent_label.clone();
+        let override_value_for_poll = override_value.clone();
         let points_for_poll = points.clone();
         let temp_path_for_poll = temp_path.clone();
         
@@ -872,12 +913,43 @@ impl Dashboard {
                 let temp_str = hf_core::display::format_temp_precise(temp);
                 temp_label_for_poll.set_label(&temp_str);
                 
-                let percent = Self::interpolate(&points_for_poll, temp);
+                let percent = match override_value_for_poll.get() {
+                    Some(value) => hf_core::display::pwm_to_percent_f32(value),
+                    None => Self::interpolate(&points_for_poll, temp),
+                };
                 let percent_str = hf_core::display::format_fan_speed_f32(percent);
                 percent_label_for_poll.set_label(&percent_str);
             }
             glib::ControlFlow::Continue
         });
+
+        // Override badge, refreshed from the runtime's override cache
+        let card_weak_override = glib::SendWeakRef::from(card.downgrade());
+        glib::timeout_add_local(std::time::Duration::from_secs(1), move || {
+            if card_weak_override.upgrade().is_none() {
+                return glib::ControlFlow::Break;
+            }
+
+            let overrides = crate::runtime::get_overrides().unwrap_or_default();
+            match overrides.iter().find(|ov| pwm_paths.contains(&ov.pwm_path)) {
+                Some(ov) => {
+                    let secs = ov.remaining_ms.div_ceil(1000);
+                    override_badge.set_label(&format!("Manual {}:{:02}", secs / 60, secs % 60));
+                    override_badge.set_tooltip_text(Some(&format!(
+                        "Held at {} by {} - the curve takes over when the timer runs out",
+                        hf_core::display::format_fan_speed(hf_core::display::pwm_to_percent(ov.value as u32)),
+                        Self::override_owner(ov)
+                    )));
+                    override_badge.set_visible(true);
+                    override_value.set(Some(ov.value));
+                }
+                None => {
+                    override_badge.set_visible(false);
+                    override_value.set(None);
+                }
+            }
+            glib::ControlFlow::Continue
+        });
         
         // Animation frame timer for smooth indicator movement (60fps)
         let anim_for_frame = anim_state.clone();
@@ -928,7 +1000,7 @@ impl Dashboard {
                             if let Err(e) = hf_core::delete_pair(&pair_id_for_settings) {
                                 warn!("Failed to delete pair from settings: {}", e);
                             } else {
-                                if let Err(e) = hf_core::daemon_reload_config() {
+                                if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Settings) {
                                     debug!("Failed to signal daemon reload: {}", e);
                                 }
                             }
@@ -948,6 +1020,94 @@ impl Dashboard {
         card
     }
 
+    /// Manual speed button: holds the pair's outputs at a chosen speed for a while
+    fn create_override_button(pwm_paths: &[String]) -> (gtk4::MenuButton, gtk4::Popover) {
+        let content = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(8)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .build();
+
+        content.append(
+            &Label::builder()
+                .label("Manual Speed")
+                .css_classes(["heading"])
+                .halign(gtk4::Align::Start)
+                .build(),
+        );
+
+        let scale = gtk4::Scale::with_range(Orientation::Horizontal, 0.0, 100.0, 1.0);
+        scale.set_value(50.0);
+        scale.set_digits(0);
+        scale.set_draw_value(true);
+        scale.set_format_value_func(|_, value| format!("{:.0}%", value));
+        scale.set_width_request(240);
+        scale.update_property(&[gtk4::accessible::Property::Label("Manual fan speed")]);
+        content.append(&scale);
+
+        let duration = gtk4::DropDown::from_strings(
+            &OVERRIDE_DURATIONS.iter().map(|(label, _)| *label).collect::<Vec<_>>(),
+        );
+        content.append(&duration);
+
+        let buttons = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(8)
+            .homogeneous(true)
+            .build();
+        let clear_btn = Button::builder().label("Return to Curve").build();
+        let apply_btn = Button::builder().label("Apply").css_classes(["suggested-action"]).build();
+        buttons.append(&clear_btn);
+        buttons.append(&apply_btn);
+        content.append(&buttons);
+
+        let popover = gtk4::Popover::builder().child(&content).build();
+        let button = gtk4::MenuButton::builder()
+            .icon_name("speedometer-symbolic")
+            .css_classes(["flat", "circular"])
+            .tooltip_text("Manual Speed")
+            .popover(&popover)
+            .build();
+
+        let paths = pwm_paths.to_vec();
+        let popover_for_apply = popover.clone();
+        apply_btn.connect_clicked(move |_| {
+            let value = hf_core::display::percent_to_pwm_u8(scale.value() as f32);
+            let ttl_ms = OVERRIDE_DURATIONS[(duration.selected() as usize).min(OVERRIDE_DURATIONS.len() - 1)].1;
+            for path in &paths {
+                if let Err(e) = hf_core::daemon_set_pwm_override(path, value, ttl_ms) {
+                    warn!("Failed to set manual speed for {}: {}", path, e);
+                }
+            }
+            popover_for_apply.popdown();
+        });
+
+        let paths = pwm_paths.to_vec();
+        let popover_for_clear = popover.clone();
+        clear_btn.connect_clicked(move |_| {
+            for path in &paths {
+                if let Err(e) = hf_core::daemon_clear_pwm_override(path) {
+                    warn!("Failed to clear manual speed for {}: {}", path, e);
+                }
+            }
+            popover_for_clear.popdown();
+        });
+
+        (button, popover)
+    }
+
+    /// Who set an override, for its badge tooltip
+    fn override_owner(ov: &hf_core::DaemonActiveOverride) -> String {
+        match ov.uid {
+            Some(uid) if uid == unsafe { libc::getuid() } => "you".to_string(),
+            Some(uid) => format!("uid {}", uid),
+            None => "Hyperfan (calibration or auto-tune)".to_string(),
+        }
+    }
+
     fn draw_mini_curve(cr: &gtk4::cairo::Context, width: i32, height: i32, points: &[(f32, f32)]) {
         let w = width as f64;
         let h = height as f64;
@@ -1002,7 +1162,7 @@ impl Dashboard {
         let _ = cr.stroke();
     }
 
-    fn draw_live_curve(cr: &gtk4::cairo::Context, width: i32, height: i32, points: &[(f32, f32)], current_temp: f32) {
+    fn draw_live_curve(cr: &gtk4::cairo::Context, width: i32, height: i32, points: &[(f32, f32)], current_temp: f32, temp_path: &str) {
         let w = width as f64;
         let h = height as f64;
         let m = 8.0;
@@ -1076,8 +1236,9 @@ impl Dashboard {
         let ix = temp_to_x(clamped_temp);
         let iy = pct_to_y(current_percent);
 
-        // Vertical line
-        let ind_line = theme_colors::indicator_line();
+        // Vertical line, in the sensor's color when one is assigned
+        let sensor_color = super::curve_card::theme_colors::sensor_color(temp_path);
+        let ind_line = sensor_color.map_or_else(theme_colors::indicator_line, |(r, g, b)| (r, g, b, 0.8));
         cr.set_source_rgba(ind_line.0, ind_line.1, ind_line.2, ind_line.3);
         cr.set_line_width(2.0);
         cr.move_to(ix, m);
@@ -1085,7 +1246,7 @@ impl Dashboard {
         let _ = cr.stroke();
 
         // Indicator dot
-        let ind = theme_colors::indicator();
+        let ind = sensor_color.unwrap_or_else(theme_colors::indicator);
         cr.set_source_rgb(ind.0, ind.1, ind.2);
         cr.arc(ix, iy, 5.0, 0.0, 2.0 * std::f64::consts::PI);
         let _ = cr.fill();
@@ -1124,7 +1285,11 @@ impl Dashboard {
         dialog.connect_create(move |data: PairData| {
             debug!("Edit pair callback: id={}, name={}", data.id, data.name);
             
-            // Save updated pair to settings
+            // Save updated pair to settings (keeping its per-profile curves)
+            let profile_curves = hf_core::get_cached_settings().active_pairs.into_iter()
+                .find(|p| p.id == data.id)
+                .map(|p| p.profile_curves)
+                .unwrap_or_default();
             let settings_pair = hf_core::FanCurvePair {
                 id: data.id.clone(),
                 name: data.name.clone(),
@@ -1134,6 +1299,7 @@ impl Dashboard {
                 fan_paths: data.fan_paths.clone(),
                 hysteresis_ms: data.hysteresis_ms,
                 active: true,
+                profile_curves,
             };
             
             if let Err(e) = hf_core::save_pair(settings_pair) {
@@ -1141,7 +1307,7 @@ impl Dashboard {
             } else {
                 debug!("Pair updated in settings: {}", data.name);
                 // Signal daemon to reload config
-                if let Err(e) = hf_core::daemon_reload_config() {
+                if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Settings) {
                     debug!("Failed to signal daemon reload: {}", e);
                 } else {
                     debug!("Daemon reload signaled");
@@ -1231,6 +1397,7 @@ impl Dashboard {
         
         // Rebuild UI
         Self::rebuild_pairs_list_static(&self.state, &self.pairs_list, &self.pairs_stack, &self.add_pair_btn);
+        self.gpu_power.refresh();
     }
 
     /// Connect a callback for when user wants to navigate to curves page
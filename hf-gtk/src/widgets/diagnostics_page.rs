@@ -0,0 +1,274 @@
+//! Diagnostics Page
+//!
+//! Runs `hf_core::run_diagnostics` off the UI thread and lists each check
+//! with its status, what was found and what to do about it. Checks whose fix
+//! is a service command (install, start or enable the daemon, disable a
+//! conflicting program) get a button that applies it and runs the checks
+//! again. The header also saves a redacted bug report bundle.
+
+use gtk4::{gio, glib};
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Image, Label, Orientation, ScrolledWindow};
+use hf_core::{DiagnosticCheck, DiagnosticFix, DiagnosticStatus};
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// How often a running check or fix is polled for its result
+const RESULT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Wait after a fix before checking again, so a started daemon can open its socket
+const RECHECK_DELAY: Duration = Duration::from_secs(1);
+
+/// Page listing the setup checks
+pub struct DiagnosticsPage {
+    container: GtkBox,
+    group: adw::PreferencesGroup,
+    rows: RefCell<Vec<adw::ActionRow>>,
+    spinner: adw::Spinner,
+    rerun_btn: Button,
+    running: Cell<bool>,
+}
+
+impl DiagnosticsPage {
+    pub fn new() -> Rc<Self> {
+        let container = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .build();
+
+        // Header - HIG: consistent 24px margins, 12px bottom spacing
+        let header_box = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .margin_start(24)
+            .margin_end(24)
+            .margin_top(24)
+            .margin_bottom(12)
+            .build();
+
+        let title = Label::builder()
+            .label("Diagnostics")
+            .css_classes(["title-1"])
+            .hexpand(true)
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let spinner = adw::Spinner::builder().visible(false).build();
+        let rerun_btn = Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .css_classes(["circular", "flat"])
+            .tooltip_text("Run checks again")
+            .build();
+
+        let report_btn = Button::builder()
+            .icon_name("document-save-symbolic")
+            .css_classes(["circular", "flat"])
+            .tooltip_text("Save a bug report bundle")
+            .build();
+        report_btn.connect_clicked(save_bug_report);
+
+        header_box.append(&title);
+        header_box.append(&spinner);
+        header_box.append(&report_btn);
+        header_box.append(&rerun_btn);
+        container.append(&header_box);
+
+        let group = adw::PreferencesGroup::builder()
+            .description("Everything fan control depends on, from the daemon service to other programs driving the same fans")
+            .margin_start(24)
+            .margin_end(24)
+            .margin_bottom(24)
+            .build();
+
+        let scroll = ScrolledWindow::builder()
+            .vexpand(true)
+            .hexpand(true)
+            .child(&group)
+            .build();
+        container.append(&scroll);
+
+        let page = Rc::new(Self {
+            container,
+            group,
+            rows: RefCell::new(Vec::new()),
+            spinner,
+            rerun_btn,
+            running: Cell::new(false),
+        });
+
+        let weak = Rc::downgrade(&page);
+        page.rerun_btn.connect_clicked(move |_| {
+            if let Some(page) = weak.upgrade() {
+                page.run();
+            }
+        });
+        page
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.container
+    }
+
+    /// Run every check in the background and show the results
+    pub fn run(self: &Rc<Self>) {
+        if self.running.replace(true) {
+            return;
+        }
+        self.spinner.set_visible(true);
+        self.rerun_btn.set_sensitive(false);
+
+        let (tx, rx) = mpsc::channel::<Vec<DiagnosticCheck>>();
+        thread::spawn(move || {
+            let _ = tx.send(hf_core::run_diagnostics());
+        });
+
+        let weak = Rc::downgrade(self);
+        glib::timeout_add_local(RESULT_POLL_INTERVAL, move || {
+            let checks = match rx.try_recv() {
+                Ok(checks) => checks,
+                Err(mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                Err(mpsc::TryRecvError::Disconnected) => Vec::new(),
+            };
+            if let Some(page) = weak.upgrade() {
+                page.running.set(false);
+                page.spinner.set_visible(false);
+                page.rerun_btn.set_sensitive(true);
+                page.show(&checks);
+            }
+            glib::ControlFlow::Break
+        });
+    }
+
+    fn show(self: &Rc<Self>, checks: &[DiagnosticCheck]) {
+        for row in self.rows.borrow_mut().drain(..) {
+            self.group.remove(&row);
+        }
+        for check in checks {
+            let row = build_check_row(check, Rc::downgrade(self));
+            self.group.add(&row);
+            self.rows.borrow_mut().push(row);
+        }
+    }
+}
+
+fn build_check_row(check: &DiagnosticCheck, page: Weak<DiagnosticsPage>) -> adw::ActionRow {
+    let subtitle = match &check.hint {
+        Some(hint) if check.status != DiagnosticStatus::Ok => format!("{}\n{}", check.summary, hint),
+        _ => check.summary.clone(),
+    };
+    let row = adw::ActionRow::builder()
+        .title(check.title)
+        .subtitle(glib::markup_escape_text(&subtitle).as_str())
+        .subtitle_selectable(true)
+        .build();
+
+    let (icon_name, css_class, status) = match check.status {
+        DiagnosticStatus::Ok => ("emblem-ok-symbolic", "success", "Passed"),
+        DiagnosticStatus::Warning => ("dialog-warning-symbolic", "warning", "Warning"),
+        DiagnosticStatus::Error => ("dialog-error-symbolic", "error", "Failed"),
+        DiagnosticStatus::Skipped => ("action-unavailable-symbolic", "dim-label", "Not applicable"),
+    };
+    let icon = Image::builder()
+        .icon_name(icon_name)
+        .css_classes([css_class])
+        .tooltip_text(status)
+        .build();
+    icon.update_property(&[gtk4::accessible::Property::Label(status)]);
+    row.add_prefix(&icon);
+
+    if let Some(fix) = &check.fix {
+        let fix_btn = Button::builder()
+            .label(fix.label())
+            .valign(gtk4::Align::Center)
+            .css_classes(["suggested-action"])
+            .build();
+        let fix = fix.clone();
+        fix_btn.connect_clicked(move |btn| apply_fix(btn, fix.clone(), page.clone()));
+        row.add_suffix(&fix_btn);
+    }
+    row
+}
+
+/// Apply `fix` in the background (it may ask for a password), then re-run the checks
+fn apply_fix(btn: &Button, fix: DiagnosticFix, page: Weak<DiagnosticsPage>) {
+    btn.set_sensitive(false);
+    btn.set_label("Working...");
+
+    let (tx, rx) = mpsc::channel::<Result<(), String>>();
+    thread::spawn(move || {
+        let _ = tx.send(hf_core::diagnostics::apply_fix(&fix));
+    });
+
+    let btn = btn.clone();
+    glib::timeout_add_local(RESULT_POLL_INTERVAL, move || {
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+            Err(mpsc::TryRecvError::Disconnected) => return glib::ControlFlow::Break,
+        };
+        if let Err(e) = result {
+            tracing::warn!("Diagnostics fix failed: {}", e);
+            btn.set_label("Retry");
+            btn.set_sensitive(true);
+            btn.set_tooltip_text(Some(&e));
+            return glib::ControlFlow::Break;
+        }
+        let page = page.clone();
+        glib::timeout_add_local_once(RECHECK_DELAY, move || {
+            if let Some(page) = page.upgrade() {
+                page.run();
+            }
+        });
+        glib::ControlFlow::Break
+    });
+}
+
+/// Ask where to save a bug report bundle, then collect it in the background
+fn save_bug_report(btn: &Button) {
+    let window = btn.root().and_downcast::<gtk4::Window>();
+    let dialog = gtk4::FileDialog::builder()
+        .title("Save Bug Report")
+        .initial_name(hf_core::default_bundle_name())
+        .build();
+
+    let btn = btn.clone();
+    dialog.save(window.as_ref(), gio::Cancellable::NONE, move |result| {
+        let Some(path) = result.ok().and_then(|file| file.path()) else { return };
+        btn.set_sensitive(false);
+
+        let (tx, rx) = mpsc::channel::<Result<std::path::PathBuf, String>>();
+        thread::spawn(move || {
+            let _ = tx.send(hf_core::create_support_bundle(&path).map_err(|e| e.to_string()));
+        });
+
+        glib::timeout_add_local(RESULT_POLL_INTERVAL, move || {
+            let result = match rx.try_recv() {
+                Ok(result) => result,
+                Err(mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                Err(mpsc::TryRecvError::Disconnected) => return glib::ControlFlow::Break,
+            };
+            btn.set_sensitive(true);
+            let (heading, body) = match result {
+                Ok(path) => (
+                    "Bug Report Saved",
+                    format!(
+                        "{}\n\nThe hostname, home directory, serial numbers and EC settings were removed. Check the contents before attaching it to an issue.",
+                        path.display()
+                    ),
+                ),
+                Err(e) => {
+                    tracing::warn!("Failed to save bug report: {}", e);
+                    ("Bug Report Failed", e)
+                }
+            };
+            let dialog = adw::AlertDialog::builder().heading(heading).body(&body).build();
+            dialog.add_response("close", "Close");
+            dialog.present(Some(&btn));
+            glib::ControlFlow::Break
+        });
+    });
+}
@@ -12,6 +12,7 @@
 //! - Color categories (16 colors) with filtering
 //! - Confidence levels (low/medium/high)
 //! - Sorting by name, color, favorites, or value
+//! - Watch view sampling a register range and highlighting values that change
 //! - Persistent storage in ~/.config/hyperfan/ec_profile.json
 
 #![allow(dead_code)]
@@ -22,11 +23,14 @@ use gtk4::{
     ScrolledWindow, TextView, ToggleButton,
 };
 use gtk4::gio;
+use gtk4::glib;
 use libadwaita as adw;
 use libadwaita::prelude::*;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::path::PathBuf;
 use tracing::{debug, error, info, warn};
 
@@ -149,6 +153,14 @@ struct EcRegisterData {
     hint: Option<String>,
 }
 
+/// Register row in the watch dialog
+struct WatchRow {
+    row: adw::ActionRow,
+    count_label: Label,
+    /// Watch rounds in which the register changed
+    rounds: u32,
+}
+
 /// EC Control Page
 pub struct EcControlPage {
     container: GtkBox,
@@ -350,6 +362,12 @@ impl EcControlPage {
             .halign(gtk4::Align::End)
             .build();
 
+        let watch_btn = Button::builder()
+            .label("Watch Changes")
+            .tooltip_text("Sample registers repeatedly and show the ones that change")
+            .build();
+        action_box.append(&watch_btn);
+
         let export_btn = Button::builder()
             .label("Export Profile")
             .icon_name("document-save-symbolic")
@@ -428,6 +446,15 @@ impl EcControlPage {
             }
         });
 
+        // Wire up watch button
+        let selected_chip_for_watch = selected_chip.clone();
+        let profile_data_for_watch = profile_data.clone();
+        watch_btn.connect_clicked(move |btn| {
+            if let Some(ref chip_info) = *selected_chip_for_watch.borrow() {
+                Self::show_watch_dialog(btn, chip_info, &profile_data_for_watch);
+            }
+        });
+
         // Wire up export button
         let selected_chip_for_export = selected_chip.clone();
         let registers_for_export = registers.clone();
@@ -1080,6 +1107,201 @@ impl EcControlPage {
         }
     }
 
+    /// Watch a register range for changes while the dialog is open
+    ///
+    /// Each round asks the daemon for one short watch window; registers that
+    /// changed in the latest round are highlighted, earlier ones stay listed
+    /// with how often they changed.
+    fn show_watch_dialog(
+        btn: &Button,
+        chip: &EcChipMetadata,
+        profile_data: &Rc<RefCell<EcProfileData>>,
+    ) {
+        let dialog = adw::Dialog::builder()
+            .title(format!("Watch {}", chip.name))
+            .content_width(480)
+            .content_height(560)
+            .build();
+
+        let content = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .build();
+
+        let header = adw::HeaderBar::builder()
+            .show_end_title_buttons(true)
+            .build();
+        let toggle_btn = ToggleButton::builder()
+            .label("Start")
+            .css_classes(["suggested-action"])
+            .build();
+        header.pack_start(&toggle_btn);
+        content.append(&header);
+
+        let prefs = adw::PreferencesGroup::builder()
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .build();
+        let start_row = adw::SpinRow::with_range(0.0, 255.0, 1.0);
+        start_row.set_title("First Register");
+        let count_row = adw::SpinRow::with_range(1.0, 64.0, 1.0);
+        count_row.set_title("Registers");
+        count_row.set_value(64.0);
+        let interval_row = adw::SpinRow::with_range(
+            hf_core::MIN_EC_WATCH_INTERVAL_MS as f64,
+            1000.0,
+            50.0,
+        );
+        interval_row.set_title("Sample Interval (ms)");
+        interval_row.set_value(250.0);
+        prefs.add(&start_row);
+        prefs.add(&count_row);
+        prefs.add(&interval_row);
+        content.append(&prefs);
+
+        let status_label = Label::builder()
+            .label("Change fan speeds or load while watching to find fan registers")
+            .css_classes(["dim-label", "caption"])
+            .wrap(true)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .margin_bottom(6)
+            .build();
+        content.append(&status_label);
+
+        let change_list = ListBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .margin_start(12)
+            .margin_end(12)
+            .margin_bottom(12)
+            .build();
+        let list_scroll = ScrolledWindow::builder()
+            .vexpand(true)
+            .child(&change_list)
+            .build();
+        content.append(&list_scroll);
+        dialog.set_child(Some(&content));
+
+        let rows: Rc<RefCell<HashMap<u8, WatchRow>>> = Rc::new(RefCell::new(HashMap::new()));
+        // Stop flag of the running watch thread, if any
+        let running: Rc<RefCell<Option<Arc<AtomicBool>>>> = Rc::new(RefCell::new(None));
+        let chip_path = chip.path.clone();
+
+        let running_for_toggle = running.clone();
+        let profile_for_toggle = profile_data.clone();
+        toggle_btn.connect_toggled(move |toggle| {
+            if let Some(stop) = running_for_toggle.borrow_mut().take() {
+                stop.store(true, Ordering::Relaxed);
+            }
+            if !toggle.is_active() {
+                toggle.set_label("Start");
+                return;
+            }
+            toggle.set_label("Stop");
+            let stop = Arc::new(AtomicBool::new(false));
+            *running_for_toggle.borrow_mut() = Some(stop.clone());
+
+            let start = start_row.value() as u8;
+            let count = (count_row.value() as u16).min(256 - start as u16).max(1) as u8;
+            let interval_ms = interval_row.value() as u32;
+            let samples = (hf_core::MAX_EC_WATCH_DURATION_MS / interval_ms + 1).min(20);
+
+            let (tx, rx) = std::sync::mpsc::channel();
+            let path = chip_path.clone();
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let result = hf_core::daemon_watch_ec_registers(&path, start, count, interval_ms, samples);
+                    let failed = result.is_err();
+                    if tx.send(result).is_err() || failed {
+                        break;
+                    }
+                }
+            });
+
+            let rows = rows.clone();
+            let change_list = change_list.clone();
+            let status_label = status_label.clone();
+            let profile_data = profile_for_toggle.clone();
+            let toggle = toggle.clone();
+            glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+                loop {
+                    match rx.try_recv() {
+                        Ok(Ok(changes)) => {
+                            Self::update_watch_rows(&change_list, &rows, &changes, &profile_data.borrow());
+                            status_label.set_label(&format!(
+                                "{} register(s) changed so far, {} in the last {} ms",
+                                rows.borrow().len(),
+                                changes.len(),
+                                interval_ms * (samples - 1),
+                            ));
+                        }
+                        Ok(Err(e)) => {
+                            warn!("EC watch failed: {}", e);
+                            status_label.set_label(&format!("Watch failed: {}", e));
+                            toggle.set_active(false);
+                            return glib::ControlFlow::Break;
+                        }
+                        Err(std::sync::mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                        Err(std::sync::mpsc::TryRecvError::Disconnected) => return glib::ControlFlow::Break,
+                    }
+                }
+            });
+        });
+
+        dialog.connect_closed(move |_| {
+            if let Some(stop) = running.borrow_mut().take() {
+                stop.store(true, Ordering::Relaxed);
+            }
+        });
+
+        if let Some(root) = btn.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
+    /// Add or refresh watch rows; only registers from `changes` stay highlighted
+    fn update_watch_rows(
+        list: &ListBox,
+        rows: &Rc<RefCell<HashMap<u8, WatchRow>>>,
+        changes: &[hf_core::DaemonEcRegisterChange],
+        profile_data: &EcProfileData,
+    ) {
+        let mut rows = rows.borrow_mut();
+        for watched in rows.values() {
+            watched.row.remove_css_class("accent");
+        }
+        for change in changes {
+            let watched = rows.entry(change.register).or_insert_with(|| {
+                let name = profile_data.registers.get(&change.register)
+                    .and_then(|u| u.friendly_name.clone())
+                    .or_else(|| change.label.clone());
+                let row = adw::ActionRow::builder()
+                    .title(&match name {
+                        Some(name) => format!("0x{:02X} - {}", change.register, name),
+                        None => format!("0x{:02X}", change.register),
+                    })
+                    .subtitle_selectable(true)
+                    .build();
+                let count_label = Label::builder()
+                    .css_classes(["dim-label"])
+                    .build();
+                row.add_suffix(&count_label);
+                list.append(&row);
+                WatchRow { row, count_label, rounds: 0 }
+            });
+            watched.rounds += 1;
+
+            let values: Vec<String> = change.values.iter().map(|v| format!("0x{:02X}", v)).collect();
+            watched.row.set_subtitle(&values.join(" → "));
+            watched.row.add_css_class("accent");
+            watched.count_label.set_label(&format!("changed in {} round(s)", watched.rounds));
+        }
+    }
+
     fn get_register_category(reg: u8) -> String {
         match reg {
             0x00..=0x0F => "Configuration".to_string(),
@@ -121,6 +121,7 @@ pub struct EditCurveDialog {
     points: Rc<RefCell<Vec<(f32, f32)>>>,
     original_points: Vec<(f32, f32)>,
     original_name: String,
+    original_interpolation: hf_core::CurveInterpolation,
     current_temp: Rc<RefCell<f32>>,
     curve_id: String,
     temp_source_path: String,
@@ -132,6 +133,7 @@ pub struct EditCurveDialog {
     delay_ms: Rc<RefCell<u32>>,
     ramp_up_speed: Rc<RefCell<f32>>,
     ramp_down_speed: Rc<RefCell<f32>>,
+    interpolation: Rc<RefCell<hf_core::CurveInterpolation>>,
 }
 
 impl EditCurveDialog {
@@ -155,6 +157,7 @@ impl EditCurveDialog {
         let delay_ms: Rc<RefCell<u32>> = Rc::new(RefCell::new(data.delay_ms));
         let ramp_up_speed: Rc<RefCell<f32>> = Rc::new(RefCell::new(data.ramp_up_speed));
         let ramp_down_speed: Rc<RefCell<f32>> = Rc::new(RefCell::new(data.ramp_down_speed));
+        let interpolation: Rc<RefCell<hf_core::CurveInterpolation>> = Rc::new(RefCell::new(data.interpolation));
 
         let content = GtkBox::builder()
             .orientation(Orientation::Vertical)
@@ -204,6 +207,7 @@ impl EditCurveDialog {
         let name_row = adw::ActionRow::builder()
             .title("Name")
             .build();
+        name_entry.update_property(&[gtk4::accessible::Property::Label("Curve name")]);
         name_row.add_suffix(&name_entry);
         name_group.add(&name_row);
 
@@ -212,13 +216,20 @@ impl EditCurveDialog {
         // Graph section
         let graph_group = adw::PreferencesGroup::builder()
             .title("Fan Curve")
-            .description("Left-click to add, right-click to remove, drag to move")
+            .description("Left-click to add, right-click to remove, drag to move. With the graph focused, \
+                          Left/Right select a point, Up/Down change its speed, Shift+Left/Right its temperature, \
+                          Insert/Delete add and remove points.")
             .build();
 
         let drawing_area = DrawingArea::builder()
             .height_request(200)
             .hexpand(true)
+            .focusable(true)
             .build();
+        drawing_area.update_property(&[
+            gtk4::accessible::Property::Label("Fan curve graph"),
+            gtk4::accessible::Property::Description(&describe_points(&points.borrow(), None)),
+        ]);
 
         let graph_frame = adw::Bin::builder()
             .css_classes(["card"])
@@ -226,6 +237,38 @@ impl EditCurveDialog {
             .build();
 
         graph_group.add(&graph_frame);
+
+        // Points a calibrated fan on this curve cannot follow
+        let calibrated_fans = Rc::new(calibrated_fans_for_curve(&data.id));
+        let zone_warning = gtk4::Label::builder()
+            .wrap(true)
+            .xalign(0.0)
+            .margin_top(6)
+            .css_classes(["warning", "caption"])
+            .visible(false)
+            .build();
+        update_zone_warning(&zone_warning, &calibrated_fans, &points.borrow());
+        graph_group.add(&zone_warning);
+
+        // 3-pin fans on this curve: how their output range differs from the graph
+        let dc_fans = dc_fans_for_curve(&data.id);
+        if !dc_fans.is_empty() {
+            graph_group.add(&gtk4::Label::builder()
+                .label(&format!(
+                    "{} {} 3-pin (DC) {}: fan speed above 0% is scaled to {:.0}-100% voltage, below which 3-pin fans \
+                     usually stall. Calibrate {} to measure the real range.",
+                    dc_fans.join(", "),
+                    if dc_fans.len() == 1 { "is a" } else { "are" },
+                    if dc_fans.len() == 1 { "fan" } else { "fans" },
+                    hf_core::constants::pwm::DC_FLOOR_PERCENT,
+                    if dc_fans.len() == 1 { "it" } else { "them" },
+                ))
+                .wrap(true)
+                .xalign(0.0)
+                .margin_top(6)
+                .css_classes(["dim-label", "caption"])
+                .build());
+        }
         content.append(&graph_group);
 
         // Curve parameters section
@@ -234,6 +277,18 @@ impl EditCurveDialog {
             .description("Fine-tune how the curve responds to temperature changes")
             .build();
 
+        // Interpolation row
+        let interpolation_row = adw::ComboRow::builder()
+            .title("Interpolation")
+            .subtitle("How fan speed moves between curve points")
+            .build();
+        interpolation_row.set_model(Some(&gtk4::StringList::new(&["Linear", "Smooth (monotone cubic)"])));
+        interpolation_row.set_selected(match data.interpolation {
+            hf_core::CurveInterpolation::Linear => 0,
+            hf_core::CurveInterpolation::Monotone => 1,
+        });
+        params_group.add(&interpolation_row);
+
         // Hysteresis row
         let hysteresis_adj = gtk4::Adjustment::new(
             data.hysteresis as f64,
@@ -252,6 +307,7 @@ impl EditCurveDialog {
             .title("Hysteresis")
             .subtitle("Temperature must change by this amount before adjusting fan speed (°C)")
             .build();
+        hysteresis_spin.update_property(&[gtk4::accessible::Property::Label("Hysteresis")]);
         hysteresis_row.add_suffix(&hysteresis_spin);
         params_group.add(&hysteresis_row);
 
@@ -273,6 +329,7 @@ impl EditCurveDialog {
             .title("Response Delay")
             .subtitle("Wait this long before responding to temperature changes (ms)")
             .build();
+        delay_spin.update_property(&[gtk4::accessible::Property::Label("Response delay")]);
         delay_row.add_suffix(&delay_spin);
         params_group.add(&delay_row);
 
@@ -294,6 +351,7 @@ impl EditCurveDialog {
             .title("Ramp Up Speed")
             .subtitle("How fast fan speeds up when temperature rises (%/sec, 0=instant)")
             .build();
+        ramp_up_spin.update_property(&[gtk4::accessible::Property::Label("Ramp up speed")]);
         ramp_up_row.add_suffix(&ramp_up_spin);
         params_group.add(&ramp_up_row);
 
@@ -315,12 +373,28 @@ impl EditCurveDialog {
             .title("Ramp Down Speed")
             .subtitle("How fast fan slows down when temperature drops (%/sec, 0=instant)")
             .build();
+        ramp_down_spin.update_property(&[gtk4::accessible::Property::Label("Ramp down speed")]);
         ramp_down_row.add_suffix(&ramp_down_spin);
         params_group.add(&ramp_down_row);
 
         content.append(&params_group);
 
-        // Connect spin buttons to update state
+        // Try the edited curve on the fans without saving it
+        content.append(&test_curve_group(&dialog, &data.id, points.clone(), interpolation.clone()));
+
+        // Connect controls to update state
+        let interpolation_for_row = interpolation.clone();
+        let is_dirty_for_interpolation = is_dirty.clone();
+        let drawing_for_interpolation = drawing_area.clone();
+        interpolation_row.connect_selected_notify(move |row| {
+            *interpolation_for_row.borrow_mut() = match row.selected() {
+                1 => hf_core::CurveInterpolation::Monotone,
+                _ => hf_core::CurveInterpolation::Linear,
+            };
+            *is_dirty_for_interpolation.borrow_mut() = true;
+            drawing_for_interpolation.queue_draw();
+        });
+
         let hysteresis_for_spin = hysteresis.clone();
         let is_dirty_for_hyst = is_dirty.clone();
         hysteresis_spin.connect_value_changed(move |spin| {
@@ -381,6 +455,7 @@ impl EditCurveDialog {
             points,
             original_points,
             original_name,
+            original_interpolation: data.interpolation,
             current_temp,
             curve_id: data.id.clone(),
             temp_source_path: data.temp_source_path.clone(),
@@ -391,6 +466,7 @@ impl EditCurveDialog {
             delay_ms,
             ramp_up_speed,
             ramp_down_speed,
+            interpolation,
         });
         
         // Add Ctrl+Z (undo) and Ctrl+Shift+Z (redo) keyboard shortcuts
@@ -399,6 +475,8 @@ impl EditCurveDialog {
         let drawing_for_undo = drawing_area.clone();
         let history_for_undo = history.clone();
         let history_idx_for_undo = history_index.clone();
+        let warning_for_undo = zone_warning.clone();
+        let fans_for_undo = calibrated_fans.clone();
         key_controller.connect_key_pressed(move |_, key, _, modifiers| {
             if modifiers.contains(gtk4::gdk::ModifierType::CONTROL_MASK) {
                 if matches!(key, gtk4::gdk::Key::z | gtk4::gdk::Key::Z) {
@@ -410,6 +488,7 @@ impl EditCurveDialog {
                             *idx += 1;
                             if let Some(next_points) = hist.get(*idx) {
                                 *points_for_undo.borrow_mut() = next_points.clone();
+                                update_zone_warning(&warning_for_undo, &fans_for_undo, next_points);
                                 drawing_for_undo.queue_draw();
                             }
                         }
@@ -421,6 +500,7 @@ impl EditCurveDialog {
                             let hist = history_for_undo.borrow();
                             if let Some(prev_points) = hist.get(*idx) {
                                 *points_for_undo.borrow_mut() = prev_points.clone();
+                                update_zone_warning(&warning_for_undo, &fans_for_undo, prev_points);
                                 drawing_for_undo.queue_draw();
                             }
                         }
@@ -436,11 +516,97 @@ impl EditCurveDialog {
         let dragging_index: Rc<RefCell<Option<usize>>> = Rc::new(RefCell::new(None));
         let drag_start_pos: Rc<RefCell<(f64, f64)>> = Rc::new(RefCell::new((0.0, 0.0)));
 
-        // Setup drawing with animated temp indicator
-        let points_for_draw = this.points.clone();
-        this.drawing_area.set_draw_func(move |_, cr, width, height| {
-            Self::draw_static_curve(cr, width, height, &points_for_draw.borrow());
+        // Point selected for keyboard editing, highlighted while the graph has focus
+        let selected_point: Rc<RefCell<Option<usize>>> = Rc::new(RefCell::new(None));
+        this.drawing_area.connect_has_focus_notify(|area| area.queue_draw());
+
+        // Record an edit of the points: history, dirty state, zone warning and
+        // the live update to the daemon. Shared by dragging and the keyboard.
+        let commit_edit: Rc<dyn Fn()> = {
+            let points = this.points.clone();
+            let selected = selected_point.clone();
+            let drawing_area = this.drawing_area.clone();
+            let curve_id = this.curve_id.clone();
+            let name_entry = this.name_entry.clone();
+            let temp_source_path = this.temp_source_path.clone();
+            let interpolation = this.interpolation.clone();
+            let history = history.clone();
+            let history_index = history_index.clone();
+            let is_dirty = is_dirty.clone();
+            let revert_btn = revert_btn.clone();
+            let zone_warning = zone_warning.clone();
+            let calibrated_fans = calibrated_fans.clone();
+            Rc::new(move || {
+                // Mark as dirty and enable revert
+                *is_dirty.borrow_mut() = true;
+                revert_btn.set_sensitive(true);
+
+                // Save to history after the edit
+                let points = points.borrow();
+                save_to_history(&history, &history_index, &points);
+                update_zone_warning(&zone_warning, &calibrated_fans, &points);
+                drawing_area.update_property(&[gtk4::accessible::Property::Description(
+                    &describe_points(&points, *selected.borrow()),
+                )]);
+
+                // LIVE UPDATE: Save curve and signal daemon immediately after the edit
+                // This ensures the user sees their changes applied in real-time
+                let persisted = hf_core::PersistedCurve {
+                    id: curve_id.clone(),
+                    name: name_entry.text().to_string(),
+                    temp_source_path: temp_source_path.clone(),
+                    temp_source_label: String::new(), // Will be filled from existing data
+                    points: points.clone(),
+                    created_at: 0,
+                    updated_at: 0,
+                    hysteresis: hf_core::constants::curve::DEFAULT_HYSTERESIS_CELSIUS,
+                    delay_ms: hf_core::constants::curve::DEFAULT_DELAY_MS,
+                    ramp_up_speed: hf_core::constants::curve::DEFAULT_RAMP_UP_SPEED,
+                    ramp_down_speed: hf_core::constants::curve::DEFAULT_RAMP_DOWN_SPEED,
+                    interpolation: *interpolation.borrow(),
+                    battery_offset: None,
+                    fallback_sources: Vec::new(),
+                };
+
+                if let Err(e) = hf_core::save_curve(persisted) {
+                    tracing::debug!("Live curve update failed: {}", e);
+                } else {
+                    // Signal daemon to reload and apply the updated curve immediately
+                    if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Curves) {
+                        tracing::debug!("Failed to signal daemon for live update: {}", e);
+                    }
+                }
+            })
+        };
+
+        // Keyboard editing of the focused graph. Ctrl shortcuts go on to the
+        // dialog (undo/redo) and Tab still moves focus out of the graph.
+        let key_nav = gtk4::EventControllerKey::new();
+        let points_for_keys = this.points.clone();
+        let selected_for_keys = selected_point.clone();
+        let drawing_area_for_keys = this.drawing_area.clone();
+        let commit_for_keys = commit_edit.clone();
+        key_nav.connect_key_pressed(move |_, key, _, modifiers| {
+            if modifiers.intersects(gtk4::gdk::ModifierType::CONTROL_MASK | gtk4::gdk::ModifierType::ALT_MASK) {
+                return gtk4::glib::Propagation::Proceed;
+            }
+            let shift = modifiers.contains(gtk4::gdk::ModifierType::SHIFT_MASK);
+            let selected = *selected_for_keys.borrow();
+            let Some((selected, edited)) = apply_graph_key(&mut points_for_keys.borrow_mut(), selected, key, shift) else {
+                return gtk4::glib::Propagation::Proceed;
+            };
+            *selected_for_keys.borrow_mut() = selected;
+            if edited {
+                commit_for_keys();
+            } else {
+                drawing_area_for_keys.update_property(&[gtk4::accessible::Property::Description(
+                    &describe_points(&points_for_keys.borrow(), selected),
+                )]);
+            }
+            drawing_area_for_keys.queue_draw();
+            gtk4::glib::Propagation::Stop
         });
+        this.drawing_area.add_controller(key_nav);
 
         // Right-click gesture for removing points
         let right_click = GestureClick::new();
@@ -448,9 +614,12 @@ impl EditCurveDialog {
 
         let points_for_right = this.points.clone();
         let drawing_area_for_right = this.drawing_area.clone();
+        let selected_for_right = selected_point.clone();
 
         let history_for_right = history.clone();
         let history_idx_for_right = history_index.clone();
+        let warning_for_right = zone_warning.clone();
+        let fans_for_right = calibrated_fans.clone();
         right_click.connect_pressed(move |_, _n_press, x, y| {
             let width = drawing_area_for_right.width() as f64;
             let height = drawing_area_for_right.height() as f64;
@@ -479,6 +648,11 @@ impl EditCurveDialog {
                     *hist_idx = hist.len() - 1;
                 }
             }
+            update_zone_warning(&warning_for_right, &fans_for_right, &points);
+            *selected_for_right.borrow_mut() = None;
+            drawing_area_for_right.update_property(&[gtk4::accessible::Property::Description(
+                &describe_points(&points, None),
+            )]);
             drop(points);
             drawing_area_for_right.queue_draw();
         });
@@ -493,6 +667,7 @@ impl EditCurveDialog {
         let drawing_area_for_drag = this.drawing_area.clone();
         let dragging_for_start = dragging_index.clone();
         let drag_start_for_start = drag_start_pos.clone();
+        let selected_for_drag = selected_point.clone();
 
         drag.connect_drag_begin(move |_, x, y| {
             let width = drawing_area_for_drag.width() as f64;
@@ -500,6 +675,8 @@ impl EditCurveDialog {
             let margin = 20.0;
 
             *drag_start_for_start.borrow_mut() = (x, y);
+            // Keyboard editing continues from the point the mouse picked
+            drawing_area_for_drag.grab_focus();
 
             let temp = 20.0 + ((x - margin) / (width - 2.0 * margin)) as f32 * 80.0;
             let percent = 100.0 - ((y - margin) / (height - 2.0 * margin)) as f32 * 100.0;
@@ -514,6 +691,7 @@ impl EditCurveDialog {
                 let dist = ((x - px).powi(2) + (y - py).powi(2)).sqrt();
                 if dist < hit_radius {
                     *dragging_for_start.borrow_mut() = Some(i);
+                    *selected_for_drag.borrow_mut() = Some(i);
                     return;
                 }
             }
@@ -525,6 +703,7 @@ impl EditCurveDialog {
             let pos = points.iter().position(|(t, _)| *t > temp).unwrap_or(points.len());
             points.insert(pos, (temp, percent));
             *dragging_for_start.borrow_mut() = Some(pos);
+            *selected_for_drag.borrow_mut() = Some(pos);
         });
 
         let points_for_update = this.points.clone();
@@ -569,69 +748,25 @@ impl EditCurveDialog {
             drawing_area_for_update.queue_draw();
         });
 
-        let points_for_end = this.points.clone();
         let dragging_for_end = dragging_index.clone();
-        let curve_id_for_end = this.curve_id.clone();
-        let name_entry_for_end = this.name_entry.clone();
-        let temp_source_path_for_end = this.temp_source_path.clone();
-        let history_for_end = history.clone();
-        let history_idx_for_end = history_index.clone();
-        let is_dirty_for_end = is_dirty.clone();
-        let revert_btn_for_end = revert_btn.clone();
+        let commit_for_end = commit_edit.clone();
 
         drag.connect_drag_end(move |_, _, _| {
             *dragging_for_end.borrow_mut() = None;
-            
-            // Mark as dirty and enable revert
-            *is_dirty_for_end.borrow_mut() = true;
-            revert_btn_for_end.set_sensitive(true);
-            
-            // Save to history after drag
-            let points = points_for_end.borrow();
-            let mut hist = history_for_end.borrow_mut();
-            let mut hist_idx = history_idx_for_end.borrow_mut();
-            hist.truncate(*hist_idx + 1);
-            hist.push(points.clone());
-            *hist_idx = hist.len() - 1;
-            drop(points);
-            drop(hist);
-            drop(hist_idx);
-            
-            // LIVE UPDATE: Save curve and signal daemon immediately after drag ends
-            // This ensures the user sees their changes applied in real-time
-            let points = points_for_end.borrow();
-            let persisted = hf_core::PersistedCurve {
-                id: curve_id_for_end.clone(),
-                name: name_entry_for_end.text().to_string(),
-                temp_source_path: temp_source_path_for_end.clone(),
-                temp_source_label: String::new(), // Will be filled from existing data
-                points: points.clone(),
-                created_at: 0,
-                updated_at: 0,
-                hysteresis: hf_core::constants::curve::DEFAULT_HYSTERESIS_CELSIUS,
-                delay_ms: hf_core::constants::curve::DEFAULT_DELAY_MS,
-                ramp_up_speed: hf_core::constants::curve::DEFAULT_RAMP_UP_SPEED,
-                ramp_down_speed: hf_core::constants::curve::DEFAULT_RAMP_DOWN_SPEED,
-            };
-            
-            if let Err(e) = hf_core::save_curve(persisted) {
-                tracing::debug!("Live curve update failed: {}", e);
-            } else {
-                // Signal daemon to reload and apply the updated curve immediately
-                if let Err(e) = hf_core::daemon_reload_config() {
-                    tracing::debug!("Failed to signal daemon for live update: {}", e);
-                }
-            }
+            commit_for_end();
         });
 
         this.drawing_area.add_controller(drag);
 
         // Static drawing - no live temperature updates needed
         let points_for_draw = this.points.clone();
+        let interpolation_for_draw = this.interpolation.clone();
+        let selected_for_draw = selected_point.clone();
         
-        this.drawing_area.set_draw_func(move |_, cr, width, height| {
+        this.drawing_area.set_draw_func(move |area, cr, width, height| {
             let points = points_for_draw.borrow();
-            Self::draw_static_curve(cr, width, height, &points);
+            let selected = if area.has_focus() { *selected_for_draw.borrow() } else { None };
+            Self::draw_static_curve(cr, width, height, &points, *interpolation_for_draw.borrow(), selected);
         });
 
         // Cancel button - check for unsaved changes
@@ -675,6 +810,7 @@ impl EditCurveDialog {
             let delay_ms = *this_for_save.delay_ms.borrow();
             let ramp_up_speed = *this_for_save.ramp_up_speed.borrow();
             let ramp_down_speed = *this_for_save.ramp_down_speed.borrow();
+            let interpolation = *this_for_save.interpolation.borrow();
 
             let updated_data = CurveCardData {
                 id: this_for_save.curve_id.clone(),
@@ -687,6 +823,7 @@ impl EditCurveDialog {
                 delay_ms,
                 ramp_up_speed,
                 ramp_down_speed,
+                interpolation,
             };
 
             // Save to persistence
@@ -702,13 +839,16 @@ impl EditCurveDialog {
                 delay_ms: updated_data.delay_ms,
                 ramp_up_speed: updated_data.ramp_up_speed,
                 ramp_down_speed: updated_data.ramp_down_speed,
+                interpolation: updated_data.interpolation,
+                battery_offset: None,
+                fallback_sources: Vec::new(),
             };
 
             if let Err(e) = hf_core::save_curve(persisted) {
                 tracing::warn!("Failed to save curve: {}", e);
             } else {
                 // Signal daemon to reload config
-                if let Err(e) = hf_core::daemon_reload_config() {
+                if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Curves) {
                     tracing::debug!("Failed to signal daemon reload: {}", e);
                 }
             }
@@ -728,8 +868,8 @@ impl EditCurveDialog {
         let current_name = self.name_entry.text().to_string();
         let current_points = self.points.borrow();
         
-        // Check if name changed
-        if current_name != self.original_name {
+        // Check if name or interpolation changed
+        if current_name != self.original_name || *self.interpolation.borrow() != self.original_interpolation {
             return true;
         }
         
@@ -777,7 +917,14 @@ impl EditCurveDialog {
         100.0
     }
 
-    fn draw_static_curve(cr: &cairo::Context, width: i32, height: i32, points: &[(f32, f32)]) {
+    fn draw_static_curve(
+        cr: &cairo::Context,
+        width: i32,
+        height: i32,
+        points: &[(f32, f32)],
+        interpolation: hf_core::CurveInterpolation,
+        selected: Option<usize>,
+    ) {
         let w = width as f64;
         let h = height as f64;
         let margin = 20.0;
@@ -840,6 +987,13 @@ impl EditCurveDialog {
         let temp_to_x = |t: f32| margin + ((t - 20.0) / 80.0) as f64 * (w - 2.0 * margin);
         let pct_to_y = |p: f32| h - margin - (p / 100.0) as f64 * (h - 2.0 * margin);
 
+        // Stepped drawing ignores interpolation, as the daemon does
+        let path = if graph_style == "stepped" {
+            points.to_vec()
+        } else {
+            super::curve_card::line_points(points, interpolation)
+        };
+
         // Draw curve fill (only for "filled" style) - use accent color
         let accent = super::curve_card::theme_colors::curve_line();
         let fill = super::curve_card::theme_colors::curve_fill();
@@ -848,7 +1002,7 @@ impl EditCurveDialog {
             cr.move_to(temp_to_x(20.0), pct_to_y(0.0));
             cr.line_to(temp_to_x(20.0), pct_to_y(points[0].1));
 
-            for (t, p) in points {
+            for (t, p) in &path {
                 cr.line_to(temp_to_x(*t), pct_to_y(*p));
             }
 
@@ -861,12 +1015,13 @@ impl EditCurveDialog {
         }
 
         // Draw curve line - use accent color
+        let high_contrast = super::curve_card::theme_colors::is_high_contrast();
         cr.set_source_rgba(accent.0, accent.1, accent.2, 1.0);
-        cr.set_line_width(2.5);
+        cr.set_line_width(if high_contrast { 4.0 } else { 2.5 });
 
         cr.move_to(temp_to_x(20.0), pct_to_y(points[0].1));
         let mut prev_p = points[0].1;
-        for (t, p) in points {
+        for (t, p) in &path {
             match graph_style.as_str() {
                 "stepped" => {
                     cr.line_to(temp_to_x(*t), pct_to_y(prev_p));
@@ -884,13 +1039,21 @@ impl EditCurveDialog {
         let _ = cr.stroke();
 
         // Draw points
-        for (t, p) in points {
+        for (i, (t, p)) in points.iter().enumerate() {
             let x = temp_to_x(*t);
             let y = pct_to_y(*p);
 
-            cr.set_source_rgba(accent.0, accent.1, accent.2, 0.3);
-            cr.arc(x, y, 10.0, 0.0, 2.0 * std::f64::consts::PI);
-            let _ = cr.fill();
+            // Glow, replaced by a solid ring on the point selected for keyboard editing
+            if selected == Some(i) {
+                cr.set_source_rgba(accent.0, accent.1, accent.2, 1.0);
+                cr.set_line_width(3.0);
+                cr.arc(x, y, 11.0, 0.0, 2.0 * std::f64::consts::PI);
+                let _ = cr.stroke();
+            } else if !high_contrast {
+                cr.set_source_rgba(accent.0, accent.1, accent.2, 0.3);
+                cr.arc(x, y, 10.0, 0.0, 2.0 * std::f64::consts::PI);
+                let _ = cr.fill();
+            }
 
             // Point fill - theme-aware
             if is_dark {
@@ -928,3 +1091,245 @@ impl EditCurveDialog {
         self.dialog.set_transient_for(Some(parent));
     }
 }
+
+/// Calibrated fan driven by the edited curve
+struct CalibratedFan {
+    name: String,
+    calibration: hf_core::DaemonFanCalibration,
+    zero_rpm: bool,
+}
+
+/// Durations offered for testing a curve (label, milliseconds)
+const TEST_DURATIONS: [(&str, u32); 4] = [("30 seconds", 30_000), ("1 minute", 60_000), ("2 minutes", 120_000), ("5 minutes", 300_000)];
+
+/// "Test Now" section: the daemon follows the edited points for a while, then
+/// goes back to the saved curve by itself
+fn test_curve_group(
+    dialog: &adw::Window,
+    curve_id: &str,
+    points: Rc<RefCell<Vec<(f32, f32)>>>,
+    interpolation: Rc<RefCell<hf_core::CurveInterpolation>>,
+) -> adw::PreferencesGroup {
+    let group = adw::PreferencesGroup::builder()
+        .title("Try It")
+        .description("Hear and see the edited curve before saving it")
+        .build();
+
+    let labels: Vec<&str> = TEST_DURATIONS.iter().map(|(label, _)| *label).collect();
+    let duration_row = adw::ComboRow::builder()
+        .title("Test This Curve")
+        .subtitle("Fans using this curve follow the edit, then return to the saved curve")
+        .model(&gtk4::StringList::new(&labels))
+        .selected(1)
+        .build();
+    let test_btn = gtk4::Button::builder()
+        .label("Test Now")
+        .valign(gtk4::Align::Center)
+        .build();
+    duration_row.add_suffix(&test_btn);
+    group.add(&duration_row);
+
+    // Revert timer of the running test
+    let running: Rc<RefCell<Option<gtk4::glib::SourceId>>> = Rc::new(RefCell::new(None));
+
+    let curve_id_for_test = curve_id.to_string();
+    let running_for_test = running.clone();
+    test_btn.connect_clicked(move |btn| {
+        if let Some(timer) = running_for_test.borrow_mut().take() {
+            timer.remove();
+            if let Err(e) = hf_core::daemon_clear_curve_preview(&curve_id_for_test) {
+                tracing::warn!("Failed to stop curve test: {}", e);
+            }
+            btn.set_label("Test Now");
+            return;
+        }
+
+        let duration_ms = TEST_DURATIONS[duration_row.selected() as usize % TEST_DURATIONS.len()].1;
+        let result = hf_core::daemon_preview_curve(
+            &curve_id_for_test,
+            &points.borrow(),
+            *interpolation.borrow(),
+            duration_ms,
+        );
+        match result {
+            Ok(()) => {
+                btn.set_label("Stop Test");
+                let btn_for_timer = btn.clone();
+                let running_for_timer = running_for_test.clone();
+                let timer = gtk4::glib::timeout_add_local_once(
+                    std::time::Duration::from_millis(duration_ms as u64),
+                    move || {
+                        running_for_timer.borrow_mut().take();
+                        btn_for_timer.set_label("Test Now");
+                    },
+                );
+                *running_for_test.borrow_mut() = Some(timer);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to test curve: {}", e);
+                duration_row.set_subtitle(&format!("Could not test the curve: {}", e));
+            }
+        }
+    });
+
+    // Closing the editor ends a running test
+    let curve_id_for_close = curve_id.to_string();
+    dialog.connect_close_request(move |_| {
+        if let Some(timer) = running.borrow_mut().take() {
+            timer.remove();
+            if let Err(e) = hf_core::daemon_clear_curve_preview(&curve_id_for_close) {
+                tracing::debug!("Failed to stop curve test: {}", e);
+            }
+        }
+        gtk4::glib::Propagation::Proceed
+    });
+
+    group
+}
+
+/// Calibrated fans of the pairs currently using `curve_id`
+fn calibrated_fans_for_curve(curve_id: &str) -> Vec<CalibratedFan> {
+    let Ok(settings) = hf_core::load_settings() else {
+        return Vec::new();
+    };
+    let mut fans = Vec::new();
+    for pair in settings.active_pairs.iter().filter(|p| p.curve_for_profile(&settings.active_profile) == curve_id) {
+        let pwm_paths = if pair.fan_paths.is_empty() { std::slice::from_ref(&pair.fan_path) } else { &pair.fan_paths[..] };
+        for pwm_path in pwm_paths.iter().filter(|p| !p.is_empty()) {
+            let Some(calibration) = hf_core::daemon_get_fan_calibration(pwm_path).ok().and_then(|s| s.calibration) else {
+                continue;
+            };
+            let zero_rpm = settings.pwm_fan_pairings.iter().any(|p| &p.pwm_path == pwm_path && p.zero_rpm);
+            fans.push(CalibratedFan { name: pair.name.clone(), calibration, zero_rpm });
+        }
+    }
+    fans
+}
+
+/// Uncalibrated fans on DC (voltage) outputs of the pairs currently using `curve_id`
+fn dc_fans_for_curve(curve_id: &str) -> Vec<String> {
+    let Ok(settings) = hf_core::load_settings() else {
+        return Vec::new();
+    };
+    settings.active_pairs.iter()
+        .filter(|p| p.curve_for_profile(&settings.active_profile) == curve_id)
+        .filter(|pair| {
+            let pwm_paths = if pair.fan_paths.is_empty() { std::slice::from_ref(&pair.fan_path) } else { &pair.fan_paths[..] };
+            pwm_paths.iter().any(|pwm_path| {
+                hf_core::is_dc_mode(std::path::Path::new(pwm_path))
+                    && hf_core::daemon_get_fan_calibration(pwm_path).ok().and_then(|s| s.calibration).is_none()
+            })
+        })
+        .map(|pair| pair.name.clone())
+        .collect()
+}
+
+/// List curve points below a fan's stall duty or inside one of its dead zones
+/// Screen-reader description of the graph: the selected point, or how to select one
+fn describe_points(points: &[(f32, f32)], selected: Option<usize>) -> String {
+    match selected.and_then(|i| points.get(i).map(|point| (i, point))) {
+        Some((i, (temp, speed))) => format!(
+            "Point {} of {}: {:.0}°C, {:.0}% fan speed", i + 1, points.len(), temp, speed
+        ),
+        None => format!("{} points. Use the arrow keys to select and adjust them", points.len()),
+    }
+}
+
+/// Apply a key press on the focused graph to its points
+///
+/// Returns the new selection and whether the points changed, or `None` for keys
+/// the graph does not handle. Temperature moves stay between the neighbours,
+/// as dragging does; at least two points are kept.
+fn apply_graph_key(
+    points: &mut Vec<(f32, f32)>,
+    selected: Option<usize>,
+    key: gtk4::gdk::Key,
+    shift: bool,
+) -> Option<(Option<usize>, bool)> {
+    use gtk4::gdk::Key;
+
+    let last = points.len().checked_sub(1)?;
+    match key {
+        Key::Home => return Some((Some(0), false)),
+        Key::End => return Some((Some(last), false)),
+        _ => {}
+    }
+    let Some(i) = selected.filter(|i| *i <= last) else {
+        // Without a selection the arrow keys select the first point
+        let navigation = matches!(key, Key::Left | Key::Right | Key::Up | Key::Down | Key::Page_Up | Key::Page_Down);
+        return navigation.then_some((Some(0), false));
+    };
+
+    let before = points[i];
+    match key {
+        Key::Left | Key::Right if shift => {
+            let step = if key == Key::Left { -1.0 } else { 1.0 };
+            let min = if i > 0 { points[i - 1].0 + POINT_SEPARATION_EPSILON } else { graph::TEMP_MIN };
+            let max = if i < last { points[i + 1].0 - POINT_SEPARATION_EPSILON } else { graph::TEMP_MAX };
+            points[i].0 = (before.0 + step).clamp(min, max);
+        }
+        Key::Left => return Some((Some(i.saturating_sub(1)), false)),
+        Key::Right => return Some((Some((i + 1).min(last)), false)),
+        Key::Up | Key::Down | Key::Page_Up | Key::Page_Down => {
+            let step = match key {
+                Key::Up => 1.0,
+                Key::Down => -1.0,
+                Key::Page_Up => 10.0,
+                _ => -10.0,
+            };
+            points[i].1 = (before.1 + step).clamp(0.0, 100.0);
+        }
+        Key::Insert | Key::plus | Key::KP_Add => {
+            // Halfway to the next point, or to the previous one from the last
+            if last == 0 {
+                return Some((Some(i), false));
+            }
+            let at = if i < last { i + 1 } else { i };
+            let (low, high) = (points[at - 1], points[at]);
+            if high.0 - low.0 <= 2.0 * POINT_SEPARATION_EPSILON {
+                return Some((Some(i), false));
+            }
+            points.insert(at, ((low.0 + high.0) / 2.0, (low.1 + high.1) / 2.0));
+            return Some((Some(at), true));
+        }
+        Key::Delete | Key::BackSpace | Key::minus | Key::KP_Subtract => {
+            if points.len() <= 2 {
+                return Some((Some(i), false));
+            }
+            points.remove(i);
+            return Some((Some(i.min(last - 1)), true));
+        }
+        _ => return None,
+    }
+    Some((Some(i), points[i] != before))
+}
+
+fn update_zone_warning(label: &gtk4::Label, fans: &[CalibratedFan], points: &[(f32, f32)]) {
+    let mut warnings = Vec::new();
+    for fan in fans {
+        if hf_core::ignores_pwm(&fan.calibration) {
+            warnings.push(format!(
+                "{} runs at the same speed at every duty - if it's a 3-pin fan, switch its header to DC mode in the firmware setup",
+                fan.name
+            ));
+            continue;
+        }
+        for &(temp, percent) in points {
+            let point = format!("{:.0}°C / {:.0}%", temp, percent);
+            match hf_core::curve_point_issue(&fan.calibration, percent) {
+                Some(hf_core::CurvePointIssue::BelowStall { min_pwm, .. }) if fan.zero_rpm => {
+                    warnings.push(format!("{}: stops {} (stall duty PWM {})", point, fan.name, min_pwm));
+                }
+                Some(hf_core::CurvePointIssue::BelowStall { min_pwm, .. }) => {
+                    warnings.push(format!("{}: below the stall duty of {}, raised to PWM {}", point, fan.name, min_pwm));
+                }
+                Some(hf_core::CurvePointIssue::DeadZone { zone: (low, high), .. }) => {
+                    warnings.push(format!("{}: {} barely changes speed between PWM {} and {}", point, fan.name, low, high));
+                }
+                None => {}
+            }
+        }
+    }
+    label.set_label(&warnings.join("\n"));
+    label.set_visible(!warnings.is_empty());
+}
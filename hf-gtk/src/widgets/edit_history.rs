@@ -0,0 +1,187 @@
+//! Undo/redo history for the curves and fan pairing pages
+//!
+//! Edits on these pages are saved straight to disk, so each history entry
+//! keeps the saved curves or PWM-fan pairings from before and after one edit.
+//! Undo writes the earlier copy back and signals the daemon to reload. The
+//! history lives for the session and keeps the last [`MAX_ENTRIES`] edits
+//! per page.
+
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use tracing::{debug, warn};
+
+/// Edits kept per page
+const MAX_ENTRIES: usize = 50;
+
+/// Which saved data a history covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// Curve store (curves page)
+    Curves,
+    /// PWM-fan pairings (fan pairing page)
+    Pairings,
+}
+
+/// Saved state of one scope
+#[derive(Clone)]
+pub enum Snapshot {
+    Curves(hf_core::CurveStore),
+    Pairings(Vec<hf_core::PwmFanPairing>),
+}
+
+impl Snapshot {
+    /// Read the current saved state of `scope`
+    fn take(scope: Scope) -> Option<Self> {
+        let snapshot = match scope {
+            Scope::Curves => hf_core::load_curves().map(Self::Curves),
+            Scope::Pairings => hf_core::load_settings().map(|s| Self::Pairings(s.pwm_fan_pairings)),
+        };
+        snapshot.map_err(|e| warn!("Failed to snapshot {:?} for undo: {}", scope, e)).ok()
+    }
+
+    fn scope(&self) -> Scope {
+        match self {
+            Self::Curves(_) => Scope::Curves,
+            Self::Pairings(_) => Scope::Pairings,
+        }
+    }
+
+    /// Write this state back to disk and let the daemon pick it up
+    fn restore(&self) -> hf_core::Result<()> {
+        let section = match self {
+            Self::Curves(store) => {
+                hf_core::save_curves(store)?;
+                hf_core::DaemonConfigSection::Curves
+            }
+            Self::Pairings(pairings) => {
+                hf_core::update_setting(|s| s.pwm_fan_pairings = pairings.clone())?;
+                hf_core::DaemonConfigSection::Pairings
+            }
+        };
+        if let Err(e) = hf_core::daemon_reload_config_section(section) {
+            debug!("Failed to signal daemon reload: {}", e);
+        }
+        Ok(())
+    }
+}
+
+struct Entry {
+    label: String,
+    before: Snapshot,
+    after: Snapshot,
+}
+
+#[derive(Default)]
+struct History {
+    undo: VecDeque<Entry>,
+    redo: Vec<Entry>,
+}
+
+thread_local! {
+    static HISTORIES: RefCell<HashMap<Scope, History>> = RefCell::new(HashMap::new());
+}
+
+/// Snapshot `scope` before an edit; pass the result to [`commit`] once it's saved
+pub fn begin(scope: Scope) -> Option<Snapshot> {
+    Snapshot::take(scope)
+}
+
+/// Record a saved edit as undoable
+///
+/// `before` comes from [`begin`]; the current saved state becomes the redo
+/// target. Recording a new edit drops anything that could be redone.
+pub fn commit(before: Option<Snapshot>, label: impl Into<String>) {
+    let Some(before) = before else { return };
+    let scope = before.scope();
+    let Some(after) = Snapshot::take(scope) else { return };
+    let label = label.into();
+    debug!("Recorded {:?} edit: {}", scope, label);
+
+    HISTORIES.with(|histories| {
+        let mut histories = histories.borrow_mut();
+        let history = histories.entry(scope).or_default();
+        history.redo.clear();
+        history.undo.push_back(Entry { label, before, after });
+        if history.undo.len() > MAX_ENTRIES {
+            history.undo.pop_front();
+        }
+    });
+}
+
+/// Revert the latest edit in `scope`, returning its label
+pub fn undo(scope: Scope) -> Option<String> {
+    step(scope, true)
+}
+
+/// Re-apply the latest undone edit in `scope`, returning its label
+pub fn redo(scope: Scope) -> Option<String> {
+    step(scope, false)
+}
+
+fn step(scope: Scope, undo: bool) -> Option<String> {
+    HISTORIES.with(|histories| {
+        let mut histories = histories.borrow_mut();
+        let history = histories.get_mut(&scope)?;
+        let entry = if undo { history.undo.pop_back()? } else { history.redo.pop()? };
+        let target = if undo { &entry.before } else { &entry.after };
+
+        if let Err(e) = target.restore() {
+            warn!("Failed to {} \"{}\": {}", if undo { "undo" } else { "redo" }, entry.label, e);
+            if undo {
+                history.undo.push_back(entry);
+            } else {
+                history.redo.push(entry);
+            }
+            return None;
+        }
+
+        let label = entry.label.clone();
+        if undo {
+            history.redo.push(entry);
+        } else {
+            history.undo.push_back(entry);
+        }
+        Some(label)
+    })
+}
+
+/// Bind Ctrl+Z / Ctrl+Shift+Z on `widget` to undo and redo in `scope`
+///
+/// `on_change` runs after the saved state changed so the page can reload it.
+pub fn connect_shortcuts(widget: &impl IsA<gtk4::Widget>, scope: Scope, on_change: impl Fn() + 'static) {
+    let key_controller = gtk4::EventControllerKey::new();
+    let widget_for_keys = widget.clone().upcast::<gtk4::Widget>();
+    key_controller.connect_key_pressed(move |_, key, _, modifiers| {
+        if !modifiers.contains(gtk4::gdk::ModifierType::CONTROL_MASK)
+            || !matches!(key, gtk4::gdk::Key::z | gtk4::gdk::Key::Z)
+        {
+            return gtk4::glib::Propagation::Proceed;
+        }
+
+        let (verb, label) = if modifiers.contains(gtk4::gdk::ModifierType::SHIFT_MASK) {
+            ("Redone", redo(scope))
+        } else {
+            ("Undone", undo(scope))
+        };
+        if let Some(label) = label {
+            on_change();
+            show_toast(&widget_for_keys, &format!("{}: {}", verb, label));
+        }
+        gtk4::glib::Propagation::Stop
+    });
+    widget.add_controller(key_controller);
+}
+
+fn show_toast(widget: &gtk4::Widget, title: &str) {
+    let overlay = widget
+        .root()
+        .and_then(|root| root.downcast::<adw::ApplicationWindow>().ok())
+        .and_then(|window| window.content())
+        .and_then(|content| content.downcast::<adw::ToastOverlay>().ok());
+    if let Some(overlay) = overlay {
+        overlay.add_toast(adw::Toast::builder().title(title).timeout(2).build());
+    }
+}
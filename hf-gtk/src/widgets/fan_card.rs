@@ -91,6 +91,7 @@ impl FanCard {
             .draw_value(true)
             .value_pos(gtk4::PositionType::Right)
             .build();
+        scale.update_property(&[gtk4::accessible::Property::Label(&format!("{} speed", title))]);
 
         // Store PWM path for the callback
         let pwm_path = pwm.pwm_path.clone();
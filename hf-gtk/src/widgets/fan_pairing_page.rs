@@ -19,6 +19,8 @@ use tracing::{debug, warn};
 
 use hf_core::daemon_client;
 
+use super::edit_history::{self, Scope};
+
 /// Data for a PWM control with optional fan pairing
 #[derive(Clone, Debug)]
 pub struct PwmPairingData {
@@ -56,6 +58,8 @@ pub struct FanPairingPage {
     pub container: GtkBox,
     pwm_list: ListBox,
     filter_entry: SearchEntry,
+    /// Shown while fingerprinted bindings no longer match the hardware
+    conflicts_banner: adw::Banner,
     state: Rc<RefCell<FanPairingState>>,
 }
 
@@ -65,6 +69,7 @@ impl Clone for FanPairingPage {
             container: self.container.clone(),
             pwm_list: self.pwm_list.clone(),
             filter_entry: self.filter_entry.clone(),
+            conflicts_banner: self.conflicts_banner.clone(),
             state: self.state.clone(),
         }
     }
@@ -74,6 +79,7 @@ impl Clone for FanPairingPage {
 struct FanPairingState {
     pwm_controls: Vec<PwmPairingData>,
     fan_sensors: Vec<FanSensorData>,
+    binding_conflicts: Vec<hf_core::DaemonBindingConflict>,
     filter_text: String,
 }
 
@@ -85,6 +91,12 @@ impl FanPairingPage {
 
         let state = Rc::new(RefCell::new(FanPairingState::default()));
 
+        let conflicts_banner = adw::Banner::builder()
+            .button_label("Review")
+            .revealed(false)
+            .build();
+        container.append(&conflicts_banner);
+
         // Header with title and description (GNOME HIG: clear page purpose)
         let header_box = GtkBox::builder()
             .orientation(Orientation::Vertical)
@@ -123,6 +135,13 @@ impl FanPairingPage {
             .valign(gtk4::Align::Center)
             .build();
         title_row.append(&refresh_btn);
+
+        let groups_btn = Button::builder()
+            .label("Groups")
+            .tooltip_text("Drive several PWM outputs from one curve")
+            .valign(gtk4::Align::Center)
+            .build();
+        title_row.append(&groups_btn);
         
         let auto_detect_btn = Button::builder()
             .label("Auto Detect")
@@ -166,6 +185,7 @@ impl FanPairingPage {
             container,
             pwm_list,
             filter_entry: filter_entry.clone(),
+            conflicts_banner: conflicts_banner.clone(),
             state: state.clone(),
         };
 
@@ -187,6 +207,32 @@ impl FanPairingPage {
             }
         });
         
+        // Review bindings that no longer match the hardware
+        let state_conflicts = state.clone();
+        let page_conflicts = page_refresh.clone();
+        conflicts_banner.connect_button_clicked(move |banner| {
+            let conflicts = state_conflicts.borrow().binding_conflicts.clone();
+            let page_accepted = page_conflicts.clone();
+            super::binding_conflict_dialog::show_binding_conflicts_dialog(banner, conflicts, move || {
+                if let Some(page) = page_accepted.borrow().as_ref() {
+                    page.refresh();
+                }
+            });
+        });
+
+        // Connect groups button
+        let state_groups = state.clone();
+        groups_btn.connect_clicked(move |btn| {
+            let pwms = state_groups.borrow().pwm_controls.iter()
+                .map(|pwm| {
+                    let name = pwm.friendly_name.clone()
+                        .unwrap_or_else(|| format!("{} PWM{}", pwm.controller_name, pwm.pwm_num));
+                    (pwm.pwm_path.clone(), name)
+                })
+                .collect();
+            super::pwm_group_dialog::show_pwm_groups_dialog(btn, pwms);
+        });
+
         // Connect auto-detect button
         let state_autodetect = state.clone();
         let pwm_list_autodetect = page.pwm_list.clone();
@@ -197,6 +243,14 @@ impl FanPairingPage {
             Self::show_autodetect_dialog(window.as_ref(), &state_autodetect, &pwm_list_autodetect);
         });
 
+        // Ctrl+Z / Ctrl+Shift+Z undo and redo saved pairing changes
+        let page_undo = page_refresh.clone();
+        super::edit_history::connect_shortcuts(&page.container, Scope::Pairings, move || {
+            if let Some(page) = page_undo.borrow().as_ref() {
+                page.refresh();
+            }
+        });
+
         // Initial load
         page.refresh();
         
@@ -210,6 +264,23 @@ impl FanPairingPage {
     pub fn refresh(&self) {
         self.load_hardware_data();
         self.rebuild_list();
+        self.load_binding_conflicts();
+    }
+
+    fn load_binding_conflicts(&self) {
+        let conflicts = match daemon_client::daemon_get_binding_conflicts() {
+            Ok(conflicts) => conflicts,
+            Err(e) => {
+                debug!("Could not load binding conflicts: {}", e);
+                Vec::new()
+            }
+        };
+        self.conflicts_banner.set_title(&format!(
+            "Hardware changed: {} fan binding(s) no longer match",
+            conflicts.len()
+        ));
+        self.conflicts_banner.set_revealed(!conflicts.is_empty());
+        self.state.borrow_mut().binding_conflicts = conflicts;
     }
 
     fn load_hardware_data(&self) {
@@ -506,6 +577,7 @@ impl FanPairingPage {
         let initial_pwm = pwm.manual_pwm.unwrap_or(77);
         slider.set_value(initial_pwm as f64);
         slider.set_increments(1.0, 25.0);
+        slider.update_property(&[gtk4::accessible::Property::Label("Test PWM duty cycle")]);
 
         // Add marks at exact percentages (255 * percentage)
         slider.add_mark(0.0, gtk4::PositionType::Bottom, Some("0%"));
@@ -661,6 +733,56 @@ impl FanPairingPage {
         drop(state_ref);
 
         main_box.append(&fan_group);
+
+        // Outputs that must never be driven (a PSU fan, a pump on its own
+        // controller) can be left out for good
+        let pwm_file = std::path::Path::new(&pwm.pwm_path);
+        if pwm.pwm_path.starts_with("/sys/")
+            && !hf_core::is_cooling_device_path(pwm_file)
+            && hf_core::controller_filter().mode == hf_core::ControllerFilterMode::Blacklist
+        {
+            let exclude_group = adw::PreferencesGroup::builder()
+                .title("Exclusion")
+                .build();
+            let exclude_row = adw::ActionRow::builder()
+                .title("Never Control This Output")
+                .subtitle("Hides it from detection and fan control; undo in Settings → Controller Filter")
+                .build();
+            let exclude_btn = Button::builder()
+                .label("Exclude")
+                .css_classes(["destructive-action"])
+                .valign(gtk4::Align::Center)
+                .build();
+            exclude_row.add_suffix(&exclude_btn);
+            exclude_group.add(&exclude_row);
+            main_box.append(&exclude_group);
+
+            let dialog_exclude = dialog.clone();
+            let pwm_path_exclude = pwm.pwm_path.clone();
+            let controller_exclude = pwm.controller_name.clone();
+            let state_exclude = state.clone();
+            exclude_btn.connect_clicked(move |_| {
+                // Hand the output back before it disappears from the daemon's view
+                if let Err(e) = daemon_client::daemon_clear_pwm_override(&pwm_path_exclude) {
+                    warn!("Failed to clear PWM override before excluding: {}", e);
+                }
+                let path = std::path::Path::new(&pwm_path_exclude);
+                let Some(entry) = hf_core::controller_filter_entry(path, &controller_exclude) else {
+                    warn!("Could not fingerprint the chip of {}", pwm_path_exclude);
+                    return;
+                };
+                if let Err(e) = hf_core::add_controller_filter_entry(entry) {
+                    warn!("Failed to exclude {}: {}", pwm_path_exclude, e);
+                    return;
+                }
+                if let Err(e) = daemon_client::daemon_reload_config_section(hf_core::DaemonConfigSection::Settings) {
+                    warn!("Failed to signal daemon reload: {}", e);
+                }
+                state_exclude.borrow_mut().pwm_controls.retain(|p| p.pwm_path != pwm_path_exclude);
+                dialog_exclude.close();
+            });
+        }
+
         content.append(&main_box);
 
         dialog.set_content(Some(&content));
@@ -905,6 +1027,7 @@ impl FanPairingPage {
             let (selected_fan_uuid, selected_fan_path, selected_fan_name) = selected_fan_save.borrow().clone();
             
             // Save the pairing with UUIDs
+            let before = edit_history::begin(Scope::Pairings);
             if let Err(e) = Self::save_pairing(
                 &pwm_uuid_save,
                 &pwm_path_save,
@@ -915,6 +1038,9 @@ impl FanPairingPage {
             ) {
                 warn!("Failed to save pairing: {}", e);
             } else {
+                let label = friendly_name.clone().or_else(|| selected_fan_name.clone()).unwrap_or_else(|| pwm_path_save.clone());
+                edit_history::commit(before, format!("Pair {}", label));
+
                 // Update in-memory state
                 {
                     let mut state_ref = state_save.borrow_mut();
@@ -1185,6 +1311,7 @@ impl FanPairingPage {
                     
                     // Save detected mappings
                     let detected_ref = detected_timer.borrow();
+                    let before = edit_history::begin(Scope::Pairings);
                     for (pwm_uuid, pwm_path, fan_uuid, fan_path, fan_name, _pwm_name) in detected_ref.iter() {
                         let _ = Self::save_pairing(
                             pwm_uuid,
@@ -1195,6 +1322,9 @@ impl FanPairingPage {
                             None,
                         );
                     }
+                    if !detected_ref.is_empty() {
+                        edit_history::commit(before, format!("Auto-detect {} pairings", detected_ref.len()));
+                    }
                     
                     // Reload state and rebuild list
                     if let Ok(settings) = hf_core::load_settings() {
@@ -1356,6 +1486,15 @@ impl FanPairingPage {
         let mut settings = hf_core::load_settings()
             .map_err(|e| format!("Failed to load settings: {}", e))?;
 
+        // Keep response tuning, control mode, zero-RPM and enforcement when re-pairing the same PWM
+        let existing = settings.pwm_fan_pairings.iter()
+            .find(|p| p.pwm_uuid.as_deref() == Some(pwm_uuid) || p.pwm_path == pwm_path);
+        let tuning = existing.and_then(|p| p.tuning);
+        let control_mode = existing.map(|p| p.control_mode).unwrap_or_default();
+        let zero_rpm = existing.is_some_and(|p| p.zero_rpm);
+        let enforcement = existing.map(|p| p.enforcement).unwrap_or_default();
+        let noise = existing.and_then(|p| p.noise.clone());
+
         // Remove existing pairing for this PWM (by UUID first, then path)
         settings.pwm_fan_pairings.retain(|p| {
             p.pwm_uuid.as_deref() != Some(pwm_uuid) && p.pwm_path != pwm_path
@@ -1379,6 +1518,11 @@ impl FanPairingPage {
             fan_path: fan_path.map(String::from),
             fan_name: fan_name.map(String::from),
             friendly_name: friendly_name.map(String::from),
+            tuning,
+            control_mode,
+            zero_rpm,
+            enforcement,
+            noise,
             // Hardware identification fields
             driver_name: pwm_hw.driver_name,
             device_path: pwm_hw.device_path,
@@ -1406,7 +1550,7 @@ impl FanPairingPage {
             .map_err(|e| format!("Failed to save settings: {}", e))?;
 
         // Signal daemon to reload config
-        if let Err(e) = hf_core::daemon_reload_config() {
+        if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Pairings) {
             debug!("Failed to signal daemon reload: {}", e);
         }
 
@@ -255,6 +255,7 @@ impl GpuCard {
                     .draw_value(true)
                     .value_pos(gtk4::PositionType::Right)
                     .build();
+                scale.update_property(&[gtk4::accessible::Property::Label(&format!("{} speed", fan.name))]);
 
                 // Store GPU info for the callback
                 let gpu_index = gpu.index;
@@ -30,6 +30,8 @@ pub struct GpuInfoCard {
     card: adw::Bin,
     gpu_index: u32,
     temp_label: Label,
+    /// Every temperature sensor (edge, junction, memory) on one line
+    sensors_label: Label,
     fan_label: Label,
     power_label: Label,
     vram_bar: ProgressBar,
@@ -65,9 +67,16 @@ impl GpuInfoCard {
             .build();
 
         let vendor_badge = Label::builder()
-            .label(&gpu.vendor.to_string())
+            .label(if gpu.read_only {
+                format!("{} · Read-only", gpu.vendor)
+            } else {
+                gpu.vendor.to_string()
+            })
             .css_classes(["caption", "dim-label"])
             .build();
+        if gpu.read_only {
+            vendor_badge.set_tooltip_text(Some("No vendor driver or tool available: monitoring only, fan control disabled"));
+        }
 
         header.append(&name_label);
         header.append(&vendor_badge);
@@ -161,6 +170,18 @@ impl GpuInfoCard {
 
         content.append(&stats_box);
 
+        // All temperature sensors (hidden for single-sensor GPUs)
+        let sensors_label = Label::builder()
+            .css_classes(["caption", "dim-label", "numeric"])
+            .halign(gtk4::Align::Start)
+            .wrap(true)
+            .build();
+        Self::set_sensors_text(
+            &sensors_label,
+            gpu.temperatures.iter().filter_map(|t| Some((t.name.clone(), t.current_temp?))).collect(),
+        );
+        content.append(&sensors_label);
+
         // VRAM usage bar
         let vram_group = adw::PreferencesGroup::builder().title("VRAM Usage").build();
 
@@ -211,6 +232,7 @@ impl GpuInfoCard {
             card,
             gpu_index: gpu.index,
             temp_label,
+            sensors_label,
             fan_label,
             power_label,
             vram_bar,
@@ -231,6 +253,10 @@ impl GpuInfoCard {
         if self.temp_label.text() != temp_value {
             self.temp_label.set_label(&temp_value);
         }
+        Self::set_sensors_text(
+            &self.sensors_label,
+            gpu.temperatures.iter().filter_map(|t| Some((t.name.clone(), t.current_temp?))).collect(),
+        );
 
         // Update fan speed (only if changed)
         let fan_value = gpu
@@ -285,6 +311,13 @@ impl GpuInfoCard {
         if self.temp_label.text() != temp_value {
             self.temp_label.set_label(&temp_value);
         }
+        // "GPU" duplicates the primary sensor; map is unordered so sort by name
+        let mut sensors: Vec<(String, f32)> = reading.temperatures.iter()
+            .filter(|(name, _)| name.as_str() != "GPU")
+            .map(|(name, temp)| (name.clone(), *temp))
+            .collect();
+        sensors.sort_by(|a, b| a.0.cmp(&b.0));
+        Self::set_sensors_text(&self.sensors_label, sensors);
 
         // Update fan speed
         let fan_value = reading.fan_percent
@@ -324,6 +357,19 @@ impl GpuInfoCard {
         }
     }
 
+    /// Show "Edge 45°C · Junction 60°C · Memory 70°C" (only if changed)
+    fn set_sensors_text(label: &Label, sensors: Vec<(String, f32)>) {
+        label.set_visible(sensors.len() > 1);
+        let text = sensors
+            .iter()
+            .map(|(name, temp)| format!("{} {}", name.trim_start_matches("GPU "), hf_core::display::format_temp(*temp)))
+            .collect::<Vec<_>>()
+            .join(" · ");
+        if label.text() != text {
+            label.set_label(&text);
+        }
+    }
+
     pub fn gpu_index(&self) -> u32 {
         self.gpu_index
     }
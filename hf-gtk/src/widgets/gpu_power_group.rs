@@ -0,0 +1,147 @@
+//! GPU Power Limit Group
+//!
+//! Dashboard rows for lowering (or restoring) graphics card power limits.
+//! Only shown with `advanced.gpu_power_limits_enabled`; every change goes
+//! through the daemon, which checks the same setting.
+
+use gtk4::prelude::*;
+use gtk4::{glib, Button};
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+use tracing::warn;
+
+use hf_core::{DaemonGpuInfo, DaemonGpuPowerLimitInfo};
+
+/// Wait for the spin button to settle before writing the limit
+const WRITE_DELAY: Duration = Duration::from_millis(800);
+
+/// Power limit rows for every GPU that reports one
+#[derive(Clone)]
+pub struct GpuPowerGroup {
+    pub container: adw::PreferencesGroup,
+    rows: Rc<RefCell<Vec<adw::SpinRow>>>,
+}
+
+impl GpuPowerGroup {
+    pub fn new() -> Self {
+        let container = adw::PreferencesGroup::builder()
+            .title("GPU Power Limits")
+            .description("Lower power limits reduce heat and fan noise at some cost in performance")
+            .visible(false)
+            .build();
+        let group = Self { container, rows: Rc::new(RefCell::new(Vec::new())) };
+        group.refresh();
+        group
+    }
+
+    /// Rebuild the rows from the daemon (hidden while the setting is off)
+    pub fn refresh(&self) {
+        let mut rows = self.rows.borrow_mut();
+        for row in rows.drain(..) {
+            self.container.remove(&row);
+        }
+
+        let enabled = hf_core::load_settings().is_ok_and(|s| s.advanced.gpu_power_limits_enabled);
+        if enabled && hf_core::is_daemon_available() {
+            let gpus = hf_core::daemon_list_gpus().unwrap_or_default();
+            for gpu in gpus.iter().filter(|g| !g.read_only) {
+                // GPUs without a readable limit (Intel, old drivers) get no row
+                let Ok(limit) = hf_core::daemon_get_gpu_power_limit(gpu.index) else {
+                    continue;
+                };
+                let row = build_row(gpu, &limit);
+                self.container.add(&row);
+                rows.push(row);
+            }
+        }
+        self.container.set_visible(!rows.is_empty());
+    }
+}
+
+impl Default for GpuPowerGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn subtitle(power_watts: Option<f32>, limit: &DaemonGpuPowerLimitInfo) -> String {
+    let draw = power_watts.map(|w| format!("Drawing {:.0} W", w));
+    let default = limit.default_watts.map(|w| format!("default {:.0} W", w));
+    match (draw, default) {
+        (Some(draw), Some(default)) => format!("{} · {}", draw, default),
+        (Some(text), None) | (None, Some(text)) => text,
+        (None, None) => String::new(),
+    }
+}
+
+fn build_row(gpu: &DaemonGpuInfo, limit: &DaemonGpuPowerLimitInfo) -> adw::SpinRow {
+    let min = limit.min_watts.unwrap_or(1.0).floor();
+    let max = limit.max_watts.unwrap_or(limit.watts).max(min).ceil();
+    let row = adw::SpinRow::with_range(min as f64, max as f64, 5.0);
+    row.set_title(&format!("{} (W)", gpu.name));
+    row.set_subtitle(&subtitle(gpu.power_watts, limit));
+    row.set_value(limit.watts.round() as f64);
+
+    let reset_btn = Button::builder()
+        .icon_name("edit-undo-symbolic")
+        .css_classes(["flat"])
+        .valign(gtk4::Align::Center)
+        .tooltip_text("Restore the default power limit")
+        .sensitive(limit.default_watts.is_some_and(|d| d.round() != limit.watts.round()))
+        .build();
+    row.add_suffix(&reset_btn);
+
+    let index = gpu.index;
+    let power_watts = gpu.power_watts;
+    // Set while the value is updated from the daemon, so it isn't written back
+    let updating = Rc::new(Cell::new(false));
+    let debounce: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+
+    let apply = {
+        let row = row.clone();
+        let reset_btn = reset_btn.clone();
+        let updating = updating.clone();
+        move |result: Result<DaemonGpuPowerLimitInfo, String>| match result {
+            Ok(limit) => {
+                updating.set(true);
+                row.set_value(limit.watts.round() as f64);
+                updating.set(false);
+                row.set_subtitle(&subtitle(power_watts, &limit));
+                reset_btn.set_sensitive(limit.default_watts.is_some_and(|d| d.round() != limit.watts.round()));
+            }
+            Err(e) => {
+                warn!("Failed to change power limit of GPU {}: {}", index, e);
+                row.set_subtitle(&format!("Failed: {}", e));
+            }
+        }
+    };
+
+    let apply_set = apply.clone();
+    let debounce_clear = debounce.clone();
+    row.connect_value_notify(move |row| {
+        if updating.get() {
+            return;
+        }
+        let mut timer = debounce.borrow_mut();
+        if let Some(id) = timer.take() {
+            id.remove();
+        }
+        let watts = row.value().round() as u32;
+        let apply = apply_set.clone();
+        let debounce_clear = debounce_clear.clone();
+        *timer = Some(glib::timeout_add_local_once(WRITE_DELAY, move || {
+            // Clear the SourceId since this one-shot timer has now completed
+            debounce_clear.borrow_mut().take();
+            apply(hf_core::daemon_set_gpu_power_limit(index, watts));
+        }));
+    });
+
+    reset_btn.connect_clicked(move |_| {
+        apply(hf_core::daemon_reset_gpu_power_limit(index));
+    });
+
+    row
+}
@@ -2,6 +2,8 @@
 //!
 //! Allows users to create custom temperature monitoring graphs.
 //! Each graph tracks a single temperature source with a rolling history.
+//! Above them, an overview card overlays every temperature, fan RPM and PWM
+//! duty series over a selectable window (see [`super::history_graph`]).
 
 #![allow(dead_code)]
 
@@ -18,6 +20,8 @@ use std::rc::Rc;
 use std::time::{Duration, Instant};
 use tracing::{debug, error, warn};
 
+use super::history_graph::HistoryGraph;
+
 // ============================================================================
 // Constants
 // ============================================================================
@@ -109,6 +113,8 @@ pub struct GraphData {
     anim_duration_ms: u64,
     /// Previous display temp for label interpolation
     prev_display_temp: f32,
+    /// Spike/median/EMA filter configured for the source (same as the daemon's)
+    filter: Option<hf_core::FilterState>,
 }
 
 impl GraphData {
@@ -127,6 +133,7 @@ impl GraphData {
             anim_start: Instant::now(),
             anim_duration_ms: 500, // Default, will be set by poll interval
             prev_display_temp: 0.0,
+            filter: None,
         }
     }
 
@@ -137,6 +144,20 @@ impl GraphData {
         self.prev_display_temp = self.display_temp;
         self.anim_start = Instant::now(); // Start animation from now
         self.anim_duration_ms = anim_duration_ms;
+
+        // Source filter first, so the graph shows what the curves see
+        // (re-checked every sample to follow changes made while the graph is open)
+        let configured = hf_core::get_cached_settings()
+            .sensor_filters
+            .into_iter()
+            .find(|f| f.path == self.temp_source_path);
+        if configured.as_ref() != self.filter.as_ref().map(|f| f.filter()) {
+            self.filter = configured.map(|f| hf_core::FilterState::new(&f));
+        }
+        let temp = match self.filter.as_mut() {
+            Some(filter) => filter.apply(temp),
+            None => temp,
+        };
         
         // Apply EMA smoothing to eliminate jitter
         let smoothed = if self.display_temp == 0.0 {
@@ -429,7 +450,7 @@ impl GraphCard {
                         .find(|t| t.path == path)
                         .map(|t| t.temp_celsius)
                 })
-                .or_else(|| hf_core::daemon_client::daemon_read_temperature(&path).ok())
+                .or_else(|| crate::fallback::read_temperature(&path).ok())
         };
 
         if let Some(temp) = temp_result {
@@ -514,7 +535,7 @@ impl GraphCard {
         let temp_name = parts[2];
 
         // Daemon authoritative: GPU fallback uses daemon list (limited fields).
-        let gpus = hf_core::daemon_list_gpus().map_err(|e| anyhow::anyhow!(e))?;
+        let gpus = crate::fallback::list_gpus().map_err(|e| anyhow::anyhow!(e))?;
         let gpu = gpus
             .iter()
             .find(|g| g.index == gpu_index)
@@ -585,8 +606,11 @@ impl GraphCard {
             })
             .collect();
 
-        // Fill (only for "filled" style) - use accent color
-        let accent = super::curve_card::theme_colors::curve_line();
+        // Fill (only for "filled" style) - sensor color if assigned, else accent color
+        let accent = match super::curve_card::theme_colors::sensor_color(&data.temp_source_path) {
+            Some((r, g, b)) => (r, g, b, 1.0),
+            None => super::curve_card::theme_colors::curve_line(),
+        };
         if graph_style == "filled" {
             cr.set_source_rgba(accent.0, accent.1, accent.2, 0.2);
             cr.move_to(margin, h - margin);
@@ -601,7 +625,7 @@ impl GraphCard {
             let _ = cr.fill();
         }
 
-        // Line - use accent color
+        // Line
         cr.set_source_rgba(accent.0, accent.1, accent.2, 1.0);
         cr.set_line_width(2.0);
 
@@ -866,12 +890,22 @@ impl GraphsPage {
         header_box.append(&add_button);
         container.append(&header_box);
 
-        // Scrollable cards
+        // Scrollable overview and cards
         let scroll = ScrolledWindow::builder()
             .vexpand(true)
             .hexpand(true)
             .build();
 
+        let page_box = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(12)
+            .build();
+
+        let overview = HistoryGraph::new();
+        overview.widget().set_margin_start(24);
+        overview.widget().set_margin_end(24);
+        page_box.append(overview.widget());
+
         let cards_box = GtkBox::builder()
             .orientation(Orientation::Vertical)
             .spacing(12)
@@ -881,8 +915,6 @@ impl GraphsPage {
             .margin_bottom(24)
             .build();
 
-        scroll.set_child(Some(&cards_box));
-
         // Stack for empty vs list state
         let stack = gtk4::Stack::builder()
             .transition_type(gtk4::StackTransitionType::Crossfade)
@@ -904,8 +936,10 @@ impl GraphsPage {
         empty_state.set_child(Some(&empty_add_btn));
 
         stack.add_named(&empty_state, Some("empty"));
-        stack.add_named(&scroll, Some("list"));
-        container.append(&stack);
+        stack.add_named(&cards_box, Some("list"));
+        page_box.append(&stack);
+        scroll.set_child(Some(&page_box));
+        container.append(&scroll);
 
         // Add Ctrl+N keyboard shortcut to add graph
         let key_controller = gtk4::EventControllerKey::new();
@@ -1103,13 +1137,14 @@ impl GraphsPage {
         let selected_source: Rc<RefCell<Option<(String, String)>>> = Rc::new(RefCell::new(None));
 
         // Add GPU temperature sources first via daemon (authoritative)
-        if let Ok(daemon_gpus) = hf_core::daemon_list_gpus() {
+        if let Ok(daemon_gpus) = crate::fallback::list_gpus() {
             for gpu in daemon_gpus {
-                // Use GPU temp from daemon response
-                if let Some(temp) = gpu.temp {
-                    let display = format!("{} - GPU", gpu.name);
-                    let path = format!("gpu:{}:GPU", gpu.index);
-                    let temp_str = format!("{:.1}°C", temp);
+                // Primary sensor ("GPU") first, then junction/memory/etc. by name
+                let sensors = gpu.temp.map(|t| ("GPU".to_string(), t)).into_iter()
+                    .chain(gpu.temperatures.iter().skip(1).map(|t| (t.name.clone(), t.value)));
+                for (sensor, _temp) in sensors {
+                    let display = format!("{} - {}", gpu.name, sensor);
+                    let path = format!("gpu:{}:{}", gpu.index, sensor);
 
                     let row = adw::ActionRow::builder()
                         .title(&display)
@@ -1132,7 +1167,7 @@ impl GraphsPage {
         }
 
         // Add hwmon temperature sources via daemon (authoritative)
-        if let Ok(hw) = hf_core::daemon_list_hardware() {
+        if let Ok(hw) = crate::fallback::list_hardware() {
             for chip in hw.chips {
                 // Skip amdgpu chips as they're in GPU section
                 if chip.name.contains("amdgpu") {
@@ -0,0 +1,758 @@
+//! Multi-series history graph
+//!
+//! Overview card at the top of the graphs page. Temperatures are plotted on
+//! the left axis; fan RPM and PWM duty share the right axis, which is
+//! labelled in both RPM and percent. Samples are recorded for the longest
+//! window (7 days) whether or not the page is visible, so changing the
+//! window never loses data; samples older than a day are merged into
+//! 5-minute averages.
+//!
+//! On startup, temperature and RPM series are backfilled from the daemon's
+//! on-disk history (`QueryHistory`), so the graph survives GUI restarts.
+//!
+//! Every series has a toggle in the legend, and the visible window can be
+//! exported as CSV or as a PNG image.
+
+use gtk4::prelude::*;
+use gtk4::{cairo, gdk, glib, Box as GtkBox, DrawingArea, DropDown, FlowBox, Label, Orientation, ToggleButton};
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::rc::Rc;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+use super::curve_card::theme_colors;
+use crate::runtime::SensorData;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// How often a sample of every series is recorded
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Selectable time windows (seconds, label)
+const WINDOWS: [(u32, &str); 6] = [
+    (300, "5 minutes"),
+    (900, "15 minutes"),
+    (3600, "1 hour"),
+    (6 * 3600, "6 hours"),
+    (24 * 3600, "24 hours"),
+    (7 * 24 * 3600, "7 days"),
+];
+
+/// Samples older than the longest window are dropped
+const RETENTION_MS: u64 = 7 * 24 * 3600 * 1000;
+
+/// Samples older than this are merged into `COMPACT_MS` averages
+const DENSE_RETENTION_MS: u64 = 24 * 3600 * 1000;
+const COMPACT_MS: u64 = 5 * 60 * 1000;
+
+/// Lines are broken where samples are missing for longer than this
+const GAP_MS: u64 = 3 * SAMPLE_INTERVAL.as_millis() as u64;
+
+/// Same, for compacted samples and daemon history
+const SPARSE_GAP_MS: u64 = 2 * 3600 * 1000;
+
+/// Plot area insets: axis labels on the left, right and bottom
+const LEFT_AXIS: f64 = 52.0;
+const RIGHT_AXIS: f64 = 84.0;
+const TOP_MARGIN: f64 = 10.0;
+const BOTTOM_AXIS: f64 = 22.0;
+
+/// Widget height and PNG export size
+const GRAPH_HEIGHT: i32 = 260;
+const EXPORT_WIDTH: i32 = 1600;
+const EXPORT_HEIGHT: i32 = 600;
+
+/// Series colors, assigned in order of appearance to series without a sensor color
+const PALETTE: [(f64, f64, f64); 10] = [
+    (0.21, 0.52, 0.89),
+    (0.88, 0.27, 0.22),
+    (0.18, 0.76, 0.49),
+    (0.96, 0.61, 0.07),
+    (0.57, 0.38, 0.79),
+    (0.20, 0.74, 0.80),
+    (0.89, 0.36, 0.65),
+    (0.55, 0.67, 0.18),
+    (0.60, 0.45, 0.30),
+    (0.45, 0.50, 0.60),
+];
+
+// ============================================================================
+// Data
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SeriesKind {
+    Temperature,
+    Rpm,
+    Duty,
+}
+
+impl SeriesKind {
+    /// Prefix of the series key stored in the hidden-series setting
+    fn key_prefix(self) -> &'static str {
+        match self {
+            Self::Temperature => "temp:",
+            Self::Rpm => "rpm:",
+            Self::Duty => "duty:",
+        }
+    }
+
+    fn unit(self) -> &'static str {
+        match self {
+            Self::Temperature => "°C",
+            Self::Rpm => "RPM",
+            Self::Duty => "%",
+        }
+    }
+}
+
+struct Series {
+    /// Kind prefix + source path
+    key: String,
+    label: String,
+    kind: SeriesKind,
+    palette_color: (f64, f64, f64),
+    visible: bool,
+    /// (Unix time in ms, value in the kind's unit)
+    samples: VecDeque<(u64, f32)>,
+    /// First sample recorded by the GUI (older ones came from the daemon)
+    live_since: u64,
+    /// Samples before this are already compacted
+    compacted_until: u64,
+}
+
+impl Series {
+    /// The source's assigned color, else the palette color picked for the series
+    fn color(&self) -> (f64, f64, f64) {
+        let path = &self.key[self.kind.key_prefix().len()..];
+        theme_colors::sensor_color(path).unwrap_or(self.palette_color)
+    }
+
+    /// Longest gap between two samples that is still drawn as a line
+    fn gap_limit(&self, t: u64, now_ms: u64) -> u64 {
+        if t < self.live_since || t < now_ms.saturating_sub(DENSE_RETENTION_MS) {
+            SPARSE_GAP_MS
+        } else {
+            GAP_MS
+        }
+    }
+
+    /// Merge samples older than `DENSE_RETENTION_MS` into `COMPACT_MS` averages
+    fn compact(&mut self, now_ms: u64) {
+        let cutoff = now_ms.saturating_sub(DENSE_RETENTION_MS);
+        let cutoff = cutoff - cutoff % COMPACT_MS;
+        let from = self.samples.partition_point(|(t, _)| *t < self.compacted_until);
+        let to = self.samples.partition_point(|(t, _)| *t < cutoff);
+        if to > from {
+            let mut merged: Vec<(u64, f64, u32)> = Vec::new();
+            for (t, value) in self.samples.drain(from..to) {
+                let bucket = t - t % COMPACT_MS;
+                match merged.last_mut() {
+                    Some((start, sum, count)) if *start == bucket => {
+                        *sum += value as f64;
+                        *count += 1;
+                    }
+                    _ => merged.push((bucket, value as f64, 1)),
+                }
+            }
+            for (i, (start, sum, count)) in merged.into_iter().enumerate() {
+                self.samples.insert(from + i, (start, (sum / count as f64) as f32));
+            }
+        }
+        self.compacted_until = self.compacted_until.max(cutoff);
+    }
+}
+
+/// Recorded samples of every series
+#[derive(Default)]
+struct History {
+    series: Vec<Series>,
+    colors_used: usize,
+    last_compaction_ms: u64,
+}
+
+impl History {
+    /// Record one sample of every reading; returns true if a series was added
+    /// or dropped, so the legend must be rebuilt
+    fn record(&mut self, data: &SensorData, now_ms: u64) -> bool {
+        let mut changed = false;
+        for temp in &data.temperatures {
+            changed |= self.push(SeriesKind::Temperature, &temp.path, &temp.label, temp.temp_celsius, now_ms);
+        }
+        for fan in &data.fans {
+            if let Some(rpm) = fan.rpm {
+                changed |= self.push(SeriesKind::Rpm, &fan.path, &format!("{} RPM", fan.label), rpm as f32, now_ms);
+            }
+            if let Some(percent) = fan.percent {
+                changed |= self.push(SeriesKind::Duty, &fan.path, &format!("{} duty", fan.label), percent, now_ms);
+            }
+        }
+        for gpu in &data.gpus {
+            let path = format!("gpu:{}", gpu.index);
+            if let Some(rpm) = gpu.fan_rpm {
+                changed |= self.push(SeriesKind::Rpm, &path, &format!("{} fan RPM", gpu.name), rpm as f32, now_ms);
+            }
+            if let Some(percent) = gpu.fan_percent {
+                changed |= self.push(SeriesKind::Duty, &path, &format!("{} fan duty", gpu.name), percent as f32, now_ms);
+            }
+        }
+
+        // Drop old samples, and series whose source disappeared a week ago
+        let cutoff = now_ms.saturating_sub(RETENTION_MS);
+        for series in &mut self.series {
+            while series.samples.front().is_some_and(|(t, _)| *t < cutoff) {
+                series.samples.pop_front();
+            }
+        }
+        if now_ms.saturating_sub(self.last_compaction_ms) >= 60_000 {
+            self.last_compaction_ms = now_ms;
+            for series in &mut self.series {
+                series.compact(now_ms);
+            }
+        }
+        let before = self.series.len();
+        self.series.retain(|s| !s.samples.is_empty());
+        changed || self.series.len() != before
+    }
+
+    /// Prepend daemon history older than each series' first sample
+    ///
+    /// Only series the GUI already shows are filled in. PWM duty is skipped:
+    /// the graph plots duty per fan, the daemon stores it per PWM output.
+    fn backfill(&mut self, data: &hf_core::DaemonHistoryData) {
+        for stored in &data.series {
+            let kind = match stored.kind {
+                hf_core::DaemonHistorySeriesKind::Temperature => SeriesKind::Temperature,
+                hf_core::DaemonHistorySeriesKind::FanRpm => SeriesKind::Rpm,
+                hf_core::DaemonHistorySeriesKind::PwmDuty => continue,
+            };
+            let key = format!("{}{}", kind.key_prefix(), stored.path);
+            let Some(series) = self.series.iter_mut().find(|s| s.key == key) else { continue };
+            let first = series.samples.front().map_or(u64::MAX, |(t, _)| *t);
+            for point in stored.points.iter().rev().filter(|p| p.timestamp_ms < first) {
+                series.samples.push_front((point.timestamp_ms, point.avg));
+            }
+        }
+    }
+
+    fn push(&mut self, kind: SeriesKind, path: &str, label: &str, value: f32, now_ms: u64) -> bool {
+        if !value.is_finite() {
+            return false;
+        }
+        let key = format!("{}{}", kind.key_prefix(), path);
+        if let Some(series) = self.series.iter_mut().find(|s| s.key == key) {
+            series.samples.push_back((now_ms, value));
+            return false;
+        }
+
+        let hidden = hf_core::get_cached_settings().display.graph_hidden_series.contains(&key);
+        let palette_color = PALETTE[self.colors_used % PALETTE.len()];
+        if theme_colors::sensor_color(path).is_none() {
+            self.colors_used += 1;
+        }
+        self.series.push(Series {
+            key,
+            label: label.to_string(),
+            kind,
+            palette_color,
+            visible: !hidden,
+            samples: VecDeque::from([(now_ms, value)]),
+            live_since: now_ms,
+            compacted_until: 0,
+        });
+        true
+    }
+
+    /// Visible series' samples from `start_ms` on, as CSV with one row per sample time
+    fn to_csv(&self, start_ms: u64) -> String {
+        let visible: Vec<&Series> = self.series.iter().filter(|s| s.visible).collect();
+        let mut rows: BTreeMap<u64, Vec<Option<f32>>> = BTreeMap::new();
+        for (column, series) in visible.iter().enumerate() {
+            for &(t, value) in series.samples.iter().filter(|(t, _)| *t >= start_ms) {
+                rows.entry(t).or_insert_with(|| vec![None; visible.len()])[column] = Some(value);
+            }
+        }
+
+        let mut csv = String::from("timestamp");
+        for series in &visible {
+            csv.push_str(&format!(",\"{} ({})\"", series.label.replace('"', "'"), series.kind.unit()));
+        }
+        csv.push('\n');
+        for (t, values) in rows {
+            let time = chrono::DateTime::from_timestamp_millis(t as i64)
+                .map(|t| t.with_timezone(&chrono::Local).to_rfc3339())
+                .unwrap_or_default();
+            csv.push_str(&time);
+            for value in values {
+                csv.push(',');
+                if let Some(value) = value {
+                    csv.push_str(&format!("{:.2}", value));
+                }
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Time axis label for a point `secs` before now
+fn format_ago(secs: u32, window_secs: u32) -> String {
+    if secs == 0 {
+        "now".to_string()
+    } else if window_secs >= 2 * 24 * 3600 {
+        format!("-{}d", secs as f64 / (24.0 * 3600.0))
+    } else if window_secs >= 2 * 3600 {
+        format!("-{}h", secs as f64 / 3600.0)
+    } else if secs.is_multiple_of(60) {
+        format!("-{}m", secs / 60)
+    } else {
+        format!("-{}m{:02}s", secs / 60, secs % 60)
+    }
+}
+
+// ============================================================================
+// Drawing
+// ============================================================================
+
+/// Draw the visible window of every visible series
+fn draw(cr: &cairo::Context, width: f64, height: f64, history: &History, window_secs: u32) {
+    let plot_w = width - LEFT_AXIS - RIGHT_AXIS;
+    let plot_h = height - TOP_MARGIN - BOTTOM_AXIS;
+    if plot_w < 10.0 || plot_h < 10.0 {
+        return;
+    }
+
+    let end_ms = now_ms();
+    let start_ms = end_ms.saturating_sub(window_secs as u64 * 1000);
+    let visible: Vec<&Series> = history.series.iter().filter(|s| s.visible).collect();
+
+    // Axis ranges from what is on screen
+    let range_of = |kinds: &[SeriesKind]| {
+        visible
+            .iter()
+            .filter(|s| kinds.contains(&s.kind))
+            .flat_map(|s| s.samples.iter().filter(|(t, _)| *t >= start_ms).map(|(_, v)| *v))
+            .fold(None, |acc: Option<(f32, f32)>, v| Some(acc.map_or((v, v), |(lo, hi)| (lo.min(v), hi.max(v)))))
+    };
+    let (temp_min, temp_max) = match range_of(&[SeriesKind::Temperature]) {
+        Some((lo, hi)) => (((lo - 5.0) / 5.0).floor() * 5.0, ((hi + 5.0) / 5.0).ceil() * 5.0),
+        None => (20.0, 100.0),
+    };
+    let temp_min = temp_min.max(0.0);
+    let rpm_max = range_of(&[SeriesKind::Rpm]).map_or(2000.0, |(_, hi)| ((hi / 500.0).ceil() * 500.0).max(500.0));
+    let has_rpm = visible.iter().any(|s| s.kind == SeriesKind::Rpm);
+    let has_duty = visible.iter().any(|s| s.kind == SeriesKind::Duty);
+
+    let x_of = |t: u64| LEFT_AXIS + t.saturating_sub(start_ms) as f64 / (window_secs as f64 * 1000.0) * plot_w;
+    let y_of = |fraction: f64| TOP_MARGIN + plot_h * (1.0 - fraction.clamp(0.0, 1.0));
+
+    // Grid and axis labels
+    let grid = theme_colors::grid_line();
+    let text = if theme_colors::is_dark_mode() { (0.8, 0.8, 0.8) } else { (0.25, 0.25, 0.25) };
+    cr.set_line_width(1.0);
+    cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Normal);
+    cr.set_font_size(11.0);
+    for i in 0..=4 {
+        let fraction = i as f64 / 4.0;
+        let y = y_of(fraction);
+        cr.set_source_rgba(grid.0, grid.1, grid.2, grid.3);
+        cr.move_to(LEFT_AXIS, y);
+        cr.line_to(LEFT_AXIS + plot_w, y);
+        let _ = cr.stroke();
+
+        cr.set_source_rgb(text.0, text.1, text.2);
+        let temp = temp_min + (temp_max - temp_min) * fraction as f32;
+        let temp_label = hf_core::display::format_temp(temp);
+        if let Ok(extents) = cr.text_extents(&temp_label) {
+            cr.move_to(LEFT_AXIS - extents.width() - 6.0, y + 4.0);
+            let _ = cr.show_text(&temp_label);
+        }
+
+        let mut right = Vec::new();
+        if has_rpm {
+            right.push(format!("{:.0}", rpm_max as f64 * fraction));
+        }
+        if has_duty {
+            right.push(format!("{:.0}%", 100.0 * fraction));
+        }
+        cr.move_to(LEFT_AXIS + plot_w + 6.0, y + 4.0);
+        let _ = cr.show_text(&right.join(" · "));
+    }
+    for i in 0..=4u32 {
+        let label = format_ago(window_secs - window_secs / 4 * i, window_secs);
+        let x = LEFT_AXIS + plot_w * i as f64 / 4.0;
+        if let Ok(extents) = cr.text_extents(&label) {
+            let x = (x - extents.width() / 2.0).clamp(0.0, width - extents.width());
+            cr.move_to(x, height - 6.0);
+            let _ = cr.show_text(&label);
+        }
+    }
+
+    // Series, averaged per pixel column so a 7 day window stays cheap to draw
+    cr.save().ok();
+    cr.rectangle(LEFT_AXIS, TOP_MARGIN, plot_w, plot_h);
+    cr.clip();
+    for series in &visible {
+        let fraction = |v: f32| -> f64 {
+            match series.kind {
+                SeriesKind::Temperature => ((v - temp_min) / (temp_max - temp_min)) as f64,
+                SeriesKind::Rpm => (v / rpm_max) as f64,
+                SeriesKind::Duty => (v / 100.0) as f64,
+            }
+        };
+        match series.kind {
+            SeriesKind::Temperature => {
+                cr.set_line_width(2.0);
+                cr.set_dash(&[], 0.0);
+            }
+            SeriesKind::Rpm => {
+                cr.set_line_width(1.5);
+                cr.set_dash(&[], 0.0);
+            }
+            SeriesKind::Duty => {
+                cr.set_line_width(1.5);
+                cr.set_dash(&[5.0, 3.0], 0.0);
+            }
+        }
+        let (r, g, b) = series.color();
+        cr.set_source_rgb(r, g, b);
+
+        let mut column: Option<(i64, f64, u32)> = None; // (pixel, sum, count)
+        let mut last_time: Option<u64> = None;
+        let mut pen_down = false;
+        let flush = |column: Option<(i64, f64, u32)>, pen_down: &mut bool| {
+            if let Some((px, sum, count)) = column {
+                let y = y_of(sum / count as f64);
+                if *pen_down {
+                    cr.line_to(px as f64, y);
+                } else {
+                    cr.move_to(px as f64, y);
+                    *pen_down = true;
+                }
+            }
+        };
+        for &(t, value) in series.samples.iter().filter(|(t, _)| *t >= start_ms) {
+            if last_time.is_some_and(|last| t.saturating_sub(last) > series.gap_limit(last, end_ms)) {
+                flush(column.take(), &mut pen_down);
+                pen_down = false;
+            }
+            last_time = Some(t);
+
+            let px = x_of(t).round() as i64;
+            match column.as_mut() {
+                Some((column_px, sum, count)) if *column_px == px => {
+                    *sum += fraction(value);
+                    *count += 1;
+                }
+                _ => {
+                    flush(column.take(), &mut pen_down);
+                    column = Some((px, fraction(value), 1));
+                }
+            }
+        }
+        flush(column, &mut pen_down);
+        let _ = cr.stroke();
+    }
+    cr.restore().ok();
+
+    if visible.is_empty() {
+        cr.set_source_rgb(text.0, text.1, text.2);
+        let message = if history.series.is_empty() { "Waiting for sensor data…" } else { "All series hidden" };
+        if let Ok(extents) = cr.text_extents(message) {
+            cr.move_to(LEFT_AXIS + (plot_w - extents.width()) / 2.0, TOP_MARGIN + plot_h / 2.0);
+            let _ = cr.show_text(message);
+        }
+    }
+}
+
+/// Render the graph off-screen and save it as PNG
+fn export_png(path: &std::path::Path, history: &History, window_secs: u32) -> Result<(), String> {
+    let mut surface = cairo::ImageSurface::create(cairo::Format::ARgb32, EXPORT_WIDTH, EXPORT_HEIGHT)
+        .map_err(|e| e.to_string())?;
+    {
+        let cr = cairo::Context::new(&surface).map_err(|e| e.to_string())?;
+        // Opaque background, matching the current theme
+        if theme_colors::is_dark_mode() {
+            cr.set_source_rgb(0.14, 0.14, 0.14);
+        } else {
+            cr.set_source_rgb(1.0, 1.0, 1.0);
+        }
+        let _ = cr.paint();
+        draw(&cr, EXPORT_WIDTH as f64, EXPORT_HEIGHT as f64, history, window_secs);
+    }
+    surface.flush();
+
+    let stride = surface.stride() as usize;
+    let data = surface.data().map_err(|e| e.to_string())?;
+    let bytes = glib::Bytes::from(&data[..]);
+    // Cairo ARGB32 is native-endian premultiplied, i.e. BGRA on little-endian
+    let format = if cfg!(target_endian = "little") {
+        gdk::MemoryFormat::B8g8r8a8Premultiplied
+    } else {
+        gdk::MemoryFormat::A8r8g8b8Premultiplied
+    };
+    let texture = gdk::MemoryTexture::new(EXPORT_WIDTH, EXPORT_HEIGHT, format, &bytes, stride);
+    texture.save_to_png(path).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Widget
+// ============================================================================
+
+/// Overview card with every temperature, RPM and duty series
+pub struct HistoryGraph {
+    card: adw::Bin,
+}
+
+impl HistoryGraph {
+    pub fn new() -> Self {
+        let history = Rc::new(RefCell::new(History::default()));
+        let saved_window = hf_core::get_cached_settings().display.graph_window_secs;
+        let window_secs = Rc::new(RefCell::new(
+            WINDOWS.iter().map(|(secs, _)| *secs).find(|secs| *secs == saved_window).unwrap_or(WINDOWS[1].0),
+        ));
+
+        let card = adw::Bin::builder().css_classes(["card"]).build();
+        let content = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .build();
+
+        // Header: title, window selector, export menu
+        let header = GtkBox::builder().orientation(Orientation::Horizontal).spacing(12).build();
+        let title = Label::builder()
+            .label("Overview")
+            .css_classes(["title-3"])
+            .hexpand(true)
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let labels: Vec<&str> = WINDOWS.iter().map(|(_, label)| *label).collect();
+        let window_dropdown = DropDown::from_strings(&labels);
+        window_dropdown.set_tooltip_text(Some("Time window"));
+        window_dropdown.set_valign(gtk4::Align::Center);
+        let selected = WINDOWS.iter().position(|(secs, _)| *secs == *window_secs.borrow()).unwrap_or(1);
+        window_dropdown.set_selected(selected as u32);
+
+        let export_btn = gtk4::MenuButton::builder()
+            .icon_name("document-save-symbolic")
+            .css_classes(["flat", "circular"])
+            .tooltip_text("Export visible window")
+            .valign(gtk4::Align::Center)
+            .build();
+        let export_menu = gtk4::gio::Menu::new();
+        export_menu.append(Some("Export as CSV..."), Some("overview.export-csv"));
+        export_menu.append(Some("Export as PNG..."), Some("overview.export-png"));
+        export_btn.set_menu_model(Some(&export_menu));
+
+        header.append(&title);
+        header.append(&window_dropdown);
+        header.append(&export_btn);
+
+        let subtitle = Label::builder()
+            .label("Temperatures on the left axis, fan RPM and PWM duty (dashed) on the right")
+            .css_classes(["dim-label", "caption"])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let drawing_area = DrawingArea::builder().height_request(GRAPH_HEIGHT).hexpand(true).build();
+        let history_for_draw = history.clone();
+        let window_for_draw = window_secs.clone();
+        drawing_area.set_draw_func(move |_, cr, width, height| {
+            draw(cr, width as f64, height as f64, &history_for_draw.borrow(), *window_for_draw.borrow());
+        });
+
+        // Legend: one toggle per series
+        let legend = FlowBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .column_spacing(6)
+            .row_spacing(6)
+            .max_children_per_line(8)
+            .homogeneous(false)
+            .build();
+
+        content.append(&header);
+        content.append(&subtitle);
+        content.append(&drawing_area);
+        content.append(&legend);
+        card.set_child(Some(&content));
+
+        // Window selection
+        let window_for_select = window_secs.clone();
+        let area_for_select = drawing_area.clone();
+        window_dropdown.connect_selected_notify(move |dropdown| {
+            let Some((secs, _)) = WINDOWS.get(dropdown.selected() as usize) else { return };
+            *window_for_select.borrow_mut() = *secs;
+            area_for_select.queue_draw();
+            let secs = *secs;
+            if let Err(e) = hf_core::update_setting(|s| s.display.graph_window_secs = secs) {
+                warn!("Failed to save graph window: {}", e);
+            }
+        });
+
+        // Export actions
+        let actions = gtk4::gio::SimpleActionGroup::new();
+        let csv_action = gtk4::gio::SimpleAction::new("export-csv", None);
+        let history_for_csv = history.clone();
+        let window_for_csv = window_secs.clone();
+        let btn_for_csv = export_btn.clone();
+        csv_action.connect_activate(move |_, _| {
+            let start_ms = now_ms().saturating_sub(*window_for_csv.borrow() as u64 * 1000);
+            let csv = history_for_csv.borrow().to_csv(start_ms);
+            Self::save_file(&btn_for_csv, "hyperfan-graph.csv", move |path| {
+                std::fs::write(path, &csv).map_err(|e| e.to_string())
+            });
+        });
+        actions.add_action(&csv_action);
+
+        let png_action = gtk4::gio::SimpleAction::new("export-png", None);
+        let history_for_png = history.clone();
+        let window_for_png = window_secs.clone();
+        let btn_for_png = export_btn.clone();
+        png_action.connect_activate(move |_, _| {
+            let history = history_for_png.clone();
+            let window_secs = *window_for_png.borrow();
+            Self::save_file(&btn_for_png, "hyperfan-graph.png", move |path| {
+                export_png(path, &history.borrow(), window_secs)
+            });
+        });
+        actions.add_action(&png_action);
+        card.insert_action_group("overview", Some(&actions));
+
+        // Daemon history from before the GUI started: the last day finer, the week coarser
+        let (backfill_tx, backfill_rx) = std::sync::mpsc::channel();
+        if !crate::fallback::is_active() {
+            std::thread::spawn(move || {
+                let now = now_ms();
+                for range_ms in [DENSE_RETENTION_MS, RETENTION_MS] {
+                    match hf_core::daemon_query_history(now.saturating_sub(range_ms), now, 0, Vec::new()) {
+                        Ok(data) => {
+                            if backfill_tx.send(data).is_err() {
+                                return;
+                            }
+                        }
+                        Err(e) => {
+                            debug!("Daemon history unavailable: {}", e);
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+
+        // Recording runs even while the page is hidden, so long windows fill up
+        let area_for_tick = drawing_area.clone();
+        let legend_for_tick = legend.clone();
+        glib::timeout_add_local(SAMPLE_INTERVAL, move || {
+            let Some(data) = crate::runtime::get_sensors() else {
+                return glib::ControlFlow::Continue;
+            };
+            let changed = history.borrow_mut().record(&data, now_ms());
+            // After recording, so the series to fill in exist
+            while let Ok(stored) = backfill_rx.try_recv() {
+                history.borrow_mut().backfill(&stored);
+            }
+            if changed {
+                Self::rebuild_legend(&legend_for_tick, &history, &area_for_tick);
+            }
+            if area_for_tick.is_mapped() {
+                area_for_tick.queue_draw();
+            }
+            glib::ControlFlow::Continue
+        });
+
+        Self { card }
+    }
+
+    /// Recreate the legend toggles after series were added or dropped
+    fn rebuild_legend(legend: &FlowBox, history: &Rc<RefCell<History>>, area: &DrawingArea) {
+        legend.remove_all();
+
+        for series in history.borrow().series.iter() {
+            let row = GtkBox::builder().orientation(Orientation::Horizontal).spacing(6).build();
+            let swatch = DrawingArea::builder().content_width(10).content_height(10).valign(gtk4::Align::Center).build();
+            let (r, g, b) = series.color();
+            let dashed = series.kind == SeriesKind::Duty;
+            swatch.set_draw_func(move |_, cr, width, height| {
+                cr.set_source_rgb(r, g, b);
+                if dashed {
+                    cr.rectangle(0.0, height as f64 / 2.0 - 1.5, width as f64, 3.0);
+                } else {
+                    cr.arc(width as f64 / 2.0, height as f64 / 2.0, width as f64 / 2.0, 0.0, 2.0 * std::f64::consts::PI);
+                }
+                let _ = cr.fill();
+            });
+            row.append(&swatch);
+            row.append(&Label::new(Some(&series.label)));
+
+            let toggle = ToggleButton::builder()
+                .child(&row)
+                .active(series.visible)
+                .css_classes(["flat", "pill"])
+                .tooltip_text(series.kind.unit())
+                .build();
+            let key = series.key.clone();
+            let history = history.clone();
+            let area = area.clone();
+            toggle.connect_toggled(move |toggle| {
+                let visible = toggle.is_active();
+                if let Some(series) = history.borrow_mut().series.iter_mut().find(|s| s.key == key) {
+                    series.visible = visible;
+                }
+                area.queue_draw();
+
+                let key = key.clone();
+                if let Err(e) = hf_core::update_setting(|s| {
+                    s.display.graph_hidden_series.retain(|k| *k != key);
+                    if !visible {
+                        s.display.graph_hidden_series.push(key);
+                    }
+                }) {
+                    warn!("Failed to save graph series visibility: {}", e);
+                }
+            });
+            legend.append(&toggle);
+        }
+    }
+
+    /// Ask for a destination and write an export there
+    fn save_file<F>(btn: &gtk4::MenuButton, initial_name: &str, write: F)
+    where
+        F: FnOnce(&std::path::Path) -> Result<(), String> + 'static,
+    {
+        let dialog = gtk4::FileDialog::builder()
+            .title("Export Graph")
+            .initial_name(initial_name)
+            .build();
+
+        if let Some(window) = btn.root().and_then(|r| r.downcast::<gtk4::Window>().ok()) {
+            dialog.save(Some(&window), gtk4::gio::Cancellable::NONE, move |result| {
+                let Some(path) = result.ok().and_then(|file| file.path()) else { return };
+                match write(&path) {
+                    Ok(()) => debug!("Exported graph to {:?}", path),
+                    Err(e) => error!("Failed to export graph to {:?}: {}", path, e),
+                }
+            });
+        }
+    }
+
+    pub fn widget(&self) -> &adw::Bin {
+        &self.card
+    }
+}
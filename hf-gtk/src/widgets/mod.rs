@@ -1,30 +1,42 @@
 pub mod add_curve_dialog;
 pub mod add_pair_dialog;
+mod apply_curve_dialog;
+mod auto_tune_dialog;
+mod binding_conflict_dialog;
 mod cpu_info_card;
 mod curve_card;
 mod curve_editor;
 mod curves_page;
+mod daemon_log_dialog;
 mod dashboard;
-mod detection_dialog;
+mod diagnostics_page;
 mod ec_control_page;
 mod fan_pairing_page;
 mod settings_page;
+mod setup_wizard;
 mod edit_curve_dialog;
+mod edit_history;
 mod fan_card;
 mod gpu_card;
 mod gpu_info_card;
+mod gpu_power_group;
 mod graphs_page;
+mod history_graph;
 mod nav_sidebar;
+mod pwm_group_dialog;
 mod sensors_page;
 mod system_info;
+mod template_dialog;
 mod temp_monitor;
+mod virtual_sensor_dialog;
 
 pub use curves_page::CurvesPage;
 pub use dashboard::Dashboard;
-pub use detection_dialog::DetectionDialog;
+pub use diagnostics_page::DiagnosticsPage;
 pub use ec_control_page::EcControlPage;
 pub use fan_pairing_page::FanPairingPage;
 pub use graphs_page::GraphsPage;
 pub use nav_sidebar::NavPage;
 pub use sensors_page::SensorsPage;
 pub use settings_page::SettingsPage;
+pub use setup_wizard::SetupWizard;
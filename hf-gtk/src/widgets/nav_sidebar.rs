@@ -21,6 +21,7 @@ pub enum NavPage {
     Sensors,
     Graphs,
     EcControl,
+    Diagnostics,
 }
 
 impl NavPage {
@@ -33,6 +34,7 @@ impl NavPage {
             NavPage::Sensors => "dialog-information-symbolic",
             NavPage::Graphs => "utilities-system-monitor-symbolic",
             NavPage::EcControl => "utilities-terminal-symbolic", // EC register access
+            NavPage::Diagnostics => "system-run-symbolic",
         }
     }
 
@@ -45,6 +47,7 @@ impl NavPage {
             NavPage::Sensors => "Temperature Sensors",
             NavPage::Graphs => "Temperature Graphs",
             NavPage::EcControl => "EC Direct Control (DANGEROUS)",
+            NavPage::Diagnostics => "Diagnostics",
         }
     }
 }
@@ -108,13 +111,16 @@ impl NavSidebar {
 
     /// Build a navigation button for a specific page
     fn build_nav_button(page: NavPage) -> Button {
-        Button::builder()
+        let button = Button::builder()
             .icon_name(page.icon_name())
             .width_request(50)
             .height_request(50)
             .tooltip_text(page.tooltip())
             .css_classes(["flat", "circular", "nav-button"])
-            .build()
+            .build();
+        // Icon-only: screen readers announce the page name instead of the icon
+        button.update_property(&[gtk4::accessible::Property::Label(page.tooltip())]);
+        button
     }
 
     /// Connect a callback for when navigation occurs
@@ -130,6 +136,7 @@ impl NavSidebar {
             } else {
                 button.remove_css_class("suggested-action");
             }
+            button.update_state(&[gtk4::accessible::State::Selected(Some(*page == active_page))]);
         }
     }
 
@@ -0,0 +1,389 @@
+//! PWM group management
+//!
+//! A group drives several PWM outputs from one curve and temperature source,
+//! each member following the curve with its own percent offset. Groups are
+//! stored by the daemon (`GetPwmGroups` / `SetPwmGroup` / `DeletePwmGroup`);
+//! a PWM output can only belong to one group.
+
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use tracing::warn;
+
+use hf_core::{DaemonPwmGroup, DaemonPwmGroupMember};
+
+/// A PWM output that can be added to a group: (pwm path, display name)
+pub type GroupPwmChoice = (String, String);
+
+/// Open the group list window
+pub fn show_pwm_groups_dialog(parent: &impl IsA<gtk4::Widget>, pwms: Vec<GroupPwmChoice>) {
+    let window = adw::Window::builder()
+        .title("PWM Groups")
+        .default_width(560)
+        .default_height(520)
+        .modal(true)
+        .build();
+    if let Some(parent_window) = parent.root().and_downcast::<gtk4::Window>() {
+        window.set_transient_for(Some(&parent_window));
+    }
+
+    let header = adw::HeaderBar::new();
+    let add_btn = gtk4::Button::builder()
+        .icon_name("list-add-symbolic")
+        .tooltip_text("Add group")
+        .build();
+    header.pack_start(&add_btn);
+
+    let page = adw::PreferencesPage::new();
+    let list_group = adw::PreferencesGroup::builder()
+        .title("Groups")
+        .description("Each group runs one curve and applies it to every member output")
+        .build();
+    page.add(&list_group);
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&header);
+    toolbar.set_content(Some(&page));
+    window.set_content(Some(&toolbar));
+
+    let rows: Rc<RefCell<Vec<gtk4::Widget>>> = Rc::new(RefCell::new(Vec::new()));
+    let pwms = Rc::new(pwms);
+
+    let refresh: Rc<RefreshSlot> = Rc::new(RefCell::new(None));
+    let refresh_weak = Rc::downgrade(&refresh);
+    {
+        let list_group = list_group.clone();
+        let window = window.clone();
+        let pwms = pwms.clone();
+        *refresh.borrow_mut() = Some(Box::new(move |groups| {
+            for row in rows.borrow_mut().drain(..) {
+                list_group.remove(&row);
+            }
+            let groups = match groups {
+                Ok(groups) => groups,
+                Err(e) => {
+                    warn!("Failed to load PWM groups: {}", e);
+                    let row = adw::ActionRow::builder()
+                        .title("Daemon Unavailable")
+                        .subtitle(e.as_str())
+                        .build();
+                    list_group.add(&row);
+                    rows.borrow_mut().push(row.upcast());
+                    return;
+                }
+            };
+            if groups.is_empty() {
+                let row = adw::ActionRow::builder()
+                    .title("No groups")
+                    .subtitle("Add a group to drive several outputs from one curve")
+                    .build();
+                list_group.add(&row);
+                rows.borrow_mut().push(row.upcast());
+            }
+            for group in &groups {
+                let row = group_row(group, &groups, &window, &pwms, refresh_weak.clone());
+                list_group.add(&row);
+                rows.borrow_mut().push(row.upcast());
+            }
+        }));
+    }
+    reload(&refresh, hf_core::daemon_get_pwm_groups());
+
+    let window_add = window.clone();
+    let refresh_add = refresh.clone();
+    add_btn.connect_clicked(move |_| {
+        let groups = hf_core::daemon_get_pwm_groups().unwrap_or_default();
+        let refresh = Rc::downgrade(&refresh_add);
+        show_group_editor(&window_add, None, &groups, &pwms, refresh);
+    });
+
+    window.present();
+}
+
+/// Rebuilds the group list from a `GetPwmGroups` result (set once the list exists)
+type RefreshSlot = RefCell<Option<Box<dyn Fn(Result<Vec<DaemonPwmGroup>, String>)>>>;
+type Refresh = std::rc::Weak<RefreshSlot>;
+
+fn reload(refresh: &RefreshSlot, groups: Result<Vec<DaemonPwmGroup>, String>) {
+    if let Some(refresh) = refresh.borrow().as_ref() {
+        refresh(groups);
+    }
+}
+
+/// List row for one group with edit and delete buttons
+fn group_row(
+    group: &DaemonPwmGroup,
+    groups: &[DaemonPwmGroup],
+    window: &adw::Window,
+    pwms: &Rc<Vec<GroupPwmChoice>>,
+    refresh: Refresh,
+) -> adw::ActionRow {
+    let curve_name = hf_core::load_curves()
+        .ok()
+        .and_then(|store| store.all().iter().find(|c| c.id == group.curve_id).map(|c| c.name.clone()))
+        .unwrap_or_else(|| group.curve_id.clone());
+    let mut subtitle = format!(
+        "{} output{} · {}",
+        group.members.len(),
+        if group.members.len() == 1 { "" } else { "s" },
+        curve_name
+    );
+    if !group.active {
+        subtitle.push_str(" · inactive");
+    }
+    let row = adw::ActionRow::builder()
+        .title(group.name.as_str())
+        .subtitle(subtitle.as_str())
+        .build();
+
+    let edit_btn = gtk4::Button::builder()
+        .icon_name("document-edit-symbolic")
+        .tooltip_text("Edit group")
+        .valign(gtk4::Align::Center)
+        .css_classes(["flat"])
+        .build();
+    let delete_btn = gtk4::Button::builder()
+        .icon_name("user-trash-symbolic")
+        .tooltip_text("Delete group")
+        .valign(gtk4::Align::Center)
+        .css_classes(["flat", "destructive-action"])
+        .build();
+    row.add_suffix(&edit_btn);
+    row.add_suffix(&delete_btn);
+
+    let window_edit = window.clone();
+    let group_edit = group.clone();
+    let groups_edit = groups.to_vec();
+    let pwms_edit = pwms.clone();
+    let refresh_edit = refresh.clone();
+    edit_btn.connect_clicked(move |_| {
+        show_group_editor(&window_edit, Some(&group_edit), &groups_edit, &pwms_edit, refresh_edit.clone());
+    });
+
+    let id = group.id.clone();
+    delete_btn.connect_clicked(move |_| {
+        let result = hf_core::daemon_delete_pwm_group(&id);
+        if let Some(refresh) = refresh.upgrade() {
+            reload(&refresh, result);
+        }
+    });
+
+    row
+}
+
+/// Editor for a new group (`existing` None) or an existing one
+fn show_group_editor(
+    parent: &adw::Window,
+    existing: Option<&DaemonPwmGroup>,
+    groups: &[DaemonPwmGroup],
+    pwms: &[GroupPwmChoice],
+    refresh: Refresh,
+) {
+    let editor = adw::Window::builder()
+        .title(if existing.is_some() { "Edit Group" } else { "New Group" })
+        .default_width(520)
+        .default_height(600)
+        .modal(true)
+        .transient_for(parent)
+        .build();
+
+    let header = adw::HeaderBar::new();
+    let save_btn = gtk4::Button::builder()
+        .label("Save")
+        .css_classes(["suggested-action"])
+        .build();
+    header.pack_end(&save_btn);
+
+    let page = adw::PreferencesPage::new();
+    let general = adw::PreferencesGroup::new();
+    page.add(&general);
+
+    let name_row = adw::EntryRow::builder().title("Name").build();
+    if let Some(group) = existing {
+        name_row.set_text(&group.name);
+    }
+    general.add(&name_row);
+
+    // Curve
+    let curves: Vec<(String, String)> = hf_core::load_curves()
+        .map(|store| store.all().iter().map(|c| (c.id.clone(), c.name.clone())).collect())
+        .unwrap_or_default();
+    let curve_names: Vec<&str> = curves.iter().map(|(_, name)| name.as_str()).collect();
+    let curve_row = adw::ComboRow::builder()
+        .title("Curve")
+        .model(&gtk4::StringList::new(&curve_names))
+        .build();
+    if let Some(idx) = existing.and_then(|g| curves.iter().position(|(id, _)| *id == g.curve_id)) {
+        curve_row.set_selected(idx as u32);
+    }
+    general.add(&curve_row);
+
+    // Temperature source
+    let temps = temp_sources();
+    let temp_names: Vec<&str> = temps.iter().map(|(_, name)| name.as_str()).collect();
+    let temp_row = adw::ComboRow::builder()
+        .title("Temperature Source")
+        .model(&gtk4::StringList::new(&temp_names))
+        .build();
+    if let Some(idx) = existing.and_then(|g| temps.iter().position(|(path, _)| *path == g.temp_source_path)) {
+        temp_row.set_selected(idx as u32);
+    }
+    general.add(&temp_row);
+
+    let active_row = adw::SwitchRow::builder()
+        .title("Active")
+        .active(existing.is_none_or(|g| g.active))
+        .build();
+    general.add(&active_row);
+
+    // Members: outputs already in another group are not offered
+    let members_group = adw::PreferencesGroup::builder()
+        .title("Outputs")
+        .description("Offsets are added to the curve's duty for that output")
+        .build();
+    page.add(&members_group);
+
+    let existing_id = existing.map(|g| g.id.as_str());
+    let taken: Vec<&str> = groups
+        .iter()
+        .filter(|g| Some(g.id.as_str()) != existing_id)
+        .flat_map(|g| g.members.iter().map(|m| m.pwm_path.as_str()))
+        .collect();
+
+    let mut member_rows: Vec<(String, gtk4::CheckButton, gtk4::SpinButton)> = Vec::new();
+    for (pwm_path, label) in pwms.iter().filter(|(path, _)| !taken.contains(&path.as_str())) {
+        let member = existing.and_then(|g| g.members.iter().find(|m| m.pwm_path == *pwm_path));
+        let check = gtk4::CheckButton::builder()
+            .active(member.is_some())
+            .valign(gtk4::Align::Center)
+            .build();
+        let max_offset = hf_core::MAX_PWM_GROUP_OFFSET_PERCENT as f64;
+        let offset = gtk4::SpinButton::builder()
+            .adjustment(&gtk4::Adjustment::new(
+                member.map_or(0.0, |m| m.offset_percent as f64),
+                -max_offset,
+                max_offset,
+                1.0,
+                5.0,
+                0.0,
+            ))
+            .digits(0)
+            .valign(gtk4::Align::Center)
+            .tooltip_text("Offset (%)")
+            .build();
+        let row = adw::ActionRow::builder()
+            .title(label.as_str())
+            .subtitle(pwm_path.as_str())
+            .activatable_widget(&check)
+            .build();
+        row.add_prefix(&check);
+        row.add_suffix(&offset);
+        members_group.add(&row);
+        member_rows.push((pwm_path.clone(), check, offset));
+    }
+    if member_rows.is_empty() {
+        members_group.add(&adw::ActionRow::builder().title("No PWM outputs available").build());
+    }
+
+    let toast_overlay = adw::ToastOverlay::new();
+    toast_overlay.set_child(Some(&page));
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&header);
+    toolbar.set_content(Some(&toast_overlay));
+    editor.set_content(Some(&toolbar));
+
+    let id = existing.map_or_else(hf_core::generate_guid, |g| g.id.clone());
+    let editor_save = editor.clone();
+    save_btn.connect_clicked(move |_| {
+        let name = name_row.text().trim().to_string();
+        let curve = curves.get(curve_row.selected() as usize);
+        let temp = temps.get(temp_row.selected() as usize);
+        let members: Vec<DaemonPwmGroupMember> = member_rows
+            .iter()
+            .filter(|(_, check, _)| check.is_active())
+            .map(|(pwm_path, _, offset)| DaemonPwmGroupMember {
+                pwm_path: pwm_path.clone(),
+                offset_percent: offset.value() as f32,
+            })
+            .collect();
+
+        let problem = if name.is_empty() {
+            Some("Enter a group name")
+        } else if curve.is_none() {
+            Some("Select a curve")
+        } else if temp.is_none() {
+            Some("Select a temperature source")
+        } else if members.is_empty() {
+            Some("Select at least one output")
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            toast_overlay.add_toast(adw::Toast::new(problem));
+            return;
+        }
+
+        let group = DaemonPwmGroup {
+            id: id.clone(),
+            name,
+            curve_id: curve.map(|(id, _)| id.clone()).unwrap_or_default(),
+            temp_source_path: temp.map(|(path, _)| path.clone()).unwrap_or_default(),
+            members,
+            active: active_row.is_active(),
+        };
+        match hf_core::daemon_set_pwm_group(group) {
+            Ok(groups) => {
+                if let Some(refresh) = refresh.upgrade() {
+                    reload(&refresh, Ok(groups));
+                }
+                editor_save.close();
+            }
+            Err(e) => {
+                warn!("Failed to save PWM group: {}", e);
+                toast_overlay.add_toast(adw::Toast::new(&format!("Failed to save group: {}", e)));
+            }
+        }
+    });
+
+    editor.present();
+}
+
+/// Temperature sources from the daemon as (path, display name)
+pub(super) fn temp_sources() -> Vec<(String, String)> {
+    let mut sources = Vec::new();
+    if let Ok(gpus) = hf_core::daemon_list_gpus() {
+        for gpu in gpus {
+            let sensors = gpu.temp.map(|_| "GPU".to_string()).into_iter()
+                .chain(gpu.temperatures.iter().skip(1).map(|t| t.name.clone()));
+            for sensor in sensors {
+                let path = format!("gpu:{}:{}", gpu.index, sensor);
+                let name = hf_core::get_sensor_friendly_name(&path)
+                    .ok()
+                    .flatten()
+                    .unwrap_or_else(|| format!("{} - {}", gpu.name, sensor));
+                sources.push((path, name));
+            }
+        }
+    }
+    if let Ok(hw) = hf_core::daemon_list_hardware() {
+        for chip in hw.chips {
+            if chip.name.contains("amdgpu") {
+                continue;
+            }
+            for temp in chip.temperatures {
+                let name = hf_core::get_sensor_friendly_name(&temp.path)
+                    .ok()
+                    .flatten()
+                    .unwrap_or_else(|| match temp.label {
+                        Some(label) => format!("{} - {}", chip.name, label),
+                        None => format!("{}/{}", chip.name, temp.name),
+                    });
+                sources.push((temp.path, name));
+            }
+        }
+    }
+    sources
+}
@@ -1,25 +1,29 @@
 //! Temperature Sensors Page
 //!
 //! Displays all detected temperature sensors with live readings.
-//! Sensors are grouped by hardware chip for easy identification.
+//! Sensors are grouped by hardware chip for easy identification: one card per
+//! chip, in processor, graphics, motherboard/case and storage sections. Cards
+//! can be reordered within their section and hidden; the layout is saved in
+//! `DisplaySettings`.
 //! Now includes GPU temperature sensors from NVIDIA and AMD GPUs.
+//! Voltage, current, power and humidity channels are listed in their own
+//! section with a kind filter.
 
 #![allow(dead_code)]
 
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, Entry, Label, Orientation, ScrolledWindow};
+use gtk4::{Box as GtkBox, Button, DropDown, Entry, Label, Orientation, ScrolledWindow};
 use gtk4::glib;
 use libadwaita as adw;
 use libadwaita::prelude::*;
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
 use std::time::Duration;
 
 use super::cpu_info_card::CpuInfoCard;
 use super::gpu_info_card::GpuInfoCard;
 
-use hf_core::daemon_client;
-
 // ============================================================================
 // Constants
 // ============================================================================
@@ -47,6 +51,14 @@ struct FanDisplay {
     pwm_label: Option<Label>,
 }
 
+/// Holds references to a voltage/current/power/humidity row for live updates
+struct AuxDisplay {
+    path: String,
+    kind: hf_core::DaemonSensorKind,
+    row: adw::ActionRow,
+    value_label: Label,
+}
+
 /// Holds references to GPU display elements for live updates
 struct GpuDisplay {
     index: u32,
@@ -58,15 +70,337 @@ struct CpuDisplay {
     card: CpuInfoCard,
 }
 
+/// Section of the page a chip's card belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChipSection {
+    Processor,
+    Graphics,
+    Case,
+    Storage,
+}
+
+impl ChipSection {
+    fn of(chip_name: &str) -> Self {
+        if chip_name.contains("coretemp")
+            || chip_name.contains("k10temp")
+            || chip_name.contains("zenpower")
+            || chip_name.contains("cpu")
+            || chip_name.contains("acpitz")
+        {
+            Self::Processor
+        } else if chip_name.contains("amdgpu") || chip_name.contains("nvidia") {
+            Self::Graphics
+        } else if hf_core::is_storage_chip(chip_name) {
+            Self::Storage
+        } else {
+            Self::Case
+        }
+    }
+}
+
+/// Card listing the sensors of one hwmon chip
+struct ChipCard {
+    /// Key the layout is saved under (chip name, "#n" for the n-th chip of a driver)
+    key: String,
+    group: adw::PreferencesGroup,
+    handle: gtk4::Image,
+    menu: gtk4::Popover,
+    up_btn: Button,
+    down_btn: Button,
+    hide_btn: Button,
+}
+
+impl ChipCard {
+    fn new(key: &str, chip_path: &str) -> Self {
+        let group = adw::PreferencesGroup::builder()
+            .title(key)
+            .description(chip_path)
+            .build();
+
+        let handle = gtk4::Image::builder()
+            .icon_name("list-drag-handle-symbolic")
+            .css_classes(["dim-label"])
+            .tooltip_text("Drag to reorder")
+            .build();
+        handle.set_cursor_from_name(Some("grab"));
+
+        let up_btn = Button::builder().label("Move Up").css_classes(["flat"]).build();
+        let down_btn = Button::builder().label("Move Down").css_classes(["flat"]).build();
+        let hide_btn = Button::builder().label("Hide Card").css_classes(["flat"]).build();
+        let menu_box = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .build();
+        menu_box.append(&up_btn);
+        menu_box.append(&down_btn);
+        menu_box.append(&hide_btn);
+        let menu = gtk4::Popover::builder().child(&menu_box).build();
+        let menu_btn = gtk4::MenuButton::builder()
+            .icon_name("view-more-symbolic")
+            .css_classes(["flat", "circular"])
+            .tooltip_text("Card options")
+            .valign(gtk4::Align::Center)
+            .popover(&menu)
+            .build();
+        menu_btn.update_property(&[gtk4::accessible::Property::Label(&format!("{} card options", key))]);
+
+        let suffix = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        suffix.append(&menu_btn);
+        suffix.append(&handle);
+        group.set_header_suffix(Some(&suffix));
+
+        Self { key: key.to_string(), group, handle, menu, up_btn, down_btn, hide_btn }
+    }
+}
+
+/// Titled section of the page: info cards followed by reorderable chip cards
+struct CardSection {
+    label: Label,
+    /// CPU and GPU info cards, always above the chip cards
+    fixed_box: GtkBox,
+    cards_box: GtkBox,
+    cards: RefCell<Vec<ChipCard>>,
+}
+
+impl CardSection {
+    fn new(title: &str, margin_top: i32, list_box: &GtkBox) -> Self {
+        let label = Label::builder()
+            .label(title)
+            .css_classes(["title-2"])
+            .halign(gtk4::Align::Start)
+            .margin_top(margin_top)
+            .margin_bottom(6)
+            .build();
+        let fixed_box = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(18)
+            .build();
+        let cards_box = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(18)
+            .build();
+        list_box.append(&label);
+        list_box.append(&fixed_box);
+        list_box.append(&cards_box);
+        Self { label, fixed_box, cards_box, cards: RefCell::new(Vec::new()) }
+    }
+
+    /// Put the card widgets in the order of `cards`
+    fn relayout(&self) {
+        let mut previous: Option<adw::PreferencesGroup> = None;
+        for card in self.cards.borrow().iter() {
+            if card.group.parent().is_none() {
+                self.cards_box.append(&card.group);
+            }
+            self.cards_box.reorder_child_after(&card.group, previous.as_ref());
+            previous = Some(card.group.clone());
+        }
+    }
+}
+
+/// Chip cards of the page with the saved order and hidden state applied
+struct CardLayout {
+    sections: Vec<CardSection>,
+    /// Hidden cards are shown dimmed so they can be restored
+    show_hidden: Cell<bool>,
+}
+
+impl CardLayout {
+    /// Sort every section into the saved order and add its cards to the page
+    fn arrange(&self) {
+        let display = hf_core::get_cached_settings().display;
+        for section in &self.sections {
+            let mut keys: Vec<String> = section.cards.borrow().iter().map(|c| c.key.clone()).collect();
+            display.arrange_sensor_cards(&mut keys);
+            section.cards.borrow_mut().sort_by_key(|c| keys.iter().position(|k| *k == c.key));
+            section.relayout();
+        }
+    }
+
+    /// Wire up the menu, drag source and drop target of every card
+    fn connect(layout: &Rc<Self>) {
+        for section in &layout.sections {
+            for card in section.cards.borrow().iter() {
+                let weak = Rc::downgrade(layout);
+                let (key, menu) = (card.key.clone(), card.menu.clone());
+                card.up_btn.connect_clicked(move |_| {
+                    menu.popdown();
+                    if let Some(layout) = weak.upgrade() {
+                        layout.shift_card(&key, false);
+                    }
+                });
+
+                let weak = Rc::downgrade(layout);
+                let (key, menu) = (card.key.clone(), card.menu.clone());
+                card.down_btn.connect_clicked(move |_| {
+                    menu.popdown();
+                    if let Some(layout) = weak.upgrade() {
+                        layout.shift_card(&key, true);
+                    }
+                });
+
+                let weak = Rc::downgrade(layout);
+                let (key, menu) = (card.key.clone(), card.menu.clone());
+                card.hide_btn.connect_clicked(move |_| {
+                    menu.popdown();
+                    let hidden = !hf_core::get_cached_settings().display.is_sensor_card_hidden(&key);
+                    if let Err(e) = hf_core::update_setting(|s| s.display.set_sensor_card_hidden(&key, hidden)) {
+                        tracing::warn!("Failed to save sensor card visibility: {}", e);
+                    }
+                    if let Some(layout) = weak.upgrade() {
+                        layout.refresh();
+                    }
+                });
+
+                let drag_source = gtk4::DragSource::builder()
+                    .actions(gtk4::gdk::DragAction::MOVE)
+                    .build();
+                let key = card.key.clone();
+                drag_source.connect_prepare(move |_, _, _| {
+                    Some(gtk4::gdk::ContentProvider::for_value(&key.to_value()))
+                });
+                let group = card.group.downgrade();
+                drag_source.connect_drag_begin(move |source, _| {
+                    if let Some(group) = group.upgrade() {
+                        source.set_icon(Some(&gtk4::WidgetPaintable::new(Some(&group))), 0, 0);
+                    }
+                });
+                card.handle.add_controller(drag_source);
+
+                let drop_target = gtk4::DropTarget::new(glib::Type::STRING, gtk4::gdk::DragAction::MOVE);
+                let weak: Weak<Self> = Rc::downgrade(layout);
+                let key = card.key.clone();
+                drop_target.connect_drop(move |target, value, _, y| {
+                    let (Some(layout), Ok(dragged)) = (weak.upgrade(), value.get::<String>()) else {
+                        return false;
+                    };
+                    let after = target.widget().is_some_and(|w| y > w.height() as f64 / 2.0);
+                    layout.drop_card(&dragged, &key, after)
+                });
+                card.group.add_controller(drop_target);
+            }
+        }
+    }
+
+    /// Section holding the card `key` and the card's index in it
+    fn find(&self, key: &str) -> Option<(&CardSection, usize)> {
+        self.sections.iter().find_map(|section| {
+            let index = section.cards.borrow().iter().position(|c| c.key == key)?;
+            Some((section, index))
+        })
+    }
+
+    /// Swap a card with its previous or next shown neighbour
+    fn shift_card(&self, key: &str, forward: bool) {
+        let Some((section, from)) = self.find(key) else {
+            return;
+        };
+        let to = {
+            let cards = section.cards.borrow();
+            let display = hf_core::get_cached_settings().display;
+            let shown = |i: &usize| self.show_hidden.get() || !display.is_sensor_card_hidden(&cards[*i].key);
+            if forward {
+                (from + 1..cards.len()).find(shown)
+            } else {
+                (0..from).rev().find(shown)
+            }
+        };
+        if let Some(to) = to {
+            self.reorder(section, from, to);
+        }
+    }
+
+    /// Move the dragged card next to the card it was dropped on (same section only)
+    fn drop_card(&self, dragged: &str, target: &str, after: bool) -> bool {
+        let (Some((section, from)), Some((target_section, target_index))) = (self.find(dragged), self.find(target)) else {
+            return false;
+        };
+        if !std::ptr::eq(section, target_section) || from == target_index {
+            return false;
+        }
+        // Index of the target once the dragged card is taken out
+        let target_index = if from < target_index { target_index - 1 } else { target_index };
+        self.reorder(section, from, target_index + usize::from(after));
+        true
+    }
+
+    fn reorder(&self, section: &CardSection, from: usize, to: usize) {
+        let keys: Vec<String> = {
+            let mut cards = section.cards.borrow_mut();
+            let card = cards.remove(from);
+            let to = to.min(cards.len());
+            cards.insert(to, card);
+            cards.iter().map(|c| c.key.clone()).collect()
+        };
+        section.relayout();
+        if let Err(e) = hf_core::update_setting(|s| s.display.set_sensor_card_order(&keys)) {
+            tracing::warn!("Failed to save sensor card order: {}", e);
+        }
+        self.refresh();
+    }
+
+    /// Apply the hidden state and update the menus and section headings
+    fn refresh(&self) {
+        let display = hf_core::get_cached_settings().display;
+        let show_hidden = self.show_hidden.get();
+        for section in &self.sections {
+            let cards = section.cards.borrow();
+            let shown: Vec<usize> = (0..cards.len())
+                .filter(|&i| show_hidden || !display.is_sensor_card_hidden(&cards[i].key))
+                .collect();
+            for (i, card) in cards.iter().enumerate() {
+                let hidden = display.is_sensor_card_hidden(&card.key);
+                card.group.set_visible(shown.contains(&i));
+                if hidden {
+                    card.group.add_css_class("dim-label");
+                } else {
+                    card.group.remove_css_class("dim-label");
+                }
+                card.hide_btn.set_label(if hidden { "Show Card" } else { "Hide Card" });
+                card.up_btn.set_sensitive(shown.first() != Some(&i));
+                card.down_btn.set_sensitive(shown.last() != Some(&i));
+            }
+            let has_fixed = section.fixed_box.first_child().is_some();
+            section.fixed_box.set_visible(has_fixed);
+            section.cards_box.set_visible(!shown.is_empty());
+            section.label.set_visible(has_fixed || !shown.is_empty());
+        }
+    }
+}
+
 /// Sensors page widget
 pub struct SensorsPage {
     container: GtkBox,
     sensors: Rc<RefCell<Vec<SensorDisplay>>>,
     fans: Rc<RefCell<Vec<FanDisplay>>>,
+    aux: Rc<RefCell<Vec<AuxDisplay>>>,
     gpu_displays: Rc<RefCell<Vec<GpuDisplay>>>,
     cpu_display: Rc<RefCell<Option<CpuDisplay>>>,
 }
 
+/// Filter choices for the electrical sensors section (index 0 = all)
+const AUX_FILTERS: [(&str, Option<hf_core::DaemonSensorKind>); 5] = [
+    ("All", None),
+    ("Voltage", Some(hf_core::DaemonSensorKind::Voltage)),
+    ("Current", Some(hf_core::DaemonSensorKind::Current)),
+    ("Power", Some(hf_core::DaemonSensorKind::Power)),
+    ("Humidity", Some(hf_core::DaemonSensorKind::Humidity)),
+];
+
+/// Format a scaled sensor value with its unit
+fn format_aux_value(kind: hf_core::DaemonSensorKind, value: f32) -> String {
+    if !value.is_finite() {
+        return format!("-- {}", kind.unit());
+    }
+    match kind {
+        hf_core::DaemonSensorKind::Voltage | hf_core::DaemonSensorKind::Current => format!("{:.3} {}", value, kind.unit()),
+        hf_core::DaemonSensorKind::Power => format!("{:.2} {}", value, kind.unit()),
+        hf_core::DaemonSensorKind::Humidity => format!("{:.1} {}", value, kind.unit()),
+    }
+}
+
 impl SensorsPage {
     pub fn new() -> Self {
         let container = GtkBox::builder()
@@ -95,7 +429,24 @@ impl SensorsPage {
             .tooltip_text("Refresh sensor list")
             .build();
 
+        let virtual_btn = Button::builder()
+            .icon_name("list-add-symbolic")
+            .css_classes(["circular", "flat"])
+            .tooltip_text("Virtual sensors")
+            .build();
+        virtual_btn.connect_clicked(|btn| {
+            super::virtual_sensor_dialog::show_virtual_sensors_dialog(btn);
+        });
+
+        let show_hidden_btn = gtk4::ToggleButton::builder()
+            .icon_name("view-reveal-symbolic")
+            .css_classes(["circular", "flat"])
+            .tooltip_text("Show hidden cards")
+            .build();
+
         header_box.append(&title);
+        header_box.append(&show_hidden_btn);
+        header_box.append(&virtual_btn);
         header_box.append(&refresh_btn);
         container.append(&header_box);
 
@@ -116,183 +467,73 @@ impl SensorsPage {
 
         let sensors: Rc<RefCell<Vec<SensorDisplay>>> = Rc::new(RefCell::new(Vec::new()));
         let fans: Rc<RefCell<Vec<FanDisplay>>> = Rc::new(RefCell::new(Vec::new()));
+        let aux: Rc<RefCell<Vec<AuxDisplay>>> = Rc::new(RefCell::new(Vec::new()));
         let gpu_displays: Rc<RefCell<Vec<GpuDisplay>>> = Rc::new(RefCell::new(Vec::new()));
         let cpu_display: Rc<RefCell<Option<CpuDisplay>>> = Rc::new(RefCell::new(None));
 
+        // Chip cards are grouped into sections; within a section the user can
+        // reorder them (drag the handle or use the card menu) and hide them
+        let processor = CardSection::new("Processor", 6, &list_box);
+        let graphics = CardSection::new("Graphics", 12, &list_box);
+        let case = CardSection::new("Motherboard & Case", 12, &list_box);
+        let storage = CardSection::new("Storage", 12, &list_box);
+
         // ================================================================
         // CPU Section
         // ================================================================
-        let cpu_section_label = Label::builder()
-            .label("Processor")
-            .css_classes(["title-2"])
-            .halign(gtk4::Align::Start)
-            .margin_top(6)
-            .margin_bottom(6)
-            .build();
-        list_box.append(&cpu_section_label);
-
         let cpu_card = CpuInfoCard::new();
-        list_box.append(cpu_card.widget());
+        processor.fixed_box.append(cpu_card.widget());
         *cpu_display.borrow_mut() = Some(CpuDisplay { card: cpu_card });
 
         // ================================================================
         // GPU Section (Sensor info only - NO controls)
         // ================================================================
         // Use daemon for GPU enumeration (authoritative)
-        if let Ok(daemon_gpus) = daemon_client::daemon_list_gpus() {
-            if !daemon_gpus.is_empty() {
-                let gpu_section_label = Label::builder()
-                    .label("Graphics")
-                    .css_classes(["title-2"])
-                    .halign(gtk4::Align::Start)
-                    .margin_top(12)
-                    .margin_bottom(6)
-                    .build();
-                list_box.append(&gpu_section_label);
-
-                // Convert daemon GPU info to GpuDevice format
-                for daemon_gpu in &daemon_gpus {
-                    // Parse vendor string to enum
-                    let vendor = match daemon_gpu.vendor.to_lowercase().as_str() {
-                        "nvidia" => hf_core::GpuVendor::Nvidia,
-                        "amd" => hf_core::GpuVendor::Amd,
-                        "intel" => hf_core::GpuVendor::Intel,
-                        _ => hf_core::GpuVendor::Nvidia, // Default fallback
-                    };
-                    
-                    // Create a minimal GpuDevice from daemon data
-                    let gpu = hf_core::GpuDevice {
-                        index: daemon_gpu.index,
-                        name: daemon_gpu.name.clone(),
-                        vendor,
-                        pci_bus_id: None,
-                        vram_total_mb: None,
-                        vram_used_mb: None,
-                        temperatures: Vec::new(), // Will be populated by live updates
-                        fans: Vec::new(),
-                        power_watts: None,
-                        power_limit_watts: None,
-                        utilization_percent: None,
-                    };
-                    
-                    let card = GpuInfoCard::new(&gpu);
-                    list_box.append(card.widget());
-                    
-                    gpu_displays.borrow_mut().push(GpuDisplay {
-                        index: daemon_gpu.index,
-                        card,
-                    });
-                }
-            }
-        }
-
-        // ================================================================
-        // Fan Sensors Section
-        // ================================================================
-        if let Ok(hw) = daemon_client::daemon_list_hardware() {
-            let mut has_fans = false;
-            for chip in &hw.chips {
-                if !chip.fans.is_empty() {
-                    has_fans = true;
-                    break;
-                }
-            }
-            
-            if has_fans {
-                let fan_section_label = Label::builder()
-                    .label("Fan Sensors")
-                    .css_classes(["title-2"])
-                    .halign(gtk4::Align::Start)
-                    .margin_top(12)
-                    .margin_bottom(6)
-                    .build();
-                list_box.append(&fan_section_label);
-
-                let fan_group = adw::PreferencesGroup::builder().build();
-
-                for chip in &hw.chips {
-                    for fan in &chip.fans {
-                        let fan_path = fan.path.clone();
-                        let default_label = fan.label.clone().unwrap_or_else(|| fan.name.clone());
-                        let chip_name = chip.name.clone();
-                        
-                        // Check if there's a matching PWM controller
-                        let fan_index = fan.name.chars()
-                            .find(|c| c.is_ascii_digit())
-                            .and_then(|c| c.to_digit(10));
-                        
-                        let has_pwm = fan_index.is_some() && fan_index
-                            .map(|idx| {
-                                let pwm_name = format!("pwm{}", idx);
-                                chip.pwms.iter().any(|p| p.name == pwm_name)
-                            })
-                            .unwrap_or(false);
-                        
-                        let display_name = format!("{} • {}", chip_name, default_label);
-                        
-                        let row = adw::ActionRow::builder()
-                            .title(&display_name)
-                            .subtitle(&fan_path)
-                            .build();
-
-                        // Create horizontal box for RPM and PWM display
-                        let stats_box = gtk4::Box::builder()
-                            .orientation(gtk4::Orientation::Horizontal)
-                            .spacing(12)
-                            .build();
-
-                        let rpm_placeholder = "-- RPM";
-                        let rpm_label = Label::builder()
-                            .label(rpm_placeholder)
-                            .css_classes(["title-3", "numeric"])
-                            .build();
-
-                        stats_box.append(&rpm_label);
-                        
-                        // Add PWM label if this fan has a controller
-                        let pwm_label = if has_pwm {
-                            let label = Label::builder()
-                                .label("--%")
-                                .css_classes(["title-3", "numeric", "dim-label"])
-                                .build();
-                            stats_box.append(&label);
-                            Some(label)
-                        } else {
-                            None
-                        };
-                        
-                        row.add_suffix(&stats_box);
-                        fan_group.add(&row);
-
-                        fans.borrow_mut().push(FanDisplay {
-                            path: fan_path,
-                            rpm_label,
-                            pwm_label,
-                        });
-                    }
-                }
-
-                list_box.append(&fan_group);
+        if let Ok(daemon_gpus) = crate::fallback::list_gpus() {
+            // Convert daemon GPU info to GpuDevice format
+            for daemon_gpu in &daemon_gpus {
+                // Parse vendor string to enum
+                let vendor = match daemon_gpu.vendor.to_lowercase().as_str() {
+                    "nvidia" => hf_core::GpuVendor::Nvidia,
+                    "amd" => hf_core::GpuVendor::Amd,
+                    "intel" => hf_core::GpuVendor::Intel,
+                    _ => hf_core::GpuVendor::Nvidia, // Default fallback
+                };
+                
+                // Create a minimal GpuDevice from daemon data
+                let gpu = hf_core::GpuDevice {
+                    index: daemon_gpu.index,
+                    name: daemon_gpu.name.clone(),
+                    vendor,
+                    pci_bus_id: None,
+                    vram_total_mb: None,
+                    vram_used_mb: None,
+                    temperatures: daemon_gpu.temperatures.iter().map(|t| hf_core::GpuTemperature {
+                        name: t.name.clone(),
+                        current_temp: Some(t.value),
+                        max_temp: t.max,
+                        critical_temp: t.critical,
+                        slowdown_temp: None,
+                    }).collect(),
+                    fans: Vec::new(),
+                    power_watts: None,
+                    power_limit_watts: None,
+                    utilization_percent: None,
+                    read_only: daemon_gpu.read_only,
+                };
+                
+                let card = GpuInfoCard::new(&gpu);
+                graphics.fixed_box.append(card.widget());
+                
+                gpu_displays.borrow_mut().push(GpuDisplay {
+                    index: daemon_gpu.index,
+                    card,
+                });
             }
         }
 
-        // ================================================================
-        // Other Sensors Section (Motherboard, etc)
-        // ================================================================
-        let other_section_label = Label::builder()
-            .label("Other Sensors")
-            .css_classes(["title-2"])
-            .halign(gtk4::Align::Start)
-            .margin_top(12)
-            .margin_bottom(6)
-            .build();
-        list_box.append(&other_section_label);
-
-        let group = adw::PreferencesGroup::builder()
-            .build();
-
         // Use daemon for hardware enumeration (authoritative)
-        let hw_result = daemon_client::daemon_list_hardware();
+        let hw_result = crate::fallback::list_hardware();
         
         if hw_result.is_err() {
             // Daemon unreachable - show empty state
@@ -326,29 +567,42 @@ impl SensorsPage {
                 container,
                 sensors,
                 fans,
+                aux,
                 gpu_displays,
                 cpu_display,
             };
         }
-        
-        if let Ok(hw) = hw_result {
-            for chip in hw.chips {
-                // Skip CPU and GPU chips as they're handled in their own sections
-                let is_cpu_chip = chip.name.contains("coretemp")
-                    || chip.name.contains("k10temp")
-                    || chip.name.contains("zenpower")
-                    || chip.name.contains("cpu")
-                    || chip.name.contains("acpitz");
-                let is_gpu_chip = chip.name.contains("amdgpu") || chip.name.contains("nvidia");
-
-                if is_cpu_chip || is_gpu_chip {
-                    continue;
-                }
 
-                for temp in &chip.temperatures {
+        // ================================================================
+        // Chip Cards (temperatures and fans of each hwmon chip)
+        // ================================================================
+        let mut aux_sensors: Vec<(String, hf_core::DaemonHwSensor)> = Vec::new();
+        if let Ok(hw) = hw_result {
+            let mut name_counts: HashMap<&str, usize> = HashMap::new();
+            for chip in &hw.chips {
+                // Electrical sensors are listed for every chip, including CPU/GPU
+                aux_sensors.extend(chip.sensors.iter().map(|s| (chip.name.clone(), s.clone())));
+
+                // Chips sharing a driver (several NVMe drives) are told apart by detection order
+                let count = name_counts.entry(chip.name.as_str()).or_default();
+                *count += 1;
+                let key = if *count == 1 { chip.name.clone() } else { format!("{} #{}", chip.name, count) };
+
+                // CPU and GPU temperatures are shown by their info cards
+                let section = match ChipSection::of(&chip.name) {
+                    ChipSection::Processor => &processor,
+                    ChipSection::Graphics => &graphics,
+                    ChipSection::Case => &case,
+                    ChipSection::Storage => &storage,
+                };
+                let show_temps = std::ptr::eq(section, &case) || std::ptr::eq(section, &storage);
+
+                let card = ChipCard::new(&key, &chip.path);
+                let mut has_rows = false;
+
+                for temp in chip.temperatures.iter().filter(|_| show_temps) {
                     let sensor_path = temp.path.clone();
                     let default_label = temp.label.clone().unwrap_or_else(|| temp.name.clone());
-                    let chip_name = chip.name.clone();
                     
                     // Check for user-defined friendly name
                     let display_name = hf_core::get_sensor_friendly_name(&sensor_path)
@@ -358,7 +612,7 @@ impl SensorsPage {
                     
                     let row = adw::ActionRow::builder()
                         .title(&display_name)
-                        .subtitle(&format!("{} • {}", chip_name, sensor_path))
+                        .subtitle(&sensor_path)
                         .activatable(true)
                         .build();
 
@@ -367,6 +621,10 @@ impl SensorsPage {
                         .label(&temp_placeholder)
                         .css_classes(["title-3", "numeric"])
                         .build();
+                    let limits = hf_core::TemperatureLimits { max: temp.max, crit: temp.crit, crit_alarm: temp.crit_alarm };
+                    if let Some(tooltip) = super::temp_monitor::limits_tooltip(&limits) {
+                        temp_label.set_tooltip_text(Some(&tooltip));
+                    }
 
                     // Edit button for friendly name
                     let edit_btn = Button::builder()
@@ -388,19 +646,172 @@ impl SensorsPage {
                         );
                     });
 
+                    // Filter button for spike rejection / smoothing
+                    let filter_btn = Button::builder()
+                        .icon_name("preferences-other-symbolic")
+                        .css_classes(["flat", "circular"])
+                        .tooltip_text("Filter readings")
+                        .valign(gtk4::Align::Center)
+                        .build();
+
+                    let sensor_path_for_filter = sensor_path.clone();
+                    let row_for_filter = row.clone();
+                    filter_btn.connect_clicked(move |btn| {
+                        Self::show_filter_dialog(btn, &sensor_path_for_filter, &row_for_filter.title());
+                    });
+
                     row.add_suffix(&edit_btn);
+                    row.add_suffix(&filter_btn);
                     row.add_suffix(&temp_label);
-                    group.add(&row);
+                    card.group.add(&row);
+                    has_rows = true;
 
                     sensors.borrow_mut().push(SensorDisplay {
                         path: sensor_path,
                         temp_label,
                     });
                 }
+
+                for fan in &chip.fans {
+                    let fan_path = fan.path.clone();
+                    let default_label = fan.label.clone().unwrap_or_else(|| fan.name.clone());
+                    
+                    // Check if there's a matching PWM controller
+                    let fan_index = fan.name.chars()
+                        .find(|c| c.is_ascii_digit())
+                        .and_then(|c| c.to_digit(10));
+                    
+                    let has_pwm = fan_index.is_some() && fan_index
+                        .map(|idx| {
+                            let pwm_name = format!("pwm{}", idx);
+                            chip.pwms.iter().any(|p| p.name == pwm_name)
+                        })
+                        .unwrap_or(false);
+                    
+                    let row = adw::ActionRow::builder()
+                        .title(&default_label)
+                        .subtitle(&fan_path)
+                        .build();
+
+                    // Create horizontal box for RPM and PWM display
+                    let stats_box = gtk4::Box::builder()
+                        .orientation(gtk4::Orientation::Horizontal)
+                        .spacing(12)
+                        .build();
+
+                    let rpm_placeholder = "-- RPM";
+                    let rpm_label = Label::builder()
+                        .label(rpm_placeholder)
+                        .css_classes(["title-3", "numeric"])
+                        .build();
+
+                    stats_box.append(&rpm_label);
+                    
+                    // Add PWM label if this fan has a controller
+                    let pwm_label = if has_pwm {
+                        let label = Label::builder()
+                            .label("--%")
+                            .css_classes(["title-3", "numeric", "dim-label"])
+                            .build();
+                        stats_box.append(&label);
+                        Some(label)
+                    } else {
+                        None
+                    };
+                    
+                    row.add_suffix(&stats_box);
+                    card.group.add(&row);
+                    has_rows = true;
+
+                    fans.borrow_mut().push(FanDisplay {
+                        path: fan_path,
+                        rpm_label,
+                        pwm_label,
+                    });
+                }
+
+                if has_rows {
+                    section.cards.borrow_mut().push(card);
+                }
             }
         }
 
-        list_box.append(&group);
+        let layout = Rc::new(CardLayout {
+            sections: vec![processor, graphics, case, storage],
+            show_hidden: Cell::new(false),
+        });
+        layout.arrange();
+        CardLayout::connect(&layout);
+        layout.refresh();
+
+        let layout_for_toggle = layout.clone();
+        show_hidden_btn.connect_toggled(move |btn| {
+            layout_for_toggle.show_hidden.set(btn.is_active());
+            layout_for_toggle.refresh();
+        });
+
+        // ================================================================
+        // Electrical Sensors Section (voltage, current, power, humidity)
+        // ================================================================
+        if !aux_sensors.is_empty() {
+            let aux_header = GtkBox::builder()
+                .orientation(Orientation::Horizontal)
+                .margin_top(12)
+                .margin_bottom(6)
+                .build();
+
+            let aux_section_label = Label::builder()
+                .label("Electrical Sensors")
+                .css_classes(["title-2"])
+                .hexpand(true)
+                .halign(gtk4::Align::Start)
+                .build();
+
+            let filter_names: Vec<&str> = AUX_FILTERS.iter().map(|(name, _)| *name).collect();
+            let filter_dropdown = DropDown::from_strings(&filter_names);
+            filter_dropdown.set_tooltip_text(Some("Filter by sensor type"));
+            filter_dropdown.set_valign(gtk4::Align::Center);
+
+            aux_header.append(&aux_section_label);
+            aux_header.append(&filter_dropdown);
+            list_box.append(&aux_header);
+
+            let aux_group = adw::PreferencesGroup::builder().build();
+
+            for (chip_name, sensor) in &aux_sensors {
+                let default_label = sensor.label.clone().unwrap_or_else(|| sensor.name.clone());
+                let row = adw::ActionRow::builder()
+                    .title(&format!("{} • {}", chip_name, default_label))
+                    .subtitle(&sensor.path)
+                    .build();
+
+                let value_label = Label::builder()
+                    .label(&format_aux_value(sensor.kind, sensor.value))
+                    .css_classes(["title-3", "numeric"])
+                    .build();
+
+                row.add_suffix(&value_label);
+                aux_group.add(&row);
+
+                aux.borrow_mut().push(AuxDisplay {
+                    path: sensor.path.clone(),
+                    kind: sensor.kind,
+                    row,
+                    value_label,
+                });
+            }
+
+            let aux_for_filter = aux.clone();
+            filter_dropdown.connect_selected_notify(move |dropdown| {
+                let filter = AUX_FILTERS.get(dropdown.selected() as usize).and_then(|(_, kind)| *kind);
+                for display in aux_for_filter.borrow().iter() {
+                    display.row.set_visible(filter.is_none_or(|kind| kind == display.kind));
+                }
+            });
+
+            list_box.append(&aux_group);
+        }
+
         scroll.set_child(Some(&list_box));
         container.append(&scroll);
 
@@ -408,6 +819,7 @@ impl SensorsPage {
             container, 
             sensors,
             fans,
+            aux,
             gpu_displays,
             cpu_display,
         };
@@ -422,6 +834,7 @@ impl SensorsPage {
     fn setup_live_updates(&self) {
         let sensors = self.sensors.clone();
         let fans = self.fans.clone();
+        let aux = self.aux.clone();
         let gpu_displays = self.gpu_displays.clone();
         let cpu_display = self.cpu_display.clone();
         let container = self.container.clone();
@@ -429,6 +842,11 @@ impl SensorsPage {
         glib::timeout_add_local(
             Duration::from_millis(get_poll_interval_ms()),
             move || {
+                // Page was replaced after a hardware change
+                if container.parent().is_none() {
+                    return glib::ControlFlow::Break;
+                }
+
                 // PERFORMANCE: Only update if this page is visible (mapped to screen)
                 if !container.is_mapped() {
                     return glib::ControlFlow::Continue;
@@ -442,6 +860,9 @@ impl SensorsPage {
                 
                 // Update fan sensors
                 Self::update_fan_readings(&fans);
+
+                // Update voltage/current/power/humidity sensors
+                Self::update_aux_readings(&aux);
                 
                 // Update GPU sensors
                 Self::update_gpu_readings(&gpu_displays);
@@ -467,15 +888,28 @@ impl SensorsPage {
         };
         
         for sensor in sensors.borrow().iter() {
-            let temp = cached_temps.temperatures.iter()
-                .find(|t| t.path == sensor.path)
-                .map(|t| t.temp_celsius);
+            let reading = cached_temps.temperatures.iter()
+                .find(|t| t.path == sensor.path);
             
-            if let Some(temp) = temp {
-                let new_text = hf_core::display::format_temp_precise(temp);
+            if let Some(reading) = reading {
+                let new_text = hf_core::display::format_temp_precise(reading.temp_celsius);
                 if sensor.temp_label.text() != new_text {
                     sensor.temp_label.set_label(&new_text);
                 }
+
+                // Only driver limits colour this page; fixed bands would flag idle drives and VRMs
+                let class = if reading.limits.is_empty() {
+                    None
+                } else {
+                    super::temp_monitor::limit_css_class(reading.temp_celsius, &reading.limits)
+                };
+                for style in ["warning", "error"] {
+                    if Some(style) == class {
+                        sensor.temp_label.add_css_class(style);
+                    } else {
+                        sensor.temp_label.remove_css_class(style);
+                    }
+                }
             }
         }
     }
@@ -515,6 +949,23 @@ impl SensorsPage {
         }
     }
 
+    /// Read and display current voltage/current/power/humidity values
+    /// PERFORMANCE: Uses cached sensor data from runtime (no fallback IPC calls)
+    fn update_aux_readings(aux: &Rc<RefCell<Vec<AuxDisplay>>>) {
+        let Some(cached_data) = crate::runtime::get_sensors() else {
+            return; // No data yet, skip this update
+        };
+
+        for display in aux.borrow().iter() {
+            if let Some(reading) = cached_data.aux.iter().find(|a| a.path == display.path) {
+                let new_text = format_aux_value(reading.kind, reading.value);
+                if display.value_label.text() != new_text {
+                    display.value_label.set_label(&new_text);
+                }
+            }
+        }
+    }
+
     /// Update GPU readings
     /// PERFORMANCE: Uses cached GPU data from runtime instead of blocking I/O
     fn update_gpu_readings(gpu_displays: &Rc<RefCell<Vec<GpuDisplay>>>) {
@@ -641,6 +1092,115 @@ impl SensorsPage {
 
         dialog.present();
     }
+
+    /// Show dialog to configure a sensor's spike rejection, median, and EMA filters
+    fn show_filter_dialog(btn: &Button, sensor_path: &str, sensor_name: &str) {
+        use hf_core::constants::sensor_filter::{MAX_MEDIAN_WINDOW, MAX_SPIKE_THRESHOLD, MIN_EMA_ALPHA};
+
+        let dialog = adw::Window::builder()
+            .title("Filter Readings")
+            .default_width(460)
+            .default_height(400)
+            .modal(true)
+            .build();
+        if let Some(window) = btn.root().and_then(|r| r.downcast::<gtk4::Window>().ok()) {
+            dialog.set_transient_for(Some(&window));
+        }
+
+        let header = adw::HeaderBar::new();
+        let save_btn = Button::builder()
+            .label("Save")
+            .css_classes(["suggested-action"])
+            .build();
+        header.pack_end(&save_btn);
+
+        let filter = hf_core::get_sensor_filter(sensor_path)
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| hf_core::SensorFilter::new(sensor_path));
+
+        let page = adw::PreferencesPage::new();
+        let group = adw::PreferencesGroup::builder()
+            .title(sensor_name)
+            .description("Applied by the daemon before curve evaluation and in the graphs")
+            .build();
+        page.add(&group);
+
+        let spike_row = adw::SpinRow::builder()
+            .title("Spike Rejection")
+            .subtitle("Ignore single-sample jumps larger than this (°C, 0 = off)")
+            .adjustment(&gtk4::Adjustment::new(
+                filter.spike_threshold.unwrap_or(0.0) as f64,
+                0.0,
+                MAX_SPIKE_THRESHOLD as f64,
+                1.0,
+                5.0,
+                0.0,
+            ))
+            .digits(1)
+            .build();
+        let median_row = adw::SpinRow::builder()
+            .title("Median Filter")
+            .subtitle("Median of the last N samples (1 = off)")
+            .adjustment(&gtk4::Adjustment::new(
+                filter.median_window.unwrap_or(1) as f64,
+                1.0,
+                MAX_MEDIAN_WINDOW as f64,
+                1.0,
+                2.0,
+                0.0,
+            ))
+            .build();
+        let ema_row = adw::SpinRow::builder()
+            .title("Smoothing")
+            .subtitle("Weight of each new sample (1.00 = off, lower is smoother)")
+            .adjustment(&gtk4::Adjustment::new(
+                filter.ema_alpha.unwrap_or(1.0) as f64,
+                MIN_EMA_ALPHA as f64,
+                1.0,
+                0.05,
+                0.1,
+                0.0,
+            ))
+            .digits(2)
+            .build();
+        group.add(&spike_row);
+        group.add(&median_row);
+        group.add(&ema_row);
+
+        let toast_overlay = adw::ToastOverlay::new();
+        toast_overlay.set_child(Some(&page));
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&header);
+        toolbar.set_content(Some(&toast_overlay));
+        dialog.set_content(Some(&toolbar));
+
+        let dialog_for_save = dialog.clone();
+        let sensor_path = sensor_path.to_string();
+        save_btn.connect_clicked(move |_| {
+            let spike = spike_row.value() as f32;
+            let median = median_row.value() as u32;
+            let alpha = ema_row.value() as f32;
+            let filter = hf_core::SensorFilter {
+                path: sensor_path.clone(),
+                ema_alpha: (alpha < 1.0).then_some(alpha),
+                median_window: (median > 1).then_some(median),
+                spike_threshold: (spike > 0.0).then_some(spike),
+            };
+
+            if let Err(e) = hf_core::set_sensor_filter(filter) {
+                toast_overlay.add_toast(adw::Toast::new(&e.to_string()));
+                return;
+            }
+            if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Settings) {
+                tracing::debug!("Failed to signal daemon reload: {}", e);
+            }
+            dialog_for_save.close();
+        });
+
+        dialog.present();
+    }
 }
 
 impl Default for SensorsPage {
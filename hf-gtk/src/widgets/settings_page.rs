@@ -1,6 +1,7 @@
 //! Settings Page
 //!
-//! Application settings with General, Display, and About sections.
+//! Application settings with General, Display, Notifications, Profile Schedule,
+//! and About sections.
 //! Settings are staged in memory and saved when Apply is clicked.
 //! Tracks dirty state and prompts user on navigation if unsaved.
 
@@ -241,6 +242,17 @@ impl SettingsPage {
                 .build()
         };
         btn_box.append(&service_btn);
+
+        // Daemon log - readable without journal access
+        let log_btn = Button::builder()
+            .icon_name("utilities-terminal-symbolic")
+            .tooltip_text("Daemon log")
+            .css_classes(["flat"])
+            .build();
+        log_btn.connect_clicked(|btn| {
+            super::daemon_log_dialog::show_daemon_log_dialog(btn);
+        });
+        btn_box.prepend(&log_btn);
         
         service_row.add_suffix(&btn_box);
         general_group.add(&service_row);
@@ -440,6 +452,46 @@ impl SettingsPage {
         });
         general_group.add(&poll_row);
 
+        // Adaptive daemon polling
+        let adaptive = settings.general.adaptive_polling;
+        let adaptive_row = adw::SwitchRow::builder()
+            .title("Adaptive Polling")
+            .subtitle(format!(
+                "Daemon polls every {}ms while temperatures change, backing off to {}ms when stable",
+                adaptive.min_interval_ms, adaptive.max_interval_ms
+            ))
+            .build();
+        adaptive_row.set_active(adaptive.enabled);
+        let pending_for_adaptive = pending_settings.clone();
+        let dirty_for_adaptive = is_dirty.clone();
+        let apply_btn_for_adaptive = apply_btn.clone();
+        adaptive_row.connect_active_notify(move |row| {
+            pending_for_adaptive.borrow_mut().general.adaptive_polling.enabled = row.is_active();
+            *dirty_for_adaptive.borrow_mut() = true;
+            apply_btn_for_adaptive.set_sensitive(true);
+        });
+        general_group.add(&adaptive_row);
+
+        // Full speed on hwmon fan/temperature alarms
+        let alarm_boost = settings.general.alarm_boost;
+        let alarm_boost_row = adw::SwitchRow::builder()
+            .title("Full Speed on Hardware Alarms")
+            .subtitle(format!(
+                "Run every fan at 100% while a sensor chip reports a fan or temperature alarm, and for {}s after it clears",
+                alarm_boost.cooldown_secs
+            ))
+            .build();
+        alarm_boost_row.set_active(alarm_boost.enabled);
+        let pending_for_alarm_boost = pending_settings.clone();
+        let dirty_for_alarm_boost = is_dirty.clone();
+        let apply_btn_for_alarm_boost = apply_btn.clone();
+        alarm_boost_row.connect_active_notify(move |row| {
+            pending_for_alarm_boost.borrow_mut().general.alarm_boost.enabled = row.is_active();
+            *dirty_for_alarm_boost.borrow_mut() = true;
+            apply_btn_for_alarm_boost.set_sensitive(true);
+        });
+        general_group.add(&alarm_boost_row);
+
         // Default page on startup
         let default_page_row = adw::ComboRow::builder()
             .title("Default Page")
@@ -684,6 +736,7 @@ impl SettingsPage {
         frame_rate_scale.set_range(0.0, 5.0);
         frame_rate_scale.set_increments(1.0, 1.0);
         frame_rate_scale.set_round_digits(0);
+        frame_rate_scale.update_property(&[gtk4::accessible::Property::Label("Graph frame rate")]);
         
         // Add marks
         frame_rate_scale.add_mark(0.0, gtk4::PositionType::Bottom, Some("24"));
@@ -846,6 +899,86 @@ impl SettingsPage {
 
         content.append(&display_group);
 
+        let mark_dirty: Rc<dyn Fn()> = {
+            let dirty = is_dirty.clone();
+            let apply_btn = apply_btn.clone();
+            Rc::new(move || {
+                *dirty.borrow_mut() = true;
+                apply_btn.set_sensitive(true);
+            })
+        };
+
+        // ================================================================
+        // Sensor Colors Section
+        // ================================================================
+        content.append(&Self::build_sensor_colors_group(&pending_settings, mark_dirty.clone()));
+
+        // ================================================================
+        // Notifications Section
+        // ================================================================
+        let notifications_group = adw::PreferencesGroup::builder()
+            .title("Notifications")
+            .description("Desktop notifications for important events")
+            .build();
+
+        let notification_toggles: [(&str, &str, fn(&mut hf_core::NotificationSettings) -> &mut bool); 4] = [
+            ("Daemon Unreachable", "Notify when the fan daemon stops responding", |n| &mut n.daemon_down),
+            ("Thermal Threshold", "Notify when a sensor exceeds the temperature threshold", |n| &mut n.thermal_threshold),
+            ("Fan Failure", "Notify when a fan stops spinning while it should be running", |n| &mut n.fan_failure),
+            ("Fingerprint Drift", "Notify when fan bindings no longer match the hardware", |n| &mut n.fingerprint_drift),
+        ];
+        let mut initial_notifications = settings.notifications.clone();
+        for (title, subtitle, field) in notification_toggles {
+            let row = adw::SwitchRow::builder()
+                .title(title)
+                .subtitle(subtitle)
+                .build();
+            row.set_active(*field(&mut initial_notifications));
+
+            let pending_for_notify = pending_settings.clone();
+            let dirty_for_notify = is_dirty.clone();
+            let apply_btn_for_notify = apply_btn.clone();
+            row.connect_active_notify(move |row| {
+                *field(&mut pending_for_notify.borrow_mut().notifications) = row.is_active();
+                *dirty_for_notify.borrow_mut() = true;
+                apply_btn_for_notify.set_sensitive(true);
+            });
+            notifications_group.add(&row);
+        }
+
+        // Thermal threshold (always configured in Celsius)
+        let threshold_row = adw::SpinRow::builder()
+            .title("Temperature Threshold")
+            .subtitle("Sensor temperature (°C) that triggers a thermal notification")
+            .adjustment(&gtk4::Adjustment::new(
+                settings.notifications.thermal_threshold_celsius as f64,
+                40.0,
+                hf_core::constants::limits::MAX_CURVE_TEMPERATURE as f64,
+                1.0,
+                5.0,
+                0.0,
+            ))
+            .digits(0)
+            .numeric(true)
+            .build();
+
+        let pending_for_threshold = pending_settings.clone();
+        let dirty_for_threshold = is_dirty.clone();
+        let apply_btn_for_threshold = apply_btn.clone();
+        threshold_row.connect_value_notify(move |row| {
+            pending_for_threshold.borrow_mut().notifications.thermal_threshold_celsius = row.value() as f32;
+            *dirty_for_threshold.borrow_mut() = true;
+            apply_btn_for_threshold.set_sensitive(true);
+        });
+        notifications_group.add(&threshold_row);
+
+        content.append(&notifications_group);
+
+        // ================================================================
+        // Profile Schedule Section
+        // ================================================================
+        content.append(&Self::build_schedule_group(&settings, &pending_settings, mark_dirty));
+
         // ================================================================
         // Export/Import Section
         // ================================================================
@@ -902,6 +1035,67 @@ impl SettingsPage {
 
         content.append(&export_group);
 
+        // ================================================================
+        // Controller Filter Section
+        // ================================================================
+        let filter_group = adw::PreferencesGroup::builder()
+            .title("Controller Filter")
+            .description("Chips and PWM outputs that must never be driven, such as PSU fans. Exclude outputs from the Fan Pairing page")
+            .build();
+
+        let filter_mode_row = adw::ComboRow::builder()
+            .title("Mode")
+            .subtitle("Allowlist: only listed outputs are controlled (add them with hyperfan hardware filter-add)")
+            .build();
+        filter_mode_row.set_model(Some(&gtk4::StringList::new(&["Blacklist", "Allowlist"])));
+        filter_mode_row.set_selected(match settings.controller_filter.mode {
+            hf_core::ControllerFilterMode::Blacklist => 0,
+            hf_core::ControllerFilterMode::Allowlist => 1,
+        });
+        filter_mode_row.connect_selected_notify(|row| {
+            let mode = match row.selected() {
+                1 => hf_core::ControllerFilterMode::Allowlist,
+                _ => hf_core::ControllerFilterMode::Blacklist,
+            };
+            if let Err(e) = hf_core::set_controller_filter_mode(mode) {
+                error!("Failed to save controller filter mode: {}", e);
+            } else if let Err(e) = hf_core::daemon_reload_config() {
+                warn!("Failed to signal daemon reload: {}", e);
+            }
+        });
+        filter_group.add(&filter_mode_row);
+
+        for entry in &settings.controller_filter.entries {
+            let entry_row = adw::ActionRow::builder()
+                .title(&entry.label)
+                .subtitle(entry.channel.as_deref().unwrap_or("Whole chip"))
+                .build();
+            let remove_btn = Button::builder()
+                .icon_name("user-trash-symbolic")
+                .valign(gtk4::Align::Center)
+                .tooltip_text("Remove from the filter")
+                .css_classes(["flat"])
+                .build();
+            entry_row.add_suffix(&remove_btn);
+
+            let group_for_remove = filter_group.clone();
+            let row_for_remove = entry_row.clone();
+            let chip_id = entry.chip_id.clone();
+            let channel = entry.channel.clone();
+            remove_btn.connect_clicked(move |_| {
+                if let Err(e) = hf_core::remove_controller_filter_entry(&chip_id, channel.as_deref()) {
+                    error!("Failed to remove controller filter entry: {}", e);
+                    return;
+                }
+                if let Err(e) = hf_core::daemon_reload_config() {
+                    warn!("Failed to signal daemon reload: {}", e);
+                }
+                group_for_remove.remove(&row_for_remove);
+            });
+            filter_group.add(&entry_row);
+        }
+        content.append(&filter_group);
+
         // ================================================================
         // Advanced Section (DANGEROUS)
         // ================================================================
@@ -939,6 +1133,46 @@ impl SettingsPage {
         });
 
         advanced_group.add(&ec_row);
+
+        // Revert raw EC writes that stop a fan or overheat the machine
+        let ec_guard_row = adw::SwitchRow::builder()
+            .title("EC Write Guard")
+            .subtitle("Watch fans and temperatures for 10 seconds after each register write and undo it if they go out of bounds")
+            .active(settings.advanced.ec_write_guard)
+            .build();
+        ec_guard_row.connect_active_notify(move |row| {
+            let enabled = row.is_active();
+            if let Err(e) = hf_core::update_setting(|s| s.advanced.ec_write_guard = enabled) {
+                error!("Failed to save EC write guard setting: {}", e);
+            }
+        });
+        advanced_group.add(&ec_guard_row);
+
+        let ec_dry_run_row = adw::SwitchRow::builder()
+            .title("EC Dry Run")
+            .subtitle("Log EC writes in the daemon log without performing them")
+            .active(settings.advanced.ec_dry_run)
+            .build();
+        ec_dry_run_row.connect_active_notify(move |row| {
+            let enabled = row.is_active();
+            if let Err(e) = hf_core::update_setting(|s| s.advanced.ec_dry_run = enabled) {
+                error!("Failed to save EC dry run setting: {}", e);
+            }
+        });
+        advanced_group.add(&ec_dry_run_row);
+
+        let gpu_power_row = adw::SwitchRow::builder()
+            .title("GPU Power Limits")
+            .subtitle("Allow changing graphics card power limits from the dashboard")
+            .active(settings.advanced.gpu_power_limits_enabled)
+            .build();
+        gpu_power_row.connect_active_notify(move |row| {
+            let enabled = row.is_active();
+            if let Err(e) = hf_core::update_setting(|s| s.advanced.gpu_power_limits_enabled = enabled) {
+                error!("Failed to save GPU power limit setting: {}", e);
+            }
+        });
+        advanced_group.add(&gpu_power_row);
         content.append(&advanced_group);
 
         // ================================================================
@@ -948,6 +1182,31 @@ impl SettingsPage {
             .title("Configuration")
             .build();
 
+        // File format - TOML keeps stable ordering and comments for dotfiles repos
+        let format_row = adw::ComboRow::builder()
+            .title("File Format")
+            .subtitle("TOML keeps your comments and diffs cleanly in version control")
+            .build();
+        let format_options = gtk4::StringList::new(&["JSON", "TOML"]);
+        format_row.set_model(Some(&format_options));
+        format_row.set_selected(match settings.general.config_format {
+            hf_core::ConfigFormat::Json => 0,
+            hf_core::ConfigFormat::Toml => 1,
+        });
+
+        let pending_for_format = pending_settings.clone();
+        let dirty_for_format = is_dirty.clone();
+        let apply_btn_for_format = apply_btn.clone();
+        format_row.connect_selected_notify(move |row| {
+            pending_for_format.borrow_mut().general.config_format = match row.selected() {
+                1 => hf_core::ConfigFormat::Toml,
+                _ => hf_core::ConfigFormat::Json,
+            };
+            *dirty_for_format.borrow_mut() = true;
+            apply_btn_for_format.set_sensitive(true);
+        });
+        config_group.add(&format_row);
+
         // Settings path - clickable to open file explorer
         let settings_path = hf_core::get_settings_path().ok();
         let path_str = settings_path.as_ref()
@@ -998,7 +1257,13 @@ impl SettingsPage {
             // Show loading state
             btn.set_sensitive(false);
             btn.set_label("Applying...");
-            let settings_to_save = pending_for_save.borrow().clone();
+            let mut settings_to_save = pending_for_save.borrow().clone();
+            // The active profile is switched by the tray and daemon schedule, not this page
+            // The controller filter is saved as soon as it is edited
+            if let Ok(current) = hf_core::load_settings() {
+                settings_to_save.active_profile = current.active_profile;
+                settings_to_save.controller_filter = current.controller_filter;
+            }
             
             // Check if window_manager changed - need to restart
             let current_wm = hf_core::load_settings()
@@ -1110,7 +1375,11 @@ impl SettingsPage {
 
     /// Apply pending settings (save to disk)
     pub fn apply_settings(&self) {
-        let settings_to_save = self.pending_settings.borrow().clone();
+        let mut settings_to_save = self.pending_settings.borrow().clone();
+        // The active profile is switched by the tray and daemon schedule, not this page
+        if let Ok(current) = hf_core::load_settings() {
+            settings_to_save.active_profile = current.active_profile;
+        }
         
         // Check if window_manager changed - need to restart
         let current_wm = hf_core::load_settings()
@@ -1230,6 +1499,384 @@ impl SettingsPage {
     }
 
     /// Format frame rate value for display
+    /// Profile schedule editor: enable switch, fallback profile, and time windows
+    /// Colors for sensors and fans, and theme preset export/import
+    fn build_sensor_colors_group(
+        pending_settings: &Rc<RefCell<hf_core::AppSettings>>,
+        mark_dirty: Rc<dyn Fn()>,
+    ) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title("Sensor Colors")
+            .description("Colors used for a sensor or fan on the dashboard, graphs and curve overlays. \
+                          They are lightened or darkened to stay readable in dark and light styles.")
+            .build();
+
+        // Sensors are listed when expanded: readings may not exist yet when the page is built
+        let sensors_row = adw::ExpanderRow::builder()
+            .title("Sensors and Fans")
+            .subtitle("Unassigned sources use the accent color or the next graph color")
+            .build();
+        group.add(&sensors_row);
+
+        let rows: Rc<RefCell<Vec<adw::ActionRow>>> = Rc::new(RefCell::new(Vec::new()));
+        let populate: Rc<dyn Fn()> = {
+            let sensors_row = sensors_row.clone();
+            let pending = pending_settings.clone();
+            let mark_dirty = mark_dirty.clone();
+            Rc::new(move || {
+                for row in rows.borrow_mut().drain(..) {
+                    sensors_row.remove(&row);
+                }
+
+                let mut sources: Vec<(String, String)> = Vec::new();
+                if let Some(data) = crate::runtime::get_sensors() {
+                    sources.extend(data.temperatures.iter().map(|t| (t.path.clone(), t.label.clone())));
+                    sources.extend(data.fans.iter().map(|f| (f.path.clone(), f.label.clone())));
+                }
+                // Keep colors of sources that are missing right now editable
+                for path in pending.borrow().display.sensor_colors.keys() {
+                    if !sources.iter().any(|(p, _)| p == path) {
+                        sources.push((path.clone(), path.clone()));
+                    }
+                }
+
+                for (path, label) in sources {
+                    let row = Self::sensor_color_row(&path, &label, &pending, mark_dirty.clone());
+                    sensors_row.add_row(&row);
+                    rows.borrow_mut().push(row);
+                }
+            })
+        };
+        let populate_on_expand = populate.clone();
+        sensors_row.connect_expanded_notify(move |row| {
+            if row.is_expanded() {
+                populate_on_expand();
+            }
+        });
+
+        // Export preset
+        let export_row = adw::ActionRow::builder()
+            .title("Export Theme Preset")
+            .subtitle("Save the sensor colors to a JSON file")
+            .activatable(true)
+            .build();
+        let export_btn = Button::builder()
+            .icon_name("document-save-symbolic")
+            .valign(gtk4::Align::Center)
+            .tooltip_text("Export theme preset")
+            .css_classes(["flat"])
+            .build();
+        export_row.add_suffix(&export_btn);
+        export_row.set_activatable_widget(Some(&export_btn));
+        let pending_for_export = pending_settings.clone();
+        export_btn.connect_clicked(move |btn| {
+            let colors = pending_for_export.borrow().display.sensor_colors.clone();
+            Self::show_theme_export_dialog(btn, colors);
+        });
+        group.add(&export_row);
+
+        // Import preset: merged into the staged settings, saved on Apply
+        let import_row = adw::ActionRow::builder()
+            .title("Import Theme Preset")
+            .subtitle("Load sensor colors from a JSON file")
+            .activatable(true)
+            .build();
+        let import_btn = Button::builder()
+            .icon_name("document-open-symbolic")
+            .valign(gtk4::Align::Center)
+            .tooltip_text("Import theme preset")
+            .css_classes(["flat"])
+            .build();
+        import_row.add_suffix(&import_btn);
+        import_row.set_activatable_widget(Some(&import_btn));
+        let pending_for_import = pending_settings.clone();
+        import_btn.connect_clicked(move |btn| {
+            let window = btn.root().and_downcast::<gtk4::Window>();
+            let dialog = gtk4::FileDialog::builder()
+                .title("Import Theme Preset")
+                .filters(&Self::json_filters())
+                .build();
+
+            let pending = pending_for_import.clone();
+            let mark_dirty = mark_dirty.clone();
+            let populate = populate.clone();
+            let sensors_row = sensors_row.clone();
+            dialog.open(window.as_ref(), gio::Cancellable::NONE, move |result| {
+                let Some(path) = result.ok().and_then(|file| file.path()) else { return };
+                match hf_core::ThemePreset::load(&path) {
+                    Ok(preset) => {
+                        info!("Imported theme preset '{}' ({} colors)", preset.name, preset.sensor_colors.len());
+                        pending.borrow_mut().display.sensor_colors.extend(preset.sensor_colors);
+                        mark_dirty();
+                        if sensors_row.is_expanded() {
+                            populate();
+                        }
+                    }
+                    Err(e) => error!("Failed to import theme preset: {}", e),
+                }
+            });
+        });
+        group.add(&import_row);
+
+        group
+    }
+
+    /// Color picker row of one sensor or fan
+    fn sensor_color_row(
+        path: &str,
+        label: &str,
+        pending_settings: &Rc<RefCell<hf_core::AppSettings>>,
+        mark_dirty: Rc<dyn Fn()>,
+    ) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(label)
+            .subtitle(path)
+            .build();
+
+        let assigned = pending_settings.borrow().display.sensor_colors.get(path)
+            .and_then(|hex| hf_core::parse_hex_color(hex));
+        let to_rgba = |(r, g, b): (u8, u8, u8)| {
+            gtk4::gdk::RGBA::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0)
+        };
+        let default_rgba = {
+            let (r, g, b, _) = super::curve_card::theme_colors::accent_color();
+            gtk4::gdk::RGBA::new(r as f32, g as f32, b as f32, 1.0)
+        };
+
+        let button = gtk4::ColorDialogButton::builder()
+            .dialog(&gtk4::ColorDialog::builder().with_alpha(false).build())
+            .rgba(&assigned.map(to_rgba).unwrap_or(default_rgba))
+            .valign(gtk4::Align::Center)
+            .build();
+        button.update_property(&[gtk4::accessible::Property::Label(&format!("{} color", label))]);
+
+        let reset_btn = Button::builder()
+            .icon_name("edit-clear-symbolic")
+            .valign(gtk4::Align::Center)
+            .tooltip_text("Use the default color")
+            .css_classes(["flat"])
+            .sensitive(assigned.is_some())
+            .build();
+        reset_btn.update_property(&[gtk4::accessible::Property::Label("Use the default color")]);
+
+        // Resetting moves the button back to the default color without assigning it
+        let resetting = Rc::new(std::cell::Cell::new(false));
+
+        let pending_for_pick = pending_settings.clone();
+        let path_for_pick = path.to_string();
+        let reset_for_pick = reset_btn.clone();
+        let resetting_for_pick = resetting.clone();
+        let dirty_for_pick = mark_dirty.clone();
+        button.connect_rgba_notify(move |button| {
+            if resetting_for_pick.get() {
+                return;
+            }
+            let rgba = button.rgba();
+            let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+            let hex = format!("#{:02x}{:02x}{:02x}", channel(rgba.red()), channel(rgba.green()), channel(rgba.blue()));
+            pending_for_pick.borrow_mut().display.sensor_colors.insert(path_for_pick.clone(), hex);
+            reset_for_pick.set_sensitive(true);
+            dirty_for_pick();
+        });
+
+        let pending_for_reset = pending_settings.clone();
+        let path_for_reset = path.to_string();
+        let button_for_reset = button.clone();
+        reset_btn.connect_clicked(move |btn| {
+            pending_for_reset.borrow_mut().display.sensor_colors.remove(&path_for_reset);
+            resetting.set(true);
+            button_for_reset.set_rgba(&default_rgba);
+            resetting.set(false);
+            btn.set_sensitive(false);
+            mark_dirty();
+        });
+
+        row.add_suffix(&button);
+        row.add_suffix(&reset_btn);
+        row
+    }
+
+    fn show_theme_export_dialog(btn: &Button, sensor_colors: std::collections::BTreeMap<String, String>) {
+        let window = btn.root().and_downcast::<gtk4::Window>();
+
+        let dialog = gtk4::FileDialog::builder()
+            .title("Export Theme Preset")
+            .initial_name("hyperfan-theme.json")
+            .filters(&Self::json_filters())
+            .build();
+
+        dialog.save(window.as_ref(), gio::Cancellable::NONE, move |result| {
+            let Some(path) = result.ok().and_then(|file| file.path()) else { return };
+            let preset = hf_core::ThemePreset {
+                name: path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+                sensor_colors,
+            };
+            match preset.save(&path) {
+                Ok(()) => info!("Exported theme preset to {:?}", path),
+                Err(e) => error!("Failed to export theme preset: {}", e),
+            }
+        });
+    }
+
+    fn json_filters() -> gio::ListStore {
+        let filter = gtk4::FileFilter::new();
+        filter.add_pattern("*.json");
+        filter.set_name(Some("JSON files"));
+
+        let filters = gio::ListStore::new::<gtk4::FileFilter>();
+        filters.append(&filter);
+        filters
+    }
+
+    fn build_schedule_group(
+        settings: &hf_core::AppSettings,
+        pending_settings: &Rc<RefCell<hf_core::AppSettings>>,
+        mark_dirty: Rc<dyn Fn()>,
+    ) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title("Profile Schedule")
+            .description("Switch curve profiles by time of day. Manual switches last until the next window starts or ends.")
+            .build();
+
+        let profiles = settings.available_profiles();
+        let profile_names: Vec<&str> = profiles.iter().map(|p| p.as_str()).collect();
+        let schedule = &settings.profile_schedule;
+
+        let enabled_row = adw::SwitchRow::builder()
+            .title("Scheduled Switching")
+            .subtitle("Let the daemon change the active profile on schedule")
+            .active(schedule.enabled)
+            .build();
+        let pending_for_enabled = pending_settings.clone();
+        let dirty_for_enabled = mark_dirty.clone();
+        enabled_row.connect_active_notify(move |row| {
+            pending_for_enabled.borrow_mut().profile_schedule.enabled = row.is_active();
+            dirty_for_enabled();
+        });
+        group.add(&enabled_row);
+
+        let default_row = adw::ComboRow::builder()
+            .title("Outside Scheduled Times")
+            .subtitle("Profile used when no time window applies")
+            .model(&gtk4::StringList::new(&profile_names))
+            .build();
+        let default_idx = profiles.iter().position(|p| *p == schedule.default_profile).unwrap_or(0);
+        default_row.set_selected(default_idx as u32);
+        let pending_for_default = pending_settings.clone();
+        let dirty_for_default = mark_dirty.clone();
+        let profiles_for_default = profiles.clone();
+        default_row.connect_selected_notify(move |row| {
+            if let Some(profile) = profiles_for_default.get(row.selected() as usize) {
+                pending_for_default.borrow_mut().profile_schedule.default_profile = profile.clone();
+                dirty_for_default();
+            }
+        });
+        group.add(&default_row);
+
+        // New window form
+        let add_row = adw::ExpanderRow::builder()
+            .title("Add Time Window")
+            .build();
+        let profile_row = adw::ComboRow::builder()
+            .title("Profile")
+            .model(&gtk4::StringList::new(&profile_names))
+            .build();
+        let start_row = adw::EntryRow::builder().title("Start (HH:MM)").text("22:00").build();
+        let end_row = adw::EntryRow::builder().title("End (HH:MM)").text("08:00").build();
+        let days_row = adw::EntryRow::builder()
+            .title("Days (e.g. mon,tue,fri - empty for every day)")
+            .build();
+        let add_btn = Button::builder()
+            .label("Add")
+            .css_classes(["suggested-action"])
+            .halign(gtk4::Align::End)
+            .margin_top(6)
+            .margin_bottom(6)
+            .margin_end(6)
+            .build();
+        add_row.add_row(&profile_row);
+        add_row.add_row(&start_row);
+        add_row.add_row(&end_row);
+        add_row.add_row(&days_row);
+        add_row.add_row(&add_btn);
+        group.add(&add_row);
+
+        for entry in &schedule.entries {
+            group.add(&Self::schedule_entry_row(entry, &group, pending_settings, mark_dirty.clone()));
+        }
+
+        let group_for_add = group.clone();
+        let pending_for_add = pending_settings.clone();
+        let add_row_for_add = add_row.clone();
+        add_btn.connect_clicked(move |_| {
+            let entry = hf_core::ScheduleEntry {
+                profile: profiles.get(profile_row.selected() as usize).cloned().unwrap_or_default(),
+                start: start_row.text().trim().to_string(),
+                end: end_row.text().trim().to_string(),
+                days: days_row.text()
+                    .split(',')
+                    .map(|d| d.trim().to_ascii_lowercase())
+                    .filter(|d| !d.is_empty())
+                    .collect(),
+            };
+            if let Err(e) = entry.validate() {
+                add_row_for_add.set_subtitle(&e);
+                add_row_for_add.add_css_class("error");
+                return;
+            }
+            add_row_for_add.set_subtitle("");
+            add_row_for_add.remove_css_class("error");
+
+            group_for_add.add(&Self::schedule_entry_row(&entry, &group_for_add, &pending_for_add, mark_dirty.clone()));
+            pending_for_add.borrow_mut().profile_schedule.entries.push(entry);
+            mark_dirty();
+        });
+
+        group
+    }
+
+    /// Row for one scheduled time window, with a remove button
+    fn schedule_entry_row(
+        entry: &hf_core::ScheduleEntry,
+        group: &adw::PreferencesGroup,
+        pending_settings: &Rc<RefCell<hf_core::AppSettings>>,
+        mark_dirty: Rc<dyn Fn()>,
+    ) -> adw::ActionRow {
+        let days = if entry.days.is_empty() {
+            "every day".to_string()
+        } else {
+            entry.days.join(", ")
+        };
+        let row = adw::ActionRow::builder()
+            .title(&entry.profile)
+            .subtitle(format!("{} – {} · {}", entry.start, entry.end, days))
+            .build();
+
+        let remove_btn = Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Remove time window")
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .build();
+        let entry = entry.clone();
+        let group = group.clone();
+        let pending = pending_settings.clone();
+        let row_for_remove = row.clone();
+        remove_btn.connect_clicked(move |_| {
+            let mut settings = pending.borrow_mut();
+            let entries = &mut settings.profile_schedule.entries;
+            if let Some(idx) = entries.iter().position(|e| *e == entry) {
+                entries.remove(idx);
+            }
+            drop(settings);
+            group.remove(&row_for_remove);
+            mark_dirty();
+        });
+        row.add_suffix(&remove_btn);
+
+        row
+    }
+
     fn format_frame_rate(fps: u32) -> String {
         if fps == 0 {
             "Native".to_string()
@@ -1321,41 +1968,18 @@ impl SettingsPage {
     }
 
     fn export_all_settings(path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
-        // Copy settings file
-        let settings_path = hf_core::get_settings_path()?;
-        let curves_path = hf_core::get_curves_path();
-        
-        // Read both files and combine into export
-        let settings_json = if settings_path.exists() {
-            std::fs::read_to_string(&settings_path)?
-        } else {
-            "{}".to_string()
-        };
-        
-        let curves_json = if curves_path.exists() {
-            std::fs::read_to_string(&curves_path)?
-        } else {
-            "{}".to_string()
-        };
-        
-        // Create combined export JSON
-        let export = format!(
-            r#"{{
-  "version": "{}",
-  "exported_at": {},
-  "settings": {},
-  "curves": {}
-}}"#,
-            env!("CARGO_PKG_VERSION"),
-            std::time::SystemTime::now()
+        // Export is always JSON, whichever format the config files use
+        let export = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "exported_at": std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .map(|d| d.as_secs())
                 .unwrap_or(0),
-            settings_json.trim(),
-            curves_json.trim()
-        );
+            "settings": hf_core::load_settings()?,
+            "curves": hf_core::load_curves()?,
+        });
 
-        std::fs::write(path, export)?;
+        std::fs::write(path, serde_json::to_string_pretty(&export)?)?;
         Ok(())
     }
 
@@ -1388,13 +2012,12 @@ impl SettingsPage {
             // Validate settings structure strictly
             Self::validate_settings_structure(settings_value)?;
             
-            let settings_path = hf_core::get_settings_path()?;
-            if let Some(parent) = settings_path.parent() {
-                std::fs::create_dir_all(parent)?;
+            // Older exports hold an empty object when there was no file
+            if settings_value.as_object().is_some_and(|obj| !obj.is_empty()) {
+                let settings: hf_core::AppSettings = serde_json::from_value(settings_value.clone())?;
+                hf_core::save_settings(&settings)?;
+                info!("Imported settings to {:?}", hf_core::get_settings_path()?);
             }
-            let settings_json = serde_json::to_string_pretty(settings_value)?;
-            std::fs::write(&settings_path, settings_json)?;
-            info!("Imported settings to {:?}", settings_path);
         }
         
         // Extract and validate curves if present
@@ -1402,13 +2025,11 @@ impl SettingsPage {
             // Validate curves structure strictly
             Self::validate_curves_structure(curves_value)?;
             
-            let curves_path = hf_core::get_curves_path();
-            if let Some(parent) = curves_path.parent() {
-                std::fs::create_dir_all(parent)?;
+            if curves_value.as_object().is_some_and(|obj| !obj.is_empty()) {
+                let store: hf_core::CurveStore = serde_json::from_value(curves_value.clone())?;
+                hf_core::save_curves(&store)?;
+                info!("Imported curves to {:?}", hf_core::get_curves_path());
             }
-            let curves_json = serde_json::to_string_pretty(curves_value)?;
-            std::fs::write(&curves_path, curves_json)?;
-            info!("Imported curves to {:?}", curves_path);
         }
         
         info!("Import complete - restart application to apply all settings");
@@ -1540,6 +2161,16 @@ impl SettingsPage {
             }
         }
         
+        if let Some(val) = obj.get("sensor_colors") {
+            let colors = val.as_object().ok_or("'display.sensor_colors' must be a JSON object")?;
+            for (path, color) in colors {
+                Self::validate_path_string(path, "display.sensor_colors")?;
+                if color.as_str().and_then(hf_core::parse_hex_color).is_none() {
+                    return Err("'display.sensor_colors' values must be '#rrggbb' colors".into());
+                }
+            }
+        }
+        
         Ok(())
     }
     
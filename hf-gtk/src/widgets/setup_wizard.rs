@@ -0,0 +1,772 @@
+//! First-Run Setup Assistant
+//!
+//! Walks a new user through everything Hyperfan needs before it controls any
+//! fan, as pages of an `adw::NavigationView`:
+//!
+//! 1. Daemon - install and start hyperfand (detection runs in the daemon)
+//! 2. Detection - find which PWM output drives which fan
+//! 3. Fans - confirm each detected pairing and pick the temperature it follows
+//! 4. Curve - choose the preset every confirmed fan starts on
+//! 5. Finish - review, then write settings and curves in one atomic step
+//!
+//! Nothing is saved before Finish; closing the assistant earlier leaves the
+//! config as it was, and it is shown again on the next start. Detection is a
+//! daemon job polled from a worker thread; going back from the detection page
+//! or closing the assistant cancels it. Passive mode only watches the fans,
+//! for systems where they must not stop.
+
+use gtk4::prelude::*;
+use gtk4::{Button, CheckButton, DropDown, Label, Orientation, ProgressBar};
+use gtk4::Box as GtkBox;
+use gtk4::glib;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use hf_core::{CurvePreset, FanMapping};
+
+/// How often the worker thread polls the daemon job
+const DETECTION_POLL: Duration = Duration::from_millis(500);
+
+/// Readiness checks after installing the daemon, 300ms apart
+const DAEMON_READY_ATTEMPTS: u32 = 10;
+
+/// Mappings below this confidence start unchecked on the fans page
+const CONFIRM_CONFIDENCE: f32 = 0.5;
+
+const ACTIVE_DESCRIPTION: &str = "Hyperfan will test each PWM controller to identify which fans they control.\n\nThis process will:\n• Set all fans to 100% speed\n• Wait 3 seconds for stabilization\n• Test each controller individually\n\nYour fans may speed up and slow down during this process.";
+
+const PASSIVE_DESCRIPTION: &str = "Hyperfan will watch how fan speeds follow the PWM changes your current curves or firmware make, without overriding any controller.\n\nThis takes a few minutes. Controllers that do not change while it watches cannot be matched.";
+
+/// Presets offered on the curve page
+const PRESETS: [(CurvePreset, &str); 3] = [
+    (CurvePreset::Quiet, "Low speeds until the system gets warm"),
+    (CurvePreset::Balanced, "A middle ground between noise and temperatures"),
+    (CurvePreset::Performance, "Keeps temperatures low at the cost of noise"),
+];
+
+/// A detected fan on the fans page
+struct FanChoice {
+    mapping: FanMapping,
+    name: String,
+    row: adw::ActionRow,
+    confirm: CheckButton,
+    source: DropDown,
+}
+
+/// State shared by the pages
+struct Inner {
+    window: adw::Window,
+    nav: adw::NavigationView,
+    mappings: RefCell<Vec<FanMapping>>,
+    job_id: RefCell<Option<u64>>,
+    choices: RefCell<Vec<FanChoice>>,
+    /// Temperature sources offered for every fan (path, display name)
+    sources: RefCell<Vec<(String, String)>>,
+    preset: Cell<CurvePreset>,
+    on_finished: RefCell<Option<Box<dyn Fn()>>>,
+}
+
+impl Inner {
+    /// Abort a running detection job (the daemon restores the original PWM state)
+    fn cancel_detection(&self) {
+        if let Some(id) = self.job_id.borrow_mut().take() {
+            if let Err(e) = hf_core::daemon_cancel_detection(id) {
+                tracing::warn!("Failed to cancel detection job {}: {}", id, e);
+            }
+        }
+    }
+}
+
+/// First-run setup assistant window
+pub struct SetupWizard {
+    inner: Rc<Inner>,
+}
+
+impl SetupWizard {
+    pub fn new() -> Self {
+        let window = adw::Window::builder()
+            .title("Set Up Hyperfan")
+            .default_width(560)
+            .default_height(640)
+            .modal(true)
+            .build();
+        let nav = adw::NavigationView::new();
+        window.set_content(Some(&nav));
+
+        let inner = Rc::new(Inner {
+            window,
+            nav,
+            mappings: RefCell::new(Vec::new()),
+            job_id: RefCell::new(None),
+            choices: RefCell::new(Vec::new()),
+            sources: RefCell::new(Vec::new()),
+            preset: Cell::new(CurvePreset::Balanced),
+            on_finished: RefCell::new(None),
+        });
+
+        let finish_page = build_finish_page(&inner);
+        let curve_page = build_curve_page(&inner, &finish_page);
+        let fans_page = build_fans_page(&inner, &curve_page);
+        let detection_page = build_detection_page(&inner, &fans_page);
+        let daemon_page = build_daemon_page(&inner, &detection_page);
+        inner.nav.add(&build_welcome_page(&inner, &daemon_page));
+
+        let inner_for_close = inner.clone();
+        inner.window.connect_close_request(move |_| {
+            inner_for_close.cancel_detection();
+            glib::Propagation::Proceed
+        });
+
+        Self { inner }
+    }
+
+    /// Run `callback` after the setup has been written
+    pub fn connect_finished<F: Fn() + 'static>(&self, callback: F) {
+        *self.inner.on_finished.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn present(&self) {
+        self.inner.window.present();
+    }
+
+    pub fn set_transient_for(&self, parent: &impl IsA<gtk4::Window>) {
+        self.inner.window.set_transient_for(Some(parent));
+    }
+}
+
+/// Navigation page with a header bar, scrolling content and a button bar
+fn wizard_page(title: &str, content: &impl IsA<gtk4::Widget>, buttons: &[&Button]) -> adw::NavigationPage {
+    let clamp = adw::Clamp::builder()
+        .maximum_size(520)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(12)
+        .margin_bottom(24)
+        .child(content)
+        .build();
+    let scroll = gtk4::ScrolledWindow::builder()
+        .vexpand(true)
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .child(&clamp)
+        .build();
+
+    let button_box = GtkBox::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(12)
+        .halign(gtk4::Align::Center)
+        .margin_top(12)
+        .margin_bottom(12)
+        .build();
+    for button in buttons {
+        button_box.append(*button);
+    }
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&adw::HeaderBar::new());
+    toolbar.set_content(Some(&scroll));
+    toolbar.add_bottom_bar(&button_box);
+    adw::NavigationPage::new(&toolbar, title)
+}
+
+fn pill_button(label: &str, suggested: bool) -> Button {
+    let button = Button::builder().label(label).css_classes(["pill"]).build();
+    if suggested {
+        button.add_css_class("suggested-action");
+    }
+    button
+}
+
+fn boxed_list() -> gtk4::ListBox {
+    gtk4::ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build()
+}
+
+fn build_welcome_page(inner: &Rc<Inner>, next: &adw::NavigationPage) -> adw::NavigationPage {
+    let status = adw::StatusPage::builder()
+        .icon_name("preferences-system-symbolic")
+        .title("Welcome to Hyperfan")
+        .description("This assistant installs the fan control daemon, finds out which controller drives which fan, and puts your fans on a curve.\n\nYour fan configuration is only saved when you finish.")
+        .build();
+
+    let skip_btn = pill_button("Skip", false);
+    let start_btn = pill_button("Get Started", true);
+    let page = wizard_page("Welcome", &status, &[&skip_btn, &start_btn]);
+    page.set_can_pop(false);
+
+    let window = inner.window.clone();
+    skip_btn.connect_clicked(move |_| window.close());
+    let nav = inner.nav.clone();
+    let next = next.clone();
+    start_btn.connect_clicked(move |_| nav.push(&next));
+    page
+}
+
+fn build_daemon_page(inner: &Rc<Inner>, next: &adw::NavigationPage) -> adw::NavigationPage {
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(18)
+        .build();
+    let intro = Label::builder()
+        .label("The hyperfand daemon runs as a system service: it reads the sensors, writes fan speeds and keeps your fans on their curves while Hyperfan is closed. Installing it asks for your password.")
+        .wrap(true)
+        .xalign(0.0)
+        .build();
+    content.append(&intro);
+
+    let list = boxed_list();
+    let service_row = adw::ActionRow::builder()
+        .title("Hyperfan Daemon")
+        .build();
+    let install_btn = Button::builder()
+        .label("Install")
+        .css_classes(["suggested-action"])
+        .valign(gtk4::Align::Center)
+        .build();
+    service_row.add_suffix(&install_btn);
+    list.append(&service_row);
+    content.append(&list);
+
+    let continue_btn = pill_button("Continue", true);
+    let page = wizard_page("Daemon", &content, &[&continue_btn]);
+
+    let update_status: Rc<dyn Fn() -> bool> = {
+        let service_row = service_row.clone();
+        let install_btn = install_btn.clone();
+        let continue_btn = continue_btn.clone();
+        Rc::new(move || {
+            let ready = hf_core::daemon_list_hardware().is_ok();
+            let subtitle = match hf_core::get_daemon_version() {
+                Ok(version) if ready => format!("Running v{}", version),
+                _ if hf_core::service::is_service_installed() => "Installed but not running".to_string(),
+                _ => "Not installed".to_string(),
+            };
+            service_row.set_subtitle(&subtitle);
+            install_btn.set_visible(!ready);
+            continue_btn.set_sensitive(ready);
+            ready
+        })
+    };
+
+    let update_on_show = update_status.clone();
+    page.connect_showing(move |_| {
+        update_on_show();
+    });
+
+    install_btn.connect_clicked(move |btn| {
+        btn.set_sensitive(false);
+        btn.set_label("Installing...");
+
+        let (tx, rx) = mpsc::channel::<Result<(), String>>();
+        thread::spawn(move || {
+            let _ = tx.send(hf_core::service::install_service());
+        });
+
+        let btn = btn.clone();
+        let update_status = update_status.clone();
+        let service_row = service_row.clone();
+        let attempts = Cell::new(0u32);
+        let installed = Cell::new(false);
+        glib::timeout_add_local(Duration::from_millis(300), move || {
+            if !installed.get() {
+                match rx.try_recv() {
+                    Ok(Ok(())) => installed.set(true),
+                    Ok(Err(e)) => {
+                        tracing::warn!("Daemon installation failed: {}", e);
+                        service_row.set_subtitle(&format!("Installation failed: {}", e));
+                        btn.set_label("Install");
+                        btn.set_sensitive(true);
+                        return glib::ControlFlow::Break;
+                    }
+                    Err(mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                    Err(mpsc::TryRecvError::Disconnected) => return glib::ControlFlow::Break,
+                }
+            }
+
+            // Wait until the daemon actually serves hardware data
+            attempts.set(attempts.get() + 1);
+            if update_status() || attempts.get() >= DAEMON_READY_ATTEMPTS {
+                btn.set_label("Install");
+                btn.set_sensitive(true);
+                return glib::ControlFlow::Break;
+            }
+            glib::ControlFlow::Continue
+        });
+    });
+
+    let nav = inner.nav.clone();
+    let next = next.clone();
+    continue_btn.connect_clicked(move |_| nav.push(&next));
+    page
+}
+
+fn build_detection_page(inner: &Rc<Inner>, next: &adw::NavigationPage) -> adw::NavigationPage {
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(18)
+        .build();
+
+    let desc = Label::builder()
+        .label(ACTIVE_DESCRIPTION)
+        .wrap(true)
+        .xalign(0.0)
+        .build();
+    content.append(&desc);
+
+    // Strategy toggle
+    let passive_row = adw::SwitchRow::builder()
+        .title("Passive mode")
+        .subtitle("Never stop fans; slower and only finds controllers that change")
+        .build();
+    let strategy_list = boxed_list();
+    strategy_list.append(&passive_row);
+    content.append(&strategy_list);
+
+    let desc_for_mode = desc.clone();
+    passive_row.connect_active_notify(move |row| {
+        desc_for_mode.set_label(if row.is_active() { PASSIVE_DESCRIPTION } else { ACTIVE_DESCRIPTION });
+    });
+
+    let progress = ProgressBar::builder()
+        .show_text(true)
+        .visible(false)
+        .build();
+    content.append(&progress);
+
+    let status = Label::builder()
+        .css_classes(["caption"])
+        .wrap(true)
+        .visible(false)
+        .build();
+    content.append(&status);
+
+    let start_btn = pill_button("Start Detection", false);
+    let continue_btn = pill_button("Continue", true);
+    continue_btn.set_sensitive(false);
+    let page = wizard_page("Detect Fans", &content, &[&start_btn, &continue_btn]);
+
+    // Going back to the daemon page stops a running job
+    let inner_for_pop = inner.clone();
+    let page_for_pop = page.clone();
+    inner.nav.connect_popped(move |_, popped| {
+        if *popped == page_for_pop {
+            inner_for_pop.cancel_detection();
+        }
+    });
+
+    let inner_for_start = inner.clone();
+    let continue_for_start = continue_btn.clone();
+    start_btn.connect_clicked(move |btn| {
+        btn.set_sensitive(false);
+        continue_for_start.set_sensitive(false);
+        passive_row.set_sensitive(false);
+        progress.set_visible(true);
+        progress.set_fraction(0.0);
+        status.set_visible(true);
+        status.remove_css_class("error");
+
+        let strategy = if passive_row.is_active() {
+            status.set_label("Watching fan speeds...");
+            hf_core::DaemonDetectionStrategy::Passive
+        } else {
+            status.set_label("Setting all fans to 100%...");
+            hf_core::DaemonDetectionStrategy::Active
+        };
+
+        let (tx, rx) = mpsc::channel::<DetectionUpdate>();
+        thread::spawn(move || {
+            run_detection_blocking(tx, strategy);
+        });
+
+        let inner = inner_for_start.clone();
+        let (btn, continue_btn) = (btn.clone(), continue_for_start.clone());
+        let (progress, status, passive_row) = (progress.clone(), status.clone(), passive_row.clone());
+        glib::timeout_add_local(Duration::from_millis(50), move || {
+            while let Ok(update) = rx.try_recv() {
+                match update {
+                    DetectionUpdate::Started { job_id } => {
+                        *inner.job_id.borrow_mut() = Some(job_id);
+                    }
+                    DetectionUpdate::Progress { fraction, message } => {
+                        progress.set_fraction(fraction);
+                        status.set_label(&message);
+                    }
+                    DetectionUpdate::Complete { mappings } => {
+                        inner.job_id.borrow_mut().take();
+                        progress.set_fraction(1.0);
+                        status.set_label(&if mappings.is_empty() {
+                            "No PWM-fan mappings detected. You can pair fans manually later; you may also need to check BIOS settings.".to_string()
+                        } else {
+                            format!("Found {} PWM-fan mapping(s).", mappings.len())
+                        });
+                        *inner.mappings.borrow_mut() = mappings;
+                        btn.set_label("Run Again");
+                        btn.set_sensitive(true);
+                        passive_row.set_sensitive(true);
+                        continue_btn.set_sensitive(true);
+                        return glib::ControlFlow::Break;
+                    }
+                    DetectionUpdate::Error { message } => {
+                        inner.job_id.borrow_mut().take();
+                        progress.set_visible(false);
+                        status.set_label(&format!("Detection failed: {}", message));
+                        status.add_css_class("error");
+                        btn.set_label("Try Again");
+                        btn.set_sensitive(true);
+                        passive_row.set_sensitive(true);
+                        return glib::ControlFlow::Break;
+                    }
+                    DetectionUpdate::Cancelled => {
+                        progress.set_visible(false);
+                        status.set_visible(false);
+                        btn.set_sensitive(true);
+                        passive_row.set_sensitive(true);
+                        return glib::ControlFlow::Break;
+                    }
+                }
+            }
+            glib::ControlFlow::Continue
+        });
+    });
+
+    let inner_for_continue = inner.clone();
+    let next = next.clone();
+    continue_btn.connect_clicked(move |_| {
+        populate_fans(&inner_for_continue);
+        inner_for_continue.nav.push(&next);
+    });
+    page
+}
+
+fn build_fans_page(inner: &Rc<Inner>, next: &adw::NavigationPage) -> adw::NavigationPage {
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(18)
+        .build();
+    let intro = Label::builder()
+        .label("Check the fans Hyperfan should control and choose the temperature each one follows. Unchecked fans stay under firmware control; you can pair them later on the Fan Pairing page.")
+        .wrap(true)
+        .xalign(0.0)
+        .build();
+    content.append(&intro);
+
+    let list = boxed_list();
+    content.append(&list);
+    let empty = adw::StatusPage::builder()
+        .icon_name("dialog-information-symbolic")
+        .title("No Fans Detected")
+        .description("Detection did not match any controller to a fan. Finish the setup and pair fans manually on the Fan Pairing page.")
+        .css_classes(["compact"])
+        .build();
+    content.append(&empty);
+
+    let continue_btn = pill_button("Continue", true);
+    let page = wizard_page("Fans", &content, &[&continue_btn]);
+
+    // Rows are rebuilt from the detection result each time the page is shown
+    let inner_for_show = inner.clone();
+    page.connect_showing(move |_| {
+        list.remove_all();
+        let choices = inner_for_show.choices.borrow();
+        for choice in choices.iter() {
+            list.append(&choice.row);
+        }
+        list.set_visible(!choices.is_empty());
+        empty.set_visible(choices.is_empty());
+    });
+
+    let nav = inner.nav.clone();
+    let next = next.clone();
+    continue_btn.connect_clicked(move |_| nav.push(&next));
+    page
+}
+
+/// Build the fan choices from the detection result and the sensor list
+fn populate_fans(inner: &Inner) {
+    let hw = crate::fallback::list_hardware().ok();
+    let chips = hw.as_ref().map(|hw| hw.chips.as_slice()).unwrap_or_default();
+
+    let mut sources: Vec<(String, String)> = Vec::new();
+    let mut cpu_source = None;
+    for chip in chips {
+        let is_cpu = ["coretemp", "k10temp", "zenpower"].iter().any(|n| chip.name.contains(n));
+        for temp in &chip.temperatures {
+            if is_cpu && cpu_source.is_none() {
+                cpu_source = Some(sources.len());
+            }
+            let label = temp.label.clone().unwrap_or_else(|| temp.name.clone());
+            sources.push((temp.path.clone(), format!("{} • {}", chip.name, label)));
+        }
+    }
+    let default_source = cpu_source.unwrap_or(0) as u32;
+    let names: Vec<&str> = sources.iter().map(|(_, name)| name.as_str()).collect();
+
+    let choices = inner.mappings.borrow().iter().map(|mapping| {
+        let fan_label = chips.iter()
+            .flat_map(|chip| chip.fans.iter().map(move |fan| (chip, fan)))
+            .find(|(_, fan)| fan.path == mapping.fan_name)
+            .map(|(chip, fan)| fan.label.clone().unwrap_or_else(|| format!("{} {}", chip.name, fan.name)));
+        let name = fan_label.unwrap_or_else(|| file_name(&mapping.fan_name).to_string());
+
+        let confirm = CheckButton::builder()
+            .active(mapping.confidence >= CONFIRM_CONFIDENCE)
+            .valign(gtk4::Align::Center)
+            .build();
+        confirm.update_property(&[gtk4::accessible::Property::Label(&format!("Control {}", name))]);
+        let source = DropDown::from_strings(&names);
+        source.set_selected(default_source);
+        source.set_valign(gtk4::Align::Center);
+        source.set_tooltip_text(Some("Temperature this fan follows"));
+        source.update_property(&[gtk4::accessible::Property::Label(&format!("Temperature source for {}", name))]);
+        source.set_sensitive(!sources.is_empty());
+
+        let row = adw::ActionRow::builder()
+            .title(&name)
+            .subtitle(format!(
+                "{} → {} ({:.0}% confidence)",
+                file_name(&mapping.pwm_name),
+                file_name(&mapping.fan_name),
+                mapping.confidence * 100.0
+            ))
+            .activatable_widget(&confirm)
+            .build();
+        row.add_prefix(&confirm);
+        row.add_suffix(&source);
+
+        FanChoice { mapping: mapping.clone(), name, row, confirm, source }
+    }).collect();
+
+    *inner.choices.borrow_mut() = choices;
+    *inner.sources.borrow_mut() = sources;
+}
+
+fn build_curve_page(inner: &Rc<Inner>, next: &adw::NavigationPage) -> adw::NavigationPage {
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(18)
+        .build();
+    let intro = Label::builder()
+        .label("Choose the curve your fans start on. It is added to your curves and can be edited, or replaced per fan, at any time.")
+        .wrap(true)
+        .xalign(0.0)
+        .build();
+    content.append(&intro);
+
+    let list = boxed_list();
+    let mut group: Option<CheckButton> = None;
+    for (preset, description) in PRESETS {
+        let check = CheckButton::builder()
+            .active(preset == inner.preset.get())
+            .valign(gtk4::Align::Center)
+            .build();
+        check.set_group(group.as_ref());
+        group.get_or_insert_with(|| check.clone());
+
+        let row = adw::ActionRow::builder()
+            .title(preset.label())
+            .subtitle(description)
+            .activatable_widget(&check)
+            .build();
+        row.add_prefix(&check);
+        list.append(&row);
+
+        let inner = inner.clone();
+        check.connect_toggled(move |check| {
+            if check.is_active() {
+                inner.preset.set(preset);
+            }
+        });
+    }
+    content.append(&list);
+
+    let continue_btn = pill_button("Continue", true);
+    let page = wizard_page("Curve", &content, &[&continue_btn]);
+
+    let nav = inner.nav.clone();
+    let next = next.clone();
+    continue_btn.connect_clicked(move |_| nav.push(&next));
+    page
+}
+
+fn build_finish_page(inner: &Rc<Inner>) -> adw::NavigationPage {
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(18)
+        .build();
+    let summary = Label::builder()
+        .wrap(true)
+        .xalign(0.0)
+        .build();
+    content.append(&summary);
+    let list = boxed_list();
+    content.append(&list);
+    let error_label = Label::builder()
+        .css_classes(["error"])
+        .wrap(true)
+        .xalign(0.0)
+        .visible(false)
+        .build();
+    content.append(&error_label);
+
+    let finish_btn = pill_button("Finish", true);
+    let page = wizard_page("Finish", &content, &[&finish_btn]);
+
+    let inner_for_show = inner.clone();
+    let error_for_show = error_label.clone();
+    page.connect_showing(move |_| {
+        error_for_show.set_visible(false);
+        list.remove_all();
+        let plan = setup_plan(&inner_for_show);
+        let sources = inner_for_show.sources.borrow();
+        for fan in &plan.fans {
+            let source = sources.iter()
+                .find(|(path, _)| *path == fan.temp_source_path)
+                .map(|(_, name)| name.as_str())
+                .unwrap_or(&fan.temp_source_path);
+            let row = adw::ActionRow::builder()
+                .title(&fan.name)
+                .subtitle(format!("Follows {}", source))
+                .build();
+            list.append(&row);
+        }
+        list.set_visible(!plan.fans.is_empty());
+        summary.set_label(&match plan.fans.len() {
+            0 => "No fan will be controlled yet. The detection results are saved, and you can pair fans on the Fan Pairing page.".to_string(),
+            n => format!("{} fan(s) will be paired and follow the {} curve:", n, plan.preset.label()),
+        });
+    });
+
+    let inner_for_finish = inner.clone();
+    finish_btn.connect_clicked(move |_| {
+        let plan = setup_plan(&inner_for_finish);
+        if let Err(e) = hf_core::apply_setup(&plan) {
+            tracing::error!("Failed to save setup: {}", e);
+            error_label.set_label(&format!("Failed to save the setup: {}", e));
+            error_label.set_visible(true);
+            return;
+        }
+        if let Err(e) = hf_core::daemon_reload_config() {
+            tracing::debug!("Failed to signal daemon reload: {}", e);
+        }
+        if let Some(callback) = inner_for_finish.on_finished.borrow().as_ref() {
+            callback();
+        }
+        inner_for_finish.window.close();
+    });
+    page
+}
+
+/// The choices made so far as a plan for `hf_core::apply_setup`
+fn setup_plan(inner: &Inner) -> hf_core::SetupPlan {
+    let sources = inner.sources.borrow();
+    let fans = inner.choices.borrow().iter()
+        .filter(|choice| choice.confirm.is_active())
+        .filter_map(|choice| {
+            let (path, _) = sources.get(choice.source.selected() as usize)?;
+            Some(hf_core::SetupFan {
+                pwm_path: choice.mapping.pwm_name.clone(),
+                fan_path: choice.mapping.fan_name.clone(),
+                name: choice.name.clone(),
+                temp_source_path: path.clone(),
+            })
+        })
+        .collect();
+    hf_core::SetupPlan {
+        mappings: inner.mappings.borrow().clone(),
+        fans,
+        preset: inner.preset.get(),
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Updates sent from detection thread to UI
+enum DetectionUpdate {
+    Started { job_id: u64 },
+    Progress { fraction: f64, message: String },
+    Complete { mappings: Vec<FanMapping> },
+    Error { message: String },
+    Cancelled,
+}
+
+/// Run detection in blocking thread and send updates via channel
+fn run_detection_blocking(tx: mpsc::Sender<DetectionUpdate>, strategy: hf_core::DaemonDetectionStrategy) {
+    // Daemon authoritative: request mapping detection via daemon IPC.
+    let _ = tx.send(DetectionUpdate::Progress {
+        fraction: 0.1,
+        message: "Requesting detection from daemon...".to_string(),
+    });
+
+    let job_id = match hf_core::daemon_start_detection(strategy) {
+        Ok(progress) => progress.job_id,
+        Err(e) => {
+            let _ = tx.send(DetectionUpdate::Error { message: e });
+            return;
+        }
+    };
+    let _ = tx.send(DetectionUpdate::Started { job_id });
+
+    let daemon_mappings = loop {
+        thread::sleep(DETECTION_POLL);
+        let progress = match hf_core::daemon_get_detection_progress(job_id) {
+            Ok(p) => p,
+            Err(e) => {
+                let _ = tx.send(DetectionUpdate::Error { message: e });
+                return;
+            }
+        };
+        match progress.state {
+            hf_core::DaemonDetectionJobState::Running => {
+                let (fraction, message) = match progress.current_pwm {
+                    None if progress.strategy == hf_core::DaemonDetectionStrategy::Passive => (
+                        progress.step as f64 / progress.total_steps.max(1) as f64,
+                        format!("Watching fan speeds ({} of {} samples)...", progress.step, progress.total_steps),
+                    ),
+                    Some(pwm) if progress.total_steps > 0 => (
+                        progress.step as f64 / (progress.total_steps + 1) as f64,
+                        format!("Testing {} ({} of {})...", pwm, progress.step, progress.total_steps),
+                    ),
+                    _ => (0.1, "Setting all fans to 100%...".to_string()),
+                };
+                // The receiver is gone once the assistant has been closed
+                if tx.send(DetectionUpdate::Progress { fraction, message }).is_err() {
+                    return;
+                }
+            }
+            hf_core::DaemonDetectionJobState::Completed => break progress.mappings,
+            hf_core::DaemonDetectionJobState::Cancelled => {
+                let _ = tx.send(DetectionUpdate::Cancelled);
+                return;
+            }
+            hf_core::DaemonDetectionJobState::Failed => {
+                let message = progress.error.unwrap_or_else(|| "Detection failed".to_string());
+                let _ = tx.send(DetectionUpdate::Error { message });
+                return;
+            }
+        }
+    };
+
+    // Map daemon result (paths) into the mappings saved in settings
+    let mappings: Vec<FanMapping> = daemon_mappings
+        .into_iter()
+        .map(|m| FanMapping {
+            fan_name: m.fan_path,
+            pwm_name: m.pwm_path,
+            confidence: m.confidence,
+            temp_sources: Vec::new(),
+            response_time_ms: None,
+            min_pwm: None,
+            max_rpm: None,
+        })
+        .collect();
+
+    let _ = tx.send(DetectionUpdate::Complete { mappings });
+}
@@ -6,7 +6,7 @@ use libadwaita as adw;
 use libadwaita::prelude::*;
 use std::path::PathBuf;
 
-use hf_core::TemperatureSensor;
+use hf_core::{TemperatureLimits, TemperatureSensor};
 
 use hf_core::daemon_client;
 
@@ -18,6 +18,42 @@ pub struct TempMonitor {
     sensor_path: PathBuf,
 }
 
+/// Style class for a reading against its driver limits
+/// Sensors without limits fall back to fixed 70°C / 85°C bands
+pub(super) fn limit_css_class(temp: f32, limits: &TemperatureLimits) -> Option<&'static str> {
+    if limits.is_empty() {
+        return if temp >= 85.0 {
+            Some("error")
+        } else if temp >= 70.0 {
+            Some("warning")
+        } else {
+            None
+        };
+    }
+    if limits.exceeds_crit(temp) {
+        Some("error")
+    } else if limits.exceeds_max(temp) {
+        Some("warning")
+    } else {
+        None
+    }
+}
+
+/// Tooltip listing a sensor's driver limits
+pub(super) fn limits_tooltip(limits: &TemperatureLimits) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(max) = limits.max {
+        parts.push(format!("High {}", hf_core::display::format_temp(max)));
+    }
+    if let Some(crit) = limits.crit {
+        parts.push(format!("Critical {}", hf_core::display::format_temp(crit)));
+    }
+    if limits.crit_alarm {
+        parts.push("Critical alarm raised".to_string());
+    }
+    (!parts.is_empty()).then(|| parts.join(" • "))
+}
+
 impl TempMonitor {
     pub fn new(chip_name: &str, sensor: &TemperatureSensor) -> Self {
         let title = sensor
@@ -57,32 +93,32 @@ impl TempMonitor {
         let row = adw::ActionRow::builder()
             .title(&title)
             .build();
+        if let Some(tooltip) = limits_tooltip(&sensor.limits) {
+            row.set_subtitle(&tooltip);
+        }
 
         row.add_suffix(&content_box);
 
-        Self {
+        let monitor = Self {
             row,
             level_bar,
             value_label,
             sensor_path: sensor.input_path.clone(),
-        }
+        };
+        monitor.update(temp, &sensor.limits);
+        monitor
     }
 
     /// Update the displayed temperature
-    pub fn update(&self, temp: f32) {
+    pub fn update(&self, temp: f32, limits: &TemperatureLimits) {
         self.value_label.set_label(&hf_core::display::format_temp_precise(temp));
         self.level_bar.set_value(temp.clamp(0.0, 100.0) as f64);
 
-        // Update styling based on temperature
-        let css_class = if temp >= 85.0 {
-            "error"
-        } else if temp >= 70.0 {
-            "warning"
-        } else {
-            ""
-        };
-
-        self.value_label.set_css_classes(&["numeric", css_class].iter().filter(|s| !s.is_empty()).copied().collect::<Vec<_>>());
+        // Warning/error styling once the reading passes the sensor's limits
+        match limit_css_class(temp, limits) {
+            Some(class) => self.value_label.set_css_classes(&["numeric", class]),
+            None => self.value_label.set_css_classes(&["numeric"]),
+        }
     }
 
     /// Get the sensor path for reading updates
@@ -111,7 +147,7 @@ impl TempMonitorGroup {
         // Load sensors from hardware (daemon authoritative)
         let mut monitors = Vec::new();
 
-        match hf_core::daemon_list_hardware() {
+        match crate::fallback::list_hardware() {
             Ok(hw) => {
                 for chip in hw.chips {
                     for sensor in chip.temperatures {
@@ -120,6 +156,11 @@ impl TempMonitorGroup {
                             input_path: PathBuf::from(sensor.path),
                             label: sensor.label,
                             current_temp: Some(sensor.value),
+                            limits: TemperatureLimits {
+                                max: sensor.max,
+                                crit: sensor.crit,
+                                crit_alarm: sensor.crit_alarm,
+                            },
                         };
 
                         let monitor = TempMonitor::new(&chip.name, &temp_sensor);
@@ -159,12 +200,11 @@ impl TempMonitorGroup {
         for monitor in &self.monitors {
             let path = monitor.sensor_path().to_string_lossy();
             
-            let temp = cached_temps.temperatures.iter()
-                .find(|t| t.path == path.as_ref())
-                .map(|t| t.temp_celsius);
+            let reading = cached_temps.temperatures.iter()
+                .find(|t| t.path == path.as_ref());
             
-            if let Some(temp) = temp {
-                monitor.update(temp);
+            if let Some(reading) = reading {
+                monitor.update(reading.temp_celsius, &reading.limits);
             }
         }
     }
@@ -0,0 +1,365 @@
+//! Curve template import/export dialogs
+//!
+//! Export writes the selected curves (or every curve a profile uses) to a
+//! shareable template file with author, hardware and notes. Import shows a
+//! template's details and lets the user pick a local sensor for each of its
+//! temperature sources before anything is saved.
+
+use gtk4::prelude::*;
+use gtk4::gio;
+use gtk4::{Label, Orientation};
+use gtk4::Box as GtkBox;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::rc::Rc;
+use tracing::{debug, info, warn};
+
+/// Open the export dialog
+pub fn show_export_dialog(parent: &impl IsA<gtk4::Widget>) {
+    let curves: Vec<hf_core::PersistedCurve> = hf_core::load_curves()
+        .map(|store| store.all().into_iter().cloned().collect())
+        .unwrap_or_default();
+    let profiles = hf_core::load_settings()
+        .map(|s| s.available_profiles())
+        .unwrap_or_default();
+
+    let (window, content, action_btn, error_label) = dialog_window(parent, "Export Curve Template", "Export");
+
+    // What to export
+    let mut choices = vec!["Selected curves".to_string()];
+    choices.extend(profiles.iter().map(|p| format!("Profile: {}", p)));
+    let choice_refs: Vec<&str> = choices.iter().map(String::as_str).collect();
+    let contents_row = adw::ComboRow::builder()
+        .title("Contents")
+        .subtitle("A profile includes which curve each pair uses")
+        .model(&gtk4::StringList::new(&choice_refs))
+        .build();
+    let contents_group = adw::PreferencesGroup::new();
+    contents_group.add(&contents_row);
+    content.append(&contents_group);
+
+    let curves_group = adw::PreferencesGroup::builder()
+        .title("Curves")
+        .build();
+    let curve_rows: Vec<(String, adw::SwitchRow)> = curves
+        .iter()
+        .map(|curve| {
+            let row = adw::SwitchRow::builder()
+                .title(&curve.name)
+                .subtitle(format!("{} points", curve.points.len()))
+                .active(true)
+                .build();
+            curves_group.add(&row);
+            (curve.id.clone(), row)
+        })
+        .collect();
+    content.append(&curves_group);
+
+    let curves_group_for_mode = curves_group.clone();
+    contents_row.connect_selected_notify(move |row| {
+        curves_group_for_mode.set_sensitive(row.selected() == 0);
+    });
+
+    // Metadata
+    let details_group = adw::PreferencesGroup::builder()
+        .title("Details")
+        .description("Shown to whoever imports the template")
+        .build();
+    let author_row = adw::EntryRow::builder().title("Author").build();
+    let hardware_row = adw::EntryRow::builder().title("Hardware").build();
+    if let Ok(summary) = hf_core::get_system_summary() {
+        hardware_row.set_text(&summary.motherboard_name);
+    }
+    let notes_row = adw::EntryRow::builder().title("Notes").build();
+    details_group.add(&author_row);
+    details_group.add(&hardware_row);
+    details_group.add(&notes_row);
+    content.append(&details_group);
+    content.append(&error_label);
+
+    let window_for_export = window.clone();
+    action_btn.connect_clicked(move |_| {
+        let metadata = hf_core::TemplateMetadata {
+            author: entry_text(&author_row),
+            hardware: entry_text(&hardware_row),
+            notes: entry_text(&notes_row),
+            created_at: 0,
+        };
+        let template = match contents_row.selected() {
+            0 => {
+                let ids: Vec<String> = curve_rows
+                    .iter()
+                    .filter(|(_, row)| row.is_active())
+                    .map(|(id, _)| id.clone())
+                    .collect();
+                if ids.is_empty() {
+                    show_error(&error_label, "Select at least one curve");
+                    return;
+                }
+                hf_core::export_curves_template(&ids, metadata)
+            }
+            n => hf_core::export_profile_template(&profiles[n as usize - 1], metadata),
+        };
+        let template = match template {
+            Ok(template) => template,
+            Err(e) => {
+                show_error(&error_label, &e.to_string());
+                return;
+            }
+        };
+
+        let file_dialog = gtk4::FileDialog::builder()
+            .title("Save Curve Template")
+            .initial_name("hyperfan-curves.json")
+            .filters(&json_filters())
+            .build();
+        let window_for_save = window_for_export.clone();
+        let error_for_save = error_label.clone();
+        file_dialog.save(Some(&window_for_export), gio::Cancellable::NONE, move |result| {
+            let Some(path) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+            match template.save(&path) {
+                Ok(()) => {
+                    info!("Exported curve template to {:?}", path);
+                    window_for_save.close();
+                }
+                Err(e) => show_error(&error_for_save, &format!("Failed to save template: {}", e)),
+            }
+        });
+    });
+
+    window.present();
+}
+
+/// Pick a template file, then open the import dialog for it
+///
+/// `on_imported` runs after the curves have been saved.
+pub fn show_import_dialog(parent: &impl IsA<gtk4::Widget>, on_imported: impl Fn() + 'static) {
+    let parent_window = parent.root().and_downcast::<gtk4::Window>();
+    let parent_widget: gtk4::Widget = parent.clone().upcast();
+    let on_imported: Rc<dyn Fn()> = Rc::new(on_imported);
+
+    let file_dialog = gtk4::FileDialog::builder()
+        .title("Import Curve Template")
+        .filters(&json_filters())
+        .build();
+    file_dialog.open(parent_window.as_ref(), gio::Cancellable::NONE, move |result| {
+        let Some(path) = result.ok().and_then(|file| file.path()) else {
+            return;
+        };
+        match hf_core::CurveTemplate::load(&path) {
+            Ok(template) => show_template_import(&parent_widget, template, on_imported.clone()),
+            Err(e) => {
+                warn!("Failed to load curve template {:?}: {}", path, e);
+                let alert = adw::AlertDialog::builder()
+                    .heading("Cannot Import Template")
+                    .body(e.to_string())
+                    .build();
+                alert.add_response("ok", "OK");
+                alert.present(parent_widget.root().and_downcast::<gtk4::Window>().as_ref());
+            }
+        }
+    });
+}
+
+/// Details and sensor re-mapping for a loaded template
+fn show_template_import(parent: &gtk4::Widget, template: hf_core::CurveTemplate, on_imported: Rc<dyn Fn()>) {
+    let (window, content, action_btn, error_label) = dialog_window(parent, "Import Curve Template", "Import");
+
+    let meta = &template.metadata;
+    let details_group = adw::PreferencesGroup::builder()
+        .title("Template")
+        .description(format!("{} curve(s)", template.curves.len()))
+        .build();
+    let details = [
+        ("Author", meta.author.clone()),
+        ("Hardware", meta.hardware.clone()),
+        ("Notes", meta.notes.clone()),
+        ("Profile", template.profile.as_ref().map(|p| format!("{} ({} pairs)", p.name, p.pairs.len()))),
+    ];
+    for (title, value) in details {
+        if let Some(value) = value.filter(|v| !v.is_empty()) {
+            let row = adw::ActionRow::builder()
+                .title(title)
+                .subtitle(&value)
+                .subtitle_selectable(true)
+                .build();
+            details_group.add(&row);
+        }
+    }
+    for curve in &template.curves {
+        let row = adw::ActionRow::builder()
+            .title(&curve.name)
+            .subtitle(format!("{} points", curve.points.len()))
+            .build();
+        details_group.add(&row);
+    }
+    content.append(&details_group);
+
+    // One sensor choice per template temperature source
+    let chips = hf_core::enumerate_hwmon_chips().unwrap_or_default();
+    let local: Vec<(String, String)> = chips
+        .iter()
+        .flat_map(|chip| {
+            chip.temperatures.iter().map(move |t| {
+                let label = t.label.as_deref().unwrap_or(&t.name);
+                (t.input_path.to_string_lossy().to_string(), format!("{} {}", chip.name, label))
+            })
+        })
+        .collect();
+    let mut names = vec!["Choose a sensor...".to_string()];
+    names.extend(local.iter().map(|(_, name)| name.clone()));
+    let name_refs: Vec<&str> = names.iter().map(String::as_str).collect();
+
+    let suggestions = hf_core::suggest_sensor_mapping(&template, &chips);
+    let sensors_group = adw::PreferencesGroup::builder()
+        .title("Sensors")
+        .description("Pick the sensor on this system each curve should read")
+        .build();
+    let sensor_rows: Vec<(String, adw::ComboRow)> = suggestions
+        .iter()
+        .map(|suggestion| {
+            let source = &suggestion.source;
+            let hint = if suggestion.exact { "same sensor found" } else { "check this choice" };
+            let row = adw::ComboRow::builder()
+                .title(&source.label)
+                .subtitle(format!("{} - {}", source.path, hint))
+                .model(&gtk4::StringList::new(&name_refs))
+                .build();
+            let selected = suggestion
+                .suggested_path
+                .as_ref()
+                .and_then(|path| local.iter().position(|(p, _)| p == path))
+                .map_or(0, |i| i as u32 + 1);
+            row.set_selected(selected);
+            sensors_group.add(&row);
+            (source.path.clone(), row)
+        })
+        .collect();
+    if !sensor_rows.is_empty() {
+        content.append(&sensors_group);
+    }
+
+    let profile_row = adw::SwitchRow::builder()
+        .title("Apply profile assignments")
+        .subtitle("Use the template's curves for pairs with the same names")
+        .active(true)
+        .build();
+    if template.profile.is_some() {
+        let profile_group = adw::PreferencesGroup::new();
+        profile_group.add(&profile_row);
+        content.append(&profile_group);
+    }
+    content.append(&error_label);
+
+    let window_for_import = window.clone();
+    action_btn.connect_clicked(move |_| {
+        let mut mapping = hf_core::SensorMapping::new();
+        for (source, row) in &sensor_rows {
+            match row.selected() {
+                0 | gtk4::INVALID_LIST_POSITION => {
+                    show_error(&error_label, &format!("Choose a sensor for {}", row.title()));
+                    return;
+                }
+                n => {
+                    mapping.insert(source.clone(), local[n as usize - 1].0.clone());
+                }
+            }
+        }
+
+        match hf_core::import_template(&template, &mapping, profile_row.is_active()) {
+            Ok(imported) => {
+                debug!(
+                    "Imported {} curves, assigned {} pairs, {} pairs not found",
+                    imported.curve_ids.len(),
+                    imported.assigned_pairs.len(),
+                    imported.unmatched_pairs.len()
+                );
+                if let Err(e) = hf_core::daemon_reload_config() {
+                    debug!("Failed to signal daemon reload: {}", e);
+                }
+                on_imported();
+                window_for_import.close();
+            }
+            Err(e) => show_error(&error_label, &e.to_string()),
+        }
+    });
+
+    window.present();
+}
+
+/// Modal window with Cancel and `action` buttons and a scrolling content box
+pub(super) fn dialog_window(parent: &impl IsA<gtk4::Widget>, title: &str, action: &str) -> (adw::Window, GtkBox, gtk4::Button, Label) {
+    let window = adw::Window::builder()
+        .title(title)
+        .default_width(480)
+        .default_height(560)
+        .modal(true)
+        .build();
+    if let Some(parent_window) = parent.root().and_downcast::<gtk4::Window>() {
+        window.set_transient_for(Some(&parent_window));
+    }
+
+    let header = adw::HeaderBar::builder()
+        .show_end_title_buttons(false)
+        .show_start_title_buttons(false)
+        .build();
+    let cancel_btn = gtk4::Button::builder()
+        .label("Cancel")
+        .build();
+    let action_btn = gtk4::Button::builder()
+        .label(action)
+        .css_classes(["suggested-action"])
+        .build();
+    header.pack_start(&cancel_btn);
+    header.pack_end(&action_btn);
+
+    let window_for_cancel = window.clone();
+    cancel_btn.connect_clicked(move |_| window_for_cancel.close());
+
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(18)
+        .margin_start(24)
+        .margin_end(24)
+        .margin_top(18)
+        .margin_bottom(24)
+        .build();
+    let scroll = gtk4::ScrolledWindow::builder()
+        .vexpand(true)
+        .child(&content)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&header);
+    toolbar.set_content(Some(&scroll));
+    window.set_content(Some(&toolbar));
+
+    let error_label = Label::builder()
+        .css_classes(["error", "caption"])
+        .wrap(true)
+        .visible(false)
+        .build();
+
+    (window, content, action_btn, error_label)
+}
+
+fn entry_text(row: &adw::EntryRow) -> Option<String> {
+    let text = row.text().trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+pub(super) fn show_error(label: &Label, message: &str) {
+    label.set_label(message);
+    label.set_visible(true);
+}
+
+fn json_filters() -> gio::ListStore {
+    let filter = gtk4::FileFilter::new();
+    filter.add_pattern("*.json");
+    filter.set_name(Some("Curve templates (JSON)"));
+    let filters = gio::ListStore::new::<gtk4::FileFilter>();
+    filters.append(&filter);
+    filters
+}
@@ -0,0 +1,371 @@
+//! Virtual sensor management
+//!
+//! Virtual sensors derive a temperature from hardware sensors (the hottest of
+//! several, a difference, or a corrected reading). They are saved in the
+//! settings and evaluated by the daemon, which lists them with the hardware
+//! sensors so curves, groups, and graphs can use them.
+
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use tracing::{debug, warn};
+
+use hf_core::{VirtualFormula, VirtualSensor};
+
+/// Formula choices in the order of the editor's combo row
+const FORMULAS: [&str; 5] = ["Highest", "Lowest", "Average", "Difference", "Corrected"];
+
+/// Open the virtual sensor list window
+pub fn show_virtual_sensors_dialog(parent: &impl IsA<gtk4::Widget>) {
+    let window = adw::Window::builder()
+        .title("Virtual Sensors")
+        .default_width(560)
+        .default_height(520)
+        .modal(true)
+        .build();
+    if let Some(parent_window) = parent.root().and_downcast::<gtk4::Window>() {
+        window.set_transient_for(Some(&parent_window));
+    }
+
+    let header = adw::HeaderBar::new();
+    let add_btn = gtk4::Button::builder()
+        .icon_name("list-add-symbolic")
+        .tooltip_text("Add virtual sensor")
+        .build();
+    header.pack_start(&add_btn);
+
+    let page = adw::PreferencesPage::new();
+    let list_group = adw::PreferencesGroup::builder()
+        .title("Virtual Sensors")
+        .description("Derived temperatures can be used as curve sources and graphs like any sensor")
+        .build();
+    page.add(&list_group);
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&header);
+    toolbar.set_content(Some(&page));
+    window.set_content(Some(&toolbar));
+
+    let rows: Rc<RefCell<Vec<gtk4::Widget>>> = Rc::new(RefCell::new(Vec::new()));
+    let refresh: Rc<RefreshSlot> = Rc::new(RefCell::new(None));
+    let refresh_weak = Rc::downgrade(&refresh);
+    {
+        let list_group = list_group.clone();
+        let window = window.clone();
+        *refresh.borrow_mut() = Some(Box::new(move || {
+            for row in rows.borrow_mut().drain(..) {
+                list_group.remove(&row);
+            }
+            let sensors = hf_core::load_settings().map(|s| s.virtual_sensors).unwrap_or_default();
+            if sensors.is_empty() {
+                let row = adw::ActionRow::builder()
+                    .title("No virtual sensors")
+                    .subtitle("Add one to combine or correct hardware temperatures")
+                    .build();
+                list_group.add(&row);
+                rows.borrow_mut().push(row.upcast());
+            }
+            for sensor in &sensors {
+                let row = sensor_row(sensor, &window, refresh_weak.clone());
+                list_group.add(&row);
+                rows.borrow_mut().push(row.upcast());
+            }
+        }));
+    }
+    reload(&refresh);
+
+    let window_add = window.clone();
+    let refresh_add = refresh.clone();
+    add_btn.connect_clicked(move |_| {
+        show_sensor_editor(&window_add, None, Rc::downgrade(&refresh_add));
+    });
+
+    window.present();
+}
+
+/// Rebuilds the sensor list from the settings (set once the list exists)
+type RefreshSlot = RefCell<Option<Box<dyn Fn()>>>;
+type Refresh = std::rc::Weak<RefreshSlot>;
+
+fn reload(refresh: &RefreshSlot) {
+    if let Some(refresh) = refresh.borrow().as_ref() {
+        refresh();
+    }
+}
+
+/// Tell the daemon to pick up changed definitions
+fn signal_daemon() {
+    if let Err(e) = hf_core::daemon_reload_config_section(hf_core::DaemonConfigSection::Settings) {
+        debug!("Failed to signal daemon reload: {}", e);
+    }
+}
+
+fn formula_summary(formula: &VirtualFormula) -> String {
+    match formula {
+        VirtualFormula::Max { inputs } => format!("Highest of {} sensors", inputs.len()),
+        VirtualFormula::Min { inputs } => format!("Lowest of {} sensors", inputs.len()),
+        VirtualFormula::Average { inputs } => format!("Average of {} sensors", inputs.len()),
+        VirtualFormula::Delta { .. } => "Difference of two sensors".to_string(),
+        VirtualFormula::Linear { scale, offset, .. } => format!("Corrected: × {:.2} {:+.1}°C", scale, offset),
+    }
+}
+
+/// List row for one sensor with edit and delete buttons
+fn sensor_row(sensor: &VirtualSensor, window: &adw::Window, refresh: Refresh) -> adw::ActionRow {
+    let row = adw::ActionRow::builder()
+        .title(sensor.name.as_str())
+        .subtitle(formula_summary(&sensor.formula).as_str())
+        .build();
+
+    let edit_btn = gtk4::Button::builder()
+        .icon_name("document-edit-symbolic")
+        .tooltip_text("Edit virtual sensor")
+        .valign(gtk4::Align::Center)
+        .css_classes(["flat"])
+        .build();
+    let delete_btn = gtk4::Button::builder()
+        .icon_name("user-trash-symbolic")
+        .tooltip_text("Delete virtual sensor")
+        .valign(gtk4::Align::Center)
+        .css_classes(["flat", "destructive-action"])
+        .build();
+    row.add_suffix(&edit_btn);
+    row.add_suffix(&delete_btn);
+
+    let window_edit = window.clone();
+    let sensor_edit = sensor.clone();
+    let refresh_edit = refresh.clone();
+    edit_btn.connect_clicked(move |_| {
+        show_sensor_editor(&window_edit, Some(&sensor_edit), refresh_edit.clone());
+    });
+
+    let id = sensor.id.clone();
+    delete_btn.connect_clicked(move |_| {
+        match hf_core::delete_virtual_sensor(&id) {
+            Ok(()) => signal_daemon(),
+            Err(e) => warn!("Failed to delete virtual sensor: {}", e),
+        }
+        if let Some(refresh) = refresh.upgrade() {
+            reload(&refresh);
+        }
+    });
+
+    row
+}
+
+/// Combo row listing the hardware temperature sources
+fn source_row(title: &str, sources: &[(String, String)], selected: Option<&str>) -> adw::ComboRow {
+    let names: Vec<&str> = sources.iter().map(|(_, name)| name.as_str()).collect();
+    let row = adw::ComboRow::builder()
+        .title(title)
+        .model(&gtk4::StringList::new(&names))
+        .build();
+    if let Some(idx) = selected.and_then(|path| sources.iter().position(|(p, _)| p == path)) {
+        row.set_selected(idx as u32);
+    }
+    row
+}
+
+/// Editor for a new sensor (`existing` None) or an existing one
+fn show_sensor_editor(parent: &adw::Window, existing: Option<&VirtualSensor>, refresh: Refresh) {
+    let editor = adw::Window::builder()
+        .title(if existing.is_some() { "Edit Virtual Sensor" } else { "New Virtual Sensor" })
+        .default_width(520)
+        .default_height(600)
+        .modal(true)
+        .transient_for(parent)
+        .build();
+
+    let header = adw::HeaderBar::new();
+    let save_btn = gtk4::Button::builder()
+        .label("Save")
+        .css_classes(["suggested-action"])
+        .build();
+    header.pack_end(&save_btn);
+
+    let page = adw::PreferencesPage::new();
+    let general = adw::PreferencesGroup::new();
+    page.add(&general);
+
+    let name_row = adw::EntryRow::builder().title("Name").build();
+    if let Some(sensor) = existing {
+        name_row.set_text(&sensor.name);
+    }
+    general.add(&name_row);
+
+    let formula_row = adw::ComboRow::builder()
+        .title("Formula")
+        .model(&gtk4::StringList::new(&FORMULAS))
+        .build();
+    formula_row.set_selected(match existing.map(|s| &s.formula) {
+        None | Some(VirtualFormula::Max { .. }) => 0,
+        Some(VirtualFormula::Min { .. }) => 1,
+        Some(VirtualFormula::Average { .. }) => 2,
+        Some(VirtualFormula::Delta { .. }) => 3,
+        Some(VirtualFormula::Linear { .. }) => 4,
+    });
+    general.add(&formula_row);
+
+    // Virtual sensors cannot be inputs of other virtual sensors
+    let sources: Rc<Vec<(String, String)>> = Rc::new(
+        super::pwm_group_dialog::temp_sources()
+            .into_iter()
+            .filter(|(path, _)| hf_core::virtual_sensor_id(path).is_none())
+            .collect(),
+    );
+
+    // Highest / lowest / average: any number of inputs
+    let inputs_group = adw::PreferencesGroup::builder().title("Inputs").build();
+    page.add(&inputs_group);
+    let selected_inputs: Vec<&str> = match existing.map(|s| &s.formula) {
+        Some(VirtualFormula::Max { inputs } | VirtualFormula::Min { inputs } | VirtualFormula::Average { inputs }) => {
+            inputs.iter().map(String::as_str).collect()
+        }
+        _ => Vec::new(),
+    };
+    let mut input_checks: Vec<(String, gtk4::CheckButton)> = Vec::new();
+    for (path, name) in sources.iter() {
+        let check = gtk4::CheckButton::builder()
+            .active(selected_inputs.contains(&path.as_str()))
+            .valign(gtk4::Align::Center)
+            .build();
+        let row = adw::ActionRow::builder()
+            .title(name.as_str())
+            .subtitle(path.as_str())
+            .activatable_widget(&check)
+            .build();
+        row.add_prefix(&check);
+        inputs_group.add(&row);
+        input_checks.push((path.clone(), check));
+    }
+    if sources.is_empty() {
+        inputs_group.add(&adw::ActionRow::builder().title("No temperature sensors available").build());
+    }
+
+    // Difference: minuend - subtrahend
+    let (minuend, subtrahend) = match existing.map(|s| &s.formula) {
+        Some(VirtualFormula::Delta { minuend, subtrahend }) => (Some(minuend.as_str()), Some(subtrahend.as_str())),
+        _ => (None, None),
+    };
+    let delta_group = adw::PreferencesGroup::builder()
+        .title("Difference")
+        .description("First sensor minus second sensor (e.g. loop outlet minus inlet)")
+        .build();
+    page.add(&delta_group);
+    let minuend_row = source_row("Sensor", &sources, minuend);
+    let subtrahend_row = source_row("Minus", &sources, subtrahend);
+    delta_group.add(&minuend_row);
+    delta_group.add(&subtrahend_row);
+
+    // Corrected: input * scale + offset
+    let (input, scale, offset) = match existing.map(|s| &s.formula) {
+        Some(VirtualFormula::Linear { input, scale, offset }) => (Some(input.as_str()), *scale, *offset),
+        _ => (None, 1.0, 0.0),
+    };
+    let linear_group = adw::PreferencesGroup::builder()
+        .title("Correction")
+        .description("Reading × scale + offset, for sensors known to read high or low")
+        .build();
+    page.add(&linear_group);
+    let input_row = source_row("Sensor", &sources, input);
+    let scale_row = adw::SpinRow::builder()
+        .title("Scale")
+        .adjustment(&gtk4::Adjustment::new(
+            scale as f64,
+            0.01,
+            hf_core::constants::virtual_sensor::MAX_SCALE as f64,
+            0.01,
+            0.1,
+            0.0,
+        ))
+        .digits(2)
+        .build();
+    let max_offset = hf_core::constants::virtual_sensor::MAX_OFFSET_CELSIUS as f64;
+    let offset_row = adw::SpinRow::builder()
+        .title("Offset (°C)")
+        .adjustment(&gtk4::Adjustment::new(offset as f64, -max_offset, max_offset, 0.5, 5.0, 0.0))
+        .digits(1)
+        .build();
+    linear_group.add(&input_row);
+    linear_group.add(&scale_row);
+    linear_group.add(&offset_row);
+
+    // Only the selected formula's settings are shown
+    let show_formula = {
+        let inputs_group = inputs_group.clone();
+        let delta_group = delta_group.clone();
+        let linear_group = linear_group.clone();
+        move |selected: u32| {
+            inputs_group.set_visible(selected <= 2);
+            delta_group.set_visible(selected == 3);
+            linear_group.set_visible(selected == 4);
+        }
+    };
+    show_formula(formula_row.selected());
+    formula_row.connect_selected_notify(move |row| show_formula(row.selected()));
+
+    let toast_overlay = adw::ToastOverlay::new();
+    toast_overlay.set_child(Some(&page));
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&header);
+    toolbar.set_content(Some(&toast_overlay));
+    editor.set_content(Some(&toolbar));
+
+    let id = existing.map_or_else(hf_core::generate_guid, |s| s.id.clone());
+    let editor_save = editor.clone();
+    save_btn.connect_clicked(move |_| {
+        let name = name_row.text().trim().to_string();
+        if name.is_empty() {
+            toast_overlay.add_toast(adw::Toast::new("Enter a sensor name"));
+            return;
+        }
+
+        let source = |row: &adw::ComboRow| sources.get(row.selected() as usize).map(|(path, _)| path.clone());
+        let inputs: Vec<String> = input_checks
+            .iter()
+            .filter(|(_, check)| check.is_active())
+            .map(|(path, _)| path.clone())
+            .collect();
+        let formula = match formula_row.selected() {
+            0 => Some(VirtualFormula::Max { inputs }),
+            1 => Some(VirtualFormula::Min { inputs }),
+            2 => Some(VirtualFormula::Average { inputs }),
+            3 => source(&minuend_row)
+                .zip(source(&subtrahend_row))
+                .map(|(minuend, subtrahend)| VirtualFormula::Delta { minuend, subtrahend }),
+            _ => source(&input_row).map(|input| VirtualFormula::Linear {
+                input,
+                scale: scale_row.value() as f32,
+                offset: offset_row.value() as f32,
+            }),
+        };
+        let Some(formula) = formula else {
+            toast_overlay.add_toast(adw::Toast::new("Select the input sensors"));
+            return;
+        };
+
+        let sensor = VirtualSensor { id: id.clone(), name, formula };
+        if let Err(e) = sensor.validate() {
+            toast_overlay.add_toast(adw::Toast::new(&e));
+            return;
+        }
+        match hf_core::save_virtual_sensor(sensor) {
+            Ok(()) => {
+                signal_daemon();
+                if let Some(refresh) = refresh.upgrade() {
+                    reload(&refresh);
+                }
+                editor_save.close();
+            }
+            Err(e) => {
+                warn!("Failed to save virtual sensor: {}", e);
+                toast_overlay.add_toast(adw::Toast::new(&format!("Failed to save sensor: {}", e)));
+            }
+        }
+    });
+
+    editor.present();
+}
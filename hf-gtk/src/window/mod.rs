@@ -16,6 +16,8 @@
 //! │ Titlebar (custom window controls)           │
 //! ├─────────────────────────────────────────────┤
 //! │ Daemon Warning Banner (conditional)         │
+//! │ Read-only Banner (daemon not installed)     │
+//! │ Competing Software Banner (conditional)     │
 //! ├──────┬──────────────────────────────────────┤
 //! │ Nav  │                                      │
 //! │ Bar  │         Page Content                 │
@@ -58,7 +60,7 @@ use std::time::Duration;
 
 use crate::perf::{self, PerfCollector};
 use crate::runtime;
-use crate::widgets::{CurvesPage, Dashboard, DetectionDialog, FanPairingPage, GraphsPage, NavPage, SensorsPage, SettingsPage};
+use crate::widgets::{CurvesPage, Dashboard, DiagnosticsPage, FanPairingPage, GraphsPage, NavPage, SensorsPage, SettingsPage, SetupWizard};
 
 // ============================================================================
 // Constants
@@ -112,6 +114,9 @@ impl HyperfanWindow {
         let default_height = saved_settings.display.window_height.unwrap_or(DEFAULT_WINDOW_HEIGHT);
         let was_maximized = saved_settings.display.window_maximized.unwrap_or(false);
 
+        // Decide before any page loads hardware whether it comes from the daemon
+        crate::fallback::detect();
+
         let window = adw::ApplicationWindow::builder()
             .application(app)
             .title("Hyperfan")
@@ -138,9 +143,13 @@ impl HyperfanWindow {
             dialogs::show_support_dialog(btn);
         });
         
-        // Daemon health warning banner (below header)
-        let daemon_banner = crate::daemon_health::create_daemon_warning_banner();
-        toolbar_view.add_top_bar(&daemon_banner);
+        // Read-only mode banner (daemon not installed)
+        let fallback_banner = crate::fallback::create_banner();
+        toolbar_view.add_top_bar(&fallback_banner);
+
+        // Failed setup checks (service, drivers, other fan control software)
+        let diagnostics_banner = crate::diagnostics::create_banner();
+        toolbar_view.add_top_bar(&diagnostics_banner);
 
         // Main content box
         let root_box = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
@@ -185,7 +194,10 @@ impl HyperfanWindow {
             .build();
         nav_bar.append(&spacer);
 
-        // Settings button at bottom of nav bar
+        // Diagnostics and settings buttons at bottom of nav bar
+        let diagnostics_btn = create_nav_button("system-run-symbolic", "Diagnostics");
+        nav_bar.append(&diagnostics_btn);
+
         let settings_btn = create_nav_button("emblem-system-symbolic", "Settings");
         nav_bar.append(&settings_btn);
 
@@ -219,12 +231,62 @@ impl HyperfanWindow {
         let sensors = SensorsPage::new();
         stack.add_named(sensors.widget(), Some("sensors"));
 
+        // Reload pages when settings, curves or pairings are saved by any client
+        if let Some(mut rx) = runtime::subscribe_ui() {
+            let dash = dash.clone();
+            let fan_pairing = fan_pairing.clone();
+            glib::spawn_future_local(async move {
+                use hf_core::DaemonConfigSection as Section;
+                loop {
+                    let section = match rx.recv().await {
+                        Ok(runtime::UiUpdate::ConfigChanged(section)) => section,
+                        Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    };
+                    hf_core::invalidate_settings_cache();
+                    if matches!(section, Section::Curves | Section::All) {
+                        curves.refresh();
+                    }
+                    if matches!(section, Section::Pairings | Section::All) {
+                        fan_pairing.refresh();
+                    }
+                    dash.refresh();
+                }
+            });
+        }
+
+        // Rebuild the sensors page when the daemon reports hwmon/DRM hotplug
+        if let Some(mut rx) = runtime::subscribe_ui() {
+            let stack_for_hotplug = stack.clone();
+            glib::spawn_future_local(async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(runtime::UiUpdate::HardwareChanged(_)) => {}
+                        Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                    let was_visible = stack_for_hotplug.visible_child_name().as_deref() == Some("sensors");
+                    if let Some(old) = stack_for_hotplug.child_by_name("sensors") {
+                        stack_for_hotplug.remove(&old);
+                    }
+                    let sensors = SensorsPage::new();
+                    stack_for_hotplug.add_named(sensors.widget(), Some("sensors"));
+                    if was_visible {
+                        stack_for_hotplug.set_visible_child_name("sensors");
+                    }
+                }
+            });
+        }
+
         let graphs = GraphsPage::new();
         stack.add_named(graphs.widget(), Some("graphs"));
 
         let settings = Rc::new(SettingsPage::new());
         stack.add_named(settings.widget(), Some("settings"));
 
+        let diagnostics = DiagnosticsPage::new();
+        stack.add_named(diagnostics.widget(), Some("diagnostics"));
+
         // Set initial page based on user's default_page setting
         let app_settings = hf_core::get_cached_settings();
         let default_page = app_settings.general.default_page.as_str();
@@ -236,6 +298,11 @@ impl HyperfanWindow {
             "graphs" => "graphs",
             _ => "dashboard",
         };
+        // Control pages need the daemon
+        let initial_page = match initial_page {
+            "curves" | "fan_pairing" if crate::fallback::is_active() => "dashboard",
+            page => page,
+        };
         stack.set_visible_child_name(initial_page);
 
         content_box.append(&stack);
@@ -284,11 +351,6 @@ impl HyperfanWindow {
         let health_monitor = crate::daemon_health::DaemonHealthMonitor::new();
         health_monitor.check_health();
         health_monitor.start_monitoring(10);
-        
-        let health_for_retry = health_monitor.clone();
-        daemon_banner.connect_button_clicked(move |_| {
-            health_for_retry.check_health();
-        });
 
         *dashboard.borrow_mut() = Some(dash.clone());
 
@@ -300,6 +362,7 @@ impl HyperfanWindow {
             (NavPage::Sensors, sensors_btn.clone()),
             (NavPage::Graphs, graphs_btn.clone()),
             (NavPage::EcControl, ec_btn.clone()),
+            (NavPage::Diagnostics, diagnostics_btn.clone()),
         ];
 
         // Setup keyboard shortcuts for navigation
@@ -322,6 +385,11 @@ impl HyperfanWindow {
                 if current_page.as_deref() == Some("settings") && settings_page.has_unsaved_changes() {
                     return;
                 }
+                if matches!(nav_page, NavPage::Curves | NavPage::FanPairing)
+                    && !crate::fallback::require_daemon(&stack_for_keys)
+                {
+                    return;
+                }
                 stack_for_keys.set_visible_child_name(page_name);
                 set_active_button(&buttons_for_keys, nav_page);
                 settings_btn_for_keys.remove_css_class("suggested-action");
@@ -354,6 +422,10 @@ impl HyperfanWindow {
                     navigate("graphs", NavPage::Graphs);
                     glib::Propagation::Stop
                 }
+                gtk4::gdk::Key::p | gtk4::gdk::Key::P => {
+                    cycle_profile();
+                    glib::Propagation::Stop
+                }
                 gtk4::gdk::Key::comma => {
                     stack_for_keys.set_visible_child_name("settings");
                     for (_, btn) in &buttons_for_keys {
@@ -372,6 +444,9 @@ impl HyperfanWindow {
         let buttons_for_dash_nav: Vec<_> = buttons.iter().map(|(p, b)| (*p, b.clone())).collect();
         let settings_btn_for_dash_nav = settings_btn.clone();
         dash.connect_navigate_curves(move || {
+            if !crate::fallback::require_daemon(&stack_for_dash_nav) {
+                return;
+            }
             stack_for_dash_nav.set_visible_child_name("curves");
             set_active_button(&buttons_for_dash_nav, NavPage::Curves);
             settings_btn_for_dash_nav.remove_css_class("suggested-action");
@@ -399,6 +474,8 @@ impl HyperfanWindow {
             &ec_btn,
             &daemon_indicator,
             &fan_pairing,
+            &diagnostics,
+            &diagnostics_banner,
         );
 
         let this = Self { window, dashboard, perf_collector, settings_page: settings.clone() };
@@ -407,6 +484,7 @@ impl HyperfanWindow {
         let daemon_indicator_for_check = daemon_indicator.clone();
         let fan_pairing_for_check = fan_pairing.clone();
         let dashboard_for_check = dash.clone();
+        let fallback_banner_for_check = fallback_banner.clone();
         let was_available = Rc::new(RefCell::new(hf_core::is_daemon_available()));
         let last_error: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
         glib::timeout_add_local(Duration::from_secs(DAEMON_CHECK_INTERVAL_SECS), move || {
@@ -433,6 +511,10 @@ impl HyperfanWindow {
             }
             
             let prev_available = *was_available.borrow();
+            if is_available && crate::fallback::is_active() {
+                crate::fallback::deactivate();
+                fallback_banner_for_check.set_revealed(false);
+            }
             if is_available && !prev_available {
                 tracing::info!("Daemon became available - refreshing pages");
                 fan_pairing_for_check.refresh();
@@ -537,6 +619,8 @@ impl HyperfanWindow {
         ec_btn: &Button,
         daemon_indicator: &Button,
         fan_pairing: &Rc<FanPairingPage>,
+        diagnostics: &Rc<DiagnosticsPage>,
+        diagnostics_banner: &adw::Banner,
     ) {
         // Dashboard button
         let stack_clone = stack.clone();
@@ -578,6 +662,9 @@ impl HyperfanWindow {
         let settings_btn_for_curves = settings_btn.clone();
         let curves_btn = buttons.iter().find(|(p, _)| *p == NavPage::Curves).map(|(_, b)| b.clone()).unwrap();
         curves_btn.connect_clicked(move |btn| {
+            if !crate::fallback::require_daemon(btn) {
+                return;
+            }
             let stack = stack_clone.clone();
             let buttons = buttons_clone.clone();
             let settings_page = settings_for_curves.clone();
@@ -603,6 +690,9 @@ impl HyperfanWindow {
         let settings_btn_for_fan = settings_btn.clone();
         let fan_pairing_btn = buttons.iter().find(|(p, _)| *p == NavPage::FanPairing).map(|(_, b)| b.clone()).unwrap();
         fan_pairing_btn.connect_clicked(move |btn| {
+            if !crate::fallback::require_daemon(btn) {
+                return;
+            }
             let stack = stack_clone.clone();
             let buttons = buttons_clone.clone();
             let settings_page = settings_for_fan.clone();
@@ -671,9 +761,41 @@ impl HyperfanWindow {
             }
         });
 
+        // Diagnostics button, and the failed-check banner leading to it
+        let stack_clone = stack.clone();
+        let buttons_clone: Vec<_> = buttons.iter().map(|(p, b)| (*p, b.clone())).collect();
+        let settings_for_diagnostics = settings.clone();
+        let settings_btn_for_diagnostics = settings_btn.clone();
+        let diagnostics_for_nav = diagnostics.clone();
+        let open_diagnostics = Rc::new(move |widget: &gtk4::Widget| {
+            let stack = stack_clone.clone();
+            let buttons = buttons_clone.clone();
+            let settings_page = settings_for_diagnostics.clone();
+            let settings_btn = settings_btn_for_diagnostics.clone();
+            let diagnostics = diagnostics_for_nav.clone();
+            let show = move || {
+                stack.set_visible_child_name("diagnostics");
+                set_active_button(&buttons, NavPage::Diagnostics);
+                settings_btn.remove_css_class("suggested-action");
+                diagnostics.run();
+            };
+
+            if stack_clone.visible_child_name().as_deref() == Some("settings") && settings_page.has_unsaved_changes() {
+                show_unsaved_changes_dialog(widget, settings_page, show);
+            } else {
+                show();
+            }
+        });
+        let diagnostics_btn = buttons.iter().find(|(p, _)| *p == NavPage::Diagnostics).map(|(_, b)| b.clone()).unwrap();
+        let open_from_nav = open_diagnostics.clone();
+        diagnostics_btn.connect_clicked(move |btn| open_from_nav(btn.upcast_ref()));
+        diagnostics_banner.connect_button_clicked(move |banner| open_diagnostics(banner.upcast_ref()));
+
         // EC Control button
         ec_btn.connect_clicked(move |btn| {
-            dialogs::show_ec_control_dialog(btn);
+            if crate::fallback::require_daemon(btn) {
+                dialogs::show_ec_control_dialog(btn);
+            }
         });
 
         // Settings button
@@ -688,6 +810,24 @@ impl HyperfanWindow {
             settings_btn_for_click.add_css_class("suggested-action");
         });
 
+        // "Install Daemon" in read-only mode opens the settings install card
+        let stack_for_install = stack.clone();
+        let buttons_for_install: Vec<_> = buttons.iter().map(|(p, b)| (*p, b.clone())).collect();
+        let settings_for_install = settings.clone();
+        let settings_btn_for_install = settings_btn.clone();
+        crate::fallback::connect_install(move || {
+            stack_for_install.set_visible_child_name("settings");
+            for (_, btn) in &buttons_for_install {
+                btn.remove_css_class("suggested-action");
+            }
+            settings_btn_for_install.add_css_class("suggested-action");
+
+            let settings_page = settings_for_install.clone();
+            glib::timeout_add_local_once(Duration::from_millis(100), move || {
+                settings_page.flash_daemon_install_card();
+            });
+        });
+
         // Daemon indicator click
         let stack_for_daemon = stack.clone();
         let buttons_for_daemon: Vec<_> = buttons.iter().map(|(p, b)| (*p, b.clone())).collect();
@@ -756,25 +896,57 @@ impl HyperfanWindow {
 
     pub fn present(&self) {
         self.window.present();
-        self.check_daemon_connectivity();
-        self.check_first_run_detection();
+        // The setup assistant installs the daemon itself
+        if !self.check_first_run_setup() {
+            self.check_daemon_connectivity();
+        }
     }
 
     fn check_daemon_connectivity(&self) {
+        // The read-only banner already explains a missing daemon
+        if crate::fallback::is_active() {
+            return;
+        }
         if hf_core::daemon_list_hardware().is_err() {
             dialogs::show_daemon_not_running_dialog(&self.window, self.settings_page.clone());
         }
     }
 
-    fn check_first_run_detection(&self) {
+    /// Show the setup assistant until it has been finished once
+    /// Returns whether it was shown
+    fn check_first_run_setup(&self) -> bool {
         let detection_completed = hf_core::is_detection_completed().unwrap_or(false);
-        
-        if !detection_completed {
-            let dialog = DetectionDialog::new();
-            dialog.connect_complete(|mappings| {
-                tracing::info!("PWM-fan detection complete: {} mappings found", mappings.len());
-            });
-            dialog.present();
+        if detection_completed {
+            return false;
         }
+
+        let wizard = SetupWizard::new();
+        wizard.set_transient_for(&self.window);
+        let dashboard = self.dashboard.clone();
+        wizard.connect_finished(move || {
+            tracing::info!("First-run setup complete");
+            if let Some(ref dash) = *dashboard.borrow() {
+                dash.refresh();
+            }
+        });
+        wizard.present();
+        true
+    }
+}
+
+/// Switch to the next curve profile (Ctrl+P)
+fn cycle_profile() {
+    let settings = hf_core::get_cached_settings();
+    let profiles = settings.available_profiles();
+    let next = profiles.iter()
+        .position(|p| *p == settings.active_profile)
+        .map(|i| (i + 1) % profiles.len())
+        .unwrap_or(0);
+    
+    match hf_core::daemon_set_active_profile(&profiles[next]) {
+        Ok(info) => tracing::info!("Switched to profile '{}'", info.active),
+        Err(e) => tracing::warn!("Failed to switch profile: {}", e),
     }
+    // The daemon saved the new profile to settings.json
+    hf_core::invalidate_settings_cache();
 }
@@ -0,0 +1,151 @@
+//! Length-prefixed message framing
+//!
+//! Besides newline-delimited JSON (capped at `MAX_MESSAGE_SIZE`), the daemon
+//! accepts binary frames on the same socket:
+//!
+//! ```text
+//! magic (1 byte, 0xF5) | flags (1 byte, 0) | payload length (u32, big-endian) | payload
+//! ```
+//!
+//! The payload is the same JSON envelope a line would carry. 0xF5 never
+//! starts a UTF-8 text line, so the daemon tells the formats apart by the
+//! first byte of each message and answers in the format the request used.
+//! Clients learn whether the daemon supports frames from the `capabilities`
+//! of the `Version` response. No flags are defined yet; frames with any flag
+//! set are rejected.
+
+use serde::{Deserialize, Serialize};
+
+/// First byte of every frame
+pub const FRAME_MAGIC: u8 = 0xF5;
+
+/// Magic, flags, and payload length
+pub const FRAME_HEADER_LEN: usize = 6;
+
+/// Largest request payload the daemon accepts in a frame
+pub const MAX_FRAMED_REQUEST_SIZE: usize = 64 * 1024;
+
+/// Largest response payload clients accept in a frame
+pub const MAX_FRAME_SIZE: usize = 8 * 1024 * 1024;
+
+/// Message framings a daemon can speak
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Framing {
+    /// Newline-delimited JSON (every daemon)
+    JsonLines,
+    /// Binary frames as described in this module
+    LengthPrefixed,
+}
+
+/// What the daemon supports, reported in the `Version` response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Capabilities {
+    pub framing: Vec<Framing>,
+    /// Largest request payload accepted in a frame
+    pub max_request_size: u32,
+}
+
+impl Capabilities {
+    pub fn supports_frames(&self) -> bool {
+        self.framing.contains(&Framing::LengthPrefixed)
+    }
+}
+
+/// Header of a received frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHeader {
+    pub len: usize,
+}
+
+/// Frame `payload`
+pub fn encode_frame(payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(FRAME_HEADER_LEN + payload.len());
+    frame.push(FRAME_MAGIC);
+    frame.push(0);
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Why a frame header was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    BadMagic(u8),
+    UnknownFlags(u8),
+    Empty,
+    TooLarge { len: usize, max: usize },
+}
+
+impl std::fmt::Display for FrameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameError::BadMagic(byte) => write!(f, "Invalid frame magic 0x{:02X}", byte),
+            FrameError::UnknownFlags(flags) => write!(f, "Unknown frame flags 0x{:02X}", flags),
+            FrameError::Empty => write!(f, "Empty frame"),
+            FrameError::TooLarge { len, max } => write!(f, "Frame too large ({} bytes, max {})", len, max),
+        }
+    }
+}
+
+impl std::error::Error for FrameError {}
+
+/// Parse a frame header, rejecting empty payloads and those longer than `max_len`
+pub fn parse_header(header: &[u8; FRAME_HEADER_LEN], max_len: usize) -> Result<FrameHeader, FrameError> {
+    if header[0] != FRAME_MAGIC {
+        return Err(FrameError::BadMagic(header[0]));
+    }
+    if header[1] != 0 {
+        return Err(FrameError::UnknownFlags(header[1]));
+    }
+    let len = u32::from_be_bytes([header[2], header[3], header[4], header[5]]) as usize;
+    if len == 0 {
+        return Err(FrameError::Empty);
+    }
+    if len > max_len {
+        return Err(FrameError::TooLarge { len, max: max_len });
+    }
+    Ok(FrameHeader { len })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(frame: &[u8]) -> [u8; FRAME_HEADER_LEN] {
+        frame[..FRAME_HEADER_LEN].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let payload = br#"{"id":1,"request":{"type":"version"}}"#;
+        let frame = encode_frame(payload);
+        assert_eq!(frame.len(), FRAME_HEADER_LEN + payload.len());
+
+        let parsed = parse_header(&header(&frame), MAX_FRAMED_REQUEST_SIZE).unwrap();
+        assert_eq!(parsed, FrameHeader { len: payload.len() });
+        assert_eq!(&frame[FRAME_HEADER_LEN..], payload);
+    }
+
+    #[test]
+    fn test_rejected_headers() {
+        let mut bad_magic = header(&encode_frame(b"{}"));
+        bad_magic[0] = b'{';
+        assert_eq!(parse_header(&bad_magic, MAX_FRAME_SIZE), Err(FrameError::BadMagic(b'{')));
+
+        let mut flags = header(&encode_frame(b"{}"));
+        flags[1] = 0x01;
+        assert_eq!(parse_header(&flags, MAX_FRAME_SIZE), Err(FrameError::UnknownFlags(0x01)));
+
+        assert_eq!(parse_header(&header(&encode_frame(b"")), MAX_FRAME_SIZE), Err(FrameError::Empty));
+
+        let over = vec![b' '; MAX_FRAMED_REQUEST_SIZE + 1];
+        assert_eq!(
+            parse_header(&header(&encode_frame(&over)), MAX_FRAMED_REQUEST_SIZE),
+            Err(FrameError::TooLarge { len: over.len(), max: MAX_FRAMED_REQUEST_SIZE })
+        );
+        // Exactly at the limit is fine
+        let at_limit = vec![b' '; MAX_FRAMED_REQUEST_SIZE];
+        assert!(parse_header(&header(&encode_frame(&at_limit)), MAX_FRAMED_REQUEST_SIZE).is_ok());
+    }
+}
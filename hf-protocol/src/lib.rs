@@ -1,22 +1,55 @@
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU64, Ordering};
 
+pub mod frame;
+
+pub use frame::Capabilities;
+
 /// Global request ID counter for correlation
 static REQUEST_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
-/// Maximum message size for IPC (8KB)
+/// Maximum message size for newline-delimited JSON (8KB)
+/// Length-prefixed frames allow more (`frame::MAX_FRAMED_REQUEST_SIZE`)
 pub const MAX_MESSAGE_SIZE: usize = 8 * 1024;
 
+/// Maximum response size accepted by clients (1MB)
+/// Hardware listings with voltage/current/power channels exceed MAX_MESSAGE_SIZE
+pub const MAX_RESPONSE_SIZE: usize = 1024 * 1024;
+
 /// Maximum path length for security validation
 const MAX_PATH_LENGTH: usize = 256;
 
 /// Maximum EC register count per read operation
 const MAX_EC_REGISTER_COUNT: u8 = 64;
 
-const ALLOWED_PATH_PREFIXES: &[&str] = &["/sys/class/hwmon/", "/sys/devices/"];
+const ALLOWED_PATH_PREFIXES: &[&str] = &["/sys/class/hwmon/", "/sys/class/thermal/", "/sys/devices/"];
 
 const ALLOWED_VIRTUAL_PWM_PREFIXES: &[&str] = &["nvidia:", "amd:", "intel:"];
 
+/// Path prefix for FreeBSD sysctl sensors and controls ("sysctl:dev.cpu.0.temperature")
+pub const SYSCTL_PATH_PREFIX: &str = "sysctl:";
+
+/// Path prefix for smartctl drive temperatures ("smart:sda")
+pub const SMART_PATH_PREFIX: &str = "smart:";
+
+/// Path prefix for BMC sensors and fan zones ("ipmi:temp:cpu_temp", "ipmi:zone:0")
+pub const IPMI_PATH_PREFIX: &str = "ipmi:";
+
+/// Path prefix for user-defined virtual sensors ("virtual:<id>", evaluated by the daemon)
+pub const VIRTUAL_PATH_PREFIX: &str = "virtual:";
+
+/// Path prefix for external sensor provider readings ("provider:<provider>/<sensor>")
+pub const PROVIDER_PATH_PREFIX: &str = "provider:";
+
+/// Longest virtual sensor ID, provider name, or provider sensor name
+pub const MAX_SOURCE_ID_LENGTH: usize = 64;
+
+/// Longest block device name accepted in a smartctl path
+const MAX_SMART_DEVICE_LENGTH: usize = 32;
+
+/// sysctl subtrees clients may name (CPU temps, ACPI thermal zones, ThinkPad fan)
+const ALLOWED_SYSCTL_PREFIXES: &[&str] = &["dev.cpu.", "hw.acpi.thermal.", "dev.acpi_ibm."];
+
 const FORBIDDEN_PATH_COMPONENTS: &[&str] = &[
     "..",      // Path traversal
     "//",      // Double slash (path normalization bypass)
@@ -76,31 +109,185 @@ pub enum Request {
     SetPwm { path: String, value: u8 },
     EnableManualPwm { path: String },
     DisableManualPwm { path: String },
+    /// Hold output `path` at `value` instead of its curve for `ttl_ms`
     SetPwmOverride { path: String, value: u8, ttl_ms: u32 },
     ClearPwmOverride { path: String },
+    /// List the overrides currently holding outputs, with who set them
+    ListActiveOverrides,
+    /// Other fan control software running, and controlled outputs whose
+    /// `pwmN_enable` mode something else changed
+    GetCompetingSoftware,
+    /// Drive every output following curve `curve_id` from `points` for
+    /// `duration_ms`, then revert to the saved curve; nothing is saved
+    PreviewCurve {
+        curve_id: String,
+        points: Vec<(f32, f32)>,
+        #[serde(default)]
+        interpolation: CurveInterpolation,
+        duration_ms: u32,
+    },
+    /// End a curve preview early
+    ClearCurvePreview { curve_id: String },
+    /// Whether the daemon is in dry-run mode
+    GetDryRun,
+    /// Turn dry-run mode on or off: the control loop keeps evaluating curves but
+    /// only logs the PWM writes it would make, with the sensor and curve behind
+    /// each. Turning it on hands the fans back to firmware control meanwhile.
+    SetDryRun { enabled: bool },
+    /// Whether the daemon runs in safe mode (`hyperfand --safe-mode` or the
+    /// `/etc/hyperfan/DISABLE` sentinel): fans under firmware control, nothing written
+    GetSafeMode,
+    /// Control loop status, with counters of sensor readings rejected as implausible
+    GetControlStatus,
     ListGpus,
     SetGpuFan { index: u32, fan_index: Option<u32>, percent: u32 },
     ResetGpuFanAuto { index: u32 },
+    /// Get the firmware fan curve of AMD GPU card `index` (PMFW, RDNA3+)
+    GetGpuFanCurve { index: u32 },
+    /// Replace the firmware fan curve; stays active while hyperfand is stopped
+    SetGpuFanCurve { index: u32, points: Vec<GpuFanCurvePoint> },
+    /// Restore the factory firmware fan curve
+    ResetGpuFanCurve { index: u32 },
+    /// Get the board power limit of GPU `index` and the range it accepts
+    GetGpuPowerLimit { index: u32 },
+    /// Set the board power limit (needs `advanced.gpu_power_limits_enabled`)
+    SetGpuPowerLimit { index: u32, watts: u32 },
+    /// Restore the factory power limit
+    ResetGpuPowerLimit { index: u32 },
     DetectFanMappings,
+    /// Start fan detection in the background; returns the job's progress
+    StartDetection {
+        #[serde(default)]
+        strategy: DetectionStrategy,
+    },
+    /// Progress of detection job `job_id` (mappings once it has completed)
+    GetDetectionProgress { job_id: u64 },
+    /// Abort detection job `job_id` and restore the original PWM state
+    CancelDetection { job_id: u64 },
+    /// Step the outputs of curve `curve_id` down from full speed while the
+    /// user runs a load, and derive the quietest curve holding `target_temp`
+    /// Runs in the background; poll with `GetAutoTuneProgress`
+    StartAutoTune { curve_id: String, target_temp: f32 },
+    /// Progress of auto-tune job `job_id` (the suggested curve once completed)
+    GetAutoTuneProgress { job_id: u64 },
+    /// Abort auto-tune job `job_id` and hand the outputs back to their curves
+    CancelAutoTune { job_id: u64 },
+    /// List fingerprinted bindings that no longer validate against the
+    /// current hardware, each with the channels that could be the same fan
+    GetBindingConflicts,
+    /// Confirm that `pwm_path` (and `fan_path`) is the hardware of binding
+    /// `pwm_id`; the binding is re-fingerprinted there and marked valid
+    AcceptBindingMatch {
+        pwm_id: String,
+        pwm_path: String,
+        #[serde(default)]
+        fan_path: Option<String>,
+    },
     ReloadConfig,
+    /// Reload one part of the configuration after editing it on disk;
+    /// announced as `ConfigChanged` for that section only
+    ReloadConfigSection { section: ConfigSection },
     GetManualPairings,
     SetManualPairing { 
         pwm_uuid: String,
         pwm_path: String, 
         fan_uuid: Option<String>,
         fan_path: Option<String>,
+        /// Response tuning for this PWM (None keeps the existing tuning)
+        #[serde(default)]
+        tuning: Option<CurveTuning>,
     },
     DeleteManualPairing { pwm_path: String },
+    /// Switch a pairing between duty-cycle and closed-loop RPM control
+    SetPairingControlMode { pwm_path: String, mode: PairingControlMode },
+    /// Allow a pairing's curve to stop the fan below its calibrated stall duty
+    SetPairingZeroRpm { pwm_path: String, enabled: bool },
+    /// Choose what the control loop does when another program writes a pairing's PWM
+    SetPairingEnforcement { pwm_path: String, policy: PwmEnforcement },
+    /// Start sweeping a PWM output and recording the paired fan's RPM
+    /// Runs in the background; poll with `GetFanCalibration`
+    CalibrateFan { pwm_path: String, fan_path: String },
+    /// Get the stored calibration of a PWM output and whether a sweep is running
+    GetFanCalibration { pwm_path: String },
     ListEcChips,
     ReadEcRegister { chip_path: String, register: u8 },
     WriteEcRegister { chip_path: String, register: u8, value: u8 },
     ReadEcRegisterRange { chip_path: String, start_register: u8, count: u8 },
+    /// Sample a register range `samples` times, `interval_ms` apart, and
+    /// return the registers whose value changed
+    WatchEcRegisters { chip_path: String, start_register: u8, count: u8, interval_ms: u32, samples: u32 },
+    /// Drive fan `fan` of the chip's known-model profile (`EcChipInfo::profile`)
+    /// `percent: None` hands the fan back to the EC's automatic control
+    SetEcFanDuty { chip_path: String, fan: u8, percent: Option<u32> },
     SetGlobalMode { mode: GlobalMode },
     GetGlobalMode,
     /// Get current daemon rate limit
     GetRateLimit,
-    /// Set daemon rate limit (1500-9999 requests per 10s window)
-    SetRateLimit { limit: u32 },
+    /// Set daemon rate limit (1500-9999 tokens refilled per 10s window)
+    /// `burst` and `costs` are left unchanged when omitted
+    SetRateLimit {
+        limit: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        burst: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        costs: Option<RequestCosts>,
+    },
+    /// Get fan failure alerts newer than `since_id` (0 = all retained alerts)
+    GetFanAlerts { since_id: u64 },
+    /// Get hwmon/DRM hotplug events newer than `since_id` (0 = all retained events)
+    GetHardwareChanges { since_id: u64 },
+    /// Start recording sensor snapshots every `interval_ms` (JSONL for replay, or CSV)
+    /// The recording stops by itself after `max_duration_ms` or once the file
+    /// reaches `max_bytes`
+    StartRecording {
+        interval_ms: u32,
+        #[serde(default)]
+        format: RecordingFormat,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_duration_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_bytes: Option<u64>,
+    },
+    /// Stop the active recording
+    StopRecording,
+    /// Get the state of the active recording, or of the last one stopped
+    GetRecording,
+    /// Get stored sensor history between `start_ms` and `end_ms` (Unix ms)
+    /// Points are at least `resolution_secs` apart (0 = finest stored); the daemon
+    /// coarsens them further so one response holds at most `MAX_HISTORY_POINTS`.
+    /// `paths` limits the series returned (empty = every series)
+    QueryHistory {
+        start_ms: u64,
+        end_ms: u64,
+        #[serde(default)]
+        resolution_secs: u32,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        paths: Vec<String>,
+    },
+    /// Get the active curve profile and the profiles available
+    GetActiveProfile,
+    /// Switch every pair to its curve for profile `name` (e.g. quiet, normal, performance)
+    SetActiveProfile { name: String },
+    /// Get daemon log entries newer than `since_id` (0 = all retained entries),
+    /// optionally only those at `min_level` or more severe
+    GetLogs {
+        since_id: u64,
+        #[serde(default)]
+        min_level: Option<LogLevel>,
+    },
+    /// Get the configured PWM groups
+    GetPwmGroups,
+    /// Create a PWM group, or replace the group with the same ID
+    SetPwmGroup { group: PwmGroup },
+    DeletePwmGroup { id: String },
+    /// Receive daemon events on this connection as they happen
+    /// Replays retained events newer than `since_seq` first (None = only new events);
+    /// if the daemon restarted since `since_seq` was seen, every retained event is replayed.
+    /// Events arrive as `EventEnvelope` lines between responses. Socket only.
+    Subscribe {
+        #[serde(default)]
+        since_seq: Option<u64>,
+    },
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -112,21 +299,107 @@ pub enum GlobalMode {
     Manual,
 }
 
+/// Rate limiter cost classes
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestClass {
+    /// Single sensor reads and status queries
+    Read,
+    /// Full hardware scans and EC register dumps
+    Scan,
+    /// Changes to fan speeds, EC registers, or daemon configuration
+    Write,
+    /// Long-running jobs that drive fans or poll the EC (detection, calibration, watches)
+    Heavy,
+}
+
+/// Tokens each request class takes from the client's bucket
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequestCosts {
+    pub read: u32,
+    pub scan: u32,
+    pub write: u32,
+    pub heavy: u32,
+}
+
+impl Default for RequestCosts {
+    fn default() -> Self {
+        Self { read: 1, scan: 5, write: 2, heavy: 100 }
+    }
+}
+
+impl RequestCosts {
+    pub fn cost(&self, class: RequestClass) -> u32 {
+        match class {
+            RequestClass::Read => self.read,
+            RequestClass::Scan => self.scan,
+            RequestClass::Write => self.write,
+            RequestClass::Heavy => self.heavy,
+        }
+    }
+}
+
+/// Daemon token-bucket rate limiter configuration (buckets are per UID)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// Tokens refilled per 10s window
+    pub limit: u32,
+    /// Bucket capacity: tokens a client can spend at once
+    pub burst: u32,
+    pub costs: RequestCosts,
+}
+
 impl Request {
     /// Validate request parameters before sending to daemon
     pub fn validate(&self) -> Result<(), String> {
         match self {
             Request::Ping | Request::Version | Request::ListHardware 
             | Request::ListAll | Request::ListGpus | Request::DetectFanMappings 
-            | Request::ReloadConfig | Request::GetManualPairings
+            | Request::StartDetection { .. } | Request::GetDetectionProgress { .. }
+            | Request::CancelDetection { .. } | Request::GetAutoTuneProgress { .. }
+            | Request::CancelAutoTune { .. }
+            | Request::ReloadConfig | Request::ReloadConfigSection { .. } | Request::GetManualPairings
             | Request::ListEcChips | Request::GetGlobalMode => Ok(()),
             
             Request::SetGlobalMode { mode: _ } => Ok(()),
             
             Request::GetRateLimit => Ok(()),
-            Request::SetRateLimit { limit } => validate_rate_limit(*limit),
+            Request::SetRateLimit { limit, burst, costs } => {
+                validate_rate_limit(*limit)?;
+                if let Some(burst) = burst {
+                    validate_rate_limit_burst(*burst)?;
+                }
+                if let Some(costs) = costs {
+                    validate_request_costs(costs)?;
+                }
+                Ok(())
+            }
+            
+            Request::GetFanAlerts { since_id: _ } => Ok(()),
+            Request::GetHardwareChanges { since_id: _ } => Ok(()),
+            Request::GetLogs { .. } => Ok(()),
+            Request::Subscribe { .. } => Ok(()),
+
+            Request::GetPwmGroups => Ok(()),
+            Request::SetPwmGroup { group } => validate_pwm_group(group),
+            Request::DeletePwmGroup { id } => validate_group_id(id),
+            
+            Request::StartRecording { interval_ms, format: _, max_duration_ms, max_bytes } => {
+                validate_recording_interval(*interval_ms)?;
+                validate_recording_limits(*max_duration_ms, *max_bytes)
+            }
+            Request::StopRecording | Request::GetRecording => Ok(()),
+            Request::QueryHistory { start_ms, end_ms, resolution_secs, paths } => {
+                validate_history_query(*start_ms, *end_ms, *resolution_secs, paths)
+            }
+            
+            Request::GetDryRun | Request::SetDryRun { .. } | Request::GetSafeMode
+            | Request::GetControlStatus => Ok(()),
+
+            Request::GetActiveProfile => Ok(()),
+            Request::SetActiveProfile { name } => validate_profile_name(name),
             
-            Request::ReadTemperature { path } => validate_hwmon_path(path),
+            Request::ReadTemperature { path } => validate_temp_source_path(path),
             Request::ReadFanRpm { path } => validate_hwmon_path(path),
             Request::ReadPwm { path } => validate_hwmon_path(path),
             
@@ -147,6 +420,24 @@ impl Request {
             }
             
             Request::ClearPwmOverride { path } => validate_pwm_target_path(path),
+            Request::ListActiveOverrides => Ok(()),
+            Request::GetCompetingSoftware => Ok(()),
+
+            Request::PreviewCurve { curve_id, points, interpolation: _, duration_ms } => {
+                validate_curve_preview(curve_id, points, *duration_ms)
+            }
+            Request::ClearCurvePreview { curve_id } => validate_curve_id(curve_id),
+            Request::StartAutoTune { curve_id, target_temp } => validate_auto_tune(curve_id, *target_temp),
+
+            Request::GetBindingConflicts => Ok(()),
+            Request::AcceptBindingMatch { pwm_id, pwm_path, fan_path } => {
+                validate_binding_id(pwm_id)?;
+                validate_hwmon_path(pwm_path)?;
+                if let Some(fp) = fan_path {
+                    validate_hwmon_path(fp)?;
+                }
+                Ok(())
+            }
             
             Request::SetGpuFan { index, fan_index, percent } => {
                 validate_gpu_index(*index)?;
@@ -164,15 +455,52 @@ impl Request {
                 Ok(())
             }
             
-            Request::SetManualPairing { pwm_uuid: _, pwm_path, fan_uuid: _, fan_path } => {
+            Request::GetGpuFanCurve { index } | Request::ResetGpuFanCurve { index } => {
+                validate_gpu_index(*index)?;
+                Ok(())
+            }
+            
+            Request::SetGpuFanCurve { index, points } => {
+                validate_gpu_index(*index)?;
+                validate_gpu_fan_curve(points)
+            }
+            
+            Request::GetGpuPowerLimit { index } | Request::ResetGpuPowerLimit { index } => {
+                validate_gpu_index(*index)?;
+                Ok(())
+            }
+            
+            Request::SetGpuPowerLimit { index, watts } => {
+                validate_gpu_index(*index)?;
+                validate_gpu_power_limit(*watts)
+            }
+            
+            Request::SetManualPairing { pwm_uuid: _, pwm_path, fan_uuid: _, fan_path, tuning } => {
                 validate_pwm_target_path(pwm_path)?;
                 if let Some(fp) = fan_path {
                     validate_hwmon_path(fp)?;
                 }
+                if let Some(t) = tuning {
+                    validate_curve_tuning(t)?;
+                }
                 Ok(())
             }
             
             Request::DeleteManualPairing { pwm_path } => validate_pwm_target_path(pwm_path),
+
+            Request::SetPairingControlMode { pwm_path, mode } => {
+                validate_pwm_target_path(pwm_path)?;
+                validate_pairing_control_mode(mode)
+            }
+
+            Request::SetPairingZeroRpm { pwm_path, .. } => validate_pwm_target_path(pwm_path),
+            Request::SetPairingEnforcement { pwm_path, .. } => validate_pwm_target_path(pwm_path),
+
+            Request::CalibrateFan { pwm_path, fan_path } => {
+                validate_pwm_target_path(pwm_path)?;
+                validate_hwmon_path(fan_path)
+            }
+            Request::GetFanCalibration { pwm_path } => validate_pwm_target_path(pwm_path),
             
             Request::ReadEcRegister { chip_path, register: _ } => {
                 validate_hwmon_path(chip_path)
@@ -187,9 +515,110 @@ impl Request {
                 validate_ec_register_count(*count)?;
                 Ok(())
             }
+
+            Request::WatchEcRegisters { chip_path, start_register: _, count, interval_ms, samples } => {
+                validate_hwmon_path(chip_path)?;
+                validate_ec_register_count(*count)?;
+                validate_ec_watch(*interval_ms, *samples)
+            }
+
+            Request::SetEcFanDuty { chip_path, fan: _, percent } => {
+                validate_hwmon_path(chip_path)?;
+                if let Some(percent) = percent {
+                    validate_percent(*percent)?;
+                }
+                Ok(())
+            }
+        }
+    }
+    
+    /// Whether the request only reads state (sensors, hardware lists, settings)
+    /// Anything that changes fan speeds, EC registers, or daemon configuration is not read-only
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            Request::Ping | Request::Version | Request::ListHardware | Request::ListAll
+            | Request::ReadTemperature { .. } | Request::ReadFanRpm { .. } | Request::ReadPwm { .. }
+            | Request::ListGpus | Request::GetManualPairings | Request::ListEcChips
+            | Request::ReadEcRegister { .. } | Request::ReadEcRegisterRange { .. }
+            | Request::WatchEcRegisters { .. }
+            | Request::GetGlobalMode | Request::GetRateLimit | Request::GetFanAlerts { .. }
+            | Request::GetHardwareChanges { .. } | Request::GetFanCalibration { .. }
+            | Request::GetDetectionProgress { .. } | Request::GetLogs { .. } | Request::GetPwmGroups
+            | Request::GetAutoTuneProgress { .. } | Request::GetBindingConflicts
+            | Request::Subscribe { .. } | Request::QueryHistory { .. } | Request::GetRecording
+            | Request::GetActiveProfile | Request::GetGpuFanCurve { .. } | Request::GetGpuPowerLimit { .. }
+            | Request::GetDryRun | Request::GetSafeMode | Request::GetControlStatus
+            | Request::ListActiveOverrides | Request::GetCompetingSoftware => true,
+
+            // DetectFanMappings pulses every PWM output to find its fan
+            Request::SetPwm { .. } | Request::EnableManualPwm { .. } | Request::DisableManualPwm { .. }
+            | Request::SetPwmOverride { .. } | Request::ClearPwmOverride { .. }
+            | Request::PreviewCurve { .. } | Request::ClearCurvePreview { .. } | Request::SetDryRun { .. }
+            | Request::SetGpuFan { .. } | Request::ResetGpuFanAuto { .. }
+            | Request::SetGpuFanCurve { .. } | Request::ResetGpuFanCurve { .. }
+            | Request::SetGpuPowerLimit { .. } | Request::ResetGpuPowerLimit { .. }
+            | Request::DetectFanMappings | Request::StartDetection { .. } | Request::CancelDetection { .. }
+            | Request::StartAutoTune { .. } | Request::CancelAutoTune { .. }
+            | Request::AcceptBindingMatch { .. }
+            | Request::ReloadConfig | Request::ReloadConfigSection { .. }
+            | Request::SetManualPairing { .. } | Request::DeleteManualPairing { .. }
+            | Request::SetPwmGroup { .. } | Request::DeletePwmGroup { .. }
+            | Request::SetPairingControlMode { .. } | Request::SetPairingZeroRpm { .. }
+            | Request::SetPairingEnforcement { .. }
+            | Request::CalibrateFan { .. }
+            | Request::WriteEcRegister { .. } | Request::SetEcFanDuty { .. } | Request::SetGlobalMode { .. }
+            | Request::SetRateLimit { .. }
+            | Request::StartRecording { .. } | Request::StopRecording
+            | Request::SetActiveProfile { .. } => false,
         }
     }
     
+    /// Configuration the request changes when it succeeds (announced as `ConfigChanged`)
+    pub fn config_section(&self) -> Option<ConfigSection> {
+        match self {
+            Request::ReloadConfig => Some(ConfigSection::All),
+            Request::ReloadConfigSection { section } => Some(*section),
+            Request::SetManualPairing { .. } | Request::DeleteManualPairing { .. }
+            | Request::SetPairingControlMode { .. } | Request::SetPairingZeroRpm { .. }
+            | Request::SetPairingEnforcement { .. } | Request::AcceptBindingMatch { .. }
+            | Request::DetectFanMappings => Some(ConfigSection::Pairings),
+            Request::SetPwmGroup { .. } | Request::DeletePwmGroup { .. }
+            | Request::SetActiveProfile { .. } => Some(ConfigSection::Settings),
+            _ => None,
+        }
+    }
+
+    /// Whether the request is served on the unauthenticated monitoring socket
+    /// Sensor readings, hardware lists, and status only - no EC access, logs, or detection
+    pub fn is_monitoring(&self) -> bool {
+        matches!(
+            self,
+            Request::Ping | Request::Version | Request::ListHardware | Request::ListAll
+            | Request::ReadTemperature { .. } | Request::ReadFanRpm { .. } | Request::ReadPwm { .. }
+            | Request::ListGpus | Request::GetGlobalMode | Request::GetFanAlerts { .. }
+            | Request::GetHardwareChanges { .. } | Request::GetActiveProfile | Request::GetPwmGroups
+            | Request::Subscribe { .. } | Request::QueryHistory { .. } | Request::GetDryRun
+            | Request::GetSafeMode | Request::GetControlStatus | Request::ListActiveOverrides
+            | Request::GetCompetingSoftware
+        )
+    }
+
+    /// Rate limiter cost class of the request
+    pub fn class(&self) -> RequestClass {
+        match self {
+            Request::ListHardware | Request::ListAll | Request::ListGpus | Request::ListEcChips
+            | Request::ReadEcRegisterRange { .. } | Request::GetLogs { .. }
+            | Request::QueryHistory { .. } | Request::GetBindingConflicts
+            | Request::GetCompetingSoftware => RequestClass::Scan,
+
+            Request::DetectFanMappings | Request::StartDetection { .. } | Request::CalibrateFan { .. }
+            | Request::StartAutoTune { .. } | Request::WatchEcRegisters { .. } => RequestClass::Heavy,
+
+            request if request.is_read_only() => RequestClass::Read,
+            _ => RequestClass::Write,
+        }
+    }
+
     pub fn type_name(&self) -> &'static str {
         match self {
             Request::Ping => "Ping",
@@ -204,22 +633,65 @@ impl Request {
             Request::DisableManualPwm { .. } => "DisableManualPwm",
             Request::SetPwmOverride { .. } => "SetPwmOverride",
             Request::ClearPwmOverride { .. } => "ClearPwmOverride",
+            Request::ListActiveOverrides => "ListActiveOverrides",
+            Request::GetCompetingSoftware => "GetCompetingSoftware",
+            Request::PreviewCurve { .. } => "PreviewCurve",
+            Request::ClearCurvePreview { .. } => "ClearCurvePreview",
             Request::ListGpus => "ListGpus",
             Request::SetGpuFan { .. } => "SetGpuFan",
             Request::ResetGpuFanAuto { .. } => "ResetGpuFanAuto",
+            Request::GetGpuFanCurve { .. } => "GetGpuFanCurve",
+            Request::SetGpuFanCurve { .. } => "SetGpuFanCurve",
+            Request::ResetGpuFanCurve { .. } => "ResetGpuFanCurve",
+            Request::GetGpuPowerLimit { .. } => "GetGpuPowerLimit",
+            Request::SetGpuPowerLimit { .. } => "SetGpuPowerLimit",
+            Request::ResetGpuPowerLimit { .. } => "ResetGpuPowerLimit",
             Request::DetectFanMappings => "DetectFanMappings",
+            Request::StartDetection { .. } => "StartDetection",
+            Request::GetDetectionProgress { .. } => "GetDetectionProgress",
+            Request::CancelDetection { .. } => "CancelDetection",
+            Request::StartAutoTune { .. } => "StartAutoTune",
+            Request::GetAutoTuneProgress { .. } => "GetAutoTuneProgress",
+            Request::CancelAutoTune { .. } => "CancelAutoTune",
+            Request::GetBindingConflicts => "GetBindingConflicts",
+            Request::AcceptBindingMatch { .. } => "AcceptBindingMatch",
             Request::ReloadConfig => "ReloadConfig",
+            Request::ReloadConfigSection { .. } => "ReloadConfigSection",
             Request::GetManualPairings => "GetManualPairings",
             Request::SetManualPairing { .. } => "SetManualPairing",
             Request::DeleteManualPairing { .. } => "DeleteManualPairing",
+            Request::SetPairingControlMode { .. } => "SetPairingControlMode",
+            Request::SetPairingZeroRpm { .. } => "SetPairingZeroRpm",
+            Request::SetPairingEnforcement { .. } => "SetPairingEnforcement",
+            Request::CalibrateFan { .. } => "CalibrateFan",
+            Request::GetFanCalibration { .. } => "GetFanCalibration",
             Request::ListEcChips => "ListEcChips",
             Request::ReadEcRegister { .. } => "ReadEcRegister",
             Request::WriteEcRegister { .. } => "WriteEcRegister",
             Request::ReadEcRegisterRange { .. } => "ReadEcRegisterRange",
+            Request::WatchEcRegisters { .. } => "WatchEcRegisters",
+            Request::SetEcFanDuty { .. } => "SetEcFanDuty",
             Request::SetGlobalMode { .. } => "SetGlobalMode",
             Request::GetGlobalMode => "GetGlobalMode",
             Request::GetRateLimit => "GetRateLimit",
             Request::SetRateLimit { .. } => "SetRateLimit",
+            Request::GetFanAlerts { .. } => "GetFanAlerts",
+            Request::GetHardwareChanges { .. } => "GetHardwareChanges",
+            Request::StartRecording { .. } => "StartRecording",
+            Request::StopRecording => "StopRecording",
+            Request::GetRecording => "GetRecording",
+            Request::QueryHistory { .. } => "QueryHistory",
+            Request::GetDryRun => "GetDryRun",
+            Request::SetDryRun { .. } => "SetDryRun",
+            Request::GetSafeMode => "GetSafeMode",
+            Request::GetControlStatus => "GetControlStatus",
+            Request::GetActiveProfile => "GetActiveProfile",
+            Request::SetActiveProfile { .. } => "SetActiveProfile",
+            Request::GetLogs { .. } => "GetLogs",
+            Request::GetPwmGroups => "GetPwmGroups",
+            Request::SetPwmGroup { .. } => "SetPwmGroup",
+            Request::DeletePwmGroup { .. } => "DeletePwmGroup",
+            Request::Subscribe { .. } => "Subscribe",
         }
     }
 }
@@ -277,9 +749,57 @@ pub struct ResponseData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ec_registers: Option<Vec<EcRegisterValue>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub ec_changes: Option<Vec<EcRegisterChange>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub global_mode: Option<GlobalMode>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_config: Option<RateLimitConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fan_alerts: Option<Vec<FanAlert>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recording: Option<RecordingInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<HistoryData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<ProfileInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu_fan_curve: Option<GpuFanCurveInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu_power_limit: Option<GpuPowerLimitInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hardware_changes: Option<Vec<HardwareChange>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calibration_status: Option<CalibrationStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detection: Option<DetectionProgress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_tune: Option<AutoTuneProgress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binding_conflicts: Option<Vec<BindingConflict>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_overrides: Option<Vec<ActiveOverride>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub competing_software: Option<CompetingSoftwareReport>,
+    /// Whether PWM writes are only logged (GetDryRun/SetDryRun)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+    /// Whether the daemon runs in monitoring-only safe mode (GetSafeMode)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_mode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub control_status: Option<ControlStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logs: Option<Vec<LogEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pwm_groups: Option<Vec<PwmGroup>>,
+    /// Replayed events (Subscribe)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<EventEnvelope>>,
+    /// Supported framings (Version)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<Capabilities>,
 }
 
 impl Default for ResponseData {
@@ -297,8 +817,30 @@ impl Default for ResponseData {
             ec_chips: None,
             ec_register: None,
             ec_registers: None,
+            ec_changes: None,
             global_mode: None,
             rate_limit: None,
+            rate_limit_config: None,
+            fan_alerts: None,
+            recording: None,
+            history: None,
+            profile: None,
+            gpu_fan_curve: None,
+            gpu_power_limit: None,
+            hardware_changes: None,
+            calibration_status: None,
+            detection: None,
+            auto_tune: None,
+            binding_conflicts: None,
+            active_overrides: None,
+            competing_software: None,
+            dry_run: None,
+            safe_mode: None,
+            control_status: None,
+            logs: None,
+            pwm_groups: None,
+            events: None,
+            capabilities: None,
         }
     }
 }
@@ -317,8 +859,29 @@ impl ResponseData {
     pub fn chips(c: Vec<EcChipInfo>) -> Self { Self { ec_chips: Some(c), ..Self::default() } }
     pub fn register(r: EcRegisterValue) -> Self { Self { ec_register: Some(r), ..Self::default() } }
     pub fn registers(r: Vec<EcRegisterValue>) -> Self { Self { ec_registers: Some(r), ..Self::default() } }
+    pub fn ec_changes(c: Vec<EcRegisterChange>) -> Self { Self { ec_changes: Some(c), ..Self::default() } }
     pub fn mode(m: GlobalMode) -> Self { Self { global_mode: Some(m), ..Self::default() } }
-    pub fn rate_limit(r: u32) -> Self { Self { rate_limit: Some(r), ..Self::default() } }
+    pub fn rate_limit(c: RateLimitConfig) -> Self { Self { rate_limit: Some(c.limit), rate_limit_config: Some(c), ..Self::default() } }
+    pub fn alerts(a: Vec<FanAlert>) -> Self { Self { fan_alerts: Some(a), ..Self::default() } }
+    pub fn recording(r: RecordingInfo) -> Self { Self { recording: Some(r), ..Self::default() } }
+    pub fn history(h: HistoryData) -> Self { Self { history: Some(h), ..Self::default() } }
+    pub fn profile(p: ProfileInfo) -> Self { Self { profile: Some(p), ..Self::default() } }
+    pub fn fan_curve(c: GpuFanCurveInfo) -> Self { Self { gpu_fan_curve: Some(c), ..Self::default() } }
+    pub fn power_limit(l: GpuPowerLimitInfo) -> Self { Self { gpu_power_limit: Some(l), ..Self::default() } }
+    pub fn hw_changes(c: Vec<HardwareChange>) -> Self { Self { hardware_changes: Some(c), ..Self::default() } }
+    pub fn calibration(s: CalibrationStatus) -> Self { Self { calibration_status: Some(s), ..Self::default() } }
+    pub fn detection(p: DetectionProgress) -> Self { Self { detection: Some(p), ..Self::default() } }
+    pub fn auto_tune(p: AutoTuneProgress) -> Self { Self { auto_tune: Some(p), ..Self::default() } }
+    pub fn binding_conflicts(c: Vec<BindingConflict>) -> Self { Self { binding_conflicts: Some(c), ..Self::default() } }
+    pub fn active_overrides(o: Vec<ActiveOverride>) -> Self { Self { active_overrides: Some(o), ..Self::default() } }
+    pub fn competing_software(r: CompetingSoftwareReport) -> Self { Self { competing_software: Some(r), ..Self::default() } }
+    pub fn dry_run(enabled: bool) -> Self { Self { dry_run: Some(enabled), ..Self::default() } }
+    pub fn safe_mode(enabled: bool) -> Self { Self { safe_mode: Some(enabled), ..Self::default() } }
+    pub fn control_status(status: ControlStatus) -> Self { Self { control_status: Some(status), ..Self::default() } }
+    pub fn logs(l: Vec<LogEntry>) -> Self { Self { logs: Some(l), ..Self::default() } }
+    pub fn pwm_groups(g: Vec<PwmGroup>) -> Self { Self { pwm_groups: Some(g), ..Self::default() } }
+    pub fn events(e: Vec<EventEnvelope>) -> Self { Self { events: Some(e), ..Self::default() } }
+    pub fn version(v: String, c: Capabilities) -> Self { Self { value: Some(v), capabilities: Some(c), ..Self::default() } }
 }
 
 /// Batched hardware data (hwmon + GPUs) for efficient polling
@@ -340,6 +903,70 @@ pub struct HwmonChip {
     pub temperatures: Vec<TempSensor>,
     pub fans: Vec<FanSensor>,
     pub pwms: Vec<PwmControl>,
+    /// Voltage, current, power and humidity channels
+    #[serde(default)]
+    pub sensors: Vec<HwSensor>,
+    /// Fan, temperature and chassis intrusion alarm flags
+    #[serde(default)]
+    pub alarms: Vec<HwAlarm>,
+}
+
+/// Kind of a non-temperature, non-fan hwmon channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SensorKind {
+    Voltage,
+    Current,
+    Power,
+    Humidity,
+}
+
+impl SensorKind {
+    /// Display unit for values of this kind
+    pub fn unit(&self) -> &'static str {
+        match self {
+            SensorKind::Voltage => "V",
+            SensorKind::Current => "A",
+            SensorKind::Power => "W",
+            SensorKind::Humidity => "%RH",
+        }
+    }
+}
+
+/// What an hwmon alarm flag watches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlarmKind {
+    /// `fanN_alarm`: fan below its minimum speed or failed
+    Fan,
+    /// `tempN_alarm`: temperature beyond a driver limit
+    Temp,
+    /// `intrusionN_alarm`: chassis opened (latched until cleared)
+    Intrusion,
+}
+
+/// Alarm flag of an hwmon channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HwAlarm {
+    /// Channel the alarm belongs to ("fan2", "temp1", "intrusion0")
+    pub name: String,
+    pub kind: AlarmKind,
+    /// The `<name>_alarm` file
+    pub path: String,
+    pub active: bool,
+    /// Chip beeps on this alarm (`<name>_beep`); None without a beep file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beep: Option<bool>,
+}
+
+/// Voltage/current/power/humidity reading, already scaled to V, A, W or %RH
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HwSensor {
+    pub name: String,
+    pub label: Option<String>,
+    pub path: String,
+    pub kind: SensorKind,
+    pub value: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -348,11 +975,20 @@ pub struct TempSensor {
     pub label: Option<String>,
     pub path: String,
     pub value: f32,
+    /// Driver-reported high threshold (°C)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f32>,
+    /// Driver-reported critical threshold (°C)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crit: Option<f32>,
+    /// The hardware has latched its critical alarm
+    #[serde(default)]
+    pub crit_alarm: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FanSensor {
-    /// Unique identifier for this fan sensor (stable across reboots)
+    /// Unique identifier for this fan sensor (stable across reboots, `hf_core::sensor_uuid`)
     pub uuid: String,
     pub name: String,
     pub label: Option<String>,
@@ -362,12 +998,19 @@ pub struct FanSensor {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PwmControl {
-    /// Unique identifier for this PWM control (stable across reboots)
+    /// Unique identifier for this PWM control (stable across reboots, `hf_core::sensor_uuid`)
     pub uuid: String,
     pub name: String,
     pub path: String,
     pub value: u8,
     pub enabled: bool,
+    /// Highest state when `path` is a thermal cooling device's `cur_state`
+    /// (devicetree fans); `value` is still scaled to 0-255
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_state: Option<u32>,
+    /// Drives its fan by voltage (`pwmN_mode` = 0), as 3-pin fans need
+    #[serde(default)]
+    pub dc_mode: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -378,6 +1021,29 @@ pub struct GpuInfo {
     pub temp: Option<f32>,
     pub fan_percent: Option<u32>,
     pub fan_rpm: Option<u32>,
+    /// Detected without a vendor driver/tool: monitoring only, no fan control
+    #[serde(default)]
+    pub read_only: bool,
+    /// Every temperature sensor (edge, junction, memory, ...); `temp` is the first
+    #[serde(default)]
+    pub temperatures: Vec<GpuTempInfo>,
+    /// Current board power draw in watts
+    #[serde(default)]
+    pub power_watts: Option<f32>,
+    /// Board power limit in watts
+    #[serde(default)]
+    pub power_limit_watts: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuTempInfo {
+    /// Sensor name (e.g. "GPU Edge", "GPU Junction", "GPU Memory")
+    pub name: String,
+    pub value: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub critical: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -391,6 +1057,102 @@ pub struct FanMapping {
     pub confidence: f32,
 }
 
+/// PWM outputs driven together by one curve
+///
+/// The curve is evaluated once per control tick from `temp_source_path`;
+/// each member then gets that speed plus its own offset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PwmGroup {
+    pub id: String,
+    pub name: String,
+    pub curve_id: String,
+    pub temp_source_path: String,
+    pub members: Vec<PwmGroupMember>,
+    pub active: bool,
+}
+
+/// Fan control program running alongside the daemon (GetCompetingSoftware)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompetingSoftware {
+    pub name: String,
+    /// Service unit that starts it
+    pub unit: String,
+    pub pids: Vec<i32>,
+    /// Command that stops and disables it on this init system
+    pub disable_hint: String,
+}
+
+/// Controlled output whose `pwmN_enable` mode changed since the daemon set it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PwmEnableFlip {
+    pub pwm_path: String,
+    /// Mode it was found in (e.g. 2 = automatic)
+    pub mode: u8,
+    /// Times it was found changed since the daemon started
+    pub count: u32,
+    pub last_seen_secs_ago: u64,
+}
+
+/// Signs of another program controlling the fans (GetCompetingSoftware)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompetingSoftwareReport {
+    pub software: Vec<CompetingSoftware>,
+    /// Recent flips only
+    pub enable_flips: Vec<PwmEnableFlip>,
+}
+
+impl CompetingSoftwareReport {
+    pub fn is_empty(&self) -> bool {
+        self.software.is_empty() && self.enable_flips.is_empty()
+    }
+}
+
+/// State of the daemon's control loop (GetControlStatus)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ControlStatus {
+    /// PWM outputs driven by a curve (pairs and group members)
+    pub controlled_outputs: u32,
+    pub dry_run: bool,
+    pub safe_mode: bool,
+    /// Sources with readings rejected before reaching curves or the failsafe
+    #[serde(default)]
+    pub implausible_readings: Vec<ImplausibleReadings>,
+}
+
+/// Readings of one sensor the control loop rejected as physically implausible
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImplausibleReadings {
+    pub path: String,
+    /// Negative or non-finite temperatures, fan speeds above 20000 RPM
+    pub out_of_range: u64,
+    /// Temperatures more than 125°C away from the last accepted one
+    pub jumps: u64,
+    /// Most recent rejected value (°C or RPM)
+    pub last_rejected: f32,
+}
+
+/// A PWM override in effect (ListActiveOverrides)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveOverride {
+    pub pwm_path: String,
+    pub value: u8,
+    /// Time left before the curve takes over again
+    pub remaining_ms: u64,
+    /// Client that set it; none for the daemon's own (calibration, auto-tune)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PwmGroupMember {
+    pub pwm_path: String,
+    /// Added to the group's curve output, in percent (e.g. -10 for a louder fan)
+    #[serde(default)]
+    pub offset_percent: f32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ManualPwmFanPairing {
     /// UUID of the PWM control (primary key for matching)
@@ -401,6 +1163,222 @@ pub struct ManualPwmFanPairing {
     pub fan_uuid: Option<String>,
     pub fan_path: Option<String>,
     pub fan_name: Option<String>,
+    /// Response tuning (None = the curve's own parameters)
+    #[serde(default)]
+    pub tuning: Option<CurveTuning>,
+    #[serde(default)]
+    pub control_mode: PairingControlMode,
+    /// Curve may stop the fan below its stall duty
+    #[serde(default)]
+    pub zero_rpm: bool,
+    /// Response to another program writing the PWM
+    #[serde(default)]
+    pub enforcement: PwmEnforcement,
+}
+
+/// Measured PWM-to-RPM response of a fan (CalibrateFan)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FanCalibration {
+    pub pwm_path: String,
+    pub fan_path: String,
+    /// (PWM, RPM) readings, ascending PWM
+    pub points: Vec<(u8, u32)>,
+    /// RPM at full duty
+    pub max_rpm: u32,
+    /// Lowest PWM that keeps a spinning fan turning (0 if it never stops)
+    pub min_pwm: u8,
+    /// Lowest PWM that starts a stopped fan (None if it never stops)
+    pub start_pwm: Option<u8>,
+    /// PWM ranges where the RPM does not change (inclusive)
+    pub dead_zones: Vec<(u8, u8)>,
+    /// Unix time in seconds
+    pub calibrated_at: u64,
+}
+
+/// Calibration state of one PWM output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationStatus {
+    /// A sweep is in progress
+    pub running: bool,
+    /// Last stored calibration
+    pub calibration: Option<FanCalibration>,
+    /// Why the last sweep failed
+    pub error: Option<String>,
+}
+
+/// How a detection job pairs PWM outputs with fans
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionStrategy {
+    /// Drop each output to 0% in turn and watch which fan slows down
+    #[default]
+    Active,
+    /// Correlate the PWM changes curves or firmware make with fan RPM over a
+    /// longer window; never writes an output, so fans keep running
+    Passive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionJobState {
+    Running,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+/// Progress of a background detection job (StartDetection)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionProgress {
+    pub job_id: u64,
+    #[serde(default)]
+    pub strategy: DetectionStrategy,
+    pub state: DetectionJobState,
+    /// PWM output being tested, counting from 1 (0 while fans spin up);
+    /// samples taken so far for passive detection
+    pub step: u32,
+    pub total_steps: u32,
+    /// Name ("chip/pwmN") of the PWM output being tested (none when passive)
+    pub current_pwm: Option<String>,
+    /// Detected mappings, set once the job has completed
+    #[serde(default)]
+    pub mappings: Vec<FanMapping>,
+    /// Why the job failed
+    pub error: Option<String>,
+}
+
+/// Steady-state temperature measured at one duty during auto-tuning
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AutoTuneStep {
+    pub duty_percent: f32,
+    pub temp: f32,
+    /// The temperature stopped changing (false: step timed out or went past the target)
+    pub settled: bool,
+}
+
+/// Progress of a background auto-tune job (StartAutoTune)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoTuneProgress {
+    pub job_id: u64,
+    pub curve_id: String,
+    pub target_temp: f32,
+    /// Auto-tune jobs share the detection job states
+    pub state: DetectionJobState,
+    /// Duty step being measured, counting from 1
+    pub step: u32,
+    pub total_steps: u32,
+    /// Duty the outputs are held at
+    pub current_duty: Option<f32>,
+    /// Latest reading of the curve's temperature source
+    pub current_temp: Option<f32>,
+    /// Steps measured so far, highest duty first
+    #[serde(default)]
+    pub steps: Vec<AutoTuneStep>,
+    /// Suggested curve, set once the job has completed; not saved by the daemon
+    #[serde(default)]
+    pub points: Vec<(f32, f32)>,
+    /// Why the job failed
+    pub error: Option<String>,
+}
+
+/// How badly a stored binding fails validation (GetBindingConflicts)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingConflictState {
+    /// Partial match; control continues but the user should confirm
+    Degraded,
+    /// Critical anchors changed; the output falls back until rebound
+    NeedsRebind,
+    /// Not found; the output falls back until rebound
+    Unsafe,
+}
+
+/// Fingerprinted binding that no longer matches the hardware at its old location
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindingConflict {
+    /// Binding ID (fingerprint ID of the PWM channel)
+    pub pwm_id: String,
+    /// Driver of the chip the binding was made on
+    pub chip_name: String,
+    /// Channel label when the binding was made, if the chip exposes one
+    pub label: Option<String>,
+    /// Paths when the binding was made
+    pub previous_pwm_path: String,
+    pub previous_fan_path: Option<String>,
+    pub state: BindingConflictState,
+    /// Confidence at the old location (0.0 - 1.0)
+    pub confidence: f32,
+    /// Why validation failed
+    #[serde(default)]
+    pub reasons: Vec<String>,
+    /// Current channels that could be the same fan, most likely first
+    #[serde(default)]
+    pub candidates: Vec<BindingCandidate>,
+}
+
+/// Current PWM channel (and fan input) that may be a conflicting binding's hardware
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindingCandidate {
+    pub pwm_path: String,
+    pub fan_path: Option<String>,
+    /// Driver of the chip the candidate is on
+    pub chip_name: String,
+    /// How closely the candidate matches the stored fingerprints (0.0 - 1.0)
+    pub confidence: f32,
+    #[serde(default)]
+    pub reasons: Vec<String>,
+}
+
+/// What the curve output of a pairing drives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum PairingControlMode {
+    /// Curve percent is written as duty cycle
+    #[default]
+    Duty,
+    /// Curve percent is a fraction of `max_rpm`, tracked against the paired
+    /// tachometer; `None` uses the maximum RPM learned during detection
+    Rpm {
+        #[serde(default)]
+        max_rpm: Option<u32>,
+    },
+}
+
+/// What the control loop does when another program writes a pairing's PWM
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PwmEnforcement {
+    /// Write the curve value back on the next iteration
+    #[default]
+    Reassert,
+    /// Leave the foreign value until the other program has been quiet for a while
+    Yield,
+    /// Leave the foreign value until the curve output moves
+    AlertOnly,
+}
+
+/// How fan speed follows a curve between two points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CurveInterpolation {
+    /// Straight lines between points
+    #[default]
+    Linear,
+    /// Monotone cubic (PCHIP): smooth, but never overshoots the points
+    Monotone,
+}
+
+/// Per-PWM response tuning, overriding the curve's own parameters
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CurveTuning {
+    /// Temperature change in °C required before the fan reacts
+    pub hysteresis_c: f32,
+    /// Maximum speed increase in percent per second (0 = instant)
+    pub ramp_up_pct_per_s: f32,
+    /// Maximum speed decrease in percent per second (0 = instant)
+    pub ramp_down_pct_per_s: f32,
+    /// Number of control iterations averaged (1 = no smoothing)
+    pub smoothing_window: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -411,6 +1389,46 @@ pub struct EcChipInfo {
     pub chip_class: String,
     pub register_count: Option<u16>,
     pub supports_direct_access: bool,
+    /// Register layout of the laptop model, matched by DMI (None if unknown)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<EcFanProfile>,
+}
+
+/// Known EC fan register layout of a laptop model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcFanProfile {
+    /// Profile name (e.g. "MSI G-series (Cooler Boost EC)")
+    pub name: String,
+    pub fans: Vec<EcFanMap>,
+    /// Other registers that are safe to read (temperatures), with labels
+    pub read_registers: Vec<EcRegisterLabel>,
+}
+
+/// Registers controlling one EC fan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcFanMap {
+    pub label: String,
+    /// Register holding the fan duty
+    pub duty_register: u8,
+    /// Consecutive registers from `duty_register` written with the same duty
+    /// (models that store a fan curve get every point set)
+    pub duty_register_count: u8,
+    /// Raw duty values at 0% and 100%
+    pub duty_min: u8,
+    pub duty_max: u8,
+    /// Register holding the fan's current speed reading, if exposed
+    pub rpm_register: Option<u8>,
+    /// Register written to switch between EC and manual control
+    /// None if the model only accepts duty through an EC command (read-only layout)
+    pub mode_register: Option<u8>,
+    pub manual_value: u8,
+    pub auto_value: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcRegisterLabel {
+    pub register: u8,
+    pub label: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -421,6 +1439,295 @@ pub struct EcRegisterValue {
     pub writable: bool,
 }
 
+/// EC register whose value changed while watched (WatchEcRegisters)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcRegisterChange {
+    pub register: u8,
+    pub label: Option<String>,
+    /// Value at each sample, oldest first
+    pub values: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FanAlertKind {
+    /// Fan reports 0 RPM while commanded above its minimum PWM
+    Stalled,
+    /// Previously stalled fan is spinning again
+    Recovered,
+    /// PWM writes to the output do not read back as written
+    WriteFailed,
+    /// Writes to a previously failing output take effect again
+    WriteRecovered,
+}
+
+/// Fan failure alert raised by the daemon's stall monitor or failed PWM writes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanAlert {
+    /// Monotonic alert ID (clients pass the last seen ID as `since_id`)
+    pub id: u64,
+    pub kind: FanAlertKind,
+    pub name: String,
+    pub pwm_path: String,
+    /// Tachometer of the output (empty for write alerts on outputs without one)
+    pub fan_path: String,
+    /// PWM value commanded when the alert was raised
+    pub pwm: u8,
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: u64,
+}
+
+/// Severity of a daemon log entry, most severe first
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// Log event retained in the daemon's in-memory ring buffer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Monotonic entry ID (clients pass the last seen ID as `since_id`)
+    pub id: u64,
+    pub level: LogLevel,
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: u64,
+    /// Message prefix such as "AUDIT" or "DETECTION", else the emitting module
+    pub subsystem: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HardwareChangeKind {
+    Added,
+    Removed,
+}
+
+/// hwmon chip or DRM card that appeared or disappeared while the daemon ran
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HardwareChange {
+    /// Monotonic event ID (clients pass the last seen ID as `since_id`)
+    pub id: u64,
+    pub kind: HardwareChangeKind,
+    /// Kernel subsystem ("hwmon" or "drm")
+    pub subsystem: String,
+    /// Device name, e.g. "hwmon4" or "card1"
+    pub name: String,
+    /// Kernel device path (relative to /sys)
+    pub devpath: String,
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: u64,
+}
+
+/// Part of the configuration a change touched
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSection {
+    /// Settings (controls, groups, profiles, filters)
+    Settings,
+    /// Fan curves
+    Curves,
+    /// PWM-to-fan pairings and hardware bindings
+    Pairings,
+    /// Anything; clients reload everything (ReloadConfig after an edit on disk)
+    All,
+}
+
+/// Event pushed to subscribed connections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", content = "data")]
+pub enum Event {
+    /// A control's temperature source crossed its failsafe threshold
+    /// (`active`: fans forced to full speed) or dropped back below it
+    ThermalAlert {
+        name: String,
+        temp_source_path: String,
+        celsius: f32,
+        limit_celsius: f32,
+        active: bool,
+    },
+    /// A fan stalled or recovered
+    FanFailure(FanAlert),
+    /// An hwmon chip or DRM card appeared or disappeared
+    HardwareChanged(HardwareChange),
+    /// The active curve profile changed (`scheduled`: by the profile schedule)
+    ProfileSwitched { previous: String, name: String, scheduled: bool },
+    /// Fingerprinted hardware bindings no longer matched their recorded paths
+    DriftDetected {
+        /// Bindings moved to their new paths
+        corrected: u32,
+        /// Bindings whose hardware could only be matched partially
+        degraded: u32,
+        /// Bindings whose hardware is gone (control disabled)
+        unsafe_bindings: u32,
+    },
+    /// A controlled PWM output holds a value the daemon didn't write
+    ForeignPwmWrite {
+        name: String,
+        pwm_path: String,
+        /// Value the daemon last wrote
+        expected: u8,
+        /// Value found in the output
+        found: u8,
+        policy: PwmEnforcement,
+    },
+    /// A control's temperature source failed and the next one in its curve's
+    /// fallback chain took over, or a preferred source recovered
+    TemperatureSourceSwitched {
+        name: String,
+        from: String,
+        to: String,
+        /// Why `from` was given up ("recovered" when switching back)
+        reason: String,
+    },
+    /// Configuration was changed through the daemon, or reloaded after a
+    /// client edited the files; other clients should re-read `section`
+    ConfigChanged {
+        section: ConfigSection,
+        /// Daemon-wide count of config changes, starting at 1 when the daemon starts;
+        /// a client that loaded its config at this revision or later can skip the reload
+        revision: u64,
+    },
+}
+
+/// Event with its daemon-wide sequence number
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    /// Monotonic sequence number (clients resubscribe with the last seen one as `since_seq`)
+    pub seq: u64,
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: u64,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+/// File format of a daemon recording
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingFormat {
+    /// JSON lines, loadable for replay
+    #[default]
+    Jsonl,
+    /// One row per sample, one column per sensor
+    Csv,
+}
+
+impl RecordingFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// State of a daemon recording session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingInfo {
+    /// Recording file (readable by clients)
+    pub path: String,
+    /// Samples written so far
+    pub samples: u64,
+    /// Time since the recording started
+    pub duration_ms: u64,
+    /// Bytes written so far
+    #[serde(default)]
+    pub bytes: u64,
+    /// Still sampling (false once stopped or a limit was reached)
+    #[serde(default)]
+    pub active: bool,
+}
+
+/// What a history series measures
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistorySeriesKind {
+    /// Temperature in Celsius
+    Temperature,
+    /// Fan speed in RPM
+    FanRpm,
+    /// PWM output duty in percent
+    PwmDuty,
+}
+
+/// Downsampled value of a series over one history bucket
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HistoryPoint {
+    /// Bucket start (Unix ms)
+    pub timestamp_ms: u64,
+    pub min: f32,
+    pub max: f32,
+    pub avg: f32,
+}
+
+/// Stored history of one sensor or output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistorySeries {
+    /// sysfs path of the sensor or PWM output
+    pub path: String,
+    pub kind: HistorySeriesKind,
+    /// Points in time order; buckets without samples are omitted
+    pub points: Vec<HistoryPoint>,
+}
+
+/// Result of `QueryHistory`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryData {
+    /// Bucket width actually used
+    pub resolution_secs: u32,
+    pub series: Vec<HistorySeries>,
+}
+
+/// Active curve profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileInfo {
+    /// Name of the active profile
+    pub active: String,
+    /// Built-in profiles plus any profile referenced by a pair
+    pub available: Vec<String>,
+}
+
+/// Point on a GPU firmware fan curve
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GpuFanCurvePoint {
+    /// Temperature in Celsius (hotspot on AMD)
+    pub temp_c: u32,
+    /// Fan speed percentage (0-100)
+    pub speed_percent: u32,
+}
+
+/// Firmware fan curve stored on a GPU
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuFanCurveInfo {
+    /// DRM card number
+    pub index: u32,
+    pub points: Vec<GpuFanCurvePoint>,
+    /// Allowed temperature range in Celsius
+    pub temp_min: Option<u32>,
+    pub temp_max: Option<u32>,
+    /// Allowed fan speed range in percent
+    pub speed_min: Option<u32>,
+    pub speed_max: Option<u32>,
+}
+
+/// Board power limit of a GPU
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuPowerLimitInfo {
+    /// NVIDIA index or AMD DRM card number (as in `GpuInfo::index`)
+    pub index: u32,
+    /// Limit currently enforced, in watts
+    pub watts: f32,
+    pub default_watts: Option<f32>,
+    /// Range the driver accepts, in watts
+    pub min_watts: Option<f32>,
+    pub max_watts: Option<f32>,
+}
+
 impl Response {
     pub fn ok() -> Self {
         Response::Ok(ResponseData::none())
@@ -448,6 +1755,18 @@ impl Response {
 }
 
 pub fn validate_hwmon_path(path: &str) -> Result<(), String> {
+    if let Some(oid) = path.strip_prefix(SYSCTL_PATH_PREFIX) {
+        return validate_sysctl_oid(oid);
+    }
+
+    if let Some(device) = path.strip_prefix(SMART_PATH_PREFIX) {
+        return validate_smart_device(device);
+    }
+
+    if let Some(rest) = path.strip_prefix(IPMI_PATH_PREFIX) {
+        return validate_ipmi_path(rest);
+    }
+
     if path.len() > MAX_PATH_LENGTH {
         return Err(format!(
             "Path too long: {} > {} chars",
@@ -500,7 +1819,108 @@ pub fn validate_hwmon_path(path: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Validate a hardware temperature path, a virtual sensor path, or a provider sensor path
+pub fn validate_temp_source_path(path: &str) -> Result<(), String> {
+    if let Some(id) = path.strip_prefix(VIRTUAL_PATH_PREFIX) {
+        return validate_virtual_sensor_id(id);
+    }
+    if let Some(rest) = path.strip_prefix(PROVIDER_PATH_PREFIX) {
+        let (provider, sensor) = rest
+            .split_once('/')
+            .ok_or("Provider sensor path must be provider:<provider>/<sensor>")?;
+        validate_provider_name(provider)?;
+        return validate_provider_name(sensor);
+    }
+    validate_hwmon_path(path)
+}
+
+pub fn validate_virtual_sensor_id(id: &str) -> Result<(), String> {
+    validate_source_id("Virtual sensor ID", id)
+}
+
+/// Validate a sensor provider name or the name of one of its sensors
+pub fn validate_provider_name(name: &str) -> Result<(), String> {
+    validate_source_id("Sensor provider name", name)
+}
+
+fn validate_source_id(what: &str, id: &str) -> Result<(), String> {
+    if id.is_empty() || id.len() > MAX_SOURCE_ID_LENGTH {
+        return Err(format!("{} must be 1-{} characters", what, MAX_SOURCE_ID_LENGTH));
+    }
+    if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("{} may only contain letters, digits, '-' and '_'", what));
+    }
+    Ok(())
+}
+
+fn validate_smart_device(device: &str) -> Result<(), String> {
+    if device.is_empty() || device.len() > MAX_SMART_DEVICE_LENGTH {
+        return Err("Invalid drive name length".into());
+    }
+
+    if !device.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()) {
+        return Err("Drive name must be a block device name (e.g. sda)".into());
+    }
+
+    Ok(())
+}
+
+/// `temp:<sensor>`, `fan:<sensor>`, `zone:<n>` or `zone:<n>:enable`
+fn validate_ipmi_path(rest: &str) -> Result<(), String> {
+    let parts: Vec<&str> = rest.split(':').collect();
+    match parts.as_slice() {
+        ["temp" | "fan", sensor] => {
+            if sensor.is_empty() || sensor.len() > MAX_SOURCE_ID_LENGTH {
+                return Err("Invalid IPMI sensor name length".into());
+            }
+            if !sensor.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+                return Err("IPMI sensor name contains invalid characters".into());
+            }
+            Ok(())
+        }
+        ["zone", zone] | ["zone", zone, "enable"] => {
+            if zone.len() != 1 || !zone.chars().all(|c| c.is_ascii_digit()) {
+                return Err("Invalid IPMI fan zone".into());
+            }
+            Ok(())
+        }
+        _ => Err("IPMI path must be temp:<sensor>, fan:<sensor> or zone:<n>".into()),
+    }
+}
+
+fn validate_sysctl_oid(oid: &str) -> Result<(), String> {
+    if oid.len() > MAX_PATH_LENGTH {
+        return Err(format!("sysctl name too long: {} > {} chars", oid.len(), MAX_PATH_LENGTH));
+    }
+
+    if !ALLOWED_SYSCTL_PREFIXES.iter().any(|prefix| oid.starts_with(prefix)) {
+        return Err(format!("sysctl name must be under one of: {:?}", ALLOWED_SYSCTL_PREFIXES));
+    }
+
+    if oid.contains("..") || oid.ends_with('.') {
+        return Err("sysctl name contains an empty component".into());
+    }
+
+    for c in oid.chars() {
+        if !c.is_ascii_alphanumeric() && c != '_' && c != '.' {
+            return Err(format!("sysctl name contains invalid character: {:?}", c));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn validate_pwm_target_path(path: &str) -> Result<(), String> {
+    if path.starts_with(SMART_PATH_PREFIX) {
+        return Err("Drive temperatures cannot be used as PWM targets".into());
+    }
+
+    if let Some(rest) = path.strip_prefix(IPMI_PATH_PREFIX) {
+        if !rest.starts_with("zone:") {
+            return Err("Only IPMI fan zones can be used as PWM targets".into());
+        }
+    }
+
     if ALLOWED_VIRTUAL_PWM_PREFIXES.iter().any(|p| path.starts_with(p)) {
         return validate_virtual_pwm_path(path);
     }
@@ -580,12 +2000,15 @@ pub fn validate_pwm_value(_value: u8) -> Result<(), &'static str> {
     Ok(())
 }
 
+/// Longest PWM override (timed manual speed from the dashboard)
+pub const MAX_PWM_OVERRIDE_TTL_MS: u32 = 60 * 60 * 1000;
+
 pub fn validate_ttl_ms(ttl_ms: u32) -> Result<(), &'static str> {
     if ttl_ms < 50 {
         return Err("TTL too short (minimum 50ms)");
     }
-    if ttl_ms > 30_000 {
-        return Err("TTL too long (maximum 30000ms)");
+    if ttl_ms > MAX_PWM_OVERRIDE_TTL_MS {
+        return Err("TTL too long (maximum 1 hour)");
     }
     Ok(())
 }
@@ -600,12 +2023,322 @@ pub fn validate_ec_register_count(count: u8) -> Result<(), &'static str> {
     Ok(())
 }
 
+/// Minimum EC watch sample interval
+pub const MIN_EC_WATCH_INTERVAL_MS: u32 = 50;
+
+/// Longest EC watch, first to last sample (stays well inside client timeouts)
+pub const MAX_EC_WATCH_DURATION_MS: u32 = 3000;
+
+pub fn validate_ec_watch(interval_ms: u32, samples: u32) -> Result<(), String> {
+    if interval_ms < MIN_EC_WATCH_INTERVAL_MS {
+        return Err(format!("Watch interval must be at least {} ms", MIN_EC_WATCH_INTERVAL_MS));
+    }
+    if samples < 2 {
+        return Err("Watch needs at least 2 samples".into());
+    }
+    if interval_ms.saturating_mul(samples - 1) > MAX_EC_WATCH_DURATION_MS {
+        return Err(format!("Watch must finish within {} ms", MAX_EC_WATCH_DURATION_MS));
+    }
+    Ok(())
+}
+
 /// Minimum rate limit (requests per 10s window)
 pub const MIN_RATE_LIMIT: u32 = 1500;
 
 /// Maximum rate limit (requests per 10s window)
 pub const MAX_RATE_LIMIT: u32 = 9999;
 
+/// Maximum curve tuning hysteresis (°C)
+pub const MAX_TUNING_HYSTERESIS_C: f32 = 10.0;
+
+/// Maximum curve tuning ramp rate (percent per second)
+pub const MAX_TUNING_RAMP_PCT_PER_S: f32 = 200.0;
+
+/// Maximum curve tuning smoothing window (control iterations)
+pub const MAX_TUNING_SMOOTHING_WINDOW: u32 = 50;
+
+pub fn validate_curve_tuning(tuning: &CurveTuning) -> Result<(), String> {
+    if !(0.0..=MAX_TUNING_HYSTERESIS_C).contains(&tuning.hysteresis_c) {
+        return Err(format!("Hysteresis must be 0-{} °C", MAX_TUNING_HYSTERESIS_C));
+    }
+    for rate in [tuning.ramp_up_pct_per_s, tuning.ramp_down_pct_per_s] {
+        if !(0.0..=MAX_TUNING_RAMP_PCT_PER_S).contains(&rate) {
+            return Err(format!("Ramp rate must be 0-{} %/s", MAX_TUNING_RAMP_PCT_PER_S));
+        }
+    }
+    if !(1..=MAX_TUNING_SMOOTHING_WINDOW).contains(&tuning.smoothing_window) {
+        return Err(format!("Smoothing window must be 1-{}", MAX_TUNING_SMOOTHING_WINDOW));
+    }
+    Ok(())
+}
+
+/// Shortest curve preview
+pub const MIN_CURVE_PREVIEW_MS: u32 = 1000;
+
+/// Longest curve preview before the saved curve is restored
+pub const MAX_CURVE_PREVIEW_MS: u32 = 10 * 60 * 1000;
+
+/// Maximum number of points in a previewed curve
+pub const MAX_PREVIEW_CURVE_POINTS: usize = 16;
+
+/// Maximum curve ID length
+const MAX_CURVE_ID_LENGTH: usize = 128;
+
+pub fn validate_curve_id(curve_id: &str) -> Result<(), String> {
+    if curve_id.is_empty() {
+        return Err("Curve ID cannot be empty".into());
+    }
+    if curve_id.len() > MAX_CURVE_ID_LENGTH {
+        return Err(format!("Curve ID too long (maximum {} chars)", MAX_CURVE_ID_LENGTH));
+    }
+    Ok(())
+}
+
+pub fn validate_curve_preview(curve_id: &str, points: &[(f32, f32)], duration_ms: u32) -> Result<(), String> {
+    validate_curve_id(curve_id)?;
+    if points.is_empty() {
+        return Err("Curve needs at least one point".into());
+    }
+    if points.len() > MAX_PREVIEW_CURVE_POINTS {
+        return Err(format!("Too many curve points (maximum {})", MAX_PREVIEW_CURVE_POINTS));
+    }
+    for (temp, percent) in points {
+        if !temp.is_finite() || !(0.0..=150.0).contains(temp) {
+            return Err("Curve temperature out of range (0-150°C)".into());
+        }
+        if !percent.is_finite() || !(0.0..=100.0).contains(percent) {
+            return Err("Curve fan speed out of range (0-100%)".into());
+        }
+    }
+    if !(MIN_CURVE_PREVIEW_MS..=MAX_CURVE_PREVIEW_MS).contains(&duration_ms) {
+        return Err(format!(
+            "Preview duration must be {}-{} seconds",
+            MIN_CURVE_PREVIEW_MS / 1000,
+            MAX_CURVE_PREVIEW_MS / 1000
+        ));
+    }
+    Ok(())
+}
+
+/// Auto-tune temperature targets accepted (°C)
+pub const MIN_AUTO_TUNE_TARGET: f32 = 30.0;
+pub const MAX_AUTO_TUNE_TARGET: f32 = 110.0;
+
+pub fn validate_auto_tune(curve_id: &str, target_temp: f32) -> Result<(), String> {
+    validate_curve_id(curve_id)?;
+    if !target_temp.is_finite() || !(MIN_AUTO_TUNE_TARGET..=MAX_AUTO_TUNE_TARGET).contains(&target_temp) {
+        return Err(format!(
+            "Target temperature must be {}-{}°C",
+            MIN_AUTO_TUNE_TARGET, MAX_AUTO_TUNE_TARGET
+        ));
+    }
+    Ok(())
+}
+
+/// Maximum binding ID length (IDs are 16 hex digits today)
+const MAX_BINDING_ID_LENGTH: usize = 64;
+
+pub fn validate_binding_id(id: &str) -> Result<(), String> {
+    if id.is_empty() || id.len() > MAX_BINDING_ID_LENGTH {
+        return Err(format!("Binding ID must be 1-{} chars", MAX_BINDING_ID_LENGTH));
+    }
+    if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err("Binding ID contains invalid characters".into());
+    }
+    Ok(())
+}
+
+/// Highest RPM accepted as an RPM-mode target
+pub const MAX_TARGET_RPM: u32 = 30_000;
+
+pub fn validate_pairing_control_mode(mode: &PairingControlMode) -> Result<(), String> {
+    match mode {
+        PairingControlMode::Rpm { max_rpm: Some(rpm) } if *rpm == 0 || *rpm > MAX_TARGET_RPM => {
+            Err(format!("Maximum RPM must be 1-{}", MAX_TARGET_RPM))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Minimum recording sample interval
+pub const MIN_RECORDING_INTERVAL_MS: u32 = 100;
+
+/// Maximum recording sample interval
+pub const MAX_RECORDING_INTERVAL_MS: u32 = 60_000;
+
+pub fn validate_recording_interval(interval_ms: u32) -> Result<(), String> {
+    if !(MIN_RECORDING_INTERVAL_MS..=MAX_RECORDING_INTERVAL_MS).contains(&interval_ms) {
+        return Err(format!(
+            "Recording interval must be {}-{} ms",
+            MIN_RECORDING_INTERVAL_MS, MAX_RECORDING_INTERVAL_MS
+        ));
+    }
+    Ok(())
+}
+
+/// Longest time range one QueryHistory may cover (the daemon keeps 30 days)
+pub const MAX_HISTORY_RANGE_MS: u64 = 31 * 24 * 3600 * 1000;
+
+/// Coarsest resolution a client may ask for
+pub const MAX_HISTORY_RESOLUTION_SECS: u32 = 24 * 3600;
+
+/// Most series one QueryHistory may name
+pub const MAX_HISTORY_PATHS: usize = 64;
+
+/// Points returned by one QueryHistory across all series (keeps it under MAX_RESPONSE_SIZE)
+pub const MAX_HISTORY_POINTS: usize = 10_000;
+
+pub fn validate_history_query(start_ms: u64, end_ms: u64, resolution_secs: u32, paths: &[String]) -> Result<(), String> {
+    if start_ms >= end_ms {
+        return Err("History range must start before it ends".into());
+    }
+    if end_ms - start_ms > MAX_HISTORY_RANGE_MS {
+        return Err(format!("History range must be at most {} days", MAX_HISTORY_RANGE_MS / (24 * 3600 * 1000)));
+    }
+    if resolution_secs > MAX_HISTORY_RESOLUTION_SECS {
+        return Err(format!("History resolution must be at most {} seconds", MAX_HISTORY_RESOLUTION_SECS));
+    }
+    if paths.len() > MAX_HISTORY_PATHS {
+        return Err(format!("At most {} history series can be queried at once", MAX_HISTORY_PATHS));
+    }
+    paths.iter().try_for_each(|path| validate_hwmon_path(path))
+}
+
+/// Longest recording a client may ask for
+pub const MAX_RECORDING_DURATION_MS: u64 = 7 * 24 * 3600 * 1000;
+
+/// Largest recording file a client may ask for
+pub const MAX_RECORDING_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+pub fn validate_recording_limits(max_duration_ms: Option<u64>, max_bytes: Option<u64>) -> Result<(), String> {
+    if max_duration_ms.is_some_and(|ms| ms == 0 || ms > MAX_RECORDING_DURATION_MS) {
+        return Err(format!("Recording duration must be 1-{} ms", MAX_RECORDING_DURATION_MS));
+    }
+    if max_bytes.is_some_and(|bytes| bytes == 0 || bytes > MAX_RECORDING_BYTES) {
+        return Err(format!("Recording size limit must be 1-{} bytes", MAX_RECORDING_BYTES));
+    }
+    Ok(())
+}
+
+/// Maximum profile name length
+pub const MAX_PROFILE_NAME_LENGTH: usize = 32;
+
+pub fn validate_profile_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".into());
+    }
+    if name.len() > MAX_PROFILE_NAME_LENGTH {
+        return Err(format!("Profile name too long (maximum {} chars)", MAX_PROFILE_NAME_LENGTH));
+    }
+    if let Some(c) = name.chars().find(|c| !c.is_ascii_lowercase() && !c.is_ascii_digit() && *c != '-' && *c != '_') {
+        return Err(format!("Profile name contains invalid character: {:?}", c));
+    }
+    Ok(())
+}
+
+/// Maximum PWM outputs in one group
+pub const MAX_PWM_GROUP_MEMBERS: usize = 32;
+
+/// Largest per-member offset from the group's curve output
+pub const MAX_PWM_GROUP_OFFSET_PERCENT: f32 = 50.0;
+
+/// Maximum group ID and name length
+const MAX_GROUP_FIELD_LENGTH: usize = 128;
+
+pub fn validate_group_id(id: &str) -> Result<(), String> {
+    if id.is_empty() {
+        return Err("Group ID cannot be empty".into());
+    }
+    if id.len() > MAX_GROUP_FIELD_LENGTH {
+        return Err(format!("Group ID too long (maximum {} chars)", MAX_GROUP_FIELD_LENGTH));
+    }
+    Ok(())
+}
+
+pub fn validate_pwm_group(group: &PwmGroup) -> Result<(), String> {
+    validate_group_id(&group.id)?;
+    if group.curve_id.is_empty() || group.curve_id.len() > MAX_GROUP_FIELD_LENGTH {
+        return Err("Group needs a curve".into());
+    }
+    if group.name.len() > MAX_GROUP_FIELD_LENGTH {
+        return Err(format!("Group name too long (maximum {} chars)", MAX_GROUP_FIELD_LENGTH));
+    }
+    // GPU sensors are addressed as gpu:<index>:<name>
+    if group.temp_source_path.starts_with("gpu:") {
+        if group.temp_source_path.len() > MAX_PATH_LENGTH {
+            return Err("Temperature source path too long".into());
+        }
+    } else {
+        validate_temp_source_path(&group.temp_source_path)?;
+    }
+    if group.members.is_empty() {
+        return Err("Group needs at least one PWM output".into());
+    }
+    if group.members.len() > MAX_PWM_GROUP_MEMBERS {
+        return Err(format!("Too many group members (maximum {})", MAX_PWM_GROUP_MEMBERS));
+    }
+    for (i, member) in group.members.iter().enumerate() {
+        validate_pwm_target_path(&member.pwm_path)?;
+        if group.members[..i].iter().any(|m| m.pwm_path == member.pwm_path) {
+            return Err(format!("{} is listed twice", member.pwm_path));
+        }
+        if !member.offset_percent.is_finite() || member.offset_percent.abs() > MAX_PWM_GROUP_OFFSET_PERCENT {
+            return Err(format!("Member offset must be within ±{}%", MAX_PWM_GROUP_OFFSET_PERCENT));
+        }
+    }
+    Ok(())
+}
+
+/// Maximum points in a GPU firmware fan curve (AMD PMFW has 5 slots)
+pub const MAX_GPU_FAN_CURVE_POINTS: usize = 16;
+
+/// Highest temperature accepted for a firmware fan curve point
+const MAX_GPU_FAN_CURVE_TEMP_C: u32 = 150;
+
+pub fn validate_gpu_fan_curve(points: &[GpuFanCurvePoint]) -> Result<(), String> {
+    if points.is_empty() {
+        return Err("Fan curve needs at least one point".into());
+    }
+    if points.len() > MAX_GPU_FAN_CURVE_POINTS {
+        return Err(format!("Too many fan curve points (maximum {})", MAX_GPU_FAN_CURVE_POINTS));
+    }
+    for point in points {
+        if point.temp_c > MAX_GPU_FAN_CURVE_TEMP_C {
+            return Err(format!("Fan curve temperature out of range (0-{}°C)", MAX_GPU_FAN_CURVE_TEMP_C));
+        }
+        validate_percent(point.speed_percent)?;
+    }
+    Ok(())
+}
+
+/// Highest GPU power limit accepted before the driver's own range check
+pub const MAX_GPU_POWER_LIMIT_WATTS: u32 = 1500;
+
+pub fn validate_gpu_power_limit(watts: u32) -> Result<(), String> {
+    if !(1..=MAX_GPU_POWER_LIMIT_WATTS).contains(&watts) {
+        return Err(format!("GPU power limit out of range (1-{}W)", MAX_GPU_POWER_LIMIT_WATTS));
+    }
+    Ok(())
+}
+
+/// Most tokens a single request class can cost (below MIN_RATE_LIMIT so any request fits a bucket)
+pub const MAX_REQUEST_COST: u32 = 1000;
+
+pub fn validate_rate_limit_burst(burst: u32) -> Result<(), String> {
+    if !(MIN_RATE_LIMIT..=MAX_RATE_LIMIT).contains(&burst) {
+        return Err(format!("Rate limit burst must be {}-{}", MIN_RATE_LIMIT, MAX_RATE_LIMIT));
+    }
+    Ok(())
+}
+
+pub fn validate_request_costs(costs: &RequestCosts) -> Result<(), String> {
+    let all = [costs.read, costs.scan, costs.write, costs.heavy];
+    if all.iter().any(|&cost| cost == 0 || cost > MAX_REQUEST_COST) {
+        return Err(format!("Request costs must be 1-{}", MAX_REQUEST_COST));
+    }
+    Ok(())
+}
+
 pub fn validate_rate_limit(limit: u32) -> Result<(), String> {
     if limit < MIN_RATE_LIMIT {
         return Err(format!("Rate limit too low (minimum {})", MIN_RATE_LIMIT));
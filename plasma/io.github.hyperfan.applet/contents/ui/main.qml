@@ -0,0 +1,196 @@
+/*
+ * Hyperfan Plasma applet
+ *
+ * Reference client of the daemon's D-Bus interface (io.github.hyperfan1):
+ * polls GetStatus through busctl and switches profiles with SetActiveProfile.
+ * The panel shows the hottest sensor; the popup lists every temperature and
+ * fan and the active profile.
+ */
+
+import QtQuick
+import QtQuick.Layouts
+import org.kde.plasma.plasmoid
+import org.kde.plasma.components as PlasmaComponents
+import org.kde.plasma.extras as PlasmaExtras
+import org.kde.plasma.plasma5support as Plasma5Support
+import org.kde.kirigami as Kirigami
+
+PlasmoidItem {
+    id: root
+
+    readonly property string daemonCall: "busctl --system --json=short call io.github.hyperfan1 "
+        + "/io/github/hyperfan1 io.github.hyperfan1.Daemon "
+    readonly property int pollInterval: 3000
+
+    // Last GetStatus document, null until the daemon answered
+    property var status: null
+    property string errorText: ""
+
+    readonly property var hottest: {
+        if (!status || status.temperatures.length === 0) {
+            return null
+        }
+        return status.temperatures.reduce((a, b) => b.celsius > a.celsius ? b : a)
+    }
+
+    function refresh() {
+        executable.connectSource(daemonCall + "GetStatus")
+    }
+
+    function setProfile(name) {
+        // Profile names are [a-z0-9_-] (validated by the daemon), safe to pass unquoted
+        executable.connectSource(daemonCall + "SetActiveProfile s " + name)
+    }
+
+    function formatTemperature(celsius) {
+        return Math.round(celsius) + " °C"
+    }
+
+    Plasma5Support.DataSource {
+        id: executable
+        engine: "executable"
+        connectedSources: []
+
+        onNewData: (source, data) => {
+            disconnectSource(source)
+            if (!source.endsWith("GetStatus")) {
+                // A profile switch: show its result right away
+                if (data["exit code"] !== 0) {
+                    root.errorText = data.stderr.trim()
+                }
+                root.refresh()
+                return
+            }
+            if (data["exit code"] !== 0) {
+                root.status = null
+                root.errorText = data.stderr.trim() || i18n("The Hyperfan daemon is not available")
+                return
+            }
+            try {
+                root.status = JSON.parse(JSON.parse(data.stdout).data[0])
+                root.errorText = ""
+            } catch (e) {
+                root.status = null
+                root.errorText = i18n("Unexpected reply from the Hyperfan daemon")
+            }
+        }
+    }
+
+    Timer {
+        interval: root.pollInterval
+        running: true
+        repeat: true
+        triggeredOnStart: true
+        onTriggered: root.refresh()
+    }
+
+    Plasmoid.icon: "io.github.hyperfan"
+    toolTipMainText: i18n("Hyperfan")
+    toolTipSubText: {
+        if (!status) {
+            return errorText
+        }
+        const profile = i18n("Profile: %1", status.profile)
+        return hottest ? profile + "\n" + hottest.label + ": " + formatTemperature(hottest.celsius) : profile
+    }
+
+    compactRepresentation: MouseArea {
+        Layout.minimumWidth: compactRow.implicitWidth
+        onClicked: root.expanded = !root.expanded
+
+        RowLayout {
+            id: compactRow
+            anchors.fill: parent
+
+            Kirigami.Icon {
+                Layout.fillHeight: true
+                Layout.preferredWidth: height
+                source: "io.github.hyperfan"
+            }
+
+            PlasmaComponents.Label {
+                text: root.hottest ? root.formatTemperature(root.hottest.celsius) : "–"
+            }
+        }
+    }
+
+    fullRepresentation: ColumnLayout {
+        Layout.minimumWidth: Kirigami.Units.gridUnit * 16
+        Layout.minimumHeight: Kirigami.Units.gridUnit * 12
+
+        PlasmaExtras.PlaceholderMessage {
+            Layout.fillWidth: true
+            visible: root.status === null
+            iconName: "io.github.hyperfan"
+            text: root.errorText || i18n("Connecting to the Hyperfan daemon…")
+        }
+
+        RowLayout {
+            visible: root.status !== null
+            Layout.fillWidth: true
+
+            PlasmaComponents.Label {
+                text: i18n("Profile")
+                Layout.fillWidth: true
+            }
+
+            PlasmaComponents.ComboBox {
+                model: root.status ? root.status.profiles : []
+                currentIndex: root.status ? root.status.profiles.indexOf(root.status.profile) : -1
+                onActivated: index => root.setProfile(model[index])
+            }
+        }
+
+        PlasmaExtras.Heading {
+            visible: root.status !== null
+            level: 4
+            text: i18n("Temperatures")
+        }
+
+        Repeater {
+            model: root.status ? root.status.temperatures : []
+
+            RowLayout {
+                Layout.fillWidth: true
+
+                PlasmaComponents.Label {
+                    text: modelData.label
+                    elide: Text.ElideRight
+                    Layout.fillWidth: true
+                }
+
+                PlasmaComponents.Label {
+                    text: root.formatTemperature(modelData.celsius)
+                }
+            }
+        }
+
+        PlasmaExtras.Heading {
+            visible: root.status !== null && root.status.fans.length > 0
+            level: 4
+            text: i18n("Fans")
+        }
+
+        Repeater {
+            model: root.status ? root.status.fans : []
+
+            RowLayout {
+                Layout.fillWidth: true
+
+                PlasmaComponents.Label {
+                    text: modelData.label
+                    elide: Text.ElideRight
+                    Layout.fillWidth: true
+                }
+
+                PlasmaComponents.Label {
+                    text: modelData.rpm === null ? "–" : i18n("%1 RPM", modelData.rpm)
+                }
+            }
+        }
+
+        Item {
+            Layout.fillHeight: true
+        }
+    }
+}